@@ -0,0 +1,367 @@
+package app
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"text/tabwriter"
+	"time"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/compose"
+	"github.com/stacklok/toolhive/pkg/container"
+	"github.com/stacklok/toolhive/pkg/container/images"
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/permissions"
+	"github.com/stacklok/toolhive/pkg/runner"
+	"github.com/stacklok/toolhive/pkg/runner/retriever"
+	"github.com/stacklok/toolhive/pkg/transport"
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+var applyCmd = &cobra.Command{
+	Use:   "apply",
+	Short: "Apply a declarative configuration of MCP servers",
+	Long: `Apply a declarative configuration of MCP servers from a compose file.
+
+The desired servers are compared with the servers managed by ToolHive, and the
+servers that are missing, have changed, or are not running are (re)created.
+Servers are started in dependency order, waiting for the dependencies declared
+with depends_on to be ready. Applying the same file twice makes no changes.
+
+Dependencies must be MCP servers declared in the file. With service_started, a
+server waits until its dependency is running, and with service_healthy until
+the proxy of its dependency reports it healthy. Containers which are not MCP
+servers managed by ToolHive, such as a database, cannot be waited for.
+
+Example compose file:
+
+  servers:
+    db:
+      image: ghcr.io/example/postgres-mcp:latest
+      transport: sse
+    app:
+      image: fetch
+      env:
+        LOG_LEVEL: debug
+        API_TOKEN: ${secret:api-token}
+      depends_on:
+        db:
+          condition: service_healthy
+
+${VAR} references in the image, volumes and permission profile of a server are
+resolved from the environment when the file is applied. References in env values
+and args, including ${secret:name}, are resolved each time the server starts.
+
+The servers are labeled with the name of the project of the file, set with the
+top-level name field and defaulting to the name of the directory of the file.
+With --prune, the servers of the project which are no longer declared are
+removed, while servers started by hand or from other projects are kept.
+
+With --locked, the servers run the images pinned in toolhive.lock (see thv lock),
+and the command fails if the compose file no longer matches the lock file.`,
+	Args: cobra.NoArgs,
+	RunE: applyCmdFunc,
+}
+
+var (
+	applyFile    string
+	applyPrune   bool
+	applyDryRun  bool
+	applyTimeout time.Duration
+	applyLocked  bool
+)
+
+func init() {
+	applyCmd.Flags().StringVarP(&applyFile, "file", "f", "toolhive.yaml", "Path to the compose file")
+	applyCmd.Flags().BoolVar(&applyPrune, "prune", false,
+		"Remove the servers of the compose project that are no longer declared")
+	applyCmd.Flags().BoolVar(&applyDryRun, "dry-run", false, "Print the planned changes without applying them")
+	applyCmd.Flags().DurationVar(&applyTimeout, "timeout", 2*time.Minute,
+		"Maximum time to wait for the dependencies of a server to be ready")
+	applyCmd.Flags().BoolVar(&applyLocked, "locked", false,
+		"Run the images pinned in toolhive.lock and fail if the compose file does not match it")
+}
+
+func applyCmdFunc(cmd *cobra.Command, _ []string) error {
+	ctx := cmd.Context()
+
+	file, err := compose.Load(applyFile)
+	if err != nil {
+		return err
+	}
+	project, err := compose.ProjectName(applyFile, file)
+	if err != nil {
+		return err
+	}
+
+	var lock *compose.LockFile
+	if applyLocked {
+		lock, err = loadVerifiedLock(cmd, applyFile, file)
+		if err != nil {
+			return err
+		}
+	}
+
+	runtime, err := container.NewFactory().Create(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create container runtime: %v", err)
+	}
+	manager := workloads.NewManagerFromRuntime(runtime)
+
+	actual, err := listActualServers(ctx, runtime)
+	if err != nil {
+		return err
+	}
+
+	plan, err := compose.ComputePlan(file, actual, project, applyPrune)
+	if err != nil {
+		return err
+	}
+	if lock != nil {
+		pinnedImages, err := lockedImages(lock)
+		if err != nil {
+			return err
+		}
+		plan.EnforceImages(actual, pinnedImages)
+	}
+	printPlan(plan)
+
+	if applyDryRun || !plan.HasChanges() {
+		return nil
+	}
+
+	if err := executePlan(ctx, runtime, manager, file, project, lock, plan); err != nil {
+		return err
+	}
+
+	printInfo("Applied %s", applyFile)
+	return nil
+}
+
+// executePlan performs the actions of a plan in order
+func executePlan(
+	ctx context.Context,
+	runtime rt.Runtime,
+	manager workloads.Manager,
+	file *compose.File,
+	project string,
+	lock *compose.LockFile,
+	plan *compose.Plan,
+) error {
+	checker := compose.NewWorkloadReadinessChecker(manager)
+	for _, action := range plan.Actions {
+		switch action.Type {
+		case compose.ActionDelete:
+			if err := deleteWorkload(ctx, manager, action.Name); err != nil {
+				return err
+			}
+		case compose.ActionUpdate:
+			if err := deleteWorkload(ctx, manager, action.Name); err != nil {
+				return err
+			}
+			server := file.Servers[action.Name]
+			if err := createComposeServer(ctx, runtime, manager, checker, project, action.Name, server, lock); err != nil {
+				return err
+			}
+		case compose.ActionCreate:
+			server := file.Servers[action.Name]
+			if err := createComposeServer(ctx, runtime, manager, checker, project, action.Name, server, lock); err != nil {
+				return err
+			}
+		case compose.ActionNone:
+			// Nothing to do
+		}
+	}
+	return nil
+}
+
+// listActualServers lists the MCP servers managed by ToolHive along with the spec they were created from
+func listActualServers(ctx context.Context, runtime rt.Runtime) ([]compose.ActualServer, error) {
+	containers, err := runtime.ListWorkloads(ctx)
+	if err != nil {
+		return nil, fmt.Errorf("failed to list containers: %v", err)
+	}
+
+	var servers []compose.ActualServer
+	for _, c := range containers {
+		if !labels.IsToolHiveContainer(c.Labels) {
+			continue
+		}
+		servers = append(servers, compose.ActualServer{
+			Name:     labels.GetContainerName(c.Labels),
+			SpecHash: labels.GetSpecHash(c.Labels),
+			Project:  labels.GetComposeProject(c.Labels),
+			Running:  c.State == "running",
+			Image:    c.Image,
+		})
+	}
+	return servers, nil
+}
+
+func printPlan(plan *compose.Plan) {
+	if IsQuiet() {
+		for _, action := range plan.Actions {
+			if action.Type != compose.ActionNone {
+				fmt.Println(action.Name)
+			}
+		}
+		return
+	}
+
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 3, ' ', 0)
+	fmt.Fprintln(w, "NAME\tACTION\tREASON")
+	for _, action := range plan.Actions {
+		fmt.Fprintf(w, "%s\t%s\t%s\n", action.Name, action.Type, action.Reason)
+	}
+	_ = w.Flush()
+}
+
+func deleteWorkload(ctx context.Context, manager workloads.Manager, name string) error {
+	printInfo("Removing %s...", name)
+	group, err := manager.DeleteWorkloads(ctx, []string{name})
+	if err != nil {
+		return fmt.Errorf("failed to delete %s: %v", name, err)
+	}
+	if err := group.Wait(); err != nil {
+		return fmt.Errorf("failed to delete %s: %v", name, err)
+	}
+	return nil
+}
+
+func createComposeServer(
+	ctx context.Context,
+	runtime rt.Runtime,
+	manager workloads.Manager,
+	checker compose.ReadinessChecker,
+	project string,
+	name string,
+	server *compose.Server,
+	lock *compose.LockFile,
+) error {
+	if err := compose.WaitForDependencies(ctx, checker, server, applyTimeout); err != nil {
+		return fmt.Errorf("failed to start %s: %v", name, err)
+	}
+
+	printInfo("Starting %s...", name)
+	var locked *compose.LockedServer
+	if lock != nil {
+		locked = lock.Servers[name]
+	}
+	runConfig, err := composeServerRunConfig(ctx, runtime, project, name, server, locked)
+	if err != nil {
+		return fmt.Errorf("failed to create run config for %s: %v", name, err)
+	}
+	if err := manager.RunWorkloadDetached(runConfig); err != nil {
+		return fmt.Errorf("failed to start %s: %v", name, err)
+	}
+	return nil
+}
+
+// composeServerRunConfig builds a RunConfig for a server declared in a compose file
+func composeServerRunConfig(
+	ctx context.Context,
+	runtime rt.Runtime,
+	project string,
+	name string,
+	server *compose.Server,
+	locked *compose.LockedServer,
+) (*runner.RunConfig, error) {
+	hash, err := server.Hash()
+	if err != nil {
+		return nil, err
+	}
+
+	imageURL, imageMetadata, err := retriever.GetMCPServer(ctx, server.Image, "", retriever.VerifyImageWarn, retriever.PullDefault)
+	if err != nil {
+		return nil, fmt.Errorf("failed to find or create the MCP server %s: %v", server.Image, err)
+	}
+	if locked != nil && locked.Digest != "" {
+		imageURL, err = pullLockedImage(ctx, locked)
+		if err != nil {
+			return nil, err
+		}
+	}
+
+	permissionProfile := server.PermissionProfile
+	if permissionProfile == "" {
+		permissionProfile = permissions.ProfileNetwork
+	}
+
+	envVars := make([]string, 0, len(server.Env))
+	for key, value := range server.Env {
+		envVars = append(envVars, fmt.Sprintf("%s=%s", key, value))
+	}
+
+	runConfig, err := runner.NewRunConfigFromFlags(
+		ctx,
+		runtime,
+		server.Args,
+		name,
+		imageURL,
+		imageMetadata,
+		transport.LocalhostIPv4,
+		false, // debug
+		server.Volumes,
+		server.Secrets,
+		"",    // authzConfigPath
+		"",    // auditConfigPath
+		false, // enableAudit
+		permissionProfile,
+		transport.LocalhostIPv4,
+		server.Transport,
+		0, // Let the manager figure out which port to use.
+		server.TargetPort,
+		envVars,
+		"", "", "", "", false, // OIDC is not supported in compose files yet
+		"", "", 0.0, nil, false, false, nil, // Telemetry is not supported in compose files yet
+		false, // isolateNetwork
+		"",    // k8s patch - not relevant here.
+		&runner.DetachedEnvVarValidator{},
+	)
+	if err != nil {
+		return nil, err
+	}
+
+	runConfig.ContainerLabels[labels.LabelSpecHash] = hash
+	runConfig.ContainerLabels[labels.LabelComposeProject] = project
+	return runConfig, nil
+}
+
+// lockedImages returns the pinned image references of the servers in a lock file which are locked by digest
+func lockedImages(lock *compose.LockFile) (map[string]string, error) {
+	pinnedImages := make(map[string]string, len(lock.Servers))
+	for name, locked := range lock.Servers {
+		if locked.Digest == "" {
+			continue
+		}
+		pinned, err := locked.PinnedImage()
+		if err != nil {
+			return nil, err
+		}
+		pinnedImages[name] = pinned
+	}
+	return pinnedImages, nil
+}
+
+// pullLockedImage makes sure the image pinned in the lock file is available locally and returns its reference
+func pullLockedImage(ctx context.Context, locked *compose.LockedServer) (string, error) {
+	pinned, err := locked.PinnedImage()
+	if err != nil {
+		return "", err
+	}
+
+	imageManager := images.NewImageManager(ctx)
+	exists, err := imageManager.ImageExists(ctx, pinned)
+	if err != nil {
+		return "", err
+	}
+	if !exists {
+		if err := imageManager.PullImage(ctx, pinned); err != nil {
+			return "", fmt.Errorf("failed to pull locked image %s: %v", pinned, err)
+		}
+	}
+	return pinned, nil
+}
@@ -0,0 +1,70 @@
+package app
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/container/images"
+	"github.com/stacklok/toolhive/pkg/runner"
+)
+
+var buildCmd = &cobra.Command{
+	Use:   "build [path]",
+	Short: "Build an MCP server image from a local Dockerfile",
+	Long: `Build an MCP server image from a directory with a Dockerfile, using the
+container runtime. The directory is the build context, and the Dockerfile in it
+is used unless another one is selected with --file.
+
+The image is tagged as toolhivelocal/NAME:latest, after the directory, unless a
+tag is given with --tag, and can be run with thv run. To build an image and run
+it in one step, use thv run --build.
+
+Examples:
+  thv build ./my-server
+  thv build . --file deploy/Dockerfile.mcp --tag example/my-server:dev`,
+	Args: cobra.MaximumNArgs(1),
+	RunE: buildCmdFunc,
+}
+
+var (
+	buildFile string
+	buildTag  string
+)
+
+func init() {
+	buildCmd.Flags().StringVarP(&buildFile, "file", "f", "",
+		"Path of the Dockerfile, inside the build context (default PATH/Dockerfile)")
+	buildCmd.Flags().StringVarP(&buildTag, "tag", "t", "", "Name of the built image (default toolhivelocal/NAME:latest)")
+}
+
+func buildCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+
+	contextDir := "."
+	if len(args) > 0 {
+		contextDir = args[0]
+	}
+	localBuild, err := runner.NewLocalBuild(contextDir, buildFile, buildTag)
+	if err != nil {
+		return err
+	}
+
+	image, err := localBuild.Build(ctx, images.NewImageManager(ctx))
+	if err != nil {
+		return err
+	}
+
+	printResult(image, "Built image %s, run it with: thv run %s", image, image)
+	return nil
+}
+
+// buildServer builds the image of an MCP server from a local build context for thv run --build
+func buildServer(ctx context.Context, contextDir string) (string, error) {
+	localBuild, err := runner.NewLocalBuild(contextDir, "", "")
+	if err != nil {
+		return "", fmt.Errorf("failed to build the MCP server %s: %w", contextDir, err)
+	}
+	return localBuild.Build(ctx, images.NewImageManager(ctx))
+}
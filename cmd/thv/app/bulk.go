@@ -0,0 +1,106 @@
+package app
+
+import (
+	"bufio"
+	"context"
+	"fmt"
+	"os"
+	"strings"
+	"sync"
+
+	"golang.org/x/term"
+
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+// selectWorkloadNames returns the names of the workloads matching the filters, for commands operating
+// on several servers at once. Only containers managed by ToolHive are listed, so other containers
+// are never selected. Stopped workloads are only included if includeStopped is set.
+func selectWorkloadNames(
+	ctx context.Context,
+	manager workloads.Manager,
+	filters []string,
+	includeStopped bool,
+) ([]string, error) {
+	filter, err := workloads.ParseFilters(filters)
+	if err != nil {
+		return nil, err
+	}
+
+	workloadList, err := manager.ListWorkloads(ctx, includeStopped)
+	if err != nil {
+		return nil, fmt.Errorf("failed to list workloads: %v", err)
+	}
+
+	var names []string
+	for _, workload := range filter.Apply(workloadList) {
+		names = append(names, workload.Name)
+	}
+	return names, nil
+}
+
+// confirmBulkOperation asks the user to confirm an operation on several servers.
+// Confirmation is only asked in interactive sessions, and is skipped when force is set.
+func confirmBulkOperation(action string, names []string, force bool) (bool, error) {
+	if force || !term.IsTerminal(int(os.Stdin.Fd())) {
+		return true, nil
+	}
+
+	fmt.Fprintf(os.Stderr, "The following MCP servers will be %s:\n", action)
+	for _, name := range names {
+		fmt.Fprintf(os.Stderr, "  %s\n", name)
+	}
+	fmt.Fprint(os.Stderr, "Continue? [y/N] ")
+
+	answer, err := bufio.NewReader(os.Stdin).ReadString('\n')
+	if err != nil {
+		return false, fmt.Errorf("failed to read confirmation: %v", err)
+	}
+	answer = strings.ToLower(strings.TrimSpace(answer))
+	return answer == "y" || answer == "yes", nil
+}
+
+// bulkResult is the outcome of an operation on one of several servers
+type bulkResult struct {
+	name string
+	err  error
+}
+
+// runBulkOperation runs the operation on each of the servers concurrently,
+// and returns the outcome for each server in the order they were given
+func runBulkOperation(names []string, operation func(name string) error) []bulkResult {
+	results := make([]bulkResult, len(names))
+	var wg sync.WaitGroup
+	for i, name := range names {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			results[i] = bulkResult{name: name, err: operation(name)}
+		}()
+	}
+	wg.Wait()
+	return results
+}
+
+// printBulkSummary prints the outcome of an operation on several servers and returns an error if
+// it failed for any of them. In quiet mode only the names of the servers it succeeded for are printed.
+func printBulkSummary(action string, results []bulkResult) error {
+	var failed int
+	for _, result := range results {
+		if result.err != nil {
+			failed++
+			fmt.Fprintf(os.Stderr, "Failed: %s: %v\n", result.name, result.err)
+		} else if IsQuiet() {
+			fmt.Println(result.name)
+		} else {
+			fmt.Printf("%s: %s\n", strings.ToUpper(action[:1])+action[1:], result.name)
+		}
+	}
+
+	succeeded := len(results) - failed
+	printInfo("%d of %d containers %s successfully, %d failed", succeeded, len(results), action, failed)
+	if failed > 0 {
+		return fmt.Errorf("%d of %d containers could not be %s", failed, len(results), action)
+	}
+	return nil
+}
@@ -0,0 +1,64 @@
+package app
+
+import (
+	"fmt"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+var (
+	clonePort      int
+	cloneOverrides []string
+)
+
+var cloneCmd = &cobra.Command{
+	Use:   "clone [flags] SERVER_NAME NEW_NAME",
+	Short: "Run a copy of an MCP server under a new name",
+	Long: `Run a copy of an MCP server in the background under a new name, e.g. to run
+staging and production variants of the same server.
+
+The copy starts from the saved configuration of the server, including its image,
+arguments, environment variables, secrets, volumes and permission profile. Use
+--set to override parts of the configuration:
+
+  env.<NAME>=<value>   set an environment variable
+  label.<KEY>=<value>  set a label
+  image=<image>        run a different image
+  host=<host>          bind the proxy to a different host
+
+The copy listens on --port, or on an available port if --port is not set.
+
+  $ thv clone fetch fetch-staging --set env.API_URL=https://staging.example.com --port 9001`,
+	Args: cobra.ExactArgs(2),
+	RunE: cloneCmdFunc,
+}
+
+func init() {
+	cloneCmd.Flags().IntVar(&clonePort, "port", 0, "Port for the HTTP proxy of the copy to listen on (host port)")
+	cloneCmd.Flags().StringArrayVar(&cloneOverrides, "set", nil,
+		"Override a part of the configuration (format: KEY=VALUE, can be specified multiple times)")
+}
+
+func cloneCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+	name, newName := args[0], args[1]
+
+	manager, err := workloads.NewManager(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create container manager: %v", err)
+	}
+
+	runConfig, err := manager.CloneWorkload(ctx, name, newName, clonePort, cloneOverrides)
+	if err != nil {
+		return fmt.Errorf("failed to clone %s: %v", name, err)
+	}
+
+	if IsQuiet() {
+		printServerURL(runConfig)
+		return nil
+	}
+	fmt.Printf("MCP server %s is running as a copy of %s on port %d\n", newName, name, runConfig.Port)
+	return nil
+}
@@ -2,9 +2,15 @@
 package app
 
 import (
+	"fmt"
+	"os"
+
 	"github.com/spf13/cobra"
 	"github.com/spf13/viper"
 
+	"github.com/stacklok/toolhive/pkg/container"
+	"github.com/stacklok/toolhive/pkg/container/docker/sdk"
+	"github.com/stacklok/toolhive/pkg/container/runtime"
 	"github.com/stacklok/toolhive/pkg/logger"
 	"github.com/stacklok/toolhive/pkg/updates"
 )
@@ -27,9 +33,41 @@ container-based isolation for running MCP servers.`,
 	},
 	PersistentPreRun: func(_ *cobra.Command, _ []string) {
 		logger.Initialize()
+		// The runtime is passed on in the environment, so detached processes use the same runtime
+		if runtimeName != "" {
+			if err := os.Setenv(container.RuntimeEnv, runtimeName); err != nil {
+				logger.Errorf("Error setting the container runtime: %v", err)
+			}
+		}
+		if containerHost != "" {
+			if err := setContainerHostEnv(containerHost); err != nil {
+				logger.Errorf("Error setting the container host: %v", err)
+			}
+		}
 	},
 }
 
+var (
+	runtimeName   string
+	containerHost string
+)
+
+// setContainerHostEnv sets the host of the container runtime in the environment, like the runtime, so
+// detached processes connect to the same host. CONTAINER_HOST is set for Podman and DOCKER_HOST otherwise,
+// and the sockets set for toolhive are unset, since they take precedence over the host.
+func setContainerHostEnv(host string) error {
+	hostEnv := sdk.DockerHostEnv
+	if runtime.Selected() == runtime.TypePodman {
+		hostEnv = sdk.ContainerHostEnv
+	}
+	for _, socketEnv := range []string{sdk.DockerSocketEnv, sdk.PodmanSocketEnv} {
+		if err := os.Unsetenv(socketEnv); err != nil {
+			return err
+		}
+	}
+	return os.Setenv(hostEnv, host)
+}
+
 // NewRootCmd creates a new root command for the ToolHive CLI.
 func NewRootCmd(enableUpdates bool) *cobra.Command {
 	// Add persistent flags
@@ -38,20 +76,55 @@ func NewRootCmd(enableUpdates bool) *cobra.Command {
 	if err != nil {
 		logger.Errorf("Error binding debug flag: %v", err)
 	}
+	rootCmd.PersistentFlags().BoolP("quiet", "q", false,
+		"Only print essential identifiers (such as server names or URLs) to stdout")
+	err = viper.BindPFlag("quiet", rootCmd.PersistentFlags().Lookup("quiet"))
+	if err != nil {
+		logger.Errorf("Error binding quiet flag: %v", err)
+	}
+
+	rootCmd.PersistentFlags().StringVar(&runtimeName, "runtime", "",
+		fmt.Sprintf("Container runtime to use (auto, %s, %s, %s, %s, %s, %s), instead of detecting it; "+
+			"can also be set with %s",
+			runtime.TypeDocker, runtime.TypePodman, runtime.TypeApple, runtime.TypeFinch, runtime.TypeContainerd,
+			runtime.TypeKubernetes, container.RuntimeEnv))
+	rootCmd.PersistentFlags().StringVar(&containerHost, "container-host", "",
+		"Host of the container runtime, e.g. ssh://user@server to manage MCP servers on a remote machine over SSH "+
+			"(stdio transport only) or tcp://server:2376; can also be set with DOCKER_HOST or CONTAINER_HOST")
 
 	// Add subcommands
 	rootCmd.AddCommand(runCmd)
+	rootCmd.AddCommand(buildCmd)
+	rootCmd.AddCommand(pullCmd)
 	rootCmd.AddCommand(listCmd)
+	rootCmd.AddCommand(portCmd)
+	rootCmd.AddCommand(inspectCmd)
+	rootCmd.AddCommand(startCmd)
 	rootCmd.AddCommand(stopCmd)
+	rootCmd.AddCommand(pauseCmd)
+	rootCmd.AddCommand(resumeCmd)
 	rootCmd.AddCommand(rmCmd)
+	rootCmd.AddCommand(renameCmd)
+	rootCmd.AddCommand(cloneCmd)
 	rootCmd.AddCommand(proxyCmd)
+	rootCmd.AddCommand(gatewayCmd)
 	rootCmd.AddCommand(restartCmd)
 	rootCmd.AddCommand(serveCmd)
 	rootCmd.AddCommand(newVersionCmd())
 	rootCmd.AddCommand(logsCommand())
+	rootCmd.AddCommand(eventsCmd)
+	rootCmd.AddCommand(execCmd)
 	rootCmd.AddCommand(newSecretCommand())
+	rootCmd.AddCommand(newPermissionCommand())
 	rootCmd.AddCommand(inspectorCommand())
 	rootCmd.AddCommand(newMCPCommand())
+	rootCmd.AddCommand(applyCmd)
+	rootCmd.AddCommand(diffCmd)
+	rootCmd.AddCommand(lockCmd)
+	rootCmd.AddCommand(outdatedCmd)
+	rootCmd.AddCommand(upgradeCmd)
+	rootCmd.AddCommand(rollbackCmd)
+	rootCmd.AddCommand(newLaunchdCommand())
 
 	// Silence printing the usage on error
 	rootCmd.SilenceUsage = true
@@ -3,8 +3,10 @@ package app
 import (
 	"context"
 	"fmt"
+	"os"
 
 	"github.com/spf13/cobra"
+	"github.com/spf13/viper"
 
 	"github.com/stacklok/toolhive/pkg/config"
 	"github.com/stacklok/toolhive/pkg/secrets"
@@ -38,6 +40,31 @@ func IsOIDCEnabled(cmd *cobra.Command) bool {
 	return jwksURL != "" || issuer != ""
 }
 
+// IsQuiet returns true if the user asked for quiet output with the --quiet flag.
+func IsQuiet() bool {
+	return viper.GetBool("quiet")
+}
+
+// printResult prints the outcome of a command. In quiet mode only the given
+// identifier is printed, so the output can be consumed by other commands.
+func printResult(identifier string, format string, args ...any) {
+	if IsQuiet() {
+		fmt.Println(identifier)
+		return
+	}
+	fmt.Printf(format+"\n", args...)
+}
+
+// printInfo prints informational messages meant for humans.
+// In quiet mode these are written to stderr to keep stdout clean.
+func printInfo(format string, args ...any) {
+	w := os.Stdout
+	if IsQuiet() {
+		w = os.Stderr
+	}
+	fmt.Fprintf(w, format+"\n", args...)
+}
+
 // SetSecretsProvider sets the secrets provider type in the configuration.
 // It validates the input, tests the provider functionality, and updates the configuration.
 // Choices are `encrypted`, `1password`, and `none`.
@@ -12,6 +12,7 @@ import (
 	"github.com/stacklok/toolhive/pkg/client"
 	"github.com/stacklok/toolhive/pkg/config"
 	"github.com/stacklok/toolhive/pkg/networking"
+	"github.com/stacklok/toolhive/pkg/registry"
 )
 
 var configCmd = &cobra.Command{
@@ -89,8 +90,20 @@ var setRegistryURLCmd = &cobra.Command{
 	Long: `Set the URL for the remote MCP server registry.
 This allows you to use a custom registry instead of the built-in one.
 
-Example:
-  thv config set-registry-url https://example.com/registry.json`,
+The registry can also be an OCI artifact published with thv registry push,
+which is pulled with the same credentials as images (e.g. those stored by docker login).
+
+The registry data is cached locally for an hour, and the cached copy is used
+when the registry can't be reached, e.g. when offline.
+
+With --trusted-key, the registry must be signed with one of the given keys
+(see thv registry sign), and registries which are not signed or were tampered
+with are refused.
+
+Examples:
+  thv config set-registry-url https://example.com/registry.json
+  thv config set-registry-url oci://ghcr.io/example/registry:latest
+  thv config set-registry-url https://example.com/registry.json --trusted-key registry-key.pub`,
 	Args: cobra.ExactArgs(1),
 	RunE: setRegistryURLCmdFunc,
 }
@@ -111,6 +124,7 @@ var unsetRegistryURLCmd = &cobra.Command{
 
 var (
 	allowPrivateRegistryIp bool
+	registryTrustedKeys    []string
 )
 
 func init() {
@@ -132,8 +146,17 @@ func init() {
 		false,
 		"Allow setting the registry URL, even if it references a private IP address",
 	)
+	setRegistryURLCmd.Flags().StringArrayVar(
+		&registryTrustedKeys,
+		"trusted-key",
+		nil,
+		"Public key the registry must be signed with (can be specified multiple times)",
+	)
 	configCmd.AddCommand(getRegistryURLCmd)
 	configCmd.AddCommand(unsetRegistryURLCmd)
+	configCmd.AddCommand(addTrustedSignerCmd)
+	configCmd.AddCommand(removeTrustedSignerCmd)
+	configCmd.AddCommand(listTrustedSignersCmd)
 
 	// Add OTEL parent command to config
 	configCmd.AddCommand(OtelCmd)
@@ -279,12 +302,16 @@ func unsetCACertCmdFunc(_ *cobra.Command, _ []string) error {
 func setRegistryURLCmdFunc(_ *cobra.Command, args []string) error {
 	registryURL := args[0]
 
-	// Basic URL validation - check if it starts with http:// or https://
-	if registryURL != "" && !strings.HasPrefix(registryURL, "http://") && !strings.HasPrefix(registryURL, "https://") {
-		return fmt.Errorf("registry URL must start with http:// or https://")
+	// Basic URL validation - check if it starts with http://, https:// or oci://
+	if registry.IsOCIReference(registryURL) {
+		if _, err := registry.ParseOCIReference(registryURL); err != nil {
+			return err
+		}
+	} else if registryURL != "" && !strings.HasPrefix(registryURL, "http://") && !strings.HasPrefix(registryURL, "https://") {
+		return fmt.Errorf("registry URL must start with http://, https:// or oci://")
 	}
 
-	if !allowPrivateRegistryIp {
+	if !allowPrivateRegistryIp && !registry.IsOCIReference(registryURL) {
 		registryClient := networking.GetHttpClient(false)
 		_, err := registryClient.Get(registryURL)
 		if err != nil && strings.Contains(fmt.Sprint(err), networking.ErrPrivateIpAddress) {
@@ -292,16 +319,25 @@ func setRegistryURLCmdFunc(_ *cobra.Command, args []string) error {
 		}
 	}
 
+	trustedKeys, err := resolveTrustedKeys(registryTrustedKeys)
+	if err != nil {
+		return err
+	}
+
 	// Update the configuration
-	err := config.UpdateConfig(func(c *config.Config) {
+	err = config.UpdateConfig(func(c *config.Config) {
 		c.RegistryUrl = registryURL
 		c.AllowPrivateRegistryIp = allowPrivateRegistryIp
+		c.RegistryTrustedKeys = trustedKeys
 	})
 	if err != nil {
 		return fmt.Errorf("failed to update configuration: %w", err)
 	}
 
 	fmt.Printf("Successfully set registry URL: %s\n", registryURL)
+	for _, key := range trustedKeys {
+		fmt.Printf("Registry must be signed with trusted key: %s\n", key)
+	}
 	if allowPrivateRegistryIp {
 		fmt.Print("Successfully enabled use of private IP addresses for the remote registry\n")
 		fmt.Print("Caution: allowing registry URLs containing private IP addresses may decrease your security.\n" +
@@ -323,9 +359,28 @@ func getRegistryURLCmdFunc(_ *cobra.Command, _ []string) error {
 	}
 
 	fmt.Printf("Current registry URL: %s\n", cfg.RegistryUrl)
+	for _, key := range cfg.RegistryTrustedKeys {
+		fmt.Printf("Trusted key: %s\n", key)
+	}
 	return nil
 }
 
+// resolveTrustedKeys validates the public keys a registry must be signed with, and returns their absolute paths
+func resolveTrustedKeys(keyPaths []string) ([]string, error) {
+	resolved := make([]string, 0, len(keyPaths))
+	for _, keyPath := range keyPaths {
+		absPath, err := filepath.Abs(keyPath)
+		if err != nil {
+			return nil, fmt.Errorf("invalid trusted key path %s: %w", keyPath, err)
+		}
+		resolved = append(resolved, absPath)
+	}
+	if _, err := registry.NewVerifier(resolved); err != nil {
+		return nil, err
+	}
+	return resolved, nil
+}
+
 func unsetRegistryURLCmdFunc(_ *cobra.Command, _ []string) error {
 	cfg := config.GetConfig()
 
@@ -337,6 +392,7 @@ func unsetRegistryURLCmdFunc(_ *cobra.Command, _ []string) error {
 	// Update the configuration
 	err := config.UpdateConfig(func(c *config.Config) {
 		c.RegistryUrl = ""
+		c.RegistryTrustedKeys = nil
 	})
 	if err != nil {
 		return fmt.Errorf("failed to update configuration: %w", err)
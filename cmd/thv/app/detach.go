@@ -0,0 +1,76 @@
+package app
+
+import (
+	"context"
+	"fmt"
+	"time"
+
+	"github.com/spf13/cobra"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/process"
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+// detachFlagUsage is the usage of the --detach flag shared by the commands which start a server
+const detachFlagUsage = "Run the server in the background (use --detach=false to run it in the foreground)"
+
+// resolveDetach returns whether the server should run in the background, from the --detach flag
+// and its legacy inverse --foreground
+func resolveDetach(cmd *cobra.Command, detach, foreground bool) (bool, error) {
+	if !foreground {
+		return detach, nil
+	}
+	if cmd.Flags().Changed("detach") && detach {
+		return false, fmt.Errorf("cannot use --detach with --foreground")
+	}
+	return false, nil
+}
+
+// runWorkloadInForeground runs a server in the foreground with run, which blocks until the server
+// is stopped, e.g. with Ctrl+C, and cleans it up. The logs of the container of the server are
+// streamed to the terminal in the meantime.
+func runWorkloadInForeground(ctx context.Context, manager workloads.Manager, name string, run func() error) error {
+	// A server supervised by ToolHive or a service manager writes its output to the proxy log file instead
+	if process.IsDetached() {
+		return run()
+	}
+
+	logsCtx, cancel := context.WithCancel(ctx)
+	defer cancel()
+	go streamWorkloadLogs(logsCtx, manager, name)
+
+	return run()
+}
+
+// streamWorkloadLogs follows the logs of the container of the named workload whenever it is running,
+// until the context is cancelled
+func streamWorkloadLogs(ctx context.Context, manager workloads.Manager, name string) {
+	ticker := time.NewTicker(time.Second)
+	defer ticker.Stop()
+
+	for {
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+
+		running, err := manager.ListWorkloads(ctx, false)
+		if err != nil {
+			logger.Debugf("Failed to list workloads: %v", err)
+			continue
+		}
+		for _, workload := range running {
+			if workload.Name != name {
+				continue
+			}
+			// Blocks until the container stops, e.g. when it is replaced on restart
+			if _, err := manager.GetLogs(ctx, name, rt.LogOptions{Follow: true, Tail: rt.DefaultLogTail}); err != nil && ctx.Err() == nil {
+				logger.Debugf("Failed to follow logs of %s: %v", name, err)
+			}
+			break
+		}
+	}
+}
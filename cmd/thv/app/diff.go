@@ -0,0 +1,211 @@
+package app
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+	"sort"
+	"text/tabwriter"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/compose"
+	"github.com/stacklok/toolhive/pkg/container"
+	"github.com/stacklok/toolhive/pkg/container/images"
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/runner"
+)
+
+var diffCmd = &cobra.Command{
+	Use:   "diff [server-name...]",
+	Short: "Detect drift between MCP servers and their expected state",
+	Long: `Detect drift between the MCP servers managed by ToolHive and their expected state.
+
+By default, each server is compared with the configuration it was recorded with
+when it was started. When a compose file is given with --file, the servers are
+compared with the declared servers instead.
+
+Drift is reported for the image, image digest, environment variables, ports,
+transport and permissions. The command exits with a non-zero status if any
+drift is found, which makes it suitable for scheduled compliance checks.
+
+The values of environment variables which are set from secrets or whose names
+suggest credentials (e.g. GITHUB_TOKEN) are masked in the output.`,
+	RunE: diffCmdFunc,
+}
+
+var (
+	diffFile   string
+	diffFormat string
+)
+
+func init() {
+	diffCmd.Flags().StringVarP(&diffFile, "file", "f", "", "Path to a compose file to compare against")
+	diffCmd.Flags().StringVar(&diffFormat, "format", FormatText, "Output format (json or text)")
+}
+
+func diffCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+
+	runtime, err := container.NewFactory().Create(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create container runtime: %v", err)
+	}
+
+	observed, err := observeServers(ctx, runtime)
+	if err != nil {
+		return err
+	}
+
+	var drifts []compose.Drift
+	if diffFile != "" {
+		drifts, err = specDrifts(args, observed)
+		if err != nil {
+			return err
+		}
+	} else {
+		drifts = recordedDrifts(args, observed)
+	}
+
+	if err := printDrifts(drifts); err != nil {
+		return err
+	}
+
+	if len(drifts) > 0 {
+		return fmt.Errorf("drift detected in %d server(s)", countDriftedServers(drifts))
+	}
+	return nil
+}
+
+// specDrifts compares the named servers, or all the servers declared in the compose file of --file, with
+// their declaration
+func specDrifts(names []string, observed map[string]compose.ObservedServer) ([]compose.Drift, error) {
+	file, err := compose.Load(diffFile)
+	if err != nil {
+		return nil, err
+	}
+	if len(names) == 0 {
+		for name := range file.Servers {
+			names = append(names, name)
+		}
+		sort.Strings(names)
+	}
+
+	var drifts []compose.Drift
+	for _, name := range names {
+		server, ok := file.Servers[name]
+		if !ok {
+			return nil, fmt.Errorf("server %s is not declared in %s", name, diffFile)
+		}
+		serverDrifts, err := compose.DetectSpecDrift(name, server, observed[name])
+		if err != nil {
+			return nil, err
+		}
+		drifts = append(drifts, serverDrifts...)
+	}
+	return drifts, nil
+}
+
+// recordedDrifts compares the named servers, or all the observed servers, with the configuration they were
+// recorded with
+func recordedDrifts(names []string, observed map[string]compose.ObservedServer) []compose.Drift {
+	if len(names) == 0 {
+		for name := range observed {
+			names = append(names, name)
+		}
+		sort.Strings(names)
+	}
+
+	var drifts []compose.Drift
+	for _, name := range names {
+		obs, ok := observed[name]
+		if !ok {
+			obs = compose.ObservedServer{Name: name}
+		}
+		drifts = append(drifts, compose.DetectRecordedDrift(obs)...)
+	}
+	return drifts
+}
+
+// observeServers collects the actual state of all MCP servers managed by ToolHive, keyed by name
+func observeServers(ctx context.Context, runtime rt.Runtime) (map[string]compose.ObservedServer, error) {
+	containers, err := runtime.ListWorkloads(ctx)
+	if err != nil {
+		return nil, fmt.Errorf("failed to list containers: %v", err)
+	}
+
+	imageManager := images.NewImageManager(ctx)
+	observed := make(map[string]compose.ObservedServer)
+	for i := range containers {
+		c := &containers[i]
+		if !labels.IsToolHiveContainer(c.Labels) {
+			continue
+		}
+
+		name := labels.GetContainerName(c.Labels)
+		obs := compose.ObservedServer{Name: name, Container: c}
+
+		mcpRunner, err := runner.LoadState(ctx, labels.GetContainerBaseName(c.Labels))
+		if err != nil {
+			logger.Debugf("No recorded configuration for %s: %v", name, err)
+		} else {
+			obs.Config = mcpRunner.Config
+			obs.LatestImageID, err = imageManager.GetImageID(ctx, obs.Config.Image)
+			if err != nil {
+				logger.Warnf("Failed to look up image %s: %v", obs.Config.Image, err)
+			}
+		}
+
+		observed[name] = obs
+	}
+	return observed, nil
+}
+
+func printDrifts(drifts []compose.Drift) error {
+	switch diffFormat {
+	case FormatJSON:
+		if drifts == nil {
+			drifts = []compose.Drift{}
+		}
+		jsonData, err := json.MarshalIndent(drifts, "", "  ")
+		if err != nil {
+			return fmt.Errorf("failed to marshal JSON: %v", err)
+		}
+		fmt.Println(string(jsonData))
+	default:
+		if len(drifts) == 0 {
+			printInfo("No drift detected")
+			return nil
+		}
+		if IsQuiet() {
+			seen := make(map[string]bool)
+			for _, drift := range drifts {
+				if !seen[drift.Server] {
+					seen[drift.Server] = true
+					fmt.Println(drift.Server)
+				}
+			}
+			return nil
+		}
+		w := tabwriter.NewWriter(os.Stdout, 0, 0, 3, ' ', 0)
+		fmt.Fprintln(w, "SERVER\tFIELD\tEXPECTED\tACTUAL")
+		for _, drift := range drifts {
+			fmt.Fprintf(w, "%s\t%s\t%s\t%s\n", drift.Server, drift.Field, drift.Expected, drift.Actual)
+		}
+		if err := w.Flush(); err != nil {
+			return fmt.Errorf("failed to flush tabwriter: %v", err)
+		}
+	}
+	return nil
+}
+
+func countDriftedServers(drifts []compose.Drift) int {
+	servers := make(map[string]struct{})
+	for _, drift := range drifts {
+		servers[drift.Server] = struct{}{}
+	}
+	return len(servers)
+}
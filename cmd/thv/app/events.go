@@ -0,0 +1,84 @@
+package app
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"time"
+
+	"github.com/spf13/cobra"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+var (
+	eventsFollow bool
+	eventsServer string
+	eventsSince  time.Duration
+	eventsFormat string
+)
+
+var eventsCmd = &cobra.Command{
+	Use:   "events [flags]",
+	Short: "Show the lifecycle events of MCP servers",
+	Long: `Show the lifecycle and health events of the MCP servers managed by ToolHive, such
+as servers starting, exiting, being paused or becoming unhealthy.
+
+By default, the events of the last hour are shown. With --follow, new events are
+shown as they happen until the command is interrupted. With --format json, each
+event is printed as a JSON object on its own line, which can be piped into jq:
+  $ thv events --follow --server fetch --format json | jq -r .action`,
+	Args: cobra.NoArgs,
+	RunE: eventsCmdFunc,
+}
+
+func init() {
+	eventsCmd.Flags().BoolVarP(&eventsFollow, "follow", "f", false, "Show new events as they happen")
+	eventsCmd.Flags().StringVar(&eventsServer, "server", "", "Only show the events of the named server")
+	eventsCmd.Flags().DurationVar(&eventsSince, "since", time.Hour,
+		"Show events from this long ago (with --follow, only new events are shown unless --since is set)")
+	eventsCmd.Flags().StringVar(&eventsFormat, "format", FormatText, "Output format (json or text)")
+}
+
+func eventsCmdFunc(cmd *cobra.Command, _ []string) error {
+	ctx := cmd.Context()
+
+	manager, err := workloads.NewManager(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create container manager: %v", err)
+	}
+
+	options := &rt.EventOptions{Follow: eventsFollow}
+	if !eventsFollow || cmd.Flags().Changed("since") {
+		options.Since = time.Now().Add(-eventsSince)
+	}
+
+	events, errs := manager.GetEvents(ctx, eventsServer, options)
+	encoder := json.NewEncoder(os.Stdout)
+	for event := range events {
+		if eventsFormat == FormatJSON {
+			if err := encoder.Encode(event); err != nil {
+				return fmt.Errorf("failed to write event: %v", err)
+			}
+			continue
+		}
+		printEvent(event)
+	}
+
+	select {
+	case err := <-errs:
+		return fmt.Errorf("failed to get events: %v", err)
+	default:
+		return nil
+	}
+}
+
+// printEvent prints an event as a line of text
+func printEvent(event workloads.Event) {
+	line := fmt.Sprintf("%s  %s  %s", event.Time.Local().Format(time.RFC3339), event.Name, event.Action)
+	if event.ExitCode != nil {
+		line += fmt.Sprintf(" (exit code %d)", *event.ExitCode)
+	}
+	fmt.Println(line)
+}
@@ -0,0 +1,103 @@
+package app
+
+import (
+	"fmt"
+	"os"
+
+	"github.com/spf13/cobra"
+	"golang.org/x/term"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+var (
+	execInteractive bool
+	execTTY         bool
+)
+
+var execCmd = &cobra.Command{
+	Use:   "exec [flags] SERVER_NAME -- COMMAND [ARGS...]",
+	Short: "Run a command in the container of an MCP server",
+	Long: `Run a command in the container of a running MCP server, e.g. to inspect its
+files or environment. The command uses the same container runtime as the other
+commands, and thv exits with the exit code of the command.
+
+Use -i to pass the standard input to the command and -t to allocate a
+pseudo-terminal, e.g. to start an interactive shell:
+  $ thv exec -it fetch -- sh
+
+The command is never privileged: it has the capabilities of the server, and if
+the permission profile of the server sets no_new_privileges, it can't gain
+privileges either, e.g. by running sudo or other setuid binaries.`,
+	Args: validateExecArgs,
+	RunE: execCmdFunc,
+}
+
+func init() {
+	execCmd.Flags().BoolVarP(&execInteractive, "interactive", "i", false, "Pass the standard input to the command")
+	execCmd.Flags().BoolVarP(&execTTY, "tty", "t", false, "Allocate a pseudo-terminal for the command")
+}
+
+func validateExecArgs(cmd *cobra.Command, args []string) error {
+	dash := cmd.ArgsLenAtDash()
+	if dash != 1 || len(args) < 2 {
+		return fmt.Errorf("expected a server name followed by -- and the command to run")
+	}
+	return nil
+}
+
+func execCmdFunc(cmd *cobra.Command, args []string) error {
+	exitCode, err := execInServer(cmd, args[0], args[1:])
+	if err != nil {
+		return err
+	}
+	if exitCode != 0 {
+		os.Exit(exitCode)
+	}
+	return nil
+}
+
+// execInServer runs the command in the container of the server and returns its exit code.
+// The terminal is restored before returning, so the caller can exit with the exit code.
+func execInServer(cmd *cobra.Command, name string, command []string) (int, error) {
+	ctx := cmd.Context()
+
+	manager, err := workloads.NewManager(ctx)
+	if err != nil {
+		return 0, fmt.Errorf("failed to create container manager: %v", err)
+	}
+
+	options := &rt.ExecOptions{
+		Stdout: os.Stdout,
+		Stderr: os.Stderr,
+		TTY:    execTTY,
+	}
+	if execInteractive {
+		options.Stdin = os.Stdin
+	}
+
+	if execTTY {
+		fd := int(os.Stdin.Fd())
+		if !term.IsTerminal(fd) {
+			return 0, fmt.Errorf("the input is not a terminal, run without -t")
+		}
+		if width, height, err := term.GetSize(fd); err == nil {
+			options.TerminalSize = &[2]uint{uint(height), uint(width)} // #nosec G115 - sizes are not negative
+		}
+		// Pass keystrokes such as Ctrl+C to the command instead of handling them locally
+		if execInteractive {
+			state, err := term.MakeRaw(fd)
+			if err != nil {
+				return 0, fmt.Errorf("failed to set the terminal to raw mode: %v", err)
+			}
+			defer func() { _ = term.Restore(fd, state) }()
+		}
+	}
+
+	exitCode, err := manager.ExecWorkload(ctx, name, command, options)
+	if err != nil {
+		return 0, fmt.Errorf("failed to exec in %s: %v", name, err)
+	}
+	return exitCode, nil
+}
@@ -0,0 +1,156 @@
+package app
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"net"
+	"net/url"
+	"os"
+	"os/signal"
+	"slices"
+	"strconv"
+	"syscall"
+	"text/tabwriter"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/networking"
+	"github.com/stacklok/toolhive/pkg/transport"
+	"github.com/stacklok/toolhive/pkg/transport/proxy/router"
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+var (
+	gatewayHost string
+	gatewayPort int
+)
+
+var gatewayCmd = &cobra.Command{
+	Use:   "gateway [flags] [SERVER_NAME...]",
+	Short: "Serve several MCP servers behind a single port",
+	Long: `Serve the MCP servers managed by ToolHive behind a single port, each under a path
+prefix named after the server, for clients which can only be configured with a
+few endpoints:
+
+	http://127.0.0.1:8000/github/sse
+	http://127.0.0.1:8000/fetch/mcp
+
+The requests under the prefix of a server are forwarded to its proxy, and the
+messages endpoint of SSE servers is announced under the same prefix, so each
+session stays on the server it was opened with. Servers are found by name when
+they are first requested, so servers started after the gateway are served too.
+
+Without arguments, all the MCP servers are served. With server names, only these
+servers are served, and requests to other servers are answered with 404.`,
+	RunE: gatewayCmdFunc,
+}
+
+func init() {
+	gatewayCmd.Flags().StringVar(&gatewayHost, "host", transport.LocalhostIPv4,
+		"Host for the gateway to listen on (IP or hostname); use 0.0.0.0 to accept connections from other machines")
+	gatewayCmd.Flags().IntVar(&gatewayPort, "port", 0, "Port for the gateway to listen on (host port)")
+}
+
+func gatewayCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+
+	validatedHost, err := ValidateAndNormaliseHostFlag(gatewayHost)
+	if err != nil {
+		return fmt.Errorf("invalid host: %s", gatewayHost)
+	}
+	gatewayHost = validatedHost
+	warnIfNotLoopback(gatewayHost)
+
+	port, err := networking.FindOrUsePort(gatewayPort)
+	if err != nil {
+		return err
+	}
+
+	manager, err := workloads.NewManager(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create workload manager: %v", err)
+	}
+
+	gateway := router.NewRouter(gatewayHost, port, gatewayResolver(manager, args))
+	// The token proves to the proxies of the servers that the client addresses the gateway forwards can be trusted
+	token, err := router.LoadToken()
+	if err != nil {
+		logger.Warnf("Warning: Failed to load the gateway token, servers will not see the addresses of clients: %v", err)
+	}
+	gateway.SetToken(token)
+	if err := gateway.Start(); err != nil {
+		return fmt.Errorf("failed to start gateway: %v", err)
+	}
+
+	if err := printGatewayURLs(ctx, manager, args, net.JoinHostPort(gatewayHost, strconv.Itoa(port))); err != nil {
+		logger.Warnf("Failed to list the MCP servers: %v", err)
+	}
+	logger.Info("Press Ctrl+C to stop")
+
+	sigCh := make(chan os.Signal, 1)
+	signal.Notify(sigCh, syscall.SIGINT, syscall.SIGTERM)
+
+	sig := <-sigCh
+	logger.Infof("Received signal %s, stopping gateway...", sig)
+
+	if err := gateway.Stop(ctx); err != nil {
+		logger.Warnf("Warning: Failed to stop gateway: %v", err)
+	}
+	logger.Info("Gateway stopped")
+	return nil
+}
+
+// gatewayResolver returns the resolver finding the proxies of the MCP servers of the gateway,
+// which are all the servers when names is empty
+func gatewayResolver(manager workloads.Manager, names []string) router.Resolver {
+	return func(ctx context.Context, name string) (*url.URL, error) {
+		if len(names) > 0 && !slices.Contains(names, name) {
+			return nil, router.ErrUnknownServer
+		}
+		endpoints, err := manager.GetWorkloadEndpoints(ctx, name)
+		if errors.Is(err, workloads.ErrContainerNotFound) {
+			return nil, router.ErrUnknownServer
+		}
+		if err != nil {
+			return nil, err
+		}
+		if endpoints.URL == "" {
+			return nil, fmt.Errorf("MCP server %s has no proxy", name)
+		}
+		serverURL, err := url.Parse(endpoints.URL)
+		if err != nil {
+			return nil, fmt.Errorf("invalid URL for MCP server %s: %w", name, err)
+		}
+		return &url.URL{Scheme: serverURL.Scheme, Host: serverURL.Host}, nil
+	}
+}
+
+// printGatewayURLs prints the URLs of the running MCP servers of the gateway, under their prefixes
+func printGatewayURLs(ctx context.Context, manager workloads.Manager, names []string, address string) error {
+	workloadList, err := manager.ListWorkloads(ctx, false)
+	if err != nil {
+		return err
+	}
+
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 3, ' ', 0)
+	fmt.Fprintln(w, "NAME\tURL")
+	for _, workload := range workloadList {
+		if len(names) > 0 && !slices.Contains(names, workload.Name) {
+			continue
+		}
+		serverURL, err := url.Parse(workload.URL)
+		if err != nil || workload.URL == "" {
+			continue
+		}
+		gatewayURL := url.URL{
+			Scheme:   "http",
+			Host:     address,
+			Path:     "/" + workload.Name + serverURL.Path,
+			Fragment: serverURL.Fragment,
+		}
+		fmt.Fprintf(w, "%s\t%s\n", workload.Name, gatewayURL.String())
+	}
+	return w.Flush()
+}
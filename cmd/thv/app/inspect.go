@@ -0,0 +1,130 @@
+package app
+
+import (
+	"fmt"
+	"os"
+	"sort"
+	"strings"
+	"text/tabwriter"
+	"time"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/environment"
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+var inspectFormat string
+
+var inspectCmd = &cobra.Command{
+	Use:   "inspect [flags] SERVER_NAME",
+	Short: "Show the configuration and state of an MCP server",
+	Long: `Show the details of an MCP server managed by ToolHive: the image it runs and the
+digest of the image of its container, its arguments, environment variables and
+secrets, its transport and ports, the Unix domain socket its proxy is served on
+with --listen-socket, its permission profile, the state of its
+container, and the PID of its proxy when it runs in the background.
+
+The values of environment variables and arguments holding secrets are masked
+like in the other commands, and secrets are only shown by name. Use --format
+json to get the details for scripting, including the full permission profile:
+   $ thv inspect fetch --format json | jq .permission_profile`,
+	Args: cobra.ExactArgs(1),
+	RunE: inspectCmdFunc,
+}
+
+func init() {
+	inspectCmd.Flags().StringVar(&inspectFormat, "format", FormatText, "Output format (json or text)")
+}
+
+func inspectCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+	name := args[0]
+
+	manager, err := workloads.NewManager(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create container manager: %v", err)
+	}
+
+	details, err := manager.GetWorkloadDetails(ctx, name)
+	if err != nil {
+		return fmt.Errorf("failed to inspect %s: %v", name, err)
+	}
+	details.Env = maskedEnv(details)
+
+	if inspectFormat == FormatJSON {
+		return printJSONOutput(details)
+	}
+	return printDetails(details)
+}
+
+// printDetails prints the details of a server as a list of names and values
+func printDetails(details workloads.Details) error {
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 3, ' ', 0)
+	fmt.Fprintf(w, "NAME\t%s\n", details.Name)
+	fmt.Fprintf(w, "IMAGE\t%s\n", details.Image)
+	if details.ImageID != "" {
+		fmt.Fprintf(w, "IMAGE ID\t%s\n", details.ImageID)
+	}
+	if len(details.Args) > 0 {
+		fmt.Fprintf(w, "ARGS\t%s\n", strings.Join(details.Args, " "))
+	}
+	for _, key := range sortedKeys(details.Env) {
+		fmt.Fprintf(w, "ENV\t%s=%s\n", key, details.Env[key])
+	}
+	for _, secret := range details.Secrets {
+		fmt.Fprintf(w, "SECRET\t%s\n", secret)
+	}
+	fmt.Fprintf(w, "TRANSPORT\t%s\n", details.TransportType)
+	if details.Host != "" {
+		fmt.Fprintf(w, "HOST\t%s\n", details.Host)
+	}
+	fmt.Fprintf(w, "PORT\t%d\n", details.Port)
+	if details.ListenSocket != "" {
+		fmt.Fprintf(w, "SOCKET\t%s\n", details.ListenSocket)
+	}
+	if details.TargetPort != 0 {
+		fmt.Fprintf(w, "TARGET PORT\t%d\n", details.TargetPort)
+	}
+	if details.PermissionProfileName != "" {
+		fmt.Fprintf(w, "PERMISSION PROFILE\t%s\n", details.PermissionProfileName)
+	}
+	fmt.Fprintf(w, "CONTAINER ID\t%s\n", details.ContainerID)
+	fmt.Fprintf(w, "STATE\t%s\n", details.State)
+	if details.Status != "" {
+		fmt.Fprintf(w, "STATUS\t%s\n", details.Status)
+	}
+	fmt.Fprintf(w, "CREATED\t%s\n", details.CreatedAt.Format(time.RFC3339))
+	if details.ProxyPID != 0 {
+		fmt.Fprintf(w, "PROXY PID\t%d\n", details.ProxyPID)
+	} else {
+		fmt.Fprintf(w, "PROXY PID\t-\n")
+	}
+	for _, key := range sortedKeys(details.Labels) {
+		fmt.Fprintf(w, "LABEL\t%s=%s\n", key, details.Labels[key])
+	}
+	return w.Flush()
+}
+
+// maskedEnv returns the environment variables of a server, with the values holding secrets masked
+func maskedEnv(details workloads.Details) map[string]string {
+	if len(details.Env) == 0 {
+		return nil
+	}
+	masker := environment.NewMasker(details.Secrets)
+	env := make(map[string]string, len(details.Env))
+	for key, value := range details.Env {
+		env[key] = masker.Mask(key, value)
+	}
+	return env
+}
+
+// sortedKeys returns the keys of a map in alphabetical order
+func sortedKeys(values map[string]string) []string {
+	keys := make([]string, 0, len(values))
+	for key := range values {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	return keys
+}
@@ -159,6 +159,10 @@ func inspectorCmdFunc(cmd *cobra.Command, args []string) error {
 		if transportType == types.TransportTypeSSE || transportType == types.TransportTypeStdio {
 			suffix = "sse"
 			transportTypeStr = transportType.String()
+		} else if transportType == types.TransportTypeWebSocket {
+			// The proxy of WebSocket servers serves SSE clients
+			suffix = "sse"
+			transportTypeStr = types.TransportTypeSSE.String()
 		} else {
 			suffix = "mcp/"
 			transportTypeStr = "streamable-http"
@@ -0,0 +1,201 @@
+package app
+
+import (
+	"fmt"
+	"os"
+	"runtime"
+	"text/tabwriter"
+
+	"github.com/adrg/xdg"
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/launchd"
+	"github.com/stacklok/toolhive/pkg/process"
+	"github.com/stacklok/toolhive/pkg/runner"
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+var launchdServe bool
+
+func newLaunchdCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "launchd",
+		Short: "Manage launchd agents which keep MCP servers running on macOS",
+		Long: `The launchd command provides subcommands to supervise MCP servers and the ToolHive
+API server with launchd agents on macOS.
+
+An agent starts its server when you log in and restarts it if it fails, so the
+server keeps running across logouts and crashes. Servers supervised by an agent
+can still be stopped with thv stop until the next login; uninstall the agent to
+stop supervising them.`,
+	}
+
+	cmd.AddCommand(
+		newLaunchdInstallCommand(),
+		newLaunchdUninstallCommand(),
+		newLaunchdListCommand(),
+	)
+
+	return cmd
+}
+
+func newLaunchdInstallCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "install [server-name]",
+		Short: "Supervise an MCP server with a launchd agent",
+		Long: `Install a launchd agent which runs an MCP server from its saved configuration.
+
+The server must have been run with thv run before. Installing the agent restarts
+the server under the supervision of launchd. Note that the agent does not inherit
+the environment of your shell, so ${VAR} references of the server are resolved
+from the environment of launchd.
+
+With --serve, the agent runs the ToolHive API server (thv serve) instead. Arguments
+after -- are passed to thv serve:
+  $ thv launchd install --serve -- --port 9090`,
+		Args: cobra.ArbitraryArgs,
+		RunE: launchdInstallCmdFunc,
+	}
+	cmd.Flags().BoolVar(&launchdServe, "serve", false, "Supervise the ToolHive API server instead of an MCP server")
+	return cmd
+}
+
+func newLaunchdUninstallCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "uninstall [server-name]",
+		Short: "Stop supervising an MCP server with launchd",
+		Long: `Uninstall the launchd agent of an MCP server, which also stops the server.
+Use thv restart to run the server in the background again.`,
+		Args: cobra.MaximumNArgs(1),
+		RunE: launchdUninstallCmdFunc,
+	}
+	cmd.Flags().BoolVar(&launchdServe, "serve", false, "Uninstall the agent of the ToolHive API server")
+	return cmd
+}
+
+func newLaunchdListCommand() *cobra.Command {
+	return &cobra.Command{
+		Use:   "list",
+		Short: "List the installed launchd agents",
+		Args:  cobra.NoArgs,
+		RunE:  launchdListCmdFunc,
+	}
+}
+
+// checkLaunchdSupported returns an error if launchd is not available on this platform
+func checkLaunchdSupported() error {
+	if runtime.GOOS != "darwin" {
+		return fmt.Errorf("launchd agents are only supported on macOS")
+	}
+	return nil
+}
+
+func launchdInstallCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+	if err := checkLaunchdSupported(); err != nil {
+		return err
+	}
+
+	execPath, err := os.Executable()
+	if err != nil {
+		return fmt.Errorf("failed to get executable path: %v", err)
+	}
+
+	// launchd starts agents with a minimal PATH, which may not include the helpers of the container runtime
+	agent := &launchd.Agent{EnvironmentVariables: map[string]string{"PATH": os.Getenv("PATH")}}
+	// The agent cannot prompt for input, so it runs like a detached process
+	agent.EnvironmentVariables[process.ToolHiveDetachedEnv] = process.ToolHiveDetachedValue
+
+	var name string
+	if launchdServe {
+		name = "ToolHive API server"
+		agent.Label = launchd.ServeLabel
+		agent.ProgramArguments = append([]string{execPath, "serve"}, args...)
+		agent.KeepAlive = launchd.KeepAliveAlways
+		if agent.LogPath, err = xdg.DataFile("toolhive/logs/serve.log"); err != nil {
+			return fmt.Errorf("failed to get log file path: %v", err)
+		}
+	} else {
+		if len(args) != 1 {
+			return fmt.Errorf("must specify either a server name or --serve")
+		}
+		name = args[0]
+		// Fail early if the server has never been run, since the agent runs it from its saved configuration
+		if _, err := runner.LoadState(ctx, name); err != nil {
+			return err
+		}
+		agent.Label = launchd.ServerLabel(name)
+		agent.ProgramArguments = []string{execPath, "restart", "--foreground", name}
+		// Restart the server only if it fails, so that it can still be stopped with thv stop
+		agent.KeepAlive = launchd.KeepAliveOnFailure
+		if agent.LogPath, err = workloads.ProxyLogFilePath(name); err != nil {
+			return fmt.Errorf("failed to get log file path: %v", err)
+		}
+	}
+
+	if err := launchd.Install(ctx, agent); err != nil {
+		return err
+	}
+	printResult(agent.Label, "Installed launchd agent %s for %s", agent.Label, name)
+	return nil
+}
+
+func launchdUninstallCmdFunc(cmd *cobra.Command, args []string) error {
+	if err := checkLaunchdSupported(); err != nil {
+		return err
+	}
+
+	var label string
+	switch {
+	case launchdServe && len(args) == 0:
+		label = launchd.ServeLabel
+	case !launchdServe && len(args) == 1:
+		label = launchd.ServerLabel(args[0])
+	default:
+		return fmt.Errorf("must specify either a server name or --serve")
+	}
+
+	if err := launchd.Uninstall(cmd.Context(), label); err != nil {
+		return err
+	}
+	printResult(label, "Uninstalled launchd agent %s", label)
+	return nil
+}
+
+func launchdListCmdFunc(cmd *cobra.Command, _ []string) error {
+	if err := checkLaunchdSupported(); err != nil {
+		return err
+	}
+
+	agentLabels, err := launchd.ListInstalled()
+	if err != nil {
+		return err
+	}
+
+	if IsQuiet() {
+		for _, label := range agentLabels {
+			fmt.Println(label)
+		}
+		return nil
+	}
+
+	if len(agentLabels) == 0 {
+		fmt.Println("No launchd agents installed")
+		return nil
+	}
+
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 3, ' ', 0)
+	fmt.Fprintln(w, "LABEL\tSERVER\tLOADED")
+	for _, label := range agentLabels {
+		server, ok := launchd.ServerName(label)
+		if !ok {
+			server = "(thv serve)"
+		}
+		loaded := "no"
+		if launchd.IsLoaded(cmd.Context(), label) {
+			loaded = "yes"
+		}
+		fmt.Fprintf(w, "%s\t%s\t%s\n", label, server, loaded)
+	}
+	return w.Flush()
+}
@@ -1,13 +1,21 @@
 package app
 
 import (
+	"context"
 	"encoding/json"
 	"fmt"
+	"io"
+	"net/http"
 	"os"
+	"sync"
 	"text/tabwriter"
+	"time"
 
 	"github.com/spf13/cobra"
 
+	"github.com/stacklok/toolhive/pkg/container"
+	"github.com/stacklok/toolhive/pkg/environment"
+	"github.com/stacklok/toolhive/pkg/healthcheck"
 	"github.com/stacklok/toolhive/pkg/logger"
 	"github.com/stacklok/toolhive/pkg/workloads"
 )
@@ -15,18 +23,72 @@ import (
 var listCmd = &cobra.Command{
 	Use:   "list",
 	Short: "List running MCP servers",
-	Long:  `List all MCP servers managed by ToolHive, including their status and configuration.`,
-	RunE:  listCmdFunc,
+	Long: `List all MCP servers managed by ToolHive, including their status and configuration.
+
+Use --filter to select a subset of the servers. Filters are given in key=value
+format, and the supported keys are:
+
+  status     Server status (running, stopped, error, starting or unknown)
+  transport  Transport mode (stdio, sse, streamable-http or websocket)
+  name       Server name, as a glob pattern (e.g., "github-*")
+  label      Label key or key=value pair
+
+Filters with the same key are combined with OR, and filters with different keys
+are combined with AND. Filtering by status also lists stopped servers, as if
+--all was given. The --status, --transport and --label flags are shorthands
+for the corresponding filters:
+   $ thv list --status stopped --label team=platform
+
+Use --format to choose the output: table (the default, also called text), wide,
+json, or mcpservers for the configuration of MCP clients. The json output also
+includes the environment variables of the servers, whose values holding secrets
+are masked. With --quiet, or --names-only, only the names of the servers are
+printed, one per line.
+
+Use --wide to also show the health reported by each server's proxy (healthy,
+starting, degraded or unhealthy), how long the server has been running, and
+how many times it was restarted by the container runtime; --format wide is the
+same as --wide. The URL column is the endpoint to configure in MCP clients, or
+the path of the Unix domain socket, as unix:PATH, for servers run with
+--listen-socket. These are not included in the mcpservers output, since MCP
+clients cannot connect to them with a URL.`,
+	RunE: listCmdFunc,
 }
 
 var (
-	listAll    bool
-	listFormat string
+	listAll        bool
+	listFormat     string
+	listFilters    []string
+	listNamesOnly  bool
+	listWide       bool
+	listStatuses   []string
+	listTransports []string
+	listLabels     []string
+)
+
+const (
+	// listFormatTable is the default output format of list, a table of the servers
+	listFormatTable = "table"
+	// listFormatWide is the output format of list which also shows the runtime information of the servers
+	listFormatWide = "wide"
+	// listFormatMCPServers is the output format of list for the configuration of MCP clients
+	listFormatMCPServers = "mcpservers"
 )
 
 func init() {
 	listCmd.Flags().BoolVarP(&listAll, "all", "a", false, "Show all containers (default shows just running)")
-	listCmd.Flags().StringVar(&listFormat, "format", FormatText, "Output format (json, text, or mcpservers)")
+	listCmd.Flags().StringVar(&listFormat, "format", FormatText,
+		"Output format (table, wide, json, or mcpservers; text is the same as table)")
+	listCmd.Flags().StringArrayVar(&listFilters, "filter", nil,
+		"Filter servers by status, transport, name or label (format: key=value, can be repeated)")
+	listCmd.Flags().BoolVar(&listNamesOnly, "names-only", false, "Only print the names of the servers, one per line")
+	listCmd.Flags().BoolVarP(&listWide, "wide", "w", false, "Show the health, uptime and restart count of the servers")
+	listCmd.Flags().StringArrayVar(&listStatuses, "status", nil,
+		"Only show servers with the given status, e.g. running or stopped (can be repeated)")
+	listCmd.Flags().StringArrayVar(&listTransports, "transport", nil,
+		"Only show servers using the given transport (can be repeated)")
+	listCmd.Flags().StringArrayVar(&listLabels, "label", nil,
+		"Only show servers with the given label, as key or key=value (can be repeated)")
 }
 
 func listCmdFunc(cmd *cobra.Command, _ []string) error {
@@ -38,22 +100,65 @@ func listCmdFunc(cmd *cobra.Command, _ []string) error {
 		return fmt.Errorf("failed to create container manager: %v", err)
 	}
 
+	if err := validateListFormat(); err != nil {
+		return err
+	}
+
+	filter, err := workloads.ParseFilters(listFilterArgs(listFilters, listStatuses, listTransports, listLabels))
+	if err != nil {
+		return err
+	}
+
 	// Create container runtime
-	toolHiveContainers, err := manager.ListWorkloads(ctx, listAll)
+	toolHiveContainers, err := manager.ListWorkloads(ctx, listAll || filter.SelectsStatus())
 	if err != nil {
 		return fmt.Errorf("failed to list containers: %v", err)
 	}
+	toolHiveContainers = filter.Apply(toolHiveContainers)
+	maskSensitiveLabels(toolHiveContainers)
+
+	return printListOutput(ctx, manager, toolHiveContainers)
+}
+
+// validateListFormat checks the format of --format, which turns on --wide for the wide format
+func validateListFormat() error {
+	switch listFormat {
+	case FormatText, listFormatTable, FormatJSON, listFormatMCPServers:
+	case listFormatWide:
+		listWide = true
+	default:
+		return fmt.Errorf("invalid format %s (supported formats are table, wide, json and mcpservers)", listFormat)
+	}
+	return nil
+}
+
+// printListOutput prints the listed workloads in the format of --format, or only their names
+func printListOutput(ctx context.Context, manager workloads.Manager, toolHiveContainers []workloads.Workload) error {
+	if listNamesOnly || IsQuiet() {
+		for _, c := range toolHiveContainers {
+			fmt.Println(c.Name)
+		}
+		return nil
+	}
 
 	if len(toolHiveContainers) == 0 {
+		// Scripts consuming JSON get an empty list rather than a message
+		if listFormat == FormatJSON {
+			return printJSONOutput([]workloads.Workload{})
+		}
 		fmt.Println("No MCP servers found")
 		return nil
 	}
 
+	if listWide && listFormat != listFormatMCPServers {
+		return printWideOutput(ctx, toolHiveContainers)
+	}
+
 	// Output based on format
 	switch listFormat {
 	case FormatJSON:
-		return printJSONOutput(toolHiveContainers)
-	case "mcpservers":
+		return printJSONOutput(withEnv(ctx, manager, toolHiveContainers))
+	case listFormatMCPServers:
 		return printMCPServersOutput(toolHiveContainers)
 	default:
 		printTextOutput(toolHiveContainers)
@@ -61,8 +166,56 @@ func listCmdFunc(cmd *cobra.Command, _ []string) error {
 	}
 }
 
+// listFilterArgs returns the filters given with --filter and with the --status, --transport and --label shorthands
+func listFilterArgs(filters, statuses, transports, labels []string) []string {
+	args := append([]string{}, filters...)
+	for _, status := range statuses {
+		args = append(args, "status="+status)
+	}
+	for _, transportType := range transports {
+		args = append(args, "transport="+transportType)
+	}
+	for _, label := range labels {
+		args = append(args, "label="+label)
+	}
+	return args
+}
+
+// maskSensitiveLabels masks the values of labels whose names suggest they hold credentials
+func maskSensitiveLabels(workloadList []workloads.Workload) {
+	for _, workload := range workloadList {
+		for key := range workload.Labels {
+			if environment.IsSensitiveName(key) {
+				workload.Labels[key] = environment.MaskedValue
+			}
+		}
+	}
+}
+
+// listedWorkload is a workload with its environment variables, as printed by list --format json
+type listedWorkload struct {
+	workloads.Workload
+	// Env are the environment variables of the workload, whose values holding secrets are masked
+	Env map[string]string `json:"env,omitempty"`
+}
+
+// withEnv adds the environment variables of the workloads from their details, masking the values holding secrets
+func withEnv(ctx context.Context, manager workloads.Manager, workloadList []workloads.Workload) []listedWorkload {
+	listed := make([]listedWorkload, len(workloadList))
+	for i, workload := range workloadList {
+		listed[i] = listedWorkload{Workload: workload}
+		details, err := manager.GetWorkloadDetails(ctx, workload.Name)
+		if err != nil {
+			logger.Debugf("Failed to get the environment variables of %s: %v", workload.Name, err)
+			continue
+		}
+		listed[i].Env = maskedEnv(details)
+	}
+	return listed
+}
+
 // printJSONOutput prints container information in JSON format
-func printJSONOutput(containers []workloads.Workload) error {
+func printJSONOutput(containers any) error {
 	// Marshal to JSON
 	jsonData, err := json.MarshalIndent(containers, "", "  ")
 	if err != nil {
@@ -81,6 +234,10 @@ func printMCPServersOutput(containers []workloads.Workload) error {
 	mcpServers := make(map[string]map[string]string)
 
 	for _, c := range containers {
+		// Servers served on a socket have no URL which clients can connect to
+		if c.URL == "" {
+			continue
+		}
 		// Add the MCP server to the map
 		mcpServers[c.Name] = map[string]string{
 			"url": c.URL,
@@ -113,7 +270,7 @@ func printTextOutput(containers []workloads.Workload) {
 			c.Name,
 			c.Package,
 			c.Status,
-			c.URL,
+			workloadEndpoint(c),
 			c.Port,
 			c.ToolType,
 			c.CreatedAt,
@@ -125,3 +282,139 @@ func printTextOutput(containers []workloads.Workload) {
 		logger.Errorf("Warning: Failed to flush tabwriter: %v", err)
 	}
 }
+
+// workloadEndpoint returns the URL of a workload, or the path of the socket its proxy is served on
+func workloadEndpoint(workload workloads.Workload) string {
+	if workload.ListenSocket != "" {
+		return "unix:" + workload.ListenSocket
+	}
+	return workload.URL
+}
+
+// workloadDetails is a workload with the runtime information shown by list --wide
+type workloadDetails struct {
+	workloads.Workload
+	// Health is the health reported by the proxy of the workload
+	Health string `json:"health"`
+	// StartedAt is the time the workload was last started
+	StartedAt *time.Time `json:"started_at,omitempty"`
+	// RestartCount is the number of times the workload was restarted by the container runtime
+	RestartCount int `json:"restart_count"`
+}
+
+// printWideOutput prints container information including health, uptime and restart count
+func printWideOutput(ctx context.Context, containers []workloads.Workload) error {
+	details, err := collectWorkloadDetails(ctx, containers)
+	if err != nil {
+		return err
+	}
+
+	if listFormat == FormatJSON {
+		return printJSONOutput(details)
+	}
+
+	printWideTable(os.Stdout, details)
+	return nil
+}
+
+// printWideTable writes the table of list --wide, with the runtime information of each workload
+func printWideTable(out io.Writer, details []workloadDetails) {
+	w := tabwriter.NewWriter(out, 0, 0, 3, ' ', 0)
+	fmt.Fprintln(w, "NAME\tPACKAGE\tSTATUS\tHEALTH\tUPTIME\tRESTARTS\tURL\tPORT\tTOOL TYPE\tCREATED AT")
+	for _, d := range details {
+		fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\t%d\t%s\t%d\t%s\t%s\n",
+			d.Name,
+			d.Package,
+			d.Status,
+			d.Health,
+			formatUptime(d.StartedAt),
+			d.RestartCount,
+			workloadEndpoint(d.Workload),
+			d.Port,
+			d.ToolType,
+			d.CreatedAt,
+		)
+	}
+
+	if err := w.Flush(); err != nil {
+		logger.Errorf("Warning: Failed to flush tabwriter: %v", err)
+	}
+}
+
+// healthTimeout is how long list waits for the health of the proxy of a workload
+const healthTimeout = 2 * time.Second
+
+// collectWorkloadDetails inspects the workloads and queries the health endpoints of their proxies concurrently
+func collectWorkloadDetails(ctx context.Context, containers []workloads.Workload) ([]workloadDetails, error) {
+	runtime, err := container.NewFactory().Create(ctx)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create container runtime: %v", err)
+	}
+	httpClient := &http.Client{Timeout: healthTimeout}
+
+	details := make([]workloadDetails, len(containers))
+	var wg sync.WaitGroup
+	for i, c := range containers {
+		details[i] = workloadDetails{Workload: c, Health: "-"}
+		wg.Add(1)
+		go func(d *workloadDetails) {
+			defer wg.Done()
+
+			info, err := runtime.GetWorkloadInfo(ctx, d.Name)
+			if err != nil {
+				logger.Debugf("Failed to inspect %s: %v", d.Name, err)
+			} else {
+				d.RestartCount = info.RestartCount
+				if !info.StartedAt.IsZero() && d.Status == workloads.WorkloadStatusRunning {
+					d.StartedAt = &info.StartedAt
+				}
+			}
+
+			if d.Status == workloads.WorkloadStatusRunning {
+				switch {
+				case d.ListenSocket != "":
+					d.Health = proxyHealth(ctx, healthcheck.NewSocketClient(d.ListenSocket, healthTimeout), healthcheck.SocketURL)
+				case d.URL != "":
+					d.Health = proxyHealth(ctx, httpClient, d.URL)
+				}
+			}
+		}(&details[i])
+	}
+	wg.Wait()
+
+	return details, nil
+}
+
+// proxyHealth returns the health reported by the proxy at the given URL, or "unreachable"
+func proxyHealth(ctx context.Context, httpClient *http.Client, proxyURL string) string {
+	health, err := healthcheck.Fetch(ctx, httpClient, proxyURL)
+	if err != nil {
+		logger.Debugf("Failed to check the health of %s: %v", proxyURL, err)
+		return "unreachable"
+	}
+	return string(health.Status)
+}
+
+// formatUptime formats the time elapsed since a workload was started, using its two most significant units
+func formatUptime(startedAt *time.Time) string {
+	if startedAt == nil {
+		return "-"
+	}
+
+	uptime := time.Since(*startedAt).Round(time.Second)
+	days := int(uptime.Hours()) / 24
+	hours := int(uptime.Hours()) % 24
+	minutes := int(uptime.Minutes()) % 60
+	seconds := int(uptime.Seconds()) % 60
+
+	switch {
+	case days > 0:
+		return fmt.Sprintf("%dd%dh", days, hours)
+	case hours > 0:
+		return fmt.Sprintf("%dh%dm", hours, minutes)
+	case minutes > 0:
+		return fmt.Sprintf("%dm%ds", minutes, seconds)
+	default:
+		return fmt.Sprintf("%ds", seconds)
+	}
+}
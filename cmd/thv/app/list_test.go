@@ -0,0 +1,187 @@
+package app
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"regexp"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/environment"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+// fakeManager is a workload manager returning the details of workloads from memory
+type fakeManager struct {
+	workloads.Manager
+	details map[string]workloads.Details
+}
+
+func (f *fakeManager) GetWorkloadDetails(_ context.Context, name string) (workloads.Details, error) {
+	details, ok := f.details[name]
+	if !ok {
+		return workloads.Details{}, workloads.ErrContainerNotFound
+	}
+	return details, nil
+}
+
+func TestWithEnv_MasksSecrets(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	manager := &fakeManager{details: map[string]workloads.Details{
+		"github": {
+			Name: "github",
+			Env: map[string]string{
+				"GITHUB_TOKEN": "ghp_secret",
+				"UPSTREAM":     "upstream-secret",
+				"LOG_LEVEL":    "debug",
+			},
+			Secrets: []string{"upstream,target=UPSTREAM"},
+		},
+	}}
+
+	listed := withEnv(context.Background(), manager, []workloads.Workload{{Name: "github"}, {Name: "fetch"}})
+	data, err := json.Marshal(listed)
+	require.NoError(t, err)
+	assert.NotContains(t, string(data), "ghp_secret")
+	assert.NotContains(t, string(data), "upstream-secret")
+
+	var output []map[string]any
+	require.NoError(t, json.Unmarshal(data, &output))
+	require.Len(t, output, 2)
+	assert.Equal(t, map[string]any{
+		"GITHUB_TOKEN": environment.MaskedValue,
+		"UPSTREAM":     environment.MaskedValue,
+		"LOG_LEVEL":    "debug",
+	}, output[0]["env"])
+	assert.Equal(t, "github", output[0]["name"])
+	// Workloads whose details cannot be read are listed without their environment variables
+	assert.NotContains(t, output[1], "env")
+}
+
+func TestListFilterArgs(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name       string
+		filters    []string
+		statuses   []string
+		transports []string
+		labels     []string
+		expected   []string
+	}{
+		{
+			name:     "No filters",
+			expected: []string{},
+		},
+		{
+			name:     "Filters only",
+			filters:  []string{"name=github-*", "status=running"},
+			expected: []string{"name=github-*", "status=running"},
+		},
+		{
+			name:       "Shorthands",
+			statuses:   []string{"running", "stopped"},
+			transports: []string{"sse"},
+			labels:     []string{"team=platform", "env"},
+			expected:   []string{"status=running", "status=stopped", "transport=sse", "label=team=platform", "label=env"},
+		},
+		{
+			name:       "Filters followed by shorthands",
+			filters:    []string{"name=fetch"},
+			statuses:   []string{"running"},
+			transports: []string{"stdio"},
+			labels:     []string{"team=ai"},
+			expected:   []string{"name=fetch", "status=running", "transport=stdio", "label=team=ai"},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			args := listFilterArgs(tt.filters, tt.statuses, tt.transports, tt.labels)
+			assert.Equal(t, tt.expected, args)
+
+			// The args are parsed like the filters given with --filter
+			_, err := workloads.ParseFilters(args)
+			assert.NoError(t, err)
+		})
+	}
+}
+
+func TestPrintWideTable(t *testing.T) {
+	t.Parallel()
+
+	created := time.Date(2025, 7, 1, 8, 0, 0, 0, time.UTC)
+	startedAt := time.Now().Add(-90 * time.Minute)
+	columnSeparator := regexp.MustCompile(`\s{2,}`)
+
+	tests := []struct {
+		name     string
+		details  workloadDetails
+		expected []string
+	}{
+		{
+			name: "Running workload",
+			details: workloadDetails{
+				Workload: workloads.Workload{
+					Name:      "github",
+					Package:   "ghcr.io/github/github-mcp-server:latest",
+					Status:    workloads.WorkloadStatusRunning,
+					URL:       "http://127.0.0.1:9090/sse#github",
+					Port:      9090,
+					ToolType:  "mcp",
+					CreatedAt: created,
+				},
+				Health:       "healthy",
+				StartedAt:    &startedAt,
+				RestartCount: 2,
+			},
+			expected: []string{
+				"github", "ghcr.io/github/github-mcp-server:latest", "running", "healthy", "1h30m", "2",
+				"http://127.0.0.1:9090/sse#github", "9090", "mcp", created.String(),
+			},
+		},
+		{
+			name: "Stopped workload served on a socket",
+			details: workloadDetails{
+				Workload: workloads.Workload{
+					Name:         "fetch",
+					Package:      "ghcr.io/stackloklabs/gofetch/server:latest",
+					Status:       workloads.WorkloadStatusStopped,
+					ListenSocket: "/run/toolhive/fetch.sock",
+					ToolType:     "mcp",
+					CreatedAt:    created,
+				},
+				Health: "-",
+			},
+			expected: []string{
+				"fetch", "ghcr.io/stackloklabs/gofetch/server:latest", "stopped", "-", "-", "0",
+				"unix:/run/toolhive/fetch.sock", "0", "mcp", created.String(),
+			},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			var out bytes.Buffer
+			printWideTable(&out, []workloadDetails{tt.details})
+			lines := strings.Split(strings.TrimSpace(out.String()), "\n")
+			require.Len(t, lines, 2)
+
+			assert.Equal(t, []string{
+				"NAME", "PACKAGE", "STATUS", "HEALTH", "UPTIME", "RESTARTS", "URL", "PORT", "TOOL TYPE", "CREATED AT",
+			}, columnSeparator.Split(strings.TrimSpace(lines[0]), -1))
+			assert.Equal(t, tt.expected, columnSeparator.Split(strings.TrimSpace(lines[1]), -1))
+		})
+	}
+}
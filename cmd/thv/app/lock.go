@@ -0,0 +1,85 @@
+package app
+
+import (
+	"fmt"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/compose"
+)
+
+var lockCmd = &cobra.Command{
+	Use:   "lock",
+	Short: "Generate a lock file pinning the servers of a compose file",
+	Long: `Generate a lock file (toolhive.lock) pinning the servers of a compose file.
+
+The lock file is written next to the compose file and records, for each server,
+the exact image digest, the version of the registry entry it was resolved from,
+and the hash of its permission profile. Commit it alongside the compose file and
+use thv apply --locked to reproduce exactly the same environment.
+
+Run this command again to update the lock file after changing the compose file
+or to pick up newer images.`,
+	Args: cobra.NoArgs,
+	RunE: lockCmdFunc,
+}
+
+var lockFile string
+
+func init() {
+	lockCmd.Flags().StringVarP(&lockFile, "file", "f", "toolhive.yaml", "Path to the compose file")
+}
+
+func lockCmdFunc(cmd *cobra.Command, _ []string) error {
+	ctx := cmd.Context()
+
+	file, err := compose.Load(lockFile)
+	if err != nil {
+		return err
+	}
+
+	resolver, err := compose.NewResolver()
+	if err != nil {
+		return err
+	}
+
+	lock, err := compose.Lock(ctx, file, resolver)
+	if err != nil {
+		return err
+	}
+
+	path := compose.LockPath(lockFile)
+	if err := lock.Save(path); err != nil {
+		return err
+	}
+
+	printResult(path, "Wrote %s", path)
+	return nil
+}
+
+// loadVerifiedLock loads the lock file of a compose file and checks that the compose file still matches it
+func loadVerifiedLock(cmd *cobra.Command, composePath string, file *compose.File) (*compose.LockFile, error) {
+	path := compose.LockPath(composePath)
+	lock, err := compose.LoadLock(path)
+	if err != nil {
+		return nil, fmt.Errorf("%v (run thv lock to generate it)", err)
+	}
+
+	resolver, err := compose.NewResolver()
+	if err != nil {
+		return nil, err
+	}
+
+	drifts, err := compose.VerifyLock(cmd.Context(), file, lock, resolver)
+	if err != nil {
+		return nil, err
+	}
+	if len(drifts) > 0 {
+		for _, drift := range drifts {
+			printInfo("%s: %s differs from %s (locked: %s, current: %s)",
+				drift.Server, drift.Field, path, drift.Expected, drift.Actual)
+		}
+		return nil, fmt.Errorf("%s does not match %s; run thv lock to update it", composePath, path)
+	}
+	return lock, nil
+}
@@ -6,26 +6,49 @@ import (
 	"fmt"
 	"os"
 	"path/filepath"
+	"strconv"
 	"strings"
+	"time"
 
 	"github.com/adrg/xdg"
 	"github.com/spf13/cobra"
 	"github.com/spf13/viper"
 
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
 	"github.com/stacklok/toolhive/pkg/logger"
 	"github.com/stacklok/toolhive/pkg/workloads"
 )
 
 var (
 	followFlag bool
+	proxyFlag  bool
+	tailFlag   int
+	sinceFlag  string
 )
 
 func logsCommand() *cobra.Command {
 	logsCommand := &cobra.Command{
 		Use:   "logs [container-name|prune]",
 		Short: "Output the logs of an MCP server or manage log files",
-		Long:  `Output the logs of an MCP server managed by ToolHive, or manage log files.`,
-		Args:  cobra.ExactArgs(1),
+		Long: `Output the logs of an MCP server managed by ToolHive, or manage log files.
+
+By default, the output of the MCP server container is shown. Use --proxy to show
+the logs of the ToolHive proxy in front of the server instead, which record
+client connections and transport errors. Proxy logs are only available for
+servers running in the background.
+
+The output of the container of every server started by ToolHive is also
+persisted to a log file, which is rotated as it grows, so the last logs of a
+server are still shown after its container was removed. The output of servers
+using the stdio transport is their MCP traffic, so only their errors are kept.
+
+The last 100 lines are shown by default. Use --tail to show another number of
+lines, or --tail 0 to show all of them, and --since to only show the logs
+written in the given duration or since the given time, e.g. --since 10m or
+--since 2025-07-01T08:00:00Z. With --follow, new logs are streamed until the
+command is interrupted:
+   $ thv logs fetch -f --tail 20 --since 10m`,
+		Args: cobra.ExactArgs(1),
 		RunE: func(cmd *cobra.Command, args []string) error {
 			// Check if the argument is "prune"
 			if args[0] == "prune" {
@@ -40,6 +63,11 @@ func logsCommand() *cobra.Command {
 	if err != nil {
 		logger.Errorf("failed to bind flag: %v", err)
 	}
+	logsCommand.Flags().BoolVar(&proxyFlag, "proxy", false, "Show the logs of the server's proxy instead of the container")
+	logsCommand.Flags().IntVar(&tailFlag, "tail", rt.DefaultLogTail,
+		"Number of lines to show from the end of the logs, or 0 to show all of them")
+	logsCommand.Flags().StringVar(&sinceFlag, "since", "",
+		"Only show the logs written in this duration (e.g. 10m) or since this RFC 3339 time (only for container logs)")
 
 	// Add prune subcommand for better discoverability
 	pruneCmd := &cobra.Command{
@@ -62,13 +90,26 @@ func logsCmdFunc(cmd *cobra.Command, args []string) error {
 	// Get container name
 	containerName := args[0]
 	follow := viper.GetBool("follow")
+	if tailFlag < 0 {
+		return fmt.Errorf("--tail cannot be negative")
+	}
+	since, err := parseLogsSince(sinceFlag, time.Now())
+	if err != nil {
+		return err
+	}
+	options := rt.LogOptions{Follow: follow, Tail: tailFlag, Since: since}
 
 	manager, err := workloads.NewManager(ctx)
 	if err != nil {
 		return fmt.Errorf("failed to create lifecycle manager: %v", err)
 	}
 
-	logs, err := manager.GetLogs(ctx, containerName, follow)
+	getLogs := manager.GetLogs
+	if proxyFlag {
+		getLogs = manager.GetProxyLogs
+	}
+
+	logs, err := getLogs(ctx, containerName, options)
 	if err != nil {
 		if errors.Is(err, workloads.ErrContainerNotFound) {
 			logger.Infof("container %s not found", containerName)
@@ -81,6 +122,25 @@ func logsCmdFunc(cmd *cobra.Command, args []string) error {
 	return nil
 }
 
+// parseLogsSince returns the time of the oldest logs to show for the value of --since, which is either
+// a duration before now or an RFC 3339 time, or the zero time if the value is empty
+func parseLogsSince(value string, now time.Time) (time.Time, error) {
+	if value == "" {
+		return time.Time{}, nil
+	}
+	if duration, err := time.ParseDuration(value); err == nil {
+		if duration < 0 {
+			return time.Time{}, fmt.Errorf("--since cannot be a negative duration")
+		}
+		return now.Add(-duration), nil
+	}
+	since, err := time.Parse(time.RFC3339, value)
+	if err != nil {
+		return time.Time{}, fmt.Errorf("invalid --since value %q: expected a duration such as 10m or an RFC 3339 time", value)
+	}
+	return since, nil
+}
+
 func logsPruneCmdFunc(cmd *cobra.Command) error {
 	ctx := cmd.Context()
 
@@ -151,20 +211,37 @@ func getLogFiles(logsDir string) ([]string, error) {
 		return nil, nil
 	}
 
-	logFiles, err := filepath.Glob(filepath.Join(logsDir, "*.log"))
-	if err != nil {
-		return nil, fmt.Errorf("failed to list log files: %v", err)
+	// Proxy logs are in the logs directory, and the persisted logs of servers, with their rotated files,
+	// in its servers directory
+	var logFiles []string
+	for _, pattern := range []string{"*.log", "servers/*.log", "servers/*.log.*"} {
+		matches, err := filepath.Glob(filepath.Join(logsDir, pattern))
+		if err != nil {
+			return nil, fmt.Errorf("failed to list log files: %v", err)
+		}
+		logFiles = append(logFiles, matches...)
 	}
 
 	return logFiles, nil
 }
 
+// logFileBaseName returns the base name of the server a log file belongs to, e.g. fetch for fetch.log.1
+func logFileBaseName(logFile string) string {
+	name := filepath.Base(logFile)
+	if ext := filepath.Ext(name); ext != ".log" {
+		if _, err := strconv.Atoi(strings.TrimPrefix(ext, ".")); err == nil {
+			name = strings.TrimSuffix(name, ext)
+		}
+	}
+	return strings.TrimSuffix(name, ".log")
+}
+
 func pruneOrphanedLogFiles(logFiles []string, managedNames map[string]bool) ([]string, []string) {
 	var prunedFiles []string
 	var errs []string
 
 	for _, logFile := range logFiles {
-		baseName := strings.TrimSuffix(filepath.Base(logFile), ".log")
+		baseName := logFileBaseName(logFile)
 
 		if !managedNames[baseName] {
 			if err := os.Remove(logFile); err != nil {
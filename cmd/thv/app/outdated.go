@@ -0,0 +1,167 @@
+package app
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"sort"
+	"strings"
+	"text/tabwriter"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/container"
+	"github.com/stacklok/toolhive/pkg/container/images"
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+var outdatedCmd = &cobra.Command{
+	Use:   "outdated [server-name...]",
+	Short: "Check MCP server images for newer versions",
+	Long: `Check the images of the MCP servers managed by ToolHive for newer versions.
+
+For each server, the upstream registry is queried for the digest its image
+reference currently resolves to, and for newer semantic version tags of the
+image. Servers whose image has moved or has newer tags are reported as outdated.
+Images pinned by digest are never reported as outdated.
+
+This command does not change anything; use it to plan upgrades.`,
+	RunE: outdatedCmdFunc,
+}
+
+var outdatedFormat string
+
+func init() {
+	outdatedCmd.Flags().StringVar(&outdatedFormat, "format", FormatText, "Output format (json or text)")
+}
+
+// serverFreshness is the freshness of the image of a server
+type serverFreshness struct {
+	Server string `json:"server"`
+	*images.Freshness
+	Error string `json:"error,omitempty"`
+}
+
+func outdatedCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+
+	runtime, err := container.NewFactory().Create(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create container runtime: %v", err)
+	}
+
+	observed, err := observeServers(ctx, runtime)
+	if err != nil {
+		return err
+	}
+
+	names := args
+	if len(names) == 0 {
+		for name := range observed {
+			names = append(names, name)
+		}
+		sort.Strings(names)
+	}
+
+	imageManager := images.NewImageManager(ctx)
+	registry := images.NewRemoteRegistry()
+
+	results := make([]serverFreshness, 0, len(names))
+	for _, name := range names {
+		obs, ok := observed[name]
+		if !ok {
+			return fmt.Errorf("server %s not found", name)
+		}
+
+		image := obs.Container.Image
+		if obs.Config != nil && obs.Config.Image != "" {
+			image = obs.Config.Image
+		}
+
+		localImage := obs.Container.ImageID
+		if localImage == "" {
+			localImage = image
+		}
+		localDigests, err := imageManager.GetRepoDigests(ctx, localImage)
+		if err != nil {
+			logger.Warnf("Failed to look up the local digests of %s: %v", image, err)
+		}
+
+		result := serverFreshness{Server: name}
+		result.Freshness, err = images.CheckFreshness(ctx, registry, image, localDigests)
+		if err != nil {
+			result.Freshness = &images.Freshness{Image: image}
+			result.Error = err.Error()
+		}
+		results = append(results, result)
+	}
+
+	return printFreshness(results)
+}
+
+func printFreshness(results []serverFreshness) error {
+	switch outdatedFormat {
+	case FormatJSON:
+		jsonData, err := json.MarshalIndent(results, "", "  ")
+		if err != nil {
+			return fmt.Errorf("failed to marshal JSON: %v", err)
+		}
+		fmt.Println(string(jsonData))
+	default:
+		if IsQuiet() {
+			for _, result := range results {
+				if result.Outdated {
+					fmt.Println(result.Server)
+				}
+			}
+			return nil
+		}
+		if len(results) == 0 {
+			fmt.Println("No MCP servers found")
+			return nil
+		}
+		w := tabwriter.NewWriter(os.Stdout, 0, 0, 3, ' ', 0)
+		fmt.Fprintln(w, "SERVER\tIMAGE\tCURRENT\tLATEST\tNEWER TAGS\tSTATUS")
+		for _, result := range results {
+			fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\t%s\n",
+				result.Server,
+				result.Image,
+				shortDigest(result.CurrentDigest),
+				shortDigest(result.LatestDigest),
+				strings.Join(result.NewerTags, ", "),
+				freshnessStatus(result),
+			)
+		}
+		if err := w.Flush(); err != nil {
+			return fmt.Errorf("failed to flush tabwriter: %v", err)
+		}
+	}
+	return nil
+}
+
+func freshnessStatus(result serverFreshness) string {
+	switch {
+	case result.Error != "":
+		return "unknown: " + result.Error
+	case result.Pinned:
+		return "pinned"
+	case result.Outdated:
+		return "outdated"
+	case result.CurrentDigest == "":
+		return "unknown"
+	default:
+		return "up to date"
+	}
+}
+
+// shortDigest shortens a digest for display, e.g. sha256:0123456789ab
+func shortDigest(digest string) string {
+	const length = len("sha256:") + 12
+	if digest == "" {
+		return "-"
+	}
+	if len(digest) > length {
+		return digest[:length]
+	}
+	return digest
+}
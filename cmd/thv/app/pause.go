@@ -0,0 +1,64 @@
+package app
+
+import (
+	"fmt"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+var pauseCmd = &cobra.Command{
+	Use:   "pause [container-name]",
+	Short: "Pause an MCP server",
+	Long: `Pause a running MCP server managed by ToolHive.
+
+The processes of the server's container are frozen, so the server stops using CPU
+while keeping its state in memory. The proxy of the server keeps running and
+rejects requests with 503 Service Unavailable until the server is resumed with
+thv resume. Stopping a paused server resumes it first.`,
+	Args: cobra.ExactArgs(1),
+	RunE: pauseCmdFunc,
+}
+
+var resumeCmd = &cobra.Command{
+	Use:   "resume [container-name]",
+	Short: "Resume a paused MCP server",
+	Long:  `Resume an MCP server paused with thv pause, which continues where it left off.`,
+	Args:  cobra.ExactArgs(1),
+	RunE:  resumeCmdFunc,
+}
+
+func pauseCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+	containerName := args[0]
+
+	manager, err := workloads.NewManager(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create container manager: %v", err)
+	}
+
+	if err := manager.PauseWorkload(ctx, containerName); err != nil {
+		return fmt.Errorf("failed to pause container: %v", err)
+	}
+
+	printResult(containerName, "Container %s paused successfully", containerName)
+	return nil
+}
+
+func resumeCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+	containerName := args[0]
+
+	manager, err := workloads.NewManager(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create container manager: %v", err)
+	}
+
+	if err := manager.ResumeWorkload(ctx, containerName); err != nil {
+		return fmt.Errorf("failed to resume container: %v", err)
+	}
+
+	printResult(containerName, "Container %s resumed successfully", containerName)
+	return nil
+}
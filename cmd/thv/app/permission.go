@@ -0,0 +1,106 @@
+package app
+
+import (
+	"fmt"
+	"os"
+	"text/tabwriter"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/permissions"
+)
+
+var (
+	permissionValidateFormat string
+	permissionValidateStrict bool
+)
+
+func newPermissionCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "permission",
+		Short: "Manage permission profiles",
+		Long:  "The permission command provides subcommands to work with permission profiles.",
+	}
+
+	cmd.AddCommand(newPermissionValidateCommand())
+
+	return cmd
+}
+
+func newPermissionValidateCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "validate [flags] FILE",
+		Short: "Validate a permission profile",
+		Long: `Validate a permission profile file before using it with thv run --permission-profile.
+
+The profile is checked for:
+  - Schema errors, such as invalid JSON or unknown fields
+  - Invalid mount declarations and network rules
+  - Contradictory rules, such as different host paths mounted on the same container path
+  - Host paths which do not exist, resolved against the current directory
+  - Overly broad grants, such as mounting the home directory or the Docker socket,
+    or allowing all outbound network traffic
+
+The command exits with a non-zero status if errors are found, or with --strict
+if warnings are found, so it can be used to lint profiles in CI. Use --format json
+for machine-readable findings.`,
+		Args: cobra.ExactArgs(1),
+		RunE: permissionValidateCmdFunc,
+	}
+
+	cmd.Flags().StringVar(&permissionValidateFormat, "format", FormatText, "Output format (json or text)")
+	cmd.Flags().BoolVar(&permissionValidateStrict, "strict", false, "Fail if warnings are found")
+
+	return cmd
+}
+
+// permissionValidateOutput is the JSON output of thv permission validate
+type permissionValidateOutput struct {
+	File     string                `json:"file"`
+	Valid    bool                  `json:"valid"`
+	Findings []permissions.Finding `json:"findings"`
+}
+
+func permissionValidateCmdFunc(_ *cobra.Command, args []string) error {
+	path := args[0]
+
+	findings, err := permissions.ValidateFile(path)
+	if err != nil {
+		return err
+	}
+	if findings == nil {
+		findings = []permissions.Finding{}
+	}
+	valid := !permissions.HasErrors(findings) && (!permissionValidateStrict || len(findings) == 0)
+
+	if permissionValidateFormat == FormatJSON {
+		if err := printJSONOutput(permissionValidateOutput{File: path, Valid: valid, Findings: findings}); err != nil {
+			return err
+		}
+	} else if err := printFindings(path, findings); err != nil {
+		return err
+	}
+
+	if !valid {
+		return fmt.Errorf("permission profile %s is not valid", path)
+	}
+	return nil
+}
+
+// printFindings prints the findings of the validation of a permission profile as a table
+func printFindings(path string, findings []permissions.Finding) error {
+	if len(findings) == 0 {
+		printResult(path, "Permission profile %s is valid", path)
+		return nil
+	}
+
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 3, ' ', 0)
+	fmt.Fprintln(w, "SEVERITY\tCODE\tFIELD\tMESSAGE")
+	for _, finding := range findings {
+		fmt.Fprintf(w, "%s\t%s\t%s\t%s\n", finding.Severity, finding.Code, finding.Field, finding.Message)
+	}
+	if err := w.Flush(); err != nil {
+		return fmt.Errorf("failed to flush tabwriter: %v", err)
+	}
+	return nil
+}
@@ -0,0 +1,76 @@
+package app
+
+import (
+	"fmt"
+	"os"
+	"text/tabwriter"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+var portFormat string
+
+var portCmd = &cobra.Command{
+	Use:   "port [flags] SERVER_NAME",
+	Short: "Show the ports and endpoints of an MCP server",
+	Long: `Show where an MCP server can be reached: the address its proxy listens on, the
+URL clients connect to, the other endpoints of the proxy, and the ports of its
+container which are published on the host.
+
+For servers using the stdio transport, clients connect to the SSE endpoint and
+learn the URL of the messages endpoint from it. The port the proxy forwards
+requests to in the container is only shown for other transports.`,
+	Args: cobra.ExactArgs(1),
+	RunE: portCmdFunc,
+}
+
+func init() {
+	portCmd.Flags().StringVar(&portFormat, "format", FormatText, "Output format (json or text)")
+}
+
+func portCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+	name := args[0]
+
+	manager, err := workloads.NewManager(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create container manager: %v", err)
+	}
+
+	endpoints, err := manager.GetWorkloadEndpoints(ctx, name)
+	if err != nil {
+		return fmt.Errorf("failed to get endpoints of %s: %v", name, err)
+	}
+
+	if portFormat == FormatJSON {
+		return printJSONOutput(endpoints)
+	}
+	if IsQuiet() {
+		fmt.Println(endpoints.URL)
+		return nil
+	}
+	return printEndpoints(endpoints)
+}
+
+// printEndpoints prints the endpoints of a server as a list of names and values
+func printEndpoints(endpoints workloads.Endpoints) error {
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 3, ' ', 0)
+	fmt.Fprintf(w, "PROXY\t%s\n", endpoints.ProxyAddress)
+	fmt.Fprintf(w, "URL\t%s\n", endpoints.URL)
+	if endpoints.MessagesURL != "" {
+		fmt.Fprintf(w, "MESSAGES\t%s\n", endpoints.MessagesURL)
+	}
+	fmt.Fprintf(w, "HEALTH\t%s\n", endpoints.HealthURL)
+	if endpoints.MetricsURL != "" {
+		fmt.Fprintf(w, "METRICS\t%s\n", endpoints.MetricsURL)
+	}
+	if endpoints.TargetPort != 0 {
+		fmt.Fprintf(w, "TARGET PORT\t%d\n", endpoints.TargetPort)
+	}
+	for _, p := range endpoints.ContainerPorts {
+		fmt.Fprintf(w, "CONTAINER\t%d/%s -> %d\n", p.ContainerPort, p.Protocol, p.HostPort)
+	}
+	return w.Flush()
+}
@@ -8,6 +8,7 @@ import (
 	"os"
 	"os/signal"
 	"path/filepath"
+	"slices"
 	"strings"
 	"syscall"
 	"time"
@@ -17,9 +18,17 @@ import (
 
 	"github.com/stacklok/toolhive/pkg/auth"
 	"github.com/stacklok/toolhive/pkg/auth/oauth"
+	"github.com/stacklok/toolhive/pkg/container"
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/environment"
 	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/mcp"
 	"github.com/stacklok/toolhive/pkg/networking"
+	"github.com/stacklok/toolhive/pkg/ratelimit"
+	"github.com/stacklok/toolhive/pkg/secrets"
 	"github.com/stacklok/toolhive/pkg/transport"
+	"github.com/stacklok/toolhive/pkg/transport/proxy"
+	"github.com/stacklok/toolhive/pkg/transport/proxy/router"
 	"github.com/stacklok/toolhive/pkg/transport/proxy/transparent"
 	"github.com/stacklok/toolhive/pkg/transport/types"
 )
@@ -28,13 +37,21 @@ var proxyCmd = &cobra.Command{
 	Use:   "proxy [flags] SERVER_NAME",
 	Short: "Create a transparent proxy for an MCP server with authentication support",
 	Long: `Create a transparent HTTP proxy that forwards requests to an MCP server endpoint.
-This command starts a standalone proxy without launching a container, providing:
+This command starts a standalone proxy without launching a container, so the
+authentication and middlewares of ToolHive can be put in front of MCP servers
+which are not run with ToolHive. The target is either a URL with --target-uri,
+or an existing container with --target-container, whose MCP server is reached
+on the host port its container port is published on. It provides:
 
 • Transparent request forwarding to the target MCP server
 • Optional OAuth/OIDC authentication to remote MCP servers
 • Automatic authentication detection via WWW-Authenticate headers
 • OIDC-based access control for incoming proxy requests
 • Secure credential handling via files or environment variables
+• Static headers, such as API keys, added to the requests to the target server
+• CORS support for web clients of the origins given with --cors-origin
+• Per-client rate limiting of incoming requests with --rate-limit
+• Logging of the MCP requests going through the proxy with --log-requests
 
 AUTHENTICATION MODES:
 The proxy supports multiple authentication scenarios:
@@ -54,6 +71,9 @@ EXAMPLES:
   # Basic transparent proxy
   thv proxy my-server --target-uri http://localhost:8080
 
+  # Proxy to the MCP server of an existing container
+  thv proxy my-server --target-container my-mcp-container --target-port 8080
+
   # Proxy with OAuth authentication to remote server
   thv proxy my-server --target-uri https://api.example.com \
     --remote-auth --remote-auth-issuer https://auth.example.com \
@@ -65,6 +85,10 @@ EXAMPLES:
     --oidc-issuer https://auth.example.com \
     --oidc-audience my-audience
 
+  # Proxy to a remote server which needs an API key kept in the secrets provider
+  thv proxy my-server --target-uri https://api.example.com \
+    --upstream-header 'Authorization: Bearer ${secret:api-key}'
+
   # Auto-detect authentication requirements
   thv proxy my-server --target-uri https://protected-api.com \
     --remote-auth-client-id my-client-id`,
@@ -73,9 +97,17 @@ EXAMPLES:
 }
 
 var (
-	proxyHost      string
-	proxyPort      int
-	proxyTargetURI string
+	proxyHost            string
+	proxyPort            int
+	proxyTargetURI       string
+	proxyTargetContainer string
+	proxyTargetPort      int
+	proxyAuthToken       string
+	proxyUpstreamHeaders []string
+	proxyCORSOrigins     []string
+	proxyRateLimit       string
+	proxyLogRequests     bool
+	proxyLogPayloads     bool
 
 	// Remote server authentication flags
 	remoteAuthIssuer           string
@@ -105,17 +137,27 @@ const (
 )
 
 func init() {
-	proxyCmd.Flags().StringVar(&proxyHost, "host", transport.LocalhostIPv4, "Host for the HTTP proxy to listen on (IP or hostname)")
+	proxyCmd.Flags().StringVar(&proxyHost, "host", transport.LocalhostIPv4,
+		"Host for the HTTP proxy to listen on (IP or hostname); use 0.0.0.0 to accept connections from other machines")
 	proxyCmd.Flags().IntVar(&proxyPort, "port", 0, "Port for the HTTP proxy to listen on (host port)")
 	proxyCmd.Flags().StringVar(
 		&proxyTargetURI,
 		"target-uri",
 		"",
-		"URI for the target MCP server (e.g., http://localhost:8080) (required)",
+		"URI for the target MCP server (e.g., http://localhost:8080) (required unless --target-container is specified)",
 	)
+	proxyCmd.Flags().StringVar(&proxyTargetContainer, "target-container", "",
+		"Name or ID of an existing container running the target MCP server, instead of --target-uri")
+	proxyCmd.Flags().IntVar(&proxyTargetPort, "target-port", 0,
+		"Port of the MCP server in the container of --target-container (required if it publishes several ports)")
 
 	// Add OIDC validation flags
 	AddOIDCFlags(proxyCmd)
+	proxyCmd.Flags().StringVar(&proxyAuthToken, "auth-token", "", authTokenUsage)
+	proxyCmd.Flags().StringArrayVar(&proxyCORSOrigins, "cors-origin", nil, corsOriginUsage)
+	proxyCmd.Flags().StringVar(&proxyRateLimit, "rate-limit", "", rateLimitUsage)
+	proxyCmd.Flags().BoolVar(&proxyLogRequests, "log-requests", false, logRequestsUsage)
+	proxyCmd.Flags().BoolVar(&proxyLogPayloads, "log-payloads", false, logPayloadsUsage)
 
 	// Add remote server authentication flags
 	proxyCmd.Flags().BoolVar(&enableRemoteAuth, "remote-auth", false, "Enable OAuth authentication to remote MCP server")
@@ -135,11 +177,9 @@ func init() {
 		"Timeout for OAuth authentication flow (e.g., 30s, 1m, 2m30s)")
 	proxyCmd.Flags().IntVar(&remoteAuthCallbackPort, "remote-auth-callback-port", 8666,
 		"Port for OAuth callback server during remote authentication (default: 8666)")
-
-	// Mark target-uri as required
-	if err := proxyCmd.MarkFlagRequired("target-uri"); err != nil {
-		logger.Warnf("Warning: Failed to mark flag as required: %v", err)
-	}
+	proxyCmd.Flags().StringArrayVar(&proxyUpstreamHeaders, "upstream-header", nil,
+		"Header to send to the target MCP server, as 'NAME: VALUE'; ${VAR} and ${secret:name} in the value "+
+			"are resolved (can be specified multiple times)")
 }
 
 func proxyCmdFunc(cmd *cobra.Command, args []string) error {
@@ -153,10 +193,10 @@ func proxyCmdFunc(cmd *cobra.Command, args []string) error {
 		return fmt.Errorf("invalid host: %s", proxyHost)
 	}
 	proxyHost = validatedHost
+	warnIfNotLoopback(proxyHost)
 
-	err = validateProxyTargetURI(proxyTargetURI)
-	if err != nil {
-		return fmt.Errorf("invalid target URI: %w", err)
+	if err := resolveProxyTarget(ctx); err != nil {
+		return err
 	}
 
 	// Select a port for the HTTP proxy (host port)
@@ -176,8 +216,12 @@ func proxyCmdFunc(cmd *cobra.Command, args []string) error {
 		}
 	}
 
-	// Create middlewares slice for incoming request authentication
-	var middlewares []types.Middleware
+	// Create middlewares slice for incoming request authentication,
+	// compressing responses for clients which accept it
+	middlewares, err := proxyBaseMiddlewares()
+	if err != nil {
+		return err
+	}
 
 	// Get OIDC configuration if enabled (for protecting the proxy endpoint)
 	var oidcConfig *auth.TokenValidatorConfig
@@ -196,12 +240,12 @@ func proxyCmdFunc(cmd *cobra.Command, args []string) error {
 		}
 	}
 
-	// Get authentication middleware for incoming requests
-	authMiddleware, err := auth.GetAuthenticationMiddleware(ctx, oidcConfig, false)
+	// Get authentication middleware for incoming requests, followed by the rate limiting and logging middlewares
+	incomingMiddlewares, err := proxyIncomingMiddlewares(ctx, oidcConfig)
 	if err != nil {
 		return fmt.Errorf("failed to create authentication middleware: %v", err)
 	}
-	middlewares = append(middlewares, authMiddleware)
+	middlewares = append(middlewares, incomingMiddlewares...)
 
 	// Add OAuth token injection middleware for outgoing requests if we have an access token
 	if tokenSource != nil {
@@ -209,13 +253,22 @@ func proxyCmdFunc(cmd *cobra.Command, args []string) error {
 		middlewares = append(middlewares, tokenMiddleware)
 	}
 
+	// Add the headers of --upstream-header to the requests forwarded to the server
+	if len(proxyUpstreamHeaders) > 0 {
+		headers, err := upstreamHeaders(ctx)
+		if err != nil {
+			return err
+		}
+		middlewares = append(middlewares, proxy.UpstreamHeaderMiddleware(headers))
+	}
+
 	// Create the transparent proxy
 	logger.Infof("Setting up transparent proxy to forward from host port %d to %s",
 		port, proxyTargetURI)
 
 	// Create the transparent proxy with middlewares
-	proxy := transparent.NewTransparentProxy(proxyHost, port, serverName, proxyTargetURI, nil, middlewares...)
-	if err := proxy.Start(ctx); err != nil {
+	transparentProxy := transparent.NewTransparentProxy(proxyHost, port, serverName, proxyTargetURI, nil, "", nil, middlewares...)
+	if err := transparentProxy.Start(ctx); err != nil {
 		return fmt.Errorf("failed to start proxy: %v", err)
 	}
 
@@ -232,7 +285,7 @@ func proxyCmdFunc(cmd *cobra.Command, args []string) error {
 	logger.Infof("Received signal %s, stopping proxy...", sig)
 
 	// Stop the proxy
-	if err := proxy.Stop(ctx); err != nil {
+	if err := transparentProxy.Stop(ctx); err != nil {
 		logger.Warnf("Warning: Failed to stop proxy: %v", err)
 	}
 
@@ -240,6 +293,83 @@ func proxyCmdFunc(cmd *cobra.Command, args []string) error {
 	return nil
 }
 
+// proxyBaseMiddlewares returns the middlewares applied to all the requests to the proxy before they are
+// authenticated: the middleware removing the gateway token, the CORS middleware for the origins of
+// --cors-origin, and the compression middleware
+func proxyBaseMiddlewares() ([]types.Middleware, error) {
+	// Without the gateway token, the requests forwarded by thv gateway are not told apart from local requests
+	gatewayToken, err := router.LoadToken()
+	if err != nil {
+		logger.Warnf("Warning: Failed to load the gateway token: %v", err)
+	}
+	middlewares := []types.Middleware{router.TokenMiddleware(gatewayToken)}
+
+	if len(proxyCORSOrigins) > 0 {
+		for _, origin := range proxyCORSOrigins {
+			if err := proxy.ValidateCORSOrigin(origin); err != nil {
+				return nil, err
+			}
+		}
+		middlewares = append(middlewares, proxy.CORSMiddleware(proxyCORSOrigins))
+	}
+	return append(middlewares, proxy.CompressionMiddleware), nil
+}
+
+// proxyIncomingMiddlewares returns the middleware authenticating the requests to the proxy, which checks
+// the static token of --auth-token if there is one, the middleware limiting them with --rate-limit,
+// and the middlewares logging them with --log-requests or --log-payloads
+func proxyIncomingMiddlewares(ctx context.Context, oidcConfig *auth.TokenValidatorConfig) ([]types.Middleware, error) {
+	authMiddleware, err := proxyAuthMiddleware(ctx, oidcConfig)
+	if err != nil {
+		return nil, err
+	}
+	middlewares := []types.Middleware{authMiddleware}
+
+	if proxyRateLimit != "" {
+		limit, err := ratelimit.ParseLimit(proxyRateLimit)
+		if err != nil {
+			return nil, err
+		}
+		logger.Infof("Rate limiting requests to %s per client", proxyRateLimit)
+		middlewares = append(middlewares, ratelimit.Middleware(limit, oidcConfig != nil))
+	}
+
+	if proxyLogRequests || proxyLogPayloads {
+		middlewares = append(middlewares, mcp.ParsingMiddleware, mcp.LoggingMiddleware(proxyLogPayloads))
+	}
+	return middlewares, nil
+}
+
+// proxyAuthMiddleware returns the middleware authenticating the requests to the proxy, which checks
+// the static token of --auth-token if there is one
+func proxyAuthMiddleware(ctx context.Context, oidcConfig *auth.TokenValidatorConfig) (types.Middleware, error) {
+	tokenValue := authTokenFromFlag(proxyAuthToken)
+	if tokenValue == "" {
+		return auth.GetAuthenticationMiddleware(ctx, oidcConfig, false)
+	}
+	if oidcConfig != nil {
+		return nil, fmt.Errorf("a static auth token cannot be used with OIDC validation")
+	}
+	logger.Info("Static token authentication enabled")
+	return auth.NewStaticTokenMiddleware(tokenValue)
+}
+
+// upstreamHeaders returns the headers of --upstream-header, resolving the references to environment variables
+// and secrets in their values. The secrets provider is only needed when a value references a secret.
+func upstreamHeaders(ctx context.Context) (http.Header, error) {
+	var manager secrets.Provider
+	for _, value := range proxyUpstreamHeaders {
+		if environment.HasSecretReferences(value) {
+			var err error
+			if manager, err = getSecretsManager(); err != nil {
+				return nil, err
+			}
+			break
+		}
+	}
+	return proxy.ParseUpstreamHeaders(ctx, proxyUpstreamHeaders, manager)
+}
+
 // AuthInfo contains authentication information extracted from WWW-Authenticate header
 type AuthInfo struct {
 	Realm string
@@ -512,6 +642,81 @@ func createTokenInjectionMiddleware(tokenSource *oauth2.TokenSource) types.Middl
 	}
 }
 
+// resolveProxyTarget checks that the proxy has a single target, and sets the target URI
+// if the target is an existing container
+func resolveProxyTarget(ctx context.Context) error {
+	if (proxyTargetURI == "") == (proxyTargetContainer == "") {
+		return fmt.Errorf("exactly one of --target-uri and --target-container must be specified")
+	}
+	if proxyTargetContainer != "" {
+		targetURI, err := containerTargetURI(ctx, proxyTargetContainer, proxyTargetPort)
+		if err != nil {
+			return err
+		}
+		proxyTargetURI = targetURI
+	} else if proxyTargetPort != 0 {
+		return fmt.Errorf("--target-port is only supported with --target-container")
+	}
+
+	if err := validateProxyTargetURI(proxyTargetURI); err != nil {
+		return fmt.Errorf("invalid target URI: %w", err)
+	}
+	return nil
+}
+
+// containerTargetURI returns the URI of the MCP server in an existing container, which is reached
+// on the host port its container port is published on
+func containerTargetURI(ctx context.Context, containerName string, containerPort int) (string, error) {
+	containerRuntime, err := container.NewFactory().Create(ctx)
+	if err != nil {
+		return "", fmt.Errorf("failed to create container runtime: %v", err)
+	}
+	running, err := containerRuntime.IsWorkloadRunning(ctx, containerName)
+	if err != nil {
+		return "", fmt.Errorf("failed to find container %s: %v", containerName, err)
+	}
+	if !running {
+		return "", fmt.Errorf("container %s is not running", containerName)
+	}
+	info, err := containerRuntime.GetWorkloadInfo(ctx, containerName)
+	if err != nil {
+		return "", fmt.Errorf("failed to inspect container %s: %v", containerName, err)
+	}
+
+	hostPort, err := publishedPort(info.Ports, containerPort)
+	if err != nil {
+		return "", fmt.Errorf("container %s: %w", containerName, err)
+	}
+	logger.Infof("Forwarding to port %d of container %s", hostPort, containerName)
+	return fmt.Sprintf("http://%s:%d", transport.LocalhostIPv4, hostPort), nil
+}
+
+// publishedPort returns the host port a TCP port of a container is published on. The container port
+// may be zero if the container publishes a single TCP port.
+func publishedPort(ports []rt.PortMapping, containerPort int) (int, error) {
+	var hostPorts []int
+	for _, mapping := range ports {
+		if mapping.HostPort == 0 || (mapping.Protocol != "" && mapping.Protocol != "tcp") {
+			continue
+		}
+		// Ports published on both IPv4 and IPv6 are listed twice
+		if (containerPort == 0 || mapping.ContainerPort == containerPort) && !slices.Contains(hostPorts, mapping.HostPort) {
+			hostPorts = append(hostPorts, mapping.HostPort)
+		}
+	}
+
+	switch {
+	case len(hostPorts) == 1:
+		return hostPorts[0], nil
+	case len(hostPorts) > 1:
+		return 0, fmt.Errorf("several ports are published on the host, select one with --target-port")
+	case containerPort != 0:
+		return 0, fmt.Errorf("port %d is not published on the host", containerPort)
+	default:
+		return 0, fmt.Errorf("no port is published on the host")
+	}
+}
+
 // validateProxyTargetURI validates that the target URI for the proxy is valid and does not contain a path
 func validateProxyTargetURI(targetURI string) error {
 	// Parse the target URI
@@ -0,0 +1,85 @@
+package app
+
+import (
+	"fmt"
+	"net/http"
+	"os"
+
+	"github.com/spf13/cobra"
+	"github.com/spf13/viper"
+
+	"github.com/stacklok/toolhive/pkg/auth"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/transport/bridge"
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+var proxyStdioAuthToken string
+
+var proxyStdioCmd = &cobra.Command{
+	Use:   "stdio [flags] SERVER_NAME",
+	Short: "Serve an MCP server as a stdio MCP server, for clients which only launch stdio servers",
+	Long: `Act as a stdio MCP server on the standard input and output of the command, forwarding
+the messages to the proxy of an MCP server managed by ToolHive, over SSE or
+streamable HTTP depending on its transport. This lets clients which can only
+launch stdio servers use MCP servers run by ToolHive, for example:
+
+	{
+	  "mcpServers": {
+	    "fetch": {
+	      "command": "thv",
+	      "args": ["proxy", "stdio", "fetch"]
+	    }
+	  }
+	}
+
+The logs of the command are written to the standard error, since the standard
+output carries the messages of the server. If the proxy of the server requires
+an auth token, it is given with --auth-token.`,
+	Args: cobra.ExactArgs(1),
+	RunE: proxyStdioCmdFunc,
+}
+
+func init() {
+	proxyStdioCmd.Flags().StringVar(&proxyStdioAuthToken, "auth-token", "",
+		"Static token the proxy of the server requires, or file:PATH to read it from a file; "+
+			"can also be set with "+auth.StaticTokenEnvVar)
+	proxyCmd.AddCommand(proxyStdioCmd)
+}
+
+func proxyStdioCmdFunc(cmd *cobra.Command, args []string) error {
+	// The standard output carries the MCP messages, so the logs are written to the standard error
+	viper.Set("quiet", true)
+	logger.Initialize()
+
+	ctx := cmd.Context()
+	name := args[0]
+
+	manager, err := workloads.NewManager(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create workload manager: %v", err)
+	}
+	endpoints, err := manager.GetWorkloadEndpoints(ctx, name)
+	if err != nil {
+		return fmt.Errorf("failed to get endpoints of %s: %v", name, err)
+	}
+	if endpoints.URL == "" {
+		return fmt.Errorf("MCP server %s has no proxy", name)
+	}
+
+	headers := http.Header{}
+	if value := authTokenFromFlag(proxyStdioAuthToken); value != "" {
+		token, err := auth.ResolveStaticToken(value)
+		if err != nil {
+			return err
+		}
+		headers.Set("Authorization", "Bearer "+token)
+	}
+
+	b, err := bridge.New(endpoints.URL, endpoints.TransportType, headers)
+	if err != nil {
+		return err
+	}
+	logger.Infof("Bridging stdio to %s", endpoints.URL)
+	return b.Run(ctx, os.Stdin, os.Stdout)
+}
@@ -0,0 +1,215 @@
+package app
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"os"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/docker/go-units"
+	"github.com/spf13/cobra"
+	"golang.org/x/term"
+
+	"github.com/stacklok/toolhive/pkg/container/images"
+	"github.com/stacklok/toolhive/pkg/runner/retriever"
+)
+
+var pullCmd = &cobra.Command{
+	Use:   "pull [flags] SERVER_OR_IMAGE...",
+	Short: "Pull the images of MCP servers",
+	Long: `Pull the images of MCP servers ahead of running them.
+
+Each argument is either the name of a server in the registry, optionally with a
+version selector (e.g. fetch@stable), or an image reference. The images are
+pulled concurrently, with at most --concurrency pulls at the same time, and the
+progress of each pull is shown while they run.
+
+Use this command to warm the image cache of CI runners or workshop machines,
+so that thv run starts the servers without waiting for their images. All images
+are pulled even if some of them fail, and the command fails if any pull failed.`,
+	Args: cobra.MinimumNArgs(1),
+	RunE: pullCmdFunc,
+}
+
+var pullConcurrency int
+
+func init() {
+	pullCmd.Flags().IntVar(&pullConcurrency, "concurrency", images.DefaultPullConcurrency,
+		"Maximum number of images pulled at the same time")
+}
+
+func pullCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+
+	if pullConcurrency < 1 {
+		return fmt.Errorf("--concurrency must be at least 1")
+	}
+
+	imageRefs := make([]string, 0, len(args))
+	for _, serverOrImage := range args {
+		image, _, err := retriever.ResolveImage(ctx, serverOrImage)
+		if err != nil {
+			return fmt.Errorf("failed to resolve %s: %v", serverOrImage, err)
+		}
+		imageRefs = append(imageRefs, image)
+	}
+
+	failures := pullImages(ctx, imageRefs, pullConcurrency)
+	if len(failures) > 0 {
+		return fmt.Errorf("failed to pull %d of %d images", len(failures), len(imageRefs))
+	}
+	return nil
+}
+
+// pullImages pulls the images concurrently while showing their progress, and returns the errors of the
+// pulls which failed by image
+func pullImages(ctx context.Context, imageRefs []string, concurrency int) map[string]error {
+	imageRefs = uniqueImages(imageRefs)
+	progress := newPullProgress(os.Stderr, imageRefs)
+
+	failures := images.PullImages(ctx, images.NewImageManager(ctx), imageRefs, concurrency, progress.update)
+	progress.finish(failures)
+	return failures
+}
+
+// uniqueImages returns the images without duplicates, in their original order
+func uniqueImages(imageRefs []string) []string {
+	seen := make(map[string]bool, len(imageRefs))
+	unique := make([]string, 0, len(imageRefs))
+	for _, image := range imageRefs {
+		if !seen[image] {
+			seen[image] = true
+			unique = append(unique, image)
+		}
+	}
+	return unique
+}
+
+const (
+	// pullBarWidth is the width of the progress bars of image pulls
+	pullBarWidth = 30
+	// pullRedrawInterval is the minimum interval between redraws of the progress bars
+	pullRedrawInterval = 100 * time.Millisecond
+)
+
+// pullProgress shows the progress of concurrent image pulls. On a terminal a progress bar is drawn for
+// each image and redrawn in place; otherwise the outcome of each pull is printed when they are done.
+type pullProgress struct {
+	mutex    sync.Mutex
+	out      io.Writer
+	terminal bool
+	images   []string
+	width    int
+	current  map[string]int64
+	total    map[string]int64
+	drawn    bool
+	drawnAt  time.Time
+}
+
+func newPullProgress(out *os.File, imageRefs []string) *pullProgress {
+	width := 0
+	for _, image := range imageRefs {
+		width = max(width, len(image))
+	}
+	p := &pullProgress{
+		out:      out,
+		terminal: !IsQuiet() && term.IsTerminal(int(out.Fd())),
+		images:   imageRefs,
+		width:    width,
+		current:  make(map[string]int64),
+		total:    make(map[string]int64),
+	}
+	if p.terminal {
+		p.draw(nil)
+	}
+	return p
+}
+
+// update records the progress of the pull of an image
+func (p *pullProgress) update(image string, current, total int64) {
+	p.mutex.Lock()
+	defer p.mutex.Unlock()
+
+	p.current[image] = current
+	p.total[image] = total
+	if p.terminal && time.Since(p.drawnAt) >= pullRedrawInterval {
+		p.draw(nil)
+	}
+}
+
+// finish shows the outcome of the pulls
+func (p *pullProgress) finish(failures map[string]error) {
+	p.mutex.Lock()
+	defer p.mutex.Unlock()
+
+	if p.terminal {
+		p.draw(failures)
+	} else if !IsQuiet() {
+		for _, image := range p.images {
+			fmt.Fprintf(p.out, "%s: %s\n", image, p.status(image, failures))
+		}
+	}
+
+	failed := make([]string, 0, len(failures))
+	for image := range failures {
+		failed = append(failed, image)
+	}
+	sort.Strings(failed)
+	for _, image := range failed {
+		fmt.Fprintf(p.out, "Error: failed to pull %s: %v\n", image, failures[image])
+	}
+}
+
+// draw redraws the progress bars in place. The pulls are still running if failures is nil.
+func (p *pullProgress) draw(failures map[string]error) {
+	if p.drawn {
+		// Move the cursor back to the first progress bar
+		fmt.Fprintf(p.out, "\033[%dA", len(p.images))
+	}
+	for _, image := range p.images {
+		fmt.Fprintf(p.out, "\033[2K%s\n", p.line(image, failures))
+	}
+	p.drawn = true
+	p.drawnAt = time.Now()
+}
+
+// line returns the progress bar of an image
+func (p *pullProgress) line(image string, failures map[string]error) string {
+	current, total := p.current[image], p.total[image]
+	var filled, percent int64
+	if total > 0 {
+		filled = min(current*pullBarWidth/total, pullBarWidth)
+		percent = min(current*100/total, 100)
+	}
+
+	var status string
+	switch {
+	case failures != nil:
+		status = p.status(image, failures)
+		if _, failed := failures[image]; !failed {
+			filled = pullBarWidth
+		}
+	case total == 0:
+		status = "waiting"
+	default:
+		status = fmt.Sprintf("%3d%% %s / %s", percent, units.HumanSize(float64(current)), units.HumanSize(float64(total)))
+	}
+
+	bar := strings.Repeat("=", int(filled)) + strings.Repeat(" ", pullBarWidth-int(filled))
+	return fmt.Sprintf("%-*s [%s] %s", p.width, image, bar, status)
+}
+
+// status returns the outcome of the pull of an image
+func (p *pullProgress) status(image string, failures map[string]error) string {
+	if _, failed := failures[image]; failed {
+		return "failed"
+	}
+	if total := p.total[image]; total > 0 {
+		return fmt.Sprintf("pulled (%s)", units.HumanSize(float64(total)))
+	}
+	return "up to date"
+}
@@ -4,6 +4,7 @@ import (
 	"encoding/json"
 	"fmt"
 	"os"
+	"path/filepath"
 	"sort"
 	"strings"
 	"text/tabwriter"
@@ -35,8 +36,49 @@ var registryInfoCmd = &cobra.Command{
 	RunE:  registryInfoCmdFunc,
 }
 
+var registryPushCmd = &cobra.Command{
+	Use:   "push [flags] FILE REFERENCE",
+	Short: "Publish a registry file as an OCI artifact",
+	Long: `Publish a registry file as an OCI artifact, so the registry can be distributed
+through an OCI registry, like images.
+
+The reference must start with oci://. The artifact is pushed with the same credentials
+as images (e.g. those stored by docker login). Configure ToolHive to use the published
+registry with thv config set-registry-url and the same reference.
+
+The detached signature of the registry file, created with thv registry sign, is
+published in the same artifact. It is read from the file given with --signature,
+or from the registry file path with the .sig suffix if it exists.
+
+Example:
+  thv registry push registry.json oci://ghcr.io/example/registry:latest`,
+	Args: cobra.ExactArgs(2),
+	RunE: registryPushCmdFunc,
+}
+
+var registrySignCmd = &cobra.Command{
+	Use:   "sign [flags] FILE",
+	Short: "Sign a registry file",
+	Long: `Sign a registry file with a private key, writing its detached signature to the
+registry file path with the .sig suffix, or to the file given with --output.
+
+The key must be a PEM encoded ECDSA, Ed25519 or RSA private key which is not encrypted.
+Signatures are compatible with cosign sign-blob. Publish the signature next to a
+registry served over HTTP (e.g. https://example.com/registry.json.sig), or with
+thv registry push, and configure the public key with thv config set-registry-url
+--trusted-key so ToolHive refuses registries which were tampered with.
+
+Example:
+  thv registry sign --key registry-key.pem registry.json`,
+	Args: cobra.ExactArgs(1),
+	RunE: registrySignCmdFunc,
+}
+
 var (
-	registryFormat string
+	registryFormat    string
+	registrySignature string
+	registrySignKey   string
+	registrySignOut   string
 )
 
 func init() {
@@ -46,10 +88,18 @@ func init() {
 	// Add subcommands to registry command
 	registryCmd.AddCommand(registryListCmd)
 	registryCmd.AddCommand(registryInfoCmd)
+	registryCmd.AddCommand(registryPushCmd)
+	registryCmd.AddCommand(registrySignCmd)
 
 	// Add flags for list and info commands
 	registryListCmd.Flags().StringVar(&registryFormat, "format", FormatText, "Output format (json or text)")
 	registryInfoCmd.Flags().StringVar(&registryFormat, "format", FormatText, "Output format (json or text)")
+	registryPushCmd.Flags().StringVar(&registrySignature, "signature", "",
+		"Detached signature of the registry file to publish with it (default FILE.sig, if it exists)")
+	registrySignCmd.Flags().StringVar(&registrySignKey, "key", "", "Private key to sign the registry file with (required)")
+	registrySignCmd.Flags().StringVarP(&registrySignOut, "output", "o", "",
+		"File to write the signature to (default FILE.sig)")
+	_ = registrySignCmd.MarkFlagRequired("key")
 }
 
 func registryListCmdFunc(_ *cobra.Command, _ []string) error {
@@ -100,6 +150,79 @@ func registryInfoCmdFunc(_ *cobra.Command, args []string) error {
 	}
 }
 
+func registryPushCmdFunc(cmd *cobra.Command, args []string) error {
+	file, reference := args[0], args[1]
+	if !registry.IsOCIReference(reference) {
+		return fmt.Errorf("reference must start with %s", registry.OCIScheme)
+	}
+
+	// #nosec G304 -- The file is given by the user
+	data, err := os.ReadFile(file)
+	if err != nil {
+		return fmt.Errorf("failed to read registry file: %v", err)
+	}
+
+	signature, err := readRegistrySignature(file, registrySignature)
+	if err != nil {
+		return err
+	}
+
+	digest, err := registry.PushOCIArtifact(cmd.Context(), reference, data, signature)
+	if err != nil {
+		return err
+	}
+
+	printResult(digest, "Pushed registry %s to %s (%s)", file, reference, digest)
+	return nil
+}
+
+// readRegistrySignature reads the detached signature of a registry file from the given path,
+// or from the registry file path with the signature suffix if it exists
+func readRegistrySignature(file, signaturePath string) ([]byte, error) {
+	if signaturePath == "" {
+		signaturePath = file + registry.SignatureSuffix
+		if _, err := os.Stat(signaturePath); os.IsNotExist(err) {
+			return nil, nil
+		}
+	}
+
+	signature, err := os.ReadFile(filepath.Clean(signaturePath))
+	if err != nil {
+		return nil, fmt.Errorf("failed to read registry signature: %v", err)
+	}
+	return signature, nil
+}
+
+func registrySignCmdFunc(_ *cobra.Command, args []string) error {
+	file := args[0]
+
+	// #nosec G304 -- The file is given by the user
+	data, err := os.ReadFile(file)
+	if err != nil {
+		return fmt.Errorf("failed to read registry file: %v", err)
+	}
+	key, err := os.ReadFile(filepath.Clean(registrySignKey))
+	if err != nil {
+		return fmt.Errorf("failed to read private key: %v", err)
+	}
+
+	signature, err := registry.Sign(data, key)
+	if err != nil {
+		return err
+	}
+
+	output := registrySignOut
+	if output == "" {
+		output = file + registry.SignatureSuffix
+	}
+	if err := os.WriteFile(output, append(signature, '\n'), 0600); err != nil {
+		return fmt.Errorf("failed to write signature: %v", err)
+	}
+
+	printResult(output, "Signed registry %s, signature written to %s", file, output)
+	return nil
+}
+
 // printJSONServers prints servers in JSON format
 func printJSONServers(servers []*registry.ImageMetadata) error {
 	// Marshal to JSON
@@ -170,7 +293,8 @@ func printTextServerInfo(name string, server *registry.ImageMetadata) {
 	fmt.Printf("Tier: %s\n", server.Tier)
 	fmt.Printf("Status: %s\n", server.Status)
 	fmt.Printf("Transport: %s\n", server.Transport)
-	if (server.Transport == "sse" || server.Transport == "streamable-http") && server.TargetPort > 0 {
+	isHTTPServer := server.Transport == "sse" || server.Transport == "streamable-http" || server.Transport == "websocket"
+	if isHTTPServer && server.TargetPort > 0 {
 		fmt.Printf("Target Port: %d\n", server.TargetPort)
 	}
 	fmt.Printf("Repository URL: %s\n", server.RepositoryURL)
@@ -0,0 +1,51 @@
+package app
+
+import (
+	"fmt"
+	"os"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/launchd"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+var renameCmd = &cobra.Command{
+	Use:   "rename [container-name] [new-name]",
+	Short: "Rename an MCP server",
+	Long: `Rename an MCP server managed by ToolHive.
+
+The container of the server is recreated under the new name with the same
+configuration and port, so the endpoint of the server does not change. Its saved
+state, upgrade history and proxy logs are moved to the new name. A running server
+is restarted, which updates the client configurations written by ToolHive; a
+stopped server keeps its configuration and is started again with thv restart.`,
+	Args: cobra.ExactArgs(2),
+	RunE: renameCmdFunc,
+}
+
+func renameCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+	oldName, newName := args[0], args[1]
+
+	manager, err := workloads.NewManager(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create container manager: %v", err)
+	}
+
+	if err := manager.RenameWorkload(ctx, oldName, newName); err != nil {
+		return fmt.Errorf("failed to rename container: %v", err)
+	}
+
+	// A launchd agent runs the server by name, so it must be installed again under the new name
+	if path, err := launchd.PlistPath(launchd.ServerLabel(oldName)); err == nil {
+		if _, err := os.Stat(path); err == nil {
+			logger.Warnf("The launchd agent of %s still runs the server under its old name, "+
+				"run thv launchd uninstall %s and thv launchd install %s to update it", oldName, oldName, newName)
+		}
+	}
+
+	printResult(newName, "Container %s renamed to %s", oldName, newName)
+	return nil
+}
@@ -11,19 +11,53 @@ import (
 )
 
 var (
-	restartAll bool
+	restartAll        bool
+	restartForeground bool
 )
 
 var restartCmd = &cobra.Command{
 	Use:   "restart [container-name]",
 	Short: "Restart a tooling server",
-	Long:  `Restart a running tooling server managed by ToolHive. If the server is not running, it will be started.`,
-	Args:  cobra.RangeArgs(0, 1),
-	RunE:  restartCmdFunc,
+	Long: `Restart a tooling server managed by ToolHive. Its proxy and container are stopped, and started
+again from the configuration saved when it was run: its image, arguments, transport, ports,
+permission profile, environment variables and secrets. If the server is not running, it will
+be started.
+
+For servers using the stdio transport, the sessions of connected clients are kept:
+the proxy restores their session IDs and replays their initialize handshake to the
+restarted server, so clients do not have to re-initialize. Requests which were in
+progress when the server was restarted are answered with an error.
+
+With --foreground, the server is run in the foreground from its saved configuration
+and the command blocks until the server is stopped. This is used by service managers
+such as launchd (see thv launchd) to supervise a server.
+
+The foreground server also supports systemd socket activation: if systemd passes
+a listening socket, the proxy serves on it instead of opening its own, so the server
+and its container are only started when the first client connects. The socket must
+listen on the port of the server, e.g. for a server run with --port 8080:
+
+  # ~/.config/systemd/user/thv-fetch.socket
+  [Socket]
+  ListenStream=127.0.0.1:8080
+
+  [Install]
+  WantedBy=sockets.target
+
+  # ~/.config/systemd/user/thv-fetch.service
+  [Service]
+  ExecStart=/usr/local/bin/thv restart --foreground fetch
+  Environment=TOOLHIVE_DETACHED=true
+
+  $ systemctl --user enable --now thv-fetch.socket`,
+	Args: cobra.RangeArgs(0, 1),
+	RunE: restartCmdFunc,
 }
 
 func init() {
 	restartCmd.Flags().BoolVarP(&restartAll, "all", "a", false, "Restart all MCP servers")
+	restartCmd.Flags().BoolVarP(&restartForeground, "foreground", "f", false,
+		"Run the server in the foreground and block until it is stopped")
 }
 
 func restartCmdFunc(cmd *cobra.Command, args []string) error {
@@ -44,22 +78,22 @@ func restartCmdFunc(cmd *cobra.Command, args []string) error {
 	}
 
 	if restartAll {
+		if restartForeground {
+			return fmt.Errorf("cannot use --foreground with --all")
+		}
 		return restartAllContainers(ctx, manager)
 	}
 
 	// Restart single container
 	containerName := args[0]
-	restartGroup, err := manager.RestartWorkloads(ctx, []string{containerName})
-	if err != nil {
-		return err
+	if restartForeground {
+		return manager.RestartWorkloadForeground(ctx, containerName)
 	}
-
-	// Wait for the restart group to complete
-	if err := restartGroup.Wait(); err != nil {
+	if err := recreateWorkload(ctx, manager, containerName); err != nil {
 		return fmt.Errorf("failed to restart container %s: %v", containerName, err)
 	}
 
-	fmt.Printf("Container %s restarted successfully\n", containerName)
+	printResult(containerName, "Container %s restarted successfully", containerName)
 	return nil
 }
 
@@ -71,7 +105,7 @@ func restartAllContainers(ctx context.Context, manager workloads.Manager) error
 	}
 
 	if len(containers) == 0 {
-		fmt.Println("No MCP servers found to restart")
+		printInfo("No MCP servers found to restart")
 		return nil
 	}
 
@@ -79,46 +113,60 @@ func restartAllContainers(ctx context.Context, manager workloads.Manager) error
 	var failedCount int
 	var errors []string
 
-	fmt.Printf("Restarting %d MCP server(s)...\n", len(containers))
+	printInfo("Restarting %d MCP server(s)...", len(containers))
+
+	// Stop the running servers first, which are otherwise left as they are
+	var names []string
+	for _, container := range containers {
+		names = append(names, container.Name)
+	}
+	stopGroup, err := manager.StopWorkloads(ctx, names, nil)
+	if err != nil {
+		return fmt.Errorf("failed to stop containers: %v", err)
+	}
+	if err := stopGroup.Wait(); err != nil {
+		return fmt.Errorf("failed to stop containers: %v", err)
+	}
 
 	var restartRequests []*errgroup.Group
+	var restartNames []string
 	// First, trigger the restarts concurrently.
 	for _, container := range containers {
 		containerName := container.Name
-		fmt.Printf("Restarting %s...", containerName)
 		restart, err := manager.RestartWorkloads(ctx, []string{containerName})
 		if err != nil {
-			fmt.Printf(" failed: %v\n", err)
+			printInfo("Restarting %s... failed: %v", containerName, err)
 			failedCount++
 			errors = append(errors, fmt.Sprintf("%s: %v", containerName, err))
 		} else {
 			// If it didn't fail during the synchronous part of the operation,
 			// append to the list of restart requests in flight.
 			restartRequests = append(restartRequests, restart)
+			restartNames = append(restartNames, containerName)
 		}
 	}
 
 	// Wait for all restarts to complete.
-	for _, restart := range restartRequests {
+	for i, restart := range restartRequests {
+		containerName := restartNames[i]
 		err = restart.Wait()
 		if err != nil {
-			fmt.Printf(" failed: %v\n", err)
+			printInfo("Restarting %s... failed: %v", containerName, err)
 			failedCount++
-			// Unfortunately we don't have the container name here, so we just log a generic error.
-			errors = append(errors, fmt.Sprintf("Error restarting container: %v", err))
+			errors = append(errors, fmt.Sprintf("%s: %v", containerName, err))
 		} else {
-			fmt.Printf(" success\n")
+			printResult(containerName, "Restarting %s... success", containerName)
 			restartedCount++
 		}
 	}
 
 	// Print summary
-	fmt.Printf("\nRestart summary: %d succeeded, %d failed\n", restartedCount, failedCount)
+	printInfo("\nRestart summary: %d succeeded, %d failed", restartedCount, failedCount)
 
 	if failedCount > 0 {
-		fmt.Println("\nFailed restarts:")
+		printInfo("\nFailed restarts:")
 		for _, errMsg := range errors {
-			fmt.Printf("  - %s\n", errMsg)
+			printInfo("  - %s", errMsg)
 		}
 		return fmt.Errorf("%d container(s) failed to restart", failedCount)
 	}
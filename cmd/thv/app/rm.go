@@ -1,6 +1,7 @@
 package app
 
 import (
+	"context"
 	"fmt"
 
 	"github.com/spf13/cobra"
@@ -11,16 +12,52 @@ import (
 var rmCmd = &cobra.Command{
 	Use:   "rm [container-name]",
 	Short: "Remove an MCP server",
-	Long:  `Remove an MCP server managed by ToolHive.`,
-	Args:  cobra.ExactArgs(1),
-	RunE:  rmCmdFunc,
+	Long: `Remove an MCP server managed by ToolHive.
+
+Use --all to remove all servers, or --filter to remove the servers matching the
+filters, e.g. --filter name="github-*" or --filter status=stopped. The filters
+are the same as for thv list. Running servers are stopped before they are removed.
+Only containers managed by ToolHive are removed. In an interactive session, the
+servers to remove are listed and must be confirmed, unless --force is given.
+The servers are removed concurrently, and a summary of the servers which were
+removed and of those which failed to be removed is printed; the command fails if
+any of them failed.`,
+	Args: validateRmArgs,
+	RunE: rmCmdFunc,
+}
+
+var (
+	rmAll     bool
+	rmFilters []string
+	rmForce   bool
+)
+
+func init() {
+	rmCmd.Flags().BoolVar(&rmAll, "all", false, "Remove all MCP servers")
+	rmCmd.Flags().StringArrayVar(&rmFilters, "filter", nil,
+		"Remove the servers matching the filter (format: key=value, can be repeated)")
+	rmCmd.Flags().BoolVarP(&rmForce, "force", "f", false, "Do not ask for confirmation when removing several servers")
+}
+
+// validateRmArgs validates the arguments for the rm command
+func validateRmArgs(cmd *cobra.Command, args []string) error {
+	all, _ := cmd.Flags().GetBool("all")
+	filters, _ := cmd.Flags().GetStringArray("filter")
+
+	if all || len(filters) > 0 {
+		if len(args) > 0 {
+			return fmt.Errorf("no arguments should be provided when --all or --filter flag is set")
+		}
+		return nil
+	}
+	if len(args) != 1 {
+		return fmt.Errorf("exactly one container name must be provided")
+	}
+	return nil
 }
 
-//nolint:gocyclo // This function is complex but manageable
 func rmCmdFunc(cmd *cobra.Command, args []string) error {
 	ctx := cmd.Context()
-	// Get container name
-	containerName := args[0]
 
 	// Create container manager.
 	manager, err := workloads.NewManager(ctx)
@@ -28,6 +65,13 @@ func rmCmdFunc(cmd *cobra.Command, args []string) error {
 		return fmt.Errorf("failed to create container manager: %v", err)
 	}
 
+	if rmAll || len(rmFilters) > 0 {
+		return rmSelectedWorkloads(ctx, manager)
+	}
+
+	// Get container name
+	containerName := args[0]
+
 	// Delete container.
 	group, err := manager.DeleteWorkloads(ctx, []string{containerName})
 	if err != nil {
@@ -39,6 +83,37 @@ func rmCmdFunc(cmd *cobra.Command, args []string) error {
 		return fmt.Errorf("failed to delete container: %v", err)
 	}
 
-	fmt.Printf("Container %s removed successfully\n", containerName)
+	printResult(containerName, "Container %s removed successfully", containerName)
 	return nil
 }
+
+// rmSelectedWorkloads removes all workloads, or those matching the filters
+func rmSelectedWorkloads(ctx context.Context, manager workloads.Manager) error {
+	workloadNames, err := selectWorkloadNames(ctx, manager, rmFilters, true)
+	if err != nil {
+		return err
+	}
+	if len(workloadNames) == 0 {
+		printInfo("No workloads to remove")
+		return nil
+	}
+
+	confirmed, err := confirmBulkOperation("removed", workloadNames, rmForce)
+	if err != nil {
+		return err
+	}
+	if !confirmed {
+		printInfo("Aborted")
+		return nil
+	}
+
+	// Remove the workloads concurrently, one group per workload so the outcome of each is known
+	results := runBulkOperation(workloadNames, func(name string) error {
+		group, err := manager.DeleteWorkloads(ctx, []string{name})
+		if err != nil {
+			return err
+		}
+		return group.Wait()
+	})
+	return printBulkSummary("removed", results)
+}
@@ -0,0 +1,154 @@
+package app
+
+import (
+	"context"
+	"fmt"
+	"time"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/container"
+	"github.com/stacklok/toolhive/pkg/container/images"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/runner"
+	"github.com/stacklok/toolhive/pkg/upgrade"
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+var rollbackCmd = &cobra.Command{
+	Use:   "rollback [flags] SERVER_NAME",
+	Short: "Roll back an MCP server to the version it ran before its last upgrade",
+	Long: `Roll back an MCP server to the image and configuration it ran with before its last upgrade.
+
+Each upgrade performed by thv upgrade is recorded in the server's upgrade history,
+along with the image and run configuration the server had before the upgrade.
+Rolling back restores them and recreates the server. Running the command again
+rolls back the upgrade before that one, up to the size of the history.
+
+When the registry digest of the previous image is known, the server is pinned to
+it, so it is not upgraded again until it is run with its image tag. Otherwise,
+the server is not upgraded again to an image that was rolled back. Use
+thv upgrade --history to show the upgrade history of a server.`,
+	Args: cobra.ExactArgs(1),
+	RunE: rollbackCmdFunc,
+}
+
+func rollbackCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+	name := args[0]
+
+	runtime, err := container.NewFactory().Create(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create container runtime: %v", err)
+	}
+	manager := workloads.NewManagerFromRuntime(runtime)
+
+	observed, err := observeServers(ctx, runtime)
+	if err != nil {
+		return err
+	}
+	obs, ok := observed[name]
+	if !ok {
+		return fmt.Errorf("server %s not found", name)
+	}
+
+	baseName := labels.GetContainerBaseName(obs.Container.Labels)
+	history, err := upgrade.LoadHistory(ctx, baseName)
+	if err != nil {
+		return err
+	}
+	last := history.LastUpgrade()
+	if last == nil {
+		return fmt.Errorf("no upgrade of %s to roll back", name)
+	}
+
+	imageManager := images.NewImageManager(ctx)
+	if err := ensurePreviousImage(ctx, imageManager, last); err != nil {
+		return err
+	}
+
+	rollback := upgrade.Transition{
+		Kind:        upgrade.TransitionRollback,
+		Time:        time.Now().UTC(),
+		Image:       last.Image,
+		FromImageID: obs.Container.ImageID,
+		ToImageID:   last.FromImageID,
+		FromDigest:  localRepoDigest(ctx, imageManager, last.Image, obs.Container.ImageID),
+		ToDigest:    last.FromDigest,
+		Config:      obs.Config,
+	}
+
+	printInfo("Rolling back %s to %s...", name, shortDigest(last.FromImageID))
+
+	// Restore the previous run configuration, pinned to the previous image when its digest is known,
+	// and point the image reference back at the previous image, so the server is recreated from them.
+	restored, err := previousRunConfig(obs.Config, last)
+	if err != nil {
+		return err
+	}
+	if err := imageManager.TagImage(ctx, last.FromImageID, last.Image); err != nil {
+		return err
+	}
+	if err := runner.NewRunner(restored).SaveState(ctx); err != nil {
+		return fmt.Errorf("failed to restore the run configuration of %s: %v", name, err)
+	}
+	if err := recreateWorkload(ctx, manager, name); err != nil {
+		return fmt.Errorf("failed to recreate %s: %v", name, err)
+	}
+
+	last.RolledBack = true
+	history.Record(rollback)
+	if err := upgrade.SaveHistory(ctx, baseName, history); err != nil {
+		return err
+	}
+
+	printResult(name, "Rolled back %s", name)
+	return nil
+}
+
+// previousRunConfig returns the run configuration a server had before an upgrade. If the registry digest
+// of the previous image is known, the configuration is pinned to it, since image references with the
+// latest tag are pulled again when the server starts.
+func previousRunConfig(current *runner.RunConfig, last *upgrade.Transition) (*runner.RunConfig, error) {
+	previous := last.Config
+	if previous == nil {
+		previous = current
+	}
+	if previous == nil {
+		return nil, fmt.Errorf("no recorded configuration")
+	}
+
+	restored := *previous
+	if last.FromDigest != "" {
+		ref, err := images.DigestReference(last.Image, last.FromDigest)
+		if err != nil {
+			return nil, err
+		}
+		restored.Image = ref
+	}
+	return &restored, nil
+}
+
+// ensurePreviousImage makes sure the image a server ran before an upgrade is available locally,
+// pulling it by digest if it was removed in the meantime
+func ensurePreviousImage(ctx context.Context, imageManager images.ImageManager, last *upgrade.Transition) error {
+	imageID, err := imageManager.GetImageID(ctx, last.FromImageID)
+	if err != nil {
+		return err
+	}
+	if imageID != "" {
+		return nil
+	}
+
+	if last.FromDigest == "" {
+		return fmt.Errorf("the previous image of %s is no longer available locally", last.Image)
+	}
+	ref, err := images.DigestReference(last.Image, last.FromDigest)
+	if err != nil {
+		return err
+	}
+	if err := imageManager.PullImage(ctx, ref); err != nil {
+		return fmt.Errorf("failed to pull the previous image %s: %v", ref, err)
+	}
+	return nil
+}
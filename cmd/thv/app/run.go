@@ -1,21 +1,35 @@
 package app
 
 import (
+	"context"
 	"fmt"
+	"io"
 	"net"
 	"os"
+	"runtime"
+	"strings"
+	"time"
 
 	"github.com/spf13/cobra"
 
+	"github.com/stacklok/toolhive/pkg/auth"
+	"github.com/stacklok/toolhive/pkg/client"
 	"github.com/stacklok/toolhive/pkg/config"
 	"github.com/stacklok/toolhive/pkg/container"
+	"github.com/stacklok/toolhive/pkg/container/docker"
+	"github.com/stacklok/toolhive/pkg/container/docker/sdk"
+	"github.com/stacklok/toolhive/pkg/container/images"
+	"github.com/stacklok/toolhive/pkg/environment"
 	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/networking"
 	"github.com/stacklok/toolhive/pkg/permissions"
 	"github.com/stacklok/toolhive/pkg/process"
 	"github.com/stacklok/toolhive/pkg/registry"
 	"github.com/stacklok/toolhive/pkg/runner"
 	"github.com/stacklok/toolhive/pkg/runner/retriever"
 	"github.com/stacklok/toolhive/pkg/transport"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+	"github.com/stacklok/toolhive/pkg/upgrade"
 	"github.com/stacklok/toolhive/pkg/workloads"
 )
 
@@ -24,29 +38,162 @@ var runCmd = &cobra.Command{
 	Short: "Run an MCP server",
 	Long: `Run an MCP server with the specified name, image, or protocol scheme.
 
-ToolHive supports three ways to run an MCP server:
+ToolHive supports four ways to run an MCP server:
 
 1. From the registry:
    $ thv run server-name [-- args...]
    Looks up the server in the registry and uses its predefined settings
    (transport, permissions, environment variables, etc.)
 
+   A release channel or a semantic version constraint can be selected
+   with @, and thv upgrade keeps the server within it:
+   $ thv run server-name@stable
+   $ thv run server-name@^1.2
+
 2. From a container image:
    $ thv run ghcr.io/example/mcp-server:latest [-- args...]
-   Runs the specified container image directly with the provided arguments
+   Runs the specified container image directly with the provided arguments.
+   Unless --transport and --target-port are given, they default to the
+   io.modelcontextprotocol.transport and io.modelcontextprotocol.port
+   labels of the image, or its MCP_TRANSPORT and MCP_PORT variables
+
+3. From a local Dockerfile:
+   $ thv run --build ./my-server [-- args...]
+   Builds the image from the Dockerfile in the directory with the container
+   runtime (see thv build), and runs it
 
-3. Using a protocol scheme:
+4. Using a protocol scheme:
    $ thv run uvx://package-name [-- args...]
    $ thv run npx://package-name [-- args...]
-   $ thv run go://package-name [-- args...]
+   $ thv run go://github.com/example/mcp-server@v1.2.3 [-- args...]
    $ thv run go://./local-path [-- args...]
    Automatically generates a container that runs the specified package
    using either uvx (Python with uv package manager), npx (Node.js),
-   or go (Golang). For Go, you can also specify local paths starting
-   with './' or '../' to build and run local Go projects.
+   or go (Golang). For Go, the module is compiled at the given version
+   (latest by default) and the binary runs in a distroless image. You can
+   also specify local paths starting with './' or '../' to build and run
+   local Go projects.
 
 The container will be started with the specified transport mode and
-permission profile. Additional configuration can be provided via flags.`,
+permission profile. Additional configuration can be provided via flags.
+
+By default the server runs in the background, supervised by ToolHive, and the
+command returns once it is started. With --detach=false (or --foreground), the
+server runs in the foreground: the logs of its container are streamed to the
+terminal, and the server is stopped and cleaned up when the command exits, e.g.
+on Ctrl+C. thv start behaves the same way for existing servers.
+
+Environment variable values and server arguments may contain ${VAR} and
+${secret:name} references, which are resolved from the environment and the
+secrets manager each time the server starts, so the stored configuration
+does not depend on the machine or contain the secrets. Write $${ for a
+literal ${, and quote references to keep the shell from expanding them:
+   $ thv run server-name -e 'API_TOKEN=${secret:api-token}' -- --root '${HOME}/src'
+
+The proxy of stdio servers serves both the SSE endpoint and the Streamable HTTP
+endpoint at /mcp, where clients start a session with an initialize request and
+send its Mcp-Session-Id header with their following requests.
+
+The proxy of SSE servers also serves the Streamable HTTP endpoint at /mcp, and
+the proxy of streamable-http servers also serves the SSE endpoint at /sse, so
+clients of either transport can use any server. The proxy records the protocol
+version each session negotiated, and sends it in the MCP-Protocol-Version header
+to streamable-http servers when older clients don't.
+
+With --transport websocket, the server accepts WebSocket connections at /ws on
+its target port. The proxy serves the same endpoints as for stdio servers, and
+connects to the server again with an exponential backoff when the connection
+is lost, replaying the initialize handshake of the client.
+
+The proxy listens on 127.0.0.1 by default, so only clients on this machine can
+connect. With --host 0.0.0.0, or the address of a network interface, clients on
+the network can connect too; the port of the container is still only published
+on the loopback interface, so they can only reach the server through the proxy.
+
+With --oidc-issuer (or --oidc-jwks-url), the proxy only accepts requests with
+a Bearer token signed by a key of the issuer (RSA or ECDSA), checking its issuer,
+its expiry and, with --oidc-audience, its audience. This is recommended when
+the proxy accepts connections from other machines.
+
+With --auth-token, the proxy only accepts requests with the given static token
+as Bearer token, and rejects others with 401 Unauthorized. Use file:PATH to read
+the token from a file, or set TOOLHIVE_AUTH_TOKEN, to keep it out of the shell
+history. It cannot be combined with the OIDC flags. Only the path of a token file
+is saved with the server: a token given otherwise must be set again with
+TOOLHIVE_AUTH_TOKEN when the server is restarted.
+
+With --cors-origin, web clients served from the given origins can connect to
+the proxy from a browser: the proxy answers their CORS preflight requests and
+lets them read its responses. Browsers block the requests of other origins.
+
+With --rate-limit, such as --rate-limit 100/min, the proxy limits the requests
+of each client, which can make bursts of up to the given number of requests,
+and rejects the requests over the limit with 429 Too Many Requests. Clients are
+identified by the subject of their token with OIDC validation, and by their IP
+address otherwise. The requests forwarded by thv gateway carry a token shared
+with the proxy, so their clients are identified by the address the gateway sets
+in the X-Forwarded-For header.
+
+With --tools, such as --tools fetch,search, the proxy only exposes the given
+tools of the server: it removes the other tools from the results of tools/list
+and rejects the calls to them, which reduces what the clients can do with
+servers offering many tools.
+
+With --tool-override, the proxy renames tools and rewrites their descriptions
+with the overrides of a YAML or JSON file, which maps the names of tools on the
+server to their new name and description, for example to avoid the collision
+of tools of different servers with the same name:
+
+  fetch:
+    name: web_fetch
+    description: Fetch a URL from the internet
+
+Clients call the tools with their new names. The names given with --tools and
+in authorization policies are the names of the tools on the server.
+
+With --log-requests, the proxy logs each MCP request with its method, the tool,
+prompt or resource it targets, the status of its response and its duration.
+With --log-payloads, it logs their parameters and JSON responses too.
+
+With --tls-cert and --tls-key, the proxy serves HTTPS with the given certificate,
+for clients which refuse plaintext endpoints or connect from other machines.
+With --tls-self-signed, it serves a self-signed certificate generated for the
+server, which is kept in the ToolHive data directory and reused on restarts.
+The URLs configured in clients and shown by thv list use https.
+
+With --listen-socket /path/to.sock, the proxy is served on a Unix domain socket
+instead of its port, so local clients can connect without a TCP port and access
+is controlled with the permissions of the socket, which only the user running
+the server can use. Clients connect to the socket with the usual endpoints, e.g.
+   $ curl --unix-socket /path/to.sock http://localhost/health
+The server is not added to the configuration of MCP clients, which connect with
+a URL, and thv list and thv inspect show the path of the socket instead.
+
+The proxy serves HTTP/1.1 and HTTP/2, also over cleartext connections with prior
+knowledge (h2c). It forwards gRPC requests to SSE and streamable-http servers
+over HTTP/2, with their trailers and streams. With --upstream-http2, it forwards
+all the requests of HTTP/2 clients over HTTP/2, for servers relying on HTTP/2
+streaming; the server must accept HTTP/2 with prior knowledge on its port.
+
+With --isolate-network, the server is placed on a dedicated internal network
+without a route to the outside. Its outbound traffic goes through an egress
+proxy container, which only allows the hosts and ports of the outbound network
+permissions of the permission profile, and its DNS queries go through a DNS
+container. An ingress proxy container publishes the port of SSE and
+streamable-http servers.
+
+With --network, the server joins existing networks of the container runtime,
+e.g. to reach the databases of a compose stack. This requires a permission
+profile which allows outbound network access, and with --isolate-network one
+which allows all outbound traffic, since traffic on the joined networks does
+not go through the egress proxy:
+   $ thv run server-name --permission-profile network --network myapp_default
+
+With --isolate-network, --grant-egress temporarily allows access to a host in
+addition to the permission profile, e.g. while debugging. Once --grant-duration
+has passed, the grant is revoked, the server reverts to its permission profile,
+and the change is logged:
+   $ thv run server-name --isolate-network --grant-egress api.example.com:443 --grant-duration 2h`,
 	Args: cobra.MinimumNArgs(1),
 	RunE: runCmdFunc,
 	// Ignore unknown flags to allow passing flags to the MCP server
@@ -65,6 +212,7 @@ var (
 	runPermissionProfile string
 	runEnv               []string
 	runForeground        bool
+	runDetach            bool
 	runVolumes           []string
 	runSecrets           []string
 	runAuthzConfig       string
@@ -73,6 +221,12 @@ var (
 	runK8sPodPatch       string
 	runCACertPath        string
 	runVerifyImage       string
+	runPull              string
+	runPrePull           bool
+
+	// Registry credentials of private images
+	runRegistryUsername      string
+	runRegistryPasswordStdin bool
 
 	// OpenTelemetry flags
 	runOtelEndpoint                    string
@@ -85,13 +239,101 @@ var (
 
 	// Network isolation flag
 	runIsolateNetwork bool
+
+	// Additional log sink for the server's logs
+	runLogSink string
+
+	// Container log driver configuration
+	runLogDriver  string
+	runLogOptions []string
+
+	// User-defined container labels
+	runLabels []string
+
+	// Automatic upgrade configuration
+	runAutoUpgrade   bool
+	runUpgradeWindow string
+	// runVersionConstraint is the release channel or version constraint passed to the detached process,
+	// which is given the resolved image rather than server@selector
+	runVersionConstraint string
+
+	// Graceful stop configuration
+	runStopTimeout int
+	runStopSignal  string
+
+	// Resource limits
+	runCPUs       float64
+	runMemory     string
+	runMemorySwap string
+	runPidsLimit  int64
+
+	// GPUs and devices
+	runGPUs    string
+	runDevices []string
+
+	// Existing networks to join
+	runNetworks []string
+
+	// Build the image from a local Dockerfile
+	runBuild bool
+
+	// Restart policy
+	runRestart          string
+	runRestartUnhealthy bool
+
+	// Proxy endpoint paths
+	runSSEPath      string
+	runMessagesPath string
+
+	// Windows named pipe listener
+	runNamedPipe bool
+
+	// Unix domain socket listener, instead of the port
+	runListenSocket string
+
+	// HTTP/2 between the proxy and the server
+	runUpstreamHTTP2 bool
+
+	// Static token clients must send to the proxy
+	runAuthToken string
+
+	// Origins of the web clients allowed to connect to the proxy
+	runCORSOrigins []string
+
+	// Number of requests each client can make to the proxy
+	runRateLimit string
+
+	// Tools of the server exposed by the proxy, and how they are presented
+	runTools        []string
+	runToolOverride string
+
+	// Logging of the MCP requests going through the proxy
+	runLogRequests bool
+	runLogPayloads bool
+
+	// Timeouts of the proxy and maximum size of the messages of the clients
+	runProxyConnectTimeout time.Duration
+	runProxyIdleTimeout    time.Duration
+	runProxySSEReadTimeout time.Duration
+	runMaxMessageSize      string
+
+	// TLS termination on the proxy
+	runTLSCert       string
+	runTLSKey        string
+	runTLSSelfSigned bool
+
+	// Temporary permission grants
+	runGrantEgress   []string
+	runGrantDuration time.Duration
 )
 
 func init() {
-	runCmd.Flags().StringVar(&runTransport, "transport", "", "Transport mode (sse, streamable-http or stdio)")
+	runCmd.Flags().StringVar(&runTransport, "transport", "", "Transport mode (sse, streamable-http, websocket or stdio)")
 	runCmd.Flags().StringVar(&runName, "name", "", "Name of the MCP server (auto-generated from image if not provided)")
-	runCmd.Flags().StringVar(&runHost, "host", transport.LocalhostIPv4, "Host for the HTTP proxy to listen on (IP or hostname)")
-	runCmd.Flags().IntVar(&runPort, "port", 0, "Port for the HTTP proxy to listen on (host port)")
+	runCmd.Flags().StringVar(&runHost, "host", transport.LocalhostIPv4,
+		"Host for the HTTP proxy to listen on (IP or hostname); use 0.0.0.0 to accept connections from other machines")
+	runCmd.Flags().IntVar(&runPort, "port", 0,
+		"Port for the HTTP proxy to listen on (host port); a free port is selected if not specified")
 	runCmd.Flags().IntVar(&runTargetPort, "target-port", 0,
 		"Port for the container to expose (only applicable to SSE or Streamable HTTP transport)")
 	runCmd.Flags().StringVar(
@@ -110,15 +352,17 @@ func init() {
 		"env",
 		"e",
 		[]string{},
-		"Environment variables to pass to the MCP server (format: KEY=VALUE)",
+		"Environment variables to pass to the MCP server (format: KEY=VALUE; ${VAR} and ${secret:name} are resolved at start)",
 	)
-	runCmd.Flags().BoolVarP(&runForeground, "foreground", "f", false, "Run in foreground mode (block until container exits)")
+	runCmd.Flags().BoolVarP(&runDetach, "detach", "d", true, detachFlagUsage)
+	runCmd.Flags().BoolVarP(&runForeground, "foreground", "f", false, "Run in foreground mode (same as --detach=false)")
 	runCmd.Flags().StringArrayVarP(
 		&runVolumes,
 		"volume",
 		"v",
 		[]string{},
-		"Mount a volume into the container (format: host-path:container-path[:ro])",
+		"Mount a volume into the container (format: host-path:container-path[:ro]); the host path must be "+
+			"allowed by the read or write paths of the permission profile",
 	)
 	runCmd.Flags().StringArrayVar(
 		&runSecrets,
@@ -148,7 +392,8 @@ func init() {
 		&runK8sPodPatch,
 		"k8s-pod-patch",
 		"",
-		"JSON string to patch the Kubernetes pod template (only applicable when using Kubernetes runtime)",
+		"JSON pod template merged into the generated pod, e.g. to add volumes, node selectors, tolerations, "+
+			"resources or security contexts (only applicable when using Kubernetes runtime)",
 	)
 	runCmd.Flags().StringVar(
 		&runCACertPath,
@@ -161,17 +406,30 @@ func init() {
 		"image-verification",
 		retriever.VerifyImageWarn,
 		fmt.Sprintf(
-			"Set image verification mode (%s, %s, %s)",
+			"Set image verification mode (%s, %s, %s); images which are not in the registry "+
+				"are verified against the signers of thv config add-trusted-signer",
 			retriever.VerifyImageWarn,
 			retriever.VerifyImageEnabled,
 			retriever.VerifyImageDisabled,
 		),
 	)
-
-	// This is used for the K8s operator which wraps the run command, but shouldn't be visible to users.
-	if err := runCmd.Flags().MarkHidden("k8s-pod-patch"); err != nil {
-		logger.Warnf("Error hiding flag: %v", err)
-	}
+	runCmd.Flags().StringVar(
+		&runPull,
+		"pull",
+		"",
+		fmt.Sprintf(
+			"Image pull policy (%s, %s, %s); by default images with the latest tag are pulled, and others only if missing",
+			retriever.PullAlways,
+			retriever.PullMissing,
+			retriever.PullNever,
+		),
+	)
+	runCmd.Flags().BoolVar(&runPrePull, "pre-pull", false,
+		"Pull the image of the server, and the images of --isolate-network, concurrently with progress bars before running")
+	runCmd.Flags().StringVar(&runRegistryUsername, "registry-username", "",
+		"Username of the registry of the image, instead of the credentials of the Docker configuration")
+	runCmd.Flags().BoolVar(&runRegistryPasswordStdin, "registry-password-stdin", false,
+		"Read the password or token of the registry of the image from stdin (requires --registry-username)")
 
 	// Add OIDC validation flags
 	AddOIDCFlags(runCmd)
@@ -193,7 +451,82 @@ func init() {
 		"Environment variable names to include in OpenTelemetry spans "+
 			"(comma-separated: ENV1,ENV2)")
 	runCmd.Flags().BoolVar(&runIsolateNetwork, "isolate-network", false,
-		"Isolate the container network from the host (default: false)")
+		"Run the server on an internal network, with outbound traffic filtered by an egress proxy (default: false)")
+	runCmd.Flags().StringVar(&runLogSink, "log-sink", string(logger.SinkFile),
+		"Where to send the server's logs in addition to the log file (file, syslog, journald)")
+	runCmd.Flags().StringVar(&runLogDriver, "log-driver", "",
+		"Log driver for the MCP server container (defaults to the container runtime's default)")
+	runCmd.Flags().StringArrayVar(&runLogOptions, "log-opt", nil,
+		"Log driver options in key=value format (e.g., max-size=10m, max-file=3)")
+	runCmd.Flags().StringArrayVar(&runLabels, "label", nil, "Set labels on the container (format: key=value)")
+	runCmd.Flags().BoolVar(&runAutoUpgrade, "auto-upgrade", false,
+		"Upgrade the server automatically when a newer image is available (see thv upgrade --auto)")
+	runCmd.Flags().StringVar(&runUpgradeWindow, "upgrade-window", "",
+		"Maintenance window for automatic upgrades, in local time (e.g., \"Sat,Sun 02:00-04:00\")")
+	runCmd.Flags().StringVar(&runVersionConstraint, "version-constraint", "",
+		"Release channel or version constraint the server is upgraded within (set by the detached process)")
+	_ = runCmd.Flags().MarkHidden("version-constraint")
+	runCmd.Flags().IntVar(&runStopTimeout, "stop-timeout", 0,
+		"Default time in seconds to wait for the server to exit when stopped before killing it (defaults to 30)")
+	runCmd.Flags().StringVar(&runStopSignal, "stop-signal", "",
+		"Default signal used to stop the server (e.g., SIGINT; defaults to the image's stop signal)")
+	runCmd.Flags().Float64Var(&runCPUs, "cpus", 0, "Number of CPUs the server may use (e.g., 0.5; unlimited by default)")
+	runCmd.Flags().StringVar(&runMemory, "memory", "", "Memory limit of the server (e.g., 512m or 2g; unlimited by default)")
+	runCmd.Flags().StringVar(&runMemorySwap, "memory-swap", "",
+		"Limit of memory and swap of the server (e.g., 1g, or -1 for unlimited swap; requires --memory)")
+	runCmd.Flags().Int64Var(&runPidsLimit, "pids-limit", 0, "Maximum number of processes of the server (unlimited by default)")
+	runCmd.Flags().BoolVar(&runBuild, "build", false,
+		"Build the image of the server from the Dockerfile in the directory given instead of an image, and run it")
+	runCmd.Flags().StringVar(&runRestart, "restart", "",
+		"Restart policy of the server when it exits (no, always, unless-stopped, on-failure[:max-retries]); "+
+			"by default the server is restarted unless it is stopped")
+	runCmd.Flags().BoolVar(&runRestartUnhealthy, "restart-unhealthy", false,
+		"Restart the server when its health checks fail repeatedly")
+	runCmd.Flags().StringVar(&runGPUs, "gpu", "",
+		"GPUs to make available to the server (all, a number of GPUs, or device=ID[,ID...]), e.g. for servers of local models")
+	runCmd.Flags().StringArrayVar(&runDevices, "device", nil,
+		"Device of the host to add to the server (format: host-path[:container-path][:permissions], "+
+			"or a CDI device name like nvidia.com/gpu=all; can be specified multiple times)")
+	runCmd.Flags().StringArrayVar(&runNetworks, "network", nil,
+		"Existing network of the container runtime for the server to join, e.g. of a compose stack; requires a permission "+
+			"profile which allows outbound network access (can be specified multiple times)")
+	runCmd.Flags().StringVar(&runSSEPath, "sse-path", "",
+		"Path of the SSE endpoint served by the proxy (stdio transport only; defaults to /sse)")
+	runCmd.Flags().StringVar(&runMessagesPath, "messages-path", "",
+		"Path of the message endpoint served by the proxy (stdio transport only; defaults to /messages)")
+	runCmd.Flags().BoolVar(&runNamedPipe, "named-pipe", false,
+		"Also serve the proxy on the named pipe \\\\.\\pipe\\toolhive-NAME, for local clients (Windows only)")
+	runCmd.Flags().StringVar(&runListenSocket, "listen-socket", "",
+		"Serve the proxy on the Unix domain socket at this path instead of the port, for local clients")
+	runCmd.Flags().BoolVar(&runUpstreamHTTP2, "upstream-http2", false,
+		"Forward the requests of HTTP/2 clients to the server over HTTP/2 (SSE and streamable-http transports only)")
+	runCmd.Flags().StringVar(&runAuthToken, "auth-token", "", authTokenUsage)
+	runCmd.Flags().StringArrayVar(&runCORSOrigins, "cors-origin", nil, corsOriginUsage)
+	runCmd.Flags().StringVar(&runRateLimit, "rate-limit", "", rateLimitUsage)
+	runCmd.Flags().StringSliceVar(&runTools, "tools", nil,
+		"Only expose these tools of the server, hiding the others from tools/list and rejecting calls to them")
+	runCmd.Flags().StringVar(&runToolOverride, "tool-override", "",
+		"Path of a YAML or JSON file renaming tools of the server and rewriting their descriptions")
+	runCmd.Flags().BoolVar(&runLogRequests, "log-requests", false, logRequestsUsage)
+	runCmd.Flags().BoolVar(&runLogPayloads, "log-payloads", false, logPayloadsUsage)
+	runCmd.Flags().DurationVar(&runProxyConnectTimeout, "proxy-connect-timeout", 0,
+		"How long the proxy waits to connect to the MCP server (defaults to 30s)")
+	runCmd.Flags().DurationVar(&runProxyIdleTimeout, "proxy-idle-timeout", 0,
+		"How long idle connections of the clients and to the MCP server are kept open (defaults to 90s)")
+	runCmd.Flags().DurationVar(&runProxySSEReadTimeout, "proxy-sse-read-timeout", 0,
+		"How long an SSE stream of the MCP server may send nothing before the proxy reconnects to it "+
+			"(SSE transport only; by default streams are kept while they are open)")
+	runCmd.Flags().StringVar(&runMaxMessageSize, "max-message-size", "",
+		"Maximum size of the JSON-RPC messages clients send to the proxy, e.g. 10m (unlimited by default)")
+	runCmd.Flags().StringVar(&runTLSCert, "tls-cert", "",
+		"Path of a PEM-encoded certificate with which the proxy serves HTTPS (requires --tls-key)")
+	runCmd.Flags().StringVar(&runTLSKey, "tls-key", "", "Path of the PEM-encoded key of the certificate of --tls-cert")
+	runCmd.Flags().BoolVar(&runTLSSelfSigned, "tls-self-signed", false,
+		"Serve the proxy over HTTPS with a self-signed certificate generated for the server")
+	runCmd.Flags().StringArrayVar(&runGrantEgress, "grant-egress", nil,
+		"Temporarily allow egress to HOST[:PORT] in addition to the permission profile (can be specified multiple times)")
+	runCmd.Flags().DurationVar(&runGrantDuration, "grant-duration", time.Hour,
+		"Time after which the grants of --grant-egress are revoked")
 
 }
 
@@ -231,6 +564,27 @@ func getTelemetryFromFlags(cmd *cobra.Command, cfg *config.Config, runOtelEndpoi
 	return finalOtelEndpoint, finalOtelSamplingRate, finalOtelEnvironmentVariables
 }
 
+// withRegistryCredentials returns a context in which images are pulled with the registry credentials of the
+// flags, if they are set. The password is read from stdin, so it isn't exposed in the process list or history.
+func withRegistryCredentials(ctx context.Context, stdin io.Reader) (context.Context, error) {
+	if runRegistryUsername == "" && !runRegistryPasswordStdin {
+		return ctx, nil
+	}
+	if runRegistryUsername == "" || !runRegistryPasswordStdin {
+		return nil, fmt.Errorf("--registry-username and --registry-password-stdin must be used together")
+	}
+
+	password, err := io.ReadAll(stdin)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read the registry password from stdin: %v", err)
+	}
+	trimmed := strings.TrimRight(string(password), "\r\n")
+	if trimmed == "" {
+		return nil, fmt.Errorf("the registry password read from stdin is empty")
+	}
+	return images.WithCredentials(ctx, &images.Credentials{Username: runRegistryUsername, Password: trimmed}), nil
+}
+
 func runCmdFunc(cmd *cobra.Command, args []string) error {
 	ctx := cmd.Context()
 
@@ -240,6 +594,7 @@ func runCmdFunc(cmd *cobra.Command, args []string) error {
 		return fmt.Errorf("invalid host: %s", runHost)
 	}
 	runHost = validatedHost
+	warnIfNotLoopback(runHost)
 
 	// Get the name of the MCP server to run.
 	// This may be a server name from the registry, a container image, or a protocol scheme.
@@ -249,11 +604,28 @@ func runCmdFunc(cmd *cobra.Command, args []string) error {
 	cmdArgs := parseCommandArguments(os.Args)
 
 	// Print the processed command arguments for debugging
-	logger.Debugf("Processed cmdArgs: %v", cmdArgs)
+	logger.Debugf("Processed cmdArgs: %v", environment.MaskArgs(cmdArgs))
 
 	// Get debug mode flag
 	debugMode, _ := cmd.Flags().GetBool("debug")
 
+	if err := logger.ValidateSinkType(runLogSink); err != nil {
+		return err
+	}
+
+	detach, err := resolveDetach(cmd, runDetach, runForeground)
+	if err != nil {
+		return err
+	}
+	pullPolicy, err := retriever.ParsePullPolicy(runPull)
+	if err != nil {
+		return err
+	}
+	ctx, err = withRegistryCredentials(ctx, os.Stdin)
+	if err != nil {
+		return err
+	}
+
 	// Get OIDC flag values
 	oidcIssuer, oidcAudience, oidcJwksURL, oidcClientID, oidcAllowOpaqueTokens, err := getOidcFromFlags(cmd)
 	if err != nil {
@@ -272,32 +644,16 @@ func runCmdFunc(cmd *cobra.Command, args []string) error {
 	}
 	workloadManager := workloads.NewManagerFromRuntime(rt)
 
-	// Select an env var validation strategy depending on how the CLI is run:
-	// If we have called the CLI directly, we use the CLIEnvVarValidator.
-	// If we are running in detached mode, or the CLI is wrapped by the K8s operator,
-	// we use the DetachedEnvVarValidator.
-	var envVarValidator runner.EnvVarValidator
-	if process.IsDetached() || container.IsKubernetesRuntime() {
-		envVarValidator = &runner.DetachedEnvVarValidator{}
-	} else {
-		envVarValidator = &runner.CLIEnvVarValidator{}
+	if err := checkPortConflict(ctx, workloadManager, runName, runPort); err != nil {
+		return err
 	}
 
-	var imageMetadata *registry.ImageMetadata
-	imageURL := serverOrImage
-
-	// Only pull image if we are not running in Kubernetes mode.
-	// This split will go away if we implement a separate command or binary
-	// for running MCP servers in Kubernetes.
-	if !container.IsKubernetesRuntime() {
-		// Take the MCP server we were supplied and either fetch the image, or
-		// build it from a protocol scheme. If the server URI refers to an image
-		// in our trusted registry, we will also fetch the image metadata.
-		imageURL, imageMetadata, err = retriever.GetMCPServer(ctx, serverOrImage, runCACertPath, runVerifyImage)
-		if err != nil {
-			return fmt.Errorf("failed to find or create the MCP server %s: %v", serverOrImage, err)
-		}
+	imageURL, imageMetadata, defaults, err := retrieveServer(ctx, serverOrImage, pullPolicy)
+	if err != nil {
+		return err
 	}
+	// Servers which are not in the registry can declare their transport and port in their image
+	mcpTransport, targetPort := defaults.Apply(runTransport, runTargetPort)
 
 	// Initialize a new RunConfig with values from command-line flags
 	// TODO: As noted elsewhere, we should use the builder pattern here to make it more readable.
@@ -317,9 +673,9 @@ func runCmdFunc(cmd *cobra.Command, args []string) error {
 		runEnableAudit,
 		runPermissionProfile,
 		runTargetHost,
-		runTransport,
+		mcpTransport,
 		runPort,
-		runTargetPort,
+		targetPort,
 		runEnv,
 		oidcIssuer,
 		oidcAudience,
@@ -335,18 +691,261 @@ func runCmdFunc(cmd *cobra.Command, args []string) error {
 		finalOtelEnvironmentVariables,
 		runIsolateNetwork,
 		runK8sPodPatch,
-		envVarValidator,
+		newEnvVarValidator(),
 	)
 	if err != nil {
 		return fmt.Errorf("failed to create RunConfig: %v", err)
 	}
+	if err := applyRunOptions(runConfig); err != nil {
+		return err
+	}
+	// The ports of workloads on a host connected to over SSH are not reachable by the local proxy
+	if sdk.UsesSSHHost() && runConfig.Transport != types.TransportTypeStdio {
+		return fmt.Errorf("the %s transport is not supported with an SSH container host, only stdio is", runConfig.Transport)
+	}
+	runConfig.VersionConstraint = versionSelector(serverOrImage, imageMetadata, runVersionConstraint)
 
 	// Once we have built the RunConfig, start the MCP workload.
+	return startWorkload(ctx, workloadManager, runConfig, detach)
+}
+
+// retrieveServer fetches the image of the MCP server, or builds it from a protocol scheme or from a local
+// Dockerfile with --build, and returns it with the registry metadata of the server, if any. For images
+// which are not in the registry, it also returns the transport and target port declared by the image.
+func retrieveServer(
+	ctx context.Context,
+	serverOrImage string,
+	pullPolicy retriever.PullPolicy,
+) (string, *registry.ImageMetadata, runner.ImageDefaults, error) {
+	// Only pull image if we are not running in Kubernetes mode.
+	// This split will go away if we implement a separate command or binary
+	// for running MCP servers in Kubernetes.
+	if container.IsKubernetesRuntime() {
+		return serverOrImage, nil, runner.ImageDefaults{}, nil
+	}
+
+	// Servers built from a local Dockerfile are never in the registry, and their image is not pulled
+	if runBuild {
+		imageURL, err := buildServer(ctx, serverOrImage)
+		if err != nil {
+			return "", nil, runner.ImageDefaults{}, err
+		}
+		return imageURL, nil, imageDefaults(ctx, imageURL), nil
+	}
+
+	if runPrePull && !runner.IsImageProtocolScheme(serverOrImage) {
+		var err error
+		pullPolicy, err = prePullImages(ctx, serverOrImage, pullPolicy)
+		if err != nil {
+			return "", nil, runner.ImageDefaults{}, err
+		}
+	}
+
+	// Take the MCP server we were supplied and either fetch the image, or
+	// build it from a protocol scheme. If the server URI refers to an image
+	// in our trusted registry, we will also fetch the image metadata.
+	imageURL, imageMetadata, err := retriever.GetMCPServer(ctx, serverOrImage, runCACertPath, runVerifyImage, pullPolicy)
+	if err != nil {
+		return "", nil, runner.ImageDefaults{}, fmt.Errorf("failed to find or create the MCP server %s: %v", serverOrImage, err)
+	}
+	if imageMetadata != nil {
+		return imageURL, imageMetadata, runner.ImageDefaults{}, nil
+	}
+	return imageURL, nil, imageDefaults(ctx, imageURL), nil
+}
+
+// prePullImages pulls the image of a server, and the images of its isolated network if it has one,
+// concurrently, and returns the pull policy of the retrieval of the server which follows. Failures are
+// left to the retrieval, which falls back to local images where its pull policy allows it.
+func prePullImages(
+	ctx context.Context,
+	serverOrImage string,
+	pullPolicy retriever.PullPolicy,
+) (retriever.PullPolicy, error) {
+	if pullPolicy == retriever.PullNever {
+		return "", fmt.Errorf("--pre-pull cannot be combined with --pull %s", retriever.PullNever)
+	}
+
+	image, _, err := retriever.ResolveImage(ctx, serverOrImage)
+	if err != nil {
+		return "", err
+	}
+	imageRefs := []string{image}
+	if runIsolateNetwork {
+		imageRefs = append(imageRefs, docker.NetworkIsolationImages()...)
+	}
+
+	failures := pullImages(ctx, imageRefs, images.DefaultPullConcurrency)
+	if _, failed := failures[image]; failed {
+		logger.Warnf("Warning: Failed to pre-pull %s, retrying while retrieving the server", image)
+		return pullPolicy, nil
+	}
+	// The image was just pulled, so it is not pulled again
+	return retriever.PullMissing, nil
+}
+
+// imageDefaults returns the transport and target port declared by the labels and environment of a local image
+func imageDefaults(ctx context.Context, image string) runner.ImageDefaults {
+	imageConfig, err := images.NewImageManager(ctx).GetImageConfig(ctx, image)
+	if err != nil {
+		logger.Debugf("Failed to read the configuration of image %s: %v", image, err)
+	}
+	return runner.ImageDefaultsFromConfig(imageConfig)
+}
+
+// startWorkload runs the MCP workload in the background, or in the foreground if detach is not set
+func startWorkload(ctx context.Context, workloadManager workloads.Manager, runConfig *runner.RunConfig, detach bool) error {
 	// If we are running the container in the foreground - call the RunWorkload method directly.
-	if runForeground {
-		return workloadManager.RunWorkload(ctx, runConfig)
+	if !detach {
+		return runWorkloadInForeground(ctx, workloadManager, runConfig.ContainerName, func() error {
+			return workloadManager.RunWorkload(ctx, runConfig)
+		})
+	}
+	if err := workloadManager.RunWorkloadDetached(runConfig); err != nil {
+		return err
+	}
+
+	// In quiet mode, print only the endpoint URL (or the name if there is no URL)
+	// so the output can be used in shell pipelines.
+	if IsQuiet() {
+		printServerURL(runConfig)
+	}
+	return nil
+}
+
+// printServerURL prints the URL of the MCP server, or its name if the server has no URL
+func printServerURL(runConfig *runner.RunConfig) {
+	// A proxy listening on all interfaces is reachable on the loopback interface
+	host := runConfig.Host
+	if networking.IsUnspecifiedHost(host) {
+		host = transport.LocalhostIPv4
+	}
+	url := client.GenerateMCPServerURLForLabels(
+		runConfig.Transport.String(), host, runConfig.Port, runConfig.ContainerName, runConfig.ContainerLabels)
+	if url == "" {
+		url = runConfig.ContainerName
+	}
+	fmt.Println(url)
+}
+
+// applyRunOptions applies the options of the run command which are not handled by NewRunConfigFromFlags
+func applyRunOptions(runConfig *runner.RunConfig) error {
+	runConfig.LogSink = runLogSink
+	if _, err := runConfig.WithLogConfig(runLogDriver, runLogOptions); err != nil {
+		return err
+	}
+	if _, err := runConfig.WithLabels(runLabels); err != nil {
+		return err
+	}
+	if runUpgradeWindow != "" {
+		if _, err := upgrade.ParseWindow(runUpgradeWindow); err != nil {
+			return err
+		}
+	}
+	runConfig.AutoUpgrade = runAutoUpgrade
+	runConfig.UpgradeWindow = runUpgradeWindow
+	if _, err := runConfig.WithStopConfig(runStopTimeout, runStopSignal); err != nil {
+		return err
 	}
-	return workloadManager.RunWorkloadDetached(runConfig)
+	if _, err := runConfig.WithResourceLimits(runCPUs, runMemory, runMemorySwap, runPidsLimit); err != nil {
+		return err
+	}
+	if _, err := runConfig.WithDevices(runGPUs, runDevices); err != nil {
+		return err
+	}
+	if _, err := runConfig.WithRestartPolicy(runRestart); err != nil {
+		return err
+	}
+	runConfig.RestartUnhealthy = runRestartUnhealthy
+	if _, err := runConfig.WithPermissionGrants(runGrantEgress, runGrantDuration); err != nil {
+		return err
+	}
+	if _, err := runConfig.WithNetworks(runNetworks); err != nil {
+		return err
+	}
+	return applyProxyOptions(runConfig)
+}
+
+// applyProxyOptions applies the options of the run command for the proxy clients connect to
+func applyProxyOptions(runConfig *runner.RunConfig) error {
+	if _, err := runConfig.WithEndpointPaths(runSSEPath, runMessagesPath); err != nil {
+		return err
+	}
+	if _, err := runConfig.WithTLS(runTLSCert, runTLSKey, runTLSSelfSigned); err != nil {
+		return err
+	}
+	if _, err := runConfig.WithAuthToken(authTokenFromFlag(runAuthToken)); err != nil {
+		return err
+	}
+	if _, err := runConfig.WithCORSOrigins(runCORSOrigins); err != nil {
+		return err
+	}
+	if _, err := runConfig.WithRateLimit(runRateLimit); err != nil {
+		return err
+	}
+	runConfig.Tools = runTools
+	if _, err := runConfig.WithToolOverrideFile(runToolOverride); err != nil {
+		return err
+	}
+	runConfig.LogRequests = runLogRequests || runLogPayloads
+	runConfig.LogPayloads = runLogPayloads
+	if _, err := runConfig.WithProxyLimits(
+		runProxyConnectTimeout, runProxyIdleTimeout, runProxySSEReadTimeout, runMaxMessageSize); err != nil {
+		return err
+	}
+	if runNamedPipe && runtime.GOOS != "windows" {
+		return fmt.Errorf("--named-pipe is only supported on Windows")
+	}
+	runConfig.WithNamedPipe(runNamedPipe)
+	if _, err := runConfig.WithListenSocket(runListenSocket); err != nil {
+		return err
+	}
+	runConfig.UpstreamHTTP2 = runUpstreamHTTP2
+	return nil
+}
+
+// newEnvVarValidator selects an env var validation strategy depending on how the CLI is run:
+// If we have called the CLI directly, we use the CLIEnvVarValidator.
+// If we are running in detached mode, or the CLI is wrapped by the K8s operator,
+// we use the DetachedEnvVarValidator.
+func newEnvVarValidator() runner.EnvVarValidator {
+	if process.IsDetached() || container.IsKubernetesRuntime() {
+		return &runner.DetachedEnvVarValidator{}
+	}
+	return &runner.CLIEnvVarValidator{}
+}
+
+// checkPortConflict returns an error if the port requested with --port is the port of another MCP server.
+// Stopped servers keep their port, so they would fail to start again if it was taken. The detached process
+// is started with the port already checked by the command that started it.
+func checkPortConflict(ctx context.Context, workloadManager workloads.Manager, name string, port int) error {
+	if port == 0 || process.IsDetached() {
+		return nil
+	}
+	workloadList, err := workloadManager.ListWorkloads(ctx, true)
+	if err != nil {
+		return fmt.Errorf("failed to list workloads: %v", err)
+	}
+	for _, workload := range workloadList {
+		if workload.Port == port && workload.Name != name {
+			return fmt.Errorf("port %d is already used by MCP server %s; choose another port with --port, "+
+				"or omit it to select a free port", port, workload.Name)
+		}
+	}
+	return nil
+}
+
+// versionSelector returns the release channel or version constraint selected for a registry server, if any.
+// The detached process is given the resolved image, so it uses the constraint forwarded by its parent.
+func versionSelector(serverOrImage string, imageMetadata *registry.ImageMetadata, forwarded string) string {
+	if forwarded != "" {
+		return forwarded
+	}
+	if imageMetadata == nil {
+		return ""
+	}
+	_, selector := registry.SplitServerVersion(serverOrImage)
+	return selector
 }
 
 // parseCommandArguments processes command-line arguments to find everything after the -- separator
@@ -390,3 +989,39 @@ func ValidateAndNormaliseHostFlag(host string) (string, error) {
 
 	return "", fmt.Errorf("could not resolve host: %s", host)
 }
+
+// authTokenUsage is the usage of the --auth-token flag of the commands running a proxy
+const authTokenUsage = "Static token clients must send as Bearer token, or file:PATH to read it from a file; " +
+	"can also be set with " + auth.StaticTokenEnvVar
+
+// corsOriginUsage is the usage of the --cors-origin flag of the commands running a proxy
+const corsOriginUsage = "Origin of web clients allowed to connect to the proxy from a browser, " +
+	"such as https://app.example.com, or * for any origin (can be specified multiple times)"
+
+// rateLimitUsage is the usage of the --rate-limit flag of the commands running a proxy
+const rateLimitUsage = "Number of requests each client can make to the proxy, as REQUESTS/UNIT with UNIT s, min or h " +
+	"(e.g. 100/min); clients are identified by the subject of their token with OIDC validation, or their IP address"
+
+// Usages of the flags logging the MCP requests going through the proxy
+const (
+	logRequestsUsage = "Log the method, target, status and duration of the MCP requests going through the proxy"
+	logPayloadsUsage = "Log the MCP requests with their parameters and JSON responses, which can contain sensitive data " +
+		"(implies --log-requests)"
+)
+
+// authTokenFromFlag returns the value of an --auth-token flag, falling back to the environment variable
+func authTokenFromFlag(value string) string {
+	if value == "" {
+		return os.Getenv(auth.StaticTokenEnvVar)
+	}
+	return value
+}
+
+// warnIfNotLoopback warns that the MCP server is reachable from other machines if the proxy
+// does not only listen on the loopback interface
+func warnIfNotLoopback(host string) {
+	if !networking.IsLoopbackHost(host) {
+		logger.Warnf("The proxy listens on %s, so the MCP server is reachable from other machines; "+
+			"use --host 127.0.0.1 to only allow connections from this machine", host)
+	}
+}
@@ -21,7 +21,10 @@ var (
 var serveCmd = &cobra.Command{
 	Use:   "serve",
 	Short: "Start the ToolHive API server",
-	Long:  `Starts the ToolHive API server and listen for HTTP requests.`,
+	Long: `Starts the ToolHive API server and listen for HTTP requests.
+
+If the server is started by systemd socket activation, it serves on the socket
+passed by systemd instead of listening on --host and --port.`,
 	RunE: func(cmd *cobra.Command, _ []string) error {
 		// Ensure server is shutdown gracefully on Ctrl+C.
 		ctx, cancel := signal.NotifyContext(cmd.Context(), os.Interrupt)
@@ -0,0 +1,57 @@
+package app
+
+import (
+	"fmt"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+var startDetach bool
+
+var startCmd = &cobra.Command{
+	Use:   "start [flags] SERVER_NAME",
+	Short: "Start a stopped MCP server",
+	Long: `Start an existing MCP server managed by ToolHive from its saved configuration.
+Starting a server which is already running has no effect.
+
+As with thv run, the server runs in the background by default, supervised by
+ToolHive. With --detach=false, the server runs in the foreground: the logs of its
+container are streamed to the terminal, and the server is stopped and cleaned up
+when the command exits, e.g. on Ctrl+C. A server running in the background is
+taken over by the foreground command.`,
+	Args: cobra.ExactArgs(1),
+	RunE: startCmdFunc,
+}
+
+func init() {
+	startCmd.Flags().BoolVarP(&startDetach, "detach", "d", true, detachFlagUsage)
+}
+
+func startCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+	name := args[0]
+
+	manager, err := workloads.NewManager(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create container manager: %v", err)
+	}
+
+	if !startDetach {
+		return runWorkloadInForeground(ctx, manager, name, func() error {
+			return manager.RestartWorkloadForeground(ctx, name)
+		})
+	}
+
+	group, err := manager.RestartWorkloads(ctx, []string{name})
+	if err != nil {
+		return err
+	}
+	if err := group.Wait(); err != nil {
+		return fmt.Errorf("failed to start container %s: %v", name, err)
+	}
+
+	printResult(name, "Container %s started successfully", name)
+	return nil
+}
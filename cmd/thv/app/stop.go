@@ -1,42 +1,66 @@
 package app
 
 import (
+	"context"
 	"errors"
 	"fmt"
+	"time"
 
 	"github.com/spf13/cobra"
-	"golang.org/x/sync/errgroup"
 
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
 	"github.com/stacklok/toolhive/pkg/workloads"
 )
 
 var stopCmd = &cobra.Command{
 	Use:   "stop [container-name]",
 	Short: "Stop an MCP server",
-	Long:  `Stop a running MCP server managed by ToolHive.`,
-	Args:  validateStopArgs,
-	RunE:  stopCmdFunc,
+	Long: `Stop a running MCP server managed by ToolHive.
+
+The server is sent its stop signal and given a grace period to exit before it
+is killed. Unless --timeout or --signal are given, the stop timeout and signal
+the server was run with are used (see thv run --stop-timeout and --stop-signal).
+
+Use --all to stop all running servers, or --filter to stop the running servers
+matching the filters, e.g. --filter name="github-*" or --filter label=team=dev.
+The filters are the same as for thv list. Only containers managed by ToolHive
+are stopped. In an interactive session, the servers to stop are listed and must
+be confirmed, unless --force is given. The servers are stopped concurrently, and
+a summary of the servers which were stopped and of those which failed to stop is
+printed; the command fails if any of them failed.`,
+	Args: validateStopArgs,
+	RunE: stopCmdFunc,
 }
 
 var (
 	stopTimeout int
+	stopSignal  string
 	stopAll     bool
+	stopFilters []string
+	stopForce   bool
 )
 
 func init() {
-	stopCmd.Flags().IntVar(&stopTimeout, "timeout", 30, "Timeout in seconds before forcibly stopping the container")
+	stopCmd.Flags().IntVar(&stopTimeout, "timeout", 0,
+		"Timeout in seconds before forcibly stopping the container (defaults to the server's stop timeout, or 30)")
+	stopCmd.Flags().StringVar(&stopSignal, "signal", "",
+		"Signal used to stop the container (defaults to the server's stop signal, or the image's)")
 	stopCmd.Flags().BoolVar(&stopAll, "all", false, "Stop all running MCP servers")
+	stopCmd.Flags().StringArrayVar(&stopFilters, "filter", nil,
+		"Stop the running servers matching the filter (format: key=value, can be repeated)")
+	stopCmd.Flags().BoolVarP(&stopForce, "force", "f", false, "Do not ask for confirmation when stopping several servers")
 }
 
 // validateStopArgs validates the arguments for the stop command
 func validateStopArgs(cmd *cobra.Command, args []string) error {
-	// Check if --all flag is set
+	// Check if --all or --filter flag is set
 	all, _ := cmd.Flags().GetBool("all")
+	filters, _ := cmd.Flags().GetStringArray("filter")
 
-	if all {
-		// If --all is set, no arguments should be provided
+	if all || len(filters) > 0 {
+		// If --all or --filter is set, no arguments should be provided
 		if len(args) > 0 {
-			return fmt.Errorf("no arguments should be provided when --all flag is set")
+			return fmt.Errorf("no arguments should be provided when --all or --filter flag is set")
 		}
 	} else {
 		// If --all is not set, exactly one argument should be provided
@@ -56,61 +80,83 @@ func stopCmdFunc(cmd *cobra.Command, args []string) error {
 		return fmt.Errorf("failed to create container manager: %v", err)
 	}
 
-	var group *errgroup.Group
+	options, err := stopOptionsFromFlags()
+	if err != nil {
+		return err
+	}
 
-	// Check if --all flag is set
-	if stopAll {
-		// Get list of all running workloads first
-		workloadList, err := manager.ListWorkloads(ctx, false) // false = only running workloads
-		if err != nil {
-			return fmt.Errorf("failed to list workloads: %v", err)
-		}
+	// Check if --all or --filter flag is set
+	if stopAll || len(stopFilters) > 0 {
+		return stopSelectedWorkloads(ctx, manager, options)
+	}
 
-		// Extract workload names
-		var workloadNames []string
-		for _, workload := range workloadList {
-			workloadNames = append(workloadNames, workload.Name)
-		}
+	// Get container name
+	containerName := args[0]
 
-		if len(workloadNames) == 0 {
-			fmt.Println("No running workloads to stop")
+	// Stop a single workload
+	group, err := manager.StopWorkloads(ctx, []string{containerName}, options)
+	if err != nil {
+		// If the container is not found or not running, treat as a non-fatal error.
+		if errors.Is(err, workloads.ErrContainerNotFound) ||
+			errors.Is(err, workloads.ErrContainerNotRunning) ||
+			errors.Is(err, workloads.ErrInvalidWorkloadName) {
+			printInfo("Container %s is not running", containerName)
 			return nil
 		}
+		return fmt.Errorf("unexpected error stopping container: %v", err)
+	}
 
-		// Stop all workloads using the bulk method
-		group, err = manager.StopWorkloads(ctx, workloadNames)
-		if err != nil {
-			return fmt.Errorf("failed to stop all containers: %v", err)
-		}
+	// Since the stop operation is asynchronous, wait for the group to finish.
+	if err := group.Wait(); err != nil {
+		return fmt.Errorf("failed to stop container %s: %v", containerName, err)
+	}
+	printResult(containerName, "Container %s stopped successfully", containerName)
+	return nil
+}
 
-		// Since the stop operation is asynchronous, wait for the group to finish.
-		if err := group.Wait(); err != nil {
-			return fmt.Errorf("failed to stop all containers: %v", err)
-		}
-		fmt.Println("All containers stopped successfully")
-	} else {
-		// Get container name
-		containerName := args[0]
+// stopSelectedWorkloads stops all running workloads, or those matching the filters
+func stopSelectedWorkloads(ctx context.Context, manager workloads.Manager, options *rt.StopOptions) error {
+	// Get list of the running workloads to stop first
+	workloadNames, err := selectWorkloadNames(ctx, manager, stopFilters, false)
+	if err != nil {
+		return err
+	}
+	if len(workloadNames) == 0 {
+		printInfo("No running workloads to stop")
+		return nil
+	}
 
-		// Stop a single workload
-		group, err = manager.StopWorkloads(ctx, []string{containerName})
+	confirmed, err := confirmBulkOperation("stopped", workloadNames, stopForce)
+	if err != nil {
+		return err
+	}
+	if !confirmed {
+		printInfo("Aborted")
+		return nil
+	}
+
+	// Stop the workloads concurrently, one group per workload so the outcome of each is known
+	results := runBulkOperation(workloadNames, func(name string) error {
+		group, err := manager.StopWorkloads(ctx, []string{name}, options)
 		if err != nil {
-			// If the container is not found or not running, treat as a non-fatal error.
-			if errors.Is(err, workloads.ErrContainerNotFound) ||
-				errors.Is(err, workloads.ErrContainerNotRunning) ||
-				errors.Is(err, workloads.ErrInvalidWorkloadName) {
-				fmt.Printf("Container %s is not running\n", containerName)
-				return nil
-			}
-			return fmt.Errorf("unexpected error stopping container: %v", err)
+			return err
 		}
+		return group.Wait()
+	})
+	return printBulkSummary("stopped", results)
+}
 
-		// Since the stop operation is asynchronous, wait for the group to finish.
-		if err := group.Wait(); err != nil {
-			return fmt.Errorf("failed to stop container %s: %v", containerName, err)
-		}
-		fmt.Printf("Container %s stopped successfully\n", containerName)
+// stopOptionsFromFlags returns the stop options given on the command line,
+// or nil to stop the servers with their own defaults
+func stopOptionsFromFlags() (*rt.StopOptions, error) {
+	if stopTimeout < 0 {
+		return nil, fmt.Errorf("--timeout must not be negative")
 	}
-
-	return nil
+	if stopTimeout == 0 && stopSignal == "" {
+		return nil, nil
+	}
+	return &rt.StopOptions{
+		Timeout: time.Duration(stopTimeout) * time.Second,
+		Signal:  stopSignal,
+	}, nil
 }
@@ -0,0 +1,137 @@
+package app
+
+import (
+	"fmt"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/config"
+)
+
+var addTrustedSignerCmd = &cobra.Command{
+	Use:   "add-trusted-signer <identity>",
+	Short: "Trust a signer of MCP server images",
+	Long: `Trust a sigstore signer of MCP server images which have no provenance information in the registry.
+
+With --image-verification=warn (the default), the signatures of such images are verified
+against the trusted signers, and a warning is printed when an image is not signed by any of them.
+With --image-verification=enabled, images which are not signed by a trusted signer are refused,
+as are images without provenance information if no signers are trusted.
+
+The identity is the signer identity of the signing certificate: an email address for images signed
+with cosign by a user, or the path of the workflow for images signed in GitHub Actions, in which
+case --repository selects the repository of the workflow. --issuer is required, and so is
+--repository for workflow paths, so that signatures by another issuer or by the workflow with
+the same path in another repository are not trusted.
+
+Examples:
+  thv config add-trusted-signer jane@example.com --issuer https://accounts.google.com
+  thv config add-trusted-signer /.github/workflows/release.yml \
+    --issuer https://token.actions.githubusercontent.com --repository https://github.com/example/mcp-server`,
+	Args: cobra.ExactArgs(1),
+	RunE: addTrustedSignerCmdFunc,
+}
+
+var removeTrustedSignerCmd = &cobra.Command{
+	Use:   "remove-trusted-signer <identity>",
+	Short: "Stop trusting a signer of MCP server images",
+	Long:  "Remove the trusted signers with the given identity.",
+	Args:  cobra.ExactArgs(1),
+	RunE:  removeTrustedSignerCmdFunc,
+}
+
+var listTrustedSignersCmd = &cobra.Command{
+	Use:   "list-trusted-signers",
+	Short: "List the trusted signers of MCP server images",
+	Long:  "List the signers which MCP server images without provenance information in the registry are verified against.",
+	RunE:  listTrustedSignersCmdFunc,
+}
+
+var (
+	trustedSignerIssuer     string
+	trustedSignerRepository string
+)
+
+func init() {
+	addTrustedSignerCmd.Flags().StringVar(&trustedSignerIssuer, "issuer", "",
+		"OIDC issuer of the signing certificate (e.g., https://token.actions.githubusercontent.com)")
+	addTrustedSignerCmd.Flags().StringVar(&trustedSignerRepository, "repository", "",
+		"Source repository of images signed in CI, required for workflow paths (e.g., https://github.com/example/mcp-server)")
+	_ = addTrustedSignerCmd.MarkFlagRequired("issuer")
+}
+
+func addTrustedSignerCmdFunc(_ *cobra.Command, args []string) error {
+	signer := config.TrustedSigner{
+		Identity:   args[0],
+		Issuer:     trustedSignerIssuer,
+		Repository: trustedSignerRepository,
+	}
+	if err := signer.Validate(); err != nil {
+		return err
+	}
+
+	err := config.UpdateConfig(func(c *config.Config) {
+		for _, existing := range c.TrustedSigners {
+			if existing == signer {
+				return
+			}
+		}
+		c.TrustedSigners = append(c.TrustedSigners, signer)
+	})
+	if err != nil {
+		return fmt.Errorf("failed to update configuration: %w", err)
+	}
+
+	fmt.Printf("Successfully added trusted signer: %s\n", signer.Identity)
+	return nil
+}
+
+func removeTrustedSignerCmdFunc(_ *cobra.Command, args []string) error {
+	identity := args[0]
+
+	removed := false
+	err := config.UpdateConfig(func(c *config.Config) {
+		var kept []config.TrustedSigner
+		for _, signer := range c.TrustedSigners {
+			if signer.Identity == identity {
+				removed = true
+				continue
+			}
+			kept = append(kept, signer)
+		}
+		c.TrustedSigners = kept
+	})
+	if err != nil {
+		return fmt.Errorf("failed to update configuration: %w", err)
+	}
+
+	if !removed {
+		fmt.Printf("Signer %s is not trusted.\n", identity)
+		return nil
+	}
+	fmt.Printf("Successfully removed trusted signer: %s\n", identity)
+	return nil
+}
+
+func listTrustedSignersCmdFunc(_ *cobra.Command, _ []string) error {
+	cfg := config.GetConfig()
+
+	if len(cfg.TrustedSigners) == 0 {
+		fmt.Println("No signers are currently trusted.")
+		return nil
+	}
+
+	fmt.Println("Trusted signers:")
+	for _, signer := range cfg.TrustedSigners {
+		fmt.Printf("  - %s", signer.Identity)
+		if signer.Issuer != "" {
+			fmt.Printf(" (issuer: %s)", signer.Issuer)
+		}
+		if signer.Repository != "" {
+			fmt.Printf(" (repository: %s)", signer.Repository)
+		}
+		fmt.Println()
+	}
+
+	return nil
+}
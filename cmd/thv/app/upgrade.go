@@ -0,0 +1,501 @@
+package app
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"net/http"
+	"os"
+	"sort"
+	"strings"
+	"text/tabwriter"
+	"time"
+
+	"github.com/spf13/cobra"
+
+	"github.com/stacklok/toolhive/pkg/compose"
+	"github.com/stacklok/toolhive/pkg/container"
+	"github.com/stacklok/toolhive/pkg/container/images"
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/healthcheck"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/runner"
+	"github.com/stacklok/toolhive/pkg/runner/retriever"
+	"github.com/stacklok/toolhive/pkg/upgrade"
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+var upgradeCmd = &cobra.Command{
+	Use:   "upgrade [server-name...]",
+	Short: "Upgrade MCP servers to the latest version of their image",
+	Long: `Upgrade MCP servers to the latest version of their image.
+
+For each running server, the latest version of its image reference is pulled
+and verified. If the image has changed, a new container of the server is
+started with the new image while the previous container keeps serving the
+clients. Once the new container responds, the proxy of the server is switched
+to it, and the previous container is stopped after the requests in flight to
+it completed, so the server stays available during the upgrade. Images pinned
+by digest are never upgraded.
+
+Servers of the stdio transport cannot be switched to a new container, and are
+stopped and started again with the new image instead, so they are unavailable
+until their new container has started.
+
+If the new container does not respond within a minute, the server keeps running
+its previous container, or a restarted server is started again with its
+previous image, and the upgrade is recorded as rolled back, so it is not
+retried.
+
+Servers run with a release channel or version constraint (e.g. thv run
+server-name@^1.2) are upgraded to the newest version within it, rather than to
+the latest version of their current image reference.
+
+Each upgrade is recorded in the server's upgrade history, which can be shown
+with --history. Use thv rollback to revert a bad upgrade; a server is not
+upgraded again to an image that was rolled back.
+
+With --auto, only the servers started with --auto-upgrade are upgraded, and
+only while inside their --upgrade-window, if any. This is intended to be run
+periodically, for example from cron or a systemd timer:
+
+  */15 * * * * thv upgrade --auto
+
+With --prune, the images the upgraded servers ran before are removed once they
+are no longer needed, keeping the disk usage of frequently updated servers
+bounded. The images of the last --keep-images upgrades of each server are kept,
+so they can still be rolled back, and images used by any server are never
+removed:
+
+  */15 * * * * thv upgrade --auto --prune --keep-images 2`,
+	RunE: upgradeCmdFunc,
+}
+
+var (
+	upgradeAuto        bool
+	upgradeHistory     bool
+	upgradeVerifyImage string
+	upgradePrune       bool
+	upgradeKeepImages  int
+)
+
+func init() {
+	upgradeCmd.Flags().BoolVar(&upgradeAuto, "auto", false,
+		"Only upgrade the servers with automatic upgrades enabled that are inside their maintenance window")
+	upgradeCmd.Flags().BoolVar(&upgradeHistory, "history", false, "Show the upgrade history of the servers instead")
+	upgradeCmd.Flags().StringVar(
+		&upgradeVerifyImage,
+		"image-verification",
+		retriever.VerifyImageWarn,
+		fmt.Sprintf(
+			"Set image verification mode (%s, %s, %s)",
+			retriever.VerifyImageWarn,
+			retriever.VerifyImageEnabled,
+			retriever.VerifyImageDisabled,
+		),
+	)
+	upgradeCmd.Flags().BoolVar(&upgradePrune, "prune", false,
+		"Remove the images the upgraded servers no longer use, except those kept for rollbacks")
+	upgradeCmd.Flags().IntVar(&upgradeKeepImages, "keep-images", upgrade.DefaultKeepImages,
+		"Number of previous images of each server kept for rollbacks when pruning")
+}
+
+func upgradeCmdFunc(cmd *cobra.Command, args []string) error {
+	ctx := cmd.Context()
+	if upgradeKeepImages < 0 {
+		return fmt.Errorf("invalid --keep-images %d: must not be negative", upgradeKeepImages)
+	}
+
+	runtime, err := container.NewFactory().Create(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to create container runtime: %v", err)
+	}
+	manager := workloads.NewManagerFromRuntime(runtime)
+
+	observed, err := observeServers(ctx, runtime)
+	if err != nil {
+		return err
+	}
+
+	names := args
+	if len(names) == 0 {
+		for name := range observed {
+			names = append(names, name)
+		}
+		sort.Strings(names)
+	}
+
+	if upgradeHistory {
+		return printUpgradeHistory(ctx, names, observed)
+	}
+
+	now := time.Now()
+	imageManager := images.NewImageManager(ctx)
+	var failed, upgraded []string
+	for _, name := range names {
+		obs, ok := observed[name]
+		if !ok {
+			return fmt.Errorf("server %s not found", name)
+		}
+		if upgradeAuto && !autoUpgradeDue(obs, now) {
+			continue
+		}
+
+		changed, err := upgradeServer(ctx, manager, imageManager, obs)
+		if err != nil {
+			logger.Errorf("Failed to upgrade %s: %v", name, err)
+			failed = append(failed, name)
+			continue
+		}
+		if changed {
+			printResult(name, "Upgraded %s", name)
+			upgraded = append(upgraded, name)
+		} else {
+			printInfo("%s is up to date", name)
+		}
+	}
+
+	if upgradePrune {
+		pruneSupersededImages(ctx, runtime, imageManager, upgraded)
+	}
+
+	if len(failed) > 0 {
+		return fmt.Errorf("failed to upgrade %s", strings.Join(failed, ", "))
+	}
+	return nil
+}
+
+// pruneSupersededImages removes the images the given servers ran before being upgraded, except those kept
+// for rollbacks and those used by any server. Failures are logged, since the upgrades themselves succeeded.
+func pruneSupersededImages(ctx context.Context, runtime rt.Runtime, imageManager images.ImageManager, names []string) {
+	if len(names) == 0 {
+		return
+	}
+
+	// Observe the servers again, so the images the servers were just upgraded to count as in use
+	observed, err := observeServers(ctx, runtime)
+	if err != nil {
+		logger.Warnf("Failed to list the servers, not pruning images: %v", err)
+		return
+	}
+	inUse := make(map[string]bool)
+	for _, obs := range observed {
+		inUse[obs.Container.ImageID] = true
+	}
+
+	removed := make(map[string]bool)
+	for _, name := range names {
+		obs, ok := observed[name]
+		if !ok {
+			continue
+		}
+		history, err := upgrade.LoadHistory(ctx, labels.GetContainerBaseName(obs.Container.Labels))
+		if err != nil {
+			logger.Warnf("Failed to load the upgrade history of %s, not pruning its images: %v", name, err)
+			continue
+		}
+		for _, imageID := range history.SupersededImages(upgradeKeepImages, inUse) {
+			if removed[imageID] {
+				continue
+			}
+			exists, err := imageManager.ImageExists(ctx, imageID)
+			if err != nil || !exists {
+				continue
+			}
+			if err := imageManager.RemoveImage(ctx, imageID); err != nil {
+				logger.Warnf("Failed to remove superseded image %s of %s: %v", shortDigest(imageID), name, err)
+				continue
+			}
+			removed[imageID] = true
+			printInfo("Removed superseded image %s of %s", shortDigest(imageID), name)
+		}
+	}
+}
+
+// autoUpgradeDue returns true if the server has automatic upgrades enabled and is inside its maintenance window
+func autoUpgradeDue(obs compose.ObservedServer, now time.Time) bool {
+	if obs.Config == nil || !obs.Config.AutoUpgrade {
+		return false
+	}
+	if obs.Config.UpgradeWindow == "" {
+		return true
+	}
+	window, err := upgrade.ParseWindow(obs.Config.UpgradeWindow)
+	if err != nil {
+		logger.Warnf("Skipping %s: %v", obs.Name, err)
+		return false
+	}
+	return window.Contains(now)
+}
+
+// upgradeServer pulls the latest version of the image of a server and recreates the server if the image changed
+func upgradeServer(
+	ctx context.Context,
+	manager workloads.Manager,
+	imageManager images.ImageManager,
+	obs compose.ObservedServer,
+) (bool, error) {
+	if obs.Config == nil {
+		return false, fmt.Errorf("no recorded configuration")
+	}
+	if obs.Container.State != "running" {
+		logger.Infof("%s is not running, skipping", obs.Name)
+		return false, nil
+	}
+
+	image := obs.Config.Image
+	if strings.Contains(image, "@") {
+		logger.Infof("%s is pinned to %s, skipping", obs.Name, image)
+		return false, nil
+	}
+
+	target, err := upgradeTarget(ctx, obs.Config)
+	if err != nil {
+		return false, err
+	}
+	if err := retriever.RefreshImage(ctx, target, upgradeVerifyImage); err != nil {
+		return false, err
+	}
+
+	newImageID, err := imageManager.GetImageID(ctx, target)
+	if err != nil {
+		return false, err
+	}
+	if newImageID == "" || newImageID == obs.Container.ImageID {
+		return false, nil
+	}
+
+	baseName := labels.GetContainerBaseName(obs.Container.Labels)
+	history, err := upgrade.LoadHistory(ctx, baseName)
+	if err != nil {
+		return false, err
+	}
+	if history.IsRolledBack(newImageID) {
+		// Point the image reference back at the running image, so the rolled back image
+		// isn't picked up when the server is restarted
+		logger.Warnf("The latest image of %s was rolled back before, skipping", obs.Name)
+		return false, imageManager.TagImage(ctx, obs.Container.ImageID, image)
+	}
+
+	transition := upgrade.Transition{
+		Kind:        upgrade.TransitionUpgrade,
+		Time:        time.Now().UTC(),
+		Image:       image,
+		FromImageID: obs.Container.ImageID,
+		ToImageID:   newImageID,
+		FromDigest:  localRepoDigest(ctx, imageManager, image, obs.Container.ImageID),
+		ToDigest:    localRepoDigest(ctx, imageManager, image, newImageID),
+		Config:      obs.Config,
+	}
+
+	printInfo("Upgrading %s to %s...", obs.Name, shortDigest(newImageID))
+	return upgradeAndRecord(ctx, manager, imageManager, obs, target, history, transition)
+}
+
+// upgradeAndRecord moves a server to the image of the target, restoring its previous image if the upgrade
+// fails, and records the upgrade or its rollback in the upgrade history of the server
+func upgradeAndRecord(
+	ctx context.Context,
+	manager workloads.Manager,
+	imageManager images.ImageManager,
+	obs compose.ObservedServer,
+	target string,
+	history *upgrade.History,
+	transition upgrade.Transition,
+) (bool, error) {
+	baseName := labels.GetContainerBaseName(obs.Container.Labels)
+	if err := saveUpgradedImage(ctx, obs.Config, target); err != nil {
+		return false, err
+	}
+	if restarted, err := applyUpgrade(ctx, manager, baseName, obs.Name, target); err != nil {
+		logger.Warnf("The upgrade of %s failed, restoring its previous image: %v", obs.Name, err)
+		if restoreErr := restoreServer(ctx, manager, imageManager, obs, restarted); restoreErr != nil {
+			return false, fmt.Errorf("%v, and failed to restore the previous image: %v", err, restoreErr)
+		}
+		transition.RolledBack = true
+		history.Record(transition)
+		if saveErr := upgrade.SaveHistory(ctx, baseName, history); saveErr != nil {
+			logger.Warnf("Failed to record the rolled back upgrade of %s: %v", obs.Name, saveErr)
+		}
+		return false, fmt.Errorf("rolled back to the previous image: %v", err)
+	}
+
+	history.Record(transition)
+	if err := upgrade.SaveHistory(ctx, baseName, history); err != nil {
+		return true, err
+	}
+	return true, nil
+}
+
+// applyUpgrade moves a server to a new container of the upgraded image. The proxy of the server switches it
+// to the new container once it responds, so its clients are not interrupted; servers whose proxy cannot switch
+// them are restarted with the image instead. It returns whether the server was restarted.
+func applyUpgrade(ctx context.Context, manager workloads.Manager, baseName, name, image string) (bool, error) {
+	err := runner.SwitchContainer(ctx, baseName, image, upgradeHealthTimeout)
+	if !errors.Is(err, runner.ErrSwitchNotHandled) {
+		return false, err
+	}
+	logger.Warnf("The proxy of %s cannot switch it to a new container, restarting it", name)
+	if err := recreateWorkload(ctx, manager, name); err != nil {
+		return true, err
+	}
+	return true, waitForHealthy(ctx, manager, name, upgradeHealthTimeout)
+}
+
+// upgradeTarget returns the image reference a server is upgraded to: the newest version within its release
+// channel or version constraint if it was run with one, and otherwise its current image reference
+func upgradeTarget(ctx context.Context, config *runner.RunConfig) (string, error) {
+	if config.VersionConstraint == "" {
+		return config.Image, nil
+	}
+	return retriever.ResolveServerVersion(ctx, config.Image, config.VersionConstraint)
+}
+
+// saveUpgradedImage records the new image reference of a server upgraded to a newer version within its
+// version constraint, so the server is recreated from it
+func saveUpgradedImage(ctx context.Context, config *runner.RunConfig, image string) error {
+	if config.Image == image {
+		return nil
+	}
+	upgraded := *config
+	upgraded.Image = image
+	if err := runner.NewRunner(&upgraded).SaveState(ctx); err != nil {
+		return fmt.Errorf("failed to save the run configuration with image %s: %v", image, err)
+	}
+	return nil
+}
+
+// upgradeHealthTimeout is how long an upgraded server has to report itself healthy before it is rolled back
+const upgradeHealthTimeout = time.Minute
+
+// restoreServer restores the image and configuration a server whose upgrade failed ran before. A server which
+// was restarted with the new image is started again with them, while a server whose proxy was not switched
+// to a new container still runs its previous container.
+func restoreServer(
+	ctx context.Context,
+	manager workloads.Manager,
+	imageManager images.ImageManager,
+	obs compose.ObservedServer,
+	restarted bool,
+) error {
+	// Point the image reference back at the previous image, since it was moved to the new image when pulled
+	if err := imageManager.TagImage(ctx, obs.Container.ImageID, obs.Config.Image); err != nil {
+		return err
+	}
+	if err := runner.NewRunner(obs.Config).SaveState(ctx); err != nil {
+		return fmt.Errorf("failed to restore the run configuration: %v", err)
+	}
+	if !restarted {
+		return nil
+	}
+	return recreateWorkload(ctx, manager, obs.Name)
+}
+
+// waitForHealthy waits until the proxy of a workload reports the MCP server behind it as healthy,
+// or the timeout expires
+func waitForHealthy(ctx context.Context, manager workloads.Manager, name string, timeout time.Duration) error {
+	ctx, cancel := context.WithTimeout(ctx, timeout)
+	defer cancel()
+
+	ticker := time.NewTicker(time.Second)
+	defer ticker.Stop()
+
+	for {
+		workload, err := manager.GetWorkload(ctx, name)
+		if err != nil {
+			return err
+		}
+		if workload.Status != workloads.WorkloadStatusRunning {
+			return fmt.Errorf("%s is %s", name, workload.Status)
+		}
+
+		httpClient, proxyURL := &http.Client{Timeout: 5 * time.Second}, workload.URL
+		if workload.ListenSocket != "" {
+			httpClient, proxyURL = healthcheck.NewSocketClient(workload.ListenSocket, 5*time.Second), healthcheck.SocketURL
+		}
+		if proxyURL == "" {
+			logger.Debugf("Cannot determine the health endpoint of %s, not waiting for it", name)
+			return nil
+		}
+		health, err := healthcheck.Fetch(ctx, httpClient, proxyURL)
+		if err == nil && health.Status == healthcheck.StatusHealthy {
+			return nil
+		}
+
+		select {
+		case <-ctx.Done():
+			if err != nil {
+				return fmt.Errorf("%s did not become healthy: %v", name, err)
+			}
+			return fmt.Errorf("%s did not become healthy, it is %s", name, health.Status)
+		case <-ticker.C:
+		}
+	}
+}
+
+// recreateWorkload stops a workload and starts it again from its saved state. The container is recreated
+// if its image reference now resolves to a different image, so the workload is unavailable until its new
+// container has started.
+func recreateWorkload(ctx context.Context, manager workloads.Manager, name string) error {
+	group, err := manager.StopWorkloads(ctx, []string{name}, nil)
+	if err != nil {
+		return err
+	}
+	if err := group.Wait(); err != nil {
+		return err
+	}
+
+	group, err = manager.RestartWorkloads(ctx, []string{name})
+	if err != nil {
+		return err
+	}
+	return group.Wait()
+}
+
+// localRepoDigest returns the registry digest of the given local image for the repository of image, if known
+func localRepoDigest(ctx context.Context, imageManager images.ImageManager, image, imageID string) string {
+	repoDigests, err := imageManager.GetRepoDigests(ctx, imageID)
+	if err != nil {
+		logger.Debugf("Failed to look up the digests of %s: %v", imageID, err)
+		return ""
+	}
+	return images.RepoDigest(image, repoDigests)
+}
+
+func printUpgradeHistory(ctx context.Context, names []string, observed map[string]compose.ObservedServer) error {
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 3, ' ', 0)
+	fmt.Fprintln(w, "SERVER\tTIME\tKIND\tIMAGE\tFROM\tTO")
+	for _, name := range names {
+		obs, ok := observed[name]
+		if !ok {
+			return fmt.Errorf("server %s not found", name)
+		}
+		history, err := upgrade.LoadHistory(ctx, labels.GetContainerBaseName(obs.Container.Labels))
+		if err != nil {
+			return err
+		}
+		for _, transition := range history.Transitions {
+			from, to := transition.FromDigest, transition.ToDigest
+			if from == "" || to == "" {
+				from, to = transition.FromImageID, transition.ToImageID
+			}
+			kind := string(transition.Kind)
+			if transition.RolledBack {
+				kind += " (rolled back)"
+			}
+			fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\t%s\n",
+				name,
+				transition.Time.Local().Format(time.RFC3339),
+				kind,
+				transition.Image,
+				shortDigest(from),
+				shortDigest(to),
+			)
+		}
+	}
+	if err := w.Flush(); err != nil {
+		return fmt.Errorf("failed to flush tabwriter: %v", err)
+	}
+	return nil
+}
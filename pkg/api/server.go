@@ -31,6 +31,7 @@ import (
 	"github.com/stacklok/toolhive/pkg/client"
 	"github.com/stacklok/toolhive/pkg/container"
 	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/networking"
 	"github.com/stacklok/toolhive/pkg/registry"
 	"github.com/stacklok/toolhive/pkg/workloads"
 )
@@ -43,6 +44,11 @@ const (
 )
 
 func setupTCPListener(address string) (net.Listener, error) {
+	// Use the socket passed by systemd socket activation, if any
+	listener, err := networking.ActivationListener()
+	if err != nil || listener != nil {
+		return listener, err
+	}
 	return net.Listen("tcp", address)
 }
 
@@ -135,7 +135,7 @@ func (s *WorkloadRoutes) stopWorkload(w http.ResponseWriter, r *http.Request) {
 	name := chi.URLParam(r, "name")
 
 	// Use the bulk method with a single workload
-	_, err := s.manager.StopWorkloads(ctx, []string{name})
+	_, err := s.manager.StopWorkloads(ctx, []string{name}, nil)
 	if err != nil {
 		if errors.Is(err, workloads.ErrInvalidWorkloadName) {
 			http.Error(w, "Invalid workload name: "+err.Error(), http.StatusBadRequest)
@@ -237,6 +237,7 @@ func (s *WorkloadRoutes) createWorkload(w http.ResponseWriter, r *http.Request)
 		req.Image,
 		"", // We do not let the user specify a CA cert path here.
 		retriever.VerifyImageWarn,
+		retriever.PullDefault,
 	)
 	if err != nil {
 		if errors.Is(err, retriever.ErrImageNotFound) {
@@ -338,7 +339,7 @@ func (s *WorkloadRoutes) stopWorkloadsBulk(w http.ResponseWriter, r *http.Reques
 
 	// Note that this is an asynchronous operation.
 	// The request is not blocked on completion.
-	_, err := s.manager.StopWorkloads(ctx, req.Names)
+	_, err := s.manager.StopWorkloads(ctx, req.Names, nil)
 	if err != nil {
 		if errors.Is(err, workloads.ErrInvalidWorkloadName) {
 			http.Error(w, "Invalid workload name: "+err.Error(), http.StatusBadRequest)
@@ -443,7 +444,7 @@ func (s *WorkloadRoutes) getLogsForWorkload(w http.ResponseWriter, r *http.Reque
 	ctx := r.Context()
 	name := chi.URLParam(r, "name")
 
-	logs, err := s.manager.GetLogs(ctx, name, false)
+	logs, err := s.manager.GetLogs(ctx, name, runtime.LogOptions{Tail: runtime.DefaultLogTail})
 	if err != nil {
 		if errors.Is(err, workloads.ErrContainerNotFound) {
 			http.Error(w, "Workload not found", http.StatusNotFound)
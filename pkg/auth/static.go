@@ -0,0 +1,89 @@
+// Package auth provides authentication and authorization utilities.
+package auth
+
+import (
+	"context"
+	"crypto/subtle"
+	"fmt"
+	"net/http"
+	"os"
+	"strings"
+
+	"github.com/golang-jwt/jwt/v5"
+)
+
+const (
+	// StaticTokenEnvVar is the environment variable the static token of the proxy can be set with,
+	// instead of a flag which is visible in the process list
+	// #nosec G101 - this is an environment variable name, not a credential
+	StaticTokenEnvVar = "TOOLHIVE_AUTH_TOKEN"
+
+	// StaticTokenEnvReference refers to the static token set with StaticTokenEnvVar. It is saved in the
+	// state of a server instead of the token itself, which must be set again when the server is restarted.
+	StaticTokenEnvReference = staticTokenEnvPrefix + StaticTokenEnvVar
+
+	// staticTokenFilePrefix is the prefix of static token values which are read from a file
+	staticTokenFilePrefix = "file:"
+	// staticTokenEnvPrefix is the prefix of static token values which are read from an environment variable
+	staticTokenEnvPrefix = "env:"
+)
+
+// ResolveStaticToken returns the static token of a value, which is either the token itself,
+// file:PATH for a token read from a file, or env:NAME for a token read from an environment variable
+func ResolveStaticToken(value string) (string, error) {
+	token := value
+	if path, ok := strings.CutPrefix(value, staticTokenFilePrefix); ok {
+		data, err := os.ReadFile(path) // #nosec G304 - the path is provided by the user
+		if err != nil {
+			return "", fmt.Errorf("failed to read auth token file: %w", err)
+		}
+		token = strings.TrimSpace(string(data))
+	} else if name, ok := strings.CutPrefix(value, staticTokenEnvPrefix); ok {
+		token = os.Getenv(name)
+		if token == "" {
+			return "", fmt.Errorf("the auth token is not saved with the server, set it again with %s", name)
+		}
+	}
+	if token == "" {
+		return "", fmt.Errorf("the auth token is empty")
+	}
+	return token, nil
+}
+
+// IsStaticTokenReference returns true if the value refers to a static token in a file or in an
+// environment variable, rather than being the token itself
+func IsStaticTokenReference(value string) bool {
+	return strings.HasPrefix(value, staticTokenFilePrefix) || strings.HasPrefix(value, staticTokenEnvPrefix)
+}
+
+// NewStaticTokenMiddleware creates an HTTP middleware which only lets requests through whose
+// Authorization header carries the static token of the value as Bearer token.
+// The value is resolved with ResolveStaticToken.
+func NewStaticTokenMiddleware(value string) (func(http.Handler) http.Handler, error) {
+	token, err := ResolveStaticToken(value)
+	if err != nil {
+		return nil, err
+	}
+	expected := []byte(token)
+
+	return func(next http.Handler) http.Handler {
+		return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			bearer, ok := strings.CutPrefix(r.Header.Get("Authorization"), "Bearer ")
+			if !ok || subtle.ConstantTimeCompare([]byte(bearer), expected) != 1 {
+				w.Header().Set("WWW-Authenticate", `Bearer realm="toolhive"`)
+				http.Error(w, "A valid auth token is required", http.StatusUnauthorized)
+				return
+			}
+
+			// Add claims for the holder of the token, so authorization policies can be applied to them
+			claims := jwt.MapClaims{
+				"sub":  "static-token",
+				"iss":  "toolhive-local",
+				"aud":  "toolhive",
+				"name": "Static Token Holder",
+			}
+			ctx := context.WithValue(r.Context(), ClaimsContextKey{}, claims)
+			next.ServeHTTP(w, r.WithContext(ctx))
+		})
+	}, nil
+}
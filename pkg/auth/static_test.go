@@ -0,0 +1,105 @@
+package auth
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestResolveStaticToken(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	tokenFile := filepath.Join(dir, "token")
+	require.NoError(t, os.WriteFile(tokenFile, []byte("file-secret\n"), 0600))
+	emptyFile := filepath.Join(dir, "empty")
+	require.NoError(t, os.WriteFile(emptyFile, nil, 0600))
+
+	tests := []struct {
+		name        string
+		value       string
+		expected    string
+		expectError bool
+	}{
+		{name: "Literal token", value: "secret", expected: "secret"},
+		{name: "Token file", value: "file:" + tokenFile, expected: "file-secret"},
+		{name: "Missing token file", value: "file:" + filepath.Join(dir, "missing"), expectError: true},
+		{name: "Empty token file", value: "file:" + emptyFile, expectError: true},
+		{name: "Empty token", value: "", expectError: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			token, err := ResolveStaticToken(tt.value)
+			if tt.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.expected, token)
+		})
+	}
+}
+
+func TestResolveStaticToken_Environment(t *testing.T) {
+	t.Setenv("THV_TEST_AUTH_TOKEN", "env-secret")
+
+	token, err := ResolveStaticToken("env:THV_TEST_AUTH_TOKEN")
+	require.NoError(t, err)
+	assert.Equal(t, "env-secret", token)
+
+	_, err = ResolveStaticToken("env:THV_TEST_UNSET_AUTH_TOKEN")
+	assert.ErrorContains(t, err, "set it again with THV_TEST_UNSET_AUTH_TOKEN")
+
+	assert.True(t, IsStaticTokenReference("env:THV_TEST_AUTH_TOKEN"))
+	assert.True(t, IsStaticTokenReference("file:/run/secrets/token"))
+	assert.False(t, IsStaticTokenReference("secret"))
+}
+
+func TestStaticTokenMiddleware(t *testing.T) {
+	t.Parallel()
+
+	middleware, err := NewStaticTokenMiddleware("secret")
+	require.NoError(t, err)
+	handler := middleware(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		claims, ok := GetClaimsFromContext(r.Context())
+		assert.True(t, ok)
+		assert.Equal(t, "static-token", claims["sub"])
+		w.WriteHeader(http.StatusOK)
+	}))
+
+	tests := []struct {
+		name           string
+		authorization  string
+		expectedStatus int
+	}{
+		{name: "Valid token", authorization: "Bearer secret", expectedStatus: http.StatusOK},
+		{name: "Missing header", expectedStatus: http.StatusUnauthorized},
+		{name: "Wrong token", authorization: "Bearer wrong", expectedStatus: http.StatusUnauthorized},
+		{name: "Token prefix", authorization: "Bearer secre", expectedStatus: http.StatusUnauthorized},
+		{name: "Not a Bearer token", authorization: "Basic secret", expectedStatus: http.StatusUnauthorized},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			req := httptest.NewRequest(http.MethodGet, "/sse", nil)
+			if tt.authorization != "" {
+				req.Header.Set("Authorization", tt.authorization)
+			}
+			rec := httptest.NewRecorder()
+			handler.ServeHTTP(rec, req)
+
+			assert.Equal(t, tt.expectedStatus, rec.Code)
+			if tt.expectedStatus == http.StatusUnauthorized {
+				assert.Equal(t, `Bearer realm="toolhive"`, rec.Header().Get("WWW-Authenticate"))
+			}
+		})
+	}
+}
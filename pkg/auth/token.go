@@ -173,8 +173,11 @@ func NewTokenValidator(ctx context.Context, config TokenValidatorConfig, allowOp
 
 // getKeyFromJWKS gets the key from the JWKS.
 func (v *TokenValidator) getKeyFromJWKS(ctx context.Context, token *jwt.Token) (interface{}, error) {
-	// Validate the signing method
-	if _, ok := token.Method.(*jwt.SigningMethodRSA); !ok {
+	// Validate the signing method. Only asymmetric methods are accepted, since the keys come from the
+	// public JWKS; the JWT library checks that the type of the key matches the method.
+	switch token.Method.(type) {
+	case *jwt.SigningMethodRSA, *jwt.SigningMethodRSAPSS, *jwt.SigningMethodECDSA:
+	default:
 		return nil, fmt.Errorf("unexpected signing method: %v", token.Header["alg"])
 	}
 
@@ -2,6 +2,8 @@ package auth
 
 import (
 	"context"
+	"crypto/ecdsa"
+	"crypto/elliptic"
 	"crypto/rand"
 	"crypto/rsa"
 	"encoding/json"
@@ -367,6 +369,85 @@ func TestTokenValidatorMiddleware(t *testing.T) {
 	}
 }
 
+func TestTokenValidator_SigningMethods(t *testing.T) {
+	t.Parallel()
+
+	rsaKey, err := rsa.GenerateKey(rand.Reader, 2048)
+	if err != nil {
+		t.Fatalf("Failed to generate RSA key pair: %v", err)
+	}
+	ecKey, err := ecdsa.GenerateKey(elliptic.P256(), rand.Reader)
+	if err != nil {
+		t.Fatalf("Failed to generate ECDSA key pair: %v", err)
+	}
+
+	// Create a key set with both public keys
+	keySet := jwk.NewSet()
+	for kid, publicKey := range map[string]any{"rsa-key": &rsaKey.PublicKey, "ec-key": &ecKey.PublicKey} {
+		key, err := jwk.FromRaw(publicKey)
+		if err != nil {
+			t.Fatalf("Failed to create JWK from public key: %v", err)
+		}
+		if err := key.Set(jwk.KeyIDKey, kid); err != nil {
+			t.Fatalf("Failed to set key ID: %v", err)
+		}
+		if err := keySet.AddKey(key); err != nil {
+			t.Fatalf("Failed to add key to set: %v", err)
+		}
+	}
+	jwksServer := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		w.Header().Set("Content-Type", "application/json")
+		_ = json.NewEncoder(w).Encode(keySet)
+	}))
+	t.Cleanup(jwksServer.Close)
+
+	validator, err := NewTokenValidator(context.Background(), TokenValidatorConfig{
+		Issuer:   "test-issuer",
+		Audience: "test-audience",
+		JWKSURL:  jwksServer.URL,
+	}, false)
+	if err != nil {
+		t.Fatalf("Failed to create token validator: %v", err)
+	}
+
+	testCases := []struct {
+		name      string
+		method    jwt.SigningMethod
+		kid       string
+		key       any
+		expectErr bool
+	}{
+		{name: "RS256", method: jwt.SigningMethodRS256, kid: "rsa-key", key: rsaKey},
+		{name: "PS256", method: jwt.SigningMethodPS256, kid: "rsa-key", key: rsaKey},
+		{name: "ES256", method: jwt.SigningMethodES256, kid: "ec-key", key: ecKey},
+		{name: "ES256 with RSA key ID", method: jwt.SigningMethodES256, kid: "rsa-key", key: ecKey, expectErr: true},
+		{name: "HS256", method: jwt.SigningMethodHS256, kid: "rsa-key", key: []byte("secret"), expectErr: true},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			token := jwt.NewWithClaims(tc.method, jwt.MapClaims{
+				"iss": "test-issuer",
+				"aud": "test-audience",
+				"exp": time.Now().Add(time.Hour).Unix(),
+			})
+			token.Header["kid"] = tc.kid
+			tokenString, err := token.SignedString(tc.key)
+			if err != nil {
+				t.Fatalf("Failed to sign token: %v", err)
+			}
+
+			_, err = validator.ValidateToken(context.Background(), tokenString)
+			if tc.expectErr && err == nil {
+				t.Errorf("Expected error but got nil")
+			} else if !tc.expectErr && err != nil {
+				t.Errorf("Expected no error but got %v", err)
+			}
+		})
+	}
+}
+
 // createTestOIDCServer creates a test OIDC discovery server that returns the given JWKS URL
 func createTestOIDCServer(_ *testing.T, jwksURL string) *httptest.Server {
 	return httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
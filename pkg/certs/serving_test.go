@@ -0,0 +1,107 @@
+package certs
+
+import (
+	"crypto/x509"
+	"encoding/pem"
+	"os"
+	"path/filepath"
+	"runtime"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+func TestSelfSignedServingConfig(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	dir := t.TempDir()
+	certFile := filepath.Join(dir, "certs", "server.crt")
+	keyFile := filepath.Join(dir, "certs", "server.key")
+
+	// The certificate is generated for the hosts, with a key only readable by the user
+	config, err := SelfSignedServingConfig(certFile, keyFile, []string{"localhost", "127.0.0.1"})
+	require.NoError(t, err)
+	require.Len(t, config.Certificates, 1)
+	cert, err := x509.ParseCertificate(config.Certificates[0].Certificate[0])
+	require.NoError(t, err)
+	assert.NoError(t, cert.VerifyHostname("localhost"))
+	assert.NoError(t, cert.VerifyHostname("127.0.0.1"))
+	if runtime.GOOS != "windows" {
+		info, err := os.Stat(keyFile)
+		require.NoError(t, err)
+		assert.Equal(t, os.FileMode(0600), info.Mode().Perm())
+	}
+
+	// The certificate is reused while it covers the hosts
+	reused, err := SelfSignedServingConfig(certFile, keyFile, []string{"localhost"})
+	require.NoError(t, err)
+	assert.Equal(t, config.Certificates[0].Certificate, reused.Certificates[0].Certificate)
+
+	// The certificate is generated again for hosts it does not cover
+	regenerated, err := SelfSignedServingConfig(certFile, keyFile, []string{"localhost", "192.168.1.10"})
+	require.NoError(t, err)
+	assert.NotEqual(t, config.Certificates[0].Certificate, regenerated.Certificates[0].Certificate)
+
+	// The files can be served as a certificate provided by the user
+	loaded, err := LoadServingConfig(certFile, keyFile)
+	require.NoError(t, err)
+	assert.Equal(t, regenerated.Certificates[0].Certificate, loaded.Certificates[0].Certificate)
+}
+
+func TestLoadServingConfig_MissingFiles(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	_, err := LoadServingConfig(filepath.Join(dir, "server.crt"), filepath.Join(dir, "server.key"))
+	assert.Error(t, err)
+}
+
+func TestCoversHosts(t *testing.T) {
+	t.Parallel()
+
+	now := time.Now()
+	certPEM, _, err := generateSelfSigned([]string{"localhost", "::1"}, now)
+	require.NoError(t, err)
+	block, _ := pem.Decode(certPEM)
+	require.NotNil(t, block)
+	cert, err := x509.ParseCertificate(block.Bytes)
+	require.NoError(t, err)
+
+	tests := []struct {
+		name     string
+		hosts    []string
+		now      time.Time
+		expected bool
+	}{
+		{
+			name:     "Covered hosts",
+			hosts:    []string{"localhost", "::1"},
+			now:      now,
+			expected: true,
+		},
+		{
+			name:     "Uncovered host",
+			hosts:    []string{"example.com"},
+			now:      now,
+			expected: false,
+		},
+		{
+			name:     "About to expire",
+			hosts:    []string{"localhost"},
+			now:      now.Add(selfSignedValidity - time.Hour),
+			expected: false,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, coversHosts(cert, tt.hosts, tt.now))
+		})
+	}
+}
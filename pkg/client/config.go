@@ -12,6 +12,7 @@ import (
 
 	"github.com/tailscale/hujson"
 
+	"github.com/stacklok/toolhive/pkg/labels"
 	"github.com/stacklok/toolhive/pkg/logger"
 	"github.com/stacklok/toolhive/pkg/transport/ssecommon"
 	"github.com/stacklok/toolhive/pkg/transport/streamable"
@@ -82,6 +83,7 @@ var supportedClientIntegrations = []mcpClientConfig{
 		Extension:            JSON,
 		SupportedTransportTypesMap: map[types.TransportType]string{
 			types.TransportTypeStdio:          "sse",
+			types.TransportTypeWebSocket:      "sse",
 			types.TransportTypeSSE:            "sse",
 			types.TransportTypeStreamableHTTP: "streamable-http",
 		},
@@ -102,8 +104,9 @@ var supportedClientIntegrations = []mcpClientConfig{
 		MCPServersPathPrefix: "/mcpServers",
 		Extension:            JSON,
 		SupportedTransportTypesMap: map[types.TransportType]string{
-			types.TransportTypeSSE:   "sse",
-			types.TransportTypeStdio: "sse",
+			types.TransportTypeSSE:       "sse",
+			types.TransportTypeStdio:     "sse",
+			types.TransportTypeWebSocket: "sse",
 		},
 		IsTransportTypeFieldSupported: false,
 	},
@@ -123,6 +126,7 @@ var supportedClientIntegrations = []mcpClientConfig{
 		Extension:            JSON,
 		SupportedTransportTypesMap: map[types.TransportType]string{
 			types.TransportTypeStdio:          "sse",
+			types.TransportTypeWebSocket:      "sse",
 			types.TransportTypeSSE:            "sse",
 			types.TransportTypeStreamableHTTP: "http",
 		},
@@ -144,6 +148,7 @@ var supportedClientIntegrations = []mcpClientConfig{
 		Extension: JSON,
 		SupportedTransportTypesMap: map[types.TransportType]string{
 			types.TransportTypeStdio:          "sse",
+			types.TransportTypeWebSocket:      "sse",
 			types.TransportTypeSSE:            "sse",
 			types.TransportTypeStreamableHTTP: "http",
 		},
@@ -158,6 +163,7 @@ var supportedClientIntegrations = []mcpClientConfig{
 		Extension:            JSON,
 		SupportedTransportTypesMap: map[types.TransportType]string{
 			types.TransportTypeStdio:          "sse",
+			types.TransportTypeWebSocket:      "sse",
 			types.TransportTypeSSE:            "sse",
 			types.TransportTypeStreamableHTTP: "http",
 		},
@@ -174,6 +180,7 @@ var supportedClientIntegrations = []mcpClientConfig{
 		Extension:            JSON,
 		SupportedTransportTypesMap: map[types.TransportType]string{
 			types.TransportTypeStdio:          "sse",
+			types.TransportTypeWebSocket:      "sse",
 			types.TransportTypeSSE:            "sse",
 			types.TransportTypeStreamableHTTP: "http",
 		},
@@ -298,12 +305,33 @@ func Upsert(cf ConfigFile, name string, url string, transportType string) error
 
 // GenerateMCPServerURL generates the URL for an MCP server
 func GenerateMCPServerURL(transportType string, host string, port int, containerName string) string {
+	return generateMCPServerURL("http", transportType, host, port, containerName, "")
+}
+
+// GenerateMCPServerURLForLabels generates the URL for an MCP server from the labels of its container,
+// which record the custom path of the SSE endpoint and whether the proxy serves HTTPS
+func GenerateMCPServerURLForLabels(
+	transportType string, host string, port int, containerName string, containerLabels map[string]string,
+) string {
+	scheme := "http"
+	if labels.IsTLS(containerLabels) {
+		scheme = "https"
+	}
+	return generateMCPServerURL(scheme, transportType, host, port, containerName, labels.GetSSEPath(containerLabels))
+}
+
+// generateMCPServerURL generates the URL for an MCP server whose proxy is served with the given scheme
+func generateMCPServerURL(scheme, transportType, host string, port int, containerName, ssePath string) string {
+	if ssePath == "" {
+		ssePath = ssecommon.HTTPSSEEndpoint
+	}
 	// The URL format is: http://host:port/sse#container-name
-	// Both SSE and STDIO transport types use an SSE proxy
-	if transportType == types.TransportTypeSSE.String() || transportType == types.TransportTypeStdio.String() {
-		return fmt.Sprintf("http://%s:%d%s#%s", host, port, ssecommon.HTTPSSEEndpoint, containerName)
+	// SSE, STDIO and WebSocket transport types use an SSE proxy
+	if transportType == types.TransportTypeSSE.String() || transportType == types.TransportTypeStdio.String() ||
+		transportType == types.TransportTypeWebSocket.String() {
+		return fmt.Sprintf("%s://%s:%d%s#%s", scheme, host, port, ssePath, containerName)
 	} else if transportType == types.TransportTypeStreamableHTTP.String() {
-		return fmt.Sprintf("http://%s:%d/%s", host, port, streamable.HTTPStreamableHTTPEndpoint)
+		return fmt.Sprintf("%s://%s:%d/%s", scheme, host, port, streamable.HTTPStreamableHTTPEndpoint)
 	}
 	return ""
 }
@@ -11,8 +11,10 @@ import (
 	"github.com/stretchr/testify/require"
 
 	"github.com/stacklok/toolhive/pkg/config"
+	"github.com/stacklok/toolhive/pkg/labels"
 	"github.com/stacklok/toolhive/pkg/logger"
 	"github.com/stacklok/toolhive/pkg/transport/ssecommon"
+	"github.com/stacklok/toolhive/pkg/transport/streamable"
 	"github.com/stacklok/toolhive/pkg/transport/types"
 )
 
@@ -192,6 +194,54 @@ func TestGenerateMCPServerURL(t *testing.T) {
 	}
 }
 
+func TestGenerateMCPServerURLForLabels(t *testing.T) {
+	t.Parallel()
+
+	tlsLabels := make(map[string]string)
+	labels.AddStandardLabels(tlsLabels, "fetch", "fetch", "stdio", 8080)
+	labels.SetTLS(tlsLabels)
+
+	tests := []struct {
+		name            string
+		transportType   types.TransportType
+		containerLabels map[string]string
+		expected        string
+	}{
+		{
+			name:            "Plain HTTP",
+			transportType:   types.TransportTypeStdio,
+			containerLabels: map[string]string{},
+			expected:        "http://localhost:8080" + ssecommon.HTTPSSEEndpoint + "#fetch",
+		},
+		{
+			name:            "Custom SSE path",
+			transportType:   types.TransportTypeStdio,
+			containerLabels: map[string]string{labels.LabelSSEPath: "/events"},
+			expected:        "http://localhost:8080/events#fetch",
+		},
+		{
+			name:            "HTTPS",
+			transportType:   types.TransportTypeStdio,
+			containerLabels: tlsLabels,
+			expected:        "https://localhost:8080" + ssecommon.HTTPSSEEndpoint + "#fetch",
+		},
+		{
+			name:            "HTTPS with streamable HTTP",
+			transportType:   types.TransportTypeStreamableHTTP,
+			containerLabels: tlsLabels,
+			expected:        "https://localhost:8080/" + streamable.HTTPStreamableHTTPEndpoint,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			url := GenerateMCPServerURLForLabels(tt.transportType.String(), "localhost", 8080, "fetch", tt.containerLabels)
+			assert.Equal(t, tt.expected, url)
+		})
+	}
+}
+
 func TestSuccessfulClientConfigOperations(t *testing.T) {
 	logger.Initialize()
 
@@ -144,7 +144,7 @@ func (m *defaultManager) addRunningMCPsToClient(ctx context.Context, clientType
 		transportType := labels.GetTransportType(c.Labels)
 
 		// Generate URL for the MCP server
-		url := GenerateMCPServerURL(transportType, transport.LocalhostIPv4, port, name)
+		url := GenerateMCPServerURLForLabels(transportType, transport.LocalhostIPv4, port, name, c.Labels)
 
 		// Update the MCP server configuration with locking
 		if err := Upsert(*clientConfig, name, url, transportType); err != nil {
@@ -0,0 +1,244 @@
+// Package compose provides a declarative file format for describing a set of
+// MCP servers managed by ToolHive, including the dependencies between them.
+package compose
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"gopkg.in/yaml.v3"
+
+	"github.com/stacklok/toolhive/pkg/environment"
+)
+
+const (
+	// ConditionServiceStarted waits until the dependency is running
+	ConditionServiceStarted = "service_started"
+	// ConditionServiceHealthy waits until the dependency is running and its proxy reports healthy. Only the MCP
+	// servers declared in the file have a proxy, so containers not managed by ToolHive cannot be waited for.
+	ConditionServiceHealthy = "service_healthy"
+)
+
+// File is a declarative description of a set of MCP servers.
+// ${VAR} references in the image, volumes and permission profile of a server are resolved from the
+// environment when the file is parsed. References in env values and args, including ${secret:name},
+// are kept as they are and resolved when the server is started.
+type File struct {
+	// Name is the name of the project of the servers, which defaults to the name of the directory of the file.
+	// Only the servers of the project are removed by apply --prune.
+	Name string `json:"name,omitempty" yaml:"name,omitempty"`
+	// Servers maps server names to their specification
+	Servers map[string]*Server `json:"servers" yaml:"servers"`
+}
+
+// Server describes a single MCP server
+type Server struct {
+	// Image is the registry server name, container image or protocol scheme to run
+	Image string `json:"image" yaml:"image"`
+	// Args are the arguments to pass to the MCP server
+	Args []string `json:"args,omitempty" yaml:"args,omitempty"`
+	// Transport is the transport mode (sse, streamable-http, websocket or stdio)
+	Transport string `json:"transport,omitempty" yaml:"transport,omitempty"`
+	// TargetPort is the port for the container to expose (only applicable to SSE or Streamable HTTP transport)
+	TargetPort int `json:"target_port,omitempty" yaml:"target_port,omitempty"`
+	// Env are the environment variables to pass to the MCP server
+	Env map[string]string `json:"env,omitempty" yaml:"env,omitempty"`
+	// Secrets are the secrets to pass to the MCP server (format: <secret name>,target=<env var>)
+	Secrets []string `json:"secrets,omitempty" yaml:"secrets,omitempty"`
+	// Volumes are the directory mounts to pass to the container (format: host-path:container-path[:ro])
+	Volumes []string `json:"volumes,omitempty" yaml:"volumes,omitempty"`
+	// PermissionProfile is the permission profile to use (none, network, or path to JSON file)
+	PermissionProfile string `json:"permission_profile,omitempty" yaml:"permission_profile,omitempty"`
+	// DependsOn lists the servers that must be ready before this server is started
+	DependsOn DependsOn `json:"depends_on,omitempty" yaml:"depends_on,omitempty"`
+}
+
+// Dependency describes the condition a dependency, which is another MCP server declared in the file, must satisfy
+type Dependency struct {
+	// Condition is either service_started (the default) or service_healthy
+	Condition string `json:"condition,omitempty" yaml:"condition,omitempty"`
+}
+
+// DependsOn maps dependency names to their conditions.
+// In YAML it may be written either as a list of names or as a map of names to conditions.
+type DependsOn map[string]Dependency
+
+// UnmarshalYAML supports both the short list syntax and the long map syntax
+func (d *DependsOn) UnmarshalYAML(value *yaml.Node) error {
+	switch value.Kind {
+	case yaml.SequenceNode:
+		var names []string
+		if err := value.Decode(&names); err != nil {
+			return err
+		}
+		*d = make(DependsOn, len(names))
+		for _, name := range names {
+			(*d)[name] = Dependency{Condition: ConditionServiceStarted}
+		}
+		return nil
+	case yaml.MappingNode:
+		var deps map[string]Dependency
+		if err := value.Decode(&deps); err != nil {
+			return err
+		}
+		for name, dep := range deps {
+			if dep.Condition == "" {
+				dep.Condition = ConditionServiceStarted
+				deps[name] = dep
+			}
+		}
+		*d = deps
+		return nil
+	default:
+		return fmt.Errorf("depends_on must be a list or a map")
+	}
+}
+
+// Load reads and validates a compose file from the given path
+func Load(path string) (*File, error) {
+	// #nosec G304 - the path is provided by the user
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read compose file: %w", err)
+	}
+	return Parse(data)
+}
+
+// ProjectName returns the name of the project of the compose file at the given path: the name set in the
+// file, or the name of the directory of the file
+func ProjectName(path string, file *File) (string, error) {
+	if file.Name != "" {
+		return file.Name, nil
+	}
+	absPath, err := filepath.Abs(path)
+	if err != nil {
+		return "", fmt.Errorf("failed to resolve the path of the compose file: %w", err)
+	}
+	return strings.ToLower(filepath.Base(filepath.Dir(absPath))), nil
+}
+
+// Parse parses and validates a compose file
+func Parse(data []byte) (*File, error) {
+	var file File
+	if err := yaml.Unmarshal(data, &file); err != nil {
+		return nil, fmt.Errorf("failed to parse compose file: %w", err)
+	}
+	if err := file.expandEnvReferences(); err != nil {
+		return nil, err
+	}
+	if err := file.Validate(); err != nil {
+		return nil, err
+	}
+	return &file, nil
+}
+
+// expandEnvReferences resolves ${VAR} references in the fields of the servers which are not passed to the server
+func (f *File) expandEnvReferences() error {
+	for name, server := range f.Servers {
+		if server == nil {
+			continue
+		}
+		var err error
+		if server.Image, err = environment.ExpandEnvReferences(server.Image); err != nil {
+			return fmt.Errorf("server %s: image: %w", name, err)
+		}
+		if server.PermissionProfile, err = environment.ExpandEnvReferences(server.PermissionProfile); err != nil {
+			return fmt.Errorf("server %s: permission profile: %w", name, err)
+		}
+		for i, volume := range server.Volumes {
+			if server.Volumes[i], err = environment.ExpandEnvReferences(volume); err != nil {
+				return fmt.Errorf("server %s: volume %s: %w", name, volume, err)
+			}
+		}
+	}
+	return nil
+}
+
+// Validate checks that every server has an image, that dependencies refer to
+// known servers with valid conditions, and that there are no dependency cycles
+func (f *File) Validate() error {
+	if len(f.Servers) == 0 {
+		return fmt.Errorf("compose file does not define any servers")
+	}
+
+	for name, server := range f.Servers {
+		if server == nil || server.Image == "" {
+			return fmt.Errorf("server %s: image is required", name)
+		}
+		for dep, cond := range server.DependsOn {
+			if dep == name {
+				return fmt.Errorf("server %s: cannot depend on itself", name)
+			}
+			if _, ok := f.Servers[dep]; !ok {
+				return fmt.Errorf("server %s: depends on unknown server %s "+
+					"(dependencies must be MCP servers declared in the file)", name, dep)
+			}
+			if cond.Condition != ConditionServiceStarted && cond.Condition != ConditionServiceHealthy {
+				return fmt.Errorf("server %s: invalid condition %q for dependency %s (valid conditions: %s, %s)",
+					name, cond.Condition, dep, ConditionServiceStarted, ConditionServiceHealthy)
+			}
+		}
+	}
+
+	_, err := f.StartOrder()
+	return err
+}
+
+// StartOrder returns the server names sorted so that every server comes after its dependencies.
+// Servers without an ordering constraint between them are sorted by name to keep the order stable.
+func (f *File) StartOrder() ([]string, error) {
+	const (
+		visiting = iota + 1
+		visited
+	)
+
+	names := make([]string, 0, len(f.Servers))
+	for name := range f.Servers {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+
+	state := make(map[string]int, len(names))
+	order := make([]string, 0, len(names))
+
+	var visit func(name string, path []string) error
+	visit = func(name string, path []string) error {
+		switch state[name] {
+		case visited:
+			return nil
+		case visiting:
+			return fmt.Errorf("dependency cycle detected: %s", strings.Join(append(path, name), " -> "))
+		}
+
+		server, ok := f.Servers[name]
+		if !ok || server == nil {
+			return fmt.Errorf("unknown server %s", name)
+		}
+
+		state[name] = visiting
+		deps := make([]string, 0, len(server.DependsOn))
+		for dep := range server.DependsOn {
+			deps = append(deps, dep)
+		}
+		sort.Strings(deps)
+		path = append(append([]string{}, path...), name)
+		for _, dep := range deps {
+			if err := visit(dep, path); err != nil {
+				return err
+			}
+		}
+		state[name] = visited
+		order = append(order, name)
+		return nil
+	}
+
+	for _, name := range names {
+		if err := visit(name, nil); err != nil {
+			return nil, err
+		}
+	}
+	return order, nil
+}
@@ -0,0 +1,273 @@
+package compose
+
+import (
+	"context"
+	"fmt"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestParse(t *testing.T) {
+	t.Parallel()
+
+	t.Run("Short and long depends_on syntax", func(t *testing.T) {
+		t.Parallel()
+		file, err := Parse([]byte(`
+servers:
+  postgres:
+    image: ghcr.io/example/postgres-mcp:latest
+    transport: sse
+  cache:
+    image: ghcr.io/example/cache-mcp:latest
+  app:
+    image: ghcr.io/example/app-mcp:latest
+    env:
+      LOG_LEVEL: debug
+    depends_on:
+      postgres:
+        condition: service_healthy
+      cache: {}
+  worker:
+    image: ghcr.io/example/worker-mcp:latest
+    depends_on:
+      - app
+`))
+		require.NoError(t, err)
+		require.Len(t, file.Servers, 4)
+
+		assert.Equal(t, "debug", file.Servers["app"].Env["LOG_LEVEL"])
+		assert.Equal(t, ConditionServiceHealthy, file.Servers["app"].DependsOn["postgres"].Condition)
+		assert.Equal(t, ConditionServiceStarted, file.Servers["app"].DependsOn["cache"].Condition)
+		assert.Equal(t, ConditionServiceStarted, file.Servers["worker"].DependsOn["app"].Condition)
+	})
+
+	testCases := []struct {
+		name        string
+		data        string
+		errContains string
+	}{
+		{
+			name:        "No servers",
+			data:        `servers: {}`,
+			errContains: "does not define any servers",
+		},
+		{
+			name: "Missing image",
+			data: `
+servers:
+  app: {}`,
+			errContains: "image is required",
+		},
+		{
+			name: "Unknown dependency",
+			data: `
+servers:
+  app:
+    image: app
+    depends_on: [db]`,
+			errContains: "depends on unknown server db",
+		},
+		{
+			name: "Healthy dependency which is not a declared server",
+			data: `
+servers:
+  app:
+    image: app
+    depends_on:
+      postgres:
+        condition: service_healthy`,
+			errContains: "dependencies must be MCP servers declared in the file",
+		},
+		{
+			name: "Invalid condition",
+			data: `
+servers:
+  db:
+    image: db
+  app:
+    image: app
+    depends_on:
+      db:
+        condition: service_completed`,
+			errContains: "invalid condition",
+		},
+		{
+			name: "Self dependency",
+			data: `
+servers:
+  app:
+    image: app
+    depends_on: [app]`,
+			errContains: "cannot depend on itself",
+		},
+		{
+			name: "Dependency cycle",
+			data: `
+servers:
+  a:
+    image: a
+    depends_on: [b]
+  b:
+    image: b
+    depends_on: [a]`,
+			errContains: "dependency cycle detected",
+		},
+		{
+			name: "Secret reference in image",
+			data: `
+servers:
+  app:
+    image: ${secret:image}`,
+			errContains: "secret references are only supported in env values and args",
+		},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			_, err := Parse([]byte(tc.data))
+			require.Error(t, err)
+			assert.Contains(t, err.Error(), tc.errContains)
+		})
+	}
+}
+
+func TestParse_EnvReferences(t *testing.T) {
+	t.Setenv("THV_TEST_REGISTRY", "registry.example.com")
+	t.Setenv("THV_TEST_DATA", "/srv/data")
+
+	file, err := Parse([]byte(`
+servers:
+  app:
+    image: ${THV_TEST_REGISTRY}/app-mcp:latest
+    volumes:
+      - ${THV_TEST_DATA}:/data:ro
+    args: ["--token", "${secret:token}"]
+    env:
+      DATA_DIR: ${THV_TEST_DATA}`))
+	require.NoError(t, err)
+
+	app := file.Servers["app"]
+	assert.Equal(t, "registry.example.com/app-mcp:latest", app.Image)
+	assert.Equal(t, []string{"/srv/data:/data:ro"}, app.Volumes)
+	// References in args and env are resolved when the server is started
+	assert.Equal(t, []string{"--token", "${secret:token}"}, app.Args)
+	assert.Equal(t, "${THV_TEST_DATA}", app.Env["DATA_DIR"])
+}
+
+func TestProjectName(t *testing.T) {
+	t.Parallel()
+
+	dir := filepath.Join(t.TempDir(), "Demo")
+
+	name, err := ProjectName(filepath.Join(dir, "toolhive.yaml"), &File{})
+	require.NoError(t, err)
+	assert.Equal(t, "demo", name)
+
+	file, err := Parse([]byte(`
+name: platform
+servers:
+  app:
+    image: ghcr.io/example/app-mcp:latest`))
+	require.NoError(t, err)
+	name, err = ProjectName(filepath.Join(dir, "toolhive.yaml"), file)
+	require.NoError(t, err)
+	assert.Equal(t, "platform", name)
+}
+
+func TestFile_StartOrder(t *testing.T) {
+	t.Parallel()
+
+	file := &File{Servers: map[string]*Server{
+		"worker": {Image: "worker", DependsOn: DependsOn{"app": {Condition: ConditionServiceStarted}}},
+		"app": {Image: "app", DependsOn: DependsOn{
+			"db":    {Condition: ConditionServiceHealthy},
+			"cache": {Condition: ConditionServiceStarted},
+		}},
+		"db":    {Image: "db"},
+		"cache": {Image: "cache"},
+		"other": {Image: "other"},
+	}}
+
+	order, err := file.StartOrder()
+	require.NoError(t, err)
+	assert.Equal(t, []string{"cache", "db", "app", "other", "worker"}, order)
+}
+
+type fakeReadinessChecker struct {
+	started map[string]bool
+	healthy map[string]bool
+}
+
+func (f *fakeReadinessChecker) CheckStarted(_ context.Context, name string) error {
+	if !f.started[name] {
+		return fmt.Errorf("%s is stopped", name)
+	}
+	return nil
+}
+
+func (f *fakeReadinessChecker) CheckHealthy(_ context.Context, name string) error {
+	if !f.healthy[name] {
+		return fmt.Errorf("%s is unhealthy", name)
+	}
+	return nil
+}
+
+func TestWaitForDependencies(t *testing.T) {
+	t.Parallel()
+
+	server := &Server{
+		Image: "app",
+		DependsOn: DependsOn{
+			"db":    {Condition: ConditionServiceHealthy},
+			"cache": {Condition: ConditionServiceStarted},
+		},
+	}
+
+	t.Run("Dependencies ready", func(t *testing.T) {
+		t.Parallel()
+		checker := &fakeReadinessChecker{
+			started: map[string]bool{"db": true, "cache": true},
+			healthy: map[string]bool{"db": true},
+		}
+		assert.NoError(t, WaitForDependencies(context.Background(), checker, server, time.Second))
+	})
+
+	t.Run("Dependency started but not healthy", func(t *testing.T) {
+		t.Parallel()
+		checker := &fakeReadinessChecker{
+			started: map[string]bool{"db": true, "cache": true},
+			healthy: map[string]bool{},
+		}
+		err := WaitForDependencies(context.Background(), checker, server, 10*time.Millisecond)
+		require.Error(t, err)
+		// The error explains why the dependency was not ready
+		assert.Contains(t, err.Error(), "dependency db did not satisfy service_healthy")
+		assert.Contains(t, err.Error(), "db is unhealthy")
+	})
+
+	t.Run("Dependency stopped", func(t *testing.T) {
+		t.Parallel()
+		checker := &fakeReadinessChecker{healthy: map[string]bool{"db": true}}
+		err := WaitForDependencies(context.Background(), checker, server, 10*time.Millisecond)
+		require.Error(t, err)
+		assert.Contains(t, err.Error(), "cache is stopped")
+	})
+
+	t.Run("Context cancelled", func(t *testing.T) {
+		t.Parallel()
+		ctx, cancel := context.WithCancel(context.Background())
+		cancel()
+		err := WaitForDependencies(ctx, &fakeReadinessChecker{}, server, time.Minute)
+		assert.ErrorIs(t, err, context.Canceled)
+	})
+
+	t.Run("No dependencies", func(t *testing.T) {
+		t.Parallel()
+		assert.NoError(t, WaitForDependencies(context.Background(), &fakeReadinessChecker{}, &Server{Image: "x"}, 0))
+	})
+}
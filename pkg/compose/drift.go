@@ -0,0 +1,238 @@
+package compose
+
+import (
+	"encoding/json"
+	"fmt"
+	"sort"
+	"strconv"
+	"strings"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/environment"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/permissions"
+	"github.com/stacklok/toolhive/pkg/runner"
+)
+
+// Drift is a difference between the expected and the actual state of a server
+type Drift struct {
+	// Server is the name of the server
+	Server string `json:"server"`
+	// Field is the drifted attribute (e.g. image, env, port)
+	Field string `json:"field"`
+	// Expected is the expected value
+	Expected string `json:"expected"`
+	// Actual is the actual value
+	Actual string `json:"actual"`
+}
+
+// ObservedServer is the actual state of a server
+type ObservedServer struct {
+	// Name is the name of the server
+	Name string
+	// Config is the run configuration the server was recorded with, if any
+	Config *runner.RunConfig
+	// Container is the container of the server, if it exists
+	Container *rt.ContainerInfo
+	// LatestImageID is the ID of the local image the recorded image reference currently resolves to, if known
+	LatestImageID string
+}
+
+// DetectRecordedDrift compares a server with the run configuration it was recorded with
+func DetectRecordedDrift(observed ObservedServer) []Drift {
+	var drifts []Drift
+	add := func(field, expected, actual string) {
+		drifts = append(drifts, Drift{Server: observed.Name, Field: field, Expected: expected, Actual: actual})
+	}
+
+	if observed.Container == nil {
+		add("status", "running", "missing")
+		return drifts
+	}
+	if observed.Container.State != "running" {
+		add("status", "running", observed.Container.State)
+	}
+
+	cfg := observed.Config
+	if cfg == nil {
+		return drifts
+	}
+
+	if cfg.Image != "" && observed.Container.Image != cfg.Image {
+		add("image", cfg.Image, observed.Container.Image)
+	}
+	if observed.LatestImageID != "" && observed.Container.ImageID != "" &&
+		observed.LatestImageID != observed.Container.ImageID {
+		add("image digest", observed.LatestImageID, observed.Container.ImageID)
+	}
+	if transportType := labels.GetTransportType(observed.Container.Labels); transportType != string(cfg.Transport) {
+		add("transport", string(cfg.Transport), transportType)
+	}
+	if port, err := labels.GetPort(observed.Container.Labels); err == nil && port != cfg.Port {
+		add("port", strconv.Itoa(cfg.Port), strconv.Itoa(port))
+	}
+
+	return drifts
+}
+
+// DetectSpecDrift compares a declared server with its recorded run configuration and container.
+// The image is only compared when the spec refers to an image reference rather than a registry name,
+// since registry names are resolved to images when the server is created.
+func DetectSpecDrift(name string, desired *Server, observed ObservedServer) ([]Drift, error) {
+	var drifts []Drift
+	add := func(field, expected, actual string) {
+		drifts = append(drifts, Drift{Server: name, Field: field, Expected: expected, Actual: actual})
+	}
+
+	if observed.Container == nil {
+		add("status", "running", "missing")
+		return drifts, nil
+	}
+
+	cfg := observed.Config
+	if cfg == nil {
+		add("config", "recorded", "missing")
+		return drifts, nil
+	}
+
+	if isImageReference(desired.Image) && desired.Image != cfg.Image {
+		add("image", desired.Image, cfg.Image)
+	}
+	if desired.Transport != "" && desired.Transport != string(cfg.Transport) {
+		add("transport", desired.Transport, string(cfg.Transport))
+	}
+	if desired.TargetPort != 0 && desired.TargetPort != cfg.TargetPort {
+		add("target port", strconv.Itoa(desired.TargetPort), strconv.Itoa(cfg.TargetPort))
+	}
+
+	drifts = append(drifts, envDrift(name, desired, cfg)...)
+	configDrifts, err := permissionsAndSpecDrift(name, desired, observed)
+	if err != nil {
+		return nil, err
+	}
+	drifts = append(drifts, configDrifts...)
+
+	drifts = append(drifts, DetectRecordedDrift(ObservedServer{
+		Name:          name,
+		Config:        cfg,
+		Container:     observed.Container,
+		LatestImageID: observed.LatestImageID,
+	})...)
+	return drifts, nil
+}
+
+// envDrift compares the environment variables of a declared server with its recorded run configuration,
+// masking the values of secrets
+func envDrift(name string, desired *Server, cfg *runner.RunConfig) []Drift {
+	keys := make([]string, 0, len(desired.Env))
+	for key := range desired.Env {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+
+	var drifts []Drift
+	masker := environment.NewMasker(append(append([]string{}, desired.Secrets...), cfg.Secrets...))
+	for _, key := range keys {
+		expected := masker.Mask(key, desired.Env[key])
+		actual, ok := cfg.EnvVars[key]
+		if !ok {
+			drifts = append(drifts, Drift{Server: name, Field: "env " + key, Expected: expected, Actual: "<unset>"})
+		} else if actual != desired.Env[key] {
+			drifts = append(drifts, Drift{Server: name, Field: "env " + key, Expected: expected, Actual: masker.Mask(key, actual)})
+		}
+	}
+	return drifts
+}
+
+// permissionsAndSpecDrift compares the permission profile of a declared server with its recorded run
+// configuration, and the hash of its spec with the one its container was labelled with
+func permissionsAndSpecDrift(name string, desired *Server, observed ObservedServer) ([]Drift, error) {
+	var drifts []Drift
+	profileDrift, err := permissionProfileDrift(desired.PermissionProfile, observed.Config.PermissionProfile)
+	if err != nil {
+		return nil, fmt.Errorf("server %s: %w", name, err)
+	}
+	if profileDrift {
+		drifts = append(drifts, Drift{
+			Server: name, Field: "permissions", Expected: profileName(desired.PermissionProfile), Actual: "modified",
+		})
+	}
+
+	hash, err := desired.Hash()
+	if err != nil {
+		return nil, err
+	}
+	if specHash := labels.GetSpecHash(observed.Container.Labels); specHash != "" && specHash != hash {
+		drifts = append(drifts, Drift{Server: name, Field: "spec", Expected: hash, Actual: specHash})
+	}
+	return drifts, nil
+}
+
+// isImageReference returns true if the given string looks like a container image reference
+// rather than the name of a server in the registry
+func isImageReference(image string) bool {
+	return strings.ContainsAny(image, "/:@")
+}
+
+func profileName(nameOrPath string) string {
+	if nameOrPath == "" {
+		return permissions.ProfileNetwork
+	}
+	return nameOrPath
+}
+
+// loadProfile loads a built-in permission profile by name, or a permission profile from a file
+func loadProfile(nameOrPath string) (*permissions.Profile, error) {
+	switch name := profileName(nameOrPath); name {
+	case permissions.ProfileNone:
+		return permissions.BuiltinNoneProfile(), nil
+	case permissions.ProfileNetwork:
+		return permissions.BuiltinNetworkProfile(), nil
+	default:
+		profile, err := permissions.FromFile(name)
+		if err != nil {
+			return nil, fmt.Errorf("failed to load permission profile: %w", err)
+		}
+		return profile, nil
+	}
+}
+
+// permissionProfileDrift returns true if the recorded profile grants different permissions than the desired one
+func permissionProfileDrift(desiredNameOrPath string, recorded *permissions.Profile) (bool, error) {
+	if recorded == nil {
+		return false, nil
+	}
+
+	desired, err := loadProfile(desiredNameOrPath)
+	if err != nil {
+		return false, err
+	}
+
+	// Volumes are added to the recorded profile as extra mounts, so the
+	// desired mounts only need to be a subset of the recorded ones.
+	desiredNetwork, err := json.Marshal(desired.Network)
+	if err != nil {
+		return false, err
+	}
+	recordedNetwork, err := json.Marshal(recorded.Network)
+	if err != nil {
+		return false, err
+	}
+	if string(desiredNetwork) != string(recordedNetwork) {
+		return true, nil
+	}
+	return !containsAllMounts(recorded.Read, desired.Read) || !containsAllMounts(recorded.Write, desired.Write), nil
+}
+
+func containsAllMounts(have, want []permissions.MountDeclaration) bool {
+	set := make(map[permissions.MountDeclaration]struct{}, len(have))
+	for _, m := range have {
+		set[m] = struct{}{}
+	}
+	for _, m := range want {
+		if _, ok := set[m]; !ok {
+			return false
+		}
+	}
+	return true
+}
@@ -0,0 +1,123 @@
+package compose
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/environment"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/permissions"
+	"github.com/stacklok/toolhive/pkg/runner"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+func newObservedServer() ObservedServer {
+	return ObservedServer{
+		Name: "fetch",
+		Config: &runner.RunConfig{
+			Image:             "ghcr.io/example/fetch:1.0",
+			Transport:         types.TransportTypeSSE,
+			Port:              8080,
+			TargetPort:        9000,
+			EnvVars:           map[string]string{"LOG_LEVEL": "info"},
+			PermissionProfile: permissions.BuiltinNetworkProfile(),
+		},
+		Container: &rt.ContainerInfo{
+			Name:    "fetch",
+			Image:   "ghcr.io/example/fetch:1.0",
+			ImageID: "sha256:aaa",
+			State:   "running",
+			Labels: map[string]string{
+				labels.LabelTransport: "sse",
+				labels.LabelPort:      "8080",
+			},
+		},
+		LatestImageID: "sha256:aaa",
+	}
+}
+
+func TestDetectRecordedDrift(t *testing.T) {
+	t.Parallel()
+
+	t.Run("No drift", func(t *testing.T) {
+		t.Parallel()
+		assert.Empty(t, DetectRecordedDrift(newObservedServer()))
+	})
+
+	t.Run("Missing container", func(t *testing.T) {
+		t.Parallel()
+		observed := newObservedServer()
+		observed.Container = nil
+		assert.Equal(t, []Drift{{Server: "fetch", Field: "status", Expected: "running", Actual: "missing"}},
+			DetectRecordedDrift(observed))
+	})
+
+	t.Run("Stopped container and newer image", func(t *testing.T) {
+		t.Parallel()
+		observed := newObservedServer()
+		observed.Container.State = "exited"
+		observed.LatestImageID = "sha256:bbb"
+		assert.Equal(t, []Drift{
+			{Server: "fetch", Field: "status", Expected: "running", Actual: "exited"},
+			{Server: "fetch", Field: "image digest", Expected: "sha256:bbb", Actual: "sha256:aaa"},
+		}, DetectRecordedDrift(observed))
+	})
+
+	t.Run("Port changed", func(t *testing.T) {
+		t.Parallel()
+		observed := newObservedServer()
+		observed.Container.Labels[labels.LabelPort] = "8081"
+		assert.Equal(t, []Drift{{Server: "fetch", Field: "port", Expected: "8080", Actual: "8081"}},
+			DetectRecordedDrift(observed))
+	})
+}
+
+func TestDetectSpecDrift(t *testing.T) {
+	t.Parallel()
+
+	t.Run("No drift", func(t *testing.T) {
+		t.Parallel()
+		drifts, err := DetectSpecDrift("fetch", &Server{
+			Image:     "ghcr.io/example/fetch:1.0",
+			Transport: "sse",
+			Env:       map[string]string{"LOG_LEVEL": "info"},
+		}, newObservedServer())
+		require.NoError(t, err)
+		assert.Empty(t, drifts)
+	})
+
+	t.Run("Registry names are not compared with images", func(t *testing.T) {
+		t.Parallel()
+		drifts, err := DetectSpecDrift("fetch", &Server{Image: "fetch"}, newObservedServer())
+		require.NoError(t, err)
+		assert.Empty(t, drifts)
+	})
+
+	t.Run("Image, env, port and permissions drift", func(t *testing.T) {
+		t.Parallel()
+		drifts, err := DetectSpecDrift("fetch", &Server{
+			Image:             "ghcr.io/example/fetch:2.0",
+			TargetPort:        9001,
+			Env:               map[string]string{"LOG_LEVEL": "debug", "TOKEN": "x"},
+			PermissionProfile: permissions.ProfileNone,
+		}, newObservedServer())
+		require.NoError(t, err)
+		assert.Equal(t, []Drift{
+			{Server: "fetch", Field: "image", Expected: "ghcr.io/example/fetch:2.0", Actual: "ghcr.io/example/fetch:1.0"},
+			{Server: "fetch", Field: "target port", Expected: "9001", Actual: "9000"},
+			{Server: "fetch", Field: "env LOG_LEVEL", Expected: "debug", Actual: "info"},
+			{Server: "fetch", Field: "env TOKEN", Expected: environment.MaskedValue, Actual: "<unset>"},
+			{Server: "fetch", Field: "permissions", Expected: "none", Actual: "modified"},
+		}, drifts)
+	})
+
+	t.Run("Missing server", func(t *testing.T) {
+		t.Parallel()
+		drifts, err := DetectSpecDrift("fetch", &Server{Image: "fetch"}, ObservedServer{})
+		require.NoError(t, err)
+		assert.Equal(t, []Drift{{Server: "fetch", Field: "status", Expected: "running", Actual: "missing"}}, drifts)
+	})
+}
@@ -0,0 +1,283 @@
+package compose
+
+import (
+	"context"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+
+	"github.com/google/go-containerregistry/pkg/name"
+	"gopkg.in/yaml.v3"
+
+	"github.com/stacklok/toolhive/pkg/container/images"
+	"github.com/stacklok/toolhive/pkg/registry"
+	"github.com/stacklok/toolhive/pkg/runner"
+)
+
+const (
+	// LockFileName is the name of the lock file, which is stored next to the compose file
+	LockFileName = "toolhive.lock"
+	// LockFileVersion is the version of the lock file format
+	LockFileVersion = 1
+)
+
+// LockFile records the exact artifacts the servers of a compose file resolved to,
+// so the same environment can be reproduced later
+type LockFile struct {
+	// Version is the version of the lock file format
+	Version int `json:"version" yaml:"version"`
+	// Servers maps server names to their locked artifacts
+	Servers map[string]*LockedServer `json:"servers" yaml:"servers"`
+}
+
+// LockedServer records the exact artifacts a server resolved to
+type LockedServer struct {
+	// SpecHash is the hash of the server spec in the compose file
+	SpecHash string `json:"spec_hash" yaml:"spec_hash"`
+	// Image is the container image the server resolved to
+	Image string `json:"image" yaml:"image"`
+	// Digest is the registry digest of the image, if the image comes from a registry
+	Digest string `json:"digest,omitempty" yaml:"digest,omitempty"`
+	// RegistryEntry is the registry entry the image was resolved from, if any
+	RegistryEntry *LockedRegistryEntry `json:"registry_entry,omitempty" yaml:"registry_entry,omitempty"`
+	// PermissionProfileHash is the hash of the permission profile of the server
+	PermissionProfileHash string `json:"permission_profile_hash" yaml:"permission_profile_hash"`
+}
+
+// LockedRegistryEntry identifies the version of a registry entry
+type LockedRegistryEntry struct {
+	// Name is the name of the server in the registry
+	Name string `json:"name" yaml:"name"`
+	// RegistryUpdated is the last update time of the registry the entry was read from
+	RegistryUpdated string `json:"registry_updated,omitempty" yaml:"registry_updated,omitempty"`
+	// Hash is the hash of the registry entry
+	Hash string `json:"hash" yaml:"hash"`
+}
+
+// PinnedImage returns the image reference pinned to the locked digest, or the image if there is no digest
+func (s *LockedServer) PinnedImage() (string, error) {
+	if s.Digest == "" {
+		return s.Image, nil
+	}
+	return images.DigestReference(s.Image, s.Digest)
+}
+
+// LockPath returns the path of the lock file for the given compose file
+func LockPath(composePath string) string {
+	return filepath.Join(filepath.Dir(composePath), LockFileName)
+}
+
+// LoadLock reads a lock file from the given path
+func LoadLock(path string) (*LockFile, error) {
+	// #nosec G304 - the path is derived from the compose file path provided by the user
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read lock file: %w", err)
+	}
+
+	var lock LockFile
+	if err := yaml.Unmarshal(data, &lock); err != nil {
+		return nil, fmt.Errorf("failed to parse lock file: %w", err)
+	}
+	if lock.Version != LockFileVersion {
+		return nil, fmt.Errorf("unsupported lock file version %d", lock.Version)
+	}
+	return &lock, nil
+}
+
+// Save writes the lock file to the given path
+func (l *LockFile) Save(path string) error {
+	data, err := yaml.Marshal(l)
+	if err != nil {
+		return fmt.Errorf("failed to encode lock file: %w", err)
+	}
+	header := []byte("# This file is generated by thv lock. Do not edit it by hand.\n")
+	if err := os.WriteFile(path, append(header, data...), 0600); err != nil {
+		return fmt.Errorf("failed to write lock file: %w", err)
+	}
+	return nil
+}
+
+// Resolver resolves the servers of a compose file to the exact artifacts they refer to
+type Resolver interface {
+	// ResolveImage resolves a registry server name or image reference to an image,
+	// returning the registry entry it was resolved from, if any
+	ResolveImage(image string) (string, *LockedRegistryEntry, error)
+	// Digest returns the registry digest of an image
+	Digest(ctx context.Context, image string) (string, error)
+	// ProfileHash returns the hash of a permission profile
+	ProfileHash(nameOrPath string) (string, error)
+}
+
+// Lock resolves every server of the compose file and returns the resulting lock file
+func Lock(ctx context.Context, file *File, resolver Resolver) (*LockFile, error) {
+	lock := &LockFile{
+		Version: LockFileVersion,
+		Servers: make(map[string]*LockedServer, len(file.Servers)),
+	}
+
+	for serverName, server := range file.Servers {
+		locked, err := lockServer(ctx, server, resolver, true)
+		if err != nil {
+			return nil, fmt.Errorf("server %s: %w", serverName, err)
+		}
+		lock.Servers[serverName] = locked
+	}
+	return lock, nil
+}
+
+func lockServer(ctx context.Context, server *Server, resolver Resolver, resolveDigest bool) (*LockedServer, error) {
+	specHash, err := server.Hash()
+	if err != nil {
+		return nil, err
+	}
+
+	image, entry, err := resolver.ResolveImage(server.Image)
+	if err != nil {
+		return nil, err
+	}
+
+	// Images built from protocol schemes are built locally, so they don't have a registry digest
+	var digest string
+	if resolveDigest && !runner.IsImageProtocolScheme(image) {
+		digest, err = resolver.Digest(ctx, image)
+		if err != nil {
+			return nil, err
+		}
+	}
+
+	profileHash, err := resolver.ProfileHash(server.PermissionProfile)
+	if err != nil {
+		return nil, err
+	}
+
+	return &LockedServer{
+		SpecHash:              specHash,
+		Image:                 image,
+		Digest:                digest,
+		RegistryEntry:         entry,
+		PermissionProfileHash: profileHash,
+	}, nil
+}
+
+// VerifyLock checks that the compose file still matches its lock file and returns the deviations.
+// The digests are not resolved again: the locked digests are used regardless of where the image tags point now.
+func VerifyLock(ctx context.Context, file *File, lock *LockFile, resolver Resolver) ([]Drift, error) {
+	names := make([]string, 0, len(file.Servers))
+	for serverName := range file.Servers {
+		names = append(names, serverName)
+	}
+	sort.Strings(names)
+
+	var drifts []Drift
+	for _, serverName := range names {
+		add := func(field, expected, actual string) {
+			drifts = append(drifts, Drift{Server: serverName, Field: field, Expected: expected, Actual: actual})
+		}
+
+		want, ok := lock.Servers[serverName]
+		if !ok {
+			add("lock", "locked", "missing")
+			continue
+		}
+		got, err := lockServer(ctx, file.Servers[serverName], resolver, false)
+		if err != nil {
+			return nil, fmt.Errorf("server %s: %w", serverName, err)
+		}
+
+		if want.SpecHash != got.SpecHash {
+			add("spec", want.SpecHash, got.SpecHash)
+		}
+		if want.Image != got.Image {
+			add("image", want.Image, got.Image)
+		}
+		wantEntry, gotEntry := registryEntryHash(want.RegistryEntry), registryEntryHash(got.RegistryEntry)
+		if wantEntry != gotEntry {
+			add("registry entry", wantEntry, gotEntry)
+		}
+		if want.PermissionProfileHash != got.PermissionProfileHash {
+			add("permissions", want.PermissionProfileHash, got.PermissionProfileHash)
+		}
+	}
+	return drifts, nil
+}
+
+func registryEntryHash(entry *LockedRegistryEntry) string {
+	if entry == nil {
+		return "<none>"
+	}
+	return entry.Hash
+}
+
+// NewResolver creates a Resolver backed by the configured registry and the upstream image registries
+func NewResolver() (Resolver, error) {
+	provider, err := registry.GetDefaultProvider()
+	if err != nil {
+		return nil, fmt.Errorf("failed to get registry provider: %w", err)
+	}
+	return &defaultResolver{provider: provider, remote: images.NewRemoteRegistry()}, nil
+}
+
+type defaultResolver struct {
+	provider registry.Provider
+	remote   images.RemoteRegistry
+}
+
+func (r *defaultResolver) ResolveImage(image string) (string, *LockedRegistryEntry, error) {
+	if runner.IsImageProtocolScheme(image) {
+		return image, nil, nil
+	}
+
+	metadata, err := r.provider.GetServer(image)
+	if err != nil {
+		// Not a registry server name, so it is an image reference
+		return image, nil, nil
+	}
+
+	entryHash, err := hashJSON(metadata)
+	if err != nil {
+		return "", nil, err
+	}
+	entry := &LockedRegistryEntry{Name: image, Hash: entryHash}
+	if reg, err := r.provider.GetRegistry(); err == nil {
+		entry.RegistryUpdated = reg.LastUpdated
+	}
+	return metadata.Image, entry, nil
+}
+
+func (r *defaultResolver) Digest(ctx context.Context, image string) (string, error) {
+	ref, err := name.ParseReference(image)
+	if err != nil {
+		return "", fmt.Errorf("invalid image reference %s: %w", image, err)
+	}
+	if digest, ok := ref.(name.Digest); ok {
+		return digest.DigestStr(), nil
+	}
+	digest, err := r.remote.Digest(ctx, ref)
+	if err != nil {
+		return "", fmt.Errorf("failed to get the digest of %s: %w", image, err)
+	}
+	return digest, nil
+}
+
+func (*defaultResolver) ProfileHash(nameOrPath string) (string, error) {
+	profile, err := loadProfile(nameOrPath)
+	if err != nil {
+		return "", err
+	}
+	return hashJSON(profile)
+}
+
+// hashJSON returns the sha256 hash of the JSON encoding of the given value
+func hashJSON(value any) (string, error) {
+	data, err := json.Marshal(value)
+	if err != nil {
+		return "", fmt.Errorf("failed to encode value for hashing: %w", err)
+	}
+	sum := sha256.Sum256(data)
+	return "sha256:" + hex.EncodeToString(sum[:]), nil
+}
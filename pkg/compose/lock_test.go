@@ -0,0 +1,172 @@
+package compose
+
+import (
+	"context"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+const testDigest = "sha256:1111111111111111111111111111111111111111111111111111111111111111"
+
+type fakeResolver struct {
+	entries  map[string]string
+	profiles map[string]string
+	digests  int
+}
+
+func (f *fakeResolver) ResolveImage(image string) (string, *LockedRegistryEntry, error) {
+	if resolved, ok := f.entries[image]; ok {
+		return resolved, &LockedRegistryEntry{Name: image, Hash: "sha256:entry-" + image}, nil
+	}
+	return image, nil, nil
+}
+
+func (f *fakeResolver) Digest(_ context.Context, _ string) (string, error) {
+	f.digests++
+	return testDigest, nil
+}
+
+func (f *fakeResolver) ProfileHash(nameOrPath string) (string, error) {
+	return f.profiles[nameOrPath], nil
+}
+
+func newFakeResolver() *fakeResolver {
+	return &fakeResolver{
+		entries:  map[string]string{"fetch": "ghcr.io/example/fetch:latest"},
+		profiles: map[string]string{"": "sha256:network", "none": "sha256:none"},
+	}
+}
+
+func newLockTestFile() *File {
+	return &File{Servers: map[string]*Server{
+		"fetch": {Image: "fetch"},
+		"db":    {Image: "ghcr.io/example/db:1.0", PermissionProfile: "none"},
+		"local": {Image: "uvx://example-server"},
+	}}
+}
+
+func TestLock(t *testing.T) {
+	t.Parallel()
+
+	lock, err := Lock(context.Background(), newLockTestFile(), newFakeResolver())
+	require.NoError(t, err)
+
+	assert.Equal(t, LockFileVersion, lock.Version)
+	require.Len(t, lock.Servers, 3)
+
+	fetch := lock.Servers["fetch"]
+	assert.Equal(t, "ghcr.io/example/fetch:latest", fetch.Image)
+	assert.Equal(t, testDigest, fetch.Digest)
+	require.NotNil(t, fetch.RegistryEntry)
+	assert.Equal(t, "fetch", fetch.RegistryEntry.Name)
+	assert.Equal(t, "sha256:network", fetch.PermissionProfileHash)
+	assert.NotEmpty(t, fetch.SpecHash)
+
+	db := lock.Servers["db"]
+	assert.Nil(t, db.RegistryEntry)
+	assert.Equal(t, "sha256:none", db.PermissionProfileHash)
+
+	// Images built from protocol schemes are not locked by digest
+	assert.Empty(t, lock.Servers["local"].Digest)
+
+	pinned, err := fetch.PinnedImage()
+	require.NoError(t, err)
+	assert.Equal(t, "ghcr.io/example/fetch@"+testDigest, pinned)
+
+	pinned, err = lock.Servers["local"].PinnedImage()
+	require.NoError(t, err)
+	assert.Equal(t, "uvx://example-server", pinned)
+}
+
+func TestVerifyLock(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name     string
+		modify   func(file *File, resolver *fakeResolver)
+		expected []Drift
+	}{
+		{
+			name:   "unchanged",
+			modify: func(_ *File, _ *fakeResolver) {},
+		},
+		{
+			name: "spec changed",
+			modify: func(file *File, _ *fakeResolver) {
+				file.Servers["db"].Env = map[string]string{"DEBUG": "1"}
+			},
+			expected: []Drift{{Server: "db", Field: "spec"}},
+		},
+		{
+			name: "server added",
+			modify: func(file *File, _ *fakeResolver) {
+				file.Servers["new"] = &Server{Image: "ghcr.io/example/new:1.0"}
+			},
+			expected: []Drift{{Server: "new", Field: "lock", Expected: "locked", Actual: "missing"}},
+		},
+		{
+			name: "registry entry points to another image",
+			modify: func(_ *File, resolver *fakeResolver) {
+				resolver.entries["fetch"] = "ghcr.io/example/fetch:2.0"
+			},
+			expected: []Drift{{
+				Server:   "fetch",
+				Field:    "image",
+				Expected: "ghcr.io/example/fetch:latest",
+				Actual:   "ghcr.io/example/fetch:2.0",
+			}},
+		},
+		{
+			name: "permission profile changed",
+			modify: func(_ *File, resolver *fakeResolver) {
+				resolver.profiles["none"] = "sha256:modified"
+			},
+			expected: []Drift{{Server: "db", Field: "permissions", Expected: "sha256:none", Actual: "sha256:modified"}},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			file := newLockTestFile()
+			resolver := newFakeResolver()
+			lock, err := Lock(context.Background(), file, resolver)
+			require.NoError(t, err)
+
+			tt.modify(file, resolver)
+			resolver.digests = 0
+			drifts, err := VerifyLock(context.Background(), file, lock, resolver)
+			require.NoError(t, err)
+
+			// Digests are never resolved again when verifying
+			assert.Zero(t, resolver.digests)
+
+			// Spec hashes are opaque, so only compare the fields of spec drifts
+			for i := range drifts {
+				if drifts[i].Field == "spec" {
+					drifts[i].Expected, drifts[i].Actual = "", ""
+				}
+			}
+			assert.Equal(t, tt.expected, drifts)
+		})
+	}
+}
+
+func TestLockFile_SaveLoad(t *testing.T) {
+	t.Parallel()
+
+	lock, err := Lock(context.Background(), newLockTestFile(), newFakeResolver())
+	require.NoError(t, err)
+
+	path := LockPath(filepath.Join(t.TempDir(), "toolhive.yaml"))
+	assert.Equal(t, LockFileName, filepath.Base(path))
+
+	require.NoError(t, lock.Save(path))
+	loaded, err := LoadLock(path)
+	require.NoError(t, err)
+	assert.Equal(t, lock, loaded)
+}
@@ -0,0 +1,141 @@
+package compose
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"fmt"
+	"sort"
+)
+
+// ActionType is the kind of change needed to converge a server to its desired state
+type ActionType string
+
+const (
+	// ActionCreate creates a server that does not exist yet
+	ActionCreate ActionType = "create"
+	// ActionUpdate recreates a server whose spec has changed
+	ActionUpdate ActionType = "update"
+	// ActionDelete removes a server that is no longer declared
+	ActionDelete ActionType = "delete"
+	// ActionNone leaves a server that is already up to date untouched
+	ActionNone ActionType = "unchanged"
+)
+
+// Action is a single step of a Plan
+type Action struct {
+	// Type is the kind of change
+	Type ActionType
+	// Name is the name of the server
+	Name string
+	// Reason explains why the action is needed
+	Reason string
+}
+
+// Plan is the ordered list of actions needed to converge the actual state to the desired state.
+// Deletions come first, followed by creations and updates in dependency order.
+type Plan struct {
+	Actions []Action
+}
+
+// HasChanges returns true if the plan contains any action other than ActionNone
+func (p *Plan) HasChanges() bool {
+	for _, action := range p.Actions {
+		if action.Type != ActionNone {
+			return true
+		}
+	}
+	return false
+}
+
+// EnforceImages turns the unchanged servers which don't run the given image references into updates.
+// It is used to converge the servers to the images pinned in a lock file.
+func (p *Plan) EnforceImages(actual []ActualServer, images map[string]string) {
+	actualImages := make(map[string]string, len(actual))
+	for _, server := range actual {
+		actualImages[server.Name] = server.Image
+	}
+
+	for i, action := range p.Actions {
+		image, pinned := images[action.Name]
+		if action.Type == ActionNone && pinned && actualImages[action.Name] != image {
+			p.Actions[i] = Action{Type: ActionUpdate, Name: action.Name, Reason: "not at the locked image"}
+		}
+	}
+}
+
+// ActualServer describes an existing server as seen by the container runtime
+type ActualServer struct {
+	// Name is the name of the server
+	Name string
+	// SpecHash is the hash of the spec the server was created from, if it was created from a compose file
+	SpecHash string
+	// Project is the name of the compose project the server was created from, if it was created from one
+	Project string
+	// Running indicates whether the server is running
+	Running bool
+	// Image is the image reference the server was created with
+	Image string
+}
+
+// Hash returns a stable hash of the server spec, used to detect changes between applies
+func (s *Server) Hash() (string, error) {
+	// json.Marshal sorts map keys, which makes the encoding stable
+	data, err := json.Marshal(s)
+	if err != nil {
+		return "", fmt.Errorf("failed to encode server spec: %w", err)
+	}
+	sum := sha256.Sum256(data)
+	return hex.EncodeToString(sum[:])[:16], nil
+}
+
+// ComputePlan compares the desired servers with the actual ones and returns the actions to converge them.
+// If prune is true, servers which were created from the compose project but are no longer declared are
+// deleted. Servers which were started by hand or from other compose projects are never deleted.
+func ComputePlan(desired *File, actual []ActualServer, project string, prune bool) (*Plan, error) {
+	order, err := desired.StartOrder()
+	if err != nil {
+		return nil, err
+	}
+
+	actualByName := make(map[string]ActualServer, len(actual))
+	for _, server := range actual {
+		actualByName[server.Name] = server
+	}
+
+	plan := &Plan{}
+
+	if prune {
+		var deletions []string
+		for _, server := range actual {
+			if _, declared := desired.Servers[server.Name]; !declared && project != "" && server.Project == project {
+				deletions = append(deletions, server.Name)
+			}
+		}
+		sort.Strings(deletions)
+		for _, name := range deletions {
+			plan.Actions = append(plan.Actions, Action{Type: ActionDelete, Name: name, Reason: "no longer declared"})
+		}
+	}
+
+	for _, name := range order {
+		hash, err := desired.Servers[name].Hash()
+		if err != nil {
+			return nil, err
+		}
+
+		existing, ok := actualByName[name]
+		switch {
+		case !ok:
+			plan.Actions = append(plan.Actions, Action{Type: ActionCreate, Name: name, Reason: "does not exist"})
+		case existing.SpecHash != hash:
+			plan.Actions = append(plan.Actions, Action{Type: ActionUpdate, Name: name, Reason: "spec changed"})
+		case !existing.Running:
+			plan.Actions = append(plan.Actions, Action{Type: ActionUpdate, Name: name, Reason: "not running"})
+		default:
+			plan.Actions = append(plan.Actions, Action{Type: ActionNone, Name: name, Reason: "up to date"})
+		}
+	}
+
+	return plan, nil
+}
@@ -0,0 +1,125 @@
+package compose
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestServer_Hash(t *testing.T) {
+	t.Parallel()
+
+	a := &Server{Image: "app", Env: map[string]string{"A": "1", "B": "2"}}
+	b := &Server{Image: "app", Env: map[string]string{"B": "2", "A": "1"}}
+	c := &Server{Image: "app", Env: map[string]string{"A": "1", "B": "3"}}
+
+	hashA, err := a.Hash()
+	require.NoError(t, err)
+	hashB, err := b.Hash()
+	require.NoError(t, err)
+	hashC, err := c.Hash()
+	require.NoError(t, err)
+
+	assert.Equal(t, hashA, hashB, "hash should not depend on map ordering")
+	assert.NotEqual(t, hashA, hashC, "hash should change when the spec changes")
+}
+
+func TestComputePlan(t *testing.T) {
+	t.Parallel()
+
+	desired := &File{Servers: map[string]*Server{
+		"db":  {Image: "db"},
+		"app": {Image: "app", DependsOn: DependsOn{"db": {Condition: ConditionServiceHealthy}}},
+		"new": {Image: "new"},
+	}}
+	dbHash, err := desired.Servers["db"].Hash()
+	require.NoError(t, err)
+
+	actual := []ActualServer{
+		{Name: "db", SpecHash: dbHash, Project: "demo", Running: true},
+		{Name: "app", SpecHash: "outdated", Project: "demo", Running: true},
+		{Name: "old", SpecHash: "abc", Project: "demo", Running: true},
+		{Name: "other", SpecHash: "def", Project: "other", Running: true},
+		{Name: "unlabeled", SpecHash: "ghi", Running: true},
+		{Name: "manual", Running: true},
+	}
+
+	t.Run("Without prune", func(t *testing.T) {
+		t.Parallel()
+		plan, err := ComputePlan(desired, actual, "demo", false)
+		require.NoError(t, err)
+		assert.True(t, plan.HasChanges())
+		assert.Equal(t, []Action{
+			{Type: ActionNone, Name: "db", Reason: "up to date"},
+			{Type: ActionUpdate, Name: "app", Reason: "spec changed"},
+			{Type: ActionCreate, Name: "new", Reason: "does not exist"},
+		}, plan.Actions)
+	})
+
+	t.Run("With prune", func(t *testing.T) {
+		t.Parallel()
+		plan, err := ComputePlan(desired, actual, "demo", true)
+		require.NoError(t, err)
+		// Only the servers of the project are pruned, not those started by hand or from other projects
+		assert.Equal(t, []Action{
+			{Type: ActionDelete, Name: "old", Reason: "no longer declared"},
+			{Type: ActionNone, Name: "db", Reason: "up to date"},
+			{Type: ActionUpdate, Name: "app", Reason: "spec changed"},
+			{Type: ActionCreate, Name: "new", Reason: "does not exist"},
+		}, plan.Actions)
+	})
+
+	t.Run("Stopped server is restarted", func(t *testing.T) {
+		t.Parallel()
+		plan, err := ComputePlan(
+			&File{Servers: map[string]*Server{"db": {Image: "db"}}},
+			[]ActualServer{{Name: "db", SpecHash: dbHash, Running: false}},
+			"demo",
+			false,
+		)
+		require.NoError(t, err)
+		assert.Equal(t, []Action{{Type: ActionUpdate, Name: "db", Reason: "not running"}}, plan.Actions)
+	})
+
+	t.Run("Up to date", func(t *testing.T) {
+		t.Parallel()
+		plan, err := ComputePlan(
+			&File{Servers: map[string]*Server{"db": {Image: "db"}}},
+			[]ActualServer{{Name: "db", SpecHash: dbHash, Running: true}},
+			"demo",
+			true,
+		)
+		require.NoError(t, err)
+		assert.False(t, plan.HasChanges())
+	})
+}
+
+func TestPlan_EnforceImages(t *testing.T) {
+	t.Parallel()
+
+	plan := &Plan{Actions: []Action{
+		{Type: ActionNone, Name: "pinned", Reason: "up to date"},
+		{Type: ActionNone, Name: "moved", Reason: "up to date"},
+		{Type: ActionNone, Name: "unlocked", Reason: "up to date"},
+		{Type: ActionCreate, Name: "new", Reason: "does not exist"},
+	}}
+	actual := []ActualServer{
+		{Name: "pinned", Image: "ghcr.io/example/pinned@sha256:aaa", Running: true},
+		{Name: "moved", Image: "ghcr.io/example/moved:latest", Running: true},
+		{Name: "unlocked", Image: "ghcr.io/example/unlocked:latest", Running: true},
+	}
+
+	plan.EnforceImages(actual, map[string]string{
+		"pinned": "ghcr.io/example/pinned@sha256:aaa",
+		"moved":  "ghcr.io/example/moved@sha256:bbb",
+		"new":    "ghcr.io/example/new@sha256:ccc",
+	})
+
+	assert.Equal(t, []Action{
+		{Type: ActionNone, Name: "pinned", Reason: "up to date"},
+		{Type: ActionUpdate, Name: "moved", Reason: "not at the locked image"},
+		{Type: ActionNone, Name: "unlocked", Reason: "up to date"},
+		{Type: ActionCreate, Name: "new", Reason: "does not exist"},
+	}, plan.Actions)
+}
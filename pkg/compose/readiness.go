@@ -0,0 +1,121 @@
+package compose
+
+import (
+	"context"
+	"fmt"
+	"net/http"
+	"time"
+
+	"github.com/stacklok/toolhive/pkg/healthcheck"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/workloads"
+)
+
+// readinessPollInterval is how often dependencies are checked while waiting for them
+const readinessPollInterval = time.Second
+
+// ReadinessChecker reports whether a server satisfies a dependency condition
+type ReadinessChecker interface {
+	// CheckStarted returns nil if the server is running, or an error explaining why it is not
+	CheckStarted(ctx context.Context, name string) error
+	// CheckHealthy returns nil if the server is running and reports itself as healthy, or an error
+	// explaining why it is not
+	CheckHealthy(ctx context.Context, name string) error
+}
+
+// WaitForDependencies blocks until every dependency of the server satisfies its condition, the timeout
+// expires, or the context is cancelled. When a dependency is not ready in time, the error explains why
+// it was not ready when it was last checked.
+func WaitForDependencies(
+	ctx context.Context, checker ReadinessChecker, server *Server, timeout time.Duration,
+) error {
+	if len(server.DependsOn) == 0 {
+		return nil
+	}
+
+	waitCtx, cancel := context.WithTimeout(ctx, timeout)
+	defer cancel()
+
+	ticker := time.NewTicker(readinessPollInterval)
+	defer ticker.Stop()
+
+	for name, dep := range server.DependsOn {
+		for {
+			err := checkReady(waitCtx, checker, name, dep.Condition)
+			if err == nil {
+				break
+			}
+
+			logger.Debugf("Waiting for dependency %s to satisfy %s: %v", name, dep.Condition, err)
+			select {
+			case <-waitCtx.Done():
+				if ctx.Err() != nil {
+					return ctx.Err()
+				}
+				return fmt.Errorf("dependency %s did not satisfy %s within %s: %w", name, dep.Condition, timeout, err)
+			case <-ticker.C:
+			}
+		}
+	}
+	return nil
+}
+
+func checkReady(ctx context.Context, checker ReadinessChecker, name, condition string) error {
+	if condition == ConditionServiceHealthy {
+		return checker.CheckHealthy(ctx, name)
+	}
+	return checker.CheckStarted(ctx, name)
+}
+
+// workloadReadinessChecker checks readiness using the workload manager and the proxy health endpoint
+type workloadReadinessChecker struct {
+	manager    workloads.Manager
+	httpClient *http.Client
+}
+
+// NewWorkloadReadinessChecker creates a ReadinessChecker backed by the given workload manager
+func NewWorkloadReadinessChecker(manager workloads.Manager) ReadinessChecker {
+	return &workloadReadinessChecker{
+		manager:    manager,
+		httpClient: &http.Client{Timeout: 5 * time.Second},
+	}
+}
+
+func (c *workloadReadinessChecker) CheckStarted(ctx context.Context, name string) error {
+	_, err := c.runningWorkload(ctx, name)
+	return err
+}
+
+func (c *workloadReadinessChecker) CheckHealthy(ctx context.Context, name string) error {
+	workload, err := c.runningWorkload(ctx, name)
+	if err != nil {
+		return err
+	}
+
+	httpClient, proxyURL := c.httpClient, workload.URL
+	if workload.ListenSocket != "" {
+		httpClient = healthcheck.NewSocketClient(workload.ListenSocket, c.httpClient.Timeout)
+		proxyURL = healthcheck.SocketURL
+	}
+	health, err := healthcheck.Fetch(ctx, httpClient, proxyURL)
+	if err != nil {
+		return fmt.Errorf("failed to check the health of %s: %w", name, err)
+	}
+	if health.Status != healthcheck.StatusHealthy {
+		return fmt.Errorf("%s is %s", name, health.Status)
+	}
+	return nil
+}
+
+// runningWorkload returns the workload of the server if it is running
+func (c *workloadReadinessChecker) runningWorkload(ctx context.Context, name string) (workloads.Workload, error) {
+	workload, err := c.manager.GetWorkload(ctx, name)
+	if err != nil {
+		// The dependency may not have been created yet
+		return workloads.Workload{}, fmt.Errorf("%s is not available: %w", name, err)
+	}
+	if workload.Status != workloads.WorkloadStatusRunning {
+		return workloads.Workload{}, fmt.Errorf("%s is %s", name, workload.Status)
+	}
+	return workload, nil
+}
@@ -8,6 +8,7 @@ import (
 	"fmt"
 	"os"
 	"path"
+	"strings"
 	"time"
 
 	"github.com/adrg/xdg"
@@ -27,8 +28,42 @@ type Config struct {
 	Clients                Clients             `yaml:"clients"`
 	RegistryUrl            string              `yaml:"registry_url"`
 	AllowPrivateRegistryIp bool                `yaml:"allow_private_registry_ip"`
+	RegistryTrustedKeys    []string            `yaml:"registry_trusted_keys,omitempty"`
 	CACertificatePath      string              `yaml:"ca_certificate_path,omitempty"`
 	OTEL                   OpenTelemetryConfig `yaml:"otel,omitempty"`
+	TrustedSigners         []TrustedSigner     `yaml:"trusted_signers,omitempty"`
+}
+
+// TrustedSigner is a signer which the signatures of images without provenance information
+// in the registry are verified against.
+type TrustedSigner struct {
+	// Identity is the signer identity of the signing certificate, e.g. an email address,
+	// or the path of the workflow for images signed in GitHub Actions
+	Identity string `yaml:"identity"`
+	// Issuer is the OIDC issuer of the signing certificate, e.g. https://token.actions.githubusercontent.com
+	Issuer string `yaml:"issuer,omitempty"`
+	// Repository is the source repository of images signed in CI, e.g. https://github.com/org/repo
+	Repository string `yaml:"repository,omitempty"`
+}
+
+// GitHubActionsIssuer is the OIDC issuer of the certificates of images signed in GitHub Actions
+const GitHubActionsIssuer = "https://token.actions.githubusercontent.com"
+
+// Validate checks that the signer can't be matched by signatures of other signers. The issuer is always
+// required, and the repository is required for workflow identities, which are only the path of the
+// workflow and would otherwise match the workflow with the same path in any repository.
+func (s TrustedSigner) Validate() error {
+	if s.Identity == "" {
+		return fmt.Errorf("the identity of the signer must not be empty")
+	}
+	if s.Issuer == "" {
+		return fmt.Errorf("the issuer of signer %s must be set", s.Identity)
+	}
+	if (s.Issuer == GitHubActionsIssuer || strings.HasPrefix(s.Identity, "/")) && s.Repository == "" {
+		return fmt.Errorf("the repository of signer %s must be set, since its workflow path "+
+			"matches the workflows with the same path in any repository", s.Identity)
+	}
+	return nil
 }
 
 // Secrets contains the settings for secrets management.
@@ -252,6 +252,90 @@ func TestRegistryURLConfig(t *testing.T) {
 	})
 }
 
+func TestTrustedSignersConfig(t *testing.T) {
+	t.Parallel()
+	tempDir, configPath := SetupTestConfig(t, nil)
+
+	signers := []TrustedSigner{
+		{Identity: "jane@example.com", Issuer: "https://accounts.google.com"},
+		{
+			Identity:   "/.github/workflows/release.yml",
+			Issuer:     "https://token.actions.githubusercontent.com",
+			Repository: "https://github.com/example/mcp-server",
+		},
+	}
+	err := UpdateConfigAtPath(configPath, func(c *Config) {
+		c.TrustedSigners = signers
+	})
+	require.NoError(t, err)
+
+	// Load config again to verify persistence
+	config, err := LoadOrCreateConfigWithPath(configPath)
+	require.NoError(t, err)
+	assert.Equal(t, signers, config.TrustedSigners)
+
+	t.Cleanup(func() {
+		if err := os.RemoveAll(tempDir); err != nil {
+			t.Logf("Failed to remove temp dir: %v", err)
+		}
+	})
+}
+
+func TestTrustedSigner_Validate(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name        string
+		signer      TrustedSigner
+		expectError bool
+	}{
+		{
+			name:   "email with issuer",
+			signer: TrustedSigner{Identity: "jane@example.com", Issuer: "https://accounts.google.com"},
+		},
+		{
+			name: "workflow with issuer and repository",
+			signer: TrustedSigner{
+				Identity:   "/.github/workflows/release.yml",
+				Issuer:     GitHubActionsIssuer,
+				Repository: "https://github.com/example/mcp-server",
+			},
+		},
+		{
+			name:        "empty identity",
+			signer:      TrustedSigner{Issuer: "https://accounts.google.com"},
+			expectError: true,
+		},
+		{
+			name:        "no issuer",
+			signer:      TrustedSigner{Identity: "jane@example.com"},
+			expectError: true,
+		},
+		{
+			name:        "workflow without repository",
+			signer:      TrustedSigner{Identity: "/.github/workflows/release.yml", Issuer: GitHubActionsIssuer},
+			expectError: true,
+		},
+		{
+			name:        "workflow path with another issuer and no repository",
+			signer:      TrustedSigner{Identity: "/.github/workflows/release.yml", Issuer: "https://issuer.example.com"},
+			expectError: true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			err := tt.signer.Validate()
+			if tt.expectError {
+				assert.Error(t, err)
+			} else {
+				assert.NoError(t, err)
+			}
+		})
+	}
+}
+
 func TestSecrets_GetProviderType_EnvironmentVariable(t *testing.T) {
 	t.Parallel()
 	logger.Initialize()
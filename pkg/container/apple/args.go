@@ -0,0 +1,166 @@
+package apple
+
+import (
+	"fmt"
+	"math"
+	"path/filepath"
+	"sort"
+	"strconv"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/networking"
+	"github.com/stacklok/toolhive/pkg/permissions"
+)
+
+// createArgs returns the arguments of the container CLI to create a workload. Workloads with stdio
+// are only created, since they are started when attaching to them, the others are created and started.
+func createArgs(
+	name, image string,
+	command []string,
+	envVars, labels map[string]string,
+	mounts []runtime.Mount,
+	publish []string,
+	resources *runtime.ResourceLimits,
+	attachStdio bool,
+) []string {
+	args := []string{"run", "--detach"}
+	if attachStdio {
+		args = []string{"create", "--interactive"}
+	}
+	args = append(args, "--name", name)
+
+	for _, key := range sortedKeys(envVars) {
+		args = append(args, "--env", key+"="+envVars[key])
+	}
+	for _, key := range sortedKeys(labels) {
+		args = append(args, "--label", key+"="+labels[key])
+	}
+	for _, m := range mounts {
+		spec := fmt.Sprintf("type=bind,source=%s,target=%s", m.Source, m.Target)
+		if m.ReadOnly {
+			spec += ",readonly"
+		}
+		args = append(args, "--mount", spec)
+	}
+	for _, p := range publish {
+		args = append(args, "--publish", p)
+	}
+	args = append(args, resourceArgs(resources)...)
+
+	args = append(args, image)
+	return append(args, command...)
+}
+
+// resourceArgs returns the resource limit arguments of a workload. Containers run in their own virtual
+// machine, which is sized with whole CPUs and memory in MiB, and swap and process limits are not supported.
+func resourceArgs(resources *runtime.ResourceLimits) []string {
+	if resources == nil {
+		return nil
+	}
+	var args []string
+	if resources.CPUs > 0 {
+		args = append(args, "--cpus", strconv.Itoa(int(math.Ceil(resources.CPUs))))
+	}
+	if resources.Memory > 0 {
+		args = append(args, "--memory", fmt.Sprintf("%dM", (resources.Memory+(1<<20)-1)>>20))
+	}
+	if resources.MemorySwap != 0 || resources.PidsLimit > 0 {
+		logger.Debugf("Swap and process limits are not supported by the Apple container runtime")
+	}
+	return args
+}
+
+// publishArgs returns the port publishing specifications of a workload, and the host port of its first
+// port. Auxiliary workloads, like the inspector, are published on the host port they request, and the
+// others on a random available port.
+func publishArgs(labels map[string]string, portBindings map[string][]runtime.PortBinding) ([]string, int, error) {
+	auxiliary := labels["toolhive-auxiliary"] == "true"
+
+	var publish []string
+	hostPort := 0
+	for _, containerPort := range sortedKeys(portBindings) {
+		bindings := portBindings[containerPort]
+		if len(bindings) == 0 {
+			continue
+		}
+		binding := bindings[0]
+		if hostPort == 0 {
+			if auxiliary {
+				port, err := strconv.Atoi(binding.HostPort)
+				if err != nil {
+					return nil, 0, fmt.Errorf("failed to convert host port %s to int: %v", binding.HostPort, err)
+				}
+				hostPort = port
+			} else {
+				hostPort = networking.FindAvailable()
+				if hostPort == 0 {
+					return nil, 0, fmt.Errorf("could not find an available port")
+				}
+				binding.HostPort = strconv.Itoa(hostPort)
+			}
+		}
+
+		spec := binding.HostPort + ":" + containerPort
+		if binding.HostIP != "" {
+			spec = binding.HostIP + ":" + spec
+		}
+		publish = append(publish, spec)
+	}
+	return publish, hostPort, nil
+}
+
+// mountsFromProfile returns the bind mounts of a permission profile. Resource URIs are not supported
+// by the container CLI, so they are skipped.
+func mountsFromProfile(profile *permissions.Profile) []runtime.Mount {
+	var mounts []runtime.Mount
+	add := func(declarations []permissions.MountDeclaration, readOnly bool) {
+		for _, declaration := range declarations {
+			source, target, err := declaration.Parse()
+			if err != nil {
+				logger.Warnf("Skipping invalid mount declaration %s: %v", declaration, err)
+				continue
+			}
+			if declaration.IsResourceURI() {
+				logger.Warnf("Skipping mount %s: resource URIs are not supported by the Apple container runtime", declaration)
+				continue
+			}
+			if !filepath.IsAbs(source) {
+				absolute, err := filepath.Abs(source)
+				if err != nil {
+					logger.Warnf("Skipping mount %s: %v", declaration, err)
+					continue
+				}
+				source = absolute
+			}
+			mounts = append(mounts, runtime.Mount{Source: source, Target: target, ReadOnly: readOnly})
+		}
+	}
+	add(profile.Read, true)
+	add(profile.Write, false)
+	return mounts
+}
+
+// stopArgs returns the arguments of the container CLI to stop a workload
+func stopArgs(workloadID string, options *runtime.StopOptions) []string {
+	args := []string{"stop"}
+	if options != nil {
+		if options.Signal != "" {
+			args = append(args, "--signal", options.Signal)
+		}
+		if options.Timeout > 0 {
+			args = append(args, "--time", strconv.Itoa(int(options.Timeout.Seconds())))
+		}
+	}
+	return append(args, workloadID)
+}
+
+// sortedKeys returns the keys of a map in order, so the arguments are deterministic
+func sortedKeys[V any](m map[string]V) []string {
+	keys := make([]string, 0, len(m))
+	for key := range m {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	return keys
+}
@@ -0,0 +1,399 @@
+// Package apple provides a container runtime backed by the container CLI of Apple's containerization
+// framework, which runs each container in a lightweight virtual machine on macOS without Docker Desktop.
+package apple
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	goruntime "runtime"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/permissions"
+)
+
+const (
+	// DefaultBinary is the name of the container CLI
+	DefaultBinary = "container"
+	// BinaryEnv is the environment variable for a custom path of the container CLI
+	BinaryEnv = "TOOLHIVE_APPLE_CONTAINER_BINARY"
+)
+
+var (
+	// ErrWorkloadNotFound is returned when a workload is not found
+	ErrWorkloadNotFound = errors.New("workload not found")
+
+	// ErrNetworkIsolationUnsupported is returned when network isolation is requested, since the
+	// container CLI has no internal networks to run the egress and DNS containers on
+	ErrNetworkIsolationUnsupported = errors.New("network isolation is not supported by the Apple container runtime")
+)
+
+// commandRunner runs the container CLI with the given arguments and returns its output
+type commandRunner func(ctx context.Context, args ...string) ([]byte, error)
+
+// Client implements the runtime.Runtime interface using the container CLI
+type Client struct {
+	binary string
+	run    commandRunner
+}
+
+// NewClient creates a new client for the container CLI, and checks that its system service is running
+func NewClient(ctx context.Context) (*Client, error) {
+	binary, err := findBinary()
+	if err != nil {
+		return nil, err
+	}
+
+	c := &Client{binary: binary}
+	c.run = c.runCommand
+	if err := c.IsRunning(ctx); err != nil {
+		return nil, err
+	}
+	return c, nil
+}
+
+// IsAvailable returns true if the container CLI is installed on macOS and its system service is running
+func IsAvailable(ctx context.Context) bool {
+	if goruntime.GOOS != "darwin" {
+		return false
+	}
+	_, err := NewClient(ctx)
+	return err == nil
+}
+
+// findBinary returns the path of the container CLI
+func findBinary() (string, error) {
+	binary := DefaultBinary
+	if custom := os.Getenv(BinaryEnv); custom != "" {
+		binary = custom
+	}
+	path, err := exec.LookPath(binary)
+	if err != nil {
+		return "", fmt.Errorf("apple container CLI not found: %w", err)
+	}
+	return path, nil
+}
+
+// runCommand runs the container CLI and returns its output, or an error including its error output
+func (c *Client) runCommand(ctx context.Context, args ...string) ([]byte, error) {
+	// #nosec G204 - the arguments are built by toolhive, and the binary is the container CLI
+	cmd := exec.CommandContext(ctx, c.binary, args...)
+	var stderr bytes.Buffer
+	cmd.Stderr = &stderr
+	output, err := cmd.Output()
+	if err != nil {
+		return nil, fmt.Errorf("container %s failed: %w: %s", args[0], err, strings.TrimSpace(stderr.String()))
+	}
+	return output, nil
+}
+
+// inspect returns the snapshot of a container
+func (c *Client) inspect(ctx context.Context, workloadID string) (*containerSnapshot, error) {
+	output, err := c.run(ctx, "inspect", workloadID)
+	if err != nil {
+		if strings.Contains(err.Error(), "not found") {
+			return nil, fmt.Errorf("%w: %s", ErrWorkloadNotFound, workloadID)
+		}
+		return nil, err
+	}
+
+	var snapshots []containerSnapshot
+	if err := json.Unmarshal(output, &snapshots); err != nil {
+		return nil, fmt.Errorf("failed to parse container inspect output: %w", err)
+	}
+	if len(snapshots) == 0 {
+		return nil, fmt.Errorf("%w: %s", ErrWorkloadNotFound, workloadID)
+	}
+	return &snapshots[0], nil
+}
+
+// DeployWorkload creates and starts a workload. Workloads with stdio are created, and started when attaching.
+// The container CLI gives each container its own virtual machine, so the capabilities and the
+// no-new-privileges option of the permission profile don't apply, and only its bind mounts are used.
+func (c *Client) DeployWorkload(
+	ctx context.Context,
+	image,
+	name string,
+	command []string,
+	envVars,
+	workloadLabels map[string]string,
+	permissionProfile *permissions.Profile,
+	transportType string,
+	options *runtime.DeployWorkloadOptions,
+	isolateNetwork bool,
+) (string, int, error) {
+	if isolateNetwork {
+		return "", 0, ErrNetworkIsolationUnsupported
+	}
+	switch transportType {
+	case "sse", "stdio", "inspector", "streamable-http", "websocket":
+		// valid, do nothing
+	default:
+		return "", 0, fmt.Errorf("unsupported transport type: %s", transportType)
+	}
+	if options == nil {
+		options = runtime.NewDeployWorkloadOptions()
+	}
+	if options.GPUs != "" || len(options.Devices) > 0 {
+		logger.Warnf("Ignoring the GPUs and devices of the workload, since they are not supported by the Apple container runtime")
+	}
+	if len(options.Networks) > 0 {
+		logger.Warnf("Ignoring the networks of the workload, since they are not supported by the Apple container runtime")
+	}
+
+	publish, hostPort, err := publishArgs(workloadLabels, options.PortBindings)
+	if err != nil {
+		return "", 0, fmt.Errorf("failed to generate port bindings: %v", err)
+	}
+
+	// Containers are identified by name, so a previous container of the workload is replaced
+	if err := c.RemoveWorkload(ctx, name); err != nil {
+		return "", 0, err
+	}
+
+	args := createArgs(name, image, command, envVars, workloadLabels,
+		mountsFromProfile(permissionProfile), publish, options.Resources, options.AttachStdio)
+	if _, err := c.run(ctx, args...); err != nil {
+		return "", 0, fmt.Errorf("failed to create container: %w", err)
+	}
+
+	if transportType == "stdio" {
+		return name, 0, nil
+	}
+	return name, hostPort, nil
+}
+
+// ListWorkloads lists the workloads managed by toolhive
+func (c *Client) ListWorkloads(ctx context.Context) ([]runtime.ContainerInfo, error) {
+	output, err := c.run(ctx, "ls", "--all", "--format", "json")
+	if err != nil {
+		return nil, fmt.Errorf("failed to list containers: %w", err)
+	}
+
+	var snapshots []containerSnapshot
+	if err := json.Unmarshal(output, &snapshots); err != nil {
+		return nil, fmt.Errorf("failed to parse container list output: %w", err)
+	}
+
+	result := make([]runtime.ContainerInfo, 0, len(snapshots))
+	for i := range snapshots {
+		info := snapshots[i].toContainerInfo()
+		if !labels.IsToolHiveContainer(info.Labels) || info.Labels["toolhive-auxiliary-workload"] == "true" {
+			continue
+		}
+		result = append(result, info)
+	}
+	return result, nil
+}
+
+// StopWorkload stops a workload. If the workload is not running, it returns success.
+func (c *Client) StopWorkload(ctx context.Context, workloadID string, options *runtime.StopOptions) error {
+	running, err := c.IsWorkloadRunning(ctx, workloadID)
+	if err != nil {
+		if errors.Is(err, ErrWorkloadNotFound) {
+			return nil
+		}
+		return err
+	}
+	if !running {
+		return nil
+	}
+
+	if _, err := c.run(ctx, stopArgs(workloadID, options)...); err != nil {
+		return fmt.Errorf("failed to stop workload %s: %w", workloadID, err)
+	}
+	return nil
+}
+
+// PauseWorkload implements runtime.Runtime.
+func (*Client) PauseWorkload(_ context.Context, _ string) error {
+	return fmt.Errorf("pausing workloads is not supported by the Apple container runtime")
+}
+
+// StartWorkload starts a stopped or exited workload. Workloads with stdio are started when attaching
+// to them instead.
+func (c *Client) StartWorkload(ctx context.Context, workloadID string) error {
+	if _, err := c.run(ctx, "start", workloadID); err != nil {
+		return fmt.Errorf("failed to start workload %s: %w", workloadID, err)
+	}
+	return nil
+}
+
+// UnpauseWorkload implements runtime.Runtime.
+func (*Client) UnpauseWorkload(_ context.Context, _ string) error {
+	return fmt.Errorf("pausing workloads is not supported by the Apple container runtime")
+}
+
+// RemoveWorkload removes a workload. If the workload doesn't exist, it returns success.
+func (c *Client) RemoveWorkload(ctx context.Context, workloadID string) error {
+	if _, err := c.inspect(ctx, workloadID); err != nil {
+		if errors.Is(err, ErrWorkloadNotFound) {
+			return nil
+		}
+		return err
+	}
+
+	if _, err := c.run(ctx, "delete", "--force", workloadID); err != nil {
+		return fmt.Errorf("failed to remove workload %s: %w", workloadID, err)
+	}
+	return nil
+}
+
+// GetWorkloadLogs gets the logs of a workload. If options.Follow is true, the logs are streamed to stdout.
+// The container CLI can't select the logs written since a given time.
+func (c *Client) GetWorkloadLogs(ctx context.Context, workloadID string, options runtime.LogOptions) (string, error) {
+	if !options.Since.IsZero() {
+		return "", fmt.Errorf("selecting logs by time is not supported by the Apple container runtime: %w",
+			errors.ErrUnsupported)
+	}
+	args := []string{"logs"}
+	if options.Tail > 0 {
+		args = append(args, "-n", strconv.Itoa(options.Tail))
+	}
+
+	if options.Follow {
+		// #nosec G204 - the arguments are built by toolhive, and the binary is the container CLI
+		cmd := exec.CommandContext(ctx, c.binary, append(args, "--follow", workloadID)...)
+		cmd.Stdout = os.Stdout
+		cmd.Stderr = os.Stderr
+		if err := cmd.Run(); err != nil && ctx.Err() == nil {
+			return "", fmt.Errorf("failed to follow workload logs: %w", err)
+		}
+		return "", nil
+	}
+
+	output, err := c.run(ctx, append(args, workloadID)...)
+	if err != nil {
+		return "", fmt.Errorf("failed to get workload logs: %w", err)
+	}
+	return string(output), nil
+}
+
+// StreamWorkloadLogs is not supported, since the container CLI can't select the logs written since a given time
+func (*Client) StreamWorkloadLogs(_ context.Context, _ string, _ time.Time, _, _ io.Writer) error {
+	return fmt.Errorf("streaming workload logs is not supported by the Apple container runtime: %w", errors.ErrUnsupported)
+}
+
+// IsWorkloadRunning checks if a workload is running
+func (c *Client) IsWorkloadRunning(ctx context.Context, workloadID string) (bool, error) {
+	snapshot, err := c.inspect(ctx, workloadID)
+	if err != nil {
+		return false, err
+	}
+	return snapshot.Status == statusRunning, nil
+}
+
+// GetWorkloadInfo gets workload information
+func (c *Client) GetWorkloadInfo(ctx context.Context, workloadID string) (runtime.ContainerInfo, error) {
+	snapshot, err := c.inspect(ctx, workloadID)
+	if err != nil {
+		return runtime.ContainerInfo{}, err
+	}
+	return snapshot.toContainerInfo(), nil
+}
+
+// AttachToWorkload starts a workload created for stdio and attaches to its input and output.
+// The container CLI can't attach to a running container, so the workload must not be running yet.
+func (c *Client) AttachToWorkload(ctx context.Context, workloadID string) (io.WriteCloser, io.ReadCloser, error) {
+	running, err := c.IsWorkloadRunning(ctx, workloadID)
+	if err != nil {
+		return nil, nil, err
+	}
+	if running {
+		return nil, nil, fmt.Errorf("cannot attach to workload %s: it is already running", workloadID)
+	}
+
+	// The process is not bound to the context of the call, since it runs as long as the workload
+	// #nosec G204 - the arguments are built by toolhive, and the binary is the container CLI
+	cmd := exec.Command(c.binary, "start", "--attach", "--interactive", workloadID)
+	stdin, err := cmd.StdinPipe()
+	if err != nil {
+		return nil, nil, fmt.Errorf("failed to attach to workload: %w", err)
+	}
+	stdout, err := cmd.StdoutPipe()
+	if err != nil {
+		return nil, nil, fmt.Errorf("failed to attach to workload: %w", err)
+	}
+
+	// Only stdout carries JSON-RPC messages; stderr is diagnostic output of the server, so it is logged
+	stderrWriter := logger.NewLineWriter(logger.Infof, fmt.Sprintf("[%s] ", workloadID))
+	cmd.Stderr = stderrWriter
+
+	if err := cmd.Start(); err != nil {
+		return nil, nil, fmt.Errorf("failed to start workload %s: %w", workloadID, err)
+	}
+	go func() {
+		defer stderrWriter.Flush()
+		if err := cmd.Wait(); err != nil {
+			logger.Debugf("Workload %s exited: %v", workloadID, err)
+		}
+	}()
+
+	return stdin, stdout, nil
+}
+
+// ExecInWorkload runs a command in a workload and returns its exit code
+func (c *Client) ExecInWorkload(
+	ctx context.Context,
+	workloadID string,
+	command []string,
+	options *runtime.ExecOptions,
+) (int, error) {
+	args := []string{"exec"}
+	if options.Stdin != nil {
+		args = append(args, "--interactive")
+	}
+	if options.TTY {
+		args = append(args, "--tty")
+	}
+	args = append(args, workloadID)
+	args = append(args, command...)
+
+	// #nosec G204 - the arguments are built by toolhive, and the binary is the container CLI
+	cmd := exec.CommandContext(ctx, c.binary, args...)
+	cmd.Stdin = options.Stdin
+	cmd.Stdout = options.Stdout
+	cmd.Stderr = options.Stderr
+	if err := cmd.Run(); err != nil {
+		var exitErr *exec.ExitError
+		if errors.As(err, &exitErr) {
+			return exitErr.ExitCode(), nil
+		}
+		return 0, fmt.Errorf("failed to exec in workload %s: %w", workloadID, err)
+	}
+	return 0, nil
+}
+
+// UpdateNetworkPermissions implements runtime.Runtime.
+func (*Client) UpdateNetworkPermissions(_ context.Context, workloadName string, _ *permissions.Profile) error {
+	// Without network isolation there is no egress proxy which enforces the network permissions
+	logger.Debugf("Network permissions of %s are not enforced by the Apple container runtime", workloadName)
+	return nil
+}
+
+// WorkloadEvents implements runtime.Runtime.
+func (*Client) WorkloadEvents(_ context.Context, _ *runtime.EventOptions) (<-chan runtime.WorkloadEvent, <-chan error) {
+	workloadEvents := make(chan runtime.WorkloadEvent)
+	close(workloadEvents)
+	errs := make(chan error, 1)
+	errs <- fmt.Errorf("workload events are not supported by the Apple container runtime")
+	return workloadEvents, errs
+}
+
+// IsRunning checks that the system service of the container CLI is running
+func (c *Client) IsRunning(ctx context.Context) error {
+	if _, err := c.run(ctx, "system", "status"); err != nil {
+		return fmt.Errorf("apple container system service is not running (start it with container system start): %w", err)
+	}
+	return nil
+}
@@ -0,0 +1,201 @@
+package apple
+
+import (
+	"context"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/container/clitest"
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/permissions"
+)
+
+func init() {
+	// Initialize the logger for tests
+	logger.Initialize()
+}
+
+const listOutput = `[
+  {
+    "status": "running",
+    "configuration": {
+      "id": "fetch",
+      "image": {"reference": "docker.io/mcp/fetch:latest", "descriptor": {"digest": "sha256:abc"}},
+      "labels": {"toolhive": "true", "toolhive-name": "fetch"},
+      "publishedPorts": [{"hostAddress": "0.0.0.0", "hostPort": 41234, "containerPort": 8080, "proto": "tcp"}]
+    }
+  },
+  {
+    "status": "stopped",
+    "configuration": {
+      "id": "github",
+      "image": {"reference": "ghcr.io/github/github-mcp-server:latest", "descriptor": {"digest": "sha256:def"}},
+      "labels": {"toolhive": "true", "toolhive-name": "github"}
+    }
+  },
+  {
+    "status": "running",
+    "configuration": {
+      "id": "other",
+      "image": {"reference": "docker.io/library/nginx:latest", "descriptor": {"digest": "sha256:123"}},
+      "labels": {}
+    }
+  }
+]`
+
+// newTestClient returns a client running a fake CLI, which answers the commands with the given outputs
+func newTestClient(outputs map[string]string) (*Client, *clitest.CLI) {
+	cli := clitest.New(outputs)
+	return &Client{binary: DefaultBinary, run: cli.Run}, cli
+}
+
+func TestListWorkloads(t *testing.T) {
+	t.Parallel()
+
+	client, _ := newTestClient(map[string]string{"ls": listOutput})
+	workloads, err := client.ListWorkloads(context.Background())
+	require.NoError(t, err)
+	require.Len(t, workloads, 2)
+
+	assert.Equal(t, "fetch", workloads[0].ID)
+	assert.Equal(t, "fetch", workloads[0].Name)
+	assert.Equal(t, "docker.io/mcp/fetch:latest", workloads[0].Image)
+	assert.Equal(t, "sha256:abc", workloads[0].ImageID)
+	assert.Equal(t, "running", workloads[0].State)
+	assert.Equal(t, []runtime.PortMapping{{ContainerPort: 8080, HostPort: 41234, Protocol: "tcp"}}, workloads[0].Ports)
+
+	assert.Equal(t, "github", workloads[1].Name)
+	assert.Equal(t, "exited", workloads[1].State)
+}
+
+func TestIsWorkloadRunning(t *testing.T) {
+	t.Parallel()
+
+	client, _ := newTestClient(map[string]string{"inspect": `[{"status": "running", "configuration": {"id": "fetch"}}]`})
+	running, err := client.IsWorkloadRunning(context.Background(), "fetch")
+	require.NoError(t, err)
+	assert.True(t, running)
+
+	client, _ = newTestClient(map[string]string{"inspect": `[]`})
+	_, err = client.IsWorkloadRunning(context.Background(), "missing")
+	assert.ErrorIs(t, err, ErrWorkloadNotFound)
+}
+
+func TestStopWorkload(t *testing.T) {
+	t.Parallel()
+
+	client, cli := newTestClient(map[string]string{
+		"inspect": `[{"status": "running", "configuration": {"id": "fetch"}}]`,
+		"stop":    "fetch",
+	})
+	err := client.StopWorkload(context.Background(), "fetch", &runtime.StopOptions{Timeout: 30 * time.Second, Signal: "SIGINT"})
+	require.NoError(t, err)
+	assert.Equal(t, []string{"stop", "--signal", "SIGINT", "--time", "30", "fetch"}, cli.Calls()[1])
+
+	// Stopping a workload which doesn't exist succeeds
+	client, _ = newTestClient(map[string]string{"inspect": `[]`})
+	assert.NoError(t, client.StopWorkload(context.Background(), "missing", nil))
+}
+
+func TestDeployWorkload_NetworkIsolation(t *testing.T) {
+	t.Parallel()
+
+	client, cli := newTestClient(nil)
+	_, _, err := client.DeployWorkload(context.Background(), "image", "name", nil, nil, nil,
+		permissions.BuiltinNoneProfile(), "stdio", nil, true)
+	assert.ErrorIs(t, err, ErrNetworkIsolationUnsupported)
+	assert.Empty(t, cli.Calls())
+}
+
+func TestCreateArgs(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name        string
+		attachStdio bool
+		mounts      []runtime.Mount
+		publish     []string
+		resources   *runtime.ResourceLimits
+		expected    []string
+	}{
+		{
+			name:        "Stdio",
+			attachStdio: true,
+			expected: []string{
+				"create", "--interactive", "--name", "fetch",
+				"--env", "A=1", "--env", "B=2", "--label", "toolhive=true",
+				"mcp/fetch", "--verbose",
+			},
+		},
+		{
+			name:    "Published port and mounts",
+			mounts:  []runtime.Mount{{Source: "/data", Target: "/data", ReadOnly: true}, {Source: "/tmp/out", Target: "/out"}},
+			publish: []string{"41234:8080/tcp"},
+			expected: []string{
+				"run", "--detach", "--name", "fetch",
+				"--env", "A=1", "--env", "B=2", "--label", "toolhive=true",
+				"--mount", "type=bind,source=/data,target=/data,readonly",
+				"--mount", "type=bind,source=/tmp/out,target=/out",
+				"--publish", "41234:8080/tcp",
+				"mcp/fetch", "--verbose",
+			},
+		},
+		{
+			name:      "Resource limits",
+			resources: &runtime.ResourceLimits{CPUs: 1.5, Memory: 300 << 20, PidsLimit: 100},
+			expected: []string{
+				"run", "--detach", "--name", "fetch",
+				"--env", "A=1", "--env", "B=2", "--label", "toolhive=true",
+				"--cpus", "2", "--memory", "300M",
+				"mcp/fetch", "--verbose",
+			},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			args := createArgs("fetch", "mcp/fetch", []string{"--verbose"},
+				map[string]string{"B": "2", "A": "1"}, map[string]string{"toolhive": "true"},
+				tt.mounts, tt.publish, tt.resources, tt.attachStdio)
+			assert.Equal(t, tt.expected, args)
+		})
+	}
+}
+
+func TestPublishArgs(t *testing.T) {
+	t.Parallel()
+
+	// Auxiliary workloads are published on the requested port
+	publish, hostPort, err := publishArgs(
+		map[string]string{"toolhive-auxiliary": "true"},
+		map[string][]runtime.PortBinding{"6274/tcp": {{HostIP: "127.0.0.1", HostPort: "6274"}}},
+	)
+	require.NoError(t, err)
+	assert.Equal(t, 6274, hostPort)
+	assert.Equal(t, []string{"127.0.0.1:6274:6274/tcp"}, publish)
+
+	// Other workloads are published on a random port
+	publish, hostPort, err = publishArgs(nil, map[string][]runtime.PortBinding{"8080/tcp": {{HostPort: ""}}})
+	require.NoError(t, err)
+	assert.NotZero(t, hostPort)
+	require.Len(t, publish, 1)
+	assert.Regexp(t, `^\d+:8080/tcp$`, publish[0])
+}
+
+func TestMountsFromProfile(t *testing.T) {
+	t.Parallel()
+
+	profile := permissions.BuiltinNoneProfile()
+	profile.Read = []permissions.MountDeclaration{"/data", "volume://cache:/cache"}
+	profile.Write = []permissions.MountDeclaration{"/tmp/out:/out"}
+
+	assert.Equal(t, []runtime.Mount{
+		{Source: "/data", Target: "/data", ReadOnly: true},
+		{Source: "/tmp/out", Target: "/out"},
+	}, mountsFromProfile(profile))
+}
@@ -0,0 +1,74 @@
+package apple
+
+import (
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+)
+
+// Container states reported by the container CLI
+const (
+	statusRunning = "running"
+	statusStopped = "stopped"
+)
+
+// containerSnapshot is the state of a container as reported by container ls and container inspect
+type containerSnapshot struct {
+	Status        string                 `json:"status"`
+	Configuration containerConfiguration `json:"configuration"`
+}
+
+// containerConfiguration is the configuration a container was created with
+type containerConfiguration struct {
+	ID             string            `json:"id"`
+	Image          imageDescription  `json:"image"`
+	Labels         map[string]string `json:"labels"`
+	PublishedPorts []publishedPort   `json:"publishedPorts"`
+}
+
+// imageDescription describes the image of a container
+type imageDescription struct {
+	Reference  string          `json:"reference"`
+	Descriptor imageDescriptor `json:"descriptor"`
+}
+
+// imageDescriptor is the OCI descriptor of an image
+type imageDescriptor struct {
+	Digest string `json:"digest"`
+}
+
+// publishedPort is a container port published on the host
+type publishedPort struct {
+	HostAddress   string `json:"hostAddress"`
+	HostPort      int    `json:"hostPort"`
+	ContainerPort int    `json:"containerPort"`
+	Proto         string `json:"proto"`
+}
+
+// toContainerInfo converts a container snapshot to the runtime representation of a workload.
+// Containers are identified by their name, so the ID and the name of the workload are the same.
+func (s *containerSnapshot) toContainerInfo() runtime.ContainerInfo {
+	ports := make([]runtime.PortMapping, 0, len(s.Configuration.PublishedPorts))
+	for _, p := range s.Configuration.PublishedPorts {
+		ports = append(ports, runtime.PortMapping{
+			ContainerPort: p.ContainerPort,
+			HostPort:      p.HostPort,
+			Protocol:      p.Proto,
+		})
+	}
+
+	// Stopped containers are reported as exited, like by the other runtimes
+	state := s.Status
+	if state == statusStopped {
+		state = "exited"
+	}
+
+	return runtime.ContainerInfo{
+		ID:      s.Configuration.ID,
+		Name:    s.Configuration.ID,
+		Image:   s.Configuration.Image.Reference,
+		ImageID: s.Configuration.Image.Descriptor.Digest,
+		Status:  s.Status,
+		State:   state,
+		Labels:  s.Configuration.Labels,
+		Ports:   ports,
+	}
+}
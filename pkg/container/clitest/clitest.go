@@ -0,0 +1,53 @@
+// Package clitest provides a fake container CLI for testing the runtimes which are managed through a CLI,
+// such as the Apple container and Finch runtimes.
+package clitest
+
+import (
+	"context"
+	"errors"
+	"strings"
+	"sync"
+)
+
+// ErrorPrefix marks the outputs which the fake CLI returns as errors
+const ErrorPrefix = "error: "
+
+// CLI is a fake container CLI which answers each command with the output given for its subcommand, and
+// records the arguments it is run with. Outputs starting with ErrorPrefix are returned as errors.
+type CLI struct {
+	outputs map[string]string
+
+	mutex sync.Mutex
+	calls [][]string
+}
+
+// New creates a fake CLI answering the commands with the given outputs, keyed by the subcommand
+func New(outputs map[string]string) *CLI {
+	return &CLI{outputs: outputs}
+}
+
+// Run runs the fake CLI with the given arguments, like the command runners of the clients
+func (c *CLI) Run(_ context.Context, args ...string) ([]byte, error) {
+	c.mutex.Lock()
+	defer c.mutex.Unlock()
+
+	c.calls = append(c.calls, args)
+	if len(args) == 0 {
+		return nil, errors.New("no command")
+	}
+	output, ok := c.outputs[args[0]]
+	if !ok {
+		return nil, errors.New("unexpected command")
+	}
+	if strings.HasPrefix(output, ErrorPrefix) {
+		return nil, errors.New(output)
+	}
+	return []byte(output), nil
+}
+
+// Calls returns the arguments of each run of the fake CLI
+func (c *CLI) Calls() [][]string {
+	c.mutex.Lock()
+	defer c.mutex.Unlock()
+	return c.calls
+}
@@ -0,0 +1,671 @@
+// Package containerd provides a container runtime for hosts which run containerd without Docker or Podman,
+// like k3s nodes and Bottlerocket hosts. The workloads are managed through the CRI API of containerd, the
+// API the kubelet uses, so no container CLI is needed. Each workload runs in its own pod sandbox, which
+// publishes its ports on the host. containerd does not restart exited workloads itself, so a workload
+// which is started again is created again from the configuration kept with its container.
+package containerd
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net/url"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/adrg/xdg"
+	"google.golang.org/grpc"
+	"google.golang.org/grpc/credentials/insecure"
+	"k8s.io/client-go/rest"
+	"k8s.io/client-go/tools/remotecommand"
+	utilexec "k8s.io/client-go/util/exec"
+	runtimeapi "k8s.io/cri-api/pkg/apis/runtime/v1"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/permissions"
+)
+
+const (
+	// AddressEnv is the environment variable for the containerd socket
+	AddressEnv = "CONTAINERD_ADDRESS"
+
+	// sandboxNamespace is the namespace of the pod sandboxes of the workloads, which keeps them apart from
+	// the pods of the kubelet on the same host
+	sandboxNamespace = "toolhive"
+	// workloadLabel is the label of the pod sandboxes and containers of a workload, whose value is the name
+	// of the workload. Workloads are identified by their name, which stays the same when they are created again.
+	workloadLabel = "toolhive-containerd-workload"
+	// specAnnotation is the annotation of the container of a workload which holds its configuration
+	specAnnotation = "toolhive-containerd-spec"
+	// defaultStopTimeout is the time a workload has to exit after it is signalled to stop, unless its
+	// options or the stop options set another timeout
+	defaultStopTimeout = 10 * time.Second
+)
+
+// socketPaths are the well-known containerd sockets, of containerd itself and of k3s
+var socketPaths = []string{
+	"/run/containerd/containerd.sock",
+	"/run/k3s/containerd/containerd.sock",
+}
+
+var (
+	// ErrWorkloadNotFound is returned when a workload is not found
+	ErrWorkloadNotFound = errors.New("workload not found")
+
+	// ErrNetworkIsolationUnsupported is returned when network isolation is requested
+	ErrNetworkIsolationUnsupported = errors.New("network isolation is not supported by the containerd runtime")
+)
+
+// Client implements the runtime.Runtime interface using the CRI API of containerd
+type Client struct {
+	address        string
+	logDir         string
+	conn           io.Closer
+	runtimeService runtimeapi.RuntimeServiceClient
+	imageService   runtimeapi.ImageServiceClient
+}
+
+// NewClient creates a new client for the containerd socket set in CONTAINERD_ADDRESS,
+// or the first well-known socket which exists, and checks that containerd is running
+func NewClient(ctx context.Context) (*Client, error) {
+	address, err := findSocket()
+	if err != nil {
+		return nil, err
+	}
+
+	conn, err := grpc.NewClient("unix://"+address, grpc.WithTransportCredentials(insecure.NewCredentials()))
+	if err != nil {
+		return nil, fmt.Errorf("failed to connect to containerd at %s: %w", address, err)
+	}
+	c := &Client{
+		address:        address,
+		logDir:         filepath.Join(xdg.DataHome, "toolhive", "logs", "containerd"),
+		conn:           conn,
+		runtimeService: runtimeapi.NewRuntimeServiceClient(conn),
+		imageService:   runtimeapi.NewImageServiceClient(conn),
+	}
+	if err := c.IsRunning(ctx); err != nil {
+		_ = conn.Close()
+		return nil, err
+	}
+	logger.Debugf("Using containerd socket at %s", address)
+	return c, nil
+}
+
+// IsAvailable returns true if containerd is running and serves the CRI API
+func IsAvailable(ctx context.Context) bool {
+	c, err := NewClient(ctx)
+	if err != nil {
+		return false
+	}
+	_ = c.conn.Close()
+	return true
+}
+
+// findSocket returns the containerd socket to use
+func findSocket() (string, error) {
+	if address := os.Getenv(AddressEnv); address != "" {
+		return strings.TrimPrefix(address, "unix://"), nil
+	}
+	for _, socketPath := range socketPaths {
+		if _, err := os.Stat(socketPath); err == nil {
+			return socketPath, nil
+		}
+	}
+	return "", fmt.Errorf("no containerd socket found (tried %s)", strings.Join(socketPaths, ", "))
+}
+
+// findContainer returns the newest container of a workload
+func (c *Client) findContainer(ctx context.Context, workloadID string) (*runtimeapi.Container, error) {
+	response, err := c.runtimeService.ListContainers(ctx, &runtimeapi.ListContainersRequest{
+		Filter: &runtimeapi.ContainerFilter{LabelSelector: map[string]string{workloadLabel: workloadID}},
+	})
+	if err != nil {
+		return nil, fmt.Errorf("failed to list containers: %w", err)
+	}
+
+	var newest *runtimeapi.Container
+	for _, ctr := range response.Containers {
+		if newest == nil || ctr.CreatedAt > newest.CreatedAt {
+			newest = ctr
+		}
+	}
+	if newest == nil {
+		return nil, fmt.Errorf("%w: %s", ErrWorkloadNotFound, workloadID)
+	}
+	return newest, nil
+}
+
+// ensureImage pulls an image if it doesn't exist on the host, and returns its reference on the host
+func (c *Client) ensureImage(ctx context.Context, image string) (string, error) {
+	status, err := c.imageService.ImageStatus(ctx, &runtimeapi.ImageStatusRequest{
+		Image: &runtimeapi.ImageSpec{Image: image},
+	})
+	if err != nil {
+		return "", fmt.Errorf("failed to get the status of image %s: %w", image, err)
+	}
+	if status.Image != nil {
+		return status.Image.Id, nil
+	}
+
+	logger.Infof("Pulling image %s...", image)
+	pulled, err := c.imageService.PullImage(ctx, &runtimeapi.PullImageRequest{
+		Image: &runtimeapi.ImageSpec{Image: image},
+	})
+	if err != nil {
+		return "", fmt.Errorf("failed to pull image %s: %w", image, err)
+	}
+	return pulled.ImageRef, nil
+}
+
+// DeployWorkload creates and starts a workload
+func (c *Client) DeployWorkload(
+	ctx context.Context,
+	image,
+	name string,
+	command []string,
+	envVars,
+	workloadLabels map[string]string,
+	permissionProfile *permissions.Profile,
+	transportType string,
+	options *runtime.DeployWorkloadOptions,
+	isolateNetwork bool,
+) (string, int, error) {
+	if isolateNetwork {
+		return "", 0, ErrNetworkIsolationUnsupported
+	}
+	switch transportType {
+	case "sse", "stdio", "inspector", "streamable-http", "websocket":
+		// valid, do nothing
+	default:
+		return "", 0, fmt.Errorf("unsupported transport type: %s", transportType)
+	}
+	if options == nil {
+		options = runtime.NewDeployWorkloadOptions()
+	}
+	if _, err := runtime.ParseRestartPolicy(options.RestartPolicy); err != nil {
+		return "", 0, err
+	}
+	if len(options.Networks) > 0 {
+		logger.Warnf("Ignoring the networks of the workload, since they are not supported by the containerd runtime")
+	}
+	if options.StopSignal != "" {
+		logger.Debugf("Ignoring the stop signal of %s, since containerd uses the stop signal of the image", name)
+	}
+
+	spec, hostPort, err := newWorkloadSpec(name, image, command, envVars, workloadLabels, permissionProfile, options)
+	if err != nil {
+		return "", 0, err
+	}
+
+	// A previous container of the workload is replaced
+	if err := c.RemoveWorkload(ctx, name); err != nil {
+		return "", 0, err
+	}
+	if err := c.createWorkload(ctx, spec, 0); err != nil {
+		return "", 0, err
+	}
+
+	if transportType == "stdio" {
+		return name, 0, nil
+	}
+	return name, hostPort, nil
+}
+
+// createWorkload creates the pod sandbox and the container of a workload, and starts the container
+func (c *Client) createWorkload(ctx context.Context, spec *workloadSpec, attempt uint32) error {
+	imageRef, err := c.ensureImage(ctx, spec.Image)
+	if err != nil {
+		return err
+	}
+	annotation, err := json.Marshal(spec)
+	if err != nil {
+		return fmt.Errorf("failed to encode the configuration of workload %s: %w", spec.Name, err)
+	}
+	logDirectory := filepath.Dir(c.logPath(spec.Name))
+	if err := os.MkdirAll(logDirectory, 0750); err != nil {
+		return fmt.Errorf("failed to create the log directory of workload %s: %w", spec.Name, err)
+	}
+
+	sandboxConfig := spec.sandboxConfig(logDirectory, attempt)
+	sandbox, err := c.runtimeService.RunPodSandbox(ctx, &runtimeapi.RunPodSandboxRequest{Config: sandboxConfig})
+	if err != nil {
+		return fmt.Errorf("failed to create pod sandbox: %w", err)
+	}
+
+	created, err := c.runtimeService.CreateContainer(ctx, &runtimeapi.CreateContainerRequest{
+		PodSandboxId:  sandbox.PodSandboxId,
+		Config:        spec.containerConfig(imageRef, string(annotation), attempt),
+		SandboxConfig: sandboxConfig,
+	})
+	if err == nil {
+		_, err = c.runtimeService.StartContainer(ctx, &runtimeapi.StartContainerRequest{ContainerId: created.ContainerId})
+	}
+	if err != nil {
+		if removeErr := c.removeSandbox(ctx, sandbox.PodSandboxId); removeErr != nil {
+			logger.Warnf("Warning: Failed to remove the pod sandbox of %s: %v", spec.Name, removeErr)
+		}
+		return fmt.Errorf("failed to create container: %w", err)
+	}
+	return nil
+}
+
+// logPath returns the path of the log file of a workload
+func (c *Client) logPath(name string) string {
+	return filepath.Join(c.logDir, name, containerLogFile)
+}
+
+// removeSandboxes removes the pod sandboxes of a workload, with their containers
+func (c *Client) removeSandboxes(ctx context.Context, name string) error {
+	response, err := c.runtimeService.ListPodSandbox(ctx, &runtimeapi.ListPodSandboxRequest{
+		Filter: &runtimeapi.PodSandboxFilter{LabelSelector: map[string]string{workloadLabel: name}},
+	})
+	if err != nil {
+		return fmt.Errorf("failed to list pod sandboxes: %w", err)
+	}
+	for _, sandbox := range response.Items {
+		if err := c.removeSandbox(ctx, sandbox.Id); err != nil {
+			return fmt.Errorf("failed to remove workload %s: %w", name, err)
+		}
+	}
+	return nil
+}
+
+// removeSandbox stops and removes a pod sandbox, with its containers
+func (c *Client) removeSandbox(ctx context.Context, sandboxID string) error {
+	_, err := c.runtimeService.StopPodSandbox(ctx, &runtimeapi.StopPodSandboxRequest{PodSandboxId: sandboxID})
+	if err != nil {
+		return err
+	}
+	_, err = c.runtimeService.RemovePodSandbox(ctx, &runtimeapi.RemovePodSandboxRequest{PodSandboxId: sandboxID})
+	return err
+}
+
+// ListWorkloads lists the workloads managed by toolhive
+func (c *Client) ListWorkloads(ctx context.Context) ([]runtime.ContainerInfo, error) {
+	response, err := c.runtimeService.ListContainers(ctx, &runtimeapi.ListContainersRequest{})
+	if err != nil {
+		return nil, fmt.Errorf("failed to list containers: %w", err)
+	}
+
+	// A workload which was created again may still have the container it had before
+	newest := map[string]*runtimeapi.Container{}
+	for _, ctr := range response.Containers {
+		name := ctr.Labels[workloadLabel]
+		if name == "" {
+			continue
+		}
+		if previous, ok := newest[name]; !ok || ctr.CreatedAt > previous.CreatedAt {
+			newest[name] = ctr
+		}
+	}
+
+	result := make([]runtime.ContainerInfo, 0, len(newest))
+	for _, name := range sortedKeys(newest) {
+		ctr := newest[name]
+		info, err := toContainerInfo(ctr.State, ctr.CreatedAt, ctr.ImageRef, ctr.Annotations)
+		if err != nil {
+			logger.Warnf("Warning: Skipping workload %s: %v", name, err)
+			continue
+		}
+		if !labels.IsToolHiveContainer(info.Labels) || info.Labels["toolhive-auxiliary-workload"] == "true" {
+			continue
+		}
+		result = append(result, info)
+	}
+	return result, nil
+}
+
+// StopWorkload stops a workload. If the workload is not running, it returns success.
+// The pod sandbox of the workload is kept, so its ports stay published while it is stopped.
+func (c *Client) StopWorkload(ctx context.Context, workloadID string, options *runtime.StopOptions) error {
+	ctr, err := c.findContainer(ctx, workloadID)
+	if err != nil {
+		if errors.Is(err, ErrWorkloadNotFound) {
+			return nil
+		}
+		return err
+	}
+	if ctr.State != runtimeapi.ContainerState_CONTAINER_RUNNING {
+		return nil
+	}
+
+	timeout := defaultStopTimeout
+	if spec, err := decodeSpec(ctr.Annotations); err == nil && spec.StopTimeout > 0 {
+		timeout = spec.StopTimeout
+	}
+	if options != nil && options.Timeout > 0 {
+		timeout = options.Timeout
+	}
+	if options != nil && options.Signal != "" {
+		logger.Debugf("Ignoring the stop signal of %s, since containerd uses the stop signal of the image", workloadID)
+	}
+
+	if _, err := c.runtimeService.StopContainer(ctx, &runtimeapi.StopContainerRequest{
+		ContainerId: ctr.Id,
+		Timeout:     int64(timeout.Seconds()),
+	}); err != nil {
+		return fmt.Errorf("failed to stop workload %s: %w", workloadID, err)
+	}
+	return nil
+}
+
+// PauseWorkload is not supported, since the CRI API can't pause containers
+func (*Client) PauseWorkload(_ context.Context, _ string) error {
+	return fmt.Errorf("pausing workloads is not supported by the containerd runtime")
+}
+
+// StartWorkload starts a stopped or exited workload. Containers which exited can't be started again through
+// the CRI API, so the workload is created again, with the same published ports and log file.
+func (c *Client) StartWorkload(ctx context.Context, workloadID string) error {
+	ctr, err := c.findContainer(ctx, workloadID)
+	if err != nil {
+		return err
+	}
+
+	switch ctr.State {
+	case runtimeapi.ContainerState_CONTAINER_RUNNING:
+		return nil
+	case runtimeapi.ContainerState_CONTAINER_CREATED:
+		if _, err := c.runtimeService.StartContainer(ctx, &runtimeapi.StartContainerRequest{ContainerId: ctr.Id}); err != nil {
+			return fmt.Errorf("failed to start workload %s: %w", workloadID, err)
+		}
+		return nil
+	case runtimeapi.ContainerState_CONTAINER_EXITED, runtimeapi.ContainerState_CONTAINER_UNKNOWN:
+		// created again below
+	}
+
+	spec, err := decodeSpec(ctr.Annotations)
+	if err != nil {
+		return fmt.Errorf("failed to start workload %s: %w", workloadID, err)
+	}
+	if err := c.removeSandboxes(ctx, workloadID); err != nil {
+		return err
+	}
+	if err := c.createWorkload(ctx, spec, ctr.Metadata.GetAttempt()+1); err != nil {
+		return fmt.Errorf("failed to start workload %s: %w", workloadID, err)
+	}
+	return nil
+}
+
+// UnpauseWorkload is not supported, since the CRI API can't pause containers
+func (*Client) UnpauseWorkload(_ context.Context, _ string) error {
+	return fmt.Errorf("pausing workloads is not supported by the containerd runtime")
+}
+
+// RemoveWorkload removes a workload and its logs. If the workload doesn't exist, it returns success.
+func (c *Client) RemoveWorkload(ctx context.Context, workloadID string) error {
+	if err := c.removeSandboxes(ctx, workloadID); err != nil {
+		return err
+	}
+	if err := os.RemoveAll(filepath.Dir(c.logPath(workloadID))); err != nil {
+		logger.Warnf("Warning: Failed to remove the logs of workload %s: %v", workloadID, err)
+	}
+	return nil
+}
+
+// GetWorkloadLogs gets the logs of a workload. If options.Follow is true, the logs are streamed to stdout
+// until the workload stops.
+func (c *Client) GetWorkloadLogs(ctx context.Context, workloadID string, options runtime.LogOptions) (string, error) {
+	if _, err := c.findContainer(ctx, workloadID); err != nil {
+		return "", err
+	}
+	logPath := c.logPath(workloadID)
+
+	if options.Follow {
+		stopped := func(ctx context.Context) bool {
+			running, err := c.IsWorkloadRunning(ctx, workloadID)
+			return err != nil || !running
+		}
+		if err := followLogs(ctx, logPath, options.Since, os.Stdout, os.Stderr, stopped); err != nil {
+			return "", fmt.Errorf("failed to follow workload logs: %w", err)
+		}
+		return "", nil
+	}
+
+	logs, err := readLogs(logPath, options.Since, options.Tail)
+	if err != nil {
+		return "", fmt.Errorf("failed to get workload logs: %w", err)
+	}
+	return logs, nil
+}
+
+// StreamWorkloadLogs follows the logs of a workload written since the given time, until the context is
+// cancelled. The logs of a workload which is created again are written to the same log file.
+func (c *Client) StreamWorkloadLogs(
+	ctx context.Context,
+	workloadID string,
+	since time.Time,
+	stdout, stderr io.Writer,
+) error {
+	if err := followLogs(ctx, c.logPath(workloadID), since, stdout, stderr, nil); err != nil {
+		return fmt.Errorf("failed to stream workload logs: %w", err)
+	}
+	return nil
+}
+
+// IsWorkloadRunning checks if a workload is running
+func (c *Client) IsWorkloadRunning(ctx context.Context, workloadID string) (bool, error) {
+	ctr, err := c.findContainer(ctx, workloadID)
+	if err != nil {
+		return false, err
+	}
+	return ctr.State == runtimeapi.ContainerState_CONTAINER_RUNNING, nil
+}
+
+// GetWorkloadInfo gets workload information
+func (c *Client) GetWorkloadInfo(ctx context.Context, workloadID string) (runtime.ContainerInfo, error) {
+	ctr, err := c.findContainer(ctx, workloadID)
+	if err != nil {
+		return runtime.ContainerInfo{}, err
+	}
+	response, err := c.runtimeService.ContainerStatus(ctx, &runtimeapi.ContainerStatusRequest{ContainerId: ctr.Id})
+	if err != nil {
+		return runtime.ContainerInfo{}, fmt.Errorf("failed to get the status of workload %s: %w", workloadID, err)
+	}
+	status := response.Status
+
+	info, err := toContainerInfo(status.State, status.CreatedAt, status.ImageRef, status.Annotations)
+	if err != nil {
+		return runtime.ContainerInfo{}, fmt.Errorf("failed to get workload %s: %w", workloadID, err)
+	}
+	if status.StartedAt > 0 {
+		info.StartedAt = time.Unix(0, status.StartedAt).UTC()
+	}
+	info.ExitCode = int(status.ExitCode)
+	info.RestartCount = int(status.Metadata.GetAttempt())
+	return info, nil
+}
+
+// AttachToWorkload attaches to the input and output of a running workload
+func (c *Client) AttachToWorkload(ctx context.Context, workloadID string) (io.WriteCloser, io.ReadCloser, error) {
+	ctr, err := c.findContainer(ctx, workloadID)
+	if err != nil {
+		return nil, nil, err
+	}
+	if ctr.State != runtimeapi.ContainerState_CONTAINER_RUNNING {
+		return nil, nil, fmt.Errorf("cannot attach to workload %s: it is not running", workloadID)
+	}
+
+	response, err := c.runtimeService.Attach(ctx, &runtimeapi.AttachRequest{
+		ContainerId: ctr.Id,
+		Stdin:       true,
+		Stdout:      true,
+		Stderr:      true,
+	})
+	if err != nil {
+		return nil, nil, fmt.Errorf("failed to attach to workload %s: %w", workloadID, err)
+	}
+	executor, err := newStreamExecutor(response.Url)
+	if err != nil {
+		return nil, nil, fmt.Errorf("failed to attach to workload %s: %w", workloadID, err)
+	}
+
+	stdinReader, stdinWriter := io.Pipe()
+	stdoutReader, stdoutWriter := io.Pipe()
+
+	// Only stdout carries JSON-RPC messages; stderr is diagnostic output of the server, so it is logged
+	stderrWriter := logger.NewLineWriter(logger.Infof, fmt.Sprintf("[%s] ", workloadID))
+
+	// The stream is not bound to the context of the call, since it lasts as long as the workload
+	go func() {
+		defer stderrWriter.Flush()
+		err := executor.StreamWithContext(context.WithoutCancel(ctx), remotecommand.StreamOptions{
+			Stdin:  stdinReader,
+			Stdout: stdoutWriter,
+			Stderr: stderrWriter,
+		})
+		if err != nil {
+			logger.Debugf("Detached from workload %s: %v", workloadID, err)
+		}
+		stdoutWriter.CloseWithError(err)
+	}()
+
+	return stdinWriter, stdoutReader, nil
+}
+
+// ExecInWorkload runs a command in a workload and returns its exit code
+func (c *Client) ExecInWorkload(
+	ctx context.Context,
+	workloadID string,
+	command []string,
+	options *runtime.ExecOptions,
+) (int, error) {
+	ctr, err := c.findContainer(ctx, workloadID)
+	if err != nil {
+		return 0, err
+	}
+
+	response, err := c.runtimeService.Exec(ctx, &runtimeapi.ExecRequest{
+		ContainerId: ctr.Id,
+		Cmd:         command,
+		Tty:         options.TTY,
+		Stdin:       options.Stdin != nil,
+		Stdout:      true,
+		Stderr:      !options.TTY,
+	})
+	if err != nil {
+		return 0, fmt.Errorf("failed to exec in workload %s: %w", workloadID, err)
+	}
+	executor, err := newStreamExecutor(response.Url)
+	if err != nil {
+		return 0, fmt.Errorf("failed to exec in workload %s: %w", workloadID, err)
+	}
+
+	streamOpts := remotecommand.StreamOptions{
+		Stdin:  options.Stdin,
+		Stdout: options.Stdout,
+		Tty:    options.TTY,
+	}
+	if !options.TTY {
+		streamOpts.Stderr = options.Stderr
+	}
+	if err := executor.StreamWithContext(ctx, streamOpts); err != nil {
+		var exitErr utilexec.ExitError
+		if errors.As(err, &exitErr) {
+			return exitErr.ExitStatus(), nil
+		}
+		return 0, fmt.Errorf("failed to exec in workload %s: %w", workloadID, err)
+	}
+	return 0, nil
+}
+
+// newStreamExecutor returns an executor for a stream of the streaming server of containerd, which serves
+// the streams of attach and exec requests like the kubelet
+func newStreamExecutor(rawURL string) (remotecommand.Executor, error) {
+	streamURL, err := url.Parse(rawURL)
+	if err != nil {
+		return nil, fmt.Errorf("invalid stream URL %s: %w", rawURL, err)
+	}
+	return remotecommand.NewSPDYExecutor(&rest.Config{}, "POST", streamURL)
+}
+
+// UpdateNetworkPermissions implements runtime.Runtime.
+func (*Client) UpdateNetworkPermissions(_ context.Context, workloadName string, _ *permissions.Profile) error {
+	// Without network isolation there is no egress proxy which enforces the network permissions
+	logger.Debugf("Network permissions of %s are not enforced by the containerd runtime", workloadName)
+	return nil
+}
+
+// WorkloadEvents implements runtime.Runtime.
+func (*Client) WorkloadEvents(_ context.Context, _ *runtime.EventOptions) (<-chan runtime.WorkloadEvent, <-chan error) {
+	workloadEvents := make(chan runtime.WorkloadEvent)
+	close(workloadEvents)
+	errs := make(chan error, 1)
+	errs <- fmt.Errorf("workload events are not supported by the containerd runtime")
+	return workloadEvents, errs
+}
+
+// IsRunning checks that containerd is running and serves the CRI API
+func (c *Client) IsRunning(ctx context.Context) error {
+	if _, err := c.runtimeService.Version(ctx, &runtimeapi.VersionRequest{}); err != nil {
+		return fmt.Errorf("containerd is not running at %s or its CRI plugin is disabled: %w", c.address, err)
+	}
+	return nil
+}
+
+// decodeSpec returns the configuration of a workload kept in the annotations of its container
+func decodeSpec(annotations map[string]string) (*workloadSpec, error) {
+	encoded, ok := annotations[specAnnotation]
+	if !ok {
+		return nil, fmt.Errorf("the container has no workload configuration")
+	}
+	spec := &workloadSpec{}
+	if err := json.Unmarshal([]byte(encoded), spec); err != nil {
+		return nil, fmt.Errorf("invalid workload configuration: %w", err)
+	}
+	return spec, nil
+}
+
+// toContainerInfo returns the runtime representation of the container of a workload
+func toContainerInfo(
+	state runtimeapi.ContainerState,
+	createdAt int64,
+	imageRef string,
+	annotations map[string]string,
+) (runtime.ContainerInfo, error) {
+	spec, err := decodeSpec(annotations)
+	if err != nil {
+		return runtime.ContainerInfo{}, err
+	}
+
+	info := runtime.ContainerInfo{
+		ID:      spec.Name,
+		Name:    spec.Name,
+		Image:   spec.Image,
+		ImageID: imageRef,
+		Status:  stateName(state),
+		State:   stateName(state),
+		Created: time.Unix(0, createdAt).UTC(),
+		Labels:  spec.Labels,
+		Ports:   spec.Ports,
+	}
+	if info.Labels == nil {
+		info.Labels = map[string]string{}
+	}
+	if info.Ports == nil {
+		info.Ports = []runtime.PortMapping{}
+	}
+	return info, nil
+}
+
+// stateName returns the name of a container state, like the Docker API names it
+func stateName(state runtimeapi.ContainerState) string {
+	switch state {
+	case runtimeapi.ContainerState_CONTAINER_CREATED:
+		return "created"
+	case runtimeapi.ContainerState_CONTAINER_RUNNING:
+		return "running"
+	case runtimeapi.ContainerState_CONTAINER_EXITED:
+		return "exited"
+	case runtimeapi.ContainerState_CONTAINER_UNKNOWN:
+		return "unknown"
+	}
+	return "unknown"
+}
@@ -0,0 +1,406 @@
+package containerd
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"google.golang.org/grpc"
+	runtimeapi "k8s.io/cri-api/pkg/apis/runtime/v1"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/permissions"
+)
+
+func init() {
+	// Initialize the logger for tests
+	logger.Initialize()
+}
+
+// fakeRuntimeService is a CRI runtime service which keeps its pod sandboxes and containers in memory
+type fakeRuntimeService struct {
+	runtimeapi.RuntimeServiceClient
+	sandboxes  map[string]*runtimeapi.PodSandboxConfig
+	containers []*runtimeapi.Container
+	stopped    map[string]int64
+}
+
+func newFakeRuntimeService(containers ...*runtimeapi.Container) *fakeRuntimeService {
+	return &fakeRuntimeService{
+		sandboxes:  map[string]*runtimeapi.PodSandboxConfig{},
+		containers: containers,
+		stopped:    map[string]int64{},
+	}
+}
+
+func (f *fakeRuntimeService) ListContainers(
+	_ context.Context, in *runtimeapi.ListContainersRequest, _ ...grpc.CallOption,
+) (*runtimeapi.ListContainersResponse, error) {
+	var result []*runtimeapi.Container
+	for _, ctr := range f.containers {
+		if name, ok := in.GetFilter().GetLabelSelector()[workloadLabel]; ok && ctr.Labels[workloadLabel] != name {
+			continue
+		}
+		result = append(result, ctr)
+	}
+	return &runtimeapi.ListContainersResponse{Containers: result}, nil
+}
+
+func (f *fakeRuntimeService) ListPodSandbox(
+	_ context.Context, in *runtimeapi.ListPodSandboxRequest, _ ...grpc.CallOption,
+) (*runtimeapi.ListPodSandboxResponse, error) {
+	var result []*runtimeapi.PodSandbox
+	for id, config := range f.sandboxes {
+		if config.Labels[workloadLabel] == in.GetFilter().GetLabelSelector()[workloadLabel] {
+			result = append(result, &runtimeapi.PodSandbox{Id: id, Labels: config.Labels})
+		}
+	}
+	return &runtimeapi.ListPodSandboxResponse{Items: result}, nil
+}
+
+func (f *fakeRuntimeService) RunPodSandbox(
+	_ context.Context, in *runtimeapi.RunPodSandboxRequest, _ ...grpc.CallOption,
+) (*runtimeapi.RunPodSandboxResponse, error) {
+	id := "sandbox-" + in.Config.Metadata.Name
+	f.sandboxes[id] = in.Config
+	return &runtimeapi.RunPodSandboxResponse{PodSandboxId: id}, nil
+}
+
+func (*fakeRuntimeService) StopPodSandbox(
+	_ context.Context, _ *runtimeapi.StopPodSandboxRequest, _ ...grpc.CallOption,
+) (*runtimeapi.StopPodSandboxResponse, error) {
+	return &runtimeapi.StopPodSandboxResponse{}, nil
+}
+
+func (f *fakeRuntimeService) RemovePodSandbox(
+	_ context.Context, in *runtimeapi.RemovePodSandboxRequest, _ ...grpc.CallOption,
+) (*runtimeapi.RemovePodSandboxResponse, error) {
+	delete(f.sandboxes, in.PodSandboxId)
+	var remaining []*runtimeapi.Container
+	for _, ctr := range f.containers {
+		if ctr.PodSandboxId != in.PodSandboxId {
+			remaining = append(remaining, ctr)
+		}
+	}
+	f.containers = remaining
+	return &runtimeapi.RemovePodSandboxResponse{}, nil
+}
+
+func (f *fakeRuntimeService) CreateContainer(
+	_ context.Context, in *runtimeapi.CreateContainerRequest, _ ...grpc.CallOption,
+) (*runtimeapi.CreateContainerResponse, error) {
+	ctr := &runtimeapi.Container{
+		Id:           "container-" + in.Config.Metadata.Name,
+		PodSandboxId: in.PodSandboxId,
+		Metadata:     in.Config.Metadata,
+		ImageRef:     in.Config.Image.Image,
+		State:        runtimeapi.ContainerState_CONTAINER_CREATED,
+		CreatedAt:    time.Now().UnixNano(),
+		Labels:       in.Config.Labels,
+		Annotations:  in.Config.Annotations,
+	}
+	f.containers = append(f.containers, ctr)
+	return &runtimeapi.CreateContainerResponse{ContainerId: ctr.Id}, nil
+}
+
+func (f *fakeRuntimeService) StartContainer(
+	_ context.Context, in *runtimeapi.StartContainerRequest, _ ...grpc.CallOption,
+) (*runtimeapi.StartContainerResponse, error) {
+	for _, ctr := range f.containers {
+		if ctr.Id == in.ContainerId {
+			ctr.State = runtimeapi.ContainerState_CONTAINER_RUNNING
+		}
+	}
+	return &runtimeapi.StartContainerResponse{}, nil
+}
+
+func (f *fakeRuntimeService) ContainerStatus(
+	_ context.Context, in *runtimeapi.ContainerStatusRequest, _ ...grpc.CallOption,
+) (*runtimeapi.ContainerStatusResponse, error) {
+	for _, ctr := range f.containers {
+		if ctr.Id == in.ContainerId {
+			return &runtimeapi.ContainerStatusResponse{Status: &runtimeapi.ContainerStatus{
+				Id:          ctr.Id,
+				Metadata:    ctr.Metadata,
+				State:       ctr.State,
+				CreatedAt:   ctr.CreatedAt,
+				StartedAt:   ctr.CreatedAt,
+				ImageRef:    ctr.ImageRef,
+				Labels:      ctr.Labels,
+				Annotations: ctr.Annotations,
+			}}, nil
+		}
+	}
+	return nil, errors.New("container not found")
+}
+
+func (f *fakeRuntimeService) StopContainer(
+	_ context.Context, in *runtimeapi.StopContainerRequest, _ ...grpc.CallOption,
+) (*runtimeapi.StopContainerResponse, error) {
+	f.stopped[in.ContainerId] = in.Timeout
+	return &runtimeapi.StopContainerResponse{}, nil
+}
+
+// fakeImageService is a CRI image service on a host which has no images yet
+type fakeImageService struct {
+	runtimeapi.ImageServiceClient
+	pulled []string
+}
+
+func (*fakeImageService) ImageStatus(
+	_ context.Context, _ *runtimeapi.ImageStatusRequest, _ ...grpc.CallOption,
+) (*runtimeapi.ImageStatusResponse, error) {
+	return &runtimeapi.ImageStatusResponse{}, nil
+}
+
+func (f *fakeImageService) PullImage(
+	_ context.Context, in *runtimeapi.PullImageRequest, _ ...grpc.CallOption,
+) (*runtimeapi.PullImageResponse, error) {
+	f.pulled = append(f.pulled, in.Image.Image)
+	return &runtimeapi.PullImageResponse{ImageRef: "sha256:abc"}, nil
+}
+
+// newTestContainer returns the container of a workload with the given configuration
+func newTestContainer(t *testing.T, spec *workloadSpec, state runtimeapi.ContainerState) *runtimeapi.Container {
+	t.Helper()
+	annotation, err := json.Marshal(spec)
+	require.NoError(t, err)
+	return &runtimeapi.Container{
+		Id:           "container-" + spec.Name,
+		PodSandboxId: "sandbox-" + spec.Name,
+		Metadata:     &runtimeapi.ContainerMetadata{Name: spec.Name, Attempt: 2},
+		ImageRef:     "sha256:abc",
+		State:        state,
+		CreatedAt:    time.Date(2025, 6, 1, 10, 0, 0, 0, time.UTC).UnixNano(),
+		Labels:       map[string]string{workloadLabel: spec.Name},
+		Annotations:  map[string]string{specAnnotation: string(annotation)},
+	}
+}
+
+func TestDeployWorkload(t *testing.T) {
+	t.Parallel()
+
+	runtimeService := newFakeRuntimeService()
+	imageService := &fakeImageService{}
+	client := &Client{logDir: t.TempDir(), runtimeService: runtimeService, imageService: imageService}
+
+	options := runtime.NewDeployWorkloadOptions()
+	options.PortBindings["8080/tcp"] = []runtime.PortBinding{{HostIP: "127.0.0.1", HostPort: "8080"}}
+	name, hostPort, err := client.DeployWorkload(context.Background(), "mcp/fetch", "fetch", []string{"--verbose"},
+		map[string]string{"MCP_PORT": "8080"}, map[string]string{"toolhive": "true"},
+		permissions.BuiltinNoneProfile(), "sse", options, false)
+	require.NoError(t, err)
+	assert.Equal(t, "fetch", name)
+	assert.NotZero(t, hostPort)
+	assert.Equal(t, []string{"mcp/fetch"}, imageService.pulled)
+
+	// The ports are published by the pod sandbox of the workload
+	sandbox := runtimeService.sandboxes["sandbox-fetch"]
+	require.NotNil(t, sandbox)
+	assert.Equal(t, sandboxNamespace, sandbox.Metadata.Namespace)
+	assert.Equal(t, []*runtimeapi.PortMapping{{
+		Protocol:      runtimeapi.Protocol_TCP,
+		ContainerPort: 8080,
+		HostPort:      int32(hostPort), //nolint:gosec // G115: ports are at most 65535
+		HostIp:        "127.0.0.1",
+	}}, sandbox.PortMappings)
+	assert.DirExists(t, sandbox.LogDirectory)
+
+	running, err := client.IsWorkloadRunning(context.Background(), "fetch")
+	require.NoError(t, err)
+	assert.True(t, running)
+
+	info, err := client.GetWorkloadInfo(context.Background(), "fetch")
+	require.NoError(t, err)
+	assert.Equal(t, "fetch", info.ID)
+	assert.Equal(t, "mcp/fetch", info.Image)
+	assert.Equal(t, "sha256:abc", info.ImageID)
+	assert.Equal(t, []runtime.PortMapping{{ContainerPort: 8080, HostPort: hostPort, Protocol: "tcp"}}, info.Ports)
+
+	_, _, err = client.DeployWorkload(context.Background(), "image", "name", nil, nil, nil,
+		permissions.BuiltinNoneProfile(), "stdio", nil, true)
+	assert.ErrorIs(t, err, ErrNetworkIsolationUnsupported)
+}
+
+func TestListWorkloads(t *testing.T) {
+	t.Parallel()
+
+	fetch := newTestContainer(t, &workloadSpec{
+		Name:   "fetch",
+		Image:  "docker.io/mcp/fetch:latest",
+		Labels: map[string]string{"toolhive": "true", "toolhive-name": "fetch"},
+		Ports:  []runtime.PortMapping{{ContainerPort: 8080, HostPort: 41234, Protocol: "tcp"}},
+	}, runtimeapi.ContainerState_CONTAINER_RUNNING)
+	inspector := newTestContainer(t, &workloadSpec{
+		Name:   "inspector",
+		Image:  "ghcr.io/modelcontextprotocol/inspector:latest",
+		Labels: map[string]string{"toolhive": "true", "toolhive-auxiliary-workload": "true"},
+	}, runtimeapi.ContainerState_CONTAINER_EXITED)
+	// Containers of the kubelet on the same host are not workloads
+	kubelet := &runtimeapi.Container{Id: "coredns", Labels: map[string]string{"io.kubernetes.pod.name": "coredns"}}
+
+	client := &Client{runtimeService: newFakeRuntimeService(fetch, inspector, kubelet)}
+	workloads, err := client.ListWorkloads(context.Background())
+	require.NoError(t, err)
+	require.Len(t, workloads, 1)
+
+	assert.Equal(t, "fetch", workloads[0].ID)
+	assert.Equal(t, "fetch", workloads[0].Name)
+	assert.Equal(t, "docker.io/mcp/fetch:latest", workloads[0].Image)
+	assert.Equal(t, "sha256:abc", workloads[0].ImageID)
+	assert.Equal(t, "running", workloads[0].State)
+	assert.Equal(t, time.Date(2025, 6, 1, 10, 0, 0, 0, time.UTC), workloads[0].Created)
+	assert.Equal(t, []runtime.PortMapping{{ContainerPort: 8080, HostPort: 41234, Protocol: "tcp"}}, workloads[0].Ports)
+}
+
+func TestStopWorkload(t *testing.T) {
+	t.Parallel()
+
+	spec := &workloadSpec{Name: "fetch", StopTimeout: 20 * time.Second}
+	runtimeService := newFakeRuntimeService(newTestContainer(t, spec, runtimeapi.ContainerState_CONTAINER_RUNNING))
+	client := &Client{runtimeService: runtimeService}
+
+	// The stop timeout of the workload is used unless the stop options set one
+	require.NoError(t, client.StopWorkload(context.Background(), "fetch", nil))
+	assert.Equal(t, int64(20), runtimeService.stopped["container-fetch"])
+	require.NoError(t, client.StopWorkload(context.Background(), "fetch", &runtime.StopOptions{Timeout: 5 * time.Second}))
+	assert.Equal(t, int64(5), runtimeService.stopped["container-fetch"])
+
+	// Stopping a workload which doesn't exist succeeds
+	assert.NoError(t, client.StopWorkload(context.Background(), "missing", nil))
+}
+
+func TestStartWorkload_CreatesExitedWorkloadAgain(t *testing.T) {
+	t.Parallel()
+
+	spec := &workloadSpec{
+		Name:  "fetch",
+		Image: "mcp/fetch",
+		Ports: []runtime.PortMapping{{ContainerPort: 8080, HostPort: 41234, Protocol: "tcp"}},
+	}
+	runtimeService := newFakeRuntimeService(newTestContainer(t, spec, runtimeapi.ContainerState_CONTAINER_EXITED))
+	runtimeService.sandboxes["sandbox-fetch"] = spec.sandboxConfig(t.TempDir(), 2)
+	client := &Client{logDir: t.TempDir(), runtimeService: runtimeService, imageService: &fakeImageService{}}
+
+	require.NoError(t, client.StartWorkload(context.Background(), "fetch"))
+	require.Len(t, runtimeService.containers, 1)
+	assert.Equal(t, runtimeapi.ContainerState_CONTAINER_RUNNING, runtimeService.containers[0].State)
+	assert.Equal(t, uint32(3), runtimeService.containers[0].Metadata.Attempt)
+	// The workload keeps its published port
+	assert.Equal(t, int32(41234), runtimeService.sandboxes["sandbox-fetch"].PortMappings[0].HostPort)
+
+	assert.ErrorIs(t, client.StartWorkload(context.Background(), "missing"), ErrWorkloadNotFound)
+}
+
+func TestContainerConfig(t *testing.T) {
+	t.Parallel()
+
+	spec := &workloadSpec{
+		Name:            "fetch",
+		Image:           "mcp/fetch",
+		Command:         []string{"--verbose"},
+		Env:             map[string]string{"B": "2", "A": "1"},
+		Labels:          map[string]string{"toolhive": "true"},
+		Mounts:          []mount{{HostPath: "/data", ContainerPath: "/data", ReadOnly: true}},
+		Devices: []runtime.Device{
+			{CDIName: "nvidia.com/gpu=all"},
+			{PathOnHost: "/dev/dri", PathInContainer: "/dev/dri", Permissions: "rwm"},
+		},
+		AttachStdio:     true,
+		NoNewPrivileges: true,
+		Resources:       &runtime.ResourceLimits{CPUs: 0.5, Memory: 512 << 20},
+	}
+	config := spec.containerConfig("sha256:abc", "{}", 1)
+
+	assert.Equal(t, &runtimeapi.ContainerMetadata{Name: "fetch", Attempt: 1}, config.Metadata)
+	assert.Equal(t, "sha256:abc", config.Image.Image)
+	assert.Equal(t, []string{"--verbose"}, config.Args)
+	assert.Empty(t, config.Command)
+	assert.Equal(t, []*runtimeapi.KeyValue{{Key: "A", Value: "1"}, {Key: "B", Value: "2"}}, config.Envs)
+	assert.Equal(t, map[string]string{"toolhive": "true", workloadLabel: "fetch"}, config.Labels)
+	assert.Equal(t, []*runtimeapi.Mount{{HostPath: "/data", ContainerPath: "/data", Readonly: true}}, config.Mounts)
+	assert.Equal(t, []*runtimeapi.CDIDevice{{Name: "nvidia.com/gpu=all"}}, config.CDIDevices)
+	assert.Equal(t, []*runtimeapi.Device{{HostPath: "/dev/dri", ContainerPath: "/dev/dri", Permissions: "rwm"}}, config.Devices)
+	assert.True(t, config.Stdin)
+	assert.False(t, config.StdinOnce)
+	assert.Equal(t, []string{"ALL"}, config.Linux.SecurityContext.Capabilities.DropCapabilities)
+	assert.True(t, config.Linux.SecurityContext.NoNewPrivs)
+	assert.Equal(t, int64(50000), config.Linux.Resources.CpuQuota)
+	assert.Equal(t, int64(512<<20), config.Linux.Resources.MemoryLimitInBytes)
+}
+
+func TestGPUDevices(t *testing.T) {
+	t.Parallel()
+
+	devices, err := gpuDevices("device=0,1")
+	require.NoError(t, err)
+	assert.Equal(t, []runtime.Device{{CDIName: "nvidia.com/gpu=0"}, {CDIName: "nvidia.com/gpu=1"}}, devices)
+
+	_, err = gpuDevices("2")
+	assert.Error(t, err)
+}
+
+func TestReadLogs(t *testing.T) {
+	t.Parallel()
+
+	path := filepath.Join(t.TempDir(), containerLogFile)
+	require.NoError(t, os.WriteFile(path, []byte(strings.Join([]string{
+		"2025-06-01T10:00:00.000000000Z stdout F starting",
+		"2025-06-01T10:00:01.000000000Z stderr P long ",
+		"2025-06-01T10:00:01.000000000Z stderr F line",
+		"2025-06-01T10:00:02.000000000Z stdout F ready",
+		"",
+	}, "\n")), 0600))
+
+	logs, err := readLogs(path, time.Time{}, 0)
+	require.NoError(t, err)
+	assert.Equal(t, "starting\nlong line\nready\n", logs)
+
+	logs, err = readLogs(path, time.Time{}, 2)
+	require.NoError(t, err)
+	assert.Equal(t, "long line\nready\n", logs)
+
+	logs, err = readLogs(path, time.Date(2025, 6, 1, 10, 0, 2, 0, time.UTC), 0)
+	require.NoError(t, err)
+	assert.Equal(t, "ready\n", logs)
+
+	// A workload which has not written logs has none
+	logs, err = readLogs(filepath.Join(t.TempDir(), containerLogFile), time.Time{}, 0)
+	require.NoError(t, err)
+	assert.Empty(t, logs)
+}
+
+func TestCopyNewLogs(t *testing.T) {
+	t.Parallel()
+
+	path := filepath.Join(t.TempDir(), containerLogFile)
+	require.NoError(t, os.WriteFile(path, []byte(
+		"2025-06-01T10:00:00.000000000Z stdout F starting\n2025-06-01T10:00:01.000000000Z stderr F fail"), 0600))
+
+	var stdout, stderr strings.Builder
+	offset, pending, err := copyNewLogs(path, 0, "", time.Time{}, &stdout, &stderr)
+	require.NoError(t, err)
+	assert.Equal(t, "starting\n", stdout.String())
+	assert.Empty(t, stderr.String())
+	assert.Equal(t, "2025-06-01T10:00:01.000000000Z stderr F fail", pending)
+
+	// The rest of the line is written once it is complete
+	file, err := os.OpenFile(path, os.O_APPEND|os.O_WRONLY, 0600)
+	require.NoError(t, err)
+	_, err = file.WriteString("ed\n")
+	require.NoError(t, err)
+	require.NoError(t, file.Close())
+
+	_, pending, err = copyNewLogs(path, offset, pending, time.Time{}, &stdout, &stderr)
+	require.NoError(t, err)
+	assert.Equal(t, "failed\n", stderr.String())
+	assert.Empty(t, pending)
+}
@@ -0,0 +1,188 @@
+package containerd
+
+import (
+	"bufio"
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"strings"
+	"time"
+)
+
+const (
+	// logPollInterval is how often a followed log file is checked for new logs
+	logPollInterval = 250 * time.Millisecond
+	// maxLogLineSize is the maximum size of a line of a log file, which containerd splits into partial lines
+	// of at most 16 KiB
+	maxLogLineSize = 1 << 20
+	// streamStderr is the stream of the log lines written to the error stream of a workload
+	streamStderr = "stderr"
+)
+
+// logEntry is a line of a log file in the format of the CRI: the time, the stream, a tag which is P for a
+// partial line and F for the end of a line, and the content
+type logEntry struct {
+	time    time.Time
+	stream  string
+	partial bool
+	content string
+}
+
+// parseLogEntry parses a line of a log file
+func parseLogEntry(line string) (logEntry, error) {
+	parts := strings.SplitN(line, " ", 4)
+	if len(parts) < 3 {
+		return logEntry{}, fmt.Errorf("invalid log line: %q", line)
+	}
+	t, err := time.Parse(time.RFC3339Nano, parts[0])
+	if err != nil {
+		return logEntry{}, fmt.Errorf("invalid time of log line: %w", err)
+	}
+	entry := logEntry{time: t, stream: parts[1], partial: parts[2] == "P"}
+	if len(parts) == 4 {
+		entry.content = parts[3]
+	}
+	return entry, nil
+}
+
+// text returns the content of a log entry as it was written by the workload
+func (e *logEntry) text() string {
+	if e.partial {
+		return e.content
+	}
+	return e.content + "\n"
+}
+
+// readLogs returns the logs written since the given time, or the last tail lines of them if tail is positive.
+// The output and error streams of the workload are both part of its logs.
+func readLogs(path string, since time.Time, tail int) (string, error) {
+	file, err := os.Open(path) // #nosec G304 - the path is the log file of a workload
+	if errors.Is(err, os.ErrNotExist) {
+		return "", nil
+	}
+	if err != nil {
+		return "", fmt.Errorf("failed to open log file: %w", err)
+	}
+	defer file.Close()
+
+	var lines []string
+	var line strings.Builder
+	scanner := bufio.NewScanner(file)
+	scanner.Buffer(make([]byte, 0, bufio.MaxScanTokenSize), maxLogLineSize)
+	for scanner.Scan() {
+		entry, err := parseLogEntry(scanner.Text())
+		if err != nil || entry.time.Before(since) {
+			continue
+		}
+		line.WriteString(entry.text())
+		if !entry.partial {
+			lines = append(lines, line.String())
+			line.Reset()
+		}
+	}
+	if err := scanner.Err(); err != nil {
+		return "", fmt.Errorf("failed to read log file: %w", err)
+	}
+	if line.Len() > 0 {
+		lines = append(lines, line.String())
+	}
+
+	if tail > 0 && len(lines) > tail {
+		lines = lines[len(lines)-tail:]
+	}
+	return strings.Join(lines, ""), nil
+}
+
+// followLogs writes the logs written since the given time to stdout and stderr, and then the logs written
+// afterwards, until the context is cancelled or stopped returns true. The log file may not exist yet, and is
+// read again from the start when it is replaced.
+func followLogs(
+	ctx context.Context,
+	path string,
+	since time.Time,
+	stdout, stderr io.Writer,
+	stopped func(context.Context) bool,
+) error {
+	if stdout == nil {
+		stdout = io.Discard
+	}
+	if stderr == nil {
+		stderr = io.Discard
+	}
+
+	ticker := time.NewTicker(logPollInterval)
+	defer ticker.Stop()
+
+	var offset int64
+	var pending string
+	for {
+		var err error
+		offset, pending, err = copyNewLogs(path, offset, pending, since, stdout, stderr)
+		if err != nil {
+			return err
+		}
+		if stopped != nil && stopped(ctx) {
+			return nil
+		}
+
+		select {
+		case <-ctx.Done():
+			return nil
+		case <-ticker.C:
+		}
+	}
+}
+
+// copyNewLogs writes the log entries written to the log file after the offset, and returns the new offset
+// and the start of a line which is not written completely yet
+func copyNewLogs(
+	path string,
+	offset int64,
+	pending string,
+	since time.Time,
+	stdout, stderr io.Writer,
+) (int64, string, error) {
+	file, err := os.Open(path) // #nosec G304 - the path is the log file of a workload
+	if errors.Is(err, os.ErrNotExist) {
+		return 0, "", nil
+	}
+	if err != nil {
+		return offset, pending, fmt.Errorf("failed to open log file: %w", err)
+	}
+	defer file.Close()
+
+	info, err := file.Stat()
+	if err != nil {
+		return offset, pending, fmt.Errorf("failed to read log file: %w", err)
+	}
+	if info.Size() < offset {
+		// The workload was created again, with a new log file
+		offset, pending = 0, ""
+	}
+	if _, err := file.Seek(offset, io.SeekStart); err != nil {
+		return offset, pending, fmt.Errorf("failed to read log file: %w", err)
+	}
+	data, err := io.ReadAll(file)
+	if err != nil {
+		return offset, pending, fmt.Errorf("failed to read log file: %w", err)
+	}
+	offset += int64(len(data))
+
+	lines := strings.Split(pending+string(data), "\n")
+	for _, line := range lines[:len(lines)-1] {
+		entry, err := parseLogEntry(line)
+		if err != nil || entry.time.Before(since) {
+			continue
+		}
+		out := stdout
+		if entry.stream == streamStderr {
+			out = stderr
+		}
+		if _, err := io.WriteString(out, entry.text()); err != nil {
+			return offset, "", fmt.Errorf("failed to write logs: %w", err)
+		}
+	}
+	return offset, lines[len(lines)-1], nil
+}
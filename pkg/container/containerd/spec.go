@@ -0,0 +1,351 @@
+package containerd
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/adrg/xdg"
+	runtimeapi "k8s.io/cri-api/pkg/apis/runtime/v1"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/networking"
+	"github.com/stacklok/toolhive/pkg/permissions"
+)
+
+const (
+	// cpuPeriod is the CFS period the CPU quota of a workload is relative to, in microseconds
+	cpuPeriod = 100000
+	// containerLogFile is the name of the log file of a workload in its log directory
+	containerLogFile = "container.log"
+)
+
+// workloadSpec is the configuration of a workload. It is kept in an annotation of the container of the
+// workload, since a container which exited can't be started again through the CRI API, and the workload
+// is created again from it instead.
+type workloadSpec struct {
+	Name            string                  `json:"name"`
+	Image           string                  `json:"image"`
+	Command         []string                `json:"command,omitempty"`
+	Env             map[string]string       `json:"env,omitempty"`
+	Labels          map[string]string       `json:"labels,omitempty"`
+	Mounts          []mount                 `json:"mounts,omitempty"`
+	Ports           []runtime.PortMapping   `json:"ports,omitempty"`
+	HostIPs         map[int]string          `json:"host_ips,omitempty"`
+	Devices         []runtime.Device        `json:"devices,omitempty"`
+	AttachStdio     bool                    `json:"attach_stdio,omitempty"`
+	NoNewPrivileges bool                    `json:"no_new_privileges,omitempty"`
+	Resources       *runtime.ResourceLimits `json:"resources,omitempty"`
+	StopTimeout     time.Duration           `json:"stop_timeout,omitempty"`
+}
+
+// mount is a host path mounted into a workload
+type mount struct {
+	HostPath      string `json:"host_path"`
+	ContainerPath string `json:"container_path"`
+	ReadOnly      bool   `json:"read_only,omitempty"`
+}
+
+// newWorkloadSpec returns the configuration of a workload, and the host port of its first port. Auxiliary
+// workloads, like the inspector, are published on the host port they request, and the others on a random
+// available port.
+func newWorkloadSpec(
+	name, image string,
+	command []string,
+	envVars, workloadLabels map[string]string,
+	permissionProfile *permissions.Profile,
+	options *runtime.DeployWorkloadOptions,
+) (*workloadSpec, int, error) {
+	spec := &workloadSpec{
+		Name:            name,
+		Image:           image,
+		Command:         command,
+		Env:             envVars,
+		Labels:          workloadLabels,
+		Mounts:          mounts(permissionProfile),
+		AttachStdio:     options.AttachStdio,
+		NoNewPrivileges: permissionProfile.NoNewPrivileges,
+		Resources:       options.Resources,
+		StopTimeout:     options.StopTimeout,
+	}
+
+	hostPort, err := spec.addPorts(workloadLabels["toolhive-auxiliary"] == "true", options.PortBindings)
+	if err != nil {
+		return nil, 0, fmt.Errorf("failed to generate port bindings: %v", err)
+	}
+
+	if options.GPUs != "" {
+		gpus, err := gpuDevices(options.GPUs)
+		if err != nil {
+			return nil, 0, err
+		}
+		spec.Devices = append(spec.Devices, gpus...)
+	}
+	for _, value := range options.Devices {
+		device, err := runtime.ParseDevice(value)
+		if err != nil {
+			return nil, 0, err
+		}
+		spec.Devices = append(spec.Devices, device)
+	}
+	return spec, hostPort, nil
+}
+
+// addPorts adds the port mappings of the port bindings to a workload, and returns the host port of its
+// first port
+func (s *workloadSpec) addPorts(auxiliary bool, portBindings map[string][]runtime.PortBinding) (int, error) {
+	hostPort := 0
+	for _, containerPort := range sortedKeys(portBindings) {
+		bindings := portBindings[containerPort]
+		if len(bindings) == 0 {
+			continue
+		}
+		port, proto, err := parsePort(containerPort)
+		if err != nil {
+			return 0, err
+		}
+
+		mapping := runtime.PortMapping{ContainerPort: port, Protocol: proto}
+		if (hostPort == 0 && !auxiliary) || bindings[0].HostPort == "" {
+			mapping.HostPort = networking.FindAvailable()
+			if mapping.HostPort == 0 {
+				return 0, fmt.Errorf("could not find an available port")
+			}
+		} else {
+			mapping.HostPort, err = strconv.Atoi(bindings[0].HostPort)
+			if err != nil {
+				return 0, fmt.Errorf("failed to convert host port %s to int: %v", bindings[0].HostPort, err)
+			}
+		}
+		if hostPort == 0 {
+			hostPort = mapping.HostPort
+		}
+
+		s.Ports = append(s.Ports, mapping)
+		if bindings[0].HostIP != "" {
+			if s.HostIPs == nil {
+				s.HostIPs = map[int]string{}
+			}
+			s.HostIPs[mapping.HostPort] = bindings[0].HostIP
+		}
+	}
+	return hostPort, nil
+}
+
+// parsePort parses a container port in the format port/protocol, where the protocol defaults to tcp
+func parsePort(value string) (int, string, error) {
+	portValue, proto, _ := strings.Cut(value, "/")
+	port, err := strconv.Atoi(portValue)
+	if err != nil {
+		return 0, "", fmt.Errorf("invalid container port: %s", value)
+	}
+	if proto == "" {
+		proto = "tcp"
+	}
+	return port, proto, nil
+}
+
+// gpuDevices returns the Container Device Interface devices of the NVIDIA GPUs of a GPU request, which
+// containerd makes available to a workload if the CDI specification of the GPUs was generated on the host
+func gpuDevices(value string) ([]runtime.Device, error) {
+	request, err := runtime.ParseGPURequest(value)
+	if err != nil {
+		return nil, err
+	}
+	if request.Count == runtime.AllGPUs {
+		return []runtime.Device{{CDIName: "nvidia.com/gpu=all"}}, nil
+	}
+	if len(request.DeviceIDs) == 0 {
+		return nil, fmt.Errorf("requesting a number of GPUs is not supported by the containerd runtime, " +
+			"request all GPUs or GPUs by ID instead")
+	}
+	devices := make([]runtime.Device, 0, len(request.DeviceIDs))
+	for _, id := range request.DeviceIDs {
+		devices = append(devices, runtime.Device{CDIName: "nvidia.com/gpu=" + id})
+	}
+	return devices, nil
+}
+
+// mounts returns the mounts of a permission profile. Host paths are bind mounted, and volume:// resources
+// are mounted from a directory of toolhive, since the CRI API has no named volumes.
+func mounts(profile *permissions.Profile) []mount {
+	var result []mount
+	add := func(declarations []permissions.MountDeclaration, readOnly bool) {
+		for _, declaration := range declarations {
+			m, err := newMount(declaration)
+			if err != nil {
+				logger.Warnf("Skipping mount %s: %v", declaration, err)
+				continue
+			}
+			m.ReadOnly = readOnly
+			result = append(result, m)
+		}
+	}
+	add(profile.Read, true)
+	add(profile.Write, false)
+	return result
+}
+
+// newMount returns the mount of a mount declaration
+func newMount(declaration permissions.MountDeclaration) (mount, error) {
+	source, target, err := declaration.Parse()
+	if err != nil {
+		return mount{}, err
+	}
+
+	if declaration.IsResourceURI() {
+		resourceType, err := declaration.GetResourceType()
+		if err != nil {
+			return mount{}, err
+		}
+		if resourceType != "volume" {
+			return mount{}, fmt.Errorf("resource type %s is not supported by the containerd runtime", resourceType)
+		}
+		volume, err := volumeDir(source[len(resourceType)+len("://"):])
+		if err != nil {
+			return mount{}, err
+		}
+		return mount{HostPath: volume, ContainerPath: target}, nil
+	}
+
+	if !filepath.IsAbs(source) {
+		source, err = filepath.Abs(source)
+		if err != nil {
+			return mount{}, err
+		}
+	}
+	return mount{HostPath: source, ContainerPath: target}, nil
+}
+
+// volumeDir returns the directory of a named volume, and creates it if it doesn't exist
+func volumeDir(name string) (string, error) {
+	if name == "" || strings.ContainsAny(name, `/\`) || name == "." || name == ".." {
+		return "", fmt.Errorf("invalid volume name: %q", name)
+	}
+	dir := filepath.Join(xdg.DataHome, "toolhive", "volumes", name)
+	if err := os.MkdirAll(dir, 0750); err != nil {
+		return "", fmt.Errorf("failed to create the directory of volume %s: %w", name, err)
+	}
+	return dir, nil
+}
+
+// sandboxConfig returns the configuration of the pod sandbox of a workload, which holds its network
+// namespace and publishes its ports on the host
+func (s *workloadSpec) sandboxConfig(logDirectory string, attempt uint32) *runtimeapi.PodSandboxConfig {
+	config := &runtimeapi.PodSandboxConfig{
+		Metadata: &runtimeapi.PodSandboxMetadata{
+			Name:      s.Name,
+			Uid:       s.Name,
+			Namespace: sandboxNamespace,
+			Attempt:   attempt,
+		},
+		Hostname:     s.Name,
+		LogDirectory: logDirectory,
+		Labels:       map[string]string{workloadLabel: s.Name},
+		Linux:        &runtimeapi.LinuxPodSandboxConfig{},
+	}
+	for _, port := range s.Ports {
+		//nolint:gosec // G115: Safe int->int32 conversion, ports are at most 65535
+		config.PortMappings = append(config.PortMappings, &runtimeapi.PortMapping{
+			Protocol:      protocol(port.Protocol),
+			ContainerPort: int32(port.ContainerPort),
+			HostPort:      int32(port.HostPort),
+			HostIp:        s.HostIPs[port.HostPort],
+		})
+	}
+	return config
+}
+
+// containerConfig returns the configuration of the container of a workload. Like with the Docker runtime,
+// all capabilities are dropped.
+func (s *workloadSpec) containerConfig(imageRef, annotation string, attempt uint32) *runtimeapi.ContainerConfig {
+	config := &runtimeapi.ContainerConfig{
+		Metadata: &runtimeapi.ContainerMetadata{Name: s.Name, Attempt: attempt},
+		Image:    &runtimeapi.ImageSpec{Image: imageRef},
+		// The command of a workload replaces the command of its image, but not its entrypoint
+		Args:        s.Command,
+		Labels:      map[string]string{workloadLabel: s.Name},
+		Annotations: map[string]string{specAnnotation: annotation},
+		LogPath:     containerLogFile,
+		Stdin:       s.AttachStdio,
+		Linux: &runtimeapi.LinuxContainerConfig{
+			Resources: resources(s.Resources),
+			SecurityContext: &runtimeapi.LinuxContainerSecurityContext{
+				Capabilities: &runtimeapi.Capability{DropCapabilities: []string{"ALL"}},
+				NoNewPrivs:   s.NoNewPrivileges,
+			},
+		},
+	}
+	for key, value := range s.Labels {
+		config.Labels[key] = value
+	}
+	for _, key := range sortedKeys(s.Env) {
+		config.Envs = append(config.Envs, &runtimeapi.KeyValue{Key: key, Value: s.Env[key]})
+	}
+	for _, m := range s.Mounts {
+		config.Mounts = append(config.Mounts, &runtimeapi.Mount{
+			HostPath:      m.HostPath,
+			ContainerPath: m.ContainerPath,
+			Readonly:      m.ReadOnly,
+		})
+	}
+	for _, device := range s.Devices {
+		if device.CDIName != "" {
+			config.CDIDevices = append(config.CDIDevices, &runtimeapi.CDIDevice{Name: device.CDIName})
+			continue
+		}
+		config.Devices = append(config.Devices, &runtimeapi.Device{
+			HostPath:      device.PathOnHost,
+			ContainerPath: device.PathInContainer,
+			Permissions:   device.Permissions,
+		})
+	}
+	return config
+}
+
+// resources returns the resource limits of a workload. The number of processes is limited per pod by the
+// kubelet, so it can't be limited through the CRI API.
+func resources(limits *runtime.ResourceLimits) *runtimeapi.LinuxContainerResources {
+	if limits == nil {
+		return nil
+	}
+	if limits.PidsLimit > 0 {
+		logger.Warnf("Ignoring the process limit of the workload, since it is not supported by the containerd runtime")
+	}
+	result := &runtimeapi.LinuxContainerResources{
+		MemoryLimitInBytes:     limits.Memory,
+		MemorySwapLimitInBytes: limits.MemorySwap,
+	}
+	if limits.CPUs > 0 {
+		result.CpuPeriod = cpuPeriod
+		result.CpuQuota = int64(limits.CPUs * cpuPeriod)
+	}
+	return result
+}
+
+// protocol returns the CRI protocol of a port protocol
+func protocol(value string) runtimeapi.Protocol {
+	switch strings.ToLower(value) {
+	case "udp":
+		return runtimeapi.Protocol_UDP
+	case "sctp":
+		return runtimeapi.Protocol_SCTP
+	default:
+		return runtimeapi.Protocol_TCP
+	}
+}
+
+// sortedKeys returns the keys of a map in order, so the configuration is deterministic
+func sortedKeys[V any](m map[string]V) []string {
+	keys := make([]string, 0, len(m))
+	for key := range m {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	return keys
+}
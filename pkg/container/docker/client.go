@@ -7,8 +7,10 @@ import (
 	"context"
 	"fmt"
 	"io"
+	"maps"
 	"os"
 	"path/filepath"
+	"slices"
 	"strconv"
 	"strings"
 	"time"
@@ -18,6 +20,7 @@ import (
 	"github.com/docker/docker/api/types/filters"
 	"github.com/docker/docker/api/types/mount"
 	"github.com/docker/docker/api/types/network"
+	"github.com/docker/docker/api/types/versions"
 	"github.com/docker/docker/client"
 	"github.com/docker/docker/pkg/stdcopy"
 	"github.com/docker/go-connections/nat"
@@ -34,6 +37,10 @@ import (
 // DnsImage is the default DNS image used for network permissions
 const DnsImage = "dockurr/dnsmasq:latest"
 
+// defaultStopTimeoutSeconds is the time to wait for a container to exit before killing it,
+// unless another stop timeout is configured
+const defaultStopTimeoutSeconds = 30
+
 // Workloads
 const (
 	ToolhiveAuxiliaryWorkloadLabel = "toolhive-auxiliary-workload"
@@ -145,7 +152,7 @@ func (c *Client) createContainer(ctx context.Context, containerName string, conf
 
 	// If container exists, check if we need to recreate it
 	if existingID != "" {
-		canReuse, err := c.handleExistingContainer(ctx, existingID, config, hostConfig)
+		canReuse, err := c.handleExistingContainer(ctx, existingID, config, hostConfig, endpointsConfig)
 		if err != nil {
 			return "", err
 		}
@@ -158,8 +165,9 @@ func (c *Client) createContainer(ctx context.Context, containerName string, conf
 	}
 
 	// network config
+	createEndpoints, connectEndpoints := splitEndpointsConfig(c.client.ClientVersion(), endpointsConfig)
 	networkConfig := &network.NetworkingConfig{
-		EndpointsConfig: endpointsConfig,
+		EndpointsConfig: createEndpoints,
 	}
 
 	// Create the container
@@ -175,15 +183,58 @@ func (c *Client) createContainer(ctx context.Context, containerName string, conf
 		return "", NewContainerError(err, "", fmt.Sprintf("failed to create container: %v", err))
 	}
 
+	for _, name := range slices.Sorted(maps.Keys(connectEndpoints)) {
+		if err := c.client.NetworkConnect(ctx, name, resp.ID, connectEndpoints[name]); err != nil {
+			return "", NewContainerError(err, resp.ID, fmt.Sprintf("failed to connect container to network %s: %v", name, err))
+		}
+	}
+
 	// Start the container
 	err = c.client.ContainerStart(ctx, resp.ID, container.StartOptions{})
 	if err != nil {
-		return "", NewContainerError(err, resp.ID, fmt.Sprintf("failed to start container: %v", err))
+		return "", c.startError(ctx, resp.ID, err)
 	}
 
 	return resp.ID, nil
 }
 
+// multipleEndpointsAPIVersion is the first API version in which containers can be connected to several
+// networks when they are created
+const multipleEndpointsAPIVersion = "1.44"
+
+// splitEndpointsConfig splits the networks of a container into the networks it is connected to when it is
+// created and the networks it is connected to afterwards. Daemons before API 1.44 reject the creation of
+// containers with several networks, so the container is created with the first network and connected to
+// the others before it is started.
+func splitEndpointsConfig(
+	apiVersion string,
+	endpointsConfig map[string]*network.EndpointSettings,
+) (map[string]*network.EndpointSettings, map[string]*network.EndpointSettings) {
+	if len(endpointsConfig) <= 1 || !versions.LessThan(apiVersion, multipleEndpointsAPIVersion) {
+		return endpointsConfig, nil
+	}
+
+	names := slices.Sorted(maps.Keys(endpointsConfig))
+	createEndpoints := map[string]*network.EndpointSettings{names[0]: endpointsConfig[names[0]]}
+	connectEndpoints := make(map[string]*network.EndpointSettings, len(names)-1)
+	for _, name := range names[1:] {
+		connectEndpoints[name] = endpointsConfig[name]
+	}
+	return createEndpoints, connectEndpoints
+}
+
+// startError returns the error of a container which failed to start, with its exit code and the last
+// lines of its logs, e.g. when the entrypoint of its image cannot be run
+func (c *Client) startError(ctx context.Context, containerID string, err error) error {
+	exitCode := -1
+	if info, inspectErr := c.client.ContainerInspect(ctx, containerID); inspectErr == nil && info.State != nil {
+		exitCode = info.State.ExitCode
+	}
+	logs, _ := c.GetWorkloadLogs(ctx, containerID, runtime.LogOptions{Tail: runtime.DefaultLogTail})
+	return NewContainerExitError(err, containerID,
+		fmt.Sprintf("failed to start container (exit code %d)", exitCode), exitCode, logs)
+}
+
 func (c *Client) createDnsContainer(ctx context.Context, dnsContainerName string,
 	attachStdio bool, networkName string, endpointsConfig map[string]*network.EndpointSettings) (string, string, error) {
 	logger.Infof("Setting up DNS container for %s with image %s...", dnsContainerName, DnsImage)
@@ -248,7 +299,7 @@ func (c *Client) createDnsContainer(ctx context.Context, dnsContainerName string
 
 func (c *Client) createMcpContainer(ctx context.Context, name string, networkName string, image string, command []string,
 	envVars map[string]string, labels map[string]string, attachStdio bool, permissionConfig *runtime.PermissionConfig,
-	additionalDNS string, exposedPorts map[string]struct{}, portBindings map[string][]runtime.PortBinding,
+	additionalDNS string, options *runtime.DeployWorkloadOptions, portBindings map[string][]runtime.PortBinding,
 	isolateNetwork bool) (string, error) {
 	// Create container configuration
 	config := &container.Config{
@@ -261,6 +312,11 @@ func (c *Client) createMcpContainer(ctx context.Context, name string, networkNam
 		AttachStderr: attachStdio,
 		OpenStdin:    attachStdio,
 		Tty:          false,
+		StopSignal:   options.StopSignal,
+	}
+	if options.StopTimeout > 0 {
+		stopTimeout := int(options.StopTimeout.Seconds())
+		config.StopTimeout = &stopTimeout
 	}
 
 	// Create host configuration
@@ -270,17 +326,36 @@ func (c *Client) createMcpContainer(ctx context.Context, name string, networkNam
 		CapAdd:      permissionConfig.CapAdd,
 		CapDrop:     permissionConfig.CapDrop,
 		SecurityOpt: permissionConfig.SecurityOpt,
-		RestartPolicy: container.RestartPolicy{
-			Name: "unless-stopped",
-		},
+		Isolation:   container.Isolation(permissionConfig.Isolation),
+		UsernsMode:  container.UsernsMode(permissionConfig.UsernsMode),
 	}
 	if additionalDNS != "" {
 		hostConfig.DNS = []string{additionalDNS}
 	}
+	if options.LogConfig != nil {
+		hostConfig.LogConfig = container.LogConfig{
+			Type:   options.LogConfig.Driver,
+			Config: options.LogConfig.Options,
+		}
+	}
+	if options.Resources != nil {
+		hostConfig.Resources = convertResources(options.Resources)
+	}
+	if err := setupDevices(hostConfig, options, c.runtimeType); err != nil {
+		return "", NewContainerError(err, "", err.Error())
+	}
+	restartPolicy, err := runtime.ParseRestartPolicy(options.RestartPolicy)
+	if err != nil {
+		return "", NewContainerError(err, "", err.Error())
+	}
+	hostConfig.RestartPolicy = container.RestartPolicy{
+		Name:              container.RestartPolicyMode(restartPolicy.Name),
+		MaximumRetryCount: restartPolicy.MaximumRetryCount,
+	}
 
 	// Configure ports if options are provided
 	// Setup exposed ports
-	if err := setupExposedPorts(config, exposedPorts); err != nil {
+	if err := setupExposedPorts(config, options.ExposedPorts); err != nil {
 		return "", NewContainerError(err, "", err.Error())
 	}
 
@@ -290,24 +365,48 @@ func (c *Client) createMcpContainer(ctx context.Context, name string, networkNam
 	}
 
 	// create mcp container
-	internalEndpointsConfig := map[string]*network.EndpointSettings{}
+	internalEndpointsConfig, err := c.mcpEndpointsConfig(ctx, networkName, isolateNetwork, options.Networks)
+	if err != nil {
+		return "", err
+	}
+	containerId, err := c.createContainer(ctx, name, config, hostConfig, internalEndpointsConfig)
+	if err != nil {
+		return "", fmt.Errorf("failed to create container: %v", err)
+	}
+
+	return containerId, nil
+
+}
+
+// mcpEndpointsConfig returns the networks the MCP container is connected to: its internal network with
+// network isolation, or else the external network, and the existing networks it should join.
+func (c *Client) mcpEndpointsConfig(
+	ctx context.Context,
+	networkName string,
+	isolateNetwork bool,
+	networks []string,
+) (map[string]*network.EndpointSettings, error) {
+	endpointsConfig := map[string]*network.EndpointSettings{}
 	if isolateNetwork {
-		internalEndpointsConfig[networkName] = &network.EndpointSettings{
+		endpointsConfig[networkName] = &network.EndpointSettings{
 			NetworkID: networkName,
 		}
 	} else {
 		// for other workloads such as inspector, add to external network
-		internalEndpointsConfig["toolhive-external"] = &network.EndpointSettings{
+		endpointsConfig["toolhive-external"] = &network.EndpointSettings{
 			NetworkID: "toolhive-external",
 		}
 	}
-	containerId, err := c.createContainer(ctx, name, config, hostConfig, internalEndpointsConfig)
-	if err != nil {
-		return "", fmt.Errorf("failed to create container: %v", err)
-	}
-
-	return containerId, nil
 
+	for _, name := range networks {
+		if _, err := c.client.NetworkInspect(ctx, name, network.InspectOptions{}); err != nil {
+			return nil, NewContainerError(err, "", fmt.Sprintf("failed to find network %s: %v", name, err))
+		}
+		endpointsConfig[name] = &network.EndpointSettings{
+			NetworkID: name,
+		}
+	}
+	return endpointsConfig, nil
 }
 
 // addEgressEnvVars adds environment variables for egress proxy configuration.
@@ -381,10 +480,10 @@ func extractFirstPort(options *runtime.DeployWorkloadOptions) (int, error) {
 	return firstPortInt, nil
 }
 
-func (c *Client) createExternalNetworks(ctx context.Context) error {
+func (c *Client) createExternalNetworks(ctx context.Context, driver string) error {
 	externalNetworkLabels := map[string]string{}
 	lb.AddNetworkLabels(externalNetworkLabels, "toolhive-external")
-	err := c.createNetwork(ctx, "toolhive-external", externalNetworkLabels, false)
+	err := c.createNetwork(ctx, "toolhive-external", externalNetworkLabels, false, driver)
 	if err != nil {
 		return err
 	}
@@ -443,10 +542,10 @@ func (c *Client) DeployWorkload(
 	options *runtime.DeployWorkloadOptions,
 	isolateNetwork bool,
 ) (string, int, error) {
-	// Get permission config from profile
-	permissionConfig, err := c.getPermissionConfigFromProfile(permissionProfile, transportType)
+	// Get permission config from profile, for the platform of the daemon
+	permissionConfig, platform, err := c.getWorkloadPermissionConfig(ctx, image, permissionProfile, transportType, isolateNetwork)
 	if err != nil {
-		return "", 0, fmt.Errorf("failed to get permission config: %w", err)
+		return "", 0, err
 	}
 
 	// Determine if we should attach stdio
@@ -460,7 +559,7 @@ func (c *Client) DeployWorkload(
 		"toolhive-external": {},
 	}
 
-	err = c.createExternalNetworks(ctx)
+	err = c.createExternalNetworks(ctx, platform.networkDriver())
 	if err != nil {
 		return "", 0, fmt.Errorf("failed to create external networks: %v", err)
 	}
@@ -468,7 +567,7 @@ func (c *Client) DeployWorkload(
 	if isolateNetwork {
 		internalNetworkLabels := map[string]string{}
 		lb.AddNetworkLabels(internalNetworkLabels, networkName)
-		err := c.createNetwork(ctx, networkName, internalNetworkLabels, true)
+		err := c.createNetwork(ctx, networkName, internalNetworkLabels, true, platform.networkDriver())
 		if err != nil {
 			return "", 0, fmt.Errorf("failed to create internal network: %v", err)
 		}
@@ -493,7 +592,7 @@ func (c *Client) DeployWorkload(
 			attachStdio,
 			nil,
 			externalEndpointsConfig,
-			permissionProfile.Network,
+			permissionProfile,
 		)
 		if err != nil {
 			return "", 0, fmt.Errorf("failed to create egress container: %v", err)
@@ -521,7 +620,7 @@ func (c *Client) DeployWorkload(
 		attachStdio,
 		permissionConfig,
 		additionalDNS,
-		options.ExposedPorts,
+		options,
 		newPortBindings,
 		isolateNetwork,
 	)
@@ -596,6 +695,7 @@ func (c *Client) ListWorkloads(ctx context.Context) ([]runtime.ContainerInfo, er
 			ID:      c.ID,
 			Name:    name,
 			Image:   c.Image,
+			ImageID: c.ImageID,
 			Status:  c.Status,
 			State:   c.State,
 			Created: created,
@@ -609,7 +709,7 @@ func (c *Client) ListWorkloads(ctx context.Context) ([]runtime.ContainerInfo, er
 
 // StopWorkload stops a workload
 // If the workload is already stopped, it returns success
-func (c *Client) StopWorkload(ctx context.Context, workloadID string) error {
+func (c *Client) StopWorkload(ctx context.Context, workloadID string, options *runtime.StopOptions) error {
 	// Check if the workload is running
 	running, err := c.IsWorkloadRunning(ctx, workloadID)
 	if err != nil {
@@ -625,9 +725,13 @@ func (c *Client) StopWorkload(ctx context.Context, workloadID string) error {
 		return nil
 	}
 
-	// Use a reasonable timeout
-	timeoutSeconds := 30
-	err = c.client.ContainerStop(ctx, workloadID, container.StopOptions{Timeout: &timeoutSeconds})
+	// Use a reasonable timeout for the sidecar containers
+	timeoutSeconds := defaultStopTimeoutSeconds
+	stopOptions, err := c.workloadStopOptions(ctx, workloadID, options)
+	if err != nil {
+		return err
+	}
+	err = c.client.ContainerStop(ctx, workloadID, stopOptions)
 	if err != nil {
 		return NewContainerError(err, workloadID, fmt.Sprintf("failed to stop workload: %v", err))
 	}
@@ -678,6 +782,36 @@ func (c *Client) StopWorkload(ctx context.Context, workloadID string) error {
 	return nil
 }
 
+// workloadStopOptions returns the options to stop a workload with. Options which are not set
+// fall back to the stop timeout and signal the workload was deployed with, and to
+// defaultStopTimeoutSeconds for workloads deployed without a stop timeout.
+func (c *Client) workloadStopOptions(
+	ctx context.Context,
+	workloadID string,
+	options *runtime.StopOptions,
+) (container.StopOptions, error) {
+	stopOptions := container.StopOptions{}
+	if options != nil {
+		stopOptions.Signal = options.Signal
+		if options.Timeout > 0 {
+			timeoutSeconds := int(options.Timeout.Seconds())
+			stopOptions.Timeout = &timeoutSeconds
+			return stopOptions, nil
+		}
+	}
+
+	// Leaving the timeout unset makes the runtime use the stop timeout of the container
+	info, err := c.client.ContainerInspect(ctx, workloadID)
+	if err != nil {
+		return stopOptions, NewContainerError(err, workloadID, fmt.Sprintf("failed to inspect workload: %v", err))
+	}
+	if info.Config == nil || info.Config.StopTimeout == nil {
+		timeoutSeconds := defaultStopTimeoutSeconds
+		stopOptions.Timeout = &timeoutSeconds
+	}
+	return stopOptions, nil
+}
+
 func (c *Client) deleteNetworks(ctx context.Context, containerName string) error {
 	// Delete networks if there are no containers using them.
 	toolHiveContainers, err := c.client.ContainerList(ctx, container.ListOptions{
@@ -705,6 +839,39 @@ func (c *Client) deleteNetworks(ctx context.Context, containerName string) error
 	return nil
 }
 
+// PauseWorkload pauses a workload
+func (c *Client) PauseWorkload(ctx context.Context, workloadID string) error {
+	if err := c.client.ContainerPause(ctx, workloadID); err != nil {
+		if errdefs.IsNotFound(err) {
+			return NewContainerError(ErrContainerNotFound, workloadID, "workload not found")
+		}
+		return NewContainerError(err, workloadID, fmt.Sprintf("failed to pause workload: %v", err))
+	}
+	return nil
+}
+
+// UnpauseWorkload unpauses a workload
+func (c *Client) UnpauseWorkload(ctx context.Context, workloadID string) error {
+	if err := c.client.ContainerUnpause(ctx, workloadID); err != nil {
+		if errdefs.IsNotFound(err) {
+			return NewContainerError(ErrContainerNotFound, workloadID, "workload not found")
+		}
+		return NewContainerError(err, workloadID, fmt.Sprintf("failed to unpause workload: %v", err))
+	}
+	return nil
+}
+
+// StartWorkload starts a stopped or exited workload
+func (c *Client) StartWorkload(ctx context.Context, workloadID string) error {
+	if err := c.client.ContainerStart(ctx, workloadID, container.StartOptions{}); err != nil {
+		if errdefs.IsNotFound(err) {
+			return NewContainerError(ErrContainerNotFound, workloadID, "workload not found")
+		}
+		return NewContainerError(err, workloadID, fmt.Sprintf("failed to start workload: %v", err))
+	}
+	return nil
+}
+
 // RemoveWorkload removes a workload
 // If the workload doesn't exist, it returns success
 func (c *Client) RemoveWorkload(ctx context.Context, workloadID string) error {
@@ -762,16 +929,23 @@ func (c *Client) RemoveWorkload(ctx context.Context, workloadID string) error {
 }
 
 // GetWorkloadLogs gets workload logs
-func (c *Client) GetWorkloadLogs(ctx context.Context, workloadID string, follow bool) (string, error) {
-	options := container.LogsOptions{
+func (c *Client) GetWorkloadLogs(ctx context.Context, workloadID string, options runtime.LogOptions) (string, error) {
+	follow := options.Follow
+	logsOptions := container.LogsOptions{
 		ShowStdout: true,
 		ShowStderr: true,
 		Follow:     follow,
-		Tail:       "100",
+		Tail:       "all",
+	}
+	if options.Tail > 0 {
+		logsOptions.Tail = strconv.Itoa(options.Tail)
+	}
+	if !options.Since.IsZero() {
+		logsOptions.Since = options.Since.Format(time.RFC3339Nano)
 	}
 
 	// Get logs
-	logs, err := c.client.ContainerLogs(ctx, workloadID, options)
+	logs, err := c.client.ContainerLogs(ctx, workloadID, logsOptions)
 	if err != nil {
 		return "", NewContainerError(err, workloadID, fmt.Sprintf("failed to get workload logs: %v", err))
 	}
@@ -795,6 +969,38 @@ func (c *Client) GetWorkloadLogs(ctx context.Context, workloadID string, follow
 	return buf.String(), nil
 }
 
+// StreamWorkloadLogs follows the logs of a workload written since the given time
+func (c *Client) StreamWorkloadLogs(
+	ctx context.Context,
+	workloadID string,
+	since time.Time,
+	stdout, stderr io.Writer,
+) error {
+	options := container.LogsOptions{
+		ShowStdout: stdout != nil,
+		ShowStderr: stderr != nil,
+		Follow:     true,
+		Since:      since.Format(time.RFC3339Nano),
+	}
+
+	logs, err := c.client.ContainerLogs(ctx, workloadID, options)
+	if err != nil {
+		return NewContainerError(err, workloadID, fmt.Sprintf("failed to stream workload logs: %v", err))
+	}
+	defer logs.Close()
+
+	if stdout == nil {
+		stdout = io.Discard
+	}
+	if stderr == nil {
+		stderr = io.Discard
+	}
+	if _, err := stdcopy.StdCopy(stdout, stderr, logs); err != nil && ctx.Err() == nil {
+		return NewContainerError(err, workloadID, fmt.Sprintf("failed to stream workload logs: %v", err))
+	}
+	return nil
+}
+
 // IsWorkloadRunning checks if a workload is running
 func (c *Client) IsWorkloadRunning(ctx context.Context, workloadID string) (bool, error) {
 	// Inspect workload
@@ -846,15 +1052,25 @@ func (c *Client) GetWorkloadInfo(ctx context.Context, workloadID string) (runtim
 		created = time.Time{} // Use zero time if parsing fails
 	}
 
+	// Convert start time; it is not set if the workload was never started
+	startedAt, err := time.Parse(time.RFC3339Nano, info.State.StartedAt)
+	if err != nil {
+		startedAt = time.Time{}
+	}
+
 	return runtime.ContainerInfo{
-		ID:      info.ID,
-		Name:    strings.TrimPrefix(info.Name, "/"),
-		Image:   info.Config.Image,
-		Status:  info.State.Status,
-		State:   info.State.Status,
-		Created: created,
-		Labels:  info.Config.Labels,
-		Ports:   ports,
+		ID:           info.ID,
+		Name:         strings.TrimPrefix(info.Name, "/"),
+		Image:        info.Config.Image,
+		ImageID:      info.Image,
+		Status:       info.State.Status,
+		State:        info.State.Status,
+		Created:      created,
+		Labels:       info.Config.Labels,
+		Ports:        ports,
+		StartedAt:    startedAt,
+		RestartCount: info.RestartCount,
+		ExitCode:     info.State.ExitCode,
 	}, nil
 }
 
@@ -882,12 +1098,16 @@ func (c *Client) AttachToWorkload(ctx context.Context, workloadID string) (io.Wr
 
 	stdoutReader, stdoutWriter := io.Pipe()
 
+	// Only stdout carries JSON-RPC messages; stderr is diagnostic output of the server, so it is logged
+	stderrWriter := logger.NewLineWriter(logger.Infof, fmt.Sprintf("[%s] ", workloadID))
+
 	go func() {
 		defer stdoutWriter.Close()
 		defer resp.Close()
+		defer stderrWriter.Flush()
 
 		// Use stdcopy to demultiplex the container streams
-		_, err := stdcopy.StdCopy(stdoutWriter, io.Discard, resp.Reader)
+		_, err := stdcopy.StdCopy(stdoutWriter, stderrWriter, resp.Reader)
 		if err != nil && err != io.EOF {
 			logger.Errorf("Error demultiplexing container streams: %v", err)
 		}
@@ -896,6 +1116,84 @@ func (c *Client) AttachToWorkload(ctx context.Context, workloadID string) (io.Wr
 	return resp.Conn, stdoutReader, nil
 }
 
+// ExecInWorkload runs a command in a workload and returns its exit code
+func (c *Client) ExecInWorkload(
+	ctx context.Context,
+	workloadID string,
+	command []string,
+	options *runtime.ExecOptions,
+) (int, error) {
+	// The command is never privileged, so it inherits the capabilities and the no-new-privileges option of the container
+	execConfig := container.ExecOptions{
+		Cmd:          command,
+		AttachStdin:  options.Stdin != nil,
+		AttachStdout: true,
+		AttachStderr: true,
+		Tty:          options.TTY,
+		ConsoleSize:  options.TerminalSize,
+		Privileged:   false,
+	}
+	created, err := c.client.ContainerExecCreate(ctx, workloadID, execConfig)
+	if err != nil {
+		if errdefs.IsNotFound(err) {
+			return 0, NewContainerError(ErrContainerNotFound, workloadID, "workload not found")
+		}
+		return 0, NewContainerError(err, workloadID, fmt.Sprintf("failed to create exec: %v", err))
+	}
+
+	resp, err := c.client.ContainerExecAttach(ctx, created.ID, container.ExecAttachOptions{
+		Tty:         options.TTY,
+		ConsoleSize: options.TerminalSize,
+	})
+	if err != nil {
+		return 0, NewContainerError(err, workloadID, fmt.Sprintf("failed to attach to exec: %v", err))
+	}
+	defer resp.Close()
+
+	if options.Stdin != nil {
+		go func() {
+			// The input is not closed when the command exits, so this goroutine may outlive the call
+			_, _ = io.Copy(resp.Conn, options.Stdin)
+			_ = resp.CloseWrite()
+		}()
+	}
+
+	// Without a pseudo-terminal, the output and error streams are multiplexed
+	if options.TTY {
+		_, err = io.Copy(options.Stdout, resp.Reader)
+	} else {
+		_, err = stdcopy.StdCopy(options.Stdout, options.Stderr, resp.Reader)
+	}
+	if err != nil && err != io.EOF {
+		return 0, NewContainerError(err, workloadID, fmt.Sprintf("failed to read exec output: %v", err))
+	}
+
+	inspect, err := c.client.ContainerExecInspect(ctx, created.ID)
+	if err != nil {
+		return 0, NewContainerError(err, workloadID, fmt.Sprintf("failed to inspect exec: %v", err))
+	}
+	return inspect.ExitCode, nil
+}
+
+// UpdateNetworkPermissions implements runtime.Runtime.
+func (c *Client) UpdateNetworkPermissions(
+	ctx context.Context,
+	workloadName string,
+	permissionProfile *permissions.Profile,
+) error {
+	egressContainerName := fmt.Sprintf("%s-egress", workloadName)
+	egressContainerId, err := c.findExistingContainer(ctx, egressContainerName)
+	if err != nil {
+		return err
+	}
+	if egressContainerId == "" {
+		// Without network isolation there is no egress proxy which enforces the network permissions
+		logger.Debugf("Workload %s has no egress container, network permissions are not enforced", workloadName)
+		return nil
+	}
+	return reconfigureEgressSquidContainer(ctx, c, workloadName, egressContainerId, permissionProfile)
+}
+
 // IsRunning checks the health of the container runtime.
 // This is used to verify that the runtime is operational and can manage workloads.
 func (c *Client) IsRunning(ctx context.Context) error {
@@ -1023,9 +1321,14 @@ func (c *Client) getPermissionConfigFromProfile(
 	c.addReadOnlyMounts(config, profile.Read)
 	c.addReadWriteMounts(config, profile.Write)
 
+	// Processes started with exec inherit the option from the container
+	if profile.NoNewPrivileges {
+		config.SecurityOpt = append(config.SecurityOpt, "no-new-privileges:true")
+	}
+
 	// Validate transport type
 	switch transportType {
-	case "sse", "stdio", "inspector", "streamable-http":
+	case "sse", "stdio", "inspector", "streamable-http", "websocket":
 		// valid, do nothing
 	default:
 		return nil, fmt.Errorf("unsupported transport type: %s", transportType)
@@ -1034,6 +1337,33 @@ func (c *Client) getPermissionConfigFromProfile(
 	return config, nil
 }
 
+// getWorkloadPermissionConfig returns the permission config of a workload adapted to the platform
+// of the daemon, and the platform
+func (c *Client) getWorkloadPermissionConfig(
+	ctx context.Context,
+	image string,
+	profile *permissions.Profile,
+	transportType string,
+	isolateNetwork bool,
+) (*runtime.PermissionConfig, daemonPlatform, error) {
+	platform := c.getDaemonPlatform(ctx)
+	permissionConfig, err := c.getPermissionConfigFromProfile(profile, transportType)
+	if err != nil {
+		return nil, platform, fmt.Errorf("failed to get permission config: %w", err)
+	}
+
+	if platform.isWindows() {
+		if isolateNetwork {
+			return nil, platform, ErrWindowsNetworkIsolation
+		}
+		c.adaptPermissionConfigForWindows(ctx, permissionConfig, image, platform)
+	}
+	if c.runtimeType == runtime.TypePodman && platform.rootless {
+		adaptPermissionConfigForRootlessPodman(permissionConfig)
+	}
+	return permissionConfig, platform, nil
+}
+
 // Error types for container operations
 var (
 	// ErrContainerNotFound is returned when a container is not found
@@ -1057,22 +1387,32 @@ type ContainerError struct {
 	ContainerID string
 	// Message is an optional error message
 	Message string
+	// ExitCode is the exit code of a container which failed to start or exited, or -1 if it is unknown.
+	// It is only set by NewContainerExitError.
+	ExitCode int
+	// Logs are the last lines of the logs of a container which failed to start or exited.
+	// It is only set by NewContainerExitError.
+	Logs string
 }
 
 // Error returns the error message
 func (e *ContainerError) Error() string {
-	if e.Message != "" {
-		if e.ContainerID != "" {
-			return fmt.Sprintf("%s: %s (container: %s)", e.Err, e.Message, e.ContainerID)
-		}
-		return fmt.Sprintf("%s: %s", e.Err, e.Message)
+	var msg string
+	switch {
+	case e.Message != "" && e.ContainerID != "":
+		msg = fmt.Sprintf("%s: %s (container: %s)", e.Err, e.Message, e.ContainerID)
+	case e.Message != "":
+		msg = fmt.Sprintf("%s: %s", e.Err, e.Message)
+	case e.ContainerID != "":
+		msg = fmt.Sprintf("%s (container: %s)", e.Err, e.ContainerID)
+	default:
+		msg = e.Err.Error()
 	}
 
-	if e.ContainerID != "" {
-		return fmt.Sprintf("%s (container: %s)", e.Err, e.ContainerID)
+	if e.Logs != "" {
+		msg = fmt.Sprintf("%s\nLast logs:\n%s", msg, e.Logs)
 	}
-
-	return e.Err.Error()
+	return msg
 }
 
 // Unwrap returns the underlying error
@@ -1089,6 +1429,34 @@ func NewContainerError(err error, containerID, message string) *ContainerError {
 	}
 }
 
+// exitLogLines is the number of lines of the logs of a container included in its exit error
+const exitLogLines = 20
+
+// NewContainerExitError creates the error of a container which failed to start or exited, with its
+// exit code and the last lines of its logs
+func NewContainerExitError(err error, containerID, message string, exitCode int, logs string) *ContainerError {
+	return &ContainerError{
+		Err:         err,
+		ContainerID: containerID,
+		Message:     message,
+		ExitCode:    exitCode,
+		Logs:        lastLines(logs, exitLogLines),
+	}
+}
+
+// lastLines returns the last n lines of the logs
+func lastLines(logs string, n int) string {
+	logs = strings.TrimRight(logs, "\n")
+	if logs == "" {
+		return ""
+	}
+	lines := strings.Split(logs, "\n")
+	if len(lines) > n {
+		lines = lines[len(lines)-n:]
+	}
+	return strings.Join(lines, "\n")
+}
+
 // findExistingContainer finds a container with the exact name
 func (c *Client) findExistingContainer(ctx context.Context, name string) (string, error) {
 	containers, err := c.client.ContainerList(ctx, container.ListOptions{
@@ -1125,12 +1493,18 @@ func compareBasicConfig(existing *container.InspectResponse, desired *container.
 	if len(existing.Config.Cmd) != len(desired.Cmd) {
 		return false
 	}
+
 	for i, cmd := range existing.Config.Cmd {
 		if i >= len(desired.Cmd) || cmd != desired.Cmd[i] {
 			return false
 		}
 	}
 
+	// Compare the stop signal and timeout
+	if !compareStopConfig(existing.Config, desired) {
+		return false
+	}
+
 	// Compare environment variables
 	if !compareEnvVars(existing.Config.Env, desired.Env) {
 		return false
@@ -1152,6 +1526,17 @@ func compareBasicConfig(existing *container.InspectResponse, desired *container.
 	return true
 }
 
+// compareStopConfig compares the stop signal and timeout, if they are configured
+func compareStopConfig(existing, desired *container.Config) bool {
+	if desired.StopSignal != "" && existing.StopSignal != desired.StopSignal {
+		return false
+	}
+	if desired.StopTimeout != nil && (existing.StopTimeout == nil || *existing.StopTimeout != *desired.StopTimeout) {
+		return false
+	}
+	return true
+}
+
 // compareEnvVars compares environment variables
 func compareEnvVars(existingEnv, desiredEnv []string) bool {
 	// Convert to maps for easier comparison
@@ -1213,14 +1598,157 @@ func compareHostConfig(existing *container.InspectResponse, desired *container.H
 		return false
 	}
 
+	// Compare user namespace mode
+	if existing.HostConfig.UsernsMode != desired.UsernsMode {
+		return false
+	}
+
 	// Compare restart policy
-	if existing.HostConfig.RestartPolicy.Name != desired.RestartPolicy.Name {
+	if existing.HostConfig.RestartPolicy != desired.RestartPolicy {
+		return false
+	}
+
+	// Compare resource limits
+	if !compareResources(existing.HostConfig.Resources, desired.Resources) {
+		return false
+	}
+
+	// Compare log configuration, only if one was requested since the runtime fills in its defaults
+	if desired.LogConfig.Type != "" && existing.HostConfig.LogConfig.Type != desired.LogConfig.Type {
+		return false
+	}
+	if !compareLabels(existing.HostConfig.LogConfig.Config, desired.LogConfig.Config) {
 		return false
 	}
 
 	return true
 }
 
+// convertResources converts the resource limits of a workload to the resources of a container
+func convertResources(limits *runtime.ResourceLimits) container.Resources {
+	resources := container.Resources{
+		NanoCPUs:   int64(limits.CPUs * 1e9),
+		Memory:     limits.Memory,
+		MemorySwap: limits.MemorySwap,
+	}
+	if limits.PidsLimit > 0 {
+		pidsLimit := limits.PidsLimit
+		resources.PidsLimit = &pidsLimit
+	}
+	return resources
+}
+
+// compareResources compares resource limits. The swap limit is only compared if one was requested,
+// since the runtime defaults it to twice the memory limit.
+func compareResources(existing, desired container.Resources) bool {
+	if existing.NanoCPUs != desired.NanoCPUs || existing.Memory != desired.Memory {
+		return false
+	}
+	if desired.MemorySwap != 0 && existing.MemorySwap != desired.MemorySwap {
+		return false
+	}
+
+	if pidsLimit(existing.PidsLimit) != pidsLimit(desired.PidsLimit) {
+		return false
+	}
+
+	return compareDevices(existing, desired)
+}
+
+// compareDevices compares the devices and device requests of containers
+func compareDevices(existing, desired container.Resources) bool {
+	if len(existing.Devices) != len(desired.Devices) || len(existing.DeviceRequests) != len(desired.DeviceRequests) {
+		return false
+	}
+	for i, device := range desired.Devices {
+		if existing.Devices[i].PathOnHost != device.PathOnHost ||
+			existing.Devices[i].PathInContainer != device.PathInContainer {
+			return false
+		}
+	}
+	for i, request := range desired.DeviceRequests {
+		if existing.DeviceRequests[i].Driver != request.Driver || existing.DeviceRequests[i].Count != request.Count ||
+			!compareStringSlices(existing.DeviceRequests[i].DeviceIDs, request.DeviceIDs) {
+			return false
+		}
+	}
+	return true
+}
+
+// setupDevices makes the GPUs and devices of the host requested for a workload available to its container.
+// Docker requests GPUs from the NVIDIA container runtime and CDI devices from the CDI driver, while
+// Podman resolves both as CDI devices, so GPUs are requested as nvidia.com/gpu devices.
+func setupDevices(hostConfig *container.HostConfig, options *runtime.DeployWorkloadOptions, runtimeType runtime.Type) error {
+	gpus, err := runtime.ParseGPURequest(options.GPUs)
+	if err != nil {
+		return err
+	}
+	var cdiNames []string
+	if gpus != nil {
+		if runtimeType == runtime.TypePodman {
+			cdiNames = append(cdiNames, nvidiaCDIDevices(gpus)...)
+		} else {
+			hostConfig.DeviceRequests = append(hostConfig.DeviceRequests, container.DeviceRequest{
+				Count:        gpus.Count,
+				DeviceIDs:    gpus.DeviceIDs,
+				Capabilities: [][]string{{"gpu"}},
+			})
+		}
+	}
+
+	for _, value := range options.Devices {
+		device, err := runtime.ParseDevice(value)
+		if err != nil {
+			return err
+		}
+		if device.CDIName != "" {
+			cdiNames = append(cdiNames, device.CDIName)
+			continue
+		}
+		hostConfig.Devices = append(hostConfig.Devices, container.DeviceMapping{
+			PathOnHost:        device.PathOnHost,
+			PathInContainer:   device.PathInContainer,
+			CgroupPermissions: device.Permissions,
+		})
+	}
+
+	for _, name := range cdiNames {
+		if runtimeType == runtime.TypePodman {
+			hostConfig.Devices = append(hostConfig.Devices, container.DeviceMapping{PathOnHost: name})
+		} else {
+			hostConfig.DeviceRequests = append(hostConfig.DeviceRequests, container.DeviceRequest{
+				Driver:    "cdi",
+				DeviceIDs: []string{name},
+			})
+		}
+	}
+	return nil
+}
+
+// nvidiaCDIDevices returns the names of the CDI devices of the NVIDIA GPUs of a GPU request
+func nvidiaCDIDevices(gpus *runtime.GPURequest) []string {
+	if gpus.Count == runtime.AllGPUs {
+		return []string{"nvidia.com/gpu=all"}
+	}
+	ids := gpus.DeviceIDs
+	for i := 0; i < gpus.Count; i++ {
+		ids = append(ids, strconv.Itoa(i))
+	}
+	names := make([]string, 0, len(ids))
+	for _, id := range ids {
+		names = append(names, "nvidia.com/gpu="+id)
+	}
+	return names
+}
+
+// pidsLimit returns the process limit of a container, where 0 is unlimited like a nil or negative limit
+func pidsLimit(limit *int64) int64 {
+	if limit == nil || *limit < 0 {
+		return 0
+	}
+	return *limit
+}
+
 // compareStringSlices compares two string slices
 func compareStringSlices(existing, desired []string) bool {
 	if len(existing) != len(desired) {
@@ -1320,6 +1848,31 @@ func compareContainerConfig(
 	return true
 }
 
+// imageChanged returns true if the image reference of the container now resolves to a different local image,
+// e.g. because a newer version of the image was pulled
+func (c *Client) imageChanged(ctx context.Context, existing *container.InspectResponse, desired *container.Config) bool {
+	imageID, err := c.imageManager.GetImageID(ctx, desired.Image)
+	if err != nil {
+		logger.Debugf("Failed to look up image %s: %v", desired.Image, err)
+		return false
+	}
+	return imageID != "" && imageID != existing.Image
+}
+
+// compareNetworks checks if the container is connected to all desired networks. Other networks are ignored,
+// since the runtime may connect containers to its default network as well.
+func compareNetworks(existing *container.InspectResponse, desired map[string]*network.EndpointSettings) bool {
+	if existing.NetworkSettings == nil {
+		return len(desired) == 0
+	}
+	for name := range desired {
+		if _, ok := existing.NetworkSettings.Networks[name]; !ok {
+			return false
+		}
+	}
+	return true
+}
+
 // handleExistingContainer checks if an existing container's configuration matches the desired configuration
 // Returns true if the container can be reused, false if it was removed and needs to be recreated
 func (c *Client) handleExistingContainer(
@@ -1327,6 +1880,7 @@ func (c *Client) handleExistingContainer(
 	containerID string,
 	desiredConfig *container.Config,
 	desiredHostConfig *container.HostConfig,
+	desiredEndpointsConfig map[string]*network.EndpointSettings,
 ) (bool, error) {
 	// Get container info
 	info, err := c.client.ContainerInspect(ctx, containerID)
@@ -1335,7 +1889,8 @@ func (c *Client) handleExistingContainer(
 	}
 
 	// Compare configurations
-	if compareContainerConfig(&info, desiredConfig, desiredHostConfig) {
+	if compareContainerConfig(&info, desiredConfig, desiredHostConfig) && compareNetworks(&info, desiredEndpointsConfig) &&
+		!c.imageChanged(ctx, &info, desiredConfig) {
 		// Configurations match, container can be reused
 
 		// Check if the container is running
@@ -1352,7 +1907,7 @@ func (c *Client) handleExistingContainer(
 
 	// Configurations don't match, need to recreate the container
 	// Stop the workload
-	if err := c.StopWorkload(ctx, containerID); err != nil {
+	if err := c.StopWorkload(ctx, containerID, nil); err != nil {
 		return false, err
 	}
 
@@ -1371,6 +1926,7 @@ func (c *Client) createNetwork(
 	name string,
 	labels map[string]string,
 	internal bool,
+	driver string,
 ) error {
 	// Check if the network already exists
 	networks, err := c.client.NetworkList(ctx, network.ListOptions{
@@ -1385,7 +1941,7 @@ func (c *Client) createNetwork(
 	}
 
 	networkCreate := network.CreateOptions{
-		Driver:   "bridge",
+		Driver:   driver,
 		Internal: internal,
 		Labels:   labels,
 	}
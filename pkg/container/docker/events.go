@@ -0,0 +1,98 @@
+package docker
+
+import (
+	"context"
+	"errors"
+	"io"
+	"strings"
+	"time"
+
+	"github.com/docker/docker/api/types/events"
+	"github.com/docker/docker/api/types/filters"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+)
+
+// lifecycleActions are the container actions reported as workload events.
+// Other actions, such as exec or attach, do not change the state of the workload.
+var lifecycleActions = map[events.Action]struct{}{
+	events.ActionCreate:  {},
+	events.ActionStart:   {},
+	events.ActionRestart: {},
+	events.ActionStop:    {},
+	events.ActionKill:    {},
+	events.ActionDie:     {},
+	events.ActionOOM:     {},
+	events.ActionPause:   {},
+	events.ActionUnPause: {},
+	events.ActionRename:  {},
+	events.ActionDestroy: {},
+}
+
+// WorkloadEvents returns the lifecycle and health events of the ToolHive containers
+func (c *Client) WorkloadEvents(ctx context.Context, options *runtime.EventOptions) (<-chan runtime.WorkloadEvent, <-chan error) {
+	listOptions := events.ListOptions{
+		Filters: filters.NewArgs(filters.Arg("type", string(events.ContainerEventType)), filters.Arg("label", "toolhive=true")),
+	}
+	if !options.Since.IsZero() {
+		listOptions.Since = options.Since.Format(time.RFC3339Nano)
+	}
+	if !options.Follow {
+		// Without an end time, the stream of events does not end
+		listOptions.Until = time.Now().Format(time.RFC3339Nano)
+	}
+
+	messages, messageErrs := c.client.Events(ctx, listOptions)
+	workloadEvents := make(chan runtime.WorkloadEvent)
+	errs := make(chan error, 1)
+
+	go func() {
+		defer close(workloadEvents)
+		for {
+			select {
+			case <-ctx.Done():
+				return
+			case err := <-messageErrs:
+				// The stream ends with EOF once the end time is reached
+				if !errors.Is(err, io.EOF) {
+					errs <- err
+				}
+				return
+			case msg, ok := <-messages:
+				if !ok {
+					return
+				}
+				event, ok := workloadEventFromMessage(msg)
+				if !ok {
+					continue
+				}
+				select {
+				case workloadEvents <- event:
+				case <-ctx.Done():
+					return
+				}
+			}
+		}
+	}()
+
+	return workloadEvents, errs
+}
+
+// workloadEventFromMessage converts a Docker event into a workload event,
+// or returns false if the event is not a lifecycle or health event of a workload
+func workloadEventFromMessage(msg events.Message) (runtime.WorkloadEvent, bool) {
+	if msg.Actor.Attributes[ToolhiveAuxiliaryWorkloadLabel] == LabelValueTrue {
+		return runtime.WorkloadEvent{}, false
+	}
+	_, lifecycle := lifecycleActions[msg.Action]
+	if !lifecycle && !strings.HasPrefix(string(msg.Action), string(events.ActionHealthStatus)) {
+		return runtime.WorkloadEvent{}, false
+	}
+
+	return runtime.WorkloadEvent{
+		Time:       time.Unix(0, msg.TimeNano),
+		WorkloadID: msg.Actor.ID,
+		Action:     string(msg.Action),
+		Attributes: msg.Actor.Attributes,
+	}, true
+}
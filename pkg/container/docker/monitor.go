@@ -7,9 +7,21 @@ import (
 	"time"
 
 	"github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/logger"
 )
 
-// ContainerMonitor watches a container's state and reports when it exits
+const (
+	// initialRestartDelay is the delay before a supervised container is restarted for the first time
+	initialRestartDelay = time.Second
+	// maxRestartDelay is the maximum delay before a supervised container is restarted
+	maxRestartDelay = 30 * time.Second
+	// restartResetPeriod is how long a supervised container must run before its restart delay is reset
+	restartResetPeriod = time.Minute
+)
+
+// ContainerMonitor watches a container's state and reports when it exits.
+// A supervising monitor restarts an exited container according to its restart policy instead,
+// and only reports when the container is not restarted.
 type ContainerMonitor struct {
 	runtime       runtime.Runtime
 	containerID   string
@@ -19,6 +31,13 @@ type ContainerMonitor struct {
 	wg            sync.WaitGroup
 	running       bool
 	mutex         sync.Mutex
+
+	// restartPolicy is the restart policy of a supervised container, or nil if the container is only monitored
+	restartPolicy *runtime.RestartPolicy
+	// restarts is the number of times the container was restarted since its restart delay was reset
+	restarts int
+	// lastRestart is the time the container was last restarted
+	lastRestart time.Time
 }
 
 // NewMonitor creates a new container monitor
@@ -32,6 +51,20 @@ func NewMonitor(rt runtime.Runtime, containerID, containerName string) runtime.M
 	}
 }
 
+// NewSupervisor creates a new container monitor which restarts the container according to the restart
+// policy when it exits, with an exponential backoff. Runtimes with restart policies restart the container
+// themselves, in which case the supervisor only keeps monitoring it.
+func NewSupervisor(rt runtime.Runtime, containerID, containerName string, policy runtime.RestartPolicy) runtime.Monitor {
+	return &ContainerMonitor{
+		runtime:       rt,
+		containerID:   containerID,
+		containerName: containerName,
+		stopCh:        make(chan struct{}),
+		errorCh:       make(chan error, 1), // Buffered to prevent blocking
+		restartPolicy: &policy,
+	}
+}
+
 // StartMonitoring starts monitoring the container
 func (m *ContainerMonitor) StartMonitoring(ctx context.Context) (<-chan error, error) {
 	m.mutex.Lock()
@@ -47,7 +80,8 @@ func (m *ContainerMonitor) StartMonitoring(ctx context.Context) (<-chan error, e
 		return nil, err
 	}
 	if !running {
-		return nil, NewContainerError(ErrContainerNotRunning, m.containerID, "container is not running")
+		// The container exited right after it was started, e.g. because of a bad entrypoint
+		return nil, m.exitError(ErrContainerNotRunning, "exited right after it was started")
 	}
 
 	m.running = true
@@ -113,25 +147,96 @@ func (m *ContainerMonitor) monitor(ctx context.Context) {
 				continue
 			}
 
-			if !running {
-				// Container has exited, get logs and info
-				// Create a short timeout context for these operations
-				infoCtx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
-				logs, _ := m.runtime.GetWorkloadLogs(infoCtx, m.containerID, false)
-				info, _ := m.runtime.GetWorkloadInfo(infoCtx, m.containerID)
-				cancel() // Always cancel the context to avoid leaks
-
-				exitErr := NewContainerError(
-					ErrContainerExited,
-					m.containerID,
-					fmt.Sprintf("Container %s (%s) exited unexpectedly. Status: %s. Last logs:\n%s",
-						m.containerName, m.containerID, info.Status, logs),
-				)
-				m.errorCh <- exitErr
-				return
+			if running {
+				m.resetRestartDelay()
+				continue
 			}
+			if m.restart(ctx) {
+				continue
+			}
+
+			// Container has exited
+			m.errorCh <- m.exitError(ErrContainerExited, "exited unexpectedly")
+			return
+		}
+	}
+}
+
+// exitError returns the error of a container which is not running, with its exit code and the last lines
+// of its logs
+func (m *ContainerMonitor) exitError(err error, reason string) error {
+	// Create a short timeout context for these operations
+	infoCtx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+	defer cancel()
+	logs, _ := m.runtime.GetWorkloadLogs(infoCtx, m.containerID, runtime.LogOptions{Tail: runtime.DefaultLogTail})
+	info, infoErr := m.runtime.GetWorkloadInfo(infoCtx, m.containerID)
+	if infoErr != nil {
+		return NewContainerExitError(err, m.containerID,
+			fmt.Sprintf("Container %s (%s) %s", m.containerName, m.containerID, reason), -1, logs)
+	}
+
+	return NewContainerExitError(err, m.containerID,
+		fmt.Sprintf("Container %s (%s) %s (exit code %d). Status: %s",
+			m.containerName, m.containerID, reason, info.ExitCode, info.Status),
+		info.ExitCode, logs)
+}
+
+// restart restarts an exited container according to the restart policy of a supervisor, waiting longer
+// after each restart. It returns false if the container is not restarted.
+func (m *ContainerMonitor) restart(ctx context.Context) bool {
+	if m.restartPolicy == nil {
+		return false
+	}
+
+	for {
+		infoCtx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+		info, err := m.runtime.GetWorkloadInfo(infoCtx, m.containerID)
+		cancel()
+		if err != nil || !m.restartPolicy.ShouldRestart(info.ExitCode, m.restarts) {
+			return false
 		}
+
+		delay := restartDelay(m.restarts)
+		logger.Infof("Container %s exited with code %d, restarting it in %s", m.containerName, info.ExitCode, delay)
+		select {
+		case <-ctx.Done():
+			return false
+		case <-m.stopCh:
+			return false
+		case <-time.After(delay):
+		}
+
+		// The runtime may have restarted the container itself in the meantime
+		startCtx, cancel := context.WithTimeout(context.Background(), 30*time.Second)
+		running, err := m.runtime.IsWorkloadRunning(startCtx, m.containerID)
+		if err == nil && !running {
+			err = m.runtime.StartWorkload(startCtx, m.containerID)
+		}
+		cancel()
+		m.restarts++
+		if err == nil {
+			m.lastRestart = time.Now()
+			return true
+		}
+		logger.Warnf("Failed to restart container %s: %v", m.containerName, err)
+	}
+}
+
+// resetRestartDelay resets the restart delay of a supervised container once it has run long enough
+func (m *ContainerMonitor) resetRestartDelay() {
+	if m.restarts > 0 && time.Since(m.lastRestart) >= restartResetPeriod {
+		m.restarts = 0
+	}
+}
+
+// restartDelay returns the delay before a container which was already restarted the given number of times
+// is restarted again, which doubles with each restart up to maxRestartDelay
+func restartDelay(restarts int) time.Duration {
+	delay := initialRestartDelay
+	for i := 0; i < restarts && delay < maxRestartDelay; i++ {
+		delay *= 2
 	}
+	return min(delay, maxRestartDelay)
 }
 
 // IsContainerNotFound checks if the error is a container not found error
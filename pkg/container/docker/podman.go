@@ -0,0 +1,32 @@
+package docker
+
+import (
+	"strings"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+)
+
+// podmanKeepIDUsernsMode maps the user running rootless Podman to the same UID and GID in the container
+const podmanKeepIDUsernsMode = "keep-id"
+
+// isRootless returns true if the security options reported by the daemon include rootless mode
+func isRootless(securityOptions []string) bool {
+	for _, option := range securityOptions {
+		for _, field := range strings.Split(option, ",") {
+			if field == "name=rootless" {
+				return true
+			}
+		}
+	}
+	return false
+}
+
+// adaptPermissionConfigForRootlessPodman keeps the UID of the user in the container if the workload has
+// bind mounts. Rootless Podman otherwise maps the user to root in the container and the container users
+// to subordinate UIDs, so the mounted host paths are unreadable or unwritable inside the container.
+func adaptPermissionConfigForRootlessPodman(config *runtime.PermissionConfig) {
+	if len(config.Mounts) == 0 || config.UsernsMode != "" {
+		return
+	}
+	config.UsernsMode = podmanKeepIDUsernsMode
+}
@@ -0,0 +1,99 @@
+package docker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+)
+
+func TestIsRootless(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name            string
+		securityOptions []string
+		expected        bool
+	}{
+		{
+			name:     "No security options",
+			expected: false,
+		},
+		{
+			name:            "Rootless Podman",
+			securityOptions: []string{"name=seccomp,profile=default", "name=rootless", "name=selinux"},
+			expected:        true,
+		},
+		{
+			name:            "Rootless Docker",
+			securityOptions: []string{"name=seccomp,profile=builtin", "name=rootless", "name=cgroupns"},
+			expected:        true,
+		},
+		{
+			name:            "Rootless among the fields of an option",
+			securityOptions: []string{"name=seccomp,name=rootless"},
+			expected:        true,
+		},
+		{
+			name:            "Rootful daemon",
+			securityOptions: []string{"name=apparmor", "name=seccomp,profile=builtin", "name=cgroupns"},
+			expected:        false,
+		},
+		{
+			name:            "Field only containing rootless",
+			securityOptions: []string{"name=rootlesskit", "profile=rootless"},
+			expected:        false,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, isRootless(tt.securityOptions))
+		})
+	}
+}
+
+func TestAdaptPermissionConfigForRootlessPodman(t *testing.T) {
+	t.Parallel()
+
+	mounts := []runtime.Mount{{Source: "/home/user/data", Target: "/data", ReadOnly: true}}
+	tests := []struct {
+		name       string
+		config     runtime.PermissionConfig
+		usernsMode string
+	}{
+		{
+			name:       "Without mounts",
+			config:     runtime.PermissionConfig{},
+			usernsMode: "",
+		},
+		{
+			name:       "With mounts",
+			config:     runtime.PermissionConfig{Mounts: mounts},
+			usernsMode: podmanKeepIDUsernsMode,
+		},
+		{
+			name:       "Without mounts and with an explicit user namespace mode",
+			config:     runtime.PermissionConfig{UsernsMode: "host"},
+			usernsMode: "host",
+		},
+		{
+			name:       "With mounts and an explicit user namespace mode",
+			config:     runtime.PermissionConfig{Mounts: mounts, UsernsMode: "auto"},
+			usernsMode: "auto",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			config := tt.config
+			adaptPermissionConfigForRootlessPodman(&config)
+			assert.Equal(t, tt.usernsMode, config.UsernsMode)
+			assert.Equal(t, tt.config.Mounts, config.Mounts, "the mounts should not change")
+		})
+	}
+}
@@ -10,6 +10,8 @@ import (
 	"net/http"
 	"os"
 	"path/filepath"
+	"strconv"
+	"strings"
 
 	"github.com/docker/docker/client"
 
@@ -34,6 +36,7 @@ func newPlatformClient(socketPath string) (*http.Client, []client.Opt) {
 	// Create Docker client options
 	opts := []client.Opt{
 		client.WithAPIVersionNegotiation(),
+		client.WithVersionFromEnv(),
 		client.WithHTTPClient(httpClient),
 		client.WithHost("unix://" + socketPath),
 	}
@@ -62,68 +65,108 @@ func findPlatformContainerSocket(rt runtime.Type) (string, runtime.Type, error)
 		return customSocketPath, runtime.TypeDocker, nil
 	}
 
-	if rt == runtime.TypePodman {
-		// Try Podman sockets first
-		// Check standard Podman location
-		_, err := os.Stat(PodmanSocketPath)
-		if err == nil {
-			logger.Debugf("Found Podman socket at %s", PodmanSocketPath)
-			return PodmanSocketPath, runtime.TypePodman, nil
-		}
-
-		logger.Debugf("Failed to check Podman socket at %s: %v", PodmanSocketPath, err)
-
-		// Check XDG_RUNTIME_DIR location for Podman
-		if xdgRuntimeDir := os.Getenv("XDG_RUNTIME_DIR"); xdgRuntimeDir != "" {
-			xdgSocketPath := filepath.Join(xdgRuntimeDir, PodmanXDGRuntimeSocketPath)
-			_, err := os.Stat(xdgSocketPath)
-
-			if err == nil {
-				logger.Debugf("Found Podman socket at %s", xdgSocketPath)
-				return xdgSocketPath, runtime.TypePodman, nil
-			}
+	switch rt {
+	case runtime.TypePodman:
+		return findSocket(rt, podmanSocketCandidates())
+	case runtime.TypeDocker:
+		return findSocket(rt, dockerSocketCandidates())
+	case runtime.TypeKubernetes, runtime.TypeApple, runtime.TypeContainerd, runtime.TypeFinch:
+		return "", "", ErrRuntimeNotFound
+	default:
+		return "", "", ErrRuntimeNotFound
+	}
+}
 
-			logger.Debugf("Failed to check Podman socket at %s: %v", xdgSocketPath, err)
-		}
+// podmanSocketCandidates returns the Podman sockets to try, in order: the system socket, the socket
+// of the user for rootless Podman, the sockets of Podman machines and the socket of a Lima VM created
+// from the podman template
+func podmanSocketCandidates() []string {
+	candidates := []string{
+		PodmanSocketPath,
+		filepath.Join(userRuntimeDir(), PodmanXDGRuntimeSocketPath),
+	}
+	candidates = append(candidates, podmanMachineAPISockets()...)
+	if home := os.Getenv("HOME"); home != "" {
+		candidates = append(candidates,
+			filepath.Join(home, PodmanMachineSocketPath),
+			filepath.Join(home, LimaPodmanSocketPath),
+		)
+	}
+	return candidates
+}
 
-		// Check user-specific location for Podman
-		if home := os.Getenv("HOME"); home != "" {
-			userSocketPath := filepath.Join(home, ".local/share/containers/podman/machine/podman.sock")
-			_, err := os.Stat(userSocketPath)
+// userRuntimeDir returns the runtime directory of the user, in which rootless Podman serves its socket.
+// It defaults to /run/user/UID, where systemd creates it, if XDG_RUNTIME_DIR is not set.
+func userRuntimeDir() string {
+	if xdgRuntimeDir := os.Getenv("XDG_RUNTIME_DIR"); xdgRuntimeDir != "" {
+		return xdgRuntimeDir
+	}
+	return filepath.Join("/run/user", strconv.Itoa(os.Getuid()))
+}
 
-			if err == nil {
-				logger.Debugf("Found Podman socket at %s", userSocketPath)
-				return userSocketPath, runtime.TypePodman, nil
-			}
+// podmanMachineAPISockets returns the API sockets of the Podman machines on macOS,
+// starting with the socket of the default machine
+func podmanMachineAPISockets() []string {
+	dir := filepath.Join(os.TempDir(), PodmanMachineAPISocketDir)
+	defaultSocket := filepath.Join(dir, PodmanDefaultMachineAPISocket)
+	sockets := []string{defaultSocket}
 
-			logger.Debugf("Failed to check Podman socket at %s: %v", userSocketPath, err)
+	matches, err := filepath.Glob(filepath.Join(dir, "*-api.sock"))
+	if err != nil {
+		return sockets
+	}
+	for _, match := range matches {
+		if match != defaultSocket {
+			sockets = append(sockets, match)
 		}
 	}
+	return sockets
+}
 
-	if rt == runtime.TypeDocker {
-		// Try Docker socket as fallback
-		_, err := os.Stat(DockerSocketPath)
+// dockerSocketCandidates returns the Docker sockets to try, in order: the socket of the current Docker
+// context, the system socket, and the sockets of Docker Desktop, Colima, Rancher Desktop and a Lima VM
+// created from the docker template
+func dockerSocketCandidates() []string {
+	var candidates []string
+	if contextSocketPath, ok := findDockerContextSocket(); ok {
+		candidates = append(candidates, contextSocketPath)
+	}
+	candidates = append(candidates, DockerSocketPath)
+	if home := os.Getenv("HOME"); home != "" {
+		candidates = append(candidates,
+			filepath.Join(home, DockerDesktopMacSocketPath),
+			filepath.Join(home, ColimaSocketPath),
+			filepath.Join(home, ColimaXDGSocketPath),
+			filepath.Join(home, RancherDesktopSocketPath),
+			filepath.Join(home, LimaDockerSocketPath),
+		)
+	}
+	return candidates
+}
 
+// findSocket returns the first of the candidate sockets which exists, or an error listing the candidates
+func findSocket(rt runtime.Type, candidates []string) (string, runtime.Type, error) {
+	for _, socketPath := range candidates {
+		_, err := os.Stat(socketPath)
 		if err == nil {
-			logger.Debugf("Found Docker socket at %s", DockerSocketPath)
-			return DockerSocketPath, runtime.TypeDocker, nil
-		}
-
-		logger.Debugf("Failed to check Docker socket at %s: %v", DockerSocketPath, err)
-
-		// Try Docker Desktop socket path on macOS
-		if home := os.Getenv("HOME"); home != "" {
-			dockerDesktopPath := filepath.Join(home, DockerDesktopMacSocketPath)
-			_, err := os.Stat(dockerDesktopPath)
-
-			if err == nil {
-				logger.Debugf("Found Docker Desktop socket at %s", dockerDesktopPath)
-				return dockerDesktopPath, runtime.TypeDocker, nil
-			}
-
-			logger.Debugf("Failed to check Docker Desktop socket at %s: %v", dockerDesktopPath, err)
+			logger.Debugf("Found %s socket at %s", rt, socketPath)
+			return socketPath, rt, nil
 		}
+		logger.Debugf("Failed to check %s socket at %s: %v", rt, socketPath, err)
 	}
+	return "", "", fmt.Errorf("%w (tried %s)", ErrRuntimeNotFound, strings.Join(candidates, ", "))
+}
 
-	return "", "", ErrRuntimeNotFound
+// findDockerContextSocket returns the socket of the current Docker context, if it is set
+func findDockerContextSocket() (string, bool) {
+	socketPath, contextName, err := currentDockerContextSocket()
+	if err != nil {
+		logger.Debugf("Failed to use the current Docker context: %v", err)
+		return "", false
+	}
+	if socketPath == "" {
+		return "", false
+	}
+	logger.Debugf("Using socket %s of Docker context %s", socketPath, contextName)
+	return socketPath, true
 }
@@ -0,0 +1,72 @@
+//go:build !windows
+// +build !windows
+
+package sdk
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+)
+
+func TestSocketCandidates(t *testing.T) { //nolint:paralleltest // Test modifies environment variables
+	home := t.TempDir()
+	tmp := t.TempDir()
+	t.Setenv("HOME", home)
+	t.Setenv("TMPDIR", tmp)
+	t.Setenv("XDG_RUNTIME_DIR", "/run/user/1000")
+	t.Setenv(DockerConfigEnv, t.TempDir())
+	t.Setenv(DockerContextEnv, "")
+
+	// The sockets of other Podman machines are tried after the default machine
+	require.NoError(t, os.MkdirAll(filepath.Join(tmp, "podman"), 0750))
+	require.NoError(t, os.WriteFile(filepath.Join(tmp, "podman", "dev-api.sock"), nil, 0600))
+
+	assert.Equal(t, []string{
+		PodmanSocketPath,
+		"/run/user/1000/podman/podman.sock",
+		filepath.Join(tmp, "podman/podman-machine-default-api.sock"),
+		filepath.Join(tmp, "podman/dev-api.sock"),
+		filepath.Join(home, ".local/share/containers/podman/machine/podman.sock"),
+		filepath.Join(home, ".lima/podman/sock/podman.sock"),
+	}, podmanSocketCandidates())
+
+	// Rootless Podman serves its socket in /run/user/UID if XDG_RUNTIME_DIR is not set
+	t.Setenv("XDG_RUNTIME_DIR", "")
+	assert.Contains(t, podmanSocketCandidates(), fmt.Sprintf("/run/user/%d/podman/podman.sock", os.Getuid()))
+
+	assert.Equal(t, []string{
+		DockerSocketPath,
+		filepath.Join(home, ".docker/run/docker.sock"),
+		filepath.Join(home, ".colima/default/docker.sock"),
+		filepath.Join(home, ".config/colima/default/docker.sock"),
+		filepath.Join(home, ".rd/docker.sock"),
+		filepath.Join(home, ".lima/docker/sock/docker.sock"),
+	}, dockerSocketCandidates())
+}
+
+func TestFindSocket(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	missing := filepath.Join(dir, "missing.sock")
+	colima := filepath.Join(dir, ".colima", "default", "docker.sock")
+	require.NoError(t, os.MkdirAll(filepath.Dir(colima), 0750))
+	require.NoError(t, os.WriteFile(colima, nil, 0600))
+
+	socketPath, rt, err := findSocket(runtime.TypeDocker, []string{missing, colima})
+	require.NoError(t, err)
+	assert.Equal(t, colima, socketPath)
+	assert.Equal(t, runtime.TypeDocker, rt)
+
+	// The error lists every socket which was tried
+	_, _, err = findSocket(runtime.TypeDocker, []string{missing, filepath.Join(dir, "other.sock")})
+	require.ErrorIs(t, err, ErrRuntimeNotFound)
+	assert.Contains(t, err.Error(), "tried "+missing+", "+filepath.Join(dir, "other.sock"))
+}
@@ -9,6 +9,7 @@ import (
 	"net"
 	"net/http"
 	"os"
+	"strings"
 	"time"
 
 	"github.com/Microsoft/go-winio"
@@ -24,8 +25,21 @@ const (
 	// DockerDesktopWindowsPipePath is the Docker Desktop named pipe path on Windows
 	DockerDesktopWindowsPipePath = `\\.\pipe\docker_engine`
 
+	// DockerDesktopLinuxEnginePipePath is the named pipe of the Linux engine of Docker Desktop on Windows
+	DockerDesktopLinuxEnginePipePath = `\\.\pipe\dockerDesktopLinuxEngine`
+
 	// PodmanDesktopWindowsPipePath is the Podman Desktop named pipe path on Windows
 	PodmanDesktopWindowsPipePath = `\\.\pipe\podman-api`
+
+	// PodmanMachineWindowsPipePath is the named pipe of the default Podman machine on Windows
+	PodmanMachineWindowsPipePath = `\\.\pipe\podman-machine-default`
+)
+
+const (
+	// pipeDir is the directory of the named pipes on Windows
+	pipeDir = `\\.\pipe\`
+	// podmanPipePrefix is the prefix of the named pipes of Podman machines, which are named after the machine
+	podmanPipePrefix = "podman-"
 )
 
 // Windows named pipe connection timeout
@@ -48,6 +62,7 @@ func newPlatformClient(pipePath string) (*http.Client, []client.Opt) {
 	// Create Docker client options
 	opts := []client.Opt{
 		client.WithAPIVersionNegotiation(),
+		client.WithVersionFromEnv(),
 		client.WithHTTPClient(httpClient),
 		client.WithHost("npipe://" + pipePath),
 	}
@@ -60,55 +75,92 @@ func findPlatformContainerSocket(rt runtime.Type) (string, runtime.Type, error)
 	// First check for custom socket paths via environment variables
 	if customPipePath := os.Getenv(PodmanSocketEnv); customPipePath != "" {
 		logger.Debugf("Using Podman pipe from env: %s", customPipePath)
-		// Validate the pipe path exists with timeout
-		ctx, cancel := context.WithTimeout(context.Background(), pipeConnectionTimeout)
-		defer cancel()
-		conn, err := winio.DialPipeContext(ctx, customPipePath)
-		if err != nil {
+		customPipePath = normalizePipePath(customPipePath)
+		if err := dialPipe(customPipePath); err != nil {
 			return "", runtime.TypePodman, fmt.Errorf("invalid Podman pipe path: %w", err)
 		}
-		conn.Close()
 		return customPipePath, runtime.TypePodman, nil
 	}
 
 	if customPipePath := os.Getenv(DockerSocketEnv); customPipePath != "" {
 		logger.Debugf("Using Docker pipe from env: %s", customPipePath)
-		// Validate the pipe path exists with timeout
-		ctx, cancel := context.WithTimeout(context.Background(), pipeConnectionTimeout)
-		defer cancel()
-		conn, err := winio.DialPipeContext(ctx, customPipePath)
-		if err != nil {
+		customPipePath = normalizePipePath(customPipePath)
+		if err := dialPipe(customPipePath); err != nil {
 			return "", runtime.TypeDocker, fmt.Errorf("invalid Docker pipe path: %w", err)
 		}
-		conn.Close()
 		return customPipePath, runtime.TypeDocker, nil
 	}
 
-	if rt == runtime.TypePodman {
-		// Try Podman named pipe with timeout
-		ctx, cancel := context.WithTimeout(context.Background(), pipeConnectionTimeout)
-		defer cancel()
-		conn, err := winio.DialPipeContext(ctx, PodmanDesktopWindowsPipePath)
-		if err == nil {
-			logger.Debugf("Found Podman pipe at %s", PodmanDesktopWindowsPipePath)
-			conn.Close()
-			return PodmanDesktopWindowsPipePath, runtime.TypePodman, nil
+	switch rt {
+	case runtime.TypePodman:
+		return findPipe(rt, podmanPipeCandidates(listPipes()))
+	case runtime.TypeDocker:
+		return findPipe(rt, []string{DockerDesktopWindowsPipePath, DockerDesktopLinuxEnginePipePath})
+	case runtime.TypeKubernetes, runtime.TypeApple, runtime.TypeContainerd, runtime.TypeFinch:
+		return "", "", ErrRuntimeNotFound
+	default:
+		return "", "", ErrRuntimeNotFound
+	}
+}
+
+// normalizePipePath returns the path of a named pipe given as a path, e.g. \\.\pipe\docker_engine, or as
+// a Docker host, e.g. npipe:////./pipe/docker_engine
+func normalizePipePath(pipePath string) string {
+	if !strings.HasPrefix(pipePath, "npipe://") {
+		return pipePath
+	}
+	return strings.ReplaceAll(strings.TrimPrefix(pipePath, "npipe://"), "/", `\`)
+}
+
+// podmanPipeCandidates returns the named pipes of Podman to try, in order: the pipe of Podman Desktop, the
+// pipe of the default Podman machine and the pipes of the other Podman machines among the given pipes
+func podmanPipeCandidates(pipes []string) []string {
+	candidates := []string{PodmanDesktopWindowsPipePath, PodmanMachineWindowsPipePath}
+	for _, pipe := range pipes {
+		pipePath := pipeDir + pipe
+		if strings.HasPrefix(pipe, podmanPipePrefix) &&
+			pipePath != PodmanDesktopWindowsPipePath && pipePath != PodmanMachineWindowsPipePath {
+			candidates = append(candidates, pipePath)
 		}
-		logger.Debugf("Failed to connect to Podman pipe at %s: %v", PodmanDesktopWindowsPipePath, err)
 	}
+	return candidates
+}
 
-	if rt == runtime.TypeDocker {
-		// Try Docker named pipe with timeout
-		ctx, cancel := context.WithTimeout(context.Background(), pipeConnectionTimeout)
-		defer cancel()
-		conn, err := winio.DialPipeContext(ctx, DockerDesktopWindowsPipePath)
+// listPipes returns the names of the named pipes of the system
+func listPipes() []string {
+	entries, err := os.ReadDir(pipeDir)
+	if err != nil {
+		logger.Debugf("Failed to list named pipes: %v", err)
+		return nil
+	}
+	pipes := make([]string, 0, len(entries))
+	for _, entry := range entries {
+		pipes = append(pipes, entry.Name())
+	}
+	return pipes
+}
+
+// findPipe returns the first of the candidate named pipes which can be connected to, or an error listing
+// the candidates
+func findPipe(rt runtime.Type, candidates []string) (string, runtime.Type, error) {
+	for _, pipePath := range candidates {
+		err := dialPipe(pipePath)
 		if err == nil {
-			logger.Debugf("Found Docker pipe at %s", DockerDesktopWindowsPipePath)
-			conn.Close()
-			return DockerDesktopWindowsPipePath, runtime.TypeDocker, nil
+			logger.Debugf("Found %s pipe at %s", rt, pipePath)
+			return pipePath, rt, nil
 		}
-		logger.Debugf("Failed to connect to Docker pipe at %s: %v", DockerDesktopWindowsPipePath, err)
+		logger.Debugf("Failed to connect to %s pipe at %s: %v", rt, pipePath, err)
 	}
+	return "", "", fmt.Errorf("%w (tried %s)", ErrRuntimeNotFound, strings.Join(candidates, ", "))
+}
 
-	return "", "", ErrRuntimeNotFound
+// dialPipe checks that a named pipe can be connected to
+func dialPipe(pipePath string) error {
+	ctx, cancel := context.WithTimeout(context.Background(), pipeConnectionTimeout)
+	defer cancel()
+	conn, err := winio.DialPipeContext(ctx, pipePath)
+	if err != nil {
+		return err
+	}
+	return conn.Close()
 }
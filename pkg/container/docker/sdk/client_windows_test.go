@@ -0,0 +1,49 @@
+//go:build windows
+// +build windows
+
+package sdk
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestNormalizePipePath(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name     string
+		pipePath string
+		expected string
+	}{
+		{
+			name:     "Pipe path",
+			pipePath: `\\.\pipe\docker_engine`,
+			expected: `\\.\pipe\docker_engine`,
+		},
+		{
+			name:     "Docker host",
+			pipePath: "npipe:////./pipe/docker_engine",
+			expected: `\\.\pipe\docker_engine`,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, normalizePipePath(tt.pipePath))
+		})
+	}
+}
+
+func TestPodmanPipeCandidates(t *testing.T) {
+	t.Parallel()
+
+	// The pipes of other Podman machines are tried after the default machine
+	assert.Equal(t, []string{
+		PodmanDesktopWindowsPipePath,
+		PodmanMachineWindowsPipePath,
+		`\\.\pipe\podman-dev`,
+	}, podmanPipeCandidates([]string{"docker_engine", "podman-machine-default", "podman-dev", "podman-api"}))
+}
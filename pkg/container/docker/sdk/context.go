@@ -0,0 +1,105 @@
+//go:build !windows
+// +build !windows
+
+package sdk
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+const (
+	// DockerConfigEnv is the environment variable for the Docker CLI configuration directory
+	DockerConfigEnv = "DOCKER_CONFIG"
+	// DockerContextEnv is the environment variable overriding the current Docker context
+	DockerContextEnv = "DOCKER_CONTEXT"
+	// defaultDockerContext is the name of the context using the default socket of the platform
+	defaultDockerContext = "default"
+)
+
+// dockerCLIConfig is the part of the Docker CLI configuration file naming the current context
+type dockerCLIConfig struct {
+	CurrentContext string `json:"currentContext"`
+}
+
+// dockerContextMeta is the metadata of a Docker context
+type dockerContextMeta struct {
+	Name      string `json:"Name"`
+	Endpoints map[string]struct {
+		Host string `json:"Host"`
+	} `json:"Endpoints"`
+}
+
+// dockerConfigDir returns the configuration directory of the Docker CLI, or an empty string if it is unknown
+func dockerConfigDir() string {
+	if dir := os.Getenv(DockerConfigEnv); dir != "" {
+		return dir
+	}
+	home, err := os.UserHomeDir()
+	if err != nil {
+		return ""
+	}
+	return filepath.Join(home, ".docker")
+}
+
+// currentDockerContextHost returns the Docker host of the current context of the Docker CLI, e.g. the
+// socket of Docker Desktop, and the name of the context. It returns an empty host for the default context.
+func currentDockerContextHost(configDir string) (string, string, error) {
+	name := os.Getenv(DockerContextEnv)
+	if name == "" {
+		// #nosec G304 - the path is the configuration file of the Docker CLI
+		data, err := os.ReadFile(filepath.Join(configDir, "config.json"))
+		if err != nil {
+			if errors.Is(err, os.ErrNotExist) {
+				return "", "", nil
+			}
+			return "", "", fmt.Errorf("failed to read Docker CLI configuration: %w", err)
+		}
+		var config dockerCLIConfig
+		if err := json.Unmarshal(data, &config); err != nil {
+			return "", "", fmt.Errorf("failed to parse Docker CLI configuration: %w", err)
+		}
+		name = config.CurrentContext
+	}
+	if name == "" || name == defaultDockerContext {
+		return "", name, nil
+	}
+
+	// The metadata of a context is stored in a directory named after the digest of its name
+	digest := sha256.Sum256([]byte(name))
+	metaPath := filepath.Join(configDir, "contexts", "meta", hex.EncodeToString(digest[:]), "meta.json")
+	// #nosec G304 - the path is built from the configuration directory of the Docker CLI
+	data, err := os.ReadFile(metaPath)
+	if err != nil {
+		return "", name, fmt.Errorf("failed to read the metadata of Docker context %s: %w", name, err)
+	}
+	var meta dockerContextMeta
+	if err := json.Unmarshal(data, &meta); err != nil {
+		return "", name, fmt.Errorf("failed to parse the metadata of Docker context %s: %w", name, err)
+	}
+	return meta.Endpoints["docker"].Host, name, nil
+}
+
+// currentDockerContextSocket returns the Unix socket of the current Docker context, or an empty
+// string if the current context is the default one or doesn't use a Unix socket
+func currentDockerContextSocket() (string, string, error) {
+	configDir := dockerConfigDir()
+	if configDir == "" {
+		return "", "", nil
+	}
+	host, name, err := currentDockerContextHost(configDir)
+	if err != nil || host == "" {
+		return "", name, err
+	}
+	socketPath, ok := strings.CutPrefix(host, "unix://")
+	if !ok {
+		return "", name, fmt.Errorf("docker context %s uses unsupported host %s", name, host)
+	}
+	return socketPath, name, nil
+}
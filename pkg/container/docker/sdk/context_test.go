@@ -0,0 +1,96 @@
+//go:build !windows
+// +build !windows
+
+package sdk
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+// writeDockerContext writes the metadata of a Docker context with the given host to the configuration directory
+func writeDockerContext(t *testing.T, configDir, name, host string) {
+	t.Helper()
+	digest := sha256.Sum256([]byte(name))
+	dir := filepath.Join(configDir, "contexts", "meta", hex.EncodeToString(digest[:]))
+	require.NoError(t, os.MkdirAll(dir, 0750))
+	meta := `{"Name":"` + name + `","Metadata":{},"Endpoints":{"docker":{"Host":"` + host + `","SkipTLSVerify":false}}}`
+	require.NoError(t, os.WriteFile(filepath.Join(dir, "meta.json"), []byte(meta), 0600))
+}
+
+func TestCurrentDockerContextHost(t *testing.T) { //nolint:paralleltest // Test modifies environment variables
+	tests := []struct {
+		name         string
+		config       string
+		contextEnv   string
+		expectedHost string
+		expectError  bool
+	}{
+		{
+			name:         "No configuration",
+			expectedHost: "",
+		},
+		{
+			name:         "Default context",
+			config:       `{"currentContext":"default"}`,
+			expectedHost: "",
+		},
+		{
+			name:         "Docker Desktop context",
+			config:       `{"auths":{},"currentContext":"desktop-linux"}`,
+			expectedHost: "unix:///Users/me/.docker/run/docker.sock",
+		},
+		{
+			name:         "Context from environment",
+			config:       `{"currentContext":"default"}`,
+			contextEnv:   "desktop-linux",
+			expectedHost: "unix:///Users/me/.docker/run/docker.sock",
+		},
+		{
+			name:        "Missing context",
+			config:      `{"currentContext":"missing"}`,
+			expectError: true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) { //nolint:paralleltest // Test modifies environment variables
+			t.Setenv(DockerContextEnv, tt.contextEnv)
+			configDir := t.TempDir()
+			writeDockerContext(t, configDir, "desktop-linux", "unix:///Users/me/.docker/run/docker.sock")
+			if tt.config != "" {
+				require.NoError(t, os.WriteFile(filepath.Join(configDir, "config.json"), []byte(tt.config), 0600))
+			}
+
+			host, _, err := currentDockerContextHost(configDir)
+			if tt.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.expectedHost, host)
+		})
+	}
+}
+
+func TestCurrentDockerContextSocket(t *testing.T) { //nolint:paralleltest // Test modifies environment variables
+	configDir := t.TempDir()
+	t.Setenv(DockerConfigEnv, configDir)
+	t.Setenv(DockerContextEnv, "remote")
+	writeDockerContext(t, configDir, "remote", "tcp://192.168.1.10:2376")
+
+	_, _, err := currentDockerContextSocket()
+	assert.Error(t, err, "only Unix sockets are supported")
+
+	writeDockerContext(t, configDir, "remote", "unix:///run/user/1000/docker.sock")
+	socketPath, name, err := currentDockerContextSocket()
+	require.NoError(t, err)
+	assert.Equal(t, "/run/user/1000/docker.sock", socketPath)
+	assert.Equal(t, "remote", name)
+}
@@ -3,8 +3,10 @@ package sdk
 
 import (
 	"context"
+	"errors"
 	"fmt"
 
+	"github.com/docker/docker/api/types"
 	"github.com/docker/docker/client"
 
 	"github.com/stacklok/toolhive/pkg/container/runtime"
@@ -34,28 +36,73 @@ const (
 	DockerSocketPath = "/var/run/docker.sock"
 	// DockerDesktopMacSocketPath is the Docker Desktop socket path on macOS
 	DockerDesktopMacSocketPath = ".docker/run/docker.sock"
+	// PodmanMachineSocketPath is the socket path of a Podman machine, relative to the home directory
+	PodmanMachineSocketPath = ".local/share/containers/podman/machine/podman.sock"
+	// PodmanMachineAPISocketDir is the directory of the API sockets of Podman machines on macOS,
+	// relative to the temporary directory
+	PodmanMachineAPISocketDir = "podman"
+	// PodmanDefaultMachineAPISocket is the name of the API socket of the default Podman machine on macOS
+	PodmanDefaultMachineAPISocket = "podman-machine-default-api.sock"
+	// ColimaSocketPath is the socket path of the default Colima instance, relative to the home directory
+	ColimaSocketPath = ".colima/default/docker.sock"
+	// ColimaXDGSocketPath is the socket path of the default Colima instance when it uses the XDG config directory
+	ColimaXDGSocketPath = ".config/colima/default/docker.sock"
+	// RancherDesktopSocketPath is the Rancher Desktop socket path, relative to the home directory
+	RancherDesktopSocketPath = ".rd/docker.sock"
+	// LimaDockerSocketPath is the socket path of a Lima VM created from the docker template
+	LimaDockerSocketPath = ".lima/docker/sock/docker.sock"
+	// LimaPodmanSocketPath is the socket path of a Lima VM created from the podman template
+	LimaPodmanSocketPath = ".lima/podman/sock/podman.sock"
 )
 
 var supportedSocketPaths = []runtime.Type{runtime.TypePodman, runtime.TypeDocker}
 
-// NewDockerClient creates a new container client
+// NewDockerClient creates a new container client. If Docker or Podman is selected in TOOLHIVE_RUNTIME,
+// only the host and sockets of the selected runtime are used.
 func NewDockerClient(ctx context.Context) (*client.Client, string, runtime.Type, error) {
-	var lastErr error
+	selected := selectedRuntime()
+
+	// A host set in the environment is used instead of probing the well-known socket paths
+	if host, runtimeType, ok := hostFromEnv(selected); ok {
+		logger.Debugf("Using container runtime host from env: %s", host)
+		c, detectedType, err := newClientWithHost(ctx, host, runtimeType)
+		if err != nil {
+			return nil, "", "", err
+		}
+		if selected != "" && detectedType != selected {
+			return nil, "", "", fmt.Errorf("the %s runtime was selected, but %s is a %s host", selected, host, detectedType)
+		}
+		logger.Debugf("Successfully connected to %s runtime", detectedType)
+		return c, host, detectedType, nil
+	}
+
+	runtimeTypes := supportedSocketPaths
+	if selected != "" {
+		runtimeTypes = []runtime.Type{selected}
+	}
+
+	var errs []error
 
 	// We try to find a container socket for the given runtime
 	// We try Podman first, then Docker as fallback
-	for _, sp := range supportedSocketPaths {
+	for _, sp := range runtimeTypes {
 		// Try to find a container socket for the given runtime
 		socketPath, runtimeType, err := findContainerSocket(sp)
 		if err != nil {
 			logger.Debugf("Failed to find socket for %s: %v", sp, err)
-			lastErr = err
+			errs = append(errs, fmt.Errorf("%s: %w", sp, err))
+			continue
+		}
+		if selected != "" && runtimeType != selected {
+			// The socket was set in the environment for the other runtime
+			errs = append(errs, fmt.Errorf("the %s runtime was selected, but the %s socket %s is set",
+				selected, runtimeType, socketPath))
 			continue
 		}
 
 		c, err := newClientWithSocketPath(ctx, socketPath)
 		if err != nil {
-			lastErr = err
+			errs = append(errs, fmt.Errorf("%s: %w", sp, err))
 			logger.Debugf("Failed to create client for %s: %v", sp, err)
 			continue
 		}
@@ -64,12 +111,26 @@ func NewDockerClient(ctx context.Context) (*client.Client, string, runtime.Type,
 		return c, socketPath, runtimeType, nil
 	}
 
-	if lastErr != nil {
-		return nil, "", "", fmt.Errorf("no supported container runtime available: %w", lastErr)
+	if len(errs) > 0 {
+		// The errors of all runtimes are reported, so the user can see every socket which was tried
+		return nil, "", "", fmt.Errorf("no supported container runtime available: %w", errors.Join(errs...))
 	}
 	return nil, "", "", fmt.Errorf("no supported container runtime found/running")
 }
 
+// selectedRuntime returns the runtime selected in TOOLHIVE_RUNTIME if it is Docker or Podman,
+// or an empty type if the runtime is detected
+func selectedRuntime() runtime.Type {
+	switch selected := runtime.Selected(); selected {
+	case runtime.TypeDocker, runtime.TypePodman:
+		return selected
+	case runtime.TypeKubernetes, runtime.TypeApple, runtime.TypeContainerd, runtime.TypeFinch:
+		return ""
+	default:
+		return ""
+	}
+}
+
 // NewClientWithSocketPath creates a new container client with a specific socket path
 func newClientWithSocketPath(ctx context.Context, socketPath string) (*client.Client, error) {
 	// Create platform-specific client
@@ -82,14 +143,24 @@ func newClientWithSocketPath(ctx context.Context, socketPath string) (*client.Cl
 	}
 
 	// Make sure we can ping the server.
-	_, err = dockerClient.Ping(ctx)
+	ping, err := dockerClient.Ping(ctx)
 	if err != nil {
 		return nil, fmt.Errorf("failed to ping Docker server at %s: %w", socketPath, err)
 	}
+	negotiateAPIVersion(dockerClient, ping)
 
 	return dockerClient, nil
 }
 
+// negotiateAPIVersion sets the API version of a client to the highest version supported by both the client
+// and the daemon it pinged, since older daemons reject the requests of newer API versions. The version set
+// in DOCKER_API_VERSION, if any, is used as is.
+func negotiateAPIVersion(dockerClient *client.Client, ping types.Ping) {
+	dockerClient.NegotiateAPIVersionPing(ping)
+	logger.Debugf("Using API version %s of the container runtime (daemon API version %s)",
+		dockerClient.ClientVersion(), ping.APIVersion)
+}
+
 // findContainerSocket finds a container socket path, preferring Podman over Docker
 func findContainerSocket(rt runtime.Type) (string, runtime.Type, error) {
 	// Use platform-specific implementation
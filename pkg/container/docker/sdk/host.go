@@ -0,0 +1,100 @@
+package sdk
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"strings"
+
+	"github.com/docker/docker/client"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+const (
+	// DockerHostEnv is the environment variable for the Docker daemon host, e.g. tcp://host:2376
+	DockerHostEnv = "DOCKER_HOST"
+	// ContainerHostEnv is the environment variable for the Podman service host, e.g. ssh://user@host
+	ContainerHostEnv = "CONTAINER_HOST"
+)
+
+// sshDialHost is the placeholder host of clients which connect over SSH, since their dialer ignores it
+const sshDialHost = "http://docker.example.com"
+
+// hostFromEnv returns the host of the container runtime set in the environment, and its runtime type.
+// CONTAINER_HOST is preferred over DOCKER_HOST, like Podman sockets are preferred over Docker sockets,
+// and both are ignored if a socket is set for toolhive itself. If a runtime is selected, only the
+// variable of the selected runtime is used.
+func hostFromEnv(selected runtime.Type) (string, runtime.Type, bool) {
+	if os.Getenv(PodmanSocketEnv) != "" || os.Getenv(DockerSocketEnv) != "" {
+		return "", "", false
+	}
+	if host := os.Getenv(ContainerHostEnv); host != "" && selected != runtime.TypeDocker {
+		return host, runtime.TypePodman, true
+	}
+	if host := os.Getenv(DockerHostEnv); host != "" && selected != runtime.TypePodman {
+		return host, runtime.TypeDocker, true
+	}
+	return "", "", false
+}
+
+// UsesSSHHost returns true if the container runtime is connected to over SSH, so the ports published by
+// workloads are on the remote host, and only their standard input and output can be attached to
+func UsesSSHHost() bool {
+	host, _, ok := hostFromEnv(selectedRuntime())
+	return ok && strings.HasPrefix(host, "ssh://")
+}
+
+// hostClientOpts returns the options of a client connecting to the given host. tcp://, unix:// and npipe://
+// hosts are supported by the Docker SDK, with TLS configured by DOCKER_TLS_VERIFY and DOCKER_CERT_PATH,
+// and ssh:// hosts are connected to over SSH. The API version is negotiated with the daemon, unless it is
+// set in DOCKER_API_VERSION.
+func hostClientOpts(host string, rt runtime.Type) ([]client.Opt, error) {
+	opts := []client.Opt{client.WithAPIVersionNegotiation(), client.WithVersionFromEnv()}
+	if strings.HasPrefix(host, "ssh://") {
+		dialer, err := sshDialer(host, rt)
+		if err != nil {
+			return nil, err
+		}
+		return append(opts, client.WithHost(sshDialHost), client.WithDialContext(dialer)), nil
+	}
+	return append(opts, client.WithHost(host), client.WithTLSClientConfigFromEnv()), nil
+}
+
+// newClientWithHost creates a new container client for the given host, and detects whether it is
+// a Podman or a Docker host
+func newClientWithHost(ctx context.Context, host string, rt runtime.Type) (*client.Client, runtime.Type, error) {
+	opts, err := hostClientOpts(host, rt)
+	if err != nil {
+		return nil, "", err
+	}
+	dockerClient, err := client.NewClientWithOpts(opts...)
+	if err != nil {
+		return nil, "", fmt.Errorf("failed to create client for %s: %w", host, err)
+	}
+
+	ping, err := dockerClient.Ping(ctx)
+	if err != nil {
+		return nil, "", fmt.Errorf("failed to ping container runtime at %s: %w", host, err)
+	}
+	negotiateAPIVersion(dockerClient, ping)
+	return dockerClient, detectRuntimeType(ctx, dockerClient, rt), nil
+}
+
+// detectRuntimeType returns whether the daemon of a client is Podman or Docker, since either API can be
+// served on any host, e.g. DOCKER_HOST is often set to the Podman socket. It returns the given type if
+// the version of the daemon can't be determined.
+func detectRuntimeType(ctx context.Context, dockerClient *client.Client, rt runtime.Type) runtime.Type {
+	version, err := dockerClient.ServerVersion(ctx)
+	if err != nil {
+		logger.Debugf("Failed to get the version of the container runtime: %v", err)
+		return rt
+	}
+	for _, component := range version.Components {
+		if strings.Contains(strings.ToLower(component.Name), "podman") {
+			return runtime.TypePodman
+		}
+	}
+	return runtime.TypeDocker
+}
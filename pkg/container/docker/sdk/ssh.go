@@ -0,0 +1,160 @@
+package sdk
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"net"
+	"net/url"
+	"os/exec"
+	"time"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+// sshConnectTimeoutSeconds is the timeout of the SSH connection to a remote host
+const sshConnectTimeoutSeconds = 30
+
+// dialFunc connects to the API of a container runtime
+type dialFunc func(ctx context.Context, network, addr string) (net.Conn, error)
+
+// sshDialer returns a dialer which connects to the API of the container runtime of a remote host over SSH,
+// like the Docker CLI does for ssh:// hosts. The runtime CLI on the host proxies its API socket to the
+// standard input and output of the SSH session with system dial-stdio, so only SSH access is required.
+func sshDialer(host string, rt runtime.Type) (dialFunc, error) {
+	args, err := sshArgs(host, rt)
+	if err != nil {
+		return nil, err
+	}
+	return func(_ context.Context, _, _ string) (net.Conn, error) {
+		return newCommandConn("ssh", args...)
+	}, nil
+}
+
+// sshArgs returns the arguments of ssh to run system dial-stdio on the host of an ssh:// URL
+func sshArgs(host string, rt runtime.Type) ([]string, error) {
+	u, err := url.Parse(host)
+	if err != nil {
+		return nil, fmt.Errorf("invalid SSH host %s: %w", host, err)
+	}
+	if u.Scheme != "ssh" || u.Hostname() == "" {
+		return nil, fmt.Errorf("invalid SSH host %s (expected ssh://[user@]host[:port])", host)
+	}
+	if _, ok := u.User.Password(); ok {
+		return nil, fmt.Errorf("invalid SSH host %s: passwords are not supported, use an SSH key or agent instead", host)
+	}
+	if u.Path != "" && u.Path != "/" {
+		// Podman URLs include the socket on the host, which the dial-stdio command finds by itself
+		logger.Debugf("Ignoring socket path %s of SSH host, using the default socket of %s", u.Path, rt)
+	}
+
+	args := []string{"-o", fmt.Sprintf("ConnectTimeout=%d", sshConnectTimeoutSeconds), "-T"}
+	if u.User != nil && u.User.Username() != "" {
+		args = append(args, "-l", u.User.Username())
+	}
+	if port := u.Port(); port != "" {
+		args = append(args, "-p", port)
+	}
+
+	cli := "docker"
+	if rt == runtime.TypePodman {
+		cli = "podman"
+	}
+	return append(args, "--", u.Hostname(), cli, "system", "dial-stdio"), nil
+}
+
+// commandConn is a connection to the standard input and output of a command
+type commandConn struct {
+	cmd    *exec.Cmd
+	stdin  io.WriteCloser
+	stdout io.ReadCloser
+	stderr *logger.LineWriter
+}
+
+// newCommandConn starts a command and returns a connection to its standard input and output.
+// The command is not bound to a context, since it runs as long as the connection is open.
+func newCommandConn(name string, args ...string) (net.Conn, error) {
+	// #nosec G204 - the arguments are built from the host of the container runtime configured by the user
+	cmd := exec.Command(name, args...)
+	stdin, err := cmd.StdinPipe()
+	if err != nil {
+		return nil, err
+	}
+	stdout, err := cmd.StdoutPipe()
+	if err != nil {
+		return nil, err
+	}
+	stderr := logger.NewLineWriter(logger.Debugf, fmt.Sprintf("[%s] ", name))
+	cmd.Stderr = stderr
+
+	if err := cmd.Start(); err != nil {
+		return nil, fmt.Errorf("failed to run %s: %w", name, err)
+	}
+	return &commandConn{cmd: cmd, stdin: stdin, stdout: stdout, stderr: stderr}, nil
+}
+
+// Read reads from the standard output of the command
+func (c *commandConn) Read(p []byte) (int, error) {
+	return c.stdout.Read(p)
+}
+
+// Write writes to the standard input of the command
+func (c *commandConn) Write(p []byte) (int, error) {
+	return c.stdin.Write(p)
+}
+
+// CloseWrite closes the standard input of the command, e.g. once the input of an attached container ends
+func (c *commandConn) CloseWrite() error {
+	return c.stdin.Close()
+}
+
+// Close stops the command
+func (c *commandConn) Close() error {
+	_ = c.stdin.Close()
+	if c.cmd.Process != nil {
+		_ = c.cmd.Process.Kill()
+	}
+	// The command was killed, so its exit status is not an error of the connection
+	_ = c.cmd.Wait()
+	c.stderr.Flush()
+	return nil
+}
+
+// LocalAddr implements net.Conn
+func (*commandConn) LocalAddr() net.Addr {
+	return commandAddr{}
+}
+
+// RemoteAddr implements net.Conn
+func (*commandConn) RemoteAddr() net.Addr {
+	return commandAddr{}
+}
+
+// SetDeadline implements net.Conn. Deadlines are not supported by pipes of commands.
+func (*commandConn) SetDeadline(_ time.Time) error {
+	return nil
+}
+
+// SetReadDeadline implements net.Conn
+func (*commandConn) SetReadDeadline(_ time.Time) error {
+	return nil
+}
+
+// SetWriteDeadline implements net.Conn
+func (*commandConn) SetWriteDeadline(_ time.Time) error {
+	return nil
+}
+
+// commandAddr is the address of a command connection
+type commandAddr struct{}
+
+// Network implements net.Addr
+func (commandAddr) Network() string {
+	return "command"
+}
+
+// String implements net.Addr
+func (commandAddr) String() string {
+	return "command"
+}
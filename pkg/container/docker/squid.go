@@ -1,11 +1,14 @@
 package docker
 
 import (
+	"bytes"
 	"context"
 	"fmt"
+	"io"
 	"os"
 	"strconv"
 	"strings"
+	"time"
 
 	"github.com/docker/docker/api/types/container"
 	"github.com/docker/docker/api/types/network"
@@ -18,6 +21,9 @@ import (
 
 const defaultSquidImage = "ghcr.io/stacklok/toolhive/egress-proxy:latest"
 
+// squidConfTarget is the path of the squid configuration in the squid containers
+const squidConfTarget = "/etc/squid/squid.conf"
+
 // createIngressSquidContainer creates an instance of the squid proxy for ingress traffic.
 func createIngressSquidContainer(
 	ctx context.Context,
@@ -57,9 +63,9 @@ func createEgressSquidContainer(
 	attachStdio bool,
 	exposedPorts map[string]struct{},
 	endpointsConfig map[string]*network.EndpointSettings,
-	perm *permissions.NetworkPermissions,
+	profile *permissions.Profile,
 ) (string, error) {
-	squidConfPath, err := createTempSquidConf(perm, containerName)
+	squidConfPath, err := createTempSquidConf(profile.Network, profile.ActiveGrants(time.Now()), containerName)
 	if err != nil {
 		return "", fmt.Errorf("failed to create temporary squid.conf: %v", err)
 	}
@@ -123,7 +129,7 @@ func createSquidContainer(
 	mounts := []runtime.Mount{}
 	mounts = append(mounts, runtime.Mount{
 		Source:   squidConfPath,
-		Target:   "/etc/squid/squid.conf",
+		Target:   squidConfTarget,
 		ReadOnly: true,
 	})
 
@@ -162,8 +168,33 @@ func createSquidContainer(
 
 func createTempSquidConf(
 	networkPermissions *permissions.NetworkPermissions,
+	grants []permissions.Grant,
 	serverHostname string,
 ) (string, error) {
+	tmpFile, err := os.CreateTemp("", "squid-*.conf")
+	if err != nil {
+		return "", err
+	}
+	defer tmpFile.Close()
+
+	if _, err := tmpFile.WriteString(egressSquidConf(networkPermissions, grants, serverHostname)); err != nil {
+		return "", fmt.Errorf("failed to write to temporary file: %v", err)
+	}
+
+	// Set file permissions to be readable by all users (including squid user in container)
+	if err := tmpFile.Chmod(0644); err != nil {
+		return "", fmt.Errorf("failed to set file permissions: %v", err)
+	}
+
+	return tmpFile.Name(), nil
+}
+
+// egressSquidConf returns the squid configuration enforcing the network permissions and the grants
+func egressSquidConf(
+	networkPermissions *permissions.NetworkPermissions,
+	grants []permissions.Grant,
+	serverHostname string,
+) string {
 	var sb strings.Builder
 
 	sb.WriteString(
@@ -185,26 +216,79 @@ func createTempSquidConf(
 	} else {
 		writeOutboundACLs(&sb, networkPermissions.Outbound)
 		writeHttpAccessRules(&sb, networkPermissions.Outbound)
+		writeGrantRules(&sb, grants)
 	}
 
 	sb.WriteString("http_access deny all\n")
+	return sb.String()
+}
 
-	tmpFile, err := os.CreateTemp("", "squid-*.conf")
-	if err != nil {
-		return "", err
+// writeGrantRules allows the traffic of each temporary grant with a separate rule,
+// so a grant extends the permissions of the profile without restricting them
+func writeGrantRules(sb *strings.Builder, grants []permissions.Grant) {
+	for i, grant := range grants {
+		fmt.Fprintf(sb, "\n# Temporary %s\n", grant.String())
+		var conditions []string
+		if len(grant.AllowHost) > 0 {
+			name := fmt.Sprintf("grant_%d_dsts", i)
+			sb.WriteString("acl " + name + " dstdomain " + strings.Join(grant.AllowHost, " ") + "\n")
+			conditions = append(conditions, name)
+		}
+		if len(grant.AllowPort) > 0 {
+			name := fmt.Sprintf("grant_%d_ports", i)
+			sb.WriteString("acl " + name + " port")
+			for _, port := range grant.AllowPort {
+				sb.WriteString(" " + strconv.Itoa(port))
+			}
+			sb.WriteString("\n")
+			conditions = append(conditions, name)
+		}
+		if len(conditions) > 0 {
+			sb.WriteString("http_access allow " + strings.Join(conditions, " ") + "\n")
+		}
 	}
-	defer tmpFile.Close()
+}
 
-	if _, err := tmpFile.WriteString(sb.String()); err != nil {
-		return "", fmt.Errorf("failed to write to temporary file: %v", err)
+// reconfigureEgressSquidContainer rewrites the configuration of a running egress squid container
+// to enforce the network permissions and the active grants of the profile, and makes squid reload it
+func reconfigureEgressSquidContainer(
+	ctx context.Context,
+	c *Client,
+	containerName string,
+	squidContainerId string,
+	profile *permissions.Profile,
+) error {
+	info, err := c.client.ContainerInspect(ctx, squidContainerId)
+	if err != nil {
+		return NewContainerError(err, squidContainerId, fmt.Sprintf("failed to inspect egress container: %v", err))
+	}
+	squidConfPath := ""
+	for _, m := range info.Mounts {
+		if m.Destination == squidConfTarget {
+			squidConfPath = m.Source
+		}
+	}
+	if squidConfPath == "" {
+		return fmt.Errorf("egress container %s has no squid configuration mounted", squidContainerId)
 	}
 
-	// Set file permissions to be readable by all users (including squid user in container)
-	if err := tmpFile.Chmod(0644); err != nil {
-		return "", fmt.Errorf("failed to set file permissions: %v", err)
+	// The file is bind mounted into the container, so it is rewritten in place rather than replaced
+	conf := egressSquidConf(profile.Network, profile.ActiveGrants(time.Now()), containerName)
+	// #nosec G306 - The configuration must be readable by the squid user in the container
+	if err := os.WriteFile(squidConfPath, []byte(conf), 0644); err != nil {
+		return fmt.Errorf("failed to write squid configuration: %v", err)
 	}
 
-	return tmpFile.Name(), nil
+	var stderr bytes.Buffer
+	exitCode, err := c.ExecInWorkload(ctx, squidContainerId, []string{"squid", "-k", "reconfigure"},
+		&runtime.ExecOptions{Stdout: io.Discard, Stderr: &stderr})
+	if err != nil {
+		return err
+	}
+	if exitCode != 0 {
+		return fmt.Errorf("failed to reconfigure egress proxy (exit code %d): %s", exitCode, strings.TrimSpace(stderr.String()))
+	}
+	return nil
 }
 
 func writeOutboundACLs(sb *strings.Builder, outbound *permissions.OutboundNetworkPermissions) {
@@ -252,6 +336,12 @@ func writeHttpAccessRules(sb *strings.Builder, outbound *permissions.OutboundNet
 	}
 }
 
+// NetworkIsolationImages returns the images of the DNS and egress proxy containers of servers with an
+// isolated network
+func NetworkIsolationImages() []string {
+	return []string{DnsImage, getSquidImage()}
+}
+
 func getSquidImage() string {
 	if egressImage := os.Getenv("TOOLHIVE_EGRESS_IMAGE"); egressImage != "" {
 		return egressImage
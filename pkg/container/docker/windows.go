@@ -0,0 +1,113 @@
+package docker
+
+import (
+	"context"
+	"fmt"
+	"strings"
+
+	"github.com/docker/docker/api/types/container"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+// osTypeWindows is the OS type reported by a daemon in Windows containers mode
+const osTypeWindows = "windows"
+
+// windowsSystemDrive is the drive on which paths without a drive letter are mounted in Windows containers
+const windowsSystemDrive = "C:"
+
+// ErrWindowsNetworkIsolation is returned when network isolation is requested for a Windows container
+var ErrWindowsNetworkIsolation = fmt.Errorf(
+	"network isolation is not supported for Windows containers, since the egress and DNS containers are Linux images")
+
+// daemonPlatform describes the platform of the containers run by the daemon
+type daemonPlatform struct {
+	// osType is the OS of the containers, linux or windows
+	osType string
+	// kernelVersion is the kernel version of the daemon's host
+	kernelVersion string
+	// rootless is true if the daemon runs without root privileges
+	rootless bool
+}
+
+// isWindows returns true if the daemon runs Windows containers
+func (p daemonPlatform) isWindows() bool {
+	return p.osType == osTypeWindows
+}
+
+// networkDriver returns the driver of the networks created for workloads
+func (p daemonPlatform) networkDriver() string {
+	if p.isWindows() {
+		return "nat"
+	}
+	return "bridge"
+}
+
+// getDaemonPlatform returns the platform of the daemon, assuming Linux containers if it can't be determined
+func (c *Client) getDaemonPlatform(ctx context.Context) daemonPlatform {
+	info, err := c.client.Info(ctx)
+	if err != nil {
+		logger.Debugf("Failed to get daemon info, assuming Linux containers: %v", err)
+		return daemonPlatform{osType: "linux"}
+	}
+	return daemonPlatform{
+		osType:        info.OSType,
+		kernelVersion: info.KernelVersion,
+		rootless:      isRootless(info.SecurityOptions),
+	}
+}
+
+// adaptPermissionConfigForWindows adapts the permission config of a workload to Windows containers,
+// which have no Linux capabilities or security options, use Windows paths, and need an isolation
+// mode matching the OS version of the image
+func (c *Client) adaptPermissionConfigForWindows(
+	ctx context.Context,
+	config *runtime.PermissionConfig,
+	image string,
+	platform daemonPlatform,
+) {
+	config.CapDrop = nil
+	config.CapAdd = nil
+	config.SecurityOpt = nil
+
+	for i, m := range config.Mounts {
+		config.Mounts[i].Target = windowsContainerPath(m.Target)
+	}
+
+	imageInfo, err := c.client.ImageInspect(ctx, image)
+	if err != nil {
+		logger.Debugf("Failed to inspect image %s, using the default isolation: %v", image, err)
+		return
+	}
+	config.Isolation = string(windowsIsolation(platform.kernelVersion, imageInfo.OsVersion))
+}
+
+// windowsIsolation returns the isolation mode for a Windows container. Process isolation requires the
+// image to be built for the same Windows build as the host, otherwise the container runs in Hyper-V.
+// The kernel version of the host is e.g. "10.0 20348 (20348.1.amd64fre.fe_release.210507-1500)",
+// and the OS version of the image e.g. "10.0.20348.2227".
+func windowsIsolation(hostKernelVersion, imageOSVersion string) container.Isolation {
+	hostFields := strings.Fields(hostKernelVersion)
+	imageFields := strings.Split(imageOSVersion, ".")
+	if len(hostFields) < 2 || len(imageFields) < 3 {
+		return container.IsolationDefault
+	}
+	if hostFields[1] == imageFields[2] {
+		return container.IsolationProcess
+	}
+	return container.IsolationHyperV
+}
+
+// windowsContainerPath converts a container path to a Windows path, mounting paths without
+// a drive letter, e.g. /data, on the system drive
+func windowsContainerPath(path string) string {
+	if len(path) >= 2 && path[1] == ':' {
+		return path
+	}
+	path = strings.ReplaceAll(path, "/", `\`)
+	if !strings.HasPrefix(path, `\`) {
+		path = `\` + path
+	}
+	return windowsSystemDrive + path
+}
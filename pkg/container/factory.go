@@ -4,13 +4,24 @@ package container
 
 import (
 	"context"
+	"fmt"
 	"os"
 
+	"github.com/stacklok/toolhive/pkg/container/apple"
+	"github.com/stacklok/toolhive/pkg/container/containerd"
 	"github.com/stacklok/toolhive/pkg/container/docker"
+	"github.com/stacklok/toolhive/pkg/container/finch"
 	"github.com/stacklok/toolhive/pkg/container/kubernetes"
 	"github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/logger"
 )
 
+// RuntimeEnv is the environment variable selecting the container runtime instead of detecting it
+const RuntimeEnv = runtime.SelectionEnv
+
+// typeKubernetesShort is the short name accepted for the Kubernetes runtime
+const typeKubernetesShort = "k8s"
+
 // Factory creates container runtimes
 type Factory struct{}
 
@@ -19,16 +30,62 @@ func NewFactory() *Factory {
 	return &Factory{}
 }
 
-// Create creates a container runtime
+// Create creates a container runtime. If neither Docker nor Podman is available, the Apple container
+// runtime or Finch is used on macOS, Finch on Windows, and containerd on hosts which only run containerd,
+// like k3s nodes.
 func (*Factory) Create(ctx context.Context) (runtime.Runtime, error) {
+	switch selected := runtime.Selected(); selected {
+	case "", "auto":
+		// detect the runtime
+	case runtime.TypeDocker, runtime.TypePodman:
+		// the socket of the selected runtime is used, even if the socket of the other one exists
+		return createDockerRuntime(ctx)
+	case runtime.TypeApple:
+		return createAppleRuntime(ctx)
+	case runtime.TypeContainerd:
+		return createContainerdRuntime(ctx)
+	case runtime.TypeFinch:
+		return createFinchRuntime(ctx)
+	case runtime.TypeKubernetes, typeKubernetesShort:
+		return createKubernetesRuntime(ctx)
+	default:
+		return nil, fmt.Errorf("unsupported container runtime %q set in %s", selected, RuntimeEnv)
+	}
+
 	if !IsKubernetesRuntime() {
 		client, err := docker.NewClient(ctx)
 		if err != nil {
+			if apple.IsAvailable(ctx) {
+				logger.Debugf("No Docker or Podman runtime found, using the Apple container runtime: %v", err)
+				return createAppleRuntime(ctx)
+			}
+			if finch.IsAvailable(ctx) {
+				logger.Debugf("No Docker or Podman runtime found, using Finch: %v", err)
+				return createFinchRuntime(ctx)
+			}
+			if containerd.IsAvailable(ctx) {
+				logger.Debugf("No Docker or Podman runtime found, using the containerd runtime: %v", err)
+				return createContainerdRuntime(ctx)
+			}
 			return nil, err
 		}
 		return client, nil
 	}
 
+	return createKubernetesRuntime(ctx)
+}
+
+// createDockerRuntime creates a client for the Docker or Podman runtime
+func createDockerRuntime(ctx context.Context) (runtime.Runtime, error) {
+	client, err := docker.NewClient(ctx)
+	if err != nil {
+		return nil, err
+	}
+	return client, nil
+}
+
+// createKubernetesRuntime creates a client for the Kubernetes runtime
+func createKubernetesRuntime(ctx context.Context) (runtime.Runtime, error) {
 	client, err := kubernetes.NewClient(ctx)
 	if err != nil {
 		return nil, err
@@ -37,13 +94,55 @@ func (*Factory) Create(ctx context.Context) (runtime.Runtime, error) {
 	return client, nil
 }
 
+// createAppleRuntime creates a client for the Apple container runtime
+func createAppleRuntime(ctx context.Context) (runtime.Runtime, error) {
+	client, err := apple.NewClient(ctx)
+	if err != nil {
+		return nil, err
+	}
+	return client, nil
+}
+
+// createContainerdRuntime creates a client for the containerd runtime
+func createContainerdRuntime(ctx context.Context) (runtime.Runtime, error) {
+	client, err := containerd.NewClient(ctx)
+	if err != nil {
+		return nil, err
+	}
+	return client, nil
+}
+
+// createFinchRuntime creates a client for Finch
+func createFinchRuntime(ctx context.Context) (runtime.Runtime, error) {
+	client, err := finch.NewClient(ctx)
+	if err != nil {
+		return nil, err
+	}
+	return client, nil
+}
+
 // NewMonitor creates a new container monitor
 func NewMonitor(rt runtime.Runtime, containerID, containerName string) runtime.Monitor {
 	return docker.NewMonitor(rt, containerID, containerName)
 }
 
-// IsKubernetesRuntime returns true if the runtime is Kubernetes
+// NewSupervisor creates a new container monitor which restarts the container according to the restart policy
+func NewSupervisor(rt runtime.Runtime, containerID, containerName string, policy runtime.RestartPolicy) runtime.Monitor {
+	return docker.NewSupervisor(rt, containerID, containerName, policy)
+}
+
+// IsKubernetesRuntime returns true if the runtime is Kubernetes, either because it was selected
+// or because toolhive runs in a cluster.
 // isn't the best way to do this, but for now it's good enough
 func IsKubernetesRuntime() bool {
-	return os.Getenv("KUBERNETES_SERVICE_HOST") != ""
+	switch runtime.Selected() {
+	case runtime.TypeKubernetes, typeKubernetesShort:
+		return true
+	case "", "auto":
+		return os.Getenv("KUBERNETES_SERVICE_HOST") != ""
+	case runtime.TypeDocker, runtime.TypePodman, runtime.TypeApple, runtime.TypeContainerd, runtime.TypeFinch:
+		return false
+	default:
+		return false
+	}
 }
@@ -0,0 +1,227 @@
+package finch
+
+import (
+	"fmt"
+	"path/filepath"
+	"sort"
+	"strconv"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/networking"
+	"github.com/stacklok/toolhive/pkg/permissions"
+)
+
+// workloadSpec is the configuration of a workload passed to finch run
+type workloadSpec struct {
+	name            string
+	image           string
+	command         []string
+	envVars         map[string]string
+	labels          map[string]string
+	mounts          []string
+	publish         []string
+	attachStdio     bool
+	noNewPrivileges bool
+	options         *runtime.DeployWorkloadOptions
+}
+
+// runArgs returns the arguments of finch to create and start a workload. Like with the Docker runtime,
+// all capabilities are dropped and the workload is restarted by its restart policy, which by default
+// restarts it unless it is stopped.
+func runArgs(spec *workloadSpec) []string {
+	restartPolicy := runtime.DefaultRestartPolicy
+	if spec.options != nil && spec.options.RestartPolicy != "" {
+		restartPolicy = spec.options.RestartPolicy
+	}
+	args := []string{"run", "--detach", "--name", spec.name, "--restart", restartPolicy, "--cap-drop", "ALL"}
+	if spec.noNewPrivileges {
+		args = append(args, "--security-opt", "no-new-privileges")
+	}
+	if spec.attachStdio {
+		args = append(args, "--interactive")
+	}
+
+	for _, key := range sortedKeys(spec.envVars) {
+		args = append(args, "--env", key+"="+spec.envVars[key])
+	}
+	for _, key := range sortedKeys(spec.labels) {
+		args = append(args, "--label", key+"="+spec.labels[key])
+	}
+	for _, m := range spec.mounts {
+		args = append(args, "--mount", m)
+	}
+	for _, p := range spec.publish {
+		args = append(args, "--publish", p)
+	}
+
+	args = append(args, optionArgs(spec.options)...)
+	args = append(args, spec.image)
+	return append(args, spec.command...)
+}
+
+// optionArgs returns the arguments of a workload for its deployment options: logging, stopping, resource
+// limits, GPUs, devices and networks.
+func optionArgs(options *runtime.DeployWorkloadOptions) []string {
+	if options == nil {
+		return nil
+	}
+	var args []string
+	if options.LogConfig != nil && options.LogConfig.Driver != "" {
+		args = append(args, "--log-driver", options.LogConfig.Driver)
+		for _, key := range sortedKeys(options.LogConfig.Options) {
+			args = append(args, "--log-opt", key+"="+options.LogConfig.Options[key])
+		}
+	}
+	if options.StopSignal != "" {
+		args = append(args, "--stop-signal", options.StopSignal)
+	}
+	if options.StopTimeout > 0 {
+		args = append(args, "--stop-timeout", strconv.Itoa(int(options.StopTimeout.Seconds())))
+	}
+	args = append(args, resourceArgs(options.Resources)...)
+	if options.GPUs != "" {
+		args = append(args, "--gpus", options.GPUs)
+	}
+	for _, device := range options.Devices {
+		args = append(args, "--device", device)
+	}
+	for _, name := range options.Networks {
+		args = append(args, "--network", name)
+	}
+	return args
+}
+
+// resourceArgs returns the resource limit arguments of a workload.
+func resourceArgs(resources *runtime.ResourceLimits) []string {
+	if resources == nil {
+		return nil
+	}
+	var args []string
+	if resources.CPUs > 0 {
+		args = append(args, "--cpus", strconv.FormatFloat(resources.CPUs, 'f', -1, 64))
+	}
+	if resources.Memory > 0 {
+		args = append(args, "--memory", strconv.FormatInt(resources.Memory, 10))
+	}
+	if resources.MemorySwap != 0 {
+		args = append(args, "--memory-swap", strconv.FormatInt(resources.MemorySwap, 10))
+	}
+	if resources.PidsLimit > 0 {
+		args = append(args, "--pids-limit", strconv.FormatInt(resources.PidsLimit, 10))
+	}
+	return args
+}
+
+// publishArgs returns the port publishing specifications of a workload, and the host port of its first
+// port. Auxiliary workloads, like the inspector, are published on the host port they request, and the
+// others on a random available port.
+func publishArgs(labels map[string]string, portBindings map[string][]runtime.PortBinding) ([]string, int, error) {
+	auxiliary := labels["toolhive-auxiliary"] == "true"
+
+	var publish []string
+	hostPort := 0
+	for _, containerPort := range sortedKeys(portBindings) {
+		bindings := portBindings[containerPort]
+		if len(bindings) == 0 {
+			continue
+		}
+		binding := bindings[0]
+		if hostPort == 0 {
+			if auxiliary {
+				port, err := strconv.Atoi(binding.HostPort)
+				if err != nil {
+					return nil, 0, fmt.Errorf("failed to convert host port %s to int: %v", binding.HostPort, err)
+				}
+				hostPort = port
+			} else {
+				hostPort = networking.FindAvailable()
+				if hostPort == 0 {
+					return nil, 0, fmt.Errorf("could not find an available port")
+				}
+				binding.HostPort = strconv.Itoa(hostPort)
+			}
+		}
+
+		spec := binding.HostPort + ":" + containerPort
+		if binding.HostIP != "" {
+			spec = binding.HostIP + ":" + spec
+		}
+		publish = append(publish, spec)
+	}
+	return publish, hostPort, nil
+}
+
+// mountArgs returns the mount specifications of a permission profile. Host paths are bind mounted,
+// and volume:// resources are mounted as named volumes.
+func mountArgs(profile *permissions.Profile) []string {
+	var mounts []string
+	add := func(declarations []permissions.MountDeclaration, readOnly bool) {
+		for _, declaration := range declarations {
+			spec, err := mountArg(declaration)
+			if err != nil {
+				logger.Warnf("Skipping mount %s: %v", declaration, err)
+				continue
+			}
+			if readOnly {
+				spec += ",readonly"
+			}
+			mounts = append(mounts, spec)
+		}
+	}
+	add(profile.Read, true)
+	add(profile.Write, false)
+	return mounts
+}
+
+// mountArg returns the mount specification of a mount declaration
+func mountArg(declaration permissions.MountDeclaration) (string, error) {
+	source, target, err := declaration.Parse()
+	if err != nil {
+		return "", err
+	}
+
+	if declaration.IsResourceURI() {
+		resourceType, err := declaration.GetResourceType()
+		if err != nil {
+			return "", err
+		}
+		if resourceType != "volume" {
+			return "", fmt.Errorf("resource type %s is not supported by the containerd runtime", resourceType)
+		}
+		volume := source[len(resourceType)+len("://"):]
+		return fmt.Sprintf("type=volume,source=%s,target=%s", volume, target), nil
+	}
+
+	if !filepath.IsAbs(source) {
+		source, err = filepath.Abs(source)
+		if err != nil {
+			return "", err
+		}
+	}
+	return fmt.Sprintf("type=bind,source=%s,target=%s", source, target), nil
+}
+
+// stopArgs returns the arguments of finch to stop a workload
+func stopArgs(workloadID string, options *runtime.StopOptions) []string {
+	args := []string{"stop"}
+	if options != nil {
+		if options.Signal != "" {
+			args = append(args, "--signal", options.Signal)
+		}
+		if options.Timeout > 0 {
+			args = append(args, "--time", strconv.Itoa(int(options.Timeout.Seconds())))
+		}
+	}
+	return append(args, workloadID)
+}
+
+// sortedKeys returns the keys of a map in order, so the arguments are deterministic
+func sortedKeys[V any](m map[string]V) []string {
+	keys := make([]string, 0, len(m))
+	for key := range m {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	return keys
+}
@@ -0,0 +1,464 @@
+// Package finch provides a container runtime for Finch, which runs containerd in a virtual machine on macOS
+// and Windows. The containerd socket of the virtual machine is not reachable from the host, so the workloads
+// are managed with the Finch CLI, which is compatible with nerdctl and reports containers in the same format
+// as the Docker API.
+package finch
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"path/filepath"
+	goruntime "runtime"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/docker/docker/api/types/container"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/permissions"
+)
+
+const (
+	// DefaultBinary is the name of the Finch CLI
+	DefaultBinary = "finch"
+	// BinaryEnv is the environment variable for a custom path of the Finch CLI
+	BinaryEnv = "TOOLHIVE_FINCH_BINARY"
+)
+
+var (
+	// ErrWorkloadNotFound is returned when a workload is not found
+	ErrWorkloadNotFound = errors.New("workload not found")
+
+	// ErrNetworkIsolationUnsupported is returned when network isolation is requested
+	ErrNetworkIsolationUnsupported = errors.New("network isolation is not supported by the Finch runtime")
+)
+
+// commandRunner runs the Finch CLI with the given arguments and returns its output
+type commandRunner func(ctx context.Context, args ...string) ([]byte, error)
+
+// Client implements the runtime.Runtime interface using the Finch CLI
+type Client struct {
+	binary string
+	run    commandRunner
+}
+
+// NewClient creates a new client for Finch, and checks that its virtual machine is running.
+// Like with Docker Desktop, only the host paths shared with the virtual machine can be mounted,
+// by default the home directory.
+func NewClient(ctx context.Context) (*Client, error) {
+	binary := DefaultBinary
+	if custom := os.Getenv(BinaryEnv); custom != "" {
+		binary = custom
+	}
+	path, err := exec.LookPath(binary)
+	if err != nil {
+		return nil, fmt.Errorf("finch not found: %w", err)
+	}
+
+	c := &Client{binary: path}
+	c.run = c.runCommand
+	if err := c.IsRunning(ctx); err != nil {
+		return nil, err
+	}
+	logger.Debugf("Using Finch at %s", path)
+	return c, nil
+}
+
+// IsAvailable returns true if Finch is installed on macOS or Windows and its virtual machine is running
+func IsAvailable(ctx context.Context) bool {
+	if goruntime.GOOS != "darwin" && goruntime.GOOS != "windows" {
+		return false
+	}
+	_, err := NewClient(ctx)
+	return err == nil
+}
+
+// command returns the Finch command with the given arguments
+func (c *Client) command(ctx context.Context, args ...string) *exec.Cmd {
+	// #nosec G204 - the arguments are built by toolhive, and the binary is the Finch CLI
+	return exec.CommandContext(ctx, c.binary, args...)
+}
+
+// runCommand runs the Finch CLI and returns its output, or an error including its error output
+func (c *Client) runCommand(ctx context.Context, args ...string) ([]byte, error) {
+	cmd := c.command(ctx, args...)
+	var stderr bytes.Buffer
+	cmd.Stderr = &stderr
+	output, err := cmd.Output()
+	if err != nil {
+		return nil, fmt.Errorf("%s %s failed: %w: %s", filepath.Base(c.binary), args[0], err, strings.TrimSpace(stderr.String()))
+	}
+	return output, nil
+}
+
+// inspect returns the Docker compatible description of the given containers
+func (c *Client) inspect(ctx context.Context, workloadIDs ...string) ([]container.InspectResponse, error) {
+	args := append([]string{"inspect", "--mode", "dockercompat", "--type", "container"}, workloadIDs...)
+	output, err := c.run(ctx, args...)
+	if err != nil {
+		if msg := strings.ToLower(err.Error()); strings.Contains(msg, "no such") || strings.Contains(msg, "not found") {
+			return nil, fmt.Errorf("%w: %s", ErrWorkloadNotFound, strings.Join(workloadIDs, ", "))
+		}
+		return nil, err
+	}
+
+	var infos []container.InspectResponse
+	if err := json.Unmarshal(output, &infos); err != nil {
+		return nil, fmt.Errorf("failed to parse finch inspect output: %w", err)
+	}
+	if len(infos) == 0 {
+		return nil, fmt.Errorf("%w: %s", ErrWorkloadNotFound, strings.Join(workloadIDs, ", "))
+	}
+	return infos, nil
+}
+
+// DeployWorkload creates and starts a workload
+func (c *Client) DeployWorkload(
+	ctx context.Context,
+	image,
+	name string,
+	command []string,
+	envVars,
+	workloadLabels map[string]string,
+	permissionProfile *permissions.Profile,
+	transportType string,
+	options *runtime.DeployWorkloadOptions,
+	isolateNetwork bool,
+) (string, int, error) {
+	if isolateNetwork {
+		return "", 0, ErrNetworkIsolationUnsupported
+	}
+	switch transportType {
+	case "sse", "stdio", "inspector", "streamable-http", "websocket":
+		// valid, do nothing
+	default:
+		return "", 0, fmt.Errorf("unsupported transport type: %s", transportType)
+	}
+	if options == nil {
+		options = runtime.NewDeployWorkloadOptions()
+	}
+	if _, err := runtime.ParseRestartPolicy(options.RestartPolicy); err != nil {
+		return "", 0, err
+	}
+
+	publish, hostPort, err := publishArgs(workloadLabels, options.PortBindings)
+	if err != nil {
+		return "", 0, fmt.Errorf("failed to generate port bindings: %v", err)
+	}
+
+	// A previous container of the workload is replaced
+	if err := c.RemoveWorkload(ctx, name); err != nil {
+		return "", 0, err
+	}
+
+	output, err := c.run(ctx, runArgs(&workloadSpec{
+		name:            name,
+		image:           image,
+		command:         command,
+		envVars:         envVars,
+		labels:          workloadLabels,
+		mounts:          mountArgs(permissionProfile),
+		publish:         publish,
+		attachStdio:     options.AttachStdio,
+		noNewPrivileges: permissionProfile.NoNewPrivileges,
+		options:         options,
+	})...)
+	if err != nil {
+		return "", 0, fmt.Errorf("failed to create container: %w", err)
+	}
+	containerID := strings.TrimSpace(string(output))
+
+	if transportType == "stdio" {
+		return containerID, 0, nil
+	}
+	return containerID, hostPort, nil
+}
+
+// ListWorkloads lists the workloads managed by toolhive
+func (c *Client) ListWorkloads(ctx context.Context) ([]runtime.ContainerInfo, error) {
+	output, err := c.run(ctx, "ps", "--all", "--quiet", "--no-trunc", "--filter", "label=toolhive=true")
+	if err != nil {
+		return nil, fmt.Errorf("failed to list containers: %w", err)
+	}
+	workloadIDs := strings.Fields(string(output))
+	if len(workloadIDs) == 0 {
+		return []runtime.ContainerInfo{}, nil
+	}
+
+	infos, err := c.inspect(ctx, workloadIDs...)
+	if err != nil {
+		return nil, fmt.Errorf("failed to inspect containers: %w", err)
+	}
+
+	result := make([]runtime.ContainerInfo, 0, len(infos))
+	for i := range infos {
+		info := toContainerInfo(&infos[i])
+		if !labels.IsToolHiveContainer(info.Labels) || info.Labels["toolhive-auxiliary-workload"] == "true" {
+			continue
+		}
+		result = append(result, info)
+	}
+	return result, nil
+}
+
+// StopWorkload stops a workload. If the workload is not running, it returns success.
+func (c *Client) StopWorkload(ctx context.Context, workloadID string, options *runtime.StopOptions) error {
+	running, err := c.IsWorkloadRunning(ctx, workloadID)
+	if err != nil {
+		if errors.Is(err, ErrWorkloadNotFound) {
+			return nil
+		}
+		return err
+	}
+	if !running {
+		return nil
+	}
+
+	if _, err := c.run(ctx, stopArgs(workloadID, options)...); err != nil {
+		return fmt.Errorf("failed to stop workload %s: %w", workloadID, err)
+	}
+	return nil
+}
+
+// PauseWorkload pauses a workload
+func (c *Client) PauseWorkload(ctx context.Context, workloadID string) error {
+	if _, err := c.run(ctx, "pause", workloadID); err != nil {
+		return fmt.Errorf("failed to pause workload %s: %w", workloadID, err)
+	}
+	return nil
+}
+
+// StartWorkload starts a stopped or exited workload
+func (c *Client) StartWorkload(ctx context.Context, workloadID string) error {
+	if _, err := c.run(ctx, "start", workloadID); err != nil {
+		return fmt.Errorf("failed to start workload %s: %w", workloadID, err)
+	}
+	return nil
+}
+
+// UnpauseWorkload unpauses a workload
+func (c *Client) UnpauseWorkload(ctx context.Context, workloadID string) error {
+	if _, err := c.run(ctx, "unpause", workloadID); err != nil {
+		return fmt.Errorf("failed to unpause workload %s: %w", workloadID, err)
+	}
+	return nil
+}
+
+// RemoveWorkload removes a workload. If the workload doesn't exist, it returns success.
+func (c *Client) RemoveWorkload(ctx context.Context, workloadID string) error {
+	if _, err := c.inspect(ctx, workloadID); err != nil {
+		if errors.Is(err, ErrWorkloadNotFound) {
+			return nil
+		}
+		return err
+	}
+
+	if _, err := c.run(ctx, "rm", "--force", workloadID); err != nil {
+		return fmt.Errorf("failed to remove workload %s: %w", workloadID, err)
+	}
+	return nil
+}
+
+// GetWorkloadLogs gets the logs of a workload. If options.Follow is true, the logs are streamed to stdout.
+func (c *Client) GetWorkloadLogs(ctx context.Context, workloadID string, options runtime.LogOptions) (string, error) {
+	args := []string{"logs"}
+	if options.Tail > 0 {
+		args = append(args, "--tail", strconv.Itoa(options.Tail))
+	}
+	if !options.Since.IsZero() {
+		args = append(args, "--since", options.Since.Format(time.RFC3339))
+	}
+
+	if options.Follow {
+		cmd := c.command(ctx, append(args, "--follow", workloadID)...)
+		cmd.Stdout = os.Stdout
+		cmd.Stderr = os.Stderr
+		if err := cmd.Run(); err != nil && ctx.Err() == nil {
+			return "", fmt.Errorf("failed to follow workload logs: %w", err)
+		}
+		return "", nil
+	}
+
+	// The output and error streams of the workload are both part of its logs
+	output, err := c.command(ctx, append(args, workloadID)...).CombinedOutput()
+	if err != nil {
+		return "", fmt.Errorf("failed to get workload logs: %w: %s", err, strings.TrimSpace(string(output)))
+	}
+	return string(output), nil
+}
+
+// StreamWorkloadLogs follows the logs of a workload written since the given time
+func (c *Client) StreamWorkloadLogs(
+	ctx context.Context,
+	workloadID string,
+	since time.Time,
+	stdout, stderr io.Writer,
+) error {
+	cmd := c.command(ctx, "logs", "--follow", "--since", since.Format(time.RFC3339), workloadID)
+	cmd.Stdout = stdout
+	cmd.Stderr = stderr
+	if err := cmd.Run(); err != nil && ctx.Err() == nil {
+		return fmt.Errorf("failed to stream workload logs: %w", err)
+	}
+	return nil
+}
+
+// IsWorkloadRunning checks if a workload is running
+func (c *Client) IsWorkloadRunning(ctx context.Context, workloadID string) (bool, error) {
+	infos, err := c.inspect(ctx, workloadID)
+	if err != nil {
+		return false, err
+	}
+	return infos[0].State != nil && infos[0].State.Running, nil
+}
+
+// GetWorkloadInfo gets workload information
+func (c *Client) GetWorkloadInfo(ctx context.Context, workloadID string) (runtime.ContainerInfo, error) {
+	infos, err := c.inspect(ctx, workloadID)
+	if err != nil {
+		return runtime.ContainerInfo{}, err
+	}
+	return toContainerInfo(&infos[0]), nil
+}
+
+// AttachToWorkload attaches to the input and output of a running workload
+func (c *Client) AttachToWorkload(ctx context.Context, workloadID string) (io.WriteCloser, io.ReadCloser, error) {
+	running, err := c.IsWorkloadRunning(ctx, workloadID)
+	if err != nil {
+		return nil, nil, err
+	}
+	if !running {
+		return nil, nil, fmt.Errorf("cannot attach to workload %s: it is not running", workloadID)
+	}
+
+	// The process is not bound to the context of the call, since it runs as long as the workload
+	cmd := c.command(context.WithoutCancel(ctx), "attach", workloadID)
+	stdin, err := cmd.StdinPipe()
+	if err != nil {
+		return nil, nil, fmt.Errorf("failed to attach to workload: %w", err)
+	}
+	stdout, err := cmd.StdoutPipe()
+	if err != nil {
+		return nil, nil, fmt.Errorf("failed to attach to workload: %w", err)
+	}
+
+	// Only stdout carries JSON-RPC messages; stderr is diagnostic output of the server, so it is logged
+	stderrWriter := logger.NewLineWriter(logger.Infof, fmt.Sprintf("[%s] ", workloadID))
+	cmd.Stderr = stderrWriter
+
+	if err := cmd.Start(); err != nil {
+		return nil, nil, fmt.Errorf("failed to attach to workload %s: %w", workloadID, err)
+	}
+	go func() {
+		defer stderrWriter.Flush()
+		if err := cmd.Wait(); err != nil {
+			logger.Debugf("Detached from workload %s: %v", workloadID, err)
+		}
+	}()
+
+	return stdin, stdout, nil
+}
+
+// ExecInWorkload runs a command in a workload and returns its exit code
+func (c *Client) ExecInWorkload(
+	ctx context.Context,
+	workloadID string,
+	command []string,
+	options *runtime.ExecOptions,
+) (int, error) {
+	args := []string{"exec"}
+	if options.Stdin != nil {
+		args = append(args, "--interactive")
+	}
+	if options.TTY {
+		args = append(args, "--tty")
+	}
+	args = append(args, workloadID)
+	args = append(args, command...)
+
+	cmd := c.command(ctx, args...)
+	cmd.Stdin = options.Stdin
+	cmd.Stdout = options.Stdout
+	cmd.Stderr = options.Stderr
+	if err := cmd.Run(); err != nil {
+		var exitErr *exec.ExitError
+		if errors.As(err, &exitErr) {
+			return exitErr.ExitCode(), nil
+		}
+		return 0, fmt.Errorf("failed to exec in workload %s: %w", workloadID, err)
+	}
+	return 0, nil
+}
+
+// UpdateNetworkPermissions implements runtime.Runtime.
+func (*Client) UpdateNetworkPermissions(_ context.Context, workloadName string, _ *permissions.Profile) error {
+	// Without network isolation there is no egress proxy which enforces the network permissions
+	logger.Debugf("Network permissions of %s are not enforced by the Finch runtime", workloadName)
+	return nil
+}
+
+// WorkloadEvents implements runtime.Runtime.
+func (*Client) WorkloadEvents(_ context.Context, _ *runtime.EventOptions) (<-chan runtime.WorkloadEvent, <-chan error) {
+	workloadEvents := make(chan runtime.WorkloadEvent)
+	close(workloadEvents)
+	errs := make(chan error, 1)
+	errs <- fmt.Errorf("workload events are not supported by the Finch runtime")
+	return workloadEvents, errs
+}
+
+// IsRunning checks that the Finch virtual machine is running
+func (c *Client) IsRunning(ctx context.Context) error {
+	if _, err := c.run(ctx, "info"); err != nil {
+		return fmt.Errorf("the Finch virtual machine is not running (start it with finch vm start): %w", err)
+	}
+	return nil
+}
+
+// toContainerInfo converts the Docker compatible description of a container to the runtime representation
+func toContainerInfo(info *container.InspectResponse) runtime.ContainerInfo {
+	result := runtime.ContainerInfo{}
+	if info.ContainerJSONBase != nil {
+		result.ID = info.ID
+		result.Name = strings.TrimPrefix(info.Name, "/")
+		result.ImageID = info.Image
+		result.RestartCount = info.RestartCount
+		result.Created, _ = time.Parse(time.RFC3339Nano, info.Created)
+		if info.State != nil {
+			result.Status = info.State.Status
+			result.State = info.State.Status
+			result.StartedAt, _ = time.Parse(time.RFC3339Nano, info.State.StartedAt)
+			result.ExitCode = info.State.ExitCode
+		}
+	}
+	if info.Config != nil {
+		result.Image = info.Config.Image
+		result.Labels = info.Config.Labels
+	}
+
+	result.Ports = []runtime.PortMapping{}
+	if info.NetworkSettings != nil {
+		for containerPort, bindings := range info.NetworkSettings.Ports {
+			for _, binding := range bindings {
+				hostPort := 0
+				if _, err := fmt.Sscanf(binding.HostPort, "%d", &hostPort); err != nil {
+					logger.Warnf("Warning: Failed to parse host port %s: %v", binding.HostPort, err)
+				}
+				result.Ports = append(result.Ports, runtime.PortMapping{
+					ContainerPort: containerPort.Int(),
+					HostPort:      hostPort,
+					Protocol:      containerPort.Proto(),
+				})
+			}
+		}
+	}
+	return result
+}
@@ -0,0 +1,238 @@
+package finch
+
+import (
+	"context"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/container/clitest"
+	"github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/permissions"
+)
+
+func init() {
+	// Initialize the logger for tests
+	logger.Initialize()
+}
+
+const inspectOutput = `[
+  {
+    "Id": "0123456789ab",
+    "Created": "2025-06-01T10:00:00.000000000Z",
+    "Name": "fetch",
+    "Image": "sha256:abc",
+    "RestartCount": 2,
+    "State": {"Status": "running", "Running": true, "StartedAt": "2025-06-01T10:00:01.000000000Z"},
+    "Config": {"Image": "docker.io/mcp/fetch:latest", "Labels": {"toolhive": "true", "toolhive-name": "fetch"}},
+    "NetworkSettings": {"Ports": {"8080/tcp": [{"HostIp": "0.0.0.0", "HostPort": "41234"}]}}
+  },
+  {
+    "Id": "456789abcdef",
+    "Created": "2025-06-01T09:00:00.000000000Z",
+    "Name": "inspector",
+    "Image": "sha256:def",
+    "State": {"Status": "exited", "Running": false},
+    "Config": {"Image": "ghcr.io/modelcontextprotocol/inspector:latest",
+      "Labels": {"toolhive": "true", "toolhive-auxiliary-workload": "true"}}
+  },
+  {
+    "Id": "89abcdef0123",
+    "Created": "2025-06-01T08:00:00.000000000Z",
+    "Name": "other",
+    "Image": "sha256:123",
+    "State": {"Status": "running", "Running": true},
+    "Config": {"Image": "docker.io/library/nginx:latest", "Labels": {}}
+  }
+]`
+
+// newTestClient returns a client running a fake CLI, which answers the commands with the given outputs
+func newTestClient(outputs map[string]string) (*Client, *clitest.CLI) {
+	cli := clitest.New(outputs)
+	return &Client{binary: DefaultBinary, run: cli.Run}, cli
+}
+
+func TestCommand(t *testing.T) {
+	t.Parallel()
+
+	finch := &Client{binary: "/usr/local/bin/finch"}
+	assert.Equal(t, []string{"/usr/local/bin/finch", "ps", "--all"}, finch.command(context.Background(), "ps", "--all").Args)
+}
+
+func TestListWorkloads(t *testing.T) {
+	t.Parallel()
+
+	client, cli := newTestClient(map[string]string{
+		"ps":      "0123456789ab\n456789abcdef\n89abcdef0123\n",
+		"inspect": inspectOutput,
+	})
+	workloads, err := client.ListWorkloads(context.Background())
+	require.NoError(t, err)
+	require.Len(t, workloads, 1)
+	assert.Equal(t, []string{"inspect", "--mode", "dockercompat", "--type", "container",
+		"0123456789ab", "456789abcdef", "89abcdef0123"}, cli.Calls()[1])
+
+	assert.Equal(t, "0123456789ab", workloads[0].ID)
+	assert.Equal(t, "fetch", workloads[0].Name)
+	assert.Equal(t, "docker.io/mcp/fetch:latest", workloads[0].Image)
+	assert.Equal(t, "sha256:abc", workloads[0].ImageID)
+	assert.Equal(t, "running", workloads[0].State)
+	assert.Equal(t, 2, workloads[0].RestartCount)
+	assert.Equal(t, time.Date(2025, 6, 1, 10, 0, 1, 0, time.UTC), workloads[0].StartedAt)
+	assert.Equal(t, []runtime.PortMapping{{ContainerPort: 8080, HostPort: 41234, Protocol: "tcp"}}, workloads[0].Ports)
+
+	// Without toolhive containers, nothing is inspected
+	client, cli = newTestClient(map[string]string{"ps": ""})
+	workloads, err = client.ListWorkloads(context.Background())
+	require.NoError(t, err)
+	assert.Empty(t, workloads)
+	assert.Len(t, cli.Calls(), 1)
+}
+
+func TestIsWorkloadRunning(t *testing.T) {
+	t.Parallel()
+
+	client, _ := newTestClient(map[string]string{"inspect": inspectOutput})
+	running, err := client.IsWorkloadRunning(context.Background(), "fetch")
+	require.NoError(t, err)
+	assert.True(t, running)
+
+	client, _ = newTestClient(map[string]string{"inspect": "error: no such container: missing"})
+	_, err = client.IsWorkloadRunning(context.Background(), "missing")
+	assert.ErrorIs(t, err, ErrWorkloadNotFound)
+}
+
+func TestStopWorkload(t *testing.T) {
+	t.Parallel()
+
+	client, cli := newTestClient(map[string]string{
+		"inspect": inspectOutput,
+		"stop":    "fetch",
+	})
+	err := client.StopWorkload(context.Background(), "fetch", &runtime.StopOptions{Timeout: 30 * time.Second, Signal: "SIGINT"})
+	require.NoError(t, err)
+	assert.Equal(t, []string{"stop", "--signal", "SIGINT", "--time", "30", "fetch"}, cli.Calls()[1])
+
+	// Stopping a workload which doesn't exist succeeds
+	client, _ = newTestClient(map[string]string{"inspect": "error: no such container: missing"})
+	assert.NoError(t, client.StopWorkload(context.Background(), "missing", nil))
+}
+
+func TestDeployWorkload_NetworkIsolation(t *testing.T) {
+	t.Parallel()
+
+	client, cli := newTestClient(nil)
+	_, _, err := client.DeployWorkload(context.Background(), "image", "name", nil, nil, nil,
+		permissions.BuiltinNoneProfile(), "stdio", nil, true)
+	assert.ErrorIs(t, err, ErrNetworkIsolationUnsupported)
+	assert.Empty(t, cli.Calls())
+}
+
+func TestRunArgs(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name     string
+		spec     workloadSpec
+		expected []string
+	}{
+		{
+			name: "Stdio",
+			spec: workloadSpec{attachStdio: true},
+			expected: []string{
+				"run", "--detach", "--name", "fetch", "--restart", "unless-stopped", "--cap-drop", "ALL", "--interactive",
+				"--env", "A=1", "--env", "B=2", "--label", "toolhive=true",
+				"mcp/fetch", "--verbose",
+			},
+		},
+		{
+			name: "Published port, mounts, stop options and restart policy",
+			spec: workloadSpec{
+				mounts:  []string{"type=bind,source=/data,target=/data,readonly"},
+				publish: []string{"41234:8080/tcp"},
+				options: &runtime.DeployWorkloadOptions{
+					StopSignal:    "SIGINT",
+					StopTimeout:   20 * time.Second,
+					RestartPolicy: "on-failure:3",
+				},
+			},
+			expected: []string{
+				"run", "--detach", "--name", "fetch", "--restart", "on-failure:3", "--cap-drop", "ALL",
+				"--env", "A=1", "--env", "B=2", "--label", "toolhive=true",
+				"--mount", "type=bind,source=/data,target=/data,readonly",
+				"--publish", "41234:8080/tcp",
+				"--stop-signal", "SIGINT", "--stop-timeout", "20",
+				"mcp/fetch", "--verbose",
+			},
+		},
+		{
+			name: "Resource limits and devices",
+			spec: workloadSpec{
+				options: &runtime.DeployWorkloadOptions{
+					Resources: &runtime.ResourceLimits{CPUs: 0.5, Memory: 512 << 20, MemorySwap: -1, PidsLimit: 100},
+					GPUs:      "all",
+					Devices:   []string{"/dev/dri"},
+				},
+			},
+			expected: []string{
+				"run", "--detach", "--name", "fetch", "--restart", "unless-stopped", "--cap-drop", "ALL",
+				"--env", "A=1", "--env", "B=2", "--label", "toolhive=true",
+				"--cpus", "0.5", "--memory", "536870912", "--memory-swap", "-1", "--pids-limit", "100",
+				"--gpus", "all", "--device", "/dev/dri",
+				"mcp/fetch", "--verbose",
+			},
+		},
+		{
+			name: "No new privileges",
+			spec: workloadSpec{noNewPrivileges: true},
+			expected: []string{
+				"run", "--detach", "--name", "fetch", "--restart", "unless-stopped", "--cap-drop", "ALL",
+				"--security-opt", "no-new-privileges",
+				"--env", "A=1", "--env", "B=2", "--label", "toolhive=true",
+				"mcp/fetch", "--verbose",
+			},
+		},
+		{
+			name: "Networks",
+			spec: workloadSpec{
+				options: &runtime.DeployWorkloadOptions{Networks: []string{"compose_default", "databases"}},
+			},
+			expected: []string{
+				"run", "--detach", "--name", "fetch", "--restart", "unless-stopped", "--cap-drop", "ALL",
+				"--env", "A=1", "--env", "B=2", "--label", "toolhive=true",
+				"--network", "compose_default", "--network", "databases",
+				"mcp/fetch", "--verbose",
+			},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			spec := tt.spec
+			spec.name = "fetch"
+			spec.image = "mcp/fetch"
+			spec.command = []string{"--verbose"}
+			spec.envVars = map[string]string{"B": "2", "A": "1"}
+			spec.labels = map[string]string{"toolhive": "true"}
+			assert.Equal(t, tt.expected, runArgs(&spec))
+		})
+	}
+}
+
+func TestMountArgs(t *testing.T) {
+	t.Parallel()
+
+	profile := permissions.BuiltinNoneProfile()
+	profile.Read = []permissions.MountDeclaration{"/data", "volume://cache:/cache"}
+	profile.Write = []permissions.MountDeclaration{"/tmp/out:/out"}
+
+	assert.Equal(t, []string{
+		"type=bind,source=/data,target=/data,readonly",
+		"type=volume,source=cache,target=/cache,readonly",
+		"type=bind,source=/tmp/out,target=/out",
+	}, mountArgs(profile))
+}
@@ -0,0 +1,68 @@
+package images
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/docker/docker/api/types/registry"
+	"github.com/google/go-containerregistry/pkg/authn"
+	"github.com/google/go-containerregistry/pkg/name"
+)
+
+// Credentials are the username and password of a registry, which are used instead of the credentials
+// of the Docker CLI configuration
+type Credentials struct {
+	Username string
+	Password string
+}
+
+// credentialsKey is the context key of the registry credentials
+type credentialsKey struct{}
+
+// WithCredentials returns a context in which images are pulled with the given registry credentials
+func WithCredentials(ctx context.Context, credentials *Credentials) context.Context {
+	return context.WithValue(ctx, credentialsKey{}, credentials)
+}
+
+// credentialsFromContext returns the registry credentials of a context, or nil if there are none
+func credentialsFromContext(ctx context.Context) *Credentials {
+	credentials, _ := ctx.Value(credentialsKey{}).(*Credentials)
+	return credentials
+}
+
+// registryAuth returns the credentials of the registry of an image, encoded for the Docker API. The
+// credentials are the given ones, or else those of the Docker CLI configuration, including credential
+// helpers like the ones of ECR and ACR. It returns an empty string if the image is pulled anonymously.
+func registryAuth(imageName string, credentials *Credentials) (string, error) {
+	ref, err := name.ParseReference(imageName)
+	if err != nil {
+		return "", fmt.Errorf("invalid image reference %s: %w", imageName, err)
+	}
+	registryName := ref.Context().Registry
+
+	var authenticator authn.Authenticator
+	if credentials != nil {
+		authenticator = &authn.Basic{Username: credentials.Username, Password: credentials.Password}
+	} else {
+		authenticator, err = authn.DefaultKeychain.Resolve(registryName)
+		if err != nil {
+			return "", fmt.Errorf("failed to get credentials of registry %s: %w", registryName.RegistryStr(), err)
+		}
+	}
+	if authenticator == authn.Anonymous {
+		return "", nil
+	}
+
+	authConfig, err := authenticator.Authorization()
+	if err != nil {
+		return "", fmt.Errorf("failed to get credentials of registry %s: %w", registryName.RegistryStr(), err)
+	}
+	return registry.EncodeAuthConfig(registry.AuthConfig{
+		Username:      authConfig.Username,
+		Password:      authConfig.Password,
+		Auth:          authConfig.Auth,
+		IdentityToken: authConfig.IdentityToken,
+		RegistryToken: authConfig.RegistryToken,
+		ServerAddress: registryName.RegistryStr(),
+	})
+}
@@ -0,0 +1,77 @@
+package images
+
+import (
+	"context"
+	"encoding/base64"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/docker/docker/api/types/registry"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestRegistryAuth(t *testing.T) { //nolint:paralleltest // Test modifies environment variables
+	configDir := t.TempDir()
+	auth := base64.StdEncoding.EncodeToString([]byte("octocat:ghp_secret"))
+	config := `{"auths": {"ghcr.io": {"auth": "` + auth + `"}}}`
+	require.NoError(t, os.WriteFile(filepath.Join(configDir, "config.json"), []byte(config), 0600))
+	t.Setenv("DOCKER_CONFIG", configDir)
+
+	tests := []struct {
+		name             string
+		image            string
+		credentials      *Credentials
+		expectedUsername string
+		expectedPassword string
+		expectedServer   string
+	}{
+		{
+			name:  "Anonymous",
+			image: "docker.io/mcp/fetch:latest",
+		},
+		{
+			name:             "Docker CLI configuration",
+			image:            "ghcr.io/acme/private-mcp:1.0.0",
+			expectedUsername: "octocat",
+			expectedPassword: "ghp_secret",
+			expectedServer:   "ghcr.io",
+		},
+		{
+			name:             "Explicit credentials",
+			image:            "acme.azurecr.io/mcp/server:latest",
+			credentials:      &Credentials{Username: "acme", Password: "token"},
+			expectedUsername: "acme",
+			expectedPassword: "token",
+			expectedServer:   "acme.azurecr.io",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) { //nolint:paralleltest // Test modifies environment variables
+			encoded, err := registryAuth(tt.image, tt.credentials)
+			require.NoError(t, err)
+			if tt.expectedServer == "" {
+				assert.Empty(t, encoded)
+				return
+			}
+
+			authConfig, err := registry.DecodeAuthConfig(encoded)
+			require.NoError(t, err)
+			assert.Equal(t, tt.expectedUsername, authConfig.Username)
+			assert.Equal(t, tt.expectedPassword, authConfig.Password)
+			assert.Equal(t, tt.expectedServer, authConfig.ServerAddress)
+		})
+	}
+}
+
+func TestCredentialsFromContext(t *testing.T) {
+	t.Parallel()
+
+	assert.Nil(t, credentialsFromContext(context.Background()))
+
+	credentials := &Credentials{Username: "acme", Password: "token"}
+	ctx := WithCredentials(context.Background(), credentials)
+	assert.Equal(t, credentials, credentialsFromContext(ctx))
+}
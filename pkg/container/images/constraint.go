@@ -0,0 +1,216 @@
+package images
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+
+	"golang.org/x/mod/semver"
+)
+
+// Constraint is a semantic version constraint on image tags, such as ^1.2, ~1.2.3, 1.2.x or >=1.2 <2.
+// The terms of a constraint, separated by spaces or commas, must all be satisfied.
+type Constraint struct {
+	raw    string
+	bounds []bound
+	// prerelease indicates that the constraint names a pre-release, so pre-release tags may satisfy it
+	prerelease bool
+}
+
+// bound is a comparison of a version with a canonical semantic version
+type bound struct {
+	op      string
+	version string
+}
+
+// ParseConstraint parses a semantic version constraint. The supported terms are exact versions (1.2.3),
+// partial versions and wildcards (1.2, 1.2.x, *), caret ranges (^1.2, compatible with 1.2), tilde ranges
+// (~1.2.3, patch releases of 1.2) and comparisons (>=1.2.0, >1.2, <2, <=2.1, =1.2.3).
+func ParseConstraint(constraint string) (*Constraint, error) {
+	c := &Constraint{raw: constraint}
+	terms := strings.FieldsFunc(constraint, func(r rune) bool { return r == ' ' || r == ',' })
+	if len(terms) == 0 {
+		return nil, fmt.Errorf("empty version constraint")
+	}
+
+	for _, term := range terms {
+		bounds, err := parseTerm(term)
+		if err != nil {
+			return nil, fmt.Errorf("invalid version constraint %s: %w", constraint, err)
+		}
+		for _, b := range bounds {
+			if semver.Prerelease(b.version) != "" {
+				c.prerelease = true
+			}
+		}
+		c.bounds = append(c.bounds, bounds...)
+	}
+	return c, nil
+}
+
+// String returns the constraint as it was parsed
+func (c *Constraint) String() string {
+	return c.raw
+}
+
+// Matches returns true if the tag is a semantic version which satisfies the constraint
+func (c *Constraint) Matches(tag string) bool {
+	version := canonicalVersion(tag)
+	if version == "" || (!c.prerelease && semver.Prerelease(version) != "") {
+		return false
+	}
+	for _, b := range c.bounds {
+		if !b.matches(version) {
+			return false
+		}
+	}
+	return true
+}
+
+// Latest returns the newest of the tags which satisfy the constraint, or an empty string if none does
+func (c *Constraint) Latest(tags []string) string {
+	latest := ""
+	for _, tag := range tags {
+		if !c.Matches(tag) {
+			continue
+		}
+		if latest == "" || semver.Compare(canonicalVersion(tag), canonicalVersion(latest)) > 0 {
+			latest = tag
+		}
+	}
+	return latest
+}
+
+func (b bound) matches(version string) bool {
+	cmp := semver.Compare(version, b.version)
+	switch b.op {
+	case ">":
+		return cmp > 0
+	case ">=":
+		return cmp >= 0
+	case "<":
+		return cmp < 0
+	case "<=":
+		return cmp <= 0
+	default:
+		return cmp == 0
+	}
+}
+
+// parseTerm parses a term of a constraint into the bounds it stands for
+func parseTerm(term string) ([]bound, error) {
+	op := ""
+	for _, prefix := range []string{">=", "<=", ">", "<", "=", "^", "~"} {
+		if strings.HasPrefix(term, prefix) {
+			op = prefix
+			break
+		}
+	}
+
+	parts, version, err := parseVersion(strings.TrimPrefix(term, op))
+	if err != nil {
+		return nil, err
+	}
+	if parts == 0 {
+		// A wildcard matches any version
+		if op != "" && op != "=" {
+			return nil, fmt.Errorf("%s can't be used with a wildcard", op)
+		}
+		return nil, nil
+	}
+	return termBounds(op, version, parts), nil
+}
+
+// termBounds returns the bounds of a term with the given operator and version,
+// of which only the given number of parts were specified
+func termBounds(op, version string, parts int) []bound {
+	next := nextVersion(version, parts)
+	switch op {
+	case "^":
+		return []bound{{">=", version}, {"<", caretUpperBound(version, parts)}}
+	case "~":
+		if parts > 1 {
+			next = nextVersion(version, 2)
+		}
+		return []bound{{">=", version}, {"<", next}}
+	case ">":
+		if parts < 3 {
+			return []bound{{">=", next}}
+		}
+		return []bound{{">", version}}
+	case "<=":
+		if parts < 3 {
+			return []bound{{"<", next}}
+		}
+		return []bound{{"<=", version}}
+	case ">=", "<":
+		return []bound{{op, version}}
+	default:
+		if parts < 3 {
+			return []bound{{">=", version}, {"<", next}}
+		}
+		return []bound{{"=", version}}
+	}
+}
+
+// caretUpperBound returns the upper bound of a caret range, which allows the changes that don't modify
+// the left-most non-zero part of the version
+func caretUpperBound(version string, parts int) string {
+	switch {
+	case semver.Major(version) != "v0" || parts == 1:
+		return nextVersion(version, 1)
+	case semver.MajorMinor(version) != "v0.0" || parts == 2:
+		return nextVersion(version, 2)
+	default:
+		return nextVersion(version, 3)
+	}
+}
+
+// parseVersion parses a full or partial version, with or without the "v" prefix. It returns the number of
+// parts which were given (0 for a wildcard), and the canonical version with the missing parts set to 0.
+func parseVersion(s string) (int, string, error) {
+	s = strings.TrimPrefix(s, "v")
+	core, suffix := s, ""
+	if i := strings.IndexAny(s, "-+"); i >= 0 {
+		core, suffix = s[:i], s[i:]
+	}
+
+	numbers := make([]string, 0, 3)
+	for _, part := range strings.Split(core, ".") {
+		if part == "x" || part == "X" || part == "*" {
+			break
+		}
+		if _, err := strconv.ParseUint(part, 10, 64); err != nil {
+			return 0, "", fmt.Errorf("%s is not a version", s)
+		}
+		numbers = append(numbers, part)
+	}
+	parts := len(numbers)
+	if parts > 3 || (suffix != "" && parts != 3) {
+		return 0, "", fmt.Errorf("%s is not a version", s)
+	}
+
+	for len(numbers) < 3 {
+		numbers = append(numbers, "0")
+	}
+	version := semver.Canonical("v" + strings.Join(numbers, ".") + suffix)
+	if version == "" {
+		return 0, "", fmt.Errorf("%s is not a version", s)
+	}
+	return parts, version, nil
+}
+
+// nextVersion returns the lowest version after all versions which share the first parts of the given version
+func nextVersion(version string, parts int) string {
+	core := strings.TrimPrefix(version, "v")
+	if i := strings.IndexAny(core, "-+"); i >= 0 {
+		core = core[:i]
+	}
+	numbers := strings.Split(core, ".")
+	n, _ := strconv.ParseUint(numbers[parts-1], 10, 64)
+	numbers[parts-1] = strconv.FormatUint(n+1, 10)
+	for i := parts; i < len(numbers); i++ {
+		numbers[i] = "0"
+	}
+	return "v" + strings.Join(numbers, ".")
+}
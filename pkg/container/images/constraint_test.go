@@ -0,0 +1,65 @@
+package images
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestConstraint(t *testing.T) {
+	t.Parallel()
+
+	tags := []string{"latest", "0.0.3", "0.1.0", "0.1.4", "1.1.0", "v1.2.0", "1.2.3", "1.2.7", "1.3.0-rc.1", "1.3.0", "2.0.0"}
+
+	tests := []struct {
+		constraint string
+		matching   []string
+		latest     string
+	}{
+		{constraint: "^1.2", matching: []string{"v1.2.0", "1.2.3", "1.2.7", "1.3.0"}, latest: "1.3.0"},
+		{constraint: "^0.1.1", matching: []string{"0.1.4"}, latest: "0.1.4"},
+		{constraint: "^0.0.3", matching: []string{"0.0.3"}, latest: "0.0.3"},
+		{constraint: "^1", matching: []string{"1.1.0", "v1.2.0", "1.2.3", "1.2.7", "1.3.0"}, latest: "1.3.0"},
+		{constraint: "~1.2.3", matching: []string{"1.2.3", "1.2.7"}, latest: "1.2.7"},
+		{constraint: "1.2", matching: []string{"v1.2.0", "1.2.3", "1.2.7"}, latest: "1.2.7"},
+		{constraint: "1.2.x", matching: []string{"v1.2.0", "1.2.3", "1.2.7"}, latest: "1.2.7"},
+		{constraint: "1.2.3", matching: []string{"1.2.3"}, latest: "1.2.3"},
+		{constraint: ">=1.2.3 <2", matching: []string{"1.2.3", "1.2.7", "1.3.0"}, latest: "1.3.0"},
+		{constraint: ">1.2, <=2.0", matching: []string{"1.3.0", "2.0.0"}, latest: "2.0.0"},
+		{constraint: "*", matching: []string{"0.0.3", "0.1.0", "0.1.4", "1.1.0", "v1.2.0", "1.2.3", "1.2.7", "1.3.0", "2.0.0"},
+			latest: "2.0.0"},
+		{constraint: "^1.3.0-rc.1", matching: []string{"1.3.0-rc.1", "1.3.0"}, latest: "1.3.0"},
+		{constraint: "^3", matching: nil, latest: ""},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.constraint, func(t *testing.T) {
+			t.Parallel()
+
+			constraint, err := ParseConstraint(tt.constraint)
+			require.NoError(t, err)
+
+			var matching []string
+			for _, tag := range tags {
+				if constraint.Matches(tag) {
+					matching = append(matching, tag)
+				}
+			}
+			assert.Equal(t, tt.matching, matching)
+			assert.Equal(t, tt.latest, constraint.Latest(tags))
+		})
+	}
+}
+
+func TestParseConstraint_Invalid(t *testing.T) {
+	t.Parallel()
+
+	for _, constraint := range []string{"", "stable", "^", ">=1.2.3.4", "1.2-rc.1", "^*", "1.a"} {
+		t.Run(constraint, func(t *testing.T) {
+			t.Parallel()
+			_, err := ParseConstraint(constraint)
+			assert.Error(t, err)
+		})
+	}
+}
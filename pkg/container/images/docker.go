@@ -9,6 +9,7 @@ import (
 	"os"
 	"path/filepath"
 
+	"github.com/containerd/errdefs"
 	"github.com/docker/docker/api/types/build"
 	"github.com/docker/docker/api/types/filters"
 	dockerimage "github.com/docker/docker/api/types/image"
@@ -47,8 +48,63 @@ func (d *DockerImageManager) ImageExists(ctx context.Context, imageName string)
 	return len(images) > 0, nil
 }
 
+// GetImageID returns the ID of the local image the given reference resolves to
+func (d *DockerImageManager) GetImageID(ctx context.Context, imageName string) (string, error) {
+	info, err := d.client.ImageInspect(ctx, imageName)
+	if err != nil {
+		if errdefs.IsNotFound(err) {
+			return "", nil
+		}
+		return "", fmt.Errorf("failed to inspect image %s: %v", imageName, err)
+	}
+	return info.ID, nil
+}
+
+// GetRepoDigests returns the registry digests of the given local image
+func (d *DockerImageManager) GetRepoDigests(ctx context.Context, imageName string) ([]string, error) {
+	info, err := d.client.ImageInspect(ctx, imageName)
+	if err != nil {
+		if errdefs.IsNotFound(err) {
+			return nil, nil
+		}
+		return nil, fmt.Errorf("failed to inspect image %s: %v", imageName, err)
+	}
+	return info.RepoDigests, nil
+}
+
+// GetImageConfig returns the labels and environment of the given local image
+func (d *DockerImageManager) GetImageConfig(ctx context.Context, imageName string) (*ImageConfig, error) {
+	info, err := d.client.ImageInspect(ctx, imageName)
+	if err != nil {
+		if errdefs.IsNotFound(err) {
+			return nil, nil
+		}
+		return nil, fmt.Errorf("failed to inspect image %s: %v", imageName, err)
+	}
+	if info.Config == nil {
+		return &ImageConfig{}, nil
+	}
+	return &ImageConfig{Labels: info.Config.Labels, Env: info.Config.Env}, nil
+}
+
+// TagImage points the target reference at the source image
+func (d *DockerImageManager) TagImage(ctx context.Context, source, target string) error {
+	if err := d.client.ImageTag(ctx, source, target); err != nil {
+		return fmt.Errorf("failed to tag image %s as %s: %v", source, target, err)
+	}
+	return nil
+}
+
+// RemoveImage removes a local image and its untagged parents
+func (d *DockerImageManager) RemoveImage(ctx context.Context, imageName string) error {
+	if _, err := d.client.ImageRemove(ctx, imageName, dockerimage.RemoveOptions{PruneChildren: true}); err != nil {
+		return fmt.Errorf("failed to remove image %s: %v", imageName, err)
+	}
+	return nil
+}
+
 // BuildImage builds a Docker image from a Dockerfile in the specified context directory
-func (d *DockerImageManager) BuildImage(ctx context.Context, contextDir, imageName string) error {
+func (d *DockerImageManager) BuildImage(ctx context.Context, contextDir, dockerfile, imageName string) error {
 	logger.Infof("Building image %s from context directory %s", imageName, contextDir)
 
 	// Create a tar archive of the context directory
@@ -70,9 +126,12 @@ func (d *DockerImageManager) BuildImage(ctx context.Context, contextDir, imageNa
 	}
 
 	// Build the image
+	if dockerfile == "" {
+		dockerfile = "Dockerfile"
+	}
 	buildOptions := build.ImageBuildOptions{
 		Tags:       []string{imageName},
-		Dockerfile: "Dockerfile",
+		Dockerfile: filepath.ToSlash(dockerfile),
 		Remove:     true,
 	}
 
@@ -94,16 +153,32 @@ func (d *DockerImageManager) BuildImage(ctx context.Context, contextDir, imageNa
 func (d *DockerImageManager) PullImage(ctx context.Context, imageName string) error {
 	logger.Infof("Pulling image: %s", imageName)
 
+	// The daemon doesn't know the credentials of the user, so they are passed with the pull
+	credentials := credentialsFromContext(ctx)
+	auth, err := registryAuth(imageName, credentials)
+	if err != nil {
+		if credentials != nil {
+			return err
+		}
+		logger.Warnf("Pulling image %s without credentials: %v", imageName, err)
+	}
+
 	// Pull the image
-	reader, err := d.client.ImagePull(ctx, imageName, dockerimage.PullOptions{})
+	reader, err := d.client.ImagePull(ctx, imageName, dockerimage.PullOptions{RegistryAuth: auth})
 	if err != nil {
 		return fmt.Errorf("failed to pull image: %v", err)
 	}
 	defer reader.Close()
 
-	// Parse and filter the pull output
+	// Report the progress of the pull if it is displayed by the caller, or else print the pull output
+	if progress := progressFromContext(ctx); progress != nil {
+		if err := parsePullProgress(reader, progress); err != nil {
+			return fmt.Errorf("failed to pull image: %w", err)
+		}
+		return nil
+	}
 	if err := parsePullOutput(reader, os.Stdout); err != nil {
-		return fmt.Errorf("failed to process pull output: %v", err)
+		return fmt.Errorf("failed to pull image: %w", err)
 	}
 
 	return nil
@@ -203,6 +278,7 @@ func parsePullOutput(reader io.Reader, writer io.Writer) error {
 			ID             string          `json:"id,omitempty"`
 			ProgressDetail json.RawMessage `json:"progressDetail,omitempty"`
 			Progress       string          `json:"progress,omitempty"`
+			Error          string          `json:"error,omitempty"`
 		}
 
 		if err := decoder.Decode(&pullStatus); err != nil {
@@ -212,6 +288,11 @@ func parsePullOutput(reader io.Reader, writer io.Writer) error {
 			return fmt.Errorf("failed to decode pull output: %w", err)
 		}
 
+		// Errors, e.g. of a missing manifest or denied access, are reported in the output of the pull
+		if pullStatus.Error != "" {
+			return fmt.Errorf("pull error: %s", pullStatus.Error)
+		}
+
 		// Format the output based on the type of message
 		if pullStatus.Progress != "" {
 			// This is a progress update
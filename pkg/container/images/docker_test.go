@@ -0,0 +1,55 @@
+package images
+
+import (
+	"bytes"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestParsePullOutput(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name          string
+		output        string
+		expected      string
+		expectedError string
+	}{
+		{
+			name: "Progress",
+			output: `{"status":"Pulling from mcp/fetch","id":"latest"}
+{"status":"Downloading","id":"abc123","progressDetail":{"current":1,"total":2},"progress":"[=====>     ]"}
+{"status":"Pull complete","id":"abc123"}
+{"status":"Status: Downloaded newer image for mcp/fetch:latest"}`,
+			expected: "Pulling from mcp/fetch: latest\n" +
+				"Downloading: abc123 [=====>     ]\n" +
+				"Pull complete: abc123\n" +
+				"Status: Downloaded newer image for mcp/fetch:latest\n",
+		},
+		{
+			name: "Error",
+			output: `{"status":"Pulling from mcp/private","id":"latest"}
+{"errorDetail":{"message":"pull access denied"},"error":"pull access denied"}`,
+			expected:      "Pulling from mcp/private: latest\n",
+			expectedError: "pull error: pull access denied",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			var out bytes.Buffer
+			err := parsePullOutput(strings.NewReader(tt.output), &out)
+			if tt.expectedError != "" {
+				require.EqualError(t, err, tt.expectedError)
+			} else {
+				require.NoError(t, err)
+			}
+			assert.Equal(t, tt.expected, out.String())
+		})
+	}
+}
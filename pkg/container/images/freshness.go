@@ -0,0 +1,171 @@
+package images
+
+import (
+	"context"
+	"fmt"
+	"sort"
+	"strings"
+
+	"github.com/google/go-containerregistry/pkg/authn"
+	"github.com/google/go-containerregistry/pkg/name"
+	"github.com/google/go-containerregistry/pkg/v1/remote"
+	"golang.org/x/mod/semver"
+)
+
+// RemoteRegistry looks up image information in upstream registries
+type RemoteRegistry interface {
+	// Digest returns the digest the given image reference currently resolves to in its registry
+	Digest(ctx context.Context, ref name.Reference) (string, error)
+	// Tags returns all tags of the given repository
+	Tags(ctx context.Context, repo name.Repository) ([]string, error)
+}
+
+// NewRemoteRegistry creates a RemoteRegistry which authenticates using the default keychain
+// (e.g. the credentials stored by docker login)
+func NewRemoteRegistry() RemoteRegistry {
+	return &remoteRegistry{}
+}
+
+type remoteRegistry struct{}
+
+func (*remoteRegistry) Digest(ctx context.Context, ref name.Reference) (string, error) {
+	desc, err := remote.Head(ref, remote.WithContext(ctx), remote.WithAuthFromKeychain(authn.DefaultKeychain))
+	if err != nil {
+		return "", err
+	}
+	return desc.Digest.String(), nil
+}
+
+func (*remoteRegistry) Tags(ctx context.Context, repo name.Repository) ([]string, error) {
+	return remote.List(repo, remote.WithContext(ctx), remote.WithAuthFromKeychain(authn.DefaultKeychain))
+}
+
+// Freshness describes how a local image compares with its upstream registry
+type Freshness struct {
+	// Image is the image reference
+	Image string `json:"image"`
+	// CurrentDigest is the registry digest of the local image, if known
+	CurrentDigest string `json:"current_digest,omitempty"`
+	// LatestDigest is the digest the image reference currently resolves to in the registry
+	LatestDigest string `json:"latest_digest,omitempty"`
+	// NewerTags are the semantic version tags of the repository which are newer than the current tag,
+	// sorted from newest to oldest
+	NewerTags []string `json:"newer_tags,omitempty"`
+	// Pinned indicates that the image is referenced by digest and can't move
+	Pinned bool `json:"pinned"`
+	// Outdated indicates that the registry has a newer digest or tag for the image
+	Outdated bool `json:"outdated"`
+}
+
+// CheckFreshness compares the local registry digests of an image with its upstream registry.
+// localDigests are the repository digests of the local image, as returned by GetRepoDigests.
+func CheckFreshness(
+	ctx context.Context,
+	registry RemoteRegistry,
+	image string,
+	localDigests []string,
+) (*Freshness, error) {
+	ref, err := name.ParseReference(image)
+	if err != nil {
+		return nil, fmt.Errorf("invalid image reference %s: %w", image, err)
+	}
+
+	result := &Freshness{Image: image}
+
+	if digest, ok := ref.(name.Digest); ok {
+		result.Pinned = true
+		result.CurrentDigest = digest.DigestStr()
+		result.LatestDigest = digest.DigestStr()
+		return result, nil
+	}
+
+	result.CurrentDigest = matchingDigest(ref.Context(), localDigests)
+	result.LatestDigest, err = registry.Digest(ctx, ref)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get the digest of %s: %w", image, err)
+	}
+
+	if tag, ok := ref.(name.Tag); ok && isSemverTag(tag.TagStr()) {
+		tags, err := registry.Tags(ctx, ref.Context())
+		if err != nil {
+			return nil, fmt.Errorf("failed to list the tags of %s: %w", ref.Context(), err)
+		}
+		result.NewerTags = NewerTags(tag.TagStr(), tags)
+	}
+
+	result.Outdated = len(result.NewerTags) > 0 ||
+		(result.CurrentDigest != "" && result.CurrentDigest != result.LatestDigest)
+	return result, nil
+}
+
+// NewerTags returns the semantic version tags which are newer than current, sorted from newest to oldest.
+// Pre-release tags are only considered if the current tag is a pre-release itself.
+func NewerTags(current string, tags []string) []string {
+	currentVersion := canonicalVersion(current)
+	if currentVersion == "" {
+		return nil
+	}
+	allowPrerelease := semver.Prerelease(currentVersion) != ""
+
+	var newer []string
+	for _, tag := range tags {
+		version := canonicalVersion(tag)
+		if version == "" || (!allowPrerelease && semver.Prerelease(version) != "") {
+			continue
+		}
+		if semver.Compare(version, currentVersion) > 0 {
+			newer = append(newer, tag)
+		}
+	}
+
+	sort.SliceStable(newer, func(i, j int) bool {
+		return semver.Compare(canonicalVersion(newer[i]), canonicalVersion(newer[j])) > 0
+	})
+	return newer
+}
+
+func isSemverTag(tag string) bool {
+	return canonicalVersion(tag) != ""
+}
+
+// canonicalVersion returns the canonical semantic version of a tag, accepting tags with or without
+// the "v" prefix, or an empty string if the tag is not a semantic version
+func canonicalVersion(tag string) string {
+	if !strings.HasPrefix(tag, "v") {
+		tag = "v" + tag
+	}
+	return semver.Canonical(tag)
+}
+
+// RepoDigest returns the digest of the repository digest which belongs to the repository of the given image,
+// or an empty string if there is none
+func RepoDigest(image string, repoDigests []string) string {
+	ref, err := name.ParseReference(image)
+	if err != nil {
+		return ""
+	}
+	return matchingDigest(ref.Context(), repoDigests)
+}
+
+// DigestReference returns the reference to the given digest in the repository of the given image
+func DigestReference(image, digest string) (string, error) {
+	ref, err := name.ParseReference(image)
+	if err != nil {
+		return "", fmt.Errorf("invalid image reference %s: %w", image, err)
+	}
+	return ref.Context().Digest(digest).String(), nil
+}
+
+// matchingDigest returns the digest of the first repository digest which belongs to the given repository
+func matchingDigest(repo name.Repository, repoDigests []string) string {
+	for _, repoDigest := range repoDigests {
+		digest, err := name.NewDigest(repoDigest)
+		if err != nil {
+			continue
+		}
+		if digest.Context().Name() == repo.Name() {
+			return digest.DigestStr()
+		}
+	}
+	return ""
+}
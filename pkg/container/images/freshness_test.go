@@ -0,0 +1,193 @@
+package images
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/google/go-containerregistry/pkg/name"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+const (
+	oldDigest = "sha256:1111111111111111111111111111111111111111111111111111111111111111"
+	newDigest = "sha256:2222222222222222222222222222222222222222222222222222222222222222"
+)
+
+type fakeRegistry struct {
+	digest string
+	tags   []string
+	err    error
+}
+
+func (f *fakeRegistry) Digest(_ context.Context, _ name.Reference) (string, error) {
+	return f.digest, f.err
+}
+
+func (f *fakeRegistry) Tags(_ context.Context, _ name.Repository) ([]string, error) {
+	return f.tags, f.err
+}
+
+func TestNewerTags(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name     string
+		current  string
+		tags     []string
+		expected []string
+	}{
+		{
+			name:     "newer releases sorted newest first",
+			current:  "v1.2.0",
+			tags:     []string{"v1.1.0", "v1.2.0", "v1.3.0", "v2.0.0", "latest"},
+			expected: []string{"v2.0.0", "v1.3.0"},
+		},
+		{
+			name:     "tags without v prefix",
+			current:  "1.2.0",
+			tags:     []string{"1.2.1", "1.10.0", "1.9.0"},
+			expected: []string{"1.10.0", "1.9.0", "1.2.1"},
+		},
+		{
+			name:     "pre-releases are ignored for stable tags",
+			current:  "v1.0.0",
+			tags:     []string{"v1.1.0-rc.1", "v1.0.1"},
+			expected: []string{"v1.0.1"},
+		},
+		{
+			name:     "pre-releases are considered for pre-release tags",
+			current:  "v1.1.0-rc.1",
+			tags:     []string{"v1.1.0-rc.2", "v1.1.0"},
+			expected: []string{"v1.1.0", "v1.1.0-rc.2"},
+		},
+		{
+			name:     "non-semver current tag",
+			current:  "latest",
+			tags:     []string{"v1.0.0"},
+			expected: nil,
+		},
+		{
+			name:     "up to date",
+			current:  "v1.0.0",
+			tags:     []string{"v0.9.0", "v1.0.0"},
+			expected: nil,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, NewerTags(tt.current, tt.tags))
+		})
+	}
+}
+
+func TestCheckFreshness(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name         string
+		image        string
+		localDigests []string
+		registry     *fakeRegistry
+		expected     *Freshness
+		wantErr      bool
+	}{
+		{
+			name:         "up to date",
+			image:        "ghcr.io/example/server:latest",
+			localDigests: []string{"ghcr.io/example/server@" + oldDigest},
+			registry:     &fakeRegistry{digest: oldDigest},
+			expected: &Freshness{
+				Image:         "ghcr.io/example/server:latest",
+				CurrentDigest: oldDigest,
+				LatestDigest:  oldDigest,
+			},
+		},
+		{
+			name:         "digest moved",
+			image:        "ghcr.io/example/server:latest",
+			localDigests: []string{"ghcr.io/example/server@" + oldDigest},
+			registry:     &fakeRegistry{digest: newDigest},
+			expected: &Freshness{
+				Image:         "ghcr.io/example/server:latest",
+				CurrentDigest: oldDigest,
+				LatestDigest:  newDigest,
+				Outdated:      true,
+			},
+		},
+		{
+			name:         "newer tag available",
+			image:        "ghcr.io/example/server:v1.0.0",
+			localDigests: []string{"ghcr.io/example/server@" + oldDigest},
+			registry:     &fakeRegistry{digest: oldDigest, tags: []string{"v1.0.0", "v1.1.0"}},
+			expected: &Freshness{
+				Image:         "ghcr.io/example/server:v1.0.0",
+				CurrentDigest: oldDigest,
+				LatestDigest:  oldDigest,
+				NewerTags:     []string{"v1.1.0"},
+				Outdated:      true,
+			},
+		},
+		{
+			name:         "docker hub short names",
+			image:        "alpine:latest",
+			localDigests: []string{"alpine@" + oldDigest},
+			registry:     &fakeRegistry{digest: oldDigest},
+			expected: &Freshness{
+				Image:         "alpine:latest",
+				CurrentDigest: oldDigest,
+				LatestDigest:  oldDigest,
+			},
+		},
+		{
+			name:         "digests of other repositories are ignored",
+			image:        "ghcr.io/example/server:latest",
+			localDigests: []string{"ghcr.io/other/server@" + oldDigest},
+			registry:     &fakeRegistry{digest: newDigest},
+			expected: &Freshness{
+				Image:        "ghcr.io/example/server:latest",
+				LatestDigest: newDigest,
+			},
+		},
+		{
+			name:     "pinned by digest",
+			image:    "ghcr.io/example/server@" + oldDigest,
+			registry: &fakeRegistry{err: errors.New("should not be called")},
+			expected: &Freshness{
+				Image:         "ghcr.io/example/server@" + oldDigest,
+				CurrentDigest: oldDigest,
+				LatestDigest:  oldDigest,
+				Pinned:        true,
+			},
+		},
+		{
+			name:     "registry error",
+			image:    "ghcr.io/example/server:latest",
+			registry: &fakeRegistry{err: errors.New("unauthorized")},
+			wantErr:  true,
+		},
+		{
+			name:     "invalid reference",
+			image:    "INVALID::ref",
+			registry: &fakeRegistry{},
+			wantErr:  true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			result, err := CheckFreshness(context.Background(), tt.registry, tt.image, tt.localDigests)
+			if tt.wantErr {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.expected, result)
+		})
+	}
+}
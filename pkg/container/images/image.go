@@ -19,8 +19,35 @@ type ImageManager interface {
 	// PullImage pulls an image from a registry
 	PullImage(ctx context.Context, image string) error
 
-	// BuildImage builds a Docker image from a Dockerfile in the specified context directory
-	BuildImage(ctx context.Context, contextDir, imageName string) error
+	// BuildImage builds a Docker image from a Dockerfile in the specified context directory.
+	// The path of the Dockerfile is relative to the context directory, and defaults to Dockerfile if empty.
+	BuildImage(ctx context.Context, contextDir, dockerfile, imageName string) error
+
+	// GetImageID returns the ID of the local image the given reference resolves to,
+	// or an empty string if the image does not exist locally
+	GetImageID(ctx context.Context, image string) (string, error)
+
+	// GetRepoDigests returns the registry digests (repository@sha256:...) of the given local image,
+	// or nil if the image does not exist locally or was not pulled from a registry
+	GetRepoDigests(ctx context.Context, image string) ([]string, error)
+
+	// TagImage points the target reference at the source image
+	TagImage(ctx context.Context, source, target string) error
+
+	// RemoveImage removes a local image. It fails if the image is used by a container.
+	RemoveImage(ctx context.Context, image string) error
+
+	// GetImageConfig returns the labels and environment of the given local image,
+	// or nil if the image does not exist locally
+	GetImageConfig(ctx context.Context, image string) (*ImageConfig, error)
+}
+
+// ImageConfig is the configuration of an image which is used as defaults when running it
+type ImageConfig struct {
+	// Labels are the labels of the image
+	Labels map[string]string
+	// Env are the environment variables of the image, in the format KEY=VALUE
+	Env []string
 }
 
 // NewImageManager creates an instance of ImageManager appropriate
@@ -51,6 +78,31 @@ func (*NoopImageManager) PullImage(_ context.Context, _ string) error {
 }
 
 // BuildImage does nothing for the no-op implementation.
-func (*NoopImageManager) BuildImage(_ context.Context, _, _ string) error {
+func (*NoopImageManager) BuildImage(_ context.Context, _, _, _ string) error {
 	return nil
 }
+
+// GetImageID always returns an empty string for the no-op implementation.
+func (*NoopImageManager) GetImageID(_ context.Context, _ string) (string, error) {
+	return "", nil
+}
+
+// GetRepoDigests always returns nil for the no-op implementation.
+func (*NoopImageManager) GetRepoDigests(_ context.Context, _ string) ([]string, error) {
+	return nil, nil
+}
+
+// TagImage does nothing for the no-op implementation.
+func (*NoopImageManager) TagImage(_ context.Context, _, _ string) error {
+	return nil
+}
+
+// RemoveImage does nothing for the no-op implementation.
+func (*NoopImageManager) RemoveImage(_ context.Context, _ string) error {
+	return nil
+}
+
+// GetImageConfig always returns nil for the no-op implementation.
+func (*NoopImageManager) GetImageConfig(_ context.Context, _ string) (*ImageConfig, error) {
+	return nil, nil
+}
@@ -0,0 +1,134 @@
+package images
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"sync"
+
+	"golang.org/x/sync/errgroup"
+)
+
+// DefaultPullConcurrency is the default number of images which are pulled at the same time
+const DefaultPullConcurrency = 4
+
+// ProgressFunc receives the progress of an image pull, as the bytes downloaded and the total bytes of the
+// layers whose size is known so far
+type ProgressFunc func(current, total int64)
+
+// progressKey is the context key of the progress function of image pulls
+type progressKey struct{}
+
+// WithPullProgress returns a context in which the progress of image pulls is reported to the given
+// function, instead of the pull output being printed
+func WithPullProgress(ctx context.Context, progress ProgressFunc) context.Context {
+	return context.WithValue(ctx, progressKey{}, progress)
+}
+
+// progressFromContext returns the progress function of a context, or nil if there is none
+func progressFromContext(ctx context.Context) ProgressFunc {
+	progress, _ := ctx.Value(progressKey{}).(ProgressFunc)
+	return progress
+}
+
+// PullImages pulls the images concurrently, with at most concurrency pulls at the same time, and returns
+// the errors of the pulls which failed by image. The progress of each pull is reported to progress, if it
+// is not nil, and all images are pulled even if some of them fail.
+func PullImages(
+	ctx context.Context,
+	imageManager ImageManager,
+	images []string,
+	concurrency int,
+	progress func(image string, current, total int64),
+) map[string]error {
+	if concurrency < 1 {
+		concurrency = DefaultPullConcurrency
+	}
+
+	var mutex sync.Mutex
+	failures := make(map[string]error)
+
+	var group errgroup.Group
+	group.SetLimit(concurrency)
+	for _, image := range images {
+		group.Go(func() error {
+			pullCtx := ctx
+			if progress != nil {
+				pullCtx = WithPullProgress(ctx, func(current, total int64) {
+					progress(image, current, total)
+				})
+			}
+			if err := imageManager.PullImage(pullCtx, image); err != nil {
+				mutex.Lock()
+				failures[image] = err
+				mutex.Unlock()
+			}
+			return nil
+		})
+	}
+	// The pulls never fail the group, their errors are collected instead
+	_ = group.Wait()
+
+	return failures
+}
+
+// parsePullProgress reads the output of a pull and reports the progress of the download, summed over the
+// layers of the image
+func parsePullProgress(reader io.Reader, progress ProgressFunc) error {
+	type layerProgress struct {
+		current int64
+		total   int64
+	}
+	layers := make(map[string]*layerProgress)
+	var order []string
+
+	decoder := json.NewDecoder(reader)
+	for {
+		var pullStatus struct {
+			Status         string `json:"status"`
+			ID             string `json:"id,omitempty"`
+			ProgressDetail struct {
+				Current int64 `json:"current"`
+				Total   int64 `json:"total"`
+			} `json:"progressDetail,omitempty"`
+			Error string `json:"error,omitempty"`
+		}
+
+		if err := decoder.Decode(&pullStatus); err != nil {
+			if err == io.EOF {
+				return nil
+			}
+			return fmt.Errorf("failed to decode pull output: %w", err)
+		}
+		if pullStatus.Error != "" {
+			return fmt.Errorf("pull error: %s", pullStatus.Error)
+		}
+		if pullStatus.ID == "" {
+			continue
+		}
+
+		layer, ok := layers[pullStatus.ID]
+		if !ok {
+			layer = &layerProgress{}
+			layers[pullStatus.ID] = layer
+			order = append(order, pullStatus.ID)
+		}
+		switch pullStatus.Status {
+		case "Downloading":
+			layer.current = pullStatus.ProgressDetail.Current
+			layer.total = pullStatus.ProgressDetail.Total
+		case "Download complete", "Pull complete", "Already exists":
+			layer.current = layer.total
+		default:
+			continue
+		}
+
+		var current, total int64
+		for _, id := range order {
+			current += layers[id].current
+			total += layers[id].total
+		}
+		progress(current, total)
+	}
+}
@@ -0,0 +1,155 @@
+package images
+
+import (
+	"context"
+	"errors"
+	"strings"
+	"sync"
+	"sync/atomic"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+// fakePullManager records the pulls of images and fails the pulls of the images in failures
+type fakePullManager struct {
+	NoopImageManager
+	failures map[string]error
+
+	mutex   sync.Mutex
+	pulled  []string
+	running atomic.Int32
+	maxRun  atomic.Int32
+}
+
+func (m *fakePullManager) PullImage(ctx context.Context, image string) error {
+	running := m.running.Add(1)
+	defer m.running.Add(-1)
+	for {
+		maxRun := m.maxRun.Load()
+		if running <= maxRun || m.maxRun.CompareAndSwap(maxRun, running) {
+			break
+		}
+	}
+
+	m.mutex.Lock()
+	m.pulled = append(m.pulled, image)
+	m.mutex.Unlock()
+
+	if progress := progressFromContext(ctx); progress != nil {
+		progress(1, 2)
+		progress(2, 2)
+	}
+	return m.failures[image]
+}
+
+func TestPullImages(t *testing.T) {
+	t.Parallel()
+
+	errDenied := errors.New("pull access denied")
+
+	tests := []struct {
+		name        string
+		images      []string
+		concurrency int
+		failures    map[string]error
+	}{
+		{
+			name:        "All images pulled",
+			images:      []string{"mcp/fetch", "mcp/github", "mcp/time"},
+			concurrency: 2,
+		},
+		{
+			name:        "Failed pulls collected",
+			images:      []string{"mcp/fetch", "mcp/private"},
+			concurrency: 2,
+			failures:    map[string]error{"mcp/private": errDenied},
+		},
+		{
+			name:        "Default concurrency",
+			images:      []string{"mcp/fetch"},
+			concurrency: 0,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			manager := &fakePullManager{failures: tt.failures}
+			var mutex sync.Mutex
+			completed := make(map[string]bool)
+
+			failures := PullImages(context.Background(), manager, tt.images, tt.concurrency,
+				func(image string, current, total int64) {
+					mutex.Lock()
+					defer mutex.Unlock()
+					completed[image] = current == total
+				})
+
+			assert.ElementsMatch(t, tt.images, manager.pulled)
+			limit := tt.concurrency
+			if limit < 1 {
+				limit = DefaultPullConcurrency
+			}
+			assert.LessOrEqual(t, int(manager.maxRun.Load()), limit)
+			for _, image := range tt.images {
+				assert.True(t, completed[image], "progress of %s not reported", image)
+			}
+			if tt.failures == nil {
+				assert.Empty(t, failures)
+			} else {
+				assert.Equal(t, tt.failures, failures)
+			}
+		})
+	}
+}
+
+func TestParsePullProgress(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name          string
+		output        string
+		expected      [][2]int64
+		expectedError string
+	}{
+		{
+			name: "Layers summed",
+			output: `{"status":"Pulling from mcp/fetch","id":"latest"}
+{"status":"Pulling fs layer","id":"abc"}
+{"status":"Downloading","id":"abc","progressDetail":{"current":1,"total":4}}
+{"status":"Downloading","id":"def","progressDetail":{"current":2,"total":6}}
+{"status":"Download complete","id":"abc"}
+{"status":"Pull complete","id":"def"}
+{"status":"Status: Downloaded newer image for mcp/fetch:latest"}`,
+			expected: [][2]int64{{1, 4}, {3, 10}, {6, 10}, {10, 10}},
+		},
+		{
+			name: "Error",
+			output: `{"status":"Downloading","id":"abc","progressDetail":{"current":1,"total":4}}
+{"errorDetail":{"message":"pull access denied"},"error":"pull access denied"}`,
+			expected:      [][2]int64{{1, 4}},
+			expectedError: "pull error: pull access denied",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			var reported [][2]int64
+			err := parsePullProgress(strings.NewReader(tt.output), func(current, total int64) {
+				reported = append(reported, [2]int64{current, total})
+			})
+
+			if tt.expectedError != "" {
+				require.EqualError(t, err, tt.expectedError)
+			} else {
+				require.NoError(t, err)
+			}
+			assert.Equal(t, tt.expected, reported)
+		})
+	}
+}
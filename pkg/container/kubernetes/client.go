@@ -5,6 +5,7 @@ package kubernetes
 import (
 	"context"
 	"encoding/json"
+	stderrors "errors"
 	"fmt"
 	"io"
 	"os"
@@ -16,6 +17,7 @@ import (
 	appsv1 "k8s.io/api/apps/v1"
 	corev1 "k8s.io/api/core/v1"
 	"k8s.io/apimachinery/pkg/api/errors"
+	"k8s.io/apimachinery/pkg/api/resource"
 	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
 	"k8s.io/apimachinery/pkg/util/intstr"
 	apimwatch "k8s.io/apimachinery/pkg/watch"
@@ -25,10 +27,13 @@ import (
 	"k8s.io/client-go/kubernetes"
 	"k8s.io/client-go/kubernetes/scheme"
 	"k8s.io/client-go/rest"
+	"k8s.io/client-go/tools/clientcmd"
 	"k8s.io/client-go/tools/remotecommand"
 	"k8s.io/client-go/tools/watch"
+	utilexec "k8s.io/client-go/util/exec"
 
 	"github.com/stacklok/toolhive/pkg/container/runtime"
+	lb "github.com/stacklok/toolhive/pkg/labels"
 	"github.com/stacklok/toolhive/pkg/logger"
 	"github.com/stacklok/toolhive/pkg/permissions"
 	transtypes "github.com/stacklok/toolhive/pkg/transport/types"
@@ -40,12 +45,16 @@ const (
 	UnknownStatus = "unknown"
 	// mcpContainerName is the name of the MCP container. This is a known constant.
 	mcpContainerName = "mcp"
+	// nvidiaGPUResource is the extended resource of the NVIDIA device plugin for GPUs
+	nvidiaGPUResource corev1.ResourceName = "nvidia.com/gpu"
 )
 
 // Client implements the Runtime interface for container operations
 type Client struct {
 	runtimeType runtime.Type
 	client      kubernetes.Interface
+	// config is the REST configuration the client was created from, in-cluster or from the kubeconfig
+	config *rest.Config
 	// waitForStatefulSetReadyFunc is used for testing to mock the waitForStatefulSetReady function
 	waitForStatefulSetReadyFunc func(ctx context.Context, clientset kubernetes.Interface, namespace, name string) error
 }
@@ -55,7 +64,12 @@ func NewClient(_ context.Context) (*Client, error) {
 	// creates the in-cluster config
 	config, err := rest.InClusterConfig()
 	if err != nil {
-		return nil, fmt.Errorf("failed to create in-cluster config: %v", err)
+		// Outside of a cluster, e.g. when the runtime is selected explicitly, use the current kubeconfig context
+		logger.Debugf("Not running in a cluster, using the kubeconfig: %v", err)
+		config, err = kubeconfig().ClientConfig()
+		if err != nil {
+			return nil, fmt.Errorf("failed to load the Kubernetes configuration: %v", err)
+		}
 	}
 	// creates the clientset
 	clientset, err := kubernetes.NewForConfig(config)
@@ -66,9 +80,28 @@ func NewClient(_ context.Context) (*Client, error) {
 	return &Client{
 		runtimeType: runtime.TypeKubernetes,
 		client:      clientset,
+		config:      config,
 	}, nil
 }
 
+// kubeconfig returns the configuration of the current context of the kubeconfig, which is
+// loaded from KUBECONFIG or ~/.kube/config
+func kubeconfig() clientcmd.ClientConfig {
+	return clientcmd.NewNonInteractiveDeferredLoadingClientConfig(
+		clientcmd.NewDefaultClientConfigLoadingRules(),
+		&clientcmd.ConfigOverrides{},
+	)
+}
+
+// getNamespaceFromKubeconfig attempts to get the namespace of the current context of the kubeconfig
+func getNamespaceFromKubeconfig() (string, error) {
+	ns, _, err := kubeconfig().Namespace()
+	if err != nil {
+		return "", fmt.Errorf("failed to get namespace from kubeconfig: %w", err)
+	}
+	return ns, nil
+}
+
 // getNamespaceFromServiceAccount attempts to read the namespace from the service account token file
 func getNamespaceFromServiceAccount() (string, error) {
 	data, err := os.ReadFile("/var/run/secrets/kubernetes.io/serviceaccount/namespace")
@@ -91,7 +124,8 @@ func getNamespaceFromEnv() (string, error) {
 // It tries multiple methods in order:
 // 1. Reading from the service account token file
 // 2. Getting the namespace from environment variables
-// 3. Falling back to "default" if both methods fail
+// 3. Getting the namespace of the current context of the kubeconfig, outside of a cluster
+// 4. Falling back to "default" if all methods fail
 func getCurrentNamespace() string {
 	// Method 1: Try to read from the service account namespace file
 	ns, err := getNamespaceFromServiceAccount()
@@ -105,31 +139,44 @@ func getCurrentNamespace() string {
 		return ns
 	}
 
-	// Method 3: Fall back to default
+	// Method 3: Try to get the namespace of the current context of the kubeconfig
+	ns, err = getNamespaceFromKubeconfig()
+	if err == nil && ns != "" {
+		return ns
+	}
+
+	// Method 4: Fall back to default
 	return "default"
 }
 
-// AttachToWorkload implements runtime.Runtime.
-func (c *Client) AttachToWorkload(ctx context.Context, workloadID string) (io.WriteCloser, io.ReadCloser, error) {
-	// AttachToWorkload attaches to a workload in Kubernetes
-	// This is a more complex operation in Kubernetes compared to Docker/Podman
-	// as it requires setting up an exec session to the pod
-
-	// First, we need to find the pod associated with the workloadID (which is actually the statefulset name)
-	namespace := getCurrentNamespace()
-	pods, err := c.client.CoreV1().Pods(namespace).List(ctx, metav1.ListOptions{
+// findWorkloadPod returns the name of the pod of the statefulset of a workload
+func (c *Client) findWorkloadPod(ctx context.Context, workloadID string) (string, error) {
+	pods, err := c.client.CoreV1().Pods(getCurrentNamespace()).List(ctx, metav1.ListOptions{
 		LabelSelector: fmt.Sprintf("app=%s", workloadID),
 	})
 	if err != nil {
-		return nil, nil, fmt.Errorf("failed to find pod for workload %s: %w", workloadID, err)
+		return "", fmt.Errorf("failed to find pod for workload %s: %w", workloadID, err)
 	}
 
 	if len(pods.Items) == 0 {
-		return nil, nil, fmt.Errorf("no pods found for workload %s", workloadID)
+		return "", fmt.Errorf("no pods found for workload %s", workloadID)
 	}
 
 	// Use the first pod found
-	podName := pods.Items[0].Name
+	return pods.Items[0].Name, nil
+}
+
+// AttachToWorkload implements runtime.Runtime.
+func (c *Client) AttachToWorkload(ctx context.Context, workloadID string) (io.WriteCloser, io.ReadCloser, error) {
+	// AttachToWorkload attaches to a workload in Kubernetes
+	// This is a more complex operation in Kubernetes compared to Docker/Podman
+	// as it requires setting up an exec session to the pod
+
+	// First, we need to find the pod associated with the workloadID (which is actually the statefulset name)
+	podName, err := c.findWorkloadPod(ctx, workloadID)
+	if err != nil {
+		return nil, nil, err
+	}
 
 	attachOpts := &corev1.PodAttachOptions{
 		Container: mcpContainerName,
@@ -147,12 +194,11 @@ func (c *Client) AttachToWorkload(ctx context.Context, workloadID string) (io.Wr
 		SubResource("attach").
 		VersionedParams(attachOpts, scheme.ParameterCodec)
 
-	config, err := rest.InClusterConfig()
-	if err != nil {
-		panic(fmt.Errorf("failed to create k8s config: %v", err))
+	if c.config == nil {
+		return nil, nil, fmt.Errorf("no Kubernetes configuration available to attach to workload %s", workloadID)
 	}
 	// Create a SPDY executor
-	exec, err := remotecommand.NewSPDYExecutor(config, "POST", req.URL())
+	exec, err := remotecommand.NewSPDYExecutor(c.config, "POST", req.URL())
 	if err != nil {
 		return nil, nil, fmt.Errorf("failed to create SPDY executor: %v", err)
 	}
@@ -161,10 +207,16 @@ func (c *Client) AttachToWorkload(ctx context.Context, workloadID string) (io.Wr
 
 	stdinReader, stdinWriter := io.Pipe()
 	stdoutReader, stdoutWriter := io.Pipe()
+
+	// Keep stderr out of the JSON-RPC stream read from stdout and log it instead
+	stderrWriter := logger.NewLineWriter(logger.Infof, fmt.Sprintf("[%s] ", workloadID))
+
 	//nolint:gosec // we don't check for an error here because it's not critical
 	// and it also returns with an error of statuscode `0`'. perhaps someone
 	// who knows the function a bit more can fix this.
 	go func() {
+		defer stderrWriter.Flush()
+
 		// wrap with retry so we can retry if the connection fails
 		// Create exponential backoff with max 5 retries
 		expBackoff := backoff.NewExponentialBackOff()
@@ -173,7 +225,7 @@ func (c *Client) AttachToWorkload(ctx context.Context, workloadID string) (io.Wr
 			return nil, exec.StreamWithContext(ctx, remotecommand.StreamOptions{
 				Stdin:  stdinReader,
 				Stdout: stdoutWriter,
-				Stderr: stdoutWriter,
+				Stderr: stderrWriter,
 				Tty:    false,
 			})
 		},
@@ -205,7 +257,7 @@ func (c *Client) AttachToWorkload(ctx context.Context, workloadID string) (io.Wr
 }
 
 // GetWorkloadLogs implements runtime.Runtime.
-func (c *Client) GetWorkloadLogs(ctx context.Context, workloadID string, follow bool) (string, error) {
+func (c *Client) GetWorkloadLogs(ctx context.Context, workloadID string, options runtime.LogOptions) (string, error) {
 	// In Kubernetes, workloadID is the statefulset name
 	namespace := getCurrentNamespace()
 
@@ -227,10 +279,17 @@ func (c *Client) GetWorkloadLogs(ctx context.Context, workloadID string, follow
 	// Get logs from the pod
 	logOptions := &corev1.PodLogOptions{
 		Container:  mcpContainerName,
-		Follow:     follow,
+		Follow:     options.Follow,
 		Previous:   false,
 		Timestamps: true,
 	}
+	if options.Tail > 0 {
+		tailLines := int64(options.Tail)
+		logOptions.TailLines = &tailLines
+	}
+	if !options.Since.IsZero() {
+		logOptions.SinceTime = &metav1.Time{Time: options.Since}
+	}
 
 	req := c.client.CoreV1().Pods(namespace).GetLogs(podName, logOptions)
 	podLogs, err := req.Stream(ctx)
@@ -248,6 +307,11 @@ func (c *Client) GetWorkloadLogs(ctx context.Context, workloadID string, follow
 	return string(logBytes), nil
 }
 
+// StreamWorkloadLogs is not supported, since the logs of pods are kept by the cluster
+func (*Client) StreamWorkloadLogs(_ context.Context, _ string, _ time.Time, _, _ io.Writer) error {
+	return fmt.Errorf("streaming workload logs is not supported by the Kubernetes runtime: %w", stderrors.ErrUnsupported)
+}
+
 // DeployWorkload implements runtime.Runtime.
 func (c *Client) DeployWorkload(ctx context.Context,
 	image string,
@@ -263,8 +327,12 @@ func (c *Client) DeployWorkload(ctx context.Context,
 	namespace := getCurrentNamespace()
 	containerLabels["app"] = containerName
 	containerLabels["toolhive"] = "true"
+	// JSON is not a valid label value in Kubernetes, so the individual labels of the workload are used instead
+	delete(containerLabels, lb.LabelWorkload)
 
 	attachStdio := options == nil || options.AttachStdio
+	warnUnsupportedRestartPolicy(options)
+	warnUnsupportedNetworks(options)
 
 	// Convert environment variables to Kubernetes format
 	var envVarList []*corev1apply.EnvVarApplyConfiguration
@@ -287,6 +355,9 @@ func (c *Client) DeployWorkload(ctx context.Context,
 	// Ensure the pod template has required configuration (labels, etc.)
 	podTemplateSpec = ensurePodTemplateConfig(podTemplateSpec, containerLabels)
 
+	// Use the stop timeout as the termination grace period, unless the patch sets one
+	setTerminationGracePeriod(podTemplateSpec, options)
+
 	// Configure the MCP container
 	err := configureMCPContainer(
 		podTemplateSpec,
@@ -369,10 +440,13 @@ func (c *Client) GetWorkloadInfo(ctx context.Context, workloadID string) (runtim
 		return runtime.ContainerInfo{}, fmt.Errorf("failed to list pods for statefulset %s: %w", workloadID, err)
 	}
 
-	// Extract port mappings from pods
+	// Extract port mappings, start time and restart count from pods
 	ports := make([]runtime.PortMapping, 0)
+	var startedAt time.Time
+	var restartCount int
 	if len(pods.Items) > 0 {
 		ports = extractPortMappingsFromPod(&pods.Items[0])
+		startedAt, restartCount = mcpContainerStatus(&pods.Items[0])
 	}
 
 	// Get ports from associated service (for SSE transport)
@@ -402,17 +476,34 @@ func (c *Client) GetWorkloadInfo(ctx context.Context, workloadID string) (runtim
 	}
 
 	return runtime.ContainerInfo{
-		ID:      string(statefulset.UID),
-		Name:    statefulset.Name,
-		Image:   image,
-		Status:  status,
-		State:   state,
-		Created: statefulset.CreationTimestamp.Time,
-		Labels:  statefulset.Labels,
-		Ports:   ports,
+		ID:           string(statefulset.UID),
+		Name:         statefulset.Name,
+		Image:        image,
+		Status:       status,
+		State:        state,
+		Created:      statefulset.CreationTimestamp.Time,
+		Labels:       statefulset.Labels,
+		Ports:        ports,
+		StartedAt:    startedAt,
+		RestartCount: restartCount,
 	}, nil
 }
 
+// mcpContainerStatus returns the start time and restart count of the MCP container of a pod
+func mcpContainerStatus(pod *corev1.Pod) (time.Time, int) {
+	for _, status := range pod.Status.ContainerStatuses {
+		if status.Name != mcpContainerName {
+			continue
+		}
+		var startedAt time.Time
+		if status.State.Running != nil {
+			startedAt = status.State.Running.StartedAt.Time
+		}
+		return startedAt, int(status.RestartCount)
+	}
+	return time.Time{}, 0
+}
+
 // IsWorkloadRunning implements runtime.Runtime.
 func (c *Client) IsWorkloadRunning(ctx context.Context, workloadID string) (bool, error) {
 	// In Kubernetes, workloadID is the statefulset name
@@ -512,7 +603,91 @@ func (c *Client) RemoveWorkload(ctx context.Context, workloadID string) error {
 }
 
 // StopWorkload implements runtime.Runtime.
-func (*Client) StopWorkload(_ context.Context, _ string) error {
+func (*Client) StopWorkload(_ context.Context, _ string, _ *runtime.StopOptions) error {
+	return nil
+}
+
+// PauseWorkload implements runtime.Runtime.
+func (*Client) PauseWorkload(_ context.Context, _ string) error {
+	return fmt.Errorf("pausing workloads is not supported in Kubernetes")
+}
+
+// WorkloadEvents implements runtime.Runtime.
+func (*Client) WorkloadEvents(_ context.Context, _ *runtime.EventOptions) (<-chan runtime.WorkloadEvent, <-chan error) {
+	workloadEvents := make(chan runtime.WorkloadEvent)
+	close(workloadEvents)
+	errs := make(chan error, 1)
+	errs <- fmt.Errorf("workload events are not supported in Kubernetes")
+	return workloadEvents, errs
+}
+
+// StartWorkload implements runtime.Runtime. The pods of a workload are restarted by Kubernetes.
+func (*Client) StartWorkload(_ context.Context, _ string) error {
+	return fmt.Errorf("starting workloads is not supported in Kubernetes, pods are restarted by Kubernetes")
+}
+
+// UnpauseWorkload implements runtime.Runtime.
+func (*Client) UnpauseWorkload(_ context.Context, _ string) error {
+	return fmt.Errorf("pausing workloads is not supported in Kubernetes")
+}
+
+// ExecInWorkload implements runtime.Runtime.
+func (c *Client) ExecInWorkload(
+	ctx context.Context,
+	workloadID string,
+	command []string,
+	options *runtime.ExecOptions,
+) (int, error) {
+	podName, err := c.findWorkloadPod(ctx, workloadID)
+	if err != nil {
+		return 0, err
+	}
+
+	execOpts := &corev1.PodExecOptions{
+		Container: mcpContainerName,
+		Command:   command,
+		Stdin:     options.Stdin != nil,
+		Stdout:    true,
+		Stderr:    !options.TTY,
+		TTY:       options.TTY,
+	}
+	req := c.client.CoreV1().RESTClient().Post().
+		Resource("pods").
+		Name(podName).
+		Namespace(getCurrentNamespace()).
+		SubResource("exec").
+		VersionedParams(execOpts, scheme.ParameterCodec)
+
+	if c.config == nil {
+		return 0, fmt.Errorf("no Kubernetes configuration available to exec in workload %s", workloadID)
+	}
+	exec, err := remotecommand.NewSPDYExecutor(c.config, "POST", req.URL())
+	if err != nil {
+		return 0, fmt.Errorf("failed to create SPDY executor: %v", err)
+	}
+
+	streamOpts := remotecommand.StreamOptions{
+		Stdin:  options.Stdin,
+		Stdout: options.Stdout,
+		Tty:    options.TTY,
+	}
+	if !options.TTY {
+		streamOpts.Stderr = options.Stderr
+	}
+	if err := exec.StreamWithContext(ctx, streamOpts); err != nil {
+		var exitErr utilexec.ExitError
+		if stderrors.As(err, &exitErr) {
+			return exitErr.ExitStatus(), nil
+		}
+		return 0, fmt.Errorf("failed to exec in pod %s: %w", podName, err)
+	}
+	return 0, nil
+}
+
+// UpdateNetworkPermissions implements runtime.Runtime.
+// Network permissions are not enforced for workloads in Kubernetes, so there is nothing to update.
+func (*Client) UpdateNetworkPermissions(_ context.Context, workloadName string, _ *permissions.Profile) error {
+	logger.Debugf("Network permissions are not enforced in Kubernetes, not updating workload %s", workloadName)
 	return nil
 }
 
@@ -920,6 +1095,18 @@ func createPodTemplateFromPatch(patchJSON string) (*corev1apply.PodTemplateSpecA
 	return ensureObjectMetaApplyConfigurationExists(&podTemplateSpec), nil
 }
 
+// setTerminationGracePeriod sets the termination grace period of the pod template to the stop timeout of the
+// workload, unless the pod template already has one
+func setTerminationGracePeriod(
+	podTemplateSpec *corev1apply.PodTemplateSpecApplyConfiguration,
+	options *runtime.DeployWorkloadOptions,
+) {
+	if options == nil || options.StopTimeout <= 0 || podTemplateSpec.Spec.TerminationGracePeriodSeconds != nil {
+		return
+	}
+	podTemplateSpec.Spec = podTemplateSpec.Spec.WithTerminationGracePeriodSeconds(int64(options.StopTimeout.Seconds()))
+}
+
 // ensurePodTemplateConfig ensures the pod template has required configuration
 func ensurePodTemplateConfig(
 	podTemplateSpec *corev1apply.PodTemplateSpecApplyConfiguration,
@@ -1064,6 +1251,92 @@ func configureContainer(
 	}
 }
 
+// warnUnsupportedRestartPolicy warns about restart policies which don't restart a workload whenever it exits,
+// since the pods of a stateful set are always restarted
+func warnUnsupportedRestartPolicy(options *runtime.DeployWorkloadOptions) {
+	if options == nil {
+		return
+	}
+	policy, err := runtime.ParseRestartPolicy(options.RestartPolicy)
+	if err == nil && (policy.Name == runtime.RestartAlways || policy.Name == runtime.RestartUnlessStopped) {
+		return
+	}
+	logger.Warnf("Ignoring the restart policy %s, since pods are always restarted by Kubernetes", options.RestartPolicy)
+}
+
+// warnUnsupportedNetworks warns about networks of the container runtime the workload should join,
+// since pods are connected to the network of the cluster
+func warnUnsupportedNetworks(options *runtime.DeployWorkloadOptions) {
+	if options != nil && len(options.Networks) > 0 {
+		logger.Warnf("Ignoring the networks of the workload, since they are not supported by the Kubernetes runtime")
+	}
+}
+
+// configureContainerResources sets the CPU, memory and GPU limits of a container, which take precedence over
+// the limits of the pod template patch. Swap and process limits are configured on the nodes of a cluster.
+func configureContainerResources(container *corev1apply.ContainerApplyConfiguration, options *runtime.DeployWorkloadOptions) {
+	if options == nil {
+		return
+	}
+	limits := corev1.ResourceList{}
+	if container.Resources != nil && container.Resources.Limits != nil {
+		limits = *container.Resources.Limits
+	}
+	addResourceLimits(limits, options.Resources)
+	addGPULimit(limits, options.GPUs)
+	if len(options.Devices) > 0 {
+		logger.Warnf("Ignoring the devices of the workload, since they are not supported by the Kubernetes runtime")
+	}
+	if len(limits) == 0 {
+		return
+	}
+
+	if container.Resources == nil {
+		container.WithResources(corev1apply.ResourceRequirements())
+	}
+	container.Resources.WithLimits(limits)
+}
+
+// addResourceLimits adds the CPU and memory limits of a workload to the limits of its container
+func addResourceLimits(limits corev1.ResourceList, resources *runtime.ResourceLimits) {
+	if resources == nil {
+		return
+	}
+	if resources.CPUs > 0 {
+		limits[corev1.ResourceCPU] = *resource.NewMilliQuantity(int64(resources.CPUs*1000), resource.DecimalSI)
+	}
+	if resources.Memory > 0 {
+		limits[corev1.ResourceMemory] = *resource.NewQuantity(resources.Memory, resource.BinarySI)
+	}
+	if resources.MemorySwap != 0 || resources.PidsLimit > 0 {
+		logger.Debugf("Swap and process limits are not supported by the Kubernetes runtime")
+	}
+}
+
+// addGPULimit adds the NVIDIA GPUs requested for a workload to the limits of its container. The device
+// plugin of the cluster chooses the GPUs, so GPUs requested by ID are requested by their number.
+func addGPULimit(limits corev1.ResourceList, value string) {
+	gpus, err := runtime.ParseGPURequest(value)
+	if err != nil {
+		logger.Warnf("Ignoring the GPUs of the workload: %v", err)
+		return
+	}
+	if gpus == nil {
+		return
+	}
+	count := gpus.Count
+	switch {
+	case count == runtime.AllGPUs:
+		logger.Warnf("Ignoring the request for all GPUs, since the Kubernetes runtime requires a number of GPUs")
+		return
+	case len(gpus.DeviceIDs) > 0:
+		logger.Warnf("GPUs cannot be chosen by ID with the Kubernetes runtime, requesting %d GPUs instead",
+			len(gpus.DeviceIDs))
+		count = len(gpus.DeviceIDs)
+	}
+	limits[nvidiaGPUResource] = *resource.NewQuantity(int64(count), resource.DecimalSI)
+}
+
 // configureMCPContainer configures the MCP container in the pod template
 func configureMCPContainer(
 	podTemplateSpec *corev1apply.PodTemplateSpecApplyConfiguration,
@@ -1083,6 +1356,7 @@ func configureMCPContainer(
 
 		// Configure the container
 		configureContainer(mcpContainer, image, command, attachStdio, envVarList)
+		configureContainerResources(mcpContainer, options)
 
 		// Configure ports if needed
 		if options != nil && transportType == string(transtypes.TransportTypeSSE) {
@@ -1098,6 +1372,7 @@ func configureMCPContainer(
 	} else {
 		// Configure the existing container
 		configureContainer(mcpContainer, image, command, attachStdio, envVarList)
+		configureContainerResources(mcpContainer, options)
 
 		// Configure ports if needed
 		if options != nil && transportType == string(transtypes.TransportTypeSSE) {
@@ -9,6 +9,7 @@ import (
 	"github.com/stretchr/testify/require"
 	appsv1 "k8s.io/api/apps/v1"
 	corev1 "k8s.io/api/core/v1"
+	"k8s.io/apimachinery/pkg/api/resource"
 	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
 	corev1apply "k8s.io/client-go/applyconfigurations/core/v1"
 	"k8s.io/client-go/kubernetes"
@@ -565,6 +566,85 @@ func TestConfigureMCPContainer(t *testing.T) {
 }
 
 // TestCreateContainerWithMCP tests the CreateContainer function with MCP container configuration
+func TestConfigureContainerResources(t *testing.T) {
+	t.Parallel()
+
+	testCases := []struct {
+		name     string
+		patch    *corev1apply.ResourceRequirementsApplyConfiguration
+		options  *runtime.DeployWorkloadOptions
+		expected corev1.ResourceList
+	}{
+		{
+			name:     "no options",
+			options:  nil,
+			expected: nil,
+		},
+		{
+			name: "CPU, memory and GPUs",
+			options: &runtime.DeployWorkloadOptions{
+				Resources: &runtime.ResourceLimits{CPUs: 0.5, Memory: 512 << 20, PidsLimit: 100},
+				GPUs:      "2",
+			},
+			expected: corev1.ResourceList{
+				corev1.ResourceCPU:    resource.MustParse("500m"),
+				corev1.ResourceMemory: resource.MustParse("512Mi"),
+				nvidiaGPUResource:     resource.MustParse("2"),
+			},
+		},
+		{
+			name:    "GPUs by ID",
+			options: &runtime.DeployWorkloadOptions{GPUs: "device=0,1"},
+			expected: corev1.ResourceList{
+				nvidiaGPUResource: resource.MustParse("2"),
+			},
+		},
+		{
+			name:     "all GPUs",
+			options:  &runtime.DeployWorkloadOptions{GPUs: "all"},
+			expected: nil,
+		},
+		{
+			name: "limits of the patch are kept",
+			patch: corev1apply.ResourceRequirements().WithLimits(corev1.ResourceList{
+				corev1.ResourceCPU:              resource.MustParse("1"),
+				corev1.ResourceEphemeralStorage: resource.MustParse("1Gi"),
+			}),
+			options: &runtime.DeployWorkloadOptions{Resources: &runtime.ResourceLimits{CPUs: 2}},
+			expected: corev1.ResourceList{
+				corev1.ResourceCPU:              resource.MustParse("2"),
+				corev1.ResourceEphemeralStorage: resource.MustParse("1Gi"),
+			},
+		},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			container := corev1apply.Container().WithName(mcpContainerName)
+			if tc.patch != nil {
+				container.WithResources(tc.patch)
+			}
+
+			configureContainerResources(container, tc.options)
+
+			if tc.expected == nil {
+				assert.Nil(t, container.Resources)
+				return
+			}
+			require.NotNil(t, container.Resources)
+			require.NotNil(t, container.Resources.Limits)
+			limits := *container.Resources.Limits
+			assert.Len(t, limits, len(tc.expected))
+			for name, quantity := range tc.expected {
+				actual, ok := limits[name]
+				require.True(t, ok, "missing limit %s", name)
+				assert.Zero(t, quantity.Cmp(actual), "limit %s is %s, expected %s", name, actual.String(), quantity.String())
+			}
+		})
+	}
+}
+
 func TestCreateContainerWithMCP(t *testing.T) {
 	t.Parallel()
 	// Test cases
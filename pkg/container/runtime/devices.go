@@ -0,0 +1,111 @@
+package runtime
+
+import (
+	"fmt"
+	"path/filepath"
+	"strconv"
+	"strings"
+)
+
+// AllGPUs is the count of a GPU request for all GPUs of the host
+const AllGPUs = -1
+
+// GPURequest is a request for GPUs of the host, in the format of the --gpus flag of the Docker CLI
+type GPURequest struct {
+	// Count is the number of GPUs, or AllGPUs for all GPUs of the host
+	Count int
+	// DeviceIDs are the indexes or UUIDs of the requested GPUs, if they are requested by ID
+	DeviceIDs []string
+}
+
+// ParseGPURequest parses a GPU request, which is "all", a number of GPUs or "device=" followed by a
+// comma separated list of GPU indexes or UUIDs. An empty request requests no GPUs.
+func ParseGPURequest(value string) (*GPURequest, error) {
+	switch {
+	case value == "":
+		return nil, nil
+	case value == "all":
+		return &GPURequest{Count: AllGPUs}, nil
+	case strings.HasPrefix(value, "device="):
+		var ids []string
+		for _, id := range strings.Split(strings.TrimPrefix(value, "device="), ",") {
+			if id = strings.TrimSpace(id); id != "" {
+				ids = append(ids, id)
+			}
+		}
+		if len(ids) == 0 {
+			return nil, fmt.Errorf("invalid GPU request: %s (no device IDs)", value)
+		}
+		return &GPURequest{DeviceIDs: ids}, nil
+	}
+
+	count, err := strconv.Atoi(value)
+	if err != nil || count <= 0 {
+		return nil, fmt.Errorf("invalid GPU request: %s (expected all, a number of GPUs or device=ID[,ID...])", value)
+	}
+	return &GPURequest{Count: count}, nil
+}
+
+// Device is a device of the host which is made available to a workload
+type Device struct {
+	// CDIName is the fully qualified name of a Container Device Interface device (e.g., nvidia.com/gpu=all).
+	// If it is set, the paths and permissions are not used.
+	CDIName string
+	// PathOnHost is the path of the device on the host
+	PathOnHost string
+	// PathInContainer is the path of the device in the container
+	PathInContainer string
+	// Permissions are the cgroup permissions of the device (e.g., rwm)
+	Permissions string
+}
+
+// IsCDIDeviceName returns true if the value is the fully qualified name of a CDI device,
+// which has the format vendor.com/class=name
+func IsCDIDeviceName(value string) bool {
+	kind, name, found := strings.Cut(value, "=")
+	if !found || name == "" {
+		return false
+	}
+	vendor, class, found := strings.Cut(kind, "/")
+	return found && vendor != "" && class != "" && !strings.HasPrefix(kind, "/")
+}
+
+// ParseDevice parses a device, which is a CDI device name or has the format
+// host-path[:container-path][:permissions] like the --device flag of the Docker CLI.
+// By default the device has the same path in the container and all permissions.
+func ParseDevice(value string) (Device, error) {
+	if IsCDIDeviceName(value) {
+		return Device{CDIName: value}, nil
+	}
+
+	parts := strings.Split(value, ":")
+	if len(parts) > 3 {
+		return Device{}, fmt.Errorf("invalid device: %s (expected host-path[:container-path][:permissions])", value)
+	}
+	device := Device{PathOnHost: parts[0], PathInContainer: parts[0], Permissions: "rwm"}
+	switch {
+	case len(parts) == 3:
+		device.PathInContainer = parts[1]
+		device.Permissions = parts[2]
+	case len(parts) == 2 && isDevicePermissions(parts[1]):
+		device.Permissions = parts[1]
+	case len(parts) == 2:
+		device.PathInContainer = parts[1]
+	}
+
+	if !filepath.IsAbs(device.PathOnHost) || !filepath.IsAbs(device.PathInContainer) {
+		return Device{}, fmt.Errorf("invalid device: %s (device paths must be absolute)", value)
+	}
+	if !isDevicePermissions(device.Permissions) {
+		return Device{}, fmt.Errorf("invalid device: %s (permissions must be a combination of r, w and m)", value)
+	}
+	return device, nil
+}
+
+// isDevicePermissions returns true if the value is a combination of the cgroup device permissions r, w and m
+func isDevicePermissions(value string) bool {
+	if value == "" || len(value) > 3 {
+		return false
+	}
+	return strings.Trim(value, "rwm") == ""
+}
@@ -0,0 +1,78 @@
+package runtime
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestParseGPURequest(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		value    string
+		expected *GPURequest
+		wantErr  bool
+	}{
+		{value: "", expected: nil},
+		{value: "all", expected: &GPURequest{Count: AllGPUs}},
+		{value: "2", expected: &GPURequest{Count: 2}},
+		{value: "device=0, 2", expected: &GPURequest{DeviceIDs: []string{"0", "2"}}},
+		{value: "device=GPU-3a23c669", expected: &GPURequest{DeviceIDs: []string{"GPU-3a23c669"}}},
+		{value: "device=", wantErr: true},
+		{value: "0", wantErr: true},
+		{value: "some", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.value, func(t *testing.T) {
+			t.Parallel()
+			request, err := ParseGPURequest(tt.value)
+			if tt.wantErr {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.expected, request)
+		})
+	}
+}
+
+func TestParseDevice(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		value    string
+		expected Device
+		wantErr  bool
+	}{
+		{value: "nvidia.com/gpu=all", expected: Device{CDIName: "nvidia.com/gpu=all"}},
+		{value: "/dev/dri", expected: Device{PathOnHost: "/dev/dri", PathInContainer: "/dev/dri", Permissions: "rwm"}},
+		{value: "/dev/dri:r", expected: Device{PathOnHost: "/dev/dri", PathInContainer: "/dev/dri", Permissions: "r"}},
+		{
+			value:    "/dev/kfd:/dev/amd",
+			expected: Device{PathOnHost: "/dev/kfd", PathInContainer: "/dev/amd", Permissions: "rwm"},
+		},
+		{
+			value:    "/dev/kfd:/dev/amd:rw",
+			expected: Device{PathOnHost: "/dev/kfd", PathInContainer: "/dev/amd", Permissions: "rw"},
+		},
+		{value: "dev/dri", wantErr: true},
+		{value: "/dev/dri:/dev/dri:rx", wantErr: true},
+		{value: "/dev/dri:/dev/dri:r:w", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.value, func(t *testing.T) {
+			t.Parallel()
+			device, err := ParseDevice(tt.value)
+			if tt.wantErr {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.expected, device)
+		})
+	}
+}
@@ -13,6 +13,7 @@ import (
 	context "context"
 	io "io"
 	reflect "reflect"
+	time "time"
 
 	runtime "github.com/stacklok/toolhive/pkg/container/runtime"
 	permissions "github.com/stacklok/toolhive/pkg/permissions"
@@ -75,6 +76,21 @@ func (mr *MockRuntimeMockRecorder) DeployWorkload(ctx, image, name, command, env
 	return mr.mock.ctrl.RecordCallWithMethodType(mr.mock, "DeployWorkload", reflect.TypeOf((*MockRuntime)(nil).DeployWorkload), ctx, image, name, command, envVars, labels, permissionProfile, transportType, options, isolateNetwork)
 }
 
+// ExecInWorkload mocks base method.
+func (m *MockRuntime) ExecInWorkload(ctx context.Context, workloadID string, command []string, options *runtime.ExecOptions) (int, error) {
+	m.ctrl.T.Helper()
+	ret := m.ctrl.Call(m, "ExecInWorkload", ctx, workloadID, command, options)
+	ret0, _ := ret[0].(int)
+	ret1, _ := ret[1].(error)
+	return ret0, ret1
+}
+
+// ExecInWorkload indicates an expected call of ExecInWorkload.
+func (mr *MockRuntimeMockRecorder) ExecInWorkload(ctx, workloadID, command, options any) *gomock.Call {
+	mr.mock.ctrl.T.Helper()
+	return mr.mock.ctrl.RecordCallWithMethodType(mr.mock, "ExecInWorkload", reflect.TypeOf((*MockRuntime)(nil).ExecInWorkload), ctx, workloadID, command, options)
+}
+
 // GetWorkloadInfo mocks base method.
 func (m *MockRuntime) GetWorkloadInfo(ctx context.Context, workloadID string) (runtime.ContainerInfo, error) {
 	m.ctrl.T.Helper()
@@ -91,18 +107,18 @@ func (mr *MockRuntimeMockRecorder) GetWorkloadInfo(ctx, workloadID any) *gomock.
 }
 
 // GetWorkloadLogs mocks base method.
-func (m *MockRuntime) GetWorkloadLogs(ctx context.Context, workloadID string, follow bool) (string, error) {
+func (m *MockRuntime) GetWorkloadLogs(ctx context.Context, workloadID string, options runtime.LogOptions) (string, error) {
 	m.ctrl.T.Helper()
-	ret := m.ctrl.Call(m, "GetWorkloadLogs", ctx, workloadID, follow)
+	ret := m.ctrl.Call(m, "GetWorkloadLogs", ctx, workloadID, options)
 	ret0, _ := ret[0].(string)
 	ret1, _ := ret[1].(error)
 	return ret0, ret1
 }
 
 // GetWorkloadLogs indicates an expected call of GetWorkloadLogs.
-func (mr *MockRuntimeMockRecorder) GetWorkloadLogs(ctx, workloadID, follow any) *gomock.Call {
+func (mr *MockRuntimeMockRecorder) GetWorkloadLogs(ctx, workloadID, options any) *gomock.Call {
 	mr.mock.ctrl.T.Helper()
-	return mr.mock.ctrl.RecordCallWithMethodType(mr.mock, "GetWorkloadLogs", reflect.TypeOf((*MockRuntime)(nil).GetWorkloadLogs), ctx, workloadID, follow)
+	return mr.mock.ctrl.RecordCallWithMethodType(mr.mock, "GetWorkloadLogs", reflect.TypeOf((*MockRuntime)(nil).GetWorkloadLogs), ctx, workloadID, options)
 }
 
 // IsRunning mocks base method.
@@ -149,6 +165,20 @@ func (mr *MockRuntimeMockRecorder) ListWorkloads(ctx any) *gomock.Call {
 	return mr.mock.ctrl.RecordCallWithMethodType(mr.mock, "ListWorkloads", reflect.TypeOf((*MockRuntime)(nil).ListWorkloads), ctx)
 }
 
+// PauseWorkload mocks base method.
+func (m *MockRuntime) PauseWorkload(ctx context.Context, workloadID string) error {
+	m.ctrl.T.Helper()
+	ret := m.ctrl.Call(m, "PauseWorkload", ctx, workloadID)
+	ret0, _ := ret[0].(error)
+	return ret0
+}
+
+// PauseWorkload indicates an expected call of PauseWorkload.
+func (mr *MockRuntimeMockRecorder) PauseWorkload(ctx, workloadID any) *gomock.Call {
+	mr.mock.ctrl.T.Helper()
+	return mr.mock.ctrl.RecordCallWithMethodType(mr.mock, "PauseWorkload", reflect.TypeOf((*MockRuntime)(nil).PauseWorkload), ctx, workloadID)
+}
+
 // RemoveWorkload mocks base method.
 func (m *MockRuntime) RemoveWorkload(ctx context.Context, workloadID string) error {
 	m.ctrl.T.Helper()
@@ -163,18 +193,89 @@ func (mr *MockRuntimeMockRecorder) RemoveWorkload(ctx, workloadID any) *gomock.C
 	return mr.mock.ctrl.RecordCallWithMethodType(mr.mock, "RemoveWorkload", reflect.TypeOf((*MockRuntime)(nil).RemoveWorkload), ctx, workloadID)
 }
 
+// StartWorkload mocks base method.
+func (m *MockRuntime) StartWorkload(ctx context.Context, workloadID string) error {
+	m.ctrl.T.Helper()
+	ret := m.ctrl.Call(m, "StartWorkload", ctx, workloadID)
+	ret0, _ := ret[0].(error)
+	return ret0
+}
+
+// StartWorkload indicates an expected call of StartWorkload.
+func (mr *MockRuntimeMockRecorder) StartWorkload(ctx, workloadID any) *gomock.Call {
+	mr.mock.ctrl.T.Helper()
+	return mr.mock.ctrl.RecordCallWithMethodType(mr.mock, "StartWorkload", reflect.TypeOf((*MockRuntime)(nil).StartWorkload), ctx, workloadID)
+}
+
+// StreamWorkloadLogs mocks base method.
+func (m *MockRuntime) StreamWorkloadLogs(ctx context.Context, workloadID string, since time.Time, stdout, stderr io.Writer) error {
+	m.ctrl.T.Helper()
+	ret := m.ctrl.Call(m, "StreamWorkloadLogs", ctx, workloadID, since, stdout, stderr)
+	ret0, _ := ret[0].(error)
+	return ret0
+}
+
+// StreamWorkloadLogs indicates an expected call of StreamWorkloadLogs.
+func (mr *MockRuntimeMockRecorder) StreamWorkloadLogs(ctx, workloadID, since, stdout, stderr any) *gomock.Call {
+	mr.mock.ctrl.T.Helper()
+	return mr.mock.ctrl.RecordCallWithMethodType(mr.mock, "StreamWorkloadLogs", reflect.TypeOf((*MockRuntime)(nil).StreamWorkloadLogs), ctx, workloadID, since, stdout, stderr)
+}
+
 // StopWorkload mocks base method.
-func (m *MockRuntime) StopWorkload(ctx context.Context, workloadID string) error {
+func (m *MockRuntime) StopWorkload(ctx context.Context, workloadID string, options *runtime.StopOptions) error {
 	m.ctrl.T.Helper()
-	ret := m.ctrl.Call(m, "StopWorkload", ctx, workloadID)
+	ret := m.ctrl.Call(m, "StopWorkload", ctx, workloadID, options)
 	ret0, _ := ret[0].(error)
 	return ret0
 }
 
 // StopWorkload indicates an expected call of StopWorkload.
-func (mr *MockRuntimeMockRecorder) StopWorkload(ctx, workloadID any) *gomock.Call {
+func (mr *MockRuntimeMockRecorder) StopWorkload(ctx, workloadID, options any) *gomock.Call {
+	mr.mock.ctrl.T.Helper()
+	return mr.mock.ctrl.RecordCallWithMethodType(mr.mock, "StopWorkload", reflect.TypeOf((*MockRuntime)(nil).StopWorkload), ctx, workloadID, options)
+}
+
+// UnpauseWorkload mocks base method.
+func (m *MockRuntime) UnpauseWorkload(ctx context.Context, workloadID string) error {
+	m.ctrl.T.Helper()
+	ret := m.ctrl.Call(m, "UnpauseWorkload", ctx, workloadID)
+	ret0, _ := ret[0].(error)
+	return ret0
+}
+
+// UnpauseWorkload indicates an expected call of UnpauseWorkload.
+func (mr *MockRuntimeMockRecorder) UnpauseWorkload(ctx, workloadID any) *gomock.Call {
+	mr.mock.ctrl.T.Helper()
+	return mr.mock.ctrl.RecordCallWithMethodType(mr.mock, "UnpauseWorkload", reflect.TypeOf((*MockRuntime)(nil).UnpauseWorkload), ctx, workloadID)
+}
+
+// UpdateNetworkPermissions mocks base method.
+func (m *MockRuntime) UpdateNetworkPermissions(ctx context.Context, workloadName string, permissionProfile *permissions.Profile) error {
+	m.ctrl.T.Helper()
+	ret := m.ctrl.Call(m, "UpdateNetworkPermissions", ctx, workloadName, permissionProfile)
+	ret0, _ := ret[0].(error)
+	return ret0
+}
+
+// UpdateNetworkPermissions indicates an expected call of UpdateNetworkPermissions.
+func (mr *MockRuntimeMockRecorder) UpdateNetworkPermissions(ctx, workloadName, permissionProfile any) *gomock.Call {
+	mr.mock.ctrl.T.Helper()
+	return mr.mock.ctrl.RecordCallWithMethodType(mr.mock, "UpdateNetworkPermissions", reflect.TypeOf((*MockRuntime)(nil).UpdateNetworkPermissions), ctx, workloadName, permissionProfile)
+}
+
+// WorkloadEvents mocks base method.
+func (m *MockRuntime) WorkloadEvents(ctx context.Context, options *runtime.EventOptions) (<-chan runtime.WorkloadEvent, <-chan error) {
+	m.ctrl.T.Helper()
+	ret := m.ctrl.Call(m, "WorkloadEvents", ctx, options)
+	ret0, _ := ret[0].(<-chan runtime.WorkloadEvent)
+	ret1, _ := ret[1].(<-chan error)
+	return ret0, ret1
+}
+
+// WorkloadEvents indicates an expected call of WorkloadEvents.
+func (mr *MockRuntimeMockRecorder) WorkloadEvents(ctx, options any) *gomock.Call {
 	mr.mock.ctrl.T.Helper()
-	return mr.mock.ctrl.RecordCallWithMethodType(mr.mock, "StopWorkload", reflect.TypeOf((*MockRuntime)(nil).StopWorkload), ctx, workloadID)
+	return mr.mock.ctrl.RecordCallWithMethodType(mr.mock, "WorkloadEvents", reflect.TypeOf((*MockRuntime)(nil).WorkloadEvents), ctx, options)
 }
 
 // MockMonitor is a mock of Monitor interface.
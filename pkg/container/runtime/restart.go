@@ -0,0 +1,83 @@
+package runtime
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+// Restart policies of workloads
+const (
+	// RestartNo never restarts a workload
+	RestartNo = "no"
+	// RestartAlways restarts a workload whenever it exits
+	RestartAlways = "always"
+	// RestartOnFailure restarts a workload when it exits with a non-zero exit code
+	RestartOnFailure = "on-failure"
+	// RestartUnlessStopped restarts a workload whenever it exits, unless it was stopped
+	RestartUnlessStopped = "unless-stopped"
+)
+
+// DefaultRestartPolicy is the restart policy of workloads which don't configure one
+const DefaultRestartPolicy = RestartUnlessStopped
+
+// RestartPolicy is the policy for restarting a workload after it exited
+type RestartPolicy struct {
+	// Name is the name of the policy (no, always, on-failure or unless-stopped)
+	Name string
+	// MaximumRetryCount is the number of times a failed workload is restarted with the on-failure
+	// policy, where zero is unlimited
+	MaximumRetryCount int
+}
+
+// ParseRestartPolicy parses a restart policy in the format of the --restart flag of the Docker CLI,
+// which is no, always, unless-stopped or on-failure[:max-retries]. An empty policy is the default policy.
+func ParseRestartPolicy(value string) (RestartPolicy, error) {
+	if value == "" {
+		return RestartPolicy{Name: DefaultRestartPolicy}, nil
+	}
+
+	name, retries, hasRetries := strings.Cut(value, ":")
+	switch name {
+	case RestartNo, RestartAlways, RestartUnlessStopped:
+		if hasRetries {
+			return RestartPolicy{}, fmt.Errorf("invalid restart policy: %s (maximum retries are only supported by %s)",
+				value, RestartOnFailure)
+		}
+		return RestartPolicy{Name: name}, nil
+	case RestartOnFailure:
+		policy := RestartPolicy{Name: name}
+		if hasRetries {
+			count, err := strconv.Atoi(retries)
+			if err != nil || count < 0 {
+				return RestartPolicy{}, fmt.Errorf("invalid restart policy: %s (maximum retries must be a number, not negative)", value)
+			}
+			policy.MaximumRetryCount = count
+		}
+		return policy, nil
+	default:
+		return RestartPolicy{}, fmt.Errorf("invalid restart policy: %s (expected %s, %s, %s or %s[:max-retries])",
+			value, RestartNo, RestartAlways, RestartUnlessStopped, RestartOnFailure)
+	}
+}
+
+// String returns the restart policy in the format of ParseRestartPolicy
+func (p RestartPolicy) String() string {
+	if p.Name == RestartOnFailure && p.MaximumRetryCount > 0 {
+		return fmt.Sprintf("%s:%d", p.Name, p.MaximumRetryCount)
+	}
+	return p.Name
+}
+
+// ShouldRestart returns true if a workload which exited with the exit code, and was already restarted
+// the given number of times, is to be restarted
+func (p RestartPolicy) ShouldRestart(exitCode, restarts int) bool {
+	switch p.Name {
+	case RestartAlways, RestartUnlessStopped:
+		return true
+	case RestartOnFailure:
+		return exitCode != 0 && (p.MaximumRetryCount == 0 || restarts < p.MaximumRetryCount)
+	default:
+		return false
+	}
+}
@@ -0,0 +1,89 @@
+package runtime
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestParseRestartPolicy(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		value    string
+		expected RestartPolicy
+		wantErr  bool
+	}{
+		{value: "", expected: RestartPolicy{Name: RestartUnlessStopped}},
+		{value: "no", expected: RestartPolicy{Name: RestartNo}},
+		{value: "always", expected: RestartPolicy{Name: RestartAlways}},
+		{value: "unless-stopped", expected: RestartPolicy{Name: RestartUnlessStopped}},
+		{value: "on-failure", expected: RestartPolicy{Name: RestartOnFailure}},
+		{value: "on-failure:3", expected: RestartPolicy{Name: RestartOnFailure, MaximumRetryCount: 3}},
+		{value: "on-failure:-1", wantErr: true},
+		{value: "on-failure:many", wantErr: true},
+		{value: "always:3", wantErr: true},
+		{value: "sometimes", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.value, func(t *testing.T) {
+			t.Parallel()
+			policy, err := ParseRestartPolicy(tt.value)
+			if tt.wantErr {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.expected, policy)
+		})
+	}
+}
+
+func TestRestartPolicy_ShouldRestart(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name     string
+		policy   RestartPolicy
+		exitCode int
+		restarts int
+		expected bool
+	}{
+		{name: "no", policy: RestartPolicy{Name: RestartNo}, exitCode: 1, expected: false},
+		{name: "always after success", policy: RestartPolicy{Name: RestartAlways}, expected: true},
+		{name: "unless-stopped after failure", policy: RestartPolicy{Name: RestartUnlessStopped}, exitCode: 1, expected: true},
+		{name: "on-failure after success", policy: RestartPolicy{Name: RestartOnFailure}, expected: false},
+		{name: "on-failure after failure", policy: RestartPolicy{Name: RestartOnFailure}, exitCode: 137, restarts: 10, expected: true},
+		{
+			name:     "on-failure within retries",
+			policy:   RestartPolicy{Name: RestartOnFailure, MaximumRetryCount: 3},
+			exitCode: 1,
+			restarts: 2,
+			expected: true,
+		},
+		{
+			name:     "on-failure out of retries",
+			policy:   RestartPolicy{Name: RestartOnFailure, MaximumRetryCount: 3},
+			exitCode: 1,
+			restarts: 3,
+			expected: false,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, tt.policy.ShouldRestart(tt.exitCode, tt.restarts))
+		})
+	}
+}
+
+func TestRestartPolicy_String(t *testing.T) {
+	t.Parallel()
+
+	assert.Equal(t, "unless-stopped", RestartPolicy{Name: RestartUnlessStopped}.String())
+	assert.Equal(t, "on-failure", RestartPolicy{Name: RestartOnFailure}.String())
+	assert.Equal(t, "on-failure:5", RestartPolicy{Name: RestartOnFailure, MaximumRetryCount: 5}.String())
+}
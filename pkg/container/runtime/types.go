@@ -5,6 +5,7 @@ package runtime
 import (
 	"context"
 	"io"
+	"os"
 	"time"
 
 	"github.com/stacklok/toolhive/pkg/permissions"
@@ -18,6 +19,8 @@ type ContainerInfo struct {
 	Name string
 	// Image is the container image
 	Image string
+	// ImageID is the ID of the image the container was created from
+	ImageID string
 	// Status is the container status
 	Status string
 	// State is the container state
@@ -28,6 +31,15 @@ type ContainerInfo struct {
 	Labels map[string]string
 	// Ports is the container port mappings
 	Ports []PortMapping
+	// StartedAt is the time the container was last started.
+	// It is only set by GetWorkloadInfo.
+	StartedAt time.Time
+	// RestartCount is the number of times the container was restarted by the runtime.
+	// It is only set by GetWorkloadInfo.
+	RestartCount int
+	// ExitCode is the exit code of the last run of the container, if it exited.
+	// It is only set by GetWorkloadInfo.
+	ExitCode int
 }
 
 // PortMapping represents a port mapping for a container
@@ -90,10 +102,21 @@ type Runtime interface {
 	// status, and resource usage.
 	ListWorkloads(ctx context.Context) ([]ContainerInfo, error)
 
+	// StartWorkload starts a stopped or exited workload again, with the configuration it was deployed with.
+	StartWorkload(ctx context.Context, workloadID string) error
+
 	// StopWorkload gracefully stops a running workload and all its components.
 	// This includes stopping the primary container, sidecars, and cleaning up
 	// any associated network resources. The workload remains available for restart.
-	StopWorkload(ctx context.Context, workloadID string) error
+	// If options is nil, the stop timeout and signal the workload was deployed with are used.
+	StopWorkload(ctx context.Context, workloadID string, options *StopOptions) error
+
+	// PauseWorkload freezes the processes of a running workload. Their state is kept
+	// in memory, so the workload continues where it left off when it is unpaused.
+	PauseWorkload(ctx context.Context, workloadID string) error
+
+	// UnpauseWorkload resumes the processes of a paused workload.
+	UnpauseWorkload(ctx context.Context, workloadID string) error
 
 	// RemoveWorkload completely removes a workload and all its components.
 	// This includes removing containers, cleaning up networks, volumes,
@@ -102,10 +125,16 @@ type Runtime interface {
 	RemoveWorkload(ctx context.Context, workloadID string) error
 
 	// GetWorkloadLogs retrieves logs from the primary container of the workload.
-	// If follow is true, the logs will be streamed continuously.
+	// If options.Follow is true, the logs will be streamed continuously.
 	// For workloads with multiple containers, this returns logs from the
 	// main MCP server container.
-	GetWorkloadLogs(ctx context.Context, workloadID string, follow bool) (string, error)
+	GetWorkloadLogs(ctx context.Context, workloadID string, options LogOptions) (string, error)
+
+	// StreamWorkloadLogs follows the logs of the primary container of the workload written since
+	// the given time, copying its output and error streams to stdout and stderr until the context
+	// is cancelled or the workload stops. A nil writer leaves out its stream, e.g. the output of
+	// stdio servers, which is the MCP traffic rather than logs.
+	StreamWorkloadLogs(ctx context.Context, workloadID string, since time.Time, stdout, stderr io.Writer) error
 
 	// IsWorkloadRunning checks if a workload is currently running and healthy.
 	// This verifies that the primary container is running and that any
@@ -122,6 +151,20 @@ type Runtime interface {
 	// for stdio transport where direct input/output streaming is required.
 	AttachToWorkload(ctx context.Context, workloadID string) (io.WriteCloser, io.ReadCloser, error)
 
+	// ExecInWorkload runs a command in the primary container of a running workload,
+	// streaming its input and output as described by options, and waits for it to exit.
+	// Returns the exit code of the command.
+	ExecInWorkload(ctx context.Context, workloadID string, command []string, options *ExecOptions) (int, error)
+
+	// UpdateNetworkPermissions applies the network permissions of the profile, extended with its grants
+	// which have not expired, to a running workload, e.g. to revoke a temporary grant once it expires.
+	UpdateNetworkPermissions(ctx context.Context, workloadName string, permissionProfile *permissions.Profile) error
+
+	// WorkloadEvents returns the lifecycle and health events of the workloads managed by this runtime.
+	// The event channel is closed once all events were returned, unless options.Follow is set,
+	// in which case new events are returned until the context is cancelled. Errors are sent on the error channel.
+	WorkloadEvents(ctx context.Context, options *EventOptions) (<-chan WorkloadEvent, <-chan error)
+
 	// IsRunning checks the health of the container runtime.
 	// This is used to verify that the runtime is operational and can manage workloads.
 	IsRunning(ctx context.Context) error
@@ -147,8 +190,23 @@ const (
 	TypeDocker Type = "docker"
 	// TypeKubernetes represents the Kubernetes runtime
 	TypeKubernetes Type = "kubernetes"
+	// TypeApple represents the container CLI of Apple's containerization framework on macOS
+	TypeApple Type = "apple"
+	// TypeContainerd represents containerd, managed through its CRI API, on hosts without Docker or Podman
+	TypeContainerd Type = "containerd"
+	// TypeFinch represents Finch, which runs containerd in a virtual machine on macOS and Windows
+	TypeFinch Type = "finch"
 )
 
+// SelectionEnv is the environment variable selecting the container runtime instead of detecting it
+const SelectionEnv = "TOOLHIVE_RUNTIME"
+
+// Selected returns the container runtime selected in the environment, or an empty type if the
+// runtime is detected
+func Selected() Type {
+	return Type(os.Getenv(SelectionEnv))
+}
+
 // PermissionConfig represents container permission configuration
 type PermissionConfig struct {
 	// Mounts is the list of volume mounts
@@ -161,6 +219,12 @@ type PermissionConfig struct {
 	CapAdd []string
 	// SecurityOpt is the list of security options
 	SecurityOpt []string
+	// Isolation is the isolation technology of Windows containers (process or hyperv),
+	// or empty for the default of the runtime
+	Isolation string
+	// UsernsMode is the user namespace mode of the container, e.g. keep-id under rootless Podman,
+	// or empty for the default of the runtime
+	UsernsMode string
 }
 
 // DeployWorkloadOptions represents configuration options for deploying a workload.
@@ -188,6 +252,118 @@ type DeployWorkloadOptions struct {
 	// SSEHeadlessServiceName is the name of the Kubernetes service to use for the workload
 	// Only applicable when using Kubernetes runtime and SSE transport
 	SSEHeadlessServiceName string
+
+	// LogConfig is the logging configuration for the workload
+	// If nil, the runtime's default logging configuration is used
+	LogConfig *LogConfig
+
+	// StopTimeout is the default time to wait for the workload to exit after the stop signal
+	// before it is killed. If zero, the runtime's default timeout is used.
+	StopTimeout time.Duration
+
+	// StopSignal is the default signal used to stop the workload (e.g., SIGTERM, SIGINT)
+	// If empty, the image's stop signal is used
+	StopSignal string
+
+	// Resources are the resource limits of the workload
+	// If nil, the workload is not limited
+	Resources *ResourceLimits
+
+	// GPUs are the GPUs of the host made available to the workload, in the format of ParseGPURequest
+	// If empty, no GPUs are made available
+	GPUs string
+
+	// Devices are the devices of the host made available to the workload, in the format of ParseDevice
+	Devices []string
+
+	// RestartPolicy is the policy for restarting the workload after it exited, in the format of
+	// ParseRestartPolicy. If empty, DefaultRestartPolicy is used.
+	RestartPolicy string
+
+	// Networks are existing networks of the container runtime which the workload joins
+	// in addition to the networks created by ToolHive
+	Networks []string
+}
+
+// ResourceLimits are the resource limits of a workload. Zero values are unlimited.
+type ResourceLimits struct {
+	// CPUs is the number of CPUs the workload may use (e.g., 0.5)
+	CPUs float64
+	// Memory is the memory limit in bytes
+	Memory int64
+	// MemorySwap is the limit of memory and swap in bytes, or -1 for unlimited swap
+	MemorySwap int64
+	// PidsLimit is the maximum number of processes of the workload
+	PidsLimit int64
+}
+
+// StopOptions represents options for stopping a workload
+type StopOptions struct {
+	// Timeout is the time to wait for the workload to exit after the stop signal before it is killed.
+	// If zero, the workload's default stop timeout is used.
+	Timeout time.Duration
+
+	// Signal is the signal used to stop the workload (e.g., SIGTERM, SIGINT)
+	// If empty, the workload's default stop signal is used.
+	Signal string
+}
+
+// ExecOptions represents the options for running a command in a workload
+type ExecOptions struct {
+	// Stdin is the input of the command, or nil if the command reads no input
+	Stdin io.Reader
+
+	// Stdout and Stderr receive the output of the command
+	Stdout io.Writer
+	Stderr io.Writer
+
+	// TTY allocates a pseudo-terminal for the command, in which case all output is written to Stdout
+	TTY bool
+
+	// TerminalSize is the initial size of the pseudo-terminal as height and width, if known
+	TerminalSize *[2]uint
+}
+
+// WorkloadEvent represents a lifecycle or health transition of a workload reported by the runtime
+type WorkloadEvent struct {
+	// Time is when the transition happened
+	Time time.Time
+	// WorkloadID is the ID of the workload
+	WorkloadID string
+	// Action is the transition, e.g. start, die, pause or health_status: unhealthy
+	Action string
+	// Attributes are the labels of the workload and the details of the transition reported by the runtime
+	Attributes map[string]string
+}
+
+// DefaultLogTail is the number of lines from the end of the logs of a workload shown by default
+const DefaultLogTail = 100
+
+// LogOptions represents options for retrieving the logs of a workload
+type LogOptions struct {
+	// Follow streams new logs to stdout until the context is cancelled or the workload stops
+	Follow bool
+	// Tail is the number of lines from the end of the logs to return. If zero, all the logs are returned.
+	Tail int
+	// Since is the time of the oldest logs to return. If zero, logs of any time are returned.
+	Since time.Time
+}
+
+// EventOptions represents options for retrieving workload events
+type EventOptions struct {
+	// Since is the time of the oldest event to return. If zero, only new events are returned.
+	Since time.Time
+	// Follow returns new events until the context is cancelled, instead of stopping at the current time
+	Follow bool
+}
+
+// LogConfig represents the logging configuration of a workload
+type LogConfig struct {
+	// Driver is the log driver to use (e.g., json-file, local, journald)
+	// If empty, the runtime's default log driver is used
+	Driver string
+	// Options are driver-specific options (e.g., max-size, max-file)
+	Options map[string]string
 }
 
 // PortBinding represents a host port binding
@@ -26,11 +26,13 @@ func TestGetDockerfileTemplate(t *testing.T) {
 				"FROM python:3.12-slim",
 				"apt-get install -y --no-install-recommends ca-certificates",
 				"pip install --no-cache-dir uv",
+				"RUN uv tool install example-package",
 				"ENTRYPOINT [\"uvx\", \"example-package\", \"--arg1\", \"--arg2\", \"value\"]",
 			},
 			wantNotContains: []string{
 				"Add custom CA certificate",
 				"update-ca-certificates",
+				"UV_NATIVE_TLS",
 			},
 			wantErr: false,
 		},
@@ -51,6 +53,7 @@ func TestGetDockerfileTemplate(t *testing.T) {
 				"COPY ca-cert.crt /tmp/custom-ca.crt",
 				"cat /tmp/custom-ca.crt >> /etc/ssl/certs/ca-certificates.crt",
 				"update-ca-certificates",
+				"ENV UV_NATIVE_TLS=1",
 			},
 			wantNotContains: []string{},
 			wantErr:         false,
@@ -64,11 +67,13 @@ func TestGetDockerfileTemplate(t *testing.T) {
 			},
 			wantContains: []string{
 				"FROM node:22-alpine",
+				"RUN npm install --omit=dev example-package",
 				"ENTRYPOINT [\"npx\", \"--yes\", \"--\", \"example-package\", \"--arg1\", \"--arg2\", \"value\"]",
 			},
 			wantNotContains: []string{
 				"Add custom CA certificate",
 				"update-ca-certificates",
+				"NODE_EXTRA_CA_CERTS",
 			},
 			wantErr: false,
 		},
@@ -87,6 +92,7 @@ func TestGetDockerfileTemplate(t *testing.T) {
 				"COPY ca-cert.crt /tmp/custom-ca.crt",
 				"cat /tmp/custom-ca.crt >> /etc/ssl/certs/ca-certificates.crt",
 				"update-ca-certificates",
+				"ENV NODE_EXTRA_CA_CERTS=/etc/ssl/certs/ca-certificates.crt",
 			},
 			wantNotContains: []string{},
 			wantErr:         false,
@@ -95,16 +101,21 @@ func TestGetDockerfileTemplate(t *testing.T) {
 			name:          "GO transport",
 			transportType: TransportTypeGO,
 			data: TemplateData{
-				MCPPackage: "example-package",
+				MCPPackage: "example-package@v1.2.3",
 				MCPArgs:    []string{"--arg1", "--arg2", "value"},
 			},
 			wantContains: []string{
-				"FROM golang:1.24-alpine",
-				"ENTRYPOINT [\"go\", \"run\", \"example-package\", \"--arg1\", \"--arg2\", \"value\"]",
+				"FROM golang:1.24-alpine AS builder",
+				"go install -trimpath -ldflags=\"-s -w\" example-package@v1.2.3",
+				"FROM gcr.io/distroless/static-debian12:nonroot",
+				"COPY --from=builder /out/mcp-server /app/mcp-server",
+				"ENTRYPOINT [\"/app/mcp-server\", \"--arg1\", \"--arg2\", \"value\"]",
 			},
 			wantNotContains: []string{
 				"Add custom CA certificate",
 				"update-ca-certificates",
+				"go run",
+				"COPY . /app/",
 			},
 			wantErr: false,
 		},
@@ -117,12 +128,13 @@ func TestGetDockerfileTemplate(t *testing.T) {
 				CACertContent: "-----BEGIN CERTIFICATE-----\nMIICertificateContent\n-----END CERTIFICATE-----",
 			},
 			wantContains: []string{
-				"FROM golang:1.24-alpine",
-				"ENTRYPOINT [\"go\", \"run\", \"example-package\", \"--arg1\", \"--arg2\", \"value\"]",
+				"FROM golang:1.24-alpine AS builder",
+				"ENTRYPOINT [\"/app/mcp-server\", \"--arg1\", \"--arg2\", \"value\"]",
 				"Add custom CA certificate BEFORE any network operations",
 				"COPY ca-cert.crt /tmp/custom-ca.crt",
 				"cat /tmp/custom-ca.crt >> /etc/ssl/certs/ca-certificates.crt",
 				"update-ca-certificates",
+				"COPY --from=builder /etc/ssl/certs/ca-certificates.crt /etc/ssl/certs/ca-certificates.crt",
 			},
 			wantNotContains: []string{},
 			wantErr:         false,
@@ -136,12 +148,15 @@ func TestGetDockerfileTemplate(t *testing.T) {
 				IsLocalPath: true,
 			},
 			wantContains: []string{
-				"FROM golang:1.24-alpine",
+				"FROM golang:1.24-alpine AS builder",
 				"COPY . /app/",
-				"ENTRYPOINT [\"go\", \"run\", \"./cmd/server\", \"--arg1\", \"value\"]",
+				"go build -trimpath -ldflags=\"-s -w\" -o /out/mcp-server ./cmd/server",
+				"FROM gcr.io/distroless/static-debian12:nonroot",
+				"ENTRYPOINT [\"/app/mcp-server\", \"--arg1\", \"value\"]",
 			},
 			wantNotContains: []string{
 				"Add custom CA certificate",
+				"go install",
 			},
 			wantErr: false,
 		},
@@ -154,9 +169,10 @@ func TestGetDockerfileTemplate(t *testing.T) {
 				IsLocalPath: true,
 			},
 			wantContains: []string{
-				"FROM golang:1.24-alpine",
+				"FROM golang:1.24-alpine AS builder",
 				"COPY . /app/",
-				"ENTRYPOINT [\"go\", \"run\", \".\"]",
+				"go build -trimpath -ldflags=\"-s -w\" -o /out/mcp-server .",
+				"ENTRYPOINT [\"/app/mcp-server\"]",
 			},
 			wantNotContains: []string{
 				"Add custom CA certificate",
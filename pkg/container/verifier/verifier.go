@@ -41,8 +41,12 @@ func New(serverInfo *registry.ImageMetadata) (*Sigstore, error) {
 	if serverInfo == nil || serverInfo.Provenance == nil {
 		return nil, ErrProvenanceServerInformationNotSet
 	}
-	sigstoreTUFRepoURL := serverInfo.Provenance.SigstoreURL
+	return NewForSigstoreURL(serverInfo.Provenance.SigstoreURL)
+}
 
+// NewForSigstoreURL creates a new Sigstore verifier for the sigstore instance with the given TUF repository,
+// or for the sigstore public good instance if it is empty
+func NewForSigstoreURL(sigstoreTUFRepoURL string) (*Sigstore, error) {
 	// Default the sigstoreTUFRepoURL to the sigstore public trusted root repo if not provided.
 	// Note: Update this if we want to support more sigstore instances
 	if sigstoreTUFRepoURL == "" {
@@ -121,6 +125,12 @@ func getVerifiedResults(
 
 // VerifyServer verifies the server information for the given image reference
 func (s *Sigstore) VerifyServer(imageRef string, serverInfo *registry.ImageMetadata) (bool, error) {
+	return s.VerifyProvenances(imageRef, []*registry.Provenance{serverInfo.Provenance})
+}
+
+// VerifyProvenances verifies that the given image reference is signed, and that its signatures match
+// any of the given provenances, e.g. the trusted signers configured by the user
+func (s *Sigstore) VerifyProvenances(imageRef string, provenances []*registry.Provenance) (bool, error) {
 	// Get the verification results for the image reference
 	results, err := s.GetVerificationResults(imageRef)
 	if err != nil {
@@ -132,15 +142,25 @@ func (s *Sigstore) VerifyServer(imageRef string, serverInfo *registry.ImageMetad
 		return false, nil
 	}
 
-	// Compare the server information with the verification results
+	// Compare the provenances with the verification results
+	for _, provenance := range provenances {
+		if isMatchingAllVerificationResults(results, provenance) {
+			// The provenance matches the verification results, pass the verification
+			return true, nil
+		}
+	}
+	// No provenance matches the verification results, fail the verification
+	return false, nil
+}
+
+// isMatchingAllVerificationResults returns true if the provenance matches all the verification results
+func isMatchingAllVerificationResults(results []*verify.VerificationResult, p *registry.Provenance) bool {
 	for _, res := range results {
-		if !isVerificationResultMatchingServerProvenance(res, serverInfo.Provenance) {
-			// The server information does not match the verification result, fail the verification
-			return false, nil
+		if !isVerificationResultMatchingServerProvenance(res, p) {
+			return false
 		}
 	}
-	// The server information matches the verification result, pass the verification
-	return true, nil
+	return true
 }
 
 func isVerificationResultMatchingServerProvenance(r *verify.VerificationResult, p *registry.Provenance) bool {
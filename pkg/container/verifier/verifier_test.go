@@ -0,0 +1,67 @@
+package verifier
+
+import (
+	"testing"
+
+	"github.com/sigstore/sigstore-go/pkg/fulcio/certificate"
+	"github.com/sigstore/sigstore-go/pkg/verify"
+	"github.com/stretchr/testify/assert"
+
+	"github.com/stacklok/toolhive/pkg/registry"
+)
+
+func TestCompareBaseProperties_WorkflowSigner(t *testing.T) {
+	t.Parallel()
+
+	// The provenance of a trusted signer for a release workflow of a repository
+	provenance := &registry.Provenance{
+		SignerIdentity: "/.github/workflows/release.yml",
+		CertIssuer:     githubTokenIssuer,
+		RepositoryURI:  "https://github.com/example/mcp-server",
+	}
+
+	tests := []struct {
+		name       string
+		issuer     string
+		repository string
+		expected   bool
+	}{
+		{
+			name:       "workflow of the repository",
+			issuer:     githubTokenIssuer,
+			repository: "https://github.com/example/mcp-server",
+			expected:   true,
+		},
+		{
+			name:       "workflow with the same path in another repository",
+			issuer:     githubTokenIssuer,
+			repository: "https://github.com/attacker/mcp-server",
+			expected:   false,
+		},
+		{
+			name:       "same identity from another issuer",
+			issuer:     "https://issuer.example.com",
+			repository: "https://github.com/example/mcp-server",
+			expected:   false,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			result := &verify.VerificationResult{
+				Signature: &verify.SignatureVerificationResult{
+					Certificate: &certificate.Summary{
+						SubjectAlternativeName: tt.repository + "/.github/workflows/release.yml@refs/tags/v1.0.0",
+						Extensions: certificate.Extensions{
+							Issuer:              tt.issuer,
+							SourceRepositoryURI: tt.repository,
+						},
+					},
+				},
+			}
+			assert.Equal(t, tt.expected, compareBaseProperties(result, provenance))
+		})
+	}
+}
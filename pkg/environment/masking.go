@@ -0,0 +1,100 @@
+package environment
+
+import (
+	"strings"
+
+	"github.com/stacklok/toolhive/pkg/secrets"
+)
+
+// MaskedValue is shown in place of the values of sensitive environment variables, labels and arguments
+const MaskedValue = "********"
+
+// sensitiveNameParts are the parts of names which suggest that a value is a credential
+var sensitiveNameParts = []string{
+	"TOKEN",
+	"SECRET",
+	"PASSWORD",
+	"PASSWD",
+	"PASSPHRASE",
+	"CREDENTIAL",
+	"API_KEY",
+	"APIKEY",
+	"ACCESS_KEY",
+	"PRIVATE_KEY",
+	"AUTHORIZATION",
+	"COOKIE",
+}
+
+// IsSensitiveName returns true if the name of an environment variable, label or flag suggests
+// that its value is a credential, e.g. GITHUB_TOKEN or --api-key
+func IsSensitiveName(name string) bool {
+	normalized := strings.ToUpper(strings.ReplaceAll(strings.TrimLeft(name, "-"), "-", "_"))
+	for _, part := range sensitiveNameParts {
+		if strings.Contains(normalized, part) {
+			return true
+		}
+	}
+	return false
+}
+
+// Masker masks the values of environment variables which hold secrets in display output
+type Masker struct {
+	secretTargets map[string]struct{}
+}
+
+// NewMasker creates a Masker which treats the targets of the given secret parameters
+// (format: <name>,target=<target>) and variables with sensitive names as secrets
+func NewMasker(secretParameters []string) *Masker {
+	m := &Masker{secretTargets: make(map[string]struct{}, len(secretParameters))}
+	for _, param := range secretParameters {
+		if parameter, err := secrets.ParseSecretParameter(param); err == nil {
+			m.secretTargets[parameter.Target] = struct{}{}
+		}
+	}
+	return m
+}
+
+// IsSecret returns true if the environment variable is set from a secret or has a sensitive name
+func (m *Masker) IsSecret(name string) bool {
+	if _, ok := m.secretTargets[name]; ok {
+		return true
+	}
+	return IsSensitiveName(name)
+}
+
+// Mask returns MaskedValue if the environment variable holds a secret, and the value otherwise
+func (m *Masker) Mask(name, value string) string {
+	if value == "" || !m.IsSecret(name) {
+		return value
+	}
+	return MaskedValue
+}
+
+// MaskArgs returns a copy of the arguments in which the values of flags with sensitive names are masked,
+// in both the --flag=value and the --flag value forms
+func MaskArgs(args []string) []string {
+	masked := make([]string, len(args))
+	maskNext := false
+	for i, arg := range args {
+		if maskNext {
+			masked[i] = MaskedValue
+			maskNext = false
+			continue
+		}
+		masked[i] = arg
+		if !strings.HasPrefix(arg, "-") {
+			continue
+		}
+
+		name, _, hasValue := strings.Cut(arg, "=")
+		if !IsSensitiveName(name) {
+			continue
+		}
+		if hasValue {
+			masked[i] = name + "=" + MaskedValue
+		} else {
+			maskNext = true
+		}
+	}
+	return masked
+}
@@ -0,0 +1,103 @@
+package environment
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestIsSensitiveName(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name string
+		want bool
+	}{
+		{"GITHUB_TOKEN", true},
+		{"github_personal_access_token", true},
+		{"DB_PASSWORD", true},
+		{"OPENAI_API_KEY", true},
+		{"--api-key", true},
+		{"--client-secret", true},
+		{"AWS_SECRET_ACCESS_KEY", true},
+		{"LOG_LEVEL", false},
+		{"MCP_TRANSPORT", false},
+		{"--port", false},
+		{"HOME", false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			if got := IsSensitiveName(tt.name); got != tt.want {
+				t.Errorf("IsSensitiveName(%q) = %v, want %v", tt.name, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestMasker_Mask(t *testing.T) {
+	t.Parallel()
+
+	masker := NewMasker([]string{"github,target=GH", "invalid-format"})
+	tests := []struct {
+		name  string
+		value string
+		want  string
+	}{
+		{"GH", "ghp_secret", MaskedValue},
+		{"OPENAI_TOKEN", "sk-secret", MaskedValue},
+		{"LOG_LEVEL", "debug", "debug"},
+		{"EMPTY_TOKEN", "", ""},
+	}
+
+	for _, tt := range tests {
+		if got := masker.Mask(tt.name, tt.value); got != tt.want {
+			t.Errorf("Mask(%q, %q) = %q, want %q", tt.name, tt.value, got, tt.want)
+		}
+	}
+}
+
+func TestMaskArgs(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name string
+		args []string
+		want []string
+	}{
+		{
+			name: "No sensitive flags",
+			args: []string{"--port", "8080", "serve"},
+			want: []string{"--port", "8080", "serve"},
+		},
+		{
+			name: "Flag with separate value",
+			args: []string{"--token", "abc", "--verbose"},
+			want: []string{"--token", MaskedValue, "--verbose"},
+		},
+		{
+			name: "Flag with inline value",
+			args: []string{"--api-key=abc", "run"},
+			want: []string{"--api-key=" + MaskedValue, "run"},
+		},
+		{
+			name: "Sensitive flag at the end",
+			args: []string{"--password"},
+			want: []string{"--password"},
+		},
+		{
+			name: "Empty arguments",
+			args: []string{},
+			want: []string{},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			if got := MaskArgs(tt.args); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("MaskArgs() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,104 @@
+package environment
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"regexp"
+	"strings"
+
+	"github.com/stacklok/toolhive/pkg/secrets"
+)
+
+// secretReferencePrefix is the prefix of references which are resolved from the secrets manager
+const secretReferencePrefix = "secret:"
+
+var (
+	// referenceRegex matches ${...} references and the $${ escape sequence
+	referenceRegex = regexp.MustCompile(`\$\$\{|\$\{([^}]*)\}`)
+	// variableNameRegex matches valid environment variable names
+	variableNameRegex = regexp.MustCompile(`^[A-Za-z_][A-Za-z0-9_]*$`)
+)
+
+// ExpandReferences replaces ${VAR} references in value with the value of the environment variable VAR
+// of the host, and ${secret:name} references with the secret name from the secrets manager.
+// A literal ${ can be written as $${. An error is returned if a variable is not set, a secret cannot
+// be read, or a secret is referenced but secretsManager is nil.
+func ExpandReferences(ctx context.Context, value string, secretsManager secrets.Provider) (string, error) {
+	return expandReferences(ctx, value, os.LookupEnv, secretsManager)
+}
+
+// ExpandEnvReferences replaces ${VAR} references in value with the value of the environment variable VAR
+// of the host. Secret references are not supported and result in an error.
+func ExpandEnvReferences(value string) (string, error) {
+	return expandReferences(context.Background(), value, os.LookupEnv, nil)
+}
+
+// HasSecretReferences returns true if value contains a ${secret:name} reference
+func HasSecretReferences(value string) bool {
+	for _, match := range referenceRegex.FindAllStringSubmatch(value, -1) {
+		if strings.HasPrefix(match[1], secretReferencePrefix) {
+			return true
+		}
+	}
+	return false
+}
+
+func expandReferences(
+	ctx context.Context,
+	value string,
+	lookupEnv func(string) (string, bool),
+	secretsManager secrets.Provider,
+) (string, error) {
+	var expandErr error
+	expanded := referenceRegex.ReplaceAllStringFunc(value, func(match string) string {
+		if expandErr != nil {
+			return match
+		}
+		if match == "$${" {
+			return "${"
+		}
+
+		reference := match[2 : len(match)-1]
+		resolved, err := resolveReference(ctx, reference, lookupEnv, secretsManager)
+		if err != nil {
+			expandErr = err
+			return match
+		}
+		return resolved
+	})
+	if expandErr != nil {
+		return "", expandErr
+	}
+	return expanded, nil
+}
+
+func resolveReference(
+	ctx context.Context,
+	reference string,
+	lookupEnv func(string) (string, bool),
+	secretsManager secrets.Provider,
+) (string, error) {
+	if name, ok := strings.CutPrefix(reference, secretReferencePrefix); ok {
+		if name == "" {
+			return "", fmt.Errorf("invalid reference ${%s}: secret name is empty", reference)
+		}
+		if secretsManager == nil {
+			return "", fmt.Errorf("cannot resolve ${%s}: secret references are only supported in env values and args", reference)
+		}
+		secret, err := secretsManager.GetSecret(ctx, name)
+		if err != nil {
+			return "", fmt.Errorf("failed to get secret %s: %w", name, err)
+		}
+		return secret, nil
+	}
+
+	if !variableNameRegex.MatchString(reference) {
+		return "", fmt.Errorf("invalid reference ${%s}: not a valid environment variable name", reference)
+	}
+	envValue, ok := lookupEnv(reference)
+	if !ok {
+		return "", fmt.Errorf("environment variable %s referenced by ${%s} is not set", reference, reference)
+	}
+	return envValue, nil
+}
@@ -0,0 +1,142 @@
+package environment
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestExpandReferences(t *testing.T) {
+	t.Parallel()
+
+	env := map[string]string{
+		"HOME":  "/home/user",
+		"TOKEN": "abc123",
+		"EMPTY": "",
+	}
+	lookupEnv := func(name string) (string, bool) {
+		value, ok := env[name]
+		return value, ok
+	}
+	provider := &mockSecretsProvider{
+		secrets: map[string]string{
+			"github": "ghp_secret",
+		},
+	}
+
+	tests := []struct {
+		name     string
+		value    string
+		provider *mockSecretsProvider
+		want     string
+		wantErr  bool
+	}{
+		{
+			name:  "No references",
+			value: "plain value $HOME",
+			want:  "plain value $HOME",
+		},
+		{
+			name:  "Environment variable",
+			value: "${HOME}/data",
+			want:  "/home/user/data",
+		},
+		{
+			name:  "Multiple references",
+			value: "Bearer ${TOKEN} from ${HOME}",
+			want:  "Bearer abc123 from /home/user",
+		},
+		{
+			name:  "Empty environment variable",
+			value: "x${EMPTY}y",
+			want:  "xy",
+		},
+		{
+			name:     "Secret",
+			value:    "token=${secret:github}",
+			provider: provider,
+			want:     "token=ghp_secret",
+		},
+		{
+			name:  "Escaped reference",
+			value: "$${HOME} is ${HOME}",
+			want:  "${HOME} is /home/user",
+		},
+		{
+			name:    "Unset environment variable",
+			value:   "${MISSING}",
+			wantErr: true,
+		},
+		{
+			name:    "Invalid variable name",
+			value:   "${NOT-VALID}",
+			wantErr: true,
+		},
+		{
+			name:    "Secret without secrets manager",
+			value:   "${secret:github}",
+			wantErr: true,
+		},
+		{
+			name:     "Missing secret",
+			value:    "${secret:missing}",
+			provider: provider,
+			wantErr:  true,
+		},
+		{
+			name:     "Secret manager error",
+			value:    "${secret:github}",
+			provider: &mockSecretsProvider{getErr: errors.New("locked")},
+			wantErr:  true,
+		},
+		{
+			name:     "Empty secret name",
+			value:    "${secret:}",
+			provider: provider,
+			wantErr:  true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			var err error
+			var got string
+			if tt.provider != nil {
+				got, err = expandReferences(t.Context(), tt.value, lookupEnv, tt.provider)
+			} else {
+				got, err = expandReferences(t.Context(), tt.value, lookupEnv, nil)
+			}
+			if (err != nil) != tt.wantErr {
+				t.Errorf("expandReferences() error = %v, wantErr %v", err, tt.wantErr)
+				return
+			}
+			if got != tt.want {
+				t.Errorf("expandReferences() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestHasSecretReferences(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		value string
+		want  bool
+	}{
+		{"${secret:github}", true},
+		{"prefix-${secret:a}-${HOME}", true},
+		{"${HOME}", false},
+		{"secret:github", false},
+		{"", false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.value, func(t *testing.T) {
+			t.Parallel()
+			if got := HasSecretReferences(tt.value); got != tt.want {
+				t.Errorf("HasSecretReferences(%q) = %v, want %v", tt.value, got, tt.want)
+			}
+		})
+	}
+}
@@ -5,7 +5,11 @@ package healthcheck
 import (
 	"context"
 	"encoding/json"
+	"fmt"
+	"net"
 	"net/http"
+	"net/url"
+	"sync"
 	"time"
 
 	"github.com/stacklok/toolhive/pkg/logger"
@@ -22,6 +26,17 @@ const (
 	StatusUnhealthy HealthStatus = "unhealthy"
 	// StatusDegraded indicates the service is partially healthy
 	StatusDegraded HealthStatus = "degraded"
+	// StatusStarting indicates the MCP server has not answered a health probe yet
+	StatusStarting HealthStatus = "starting"
+)
+
+const (
+	// ProbeInterval is the interval at which proxies probe the health of their MCP server
+	ProbeInterval = 10 * time.Second
+	// FailureThreshold is the number of consecutive failed probes after which an MCP server is unhealthy
+	FailureThreshold = 3
+	// StartPeriod is how long an MCP server which has not answered a probe yet is starting, rather than failing
+	StartPeriod = time.Minute
 )
 
 // MCPStatus represents the status of an MCP server connection
@@ -64,6 +79,13 @@ type MCPPinger interface {
 type HealthChecker struct {
 	transport string
 	mcpPinger MCPPinger
+
+	// Results of the periodic probes
+	mutex     sync.Mutex
+	created   time.Time
+	lastProbe *HealthResponse
+	failures  int
+	answered  bool
 }
 
 // NewHealthChecker creates a new health checker instance
@@ -71,6 +93,7 @@ func NewHealthChecker(transport string, mcpPinger MCPPinger) *HealthChecker {
 	return &HealthChecker{
 		transport: transport,
 		mcpPinger: mcpPinger,
+		created:   time.Now(),
 	}
 }
 
@@ -97,6 +120,37 @@ func (hc *HealthChecker) CheckHealth(ctx context.Context) *HealthResponse {
 	return response
 }
 
+// Probe checks the health of the MCP server like CheckHealth and records the result, which is served
+// by ServeHTTP from then on. The MCP server is starting until it answers a probe, unless StartPeriod has
+// passed, and it is unhealthy once FailureThreshold consecutive probes failed.
+func (hc *HealthChecker) Probe(ctx context.Context) *HealthResponse {
+	response := hc.CheckHealth(ctx)
+
+	hc.mutex.Lock()
+	defer hc.mutex.Unlock()
+	if response.Status == StatusHealthy {
+		hc.failures = 0
+		hc.answered = true
+	} else {
+		hc.failures++
+		switch {
+		case !hc.answered && time.Since(hc.created) < StartPeriod:
+			response.Status = StatusStarting
+		case hc.failures >= FailureThreshold:
+			response.Status = StatusUnhealthy
+		}
+	}
+	hc.lastProbe = response
+	return response
+}
+
+// lastProbeResult returns the result of the last probe, or nil if the MCP server was not probed yet
+func (hc *HealthChecker) lastProbeResult() *HealthResponse {
+	hc.mutex.Lock()
+	defer hc.mutex.Unlock()
+	return hc.lastProbe
+}
+
 // checkMCPStatus checks the status of the MCP server using ping
 func (hc *HealthChecker) checkMCPStatus(ctx context.Context) *MCPStatus {
 	status := &MCPStatus{
@@ -130,7 +184,11 @@ func (hc *HealthChecker) ServeHTTP(w http.ResponseWriter, r *http.Request) {
 		return
 	}
 
-	health := hc.CheckHealth(r.Context())
+	// Servers which are probed periodically report the result of the last probe
+	health := hc.lastProbeResult()
+	if health == nil {
+		health = hc.CheckHealth(r.Context())
+	}
 
 	w.Header().Set("Content-Type", "application/json")
 
@@ -138,8 +196,8 @@ func (hc *HealthChecker) ServeHTTP(w http.ResponseWriter, r *http.Request) {
 	switch health.Status {
 	case StatusHealthy:
 		w.WriteHeader(http.StatusOK)
-	case StatusDegraded:
-		w.WriteHeader(http.StatusOK) // Still return 200 for degraded state
+	case StatusDegraded, StatusStarting:
+		w.WriteHeader(http.StatusOK) // Still return 200 for degraded and starting states
 	case StatusUnhealthy:
 		w.WriteHeader(http.StatusServiceUnavailable)
 	}
@@ -149,3 +207,46 @@ func (hc *HealthChecker) ServeHTTP(w http.ResponseWriter, r *http.Request) {
 		http.Error(w, "Internal server error", http.StatusInternalServerError)
 	}
 }
+
+// Fetch retrieves the health of the MCP server behind the proxy at the given URL.
+// Only the scheme and host of the URL are used, since the health endpoint is served at /health.
+func Fetch(ctx context.Context, httpClient *http.Client, proxyURL string) (*HealthResponse, error) {
+	parsed, err := url.Parse(proxyURL)
+	if err != nil || parsed.Host == "" {
+		return nil, fmt.Errorf("cannot determine health endpoint for %s", proxyURL)
+	}
+	healthURL := url.URL{Scheme: parsed.Scheme, Host: parsed.Host, Path: "/health"}
+
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, healthURL.String(), nil)
+	if err != nil {
+		return nil, err
+	}
+	resp, err := httpClient.Do(req)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+
+	var health HealthResponse
+	if err := json.NewDecoder(resp.Body).Decode(&health); err != nil {
+		return nil, fmt.Errorf("failed to decode health response: %w", err)
+	}
+	return &health, nil
+}
+
+// SocketURL is the URL of a proxy served on a Unix domain socket, for the clients returned by NewSocketClient
+const SocketURL = "http://localhost"
+
+// NewSocketClient returns an HTTP client which sends its requests to the proxy served on the Unix domain
+// socket at the given path, whatever the host of their URL
+func NewSocketClient(socketPath string, timeout time.Duration) *http.Client {
+	dialer := &net.Dialer{}
+	return &http.Client{
+		Timeout: timeout,
+		Transport: &http.Transport{
+			DialContext: func(ctx context.Context, _, _ string) (net.Conn, error) {
+				return dialer.DialContext(ctx, "unix", socketPath)
+			},
+		},
+	}
+}
@@ -3,8 +3,10 @@ package healthcheck
 import (
 	"context"
 	"encoding/json"
+	"net"
 	"net/http"
 	"net/http/httptest"
+	"path/filepath"
 	"testing"
 	"time"
 
@@ -214,3 +216,97 @@ func TestHealthResponse_JSON(t *testing.T) {
 	assert.Equal(t, *response.MCP.ResponseTime, *unmarshaled.MCP.ResponseTime)
 	assert.True(t, response.MCP.LastChecked.Equal(unmarshaled.MCP.LastChecked))
 }
+
+func TestFetch(t *testing.T) {
+	t.Parallel()
+
+	// Initialize logger for tests
+	logger.Initialize()
+
+	mux := http.NewServeMux()
+	mux.Handle("/health", NewHealthChecker("sse", &mockMCPPinger{pingError: assert.AnError}))
+	server := httptest.NewServer(mux)
+	defer server.Close()
+
+	// The path of the proxy URL is ignored
+	health, err := Fetch(context.Background(), server.Client(), server.URL+"/sse#fetch")
+	require.NoError(t, err)
+	assert.Equal(t, StatusDegraded, health.Status)
+	assert.Equal(t, "sse", health.Transport)
+
+	_, err = Fetch(context.Background(), server.Client(), "not a url")
+	assert.Error(t, err)
+}
+
+func TestFetch_Socket(t *testing.T) {
+	t.Parallel()
+
+	// Initialize logger for tests
+	logger.Initialize()
+
+	socketPath := filepath.Join(t.TempDir(), "fetch.sock")
+	listener, err := net.Listen("unix", socketPath)
+	require.NoError(t, err)
+	mux := http.NewServeMux()
+	mux.Handle("/health", NewHealthChecker("sse", &mockMCPPinger{pingError: assert.AnError}))
+	server := httptest.NewUnstartedServer(mux)
+	server.Listener = listener
+	server.Start()
+	defer server.Close()
+
+	health, err := Fetch(context.Background(), NewSocketClient(socketPath, time.Second), SocketURL)
+	require.NoError(t, err)
+	assert.Equal(t, StatusDegraded, health.Status)
+
+	_, err = Fetch(context.Background(), NewSocketClient(filepath.Join(t.TempDir(), "missing.sock"), time.Second), SocketURL)
+	assert.Error(t, err)
+}
+
+func TestHealthChecker_Probe(t *testing.T) {
+	t.Parallel()
+
+	// Initialize logger for tests
+	logger.Initialize()
+
+	pinger := &mockMCPPinger{pingDuration: 10 * time.Millisecond, pingError: assert.AnError}
+	hc := NewHealthChecker("stdio", pinger)
+
+	// A server which has not answered yet is starting
+	assert.Equal(t, StatusStarting, hc.Probe(context.Background()).Status)
+
+	// Once it answered, failed probes degrade it until the failure threshold is reached
+	pinger.pingError = nil
+	assert.Equal(t, StatusHealthy, hc.Probe(context.Background()).Status)
+	pinger.pingError = assert.AnError
+	for i := 1; i < FailureThreshold; i++ {
+		assert.Equal(t, StatusDegraded, hc.Probe(context.Background()).Status)
+	}
+	assert.Equal(t, StatusUnhealthy, hc.Probe(context.Background()).Status)
+
+	// The result of the last probe is served
+	recorder := httptest.NewRecorder()
+	hc.ServeHTTP(recorder, httptest.NewRequest(http.MethodGet, "/health", nil))
+	assert.Equal(t, http.StatusServiceUnavailable, recorder.Code)
+
+	// A successful probe resets the failures
+	pinger.pingError = nil
+	assert.Equal(t, StatusHealthy, hc.Probe(context.Background()).Status)
+	pinger.pingError = assert.AnError
+	assert.Equal(t, StatusDegraded, hc.Probe(context.Background()).Status)
+}
+
+func TestHealthChecker_ProbeAfterStartPeriod(t *testing.T) {
+	t.Parallel()
+
+	// Initialize logger for tests
+	logger.Initialize()
+
+	hc := NewHealthChecker("sse", &mockMCPPinger{pingError: assert.AnError})
+	hc.created = time.Now().Add(-StartPeriod)
+
+	// A server which never answered is failing once the start period has passed
+	for i := 1; i < FailureThreshold; i++ {
+		assert.Equal(t, StatusDegraded, hc.Probe(context.Background()).Status)
+	}
+	assert.Equal(t, StatusUnhealthy, hc.Probe(context.Background()).Status)
+}
@@ -3,7 +3,9 @@
 package labels
 
 import (
+	"encoding/json"
 	"fmt"
+	"strconv"
 	"strings"
 )
 
@@ -29,10 +31,50 @@ const (
 	// LabelToolType is the label that indicates the type of tool
 	LabelToolType = "toolhive-tool-type"
 
+	// LabelSSEPath is the label that contains the custom path of the SSE endpoint of the proxy
+	LabelSSEPath = "toolhive-sse-path"
+
+	// LabelSpecHash is the label that contains the hash of the declarative spec a workload was created from
+	LabelSpecHash = "toolhive-spec-hash"
+
+	// LabelComposeProject is the label that contains the name of the compose project a workload was created from
+	LabelComposeProject = "toolhive-compose-project"
+
+	// LabelSchema is the label that contains the version of the label schema of a container
+	LabelSchema = "toolhive-schema"
+
+	// LabelWorkload is the label that contains the metadata of a workload as JSON (schema 2)
+	LabelWorkload = "toolhive-workload"
+
 	// LabelEnabledValue is the value for the LabelEnabled label
 	LabelEnabledValue = "true"
 )
 
+// SchemaVersion is the version of the label schema of the containers created by this version of ToolHive.
+//
+// Containers of schema 1, which have no LabelSchema label, only have the individual transport, port and
+// tool type labels. Schema 2 adds the LabelWorkload label with the metadata of the workload as JSON, which
+// new metadata is only added to. The individual labels are still written, so that older versions and
+// runtimes which don't allow JSON label values keep working, and the getters fall back to them for
+// containers of schema 1. These are migrated to schema 2 once they are recreated, e.g. by thv restart.
+const SchemaVersion = 2
+
+// WorkloadMetadata is the metadata of a workload stored in the LabelWorkload label
+type WorkloadMetadata struct {
+	// Transport is the transport mode of the workload
+	Transport string `json:"transport,omitempty"`
+	// Port is the port of the proxy of the workload
+	Port int `json:"port,omitempty"`
+	// ToolType is the type of tool of the workload
+	ToolType string `json:"tool_type,omitempty"`
+	// PermissionProfile is the name or path of the permission profile of the workload
+	PermissionProfile string `json:"permission_profile,omitempty"`
+	// TLS is set if the proxy of the workload serves HTTPS
+	TLS bool `json:"tls,omitempty"`
+	// ListenSocket is the path of the Unix domain socket the proxy of the workload is served on instead of its port
+	ListenSocket string `json:"listen_socket,omitempty"`
+}
+
 // AddStandardLabels adds standard labels to a container
 func AddStandardLabels(labels map[string]string, containerName, containerBaseName, transportType string, port int) {
 	// Add standard labels
@@ -44,6 +86,92 @@ func AddStandardLabels(labels map[string]string, containerName, containerBaseNam
 
 	// TODO: In the future, we'll support different tool types beyond just "mcp"
 	labels[LabelToolType] = "mcp"
+
+	metadata := GetWorkloadMetadata(labels)
+	metadata.Transport = transportType
+	metadata.Port = port
+	metadata.ToolType = "mcp"
+	setWorkloadMetadata(labels, metadata)
+}
+
+// SetPermissionProfile records the name or path of the permission profile of a workload in its labels
+func SetPermissionProfile(labels map[string]string, profile string) {
+	metadata := GetWorkloadMetadata(labels)
+	metadata.PermissionProfile = profile
+	setWorkloadMetadata(labels, metadata)
+}
+
+// SetTLS records in the labels of a workload that its proxy serves HTTPS
+func SetTLS(labels map[string]string) {
+	metadata := GetWorkloadMetadata(labels)
+	metadata.TLS = true
+	setWorkloadMetadata(labels, metadata)
+}
+
+// SetListenSocket records in the labels of a workload the path of the Unix domain socket its proxy is served on
+func SetListenSocket(labels map[string]string, path string) {
+	metadata := GetWorkloadMetadata(labels)
+	metadata.ListenSocket = path
+	setWorkloadMetadata(labels, metadata)
+}
+
+// setWorkloadMetadata stores the metadata of a workload in its labels, in the current label schema
+func setWorkloadMetadata(labels map[string]string, metadata WorkloadMetadata) {
+	// Marshaling a struct of strings, integers and booleans cannot fail
+	data, _ := json.Marshal(metadata)
+	labels[LabelSchema] = strconv.Itoa(SchemaVersion)
+	labels[LabelWorkload] = string(data)
+}
+
+// GetSchemaVersion gets the version of the label schema of a container, which is 1 for containers
+// created before the schema was versioned
+func GetSchemaVersion(labels map[string]string) int {
+	version, err := strconv.Atoi(labels[LabelSchema])
+	if err != nil || version < 1 {
+		return 1
+	}
+	return version
+}
+
+// GetWorkloadMetadata gets the metadata of a workload from its labels. The metadata of containers without
+// the LabelWorkload label, like those of schema 1, is read from the individual labels.
+func GetWorkloadMetadata(labels map[string]string) WorkloadMetadata {
+	if metadata, ok := workloadMetadata(labels); ok {
+		return metadata
+	}
+
+	metadata := WorkloadMetadata{
+		Transport: labels[LabelTransport],
+		ToolType:  labels[LabelToolType],
+	}
+	if port, err := strconv.Atoi(labels[LabelPort]); err == nil {
+		metadata.Port = port
+	}
+	return metadata
+}
+
+// workloadMetadata parses the LabelWorkload label of a container of schema 2 or later
+func workloadMetadata(labels map[string]string) (WorkloadMetadata, bool) {
+	var metadata WorkloadMetadata
+	data, ok := labels[LabelWorkload]
+	if !ok || GetSchemaVersion(labels) < 2 {
+		return metadata, false
+	}
+	if err := json.Unmarshal([]byte(data), &metadata); err != nil {
+		return metadata, false
+	}
+	return metadata, true
+}
+
+// IsStandardLabel checks if the given label key is one of the standard labels managed by ToolHive
+func IsStandardLabel(key string) bool {
+	switch key {
+	case LabelEnabled, LabelName, LabelBaseName, LabelTransport, LabelPort, LabelToolType, LabelSSEPath,
+		LabelSchema, LabelWorkload:
+		return true
+	default:
+		return false
+	}
 }
 
 // AddNetworkLabels adds network-related labels to a network
@@ -75,11 +203,15 @@ func GetContainerBaseName(labels map[string]string) string {
 
 // GetTransportType gets the transport type from labels
 func GetTransportType(labels map[string]string) string {
-	return labels[LabelTransport]
+	return GetWorkloadMetadata(labels).Transport
 }
 
 // GetPort gets the port from labels
 func GetPort(labels map[string]string) (int, error) {
+	if metadata, ok := workloadMetadata(labels); ok && metadata.Port != 0 {
+		return metadata.Port, nil
+	}
+
 	portStr, ok := labels[LabelPort]
 	if !ok {
 		return 0, fmt.Errorf("port label not found")
@@ -95,5 +227,48 @@ func GetPort(labels map[string]string) (int, error) {
 
 // GetToolType gets the tool type from labels
 func GetToolType(labels map[string]string) string {
-	return labels[LabelToolType]
+	return GetWorkloadMetadata(labels).ToolType
+}
+
+// GetPermissionProfile gets the name or path of the permission profile from labels, or an empty string
+// for containers which don't record it, like those of schema 1
+func GetPermissionProfile(labels map[string]string) string {
+	return GetWorkloadMetadata(labels).PermissionProfile
+}
+
+// IsTLS checks if the proxy of a workload serves HTTPS
+func IsTLS(labels map[string]string) bool {
+	return GetWorkloadMetadata(labels).TLS
+}
+
+// GetListenSocket gets the path of the Unix domain socket the proxy of a workload is served on, or an empty
+// string if it is served on its port
+func GetListenSocket(labels map[string]string) string {
+	return GetWorkloadMetadata(labels).ListenSocket
+}
+
+// GetSSEPath gets the custom path of the SSE endpoint from labels, or an empty string if the default is used
+func GetSSEPath(labels map[string]string) string {
+	return labels[LabelSSEPath]
+}
+
+// GetSpecHash gets the declarative spec hash from labels
+func GetSpecHash(labels map[string]string) string {
+	return labels[LabelSpecHash]
+}
+
+// GetComposeProject gets the name of the compose project a workload was created from, or an empty string
+func GetComposeProject(labels map[string]string) string {
+	return labels[LabelComposeProject]
+}
+
+// GetUserLabels returns the labels which are not managed by ToolHive
+func GetUserLabels(labels map[string]string) map[string]string {
+	userLabels := make(map[string]string)
+	for key, value := range labels {
+		if !IsStandardLabel(key) && key != LabelSpecHash && key != LabelComposeProject {
+			userLabels[key] = value
+		}
+	}
+	return userLabels
 }
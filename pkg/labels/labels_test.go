@@ -335,3 +335,157 @@ func TestGetToolType(t *testing.T) {
 		})
 	}
 }
+
+func TestIsStandardLabel(t *testing.T) {
+	t.Parallel()
+	tests := []struct {
+		key      string
+		expected bool
+	}{
+		{LabelEnabled, true},
+		{LabelName, true},
+		{LabelBaseName, true},
+		{LabelTransport, true},
+		{LabelPort, true},
+		{LabelToolType, true},
+		{LabelSSEPath, true},
+		{LabelSchema, true},
+		{LabelWorkload, true},
+		{LabelSpecHash, false},
+		{LabelComposeProject, false},
+		{"com.example.team", false},
+	}
+
+	for _, tc := range tests {
+		t.Run(tc.key, func(t *testing.T) {
+			t.Parallel()
+			if result := IsStandardLabel(tc.key); result != tc.expected {
+				t.Errorf("Expected IsStandardLabel(%s) to be %v, but got %v", tc.key, tc.expected, result)
+			}
+		})
+	}
+}
+
+func TestGetUserLabels(t *testing.T) {
+	t.Parallel()
+	containerLabels := map[string]string{
+		LabelEnabled:        LabelEnabledValue,
+		LabelName:           "fetch",
+		LabelSpecHash:       "sha256:abc",
+		LabelComposeProject: "demo",
+		"com.example.team":  "platform",
+	}
+
+	result := GetUserLabels(containerLabels)
+	if len(result) != 1 || result["com.example.team"] != "platform" {
+		t.Errorf("Expected only the user label, but got %v", result)
+	}
+}
+
+func TestWorkloadMetadata(t *testing.T) {
+	t.Parallel()
+
+	labels := make(map[string]string)
+	AddStandardLabels(labels, "fetch", "fetch", "sse", 8080)
+	SetPermissionProfile(labels, "network")
+	SetTLS(labels)
+	SetListenSocket(labels, "/tmp/fetch.sock")
+
+	if labels[LabelSchema] != "2" {
+		t.Errorf("Expected schema version 2, but got %q", labels[LabelSchema])
+	}
+	expected := WorkloadMetadata{
+		Transport: "sse", Port: 8080, ToolType: "mcp", PermissionProfile: "network", TLS: true, ListenSocket: "/tmp/fetch.sock",
+	}
+	if metadata := GetWorkloadMetadata(labels); metadata != expected {
+		t.Errorf("Expected metadata %+v, but got %+v", expected, metadata)
+	}
+
+	// The metadata is preserved when the standard labels are added again, e.g. on rename
+	AddStandardLabels(labels, "web-fetch", "web-fetch", "sse", 9090)
+	if port, err := GetPort(labels); err != nil || port != 9090 {
+		t.Errorf("Expected port 9090, but got %d (%v)", port, err)
+	}
+	if profile := GetPermissionProfile(labels); profile != "network" {
+		t.Errorf("Expected permission profile network, but got %q", profile)
+	}
+	if !IsTLS(labels) {
+		t.Errorf("Expected the proxy to serve HTTPS")
+	}
+	if socket := GetListenSocket(labels); socket != "/tmp/fetch.sock" {
+		t.Errorf("Expected listen socket /tmp/fetch.sock, but got %q", socket)
+	}
+}
+
+func TestWorkloadMetadata_Schema1(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name     string
+		labels   map[string]string
+		expected WorkloadMetadata
+	}{
+		{
+			name: "Labels of schema 1",
+			labels: map[string]string{
+				LabelEnabled:   LabelEnabledValue,
+				LabelTransport: "stdio",
+				LabelPort:      "8081",
+				LabelToolType:  "mcp",
+			},
+			expected: WorkloadMetadata{Transport: "stdio", Port: 8081, ToolType: "mcp"},
+		},
+		{
+			name: "Invalid metadata falls back to the individual labels",
+			labels: map[string]string{
+				LabelSchema:    "2",
+				LabelWorkload:  "{invalid",
+				LabelTransport: "sse",
+				LabelPort:      "8080",
+			},
+			expected: WorkloadMetadata{Transport: "sse", Port: 8080},
+		},
+		{
+			name: "Metadata without a schema version is ignored",
+			labels: map[string]string{
+				LabelWorkload:  `{"transport":"streamable-http","port":9090}`,
+				LabelTransport: "sse",
+			},
+			expected: WorkloadMetadata{Transport: "sse"},
+		},
+	}
+
+	for _, tc := range tests {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			if metadata := GetWorkloadMetadata(tc.labels); metadata != tc.expected {
+				t.Errorf("Expected metadata %+v, but got %+v", tc.expected, metadata)
+			}
+			if transportType := GetTransportType(tc.labels); transportType != tc.expected.Transport {
+				t.Errorf("Expected transport %q, but got %q", tc.expected.Transport, transportType)
+			}
+		})
+	}
+}
+
+func TestGetSchemaVersion(t *testing.T) {
+	t.Parallel()
+	tests := []struct {
+		name     string
+		labels   map[string]string
+		expected int
+	}{
+		{name: "No schema label", labels: map[string]string{}, expected: 1},
+		{name: "Schema 2", labels: map[string]string{LabelSchema: "2"}, expected: 2},
+		{name: "Invalid schema label", labels: map[string]string{LabelSchema: "two"}, expected: 1},
+	}
+
+	for _, tc := range tests {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			if version := GetSchemaVersion(tc.labels); version != tc.expected {
+				t.Errorf("Expected schema version %d, but got %d", tc.expected, version)
+			}
+		})
+	}
+}
@@ -0,0 +1,244 @@
+// Package launchd manages the launchd agents which keep ToolHive processes running
+// in the background on macOS, starting them when the user logs in and restarting them if they fail.
+package launchd
+
+import (
+	"bytes"
+	"context"
+	"encoding/xml"
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"sort"
+	"strings"
+)
+
+const (
+	// LabelPrefix is the prefix of the labels of the launchd agents managed by ToolHive
+	LabelPrefix = "com.stacklok.toolhive."
+
+	// ServeLabel is the label of the agent which runs the ToolHive API server
+	ServeLabel = LabelPrefix + "serve"
+
+	// serverLabelPrefix is the prefix of the labels of the agents which run MCP servers
+	serverLabelPrefix = LabelPrefix + "server."
+
+	plistExtension = ".plist"
+	plistDoctype   = `<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">`
+)
+
+// KeepAlive describes when launchd restarts an agent after it exits
+type KeepAlive string
+
+const (
+	// KeepAliveAlways restarts the agent whenever it exits
+	KeepAliveAlways KeepAlive = "always"
+	// KeepAliveOnFailure restarts the agent only if it exits with an error,
+	// so the process can still be stopped on purpose
+	KeepAliveOnFailure KeepAlive = "on-failure"
+)
+
+// Agent describes a launchd agent which runs a process when the user logs in
+type Agent struct {
+	// Label uniquely identifies the agent
+	Label string
+	// ProgramArguments is the command line of the process, starting with the absolute path of the executable
+	ProgramArguments []string
+	// EnvironmentVariables are set for the process in addition to the ones set by launchd
+	EnvironmentVariables map[string]string
+	// LogPath is the file the standard output and error of the process are appended to
+	LogPath string
+	// KeepAlive describes when the process is restarted after it exits
+	KeepAlive KeepAlive
+}
+
+// ServerLabel returns the label of the agent which runs the MCP server with the given name
+func ServerLabel(name string) string {
+	return serverLabelPrefix + name
+}
+
+// ServerName returns the name of the MCP server run by the agent with the given label,
+// or false if the agent does not run an MCP server
+func ServerName(label string) (string, bool) {
+	name, ok := strings.CutPrefix(label, serverLabelPrefix)
+	return name, ok && name != ""
+}
+
+// Plist renders the property list which describes the agent to launchd
+func (a *Agent) Plist() []byte {
+	var b bytes.Buffer
+	b.WriteString(xml.Header)
+	b.WriteString(plistDoctype + "\n")
+	b.WriteString("<plist version=\"1.0\">\n<dict>\n")
+
+	writeElement(&b, "\t", "key", "Label")
+	writeElement(&b, "\t", "string", a.Label)
+
+	writeElement(&b, "\t", "key", "ProgramArguments")
+	b.WriteString("\t<array>\n")
+	for _, arg := range a.ProgramArguments {
+		writeElement(&b, "\t\t", "string", arg)
+	}
+	b.WriteString("\t</array>\n")
+
+	if len(a.EnvironmentVariables) > 0 {
+		names := make([]string, 0, len(a.EnvironmentVariables))
+		for name := range a.EnvironmentVariables {
+			names = append(names, name)
+		}
+		sort.Strings(names)
+
+		writeElement(&b, "\t", "key", "EnvironmentVariables")
+		b.WriteString("\t<dict>\n")
+		for _, name := range names {
+			writeElement(&b, "\t\t", "key", name)
+			writeElement(&b, "\t\t", "string", a.EnvironmentVariables[name])
+		}
+		b.WriteString("\t</dict>\n")
+	}
+
+	if a.LogPath != "" {
+		writeElement(&b, "\t", "key", "StandardOutPath")
+		writeElement(&b, "\t", "string", a.LogPath)
+		writeElement(&b, "\t", "key", "StandardErrorPath")
+		writeElement(&b, "\t", "string", a.LogPath)
+	}
+
+	writeElement(&b, "\t", "key", "RunAtLoad")
+	b.WriteString("\t<true/>\n")
+
+	writeElement(&b, "\t", "key", "KeepAlive")
+	if a.KeepAlive == KeepAliveOnFailure {
+		b.WriteString("\t<dict>\n")
+		writeElement(&b, "\t\t", "key", "SuccessfulExit")
+		b.WriteString("\t\t<false/>\n")
+		b.WriteString("\t</dict>\n")
+	} else {
+		b.WriteString("\t<true/>\n")
+	}
+
+	b.WriteString("</dict>\n</plist>\n")
+	return b.Bytes()
+}
+
+// writeElement writes an XML element with escaped text content on its own line
+func writeElement(b *bytes.Buffer, indent, tag, text string) {
+	b.WriteString(indent + "<" + tag + ">")
+	// Writing to a bytes.Buffer never fails
+	_ = xml.EscapeText(b, []byte(text))
+	b.WriteString("</" + tag + ">\n")
+}
+
+// AgentsDir returns the directory launchd loads the agents of the current user from
+func AgentsDir() (string, error) {
+	home, err := os.UserHomeDir()
+	if err != nil {
+		return "", fmt.Errorf("failed to get home directory: %w", err)
+	}
+	return filepath.Join(home, "Library", "LaunchAgents"), nil
+}
+
+// PlistPath returns the path of the property list of the agent with the given label
+func PlistPath(label string) (string, error) {
+	dir, err := AgentsDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(dir, label+plistExtension), nil
+}
+
+// Install writes the property list of the agent and loads it, which starts the process.
+// An agent which is already installed with the same label is replaced.
+func Install(ctx context.Context, agent *Agent) error {
+	path, err := PlistPath(agent.Label)
+	if err != nil {
+		return err
+	}
+	if err := os.MkdirAll(filepath.Dir(path), 0750); err != nil {
+		return fmt.Errorf("failed to create launch agents directory: %w", err)
+	}
+
+	// Unload the previous version of the agent, if any, so the new property list takes effect
+	_ = launchctl(ctx, "bootout", serviceTarget(agent.Label))
+
+	if err := os.WriteFile(path, agent.Plist(), 0600); err != nil {
+		return fmt.Errorf("failed to write launch agent %s: %w", path, err)
+	}
+	if err := launchctl(ctx, "bootstrap", domainTarget(), path); err != nil {
+		return fmt.Errorf("failed to load launch agent %s: %w", agent.Label, err)
+	}
+	return nil
+}
+
+// Uninstall unloads the agent with the given label, which stops the process, and removes its property list
+func Uninstall(ctx context.Context, label string) error {
+	path, err := PlistPath(label)
+	if err != nil {
+		return err
+	}
+	if _, err := os.Stat(path); errors.Is(err, os.ErrNotExist) {
+		return fmt.Errorf("launch agent %s is not installed", label)
+	}
+
+	// The agent may already be unloaded, e.g. if loading it failed
+	if IsLoaded(ctx, label) {
+		if err := launchctl(ctx, "bootout", serviceTarget(label)); err != nil {
+			return fmt.Errorf("failed to unload launch agent %s: %w", label, err)
+		}
+	}
+	if err := os.Remove(path); err != nil {
+		return fmt.Errorf("failed to remove launch agent %s: %w", path, err)
+	}
+	return nil
+}
+
+// IsLoaded returns true if the agent with the given label is loaded by launchd
+func IsLoaded(ctx context.Context, label string) bool {
+	return launchctl(ctx, "print", serviceTarget(label)) == nil
+}
+
+// ListInstalled returns the labels of the agents managed by ToolHive which are installed for the current user
+func ListInstalled() ([]string, error) {
+	dir, err := AgentsDir()
+	if err != nil {
+		return nil, err
+	}
+	entries, err := os.ReadDir(dir)
+	if err != nil {
+		if errors.Is(err, os.ErrNotExist) {
+			return nil, nil
+		}
+		return nil, fmt.Errorf("failed to read launch agents directory: %w", err)
+	}
+
+	var agentLabels []string
+	for _, entry := range entries {
+		label, ok := strings.CutSuffix(entry.Name(), plistExtension)
+		if ok && !entry.IsDir() && strings.HasPrefix(label, LabelPrefix) {
+			agentLabels = append(agentLabels, label)
+		}
+	}
+	return agentLabels, nil
+}
+
+// domainTarget returns the launchd domain of the GUI session of the current user
+func domainTarget() string {
+	return fmt.Sprintf("gui/%d", os.Getuid())
+}
+
+// serviceTarget returns the launchd service target of the agent with the given label
+func serviceTarget(label string) string {
+	return domainTarget() + "/" + label
+}
+
+// launchctl runs launchctl with the given arguments
+func launchctl(ctx context.Context, args ...string) error {
+	// #nosec G204 - the arguments are labels and paths generated by ToolHive
+	output, err := exec.CommandContext(ctx, "launchctl", args...).CombinedOutput()
+	if err != nil {
+		return fmt.Errorf("launchctl %s: %w: %s", args[0], err, strings.TrimSpace(string(output)))
+	}
+	return nil
+}
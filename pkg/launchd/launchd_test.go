@@ -0,0 +1,115 @@
+package launchd
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestAgent_Plist(t *testing.T) {
+	t.Parallel()
+
+	agent := &Agent{
+		Label:            ServerLabel("fetch"),
+		ProgramArguments: []string{"/usr/local/bin/thv", "restart", "--foreground", "fetch"},
+		EnvironmentVariables: map[string]string{
+			"TOOLHIVE_DETACHED": "true",
+			"PATH":              "/usr/bin:/bin",
+		},
+		LogPath:   "/Users/me/Library/Application Support/toolhive/logs/fetch.log",
+		KeepAlive: KeepAliveOnFailure,
+	}
+
+	expected := `<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>Label</key>
+	<string>com.stacklok.toolhive.server.fetch</string>
+	<key>ProgramArguments</key>
+	<array>
+		<string>/usr/local/bin/thv</string>
+		<string>restart</string>
+		<string>--foreground</string>
+		<string>fetch</string>
+	</array>
+	<key>EnvironmentVariables</key>
+	<dict>
+		<key>PATH</key>
+		<string>/usr/bin:/bin</string>
+		<key>TOOLHIVE_DETACHED</key>
+		<string>true</string>
+	</dict>
+	<key>StandardOutPath</key>
+	<string>/Users/me/Library/Application Support/toolhive/logs/fetch.log</string>
+	<key>StandardErrorPath</key>
+	<string>/Users/me/Library/Application Support/toolhive/logs/fetch.log</string>
+	<key>RunAtLoad</key>
+	<true/>
+	<key>KeepAlive</key>
+	<dict>
+		<key>SuccessfulExit</key>
+		<false/>
+	</dict>
+</dict>
+</plist>
+`
+	assert.Equal(t, expected, string(agent.Plist()))
+}
+
+func TestAgent_PlistKeepAliveAndEscaping(t *testing.T) {
+	t.Parallel()
+
+	agent := &Agent{
+		Label:            ServeLabel,
+		ProgramArguments: []string{"/opt/thv & co/thv", "serve"},
+		KeepAlive:        KeepAliveAlways,
+	}
+
+	plist := string(agent.Plist())
+	assert.Contains(t, plist, "<string>/opt/thv &amp; co/thv</string>")
+	assert.Contains(t, plist, "<key>KeepAlive</key>\n\t<true/>\n")
+	assert.NotContains(t, plist, "EnvironmentVariables")
+	assert.NotContains(t, plist, "StandardOutPath")
+}
+
+func TestServerName(t *testing.T) {
+	t.Parallel()
+
+	name, ok := ServerName(ServerLabel("fetch"))
+	assert.True(t, ok)
+	assert.Equal(t, "fetch", name)
+
+	_, ok = ServerName(ServeLabel)
+	assert.False(t, ok)
+
+	_, ok = ServerName(ServerLabel(""))
+	assert.False(t, ok)
+}
+
+func TestListInstalled(t *testing.T) {
+	home := t.TempDir()
+	t.Setenv("HOME", home)
+
+	labels, err := ListInstalled()
+	require.NoError(t, err)
+	assert.Empty(t, labels)
+
+	dir := filepath.Join(home, "Library", "LaunchAgents")
+	require.NoError(t, os.MkdirAll(dir, 0750))
+	for _, name := range []string{
+		ServerLabel("fetch") + ".plist",
+		ServeLabel + ".plist",
+		"com.example.other.plist",
+		ServerLabel("notes") + ".txt",
+	} {
+		require.NoError(t, os.WriteFile(filepath.Join(dir, name), nil, 0600))
+	}
+
+	labels, err = ListInstalled()
+	require.NoError(t, err)
+	assert.Equal(t, []string{ServeLabel, ServerLabel("fetch")}, labels)
+}
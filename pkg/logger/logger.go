@@ -159,6 +159,10 @@ func Initialize() {
 		log = &slogLogger{logger: slogger}
 	} else {
 		w := os.Stdout
+		// In quiet mode stdout is reserved for essential identifiers only.
+		if viper.GetBool("quiet") {
+			w = os.Stderr
+		}
 
 		handler := slog.NewJSONHandler(w, &slog.HandlerOptions{
 			Level: getLogLevel(),
@@ -182,12 +186,15 @@ func GetLogger(component string) Logger {
 	return log
 }
 
-// getLogLevel returns the appropriate slog.Level based on the debug flag
+// getLogLevel returns the appropriate slog.Level based on the debug and quiet flags
 func getLogLevel() slog.Level {
 	var level slog.Level
-	if viper.GetBool("debug") {
+	switch {
+	case viper.GetBool("debug"):
 		level = slog.LevelDebug
-	} else {
+	case viper.GetBool("quiet"):
+		level = slog.LevelWarn
+	default:
 		level = slog.LevelInfo
 	}
 	return level
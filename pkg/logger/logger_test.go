@@ -4,6 +4,7 @@ import (
 	"bytes"
 	"encoding/json"
 	"io"
+	"log/slog"
 	"os"
 	"strings"
 	"testing"
@@ -353,3 +354,30 @@ func TestGetLogger(t *testing.T) { //nolint:paralleltest // Uses environment var
 		t.Errorf("Expected component='test-component', got %v", logEntry["component"])
 	}
 }
+
+func TestGetLogLevel(t *testing.T) { //nolint:paralleltest // Uses global viper state
+	tests := []struct {
+		name     string
+		debug    bool
+		quiet    bool
+		expected slog.Level
+	}{
+		{"Default", false, false, slog.LevelInfo},
+		{"Debug", true, false, slog.LevelDebug},
+		{"Quiet", false, true, slog.LevelWarn},
+		{"DebugTakesPrecedenceOverQuiet", true, true, slog.LevelDebug},
+	}
+
+	for _, tt := range tests { //nolint:paralleltest // Uses global viper state
+		t.Run(tt.name, func(t *testing.T) {
+			viper.SetDefault("debug", tt.debug)
+			viper.SetDefault("quiet", tt.quiet)
+			defer func() {
+				viper.SetDefault("debug", false)
+				viper.SetDefault("quiet", false)
+			}()
+
+			assert.Equal(t, tt.expected, getLogLevel())
+		})
+	}
+}
@@ -0,0 +1,100 @@
+package logger
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestRotatingFile(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name       string
+		maxBackups int
+		writes     []string
+		expected   map[string]string
+	}{
+		{
+			name:       "No rotation below the maximum size",
+			maxBackups: 2,
+			writes:     []string{"one\n", "two\n"},
+			expected:   map[string]string{"server.log": "one\ntwo\n"},
+		},
+		{
+			name:       "Oldest backups dropped",
+			maxBackups: 2,
+			writes:     []string{"first\n", "second\n", "third\n", "fourth\n"},
+			expected: map[string]string{
+				"server.log":   "fourth\n",
+				"server.log.1": "third\n",
+				"server.log.2": "second\n",
+			},
+		},
+		{
+			name:       "No backups",
+			maxBackups: 0,
+			writes:     []string{"first\n", "second\n"},
+			expected:   map[string]string{"server.log": "second\n"},
+		},
+		{
+			name:       "Oversized write kept whole",
+			maxBackups: 1,
+			writes:     []string{"a very long line\n"},
+			expected:   map[string]string{"server.log": "a very long line\n"},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			dir := t.TempDir()
+			f, err := NewRotatingFile(filepath.Join(dir, "logs", "server.log"), 10, tt.maxBackups)
+			require.NoError(t, err)
+			for _, data := range tt.writes {
+				n, err := f.Write([]byte(data))
+				require.NoError(t, err)
+				assert.Equal(t, len(data), n)
+			}
+			require.NoError(t, f.Close())
+
+			entries, err := os.ReadDir(filepath.Join(dir, "logs"))
+			require.NoError(t, err)
+			files := make(map[string]string, len(entries))
+			for _, entry := range entries {
+				data, err := os.ReadFile(filepath.Join(dir, "logs", entry.Name()))
+				require.NoError(t, err)
+				files[entry.Name()] = string(data)
+			}
+			assert.Equal(t, tt.expected, files)
+		})
+	}
+}
+
+func TestRotatingFile_Append(t *testing.T) {
+	t.Parallel()
+
+	// The size of an existing file counts towards its rotation
+	path := filepath.Join(t.TempDir(), "server.log")
+	require.NoError(t, os.WriteFile(path, []byte("existing\n"), 0600))
+
+	f, err := NewRotatingFile(path, 10, 1)
+	require.NoError(t, err)
+	_, err = f.Write([]byte("new\n"))
+	require.NoError(t, err)
+	require.NoError(t, f.Close())
+
+	data, err := os.ReadFile(path)
+	require.NoError(t, err)
+	assert.Equal(t, "new\n", string(data))
+	data, err = os.ReadFile(path + ".1")
+	require.NoError(t, err)
+	assert.Equal(t, "existing\n", string(data))
+
+	_, err = f.Write([]byte("closed\n"))
+	assert.ErrorIs(t, err, os.ErrClosed)
+}
@@ -0,0 +1,181 @@
+package logger
+
+import (
+	"bytes"
+	"context"
+	"encoding/binary"
+	"errors"
+	"fmt"
+	"log/slog"
+	"strings"
+)
+
+// SinkType is an additional destination for log records, used alongside the
+// default stderr/stdout output.
+type SinkType string
+
+const (
+	// SinkFile is the default sink. Output goes to stderr, which is redirected
+	// to a log file when a server runs in the background.
+	SinkFile SinkType = "file"
+	// SinkSyslog sends logs to the local syslog daemon.
+	SinkSyslog SinkType = "syslog"
+	// SinkJournald sends logs to systemd-journald using its native protocol.
+	SinkJournald SinkType = "journald"
+)
+
+// ValidateSinkType checks that the given sink type is supported.
+// An empty value is treated as SinkFile.
+func ValidateSinkType(sink string) error {
+	switch SinkType(sink) {
+	case "", SinkFile, SinkSyslog, SinkJournald:
+		return nil
+	default:
+		return fmt.Errorf("invalid log sink: %s (valid sinks: %s, %s, %s)", sink, SinkFile, SinkSyslog, SinkJournald)
+	}
+}
+
+// EnableSink adds the given sink to the global logger, in addition to the existing output.
+// The identifier is used to tag the records so that logs from different servers can be
+// told apart, e.g. the syslog tag or the journald SYSLOG_IDENTIFIER field.
+func EnableSink(sink SinkType, identifier string) error {
+	if sink == "" || sink == SinkFile {
+		return nil
+	}
+
+	current, ok := log.(*slogLogger)
+	if !ok {
+		return errors.New("logger is not initialized")
+	}
+
+	handler, err := newSinkHandler(sink, identifier, getLogLevel())
+	if err != nil {
+		return fmt.Errorf("failed to create %s log sink: %w", sink, err)
+	}
+
+	slogger := slog.New(&multiHandler{handlers: []slog.Handler{current.logger.Handler(), handler}})
+	slog.SetDefault(slogger)
+	log = &slogLogger{logger: slogger}
+	return nil
+}
+
+// multiHandler fans out log records to several handlers
+type multiHandler struct {
+	handlers []slog.Handler
+}
+
+func (m *multiHandler) Enabled(ctx context.Context, level slog.Level) bool {
+	for _, h := range m.handlers {
+		if h.Enabled(ctx, level) {
+			return true
+		}
+	}
+	return false
+}
+
+func (m *multiHandler) Handle(ctx context.Context, record slog.Record) error {
+	var errs []error
+	for _, h := range m.handlers {
+		if !h.Enabled(ctx, record.Level) {
+			continue
+		}
+		if err := h.Handle(ctx, record.Clone()); err != nil {
+			errs = append(errs, err)
+		}
+	}
+	return errors.Join(errs...)
+}
+
+func (m *multiHandler) WithAttrs(attrs []slog.Attr) slog.Handler {
+	handlers := make([]slog.Handler, len(m.handlers))
+	for i, h := range m.handlers {
+		handlers[i] = h.WithAttrs(attrs)
+	}
+	return &multiHandler{handlers: handlers}
+}
+
+func (m *multiHandler) WithGroup(name string) slog.Handler {
+	handlers := make([]slog.Handler, len(m.handlers))
+	for i, h := range m.handlers {
+		handlers[i] = h.WithGroup(name)
+	}
+	return &multiHandler{handlers: handlers}
+}
+
+// sinkHandler is a minimal slog.Handler that renders records as a single line
+// of text and hands them, along with their level, to a sink specific writer.
+type sinkHandler struct {
+	level  slog.Leveler
+	attrs  []slog.Attr
+	prefix string
+	write  func(level slog.Level, msg string) error
+}
+
+func (h *sinkHandler) Enabled(_ context.Context, level slog.Level) bool {
+	return level >= h.level.Level()
+}
+
+func (h *sinkHandler) Handle(_ context.Context, record slog.Record) error {
+	var b strings.Builder
+	b.WriteString(record.Message)
+	for _, attr := range h.attrs {
+		fmt.Fprintf(&b, " %s=%v", attr.Key, attr.Value)
+	}
+	record.Attrs(func(attr slog.Attr) bool {
+		fmt.Fprintf(&b, " %s%s=%v", h.prefix, attr.Key, attr.Value)
+		return true
+	})
+	return h.write(record.Level, b.String())
+}
+
+func (h *sinkHandler) WithAttrs(attrs []slog.Attr) slog.Handler {
+	newAttrs := make([]slog.Attr, 0, len(h.attrs)+len(attrs))
+	newAttrs = append(newAttrs, h.attrs...)
+	for _, attr := range attrs {
+		newAttrs = append(newAttrs, slog.Attr{Key: h.prefix + attr.Key, Value: attr.Value})
+	}
+	return &sinkHandler{level: h.level, attrs: newAttrs, prefix: h.prefix, write: h.write}
+}
+
+func (h *sinkHandler) WithGroup(name string) slog.Handler {
+	if name == "" {
+		return h
+	}
+	return &sinkHandler{level: h.level, attrs: h.attrs, prefix: h.prefix + name + ".", write: h.write}
+}
+
+// journaldPriority maps a slog level to a syslog priority as used by journald
+func journaldPriority(level slog.Level) int {
+	switch {
+	case level >= slog.LevelError:
+		return 3
+	case level >= slog.LevelWarn:
+		return 4
+	case level >= slog.LevelInfo:
+		return 6
+	default:
+		return 7
+	}
+}
+
+// encodeJournaldEntry encodes the given fields using the journald native protocol.
+// Values containing newlines are encoded in the binary length-prefixed form.
+func encodeJournaldEntry(fields [][2]string) []byte {
+	var buf bytes.Buffer
+	for _, field := range fields {
+		key, value := field[0], field[1]
+		if !strings.Contains(value, "\n") {
+			buf.WriteString(key)
+			buf.WriteByte('=')
+			buf.WriteString(value)
+			buf.WriteByte('\n')
+			continue
+		}
+		buf.WriteString(key)
+		buf.WriteByte('\n')
+		_ = binary.Write(&buf, binary.LittleEndian, uint64(len(value)))
+		buf.WriteString(value)
+		buf.WriteByte('\n')
+	}
+	return buf.Bytes()
+}
@@ -0,0 +1,96 @@
+package logger
+
+import (
+	"bytes"
+	"context"
+	"encoding/binary"
+	"log/slog"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestValidateSinkType(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name    string
+		sink    string
+		wantErr bool
+	}{
+		{"Empty", "", false},
+		{"File", "file", false},
+		{"Syslog", "syslog", false},
+		{"Journald", "journald", false},
+		{"Invalid", "kafka", true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			err := ValidateSinkType(tt.sink)
+			if tt.wantErr {
+				assert.Error(t, err)
+			} else {
+				assert.NoError(t, err)
+			}
+		})
+	}
+}
+
+func TestEncodeJournaldEntry(t *testing.T) {
+	t.Parallel()
+
+	t.Run("SingleLineValues", func(t *testing.T) {
+		t.Parallel()
+		got := encodeJournaldEntry([][2]string{
+			{"MESSAGE", "hello"},
+			{"PRIORITY", "6"},
+		})
+		assert.Equal(t, "MESSAGE=hello\nPRIORITY=6\n", string(got))
+	})
+
+	t.Run("MultiLineValue", func(t *testing.T) {
+		t.Parallel()
+		got := encodeJournaldEntry([][2]string{{"MESSAGE", "a\nb"}})
+
+		var expected bytes.Buffer
+		expected.WriteString("MESSAGE\n")
+		require.NoError(t, binary.Write(&expected, binary.LittleEndian, uint64(3)))
+		expected.WriteString("a\nb\n")
+		assert.Equal(t, expected.Bytes(), got)
+	})
+}
+
+func TestJournaldPriority(t *testing.T) {
+	t.Parallel()
+
+	assert.Equal(t, 7, journaldPriority(slog.LevelDebug))
+	assert.Equal(t, 6, journaldPriority(slog.LevelInfo))
+	assert.Equal(t, 4, journaldPriority(slog.LevelWarn))
+	assert.Equal(t, 3, journaldPriority(slog.LevelError))
+}
+
+func TestMultiHandlerFansOut(t *testing.T) {
+	t.Parallel()
+
+	var infoLines, warnLines []string
+	infoHandler := &sinkHandler{level: slog.LevelInfo, write: func(_ slog.Level, msg string) error {
+		infoLines = append(infoLines, msg)
+		return nil
+	}}
+	warnHandler := &sinkHandler{level: slog.LevelWarn, write: func(_ slog.Level, msg string) error {
+		warnLines = append(warnLines, msg)
+		return nil
+	}}
+
+	slogger := slog.New(&multiHandler{handlers: []slog.Handler{infoHandler, warnHandler}})
+	slogger.With("server", "fetch").Info("started", "port", 8080)
+	slogger.Warn("slow")
+	slogger.Debug("ignored")
+
+	assert.Equal(t, []string{"started server=fetch port=8080", "slow"}, infoLines)
+	assert.Equal(t, []string{"slow"}, warnLines)
+	assert.False(t, slogger.Handler().Enabled(context.Background(), slog.LevelDebug))
+}
@@ -0,0 +1,68 @@
+//go:build !windows
+// +build !windows
+
+package logger
+
+import (
+	"fmt"
+	"log/slog"
+	"log/syslog"
+	"net"
+	"strconv"
+)
+
+// journaldSocketPath is the well-known path of the journald native protocol socket
+const journaldSocketPath = "/run/systemd/journal/socket"
+
+func newSinkHandler(sink SinkType, identifier string, level slog.Level) (slog.Handler, error) {
+	switch sink {
+	case SinkSyslog:
+		return newSyslogHandler(identifier, level)
+	case SinkJournald:
+		return newJournaldHandler(identifier, level)
+	default:
+		return nil, fmt.Errorf("unsupported log sink: %s", sink)
+	}
+}
+
+func newSyslogHandler(identifier string, level slog.Level) (slog.Handler, error) {
+	w, err := syslog.New(syslog.LOG_INFO|syslog.LOG_DAEMON, identifier)
+	if err != nil {
+		return nil, err
+	}
+
+	return &sinkHandler{
+		level: level,
+		write: func(level slog.Level, msg string) error {
+			switch {
+			case level >= slog.LevelError:
+				return w.Err(msg)
+			case level >= slog.LevelWarn:
+				return w.Warning(msg)
+			case level >= slog.LevelInfo:
+				return w.Info(msg)
+			default:
+				return w.Debug(msg)
+			}
+		},
+	}, nil
+}
+
+func newJournaldHandler(identifier string, level slog.Level) (slog.Handler, error) {
+	conn, err := net.DialUnix("unixgram", nil, &net.UnixAddr{Name: journaldSocketPath, Net: "unixgram"})
+	if err != nil {
+		return nil, fmt.Errorf("failed to connect to journald: %w", err)
+	}
+
+	return &sinkHandler{
+		level: level,
+		write: func(level slog.Level, msg string) error {
+			_, err := conn.Write(encodeJournaldEntry([][2]string{
+				{"MESSAGE", msg},
+				{"PRIORITY", strconv.Itoa(journaldPriority(level))},
+				{"SYSLOG_IDENTIFIER", identifier},
+			}))
+			return err
+		},
+	}, nil
+}
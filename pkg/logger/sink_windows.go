@@ -0,0 +1,13 @@
+//go:build windows
+// +build windows
+
+package logger
+
+import (
+	"fmt"
+	"log/slog"
+)
+
+func newSinkHandler(sink SinkType, _ string, _ slog.Level) (slog.Handler, error) {
+	return nil, fmt.Errorf("log sink %s is not supported on Windows", sink)
+}
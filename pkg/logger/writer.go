@@ -0,0 +1,58 @@
+package logger
+
+import (
+	"bytes"
+	"strings"
+	"sync"
+)
+
+// LineWriter is an io.Writer which logs every line written to it.
+// It is used to forward the output of other processes, such as the stderr of MCP servers, to the ToolHive logs.
+type LineWriter struct {
+	logf   func(msg string, args ...any)
+	prefix string
+
+	mu  sync.Mutex
+	buf bytes.Buffer
+}
+
+// NewLineWriter creates a LineWriter which logs every line using logf, prefixed with prefix
+func NewLineWriter(logf func(msg string, args ...any), prefix string) *LineWriter {
+	return &LineWriter{logf: logf, prefix: prefix}
+}
+
+// Write logs every complete line in p and buffers the remainder until the rest of the line is written
+func (w *LineWriter) Write(p []byte) (int, error) {
+	w.mu.Lock()
+	defer w.mu.Unlock()
+
+	w.buf.Write(p)
+	for {
+		idx := bytes.IndexByte(w.buf.Bytes(), '\n')
+		if idx < 0 {
+			break
+		}
+		line := string(w.buf.Next(idx + 1))
+		w.log(line)
+	}
+	return len(p), nil
+}
+
+// Flush logs the incomplete line buffered by the writer, if any
+func (w *LineWriter) Flush() {
+	w.mu.Lock()
+	defer w.mu.Unlock()
+
+	if w.buf.Len() > 0 {
+		w.log(w.buf.String())
+		w.buf.Reset()
+	}
+}
+
+func (w *LineWriter) log(line string) {
+	line = strings.TrimRight(line, "\r\n")
+	if strings.TrimSpace(line) == "" {
+		return
+	}
+	w.logf("%s%s", w.prefix, line)
+}
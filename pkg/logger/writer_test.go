@@ -0,0 +1,29 @@
+package logger
+
+import (
+	"fmt"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestLineWriter(t *testing.T) {
+	t.Parallel()
+
+	var lines []string
+	w := NewLineWriter(func(msg string, args ...any) {
+		lines = append(lines, fmt.Sprintf(msg, args...))
+	}, "server: ")
+
+	// Lines may be split across writes, and empty lines are skipped
+	for _, chunk := range []string{"starting", " up\r\n\nlistening on %s\n", "partial"} {
+		n, err := w.Write([]byte(chunk))
+		require.NoError(t, err)
+		assert.Equal(t, len(chunk), n)
+	}
+	assert.Equal(t, []string{"server: starting up", "server: listening on %s"}, lines)
+
+	w.Flush()
+	assert.Equal(t, "server: partial", lines[len(lines)-1])
+}
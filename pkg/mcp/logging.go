@@ -0,0 +1,101 @@
+package mcp
+
+import (
+	"bytes"
+	"fmt"
+	"net/http"
+	"strings"
+	"time"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+// maxLoggedPayloadSize is the maximum number of bytes of a payload which are logged
+const maxLoggedPayloadSize = 4096
+
+// LoggingMiddleware creates an HTTP middleware logging the MCP requests going through the proxy, with their
+// method, the tool, prompt or resource they target, the status of the response and how long it took.
+// With logPayloads, the parameters of the requests and the JSON responses are logged too, truncated to
+// maxLoggedPayloadSize bytes; they are not logged by default since they can contain sensitive data.
+//
+// It must follow ParsingMiddleware. Requests sent over the SSE transport are answered on the SSE stream,
+// so their status and duration are the ones of the delivery of the request to the server.
+func LoggingMiddleware(logPayloads bool) func(http.Handler) http.Handler {
+	return func(next http.Handler) http.Handler {
+		return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			parsed := GetParsedMCPRequest(r.Context())
+			if parsed == nil {
+				next.ServeHTTP(w, r)
+				return
+			}
+
+			lw := &loggingWriter{ResponseWriter: w, statusCode: http.StatusOK, capture: logPayloads}
+			start := time.Now()
+			next.ServeHTTP(lw, r)
+			duration := time.Since(start)
+
+			var msg strings.Builder
+			fmt.Fprintf(&msg, "MCP request: method=%s", parsed.Method)
+			if parsed.ResourceID != "" {
+				fmt.Fprintf(&msg, " target=%s", parsed.ResourceID)
+			}
+			if parsed.ID != nil {
+				fmt.Fprintf(&msg, " id=%v", parsed.ID)
+			}
+			fmt.Fprintf(&msg, " status=%d duration=%s", lw.statusCode, duration.Round(time.Microsecond))
+			if logPayloads {
+				if len(parsed.Params) > 0 {
+					fmt.Fprintf(&msg, " params=%s", truncatePayload(parsed.Params))
+				}
+				if lw.body.Len() > 0 {
+					fmt.Fprintf(&msg, " response=%s", truncatePayload(lw.body.Bytes()))
+				}
+			}
+			logger.Info(msg.String())
+		})
+	}
+}
+
+// truncatePayload returns a payload truncated to maxLoggedPayloadSize bytes
+func truncatePayload(payload []byte) string {
+	if len(payload) <= maxLoggedPayloadSize {
+		return string(payload)
+	}
+	return string(payload[:maxLoggedPayloadSize]) + "...(truncated)"
+}
+
+// loggingWriter wraps http.ResponseWriter to capture the status and the JSON body of responses
+type loggingWriter struct {
+	http.ResponseWriter
+	statusCode  int
+	capture     bool
+	wroteHeader bool
+	body        bytes.Buffer
+}
+
+func (lw *loggingWriter) WriteHeader(statusCode int) {
+	if !lw.wroteHeader {
+		lw.statusCode = statusCode
+		lw.wroteHeader = true
+		// Event streams are long-lived, so only plain JSON responses are captured
+		lw.capture = lw.capture && strings.HasPrefix(lw.Header().Get("Content-Type"), "application/json")
+	}
+	lw.ResponseWriter.WriteHeader(statusCode)
+}
+
+func (lw *loggingWriter) Write(data []byte) (int, error) {
+	if !lw.wroteHeader {
+		lw.WriteHeader(http.StatusOK)
+	}
+	if lw.capture && lw.body.Len() <= maxLoggedPayloadSize {
+		lw.body.Write(data)
+	}
+	return lw.ResponseWriter.Write(data)
+}
+
+// Flush sends the response written so far to the client, so event streams are not buffered
+func (lw *loggingWriter) Flush() {
+	if flusher, ok := lw.ResponseWriter.(http.Flusher); ok {
+		flusher.Flush()
+	}
+}
@@ -0,0 +1,110 @@
+package mcp
+
+import (
+	"context"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+func TestLoggingMiddleware(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	tests := []struct {
+		name        string
+		logPayloads bool
+		contentType string
+		status      int
+		body        string
+		parsed      bool
+		expectBody  string
+	}{
+		{
+			name:        "JSON response without payloads",
+			contentType: "application/json",
+			status:      http.StatusOK,
+			body:        `{"jsonrpc":"2.0","id":1,"result":{}}`,
+			parsed:      true,
+		},
+		{
+			name:        "JSON response with payloads",
+			logPayloads: true,
+			contentType: "application/json",
+			status:      http.StatusOK,
+			body:        `{"jsonrpc":"2.0","id":1,"result":{}}`,
+			parsed:      true,
+			expectBody:  `{"jsonrpc":"2.0","id":1,"result":{}}`,
+		},
+		{
+			name:        "Event stream with payloads",
+			logPayloads: true,
+			contentType: "text/event-stream",
+			status:      http.StatusOK,
+			body:        "data: {}\n\n",
+			parsed:      true,
+		},
+		{
+			name:        "Accepted request",
+			contentType: "text/plain",
+			status:      http.StatusAccepted,
+			body:        "Accepted",
+			parsed:      true,
+		},
+		{
+			name:        "Request which is not an MCP request",
+			contentType: "text/plain",
+			status:      http.StatusOK,
+			body:        "OK",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			var writer *loggingWriter
+			handler := LoggingMiddleware(tt.logPayloads)(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+				writer, _ = w.(*loggingWriter)
+				w.Header().Set("Content-Type", tt.contentType)
+				w.WriteHeader(tt.status)
+				_, _ = w.Write([]byte(tt.body))
+			}))
+
+			req := httptest.NewRequest(http.MethodPost, "/messages", nil)
+			if tt.parsed {
+				parsed := &ParsedMCPRequest{Method: "tools/call", ID: 1, ResourceID: "fetch", IsRequest: true}
+				req = req.WithContext(context.WithValue(req.Context(), MCPRequestContextKey, parsed))
+			}
+			rec := httptest.NewRecorder()
+			handler.ServeHTTP(rec, req)
+
+			// The response reaches the client unchanged
+			assert.Equal(t, tt.status, rec.Code)
+			assert.Equal(t, tt.body, rec.Body.String())
+
+			if !tt.parsed {
+				assert.Nil(t, writer)
+				return
+			}
+			if assert.NotNil(t, writer) {
+				assert.Equal(t, tt.status, writer.statusCode)
+				assert.Equal(t, tt.expectBody, writer.body.String())
+			}
+		})
+	}
+}
+
+func TestTruncatePayload(t *testing.T) {
+	t.Parallel()
+
+	assert.Equal(t, `{"a":1}`, truncatePayload([]byte(`{"a":1}`)))
+
+	long := truncatePayload([]byte(strings.Repeat("x", maxLoggedPayloadSize+10)))
+	assert.Equal(t, strings.Repeat("x", maxLoggedPayloadSize)+"...(truncated)", long)
+}
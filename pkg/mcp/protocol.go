@@ -0,0 +1,36 @@
+package mcp
+
+import (
+	"encoding/json"
+
+	"golang.org/x/exp/jsonrpc2"
+)
+
+const (
+	// ProtocolVersionHeader is the header in which streamable HTTP clients send the protocol version
+	// negotiated with the initialize request, following
+	// https://modelcontextprotocol.io/specification/2025-06-18/basic/transports#protocol-version-header
+	ProtocolVersionHeader = "MCP-Protocol-Version"
+	// DefaultProtocolVersion is the protocol version servers assume for clients which don't send the header
+	DefaultProtocolVersion = "2025-03-26"
+)
+
+// NegotiatedProtocolVersion returns the protocol version of the response to an initialize request,
+// or an empty string if the message is not such a response
+func NegotiatedProtocolVersion(message []byte) string {
+	msg, err := jsonrpc2.DecodeMessage(message)
+	if err != nil {
+		return ""
+	}
+	resp, ok := msg.(*jsonrpc2.Response)
+	if !ok || resp.Error != nil || len(resp.Result) == 0 {
+		return ""
+	}
+	var result struct {
+		ProtocolVersion string `json:"protocolVersion"`
+	}
+	if json.Unmarshal(resp.Result, &result) != nil {
+		return ""
+	}
+	return result.ProtocolVersion
+}
@@ -0,0 +1,34 @@
+package mcp
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestNegotiatedProtocolVersion(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name     string
+		message  string
+		expected string
+	}{
+		{
+			name:     "Initialize response",
+			message:  `{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-06-18","capabilities":{}}}`,
+			expected: "2025-06-18",
+		},
+		{name: "Other response", message: `{"jsonrpc":"2.0","id":2,"result":{"tools":[]}}`},
+		{name: "Error response", message: `{"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"Unsupported"}}`},
+		{name: "Request", message: `{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-06-18"}}`},
+		{name: "Invalid JSON", message: `{"jsonrpc"`},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, NegotiatedProtocolVersion([]byte(tt.message)))
+		})
+	}
+}
@@ -0,0 +1,250 @@
+package mcp
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"slices"
+	"strings"
+)
+
+const (
+	// toolNotFoundCode is the JSON-RPC error code of calls to tools which are not exposed (invalid params)
+	toolNotFoundCode = -32602
+	// invalidRequestCode is the JSON-RPC error code of requests which are rejected as a whole
+	invalidRequestCode = -32600
+)
+
+// ToolFilterMiddleware creates an HTTP middleware exposing only the given tools of an MCP server.
+// Calls to other tools are rejected with a JSON-RPC error, and the other tools are removed from the
+// results of tools/list, whether they are returned in the response to the request, in an event stream
+// of the Streamable HTTP transport, or in the SSE stream of the SSE transport.
+//
+// JSON-RPC batches are rejected, since the calls and the tool lists they hold are not filtered. Requests
+// which ParsingMiddleware does not parse, e.g. because of their content type, are parsed by the middleware.
+//
+// It must follow ParsingMiddleware.
+func ToolFilterMiddleware(tools []string) func(http.Handler) http.Handler {
+	return func(next http.Handler) http.Handler {
+		return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			// WebSocket connections take over the connection of the response, and do not carry tool lists
+			if isWebSocketUpgrade(r) {
+				next.ServeHTTP(w, r)
+				return
+			}
+
+			parsed := GetParsedMCPRequest(r.Context())
+			if r.Method == http.MethodPost {
+				body, err := io.ReadAll(r.Body)
+				if err != nil {
+					http.Error(w, "failed to read request body", http.StatusBadRequest)
+					return
+				}
+				r.Body = io.NopCloser(bytes.NewReader(body))
+				if isBatch(body) {
+					writeRequestError(w, "Batch requests are not supported when tools are filtered")
+					return
+				}
+				if parsed == nil {
+					parsed = parseMCPRequest(body)
+				}
+			}
+			if parsed != nil && parsed.Method == "tools/call" && !slices.Contains(tools, parsed.ResourceID) {
+				writeToolNotFound(w, parsed.ID, parsed.ResourceID)
+				return
+			}
+
+			lw := &toolsListWriter{ResponseWriter: w, rewrite: func(listed []json.RawMessage) []json.RawMessage {
+				return filterTools(listed, tools)
+			}}
+			next.ServeHTTP(lw, r)
+			lw.finish()
+		})
+	}
+}
+
+// isWebSocketUpgrade returns true if the request opens a WebSocket connection. Other requests with an
+// Upgrade header are handled like any other request, so the header can't be used to skip the checks.
+func isWebSocketUpgrade(r *http.Request) bool {
+	if r.Method != http.MethodGet || !strings.EqualFold(r.Header.Get("Upgrade"), "websocket") {
+		return false
+	}
+	for _, value := range r.Header.Values("Connection") {
+		for _, token := range strings.Split(value, ",") {
+			if strings.EqualFold(strings.TrimSpace(token), "upgrade") {
+				return true
+			}
+		}
+	}
+	return false
+}
+
+// isBatch returns true if the body is a JSON-RPC batch, i.e. a JSON array
+func isBatch(body []byte) bool {
+	trimmed := bytes.TrimSpace(body)
+	return len(trimmed) > 0 && trimmed[0] == '['
+}
+
+// writeRequestError writes the JSON-RPC error response to a request which is rejected as a whole
+func writeRequestError(w http.ResponseWriter, message string) {
+	writeRPCError(w, http.StatusBadRequest, nil, invalidRequestCode, message)
+}
+
+// writeToolNotFound writes the JSON-RPC error response to a call to a tool which is not exposed
+func writeToolNotFound(w http.ResponseWriter, id any, tool string) {
+	writeRPCError(w, http.StatusForbidden, id, toolNotFoundCode, fmt.Sprintf("Tool not available: %s", tool))
+}
+
+// writeRPCError writes a JSON-RPC error response with the HTTP status
+func writeRPCError(w http.ResponseWriter, status int, id any, code int, message string) {
+	type rpcError struct {
+		Code    int    `json:"code"`
+		Message string `json:"message"`
+	}
+	response := struct {
+		JSONRPC string   `json:"jsonrpc"`
+		ID      any      `json:"id"`
+		Error   rpcError `json:"error"`
+	}{
+		JSONRPC: "2.0",
+		ID:      id,
+		Error:   rpcError{Code: code, Message: message},
+	}
+	w.Header().Set("Content-Type", "application/json")
+	w.WriteHeader(status)
+	_ = json.NewEncoder(w).Encode(response)
+}
+
+// filterTools returns the tools of a tools/list result which are exposed
+func filterTools(listed []json.RawMessage, tools []string) []json.RawMessage {
+	exposed := make([]json.RawMessage, 0, len(listed))
+	for _, tool := range listed {
+		var named struct {
+			Name string `json:"name"`
+		}
+		if err := json.Unmarshal(tool, &named); err == nil && slices.Contains(tools, named.Name) {
+			exposed = append(exposed, tool)
+		}
+	}
+	return exposed
+}
+
+// rewriteToolsResult rewrites the tools of a JSON-RPC message with the rewrite function, if it is the
+// response to tools/list. It returns the message unchanged otherwise.
+func rewriteToolsResult(message []byte, rewrite func([]json.RawMessage) []json.RawMessage) []byte {
+	var fields map[string]json.RawMessage
+	if err := json.Unmarshal(message, &fields); err != nil || fields["result"] == nil {
+		return message
+	}
+	var result map[string]json.RawMessage
+	if err := json.Unmarshal(fields["result"], &result); err != nil || result["tools"] == nil {
+		return message
+	}
+	var listed []json.RawMessage
+	if err := json.Unmarshal(result["tools"], &listed); err != nil {
+		return message
+	}
+
+	var err error
+	if result["tools"], err = json.Marshal(rewrite(listed)); err != nil {
+		return message
+	}
+	if fields["result"], err = json.Marshal(result); err != nil {
+		return message
+	}
+	rewritten, err := json.Marshal(fields)
+	if err != nil {
+		return message
+	}
+	return rewritten
+}
+
+// toolsListWriter wraps http.ResponseWriter to rewrite the results of tools/list in the responses.
+// JSON responses are buffered and rewritten once complete, and event streams are rewritten event by event.
+type toolsListWriter struct {
+	http.ResponseWriter
+	rewrite     func([]json.RawMessage) []json.RawMessage
+	wroteHeader bool
+	statusCode  int
+	buffered    bool
+	stream      bool
+	pending     bytes.Buffer
+}
+
+func (lw *toolsListWriter) WriteHeader(statusCode int) {
+	if lw.wroteHeader {
+		return
+	}
+	lw.wroteHeader = true
+	contentType := lw.Header().Get("Content-Type")
+	lw.buffered = strings.HasPrefix(contentType, "application/json")
+	lw.stream = strings.HasPrefix(contentType, "text/event-stream")
+	if lw.buffered {
+		// The header is written with the rewritten response, whose length differs
+		lw.statusCode = statusCode
+		lw.Header().Del("Content-Length")
+		return
+	}
+	lw.ResponseWriter.WriteHeader(statusCode)
+}
+
+func (lw *toolsListWriter) Write(data []byte) (int, error) {
+	if !lw.wroteHeader {
+		lw.WriteHeader(http.StatusOK)
+	}
+	if lw.buffered {
+		return lw.pending.Write(data)
+	}
+	if !lw.stream {
+		return lw.ResponseWriter.Write(data)
+	}
+
+	// Rewrite the complete lines of the event stream, and keep the rest until the end of its line is written
+	lw.pending.Write(data)
+	for {
+		line, err := lw.pending.ReadBytes('\n')
+		if err != nil {
+			// Keep the incomplete line
+			remaining := append([]byte(nil), line...)
+			lw.pending.Reset()
+			lw.pending.Write(remaining)
+			return len(data), nil
+		}
+		if payload, ok := bytes.CutPrefix(line, []byte("data:")); ok {
+			payload = bytes.TrimSpace(payload)
+			if rewritten := rewriteToolsResult(payload, lw.rewrite); !bytes.Equal(rewritten, payload) {
+				line = append(append([]byte("data: "), rewritten...), '\n')
+			}
+		}
+		if _, err := lw.ResponseWriter.Write(line); err != nil {
+			return 0, err
+		}
+	}
+}
+
+// Flush sends the events written so far to the client. Buffered JSON responses are sent by finish.
+func (lw *toolsListWriter) Flush() {
+	if !lw.wroteHeader {
+		lw.WriteHeader(http.StatusOK)
+	}
+	if lw.buffered {
+		return
+	}
+	if flusher, ok := lw.ResponseWriter.(http.Flusher); ok {
+		flusher.Flush()
+	}
+}
+
+// finish writes the rewritten JSON response, or the rest of an event stream, once the response is complete
+func (lw *toolsListWriter) finish() {
+	if lw.buffered {
+		lw.ResponseWriter.WriteHeader(lw.statusCode)
+		_, _ = lw.ResponseWriter.Write(rewriteToolsResult(lw.pending.Bytes(), lw.rewrite))
+		return
+	}
+	if lw.pending.Len() > 0 {
+		_, _ = lw.ResponseWriter.Write(lw.pending.Bytes())
+	}
+}
@@ -0,0 +1,212 @@
+package mcp
+
+import (
+	"context"
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+const toolsListResult = `{"jsonrpc":"2.0","id":1,"result":{"tools":[` +
+	`{"name":"fetch","inputSchema":{"type":"object"}},` +
+	`{"name":"delete_everything","inputSchema":{"type":"object"}}]}}`
+
+func TestToolFilterMiddleware(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name          string
+		parsed        *ParsedMCPRequest
+		contentType   string
+		body          string
+		expectStatus  int
+		expectTools   []string
+		expectBody    string
+		expectBlocked bool
+	}{
+		{
+			name:         "Allowed tool call",
+			parsed:       &ParsedMCPRequest{Method: "tools/call", ID: 1, ResourceID: "fetch"},
+			contentType:  "application/json",
+			body:         `{"jsonrpc":"2.0","id":1,"result":{"content":[]}}`,
+			expectStatus: http.StatusOK,
+			expectBody:   `{"jsonrpc":"2.0","id":1,"result":{"content":[]}}`,
+		},
+		{
+			name:          "Call to a tool which is not exposed",
+			parsed:        &ParsedMCPRequest{Method: "tools/call", ID: 2, ResourceID: "delete_everything"},
+			expectStatus:  http.StatusForbidden,
+			expectBlocked: true,
+		},
+		{
+			name:         "JSON tools/list response",
+			parsed:       &ParsedMCPRequest{Method: "tools/list", ID: 1},
+			contentType:  "application/json",
+			body:         toolsListResult,
+			expectStatus: http.StatusOK,
+			expectTools:  []string{"fetch"},
+		},
+		{
+			name:         "Event stream tools/list response",
+			parsed:       &ParsedMCPRequest{Method: "tools/list", ID: 1},
+			contentType:  "text/event-stream",
+			body:         "event: message\ndata: " + toolsListResult + "\n\n",
+			expectStatus: http.StatusOK,
+			expectTools:  []string{"fetch"},
+		},
+		{
+			name:         "SSE stream with other events",
+			contentType:  "text/event-stream",
+			body:         "event: endpoint\ndata: /messages?session_id=abc\n\n",
+			expectStatus: http.StatusOK,
+			expectBody:   "event: endpoint\ndata: /messages?session_id=abc\n\n",
+		},
+		{
+			name:         "Error response",
+			parsed:       &ParsedMCPRequest{Method: "tools/list", ID: 1},
+			contentType:  "application/json",
+			body:         `{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"Method not found"}}`,
+			expectStatus: http.StatusOK,
+			expectBody:   `{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"Method not found"}}`,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			called := false
+			handler := ToolFilterMiddleware([]string{"fetch"})(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+				called = true
+				w.Header().Set("Content-Type", tt.contentType)
+				// Write the body in two parts, as a server streaming its response could
+				half := len(tt.body) / 2
+				_, _ = w.Write([]byte(tt.body[:half]))
+				_, _ = w.Write([]byte(tt.body[half:]))
+			}))
+
+			req := httptest.NewRequest(http.MethodPost, "/mcp", nil)
+			if tt.parsed != nil {
+				req = req.WithContext(context.WithValue(req.Context(), MCPRequestContextKey, tt.parsed))
+			}
+			rec := httptest.NewRecorder()
+			handler.ServeHTTP(rec, req)
+
+			assert.Equal(t, tt.expectStatus, rec.Code)
+			assert.Equal(t, !tt.expectBlocked, called)
+			if tt.expectBlocked {
+				assert.Contains(t, rec.Body.String(), "Tool not available: delete_everything")
+				return
+			}
+			if tt.expectBody != "" {
+				assert.Equal(t, tt.expectBody, rec.Body.String())
+				return
+			}
+
+			body := rec.Body.String()
+			if tt.contentType == "text/event-stream" {
+				// Extract the data of the event
+				_, data, found := strings.Cut(body, "data: ")
+				require.True(t, found)
+				body, _, _ = strings.Cut(data, "\n")
+			}
+			var response struct {
+				Result struct {
+					Tools []struct {
+						Name string `json:"name"`
+					} `json:"tools"`
+				} `json:"result"`
+			}
+			require.NoError(t, json.Unmarshal([]byte(body), &response))
+			var names []string
+			for _, tool := range response.Result.Tools {
+				names = append(names, tool.Name)
+			}
+			assert.Equal(t, tt.expectTools, names)
+		})
+	}
+}
+
+func TestToolFilterMiddleware_RequestsWhichCantSkipTheFilter(t *testing.T) {
+	t.Parallel()
+
+	blockedCall := `{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"delete_everything","arguments":{}}}`
+	tests := []struct {
+		name         string
+		method       string
+		headers      map[string]string
+		body         string
+		expectStatus int
+		expectError  string
+	}{
+		{
+			name:         "Call with an Upgrade header",
+			method:       http.MethodPost,
+			headers:      map[string]string{"Content-Type": "application/json", "Upgrade": "x", "Connection": "upgrade"},
+			body:         blockedCall,
+			expectStatus: http.StatusForbidden,
+			expectError:  "Tool not available: delete_everything",
+		},
+		{
+			name:         "Call in a batch",
+			method:       http.MethodPost,
+			headers:      map[string]string{"Content-Type": "application/json"},
+			body:         "[" + blockedCall + "]",
+			expectStatus: http.StatusBadRequest,
+			expectError:  "Batch requests are not supported",
+		},
+		{
+			name:         "Allowed call in a batch",
+			method:       http.MethodPost,
+			headers:      map[string]string{"Content-Type": "application/json"},
+			body:         ` [{"jsonrpc":"2.0","id":4,"method":"tools/call","params":{"name":"fetch"}}]`,
+			expectStatus: http.StatusBadRequest,
+			expectError:  "Batch requests are not supported",
+		},
+		{
+			name:         "Call with another content type",
+			method:       http.MethodPost,
+			headers:      map[string]string{"Content-Type": "text/plain"},
+			body:         blockedCall,
+			expectStatus: http.StatusForbidden,
+			expectError:  "Tool not available: delete_everything",
+		},
+		{
+			name:         "WebSocket upgrade",
+			method:       http.MethodGet,
+			headers:      map[string]string{"Upgrade": "websocket", "Connection": "keep-alive, Upgrade"},
+			expectStatus: http.StatusSwitchingProtocols,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			called := false
+			next := http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+				called = true
+				w.WriteHeader(http.StatusSwitchingProtocols)
+			})
+			handler := ParsingMiddleware(ToolFilterMiddleware([]string{"fetch"})(next))
+
+			req := httptest.NewRequest(tt.method, "/mcp", strings.NewReader(tt.body))
+			for key, value := range tt.headers {
+				req.Header.Set(key, value)
+			}
+			rec := httptest.NewRecorder()
+			handler.ServeHTTP(rec, req)
+
+			assert.Equal(t, tt.expectStatus, rec.Code)
+			assert.Equal(t, tt.expectError == "", called)
+			if tt.expectError != "" {
+				assert.Contains(t, rec.Body.String(), tt.expectError)
+			}
+		})
+	}
+}
@@ -0,0 +1,166 @@
+package mcp
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"strconv"
+
+	"sigs.k8s.io/yaml"
+)
+
+// ToolOverride is how the proxy presents a tool of an MCP server to its clients
+type ToolOverride struct {
+	// Name is the name of the tool for the clients, instead of its name on the server
+	Name string `json:"name,omitempty" yaml:"name,omitempty"`
+	// Description is the description of the tool for the clients, instead of its description on the server
+	Description string `json:"description,omitempty" yaml:"description,omitempty"`
+}
+
+// LoadToolOverrides loads the overrides of the tools of an MCP server from a YAML or JSON file,
+// which maps the names of the tools on the server to their overrides, for example:
+//
+//	fetch:
+//	  name: web_fetch
+//	  description: Fetch a URL from the internet
+//
+//nolint:gosec // This is intentionally loading a file specified by the user
+func LoadToolOverrides(path string) (map[string]ToolOverride, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read tool override file: %w", err)
+	}
+	var overrides map[string]ToolOverride
+	if err := yaml.Unmarshal(data, &overrides); err != nil {
+		return nil, fmt.Errorf("failed to parse tool override file: %w", err)
+	}
+	if err := validateToolOverrides(overrides); err != nil {
+		return nil, fmt.Errorf("invalid tool override file: %w", err)
+	}
+	return overrides, nil
+}
+
+// validateToolOverrides checks that the overrides change something, and that no two tools get the same name
+func validateToolOverrides(overrides map[string]ToolOverride) error {
+	names := make(map[string]string, len(overrides))
+	for tool, override := range overrides {
+		if override.Name == "" && override.Description == "" {
+			return fmt.Errorf("the override of tool %s has neither a name nor a description", tool)
+		}
+		name := exposedToolName(tool, override)
+		if other, ok := names[name]; ok {
+			return fmt.Errorf("tools %s and %s are both exposed as %s", other, tool, name)
+		}
+		names[name] = tool
+	}
+	return nil
+}
+
+// exposedToolName returns the name of a tool for the clients
+func exposedToolName(tool string, override ToolOverride) string {
+	if override.Name != "" {
+		return override.Name
+	}
+	return tool
+}
+
+// ToolOverrideMiddleware creates an HTTP middleware renaming the tools of an MCP server and rewriting their
+// descriptions with the overrides: the tools are listed by tools/list with their new names and descriptions,
+// and the calls to their new names are forwarded to the server with their original names. Calls to the
+// original names of renamed tools are rejected, as the clients do not see them.
+//
+// It must follow ParsingMiddleware, and precede the middlewares which use the names of the tools, so
+// they see the names of the tools on the server.
+func ToolOverrideMiddleware(overrides map[string]ToolOverride) func(http.Handler) http.Handler {
+	originalNames := make(map[string]string, len(overrides))
+	for tool, override := range overrides {
+		originalNames[exposedToolName(tool, override)] = tool
+	}
+
+	return func(next http.Handler) http.Handler {
+		return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			if isWebSocketUpgrade(r) {
+				next.ServeHTTP(w, r)
+				return
+			}
+
+			if parsed := GetParsedMCPRequest(r.Context()); parsed != nil && parsed.Method == "tools/call" {
+				if original, renamed := originalNames[parsed.ResourceID]; renamed && original != parsed.ResourceID {
+					r = renameToolCall(r, original)
+				} else if override, ok := overrides[parsed.ResourceID]; ok && override.Name != "" {
+					writeToolNotFound(w, parsed.ID, parsed.ResourceID)
+					return
+				}
+			}
+
+			lw := &toolsListWriter{ResponseWriter: w, rewrite: func(listed []json.RawMessage) []json.RawMessage {
+				return overrideTools(listed, overrides)
+			}}
+			next.ServeHTTP(lw, r)
+			lw.finish()
+		})
+	}
+}
+
+// renameToolCall returns the request calling a tool with the name of the tool on the server
+func renameToolCall(r *http.Request, original string) *http.Request {
+	body, err := io.ReadAll(r.Body)
+	if err != nil {
+		return r
+	}
+	r.Body = io.NopCloser(bytes.NewReader(body))
+
+	var message map[string]json.RawMessage
+	var params map[string]json.RawMessage
+	if json.Unmarshal(body, &message) != nil || json.Unmarshal(message["params"], &params) != nil {
+		return r
+	}
+	var marshalErr error
+	if params["name"], marshalErr = json.Marshal(original); marshalErr != nil {
+		return r
+	}
+	if message["params"], marshalErr = json.Marshal(params); marshalErr != nil {
+		return r
+	}
+	renamed, err := json.Marshal(message)
+	if err != nil {
+		return r
+	}
+
+	r.Body = io.NopCloser(bytes.NewReader(renamed))
+	r.ContentLength = int64(len(renamed))
+	r.Header.Set("Content-Length", strconv.Itoa(len(renamed)))
+	if parsed := parseMCPRequest(renamed); parsed != nil {
+		r = r.WithContext(context.WithValue(r.Context(), MCPRequestContextKey, parsed))
+	}
+	return r
+}
+
+// overrideTools returns the tools of a tools/list result with the names and descriptions of their overrides
+func overrideTools(listed []json.RawMessage, overrides map[string]ToolOverride) []json.RawMessage {
+	for i, tool := range listed {
+		var fields map[string]json.RawMessage
+		var name string
+		if json.Unmarshal(tool, &fields) != nil || json.Unmarshal(fields["name"], &name) != nil {
+			continue
+		}
+		override, ok := overrides[name]
+		if !ok {
+			continue
+		}
+		if override.Name != "" {
+			fields["name"], _ = json.Marshal(override.Name)
+		}
+		if override.Description != "" {
+			fields["description"], _ = json.Marshal(override.Description)
+		}
+		if rewritten, err := json.Marshal(fields); err == nil {
+			listed[i] = rewritten
+		}
+	}
+	return listed
+}
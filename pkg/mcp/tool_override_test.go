@@ -0,0 +1,190 @@
+package mcp
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestLoadToolOverrides(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name        string
+		file        string
+		content     string
+		expected    map[string]ToolOverride
+		expectError bool
+	}{
+		{
+			name:    "YAML file",
+			file:    "overrides.yaml",
+			content: "fetch:\n  name: web_fetch\n  description: Fetch a URL\nsearch:\n  description: Search the web\n",
+			expected: map[string]ToolOverride{
+				"fetch":  {Name: "web_fetch", Description: "Fetch a URL"},
+				"search": {Description: "Search the web"},
+			},
+		},
+		{
+			name:     "JSON file",
+			file:     "overrides.json",
+			content:  `{"fetch": {"name": "web_fetch"}}`,
+			expected: map[string]ToolOverride{"fetch": {Name: "web_fetch"}},
+		},
+		{
+			name:        "Empty override",
+			file:        "overrides.yaml",
+			content:     "fetch: {}\n",
+			expectError: true,
+		},
+		{
+			name:        "Two tools with the same name",
+			file:        "overrides.yaml",
+			content:     "fetch:\n  name: get\nread:\n  name: get\n",
+			expectError: true,
+		},
+		{
+			name:        "Tool renamed as another tool which is not renamed",
+			file:        "overrides.yaml",
+			content:     "fetch:\n  name: search\nsearch:\n  description: Search the web\n",
+			expectError: true,
+		},
+		{
+			name:        "Invalid file",
+			file:        "overrides.yaml",
+			content:     "- fetch\n",
+			expectError: true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			path := filepath.Join(t.TempDir(), tt.file)
+			require.NoError(t, os.WriteFile(path, []byte(tt.content), 0600))
+
+			overrides, err := LoadToolOverrides(path)
+			if tt.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.expected, overrides)
+		})
+	}
+}
+
+func TestToolOverrideMiddleware(t *testing.T) {
+	t.Parallel()
+
+	overrides := map[string]ToolOverride{
+		"fetch":  {Name: "web_fetch", Description: "Fetch a URL from the internet"},
+		"search": {Description: "Search the web"},
+	}
+
+	tests := []struct {
+		name           string
+		body           string
+		expectStatus   int
+		expectToolName string
+		expectForward  bool
+	}{
+		{
+			name:           "Call to a renamed tool",
+			body:           `{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"web_fetch","arguments":{"url":"x"}}}`,
+			expectStatus:   http.StatusOK,
+			expectToolName: "fetch",
+			expectForward:  true,
+		},
+		{
+			name:           "Call to a tool which is not renamed",
+			body:           `{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"search"}}`,
+			expectStatus:   http.StatusOK,
+			expectToolName: "search",
+			expectForward:  true,
+		},
+		{
+			name:         "Call to the original name of a renamed tool",
+			body:         `{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"fetch"}}`,
+			expectStatus: http.StatusForbidden,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			var forwarded []byte
+			var parsedName string
+			handler := ParsingMiddleware(ToolOverrideMiddleware(overrides)(
+				http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+					forwarded, _ = io.ReadAll(r.Body)
+					parsedName = GetMCPResourceID(r.Context())
+					w.WriteHeader(http.StatusOK)
+				})))
+
+			req := httptest.NewRequest(http.MethodPost, "/mcp", bytes.NewBufferString(tt.body))
+			req.Header.Set("Content-Type", "application/json")
+			rec := httptest.NewRecorder()
+			handler.ServeHTTP(rec, req)
+
+			assert.Equal(t, tt.expectStatus, rec.Code)
+			if !tt.expectForward {
+				assert.Nil(t, forwarded)
+				return
+			}
+			var message struct {
+				Params struct {
+					Name      string         `json:"name"`
+					Arguments map[string]any `json:"arguments"`
+				} `json:"params"`
+			}
+			require.NoError(t, json.Unmarshal(forwarded, &message))
+			assert.Equal(t, tt.expectToolName, message.Params.Name)
+			assert.Equal(t, tt.expectToolName, parsedName)
+		})
+	}
+}
+
+func TestToolOverrideMiddleware_ToolsList(t *testing.T) {
+	t.Parallel()
+
+	overrides := map[string]ToolOverride{"fetch": {Name: "web_fetch", Description: "Fetch a URL from the internet"}}
+	handler := ToolOverrideMiddleware(overrides)(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		w.Header().Set("Content-Type", "application/json")
+		_, _ = w.Write([]byte(`{"jsonrpc":"2.0","id":1,"result":{"tools":[` +
+			`{"name":"fetch","description":"Fetches","inputSchema":{"type":"object"}},` +
+			`{"name":"search","description":"Searches","inputSchema":{"type":"object"}}]}}`))
+	}))
+
+	req := httptest.NewRequest(http.MethodPost, "/mcp", nil)
+	req = req.WithContext(context.WithValue(req.Context(), MCPRequestContextKey, &ParsedMCPRequest{Method: "tools/list"}))
+	rec := httptest.NewRecorder()
+	handler.ServeHTTP(rec, req)
+
+	var response struct {
+		Result struct {
+			Tools []struct {
+				Name        string         `json:"name"`
+				Description string         `json:"description"`
+				InputSchema map[string]any `json:"inputSchema"`
+			} `json:"tools"`
+		} `json:"result"`
+	}
+	require.NoError(t, json.Unmarshal(rec.Body.Bytes(), &response))
+	require.Len(t, response.Result.Tools, 2)
+	assert.Equal(t, "web_fetch", response.Result.Tools[0].Name)
+	assert.Equal(t, "Fetch a URL from the internet", response.Result.Tools[0].Description)
+	assert.Equal(t, "object", response.Result.Tools[0].InputSchema["type"])
+	assert.Equal(t, "search", response.Result.Tools[1].Name)
+	assert.Equal(t, "Searches", response.Result.Tools[1].Description)
+}
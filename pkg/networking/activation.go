@@ -0,0 +1,86 @@
+package networking
+
+import (
+	"fmt"
+	"net"
+	"net/http"
+	"os"
+	"strconv"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+const (
+	// listenPIDEnv is set by systemd to the PID of the process the sockets are passed to
+	listenPIDEnv = "LISTEN_PID"
+	// listenFDsEnv is set by systemd to the number of sockets passed to the process
+	listenFDsEnv = "LISTEN_FDS"
+	// listenFDNamesEnv is set by systemd to the names of the sockets passed to the process
+	listenFDNamesEnv = "LISTEN_FDNAMES"
+	// listenFDsStart is the first file descriptor of the sockets passed by systemd
+	listenFDsStart = 3
+)
+
+// ActivationListener returns the listening socket passed to the process by systemd socket activation,
+// or nil if the process was not socket activated. The socket can only be taken once: the socket
+// activation environment variables are unset, so child processes and later calls do not use it.
+func ActivationListener() (net.Listener, error) {
+	pid, fds := os.Getenv(listenPIDEnv), os.Getenv(listenFDsEnv)
+	if pid == "" || fds == "" {
+		return nil, nil
+	}
+	for _, name := range []string{listenPIDEnv, listenFDsEnv, listenFDNamesEnv} {
+		if err := os.Unsetenv(name); err != nil {
+			return nil, fmt.Errorf("failed to unset %s: %w", name, err)
+		}
+	}
+
+	// The variables are inherited by child processes which were not socket activated
+	if pid != strconv.Itoa(os.Getpid()) {
+		return nil, nil
+	}
+	count, err := strconv.Atoi(fds)
+	if err != nil || count < 1 {
+		return nil, fmt.Errorf("invalid %s value: %q", listenFDsEnv, fds)
+	}
+	if count > 1 {
+		logger.Warnf("%d sockets passed by systemd, only the first one is used", count)
+	}
+
+	file := os.NewFile(listenFDsStart, "LISTEN_FD_3")
+	defer file.Close()
+	listener, err := net.FileListener(file)
+	if err != nil {
+		return nil, fmt.Errorf("failed to use the socket passed by systemd: %w", err)
+	}
+	return listener, nil
+}
+
+// ListenAndServe serves HTTP requests on the socket passed by systemd socket activation if there is one,
+// and on the address of the server otherwise. HTTPS is served if the server has a TLS configuration.
+func ListenAndServe(server *http.Server) error {
+	listener, err := ActivationListener()
+	if err != nil {
+		return err
+	}
+	if listener == nil {
+		if server.TLSConfig != nil {
+			return server.ListenAndServeTLS("", "")
+		}
+		return server.ListenAndServe()
+	}
+
+	logger.Infof("Serving on socket %s passed by systemd", listener.Addr())
+	if server.TLSConfig != nil {
+		return server.ServeTLS(listener, "", "")
+	}
+	return server.Serve(listener)
+}
+
+// ServerScheme returns the URL scheme of the requests served by ListenAndServe
+func ServerScheme(server *http.Server) string {
+	if server.TLSConfig != nil {
+		return "https"
+	}
+	return "http"
+}
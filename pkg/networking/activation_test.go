@@ -0,0 +1,44 @@
+package networking
+
+import (
+	"os"
+	"strconv"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestActivationListener(t *testing.T) {
+	tests := []struct {
+		name      string
+		pid       string
+		fds       string
+		expectErr bool
+	}{
+		{name: "Not socket activated"},
+		{name: "Sockets passed to another process", pid: "1", fds: "1"},
+		{name: "Invalid socket count", pid: strconv.Itoa(os.Getpid()), fds: "none", expectErr: true},
+		{name: "No sockets", pid: strconv.Itoa(os.Getpid()), fds: "0", expectErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Setenv(listenPIDEnv, tt.pid)
+			t.Setenv(listenFDsEnv, tt.fds)
+
+			listener, err := ActivationListener()
+			if tt.expectErr {
+				require.Error(t, err)
+			} else {
+				require.NoError(t, err)
+			}
+			assert.Nil(t, listener)
+
+			if tt.pid != "" {
+				_, ok := os.LookupEnv(listenFDsEnv)
+				assert.False(t, ok, "socket activation variables should be unset")
+			}
+		})
+	}
+}
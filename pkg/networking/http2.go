@@ -0,0 +1,15 @@
+package networking
+
+import "net/http"
+
+// ProxyProtocols returns the HTTP protocols served by the proxies of MCP servers: HTTP/1.1,
+// HTTP/2 over TLS (h2), and HTTP/2 over cleartext connections with prior knowledge (h2c).
+// HTTP/2 lets clients multiplex many concurrent requests over one connection, without
+// head-of-line blocking or running into the limits on the number of HTTP/1.1 connections.
+func ProxyProtocols() *http.Protocols {
+	protocols := &http.Protocols{}
+	protocols.SetHTTP1(true)
+	protocols.SetHTTP2(true)
+	protocols.SetUnencryptedHTTP2(true)
+	return protocols
+}
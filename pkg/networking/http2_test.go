@@ -0,0 +1,57 @@
+package networking
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestProxyProtocols(t *testing.T) {
+	t.Parallel()
+
+	server := httptest.NewUnstartedServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		w.Header().Set("X-Proto", r.Proto)
+		w.WriteHeader(http.StatusNoContent)
+	}))
+	server.Config.Protocols = ProxyProtocols()
+	server.Start()
+	t.Cleanup(server.Close)
+
+	http1 := &http.Protocols{}
+	http1.SetHTTP1(true)
+	h2c := &http.Protocols{}
+	h2c.SetUnencryptedHTTP2(true)
+
+	tests := []struct {
+		name          string
+		protocols     *http.Protocols
+		expectedProto string
+	}{
+		{
+			name:          "HTTP/1.1",
+			protocols:     http1,
+			expectedProto: "HTTP/1.1",
+		},
+		{
+			name:          "HTTP/2 over cleartext",
+			protocols:     h2c,
+			expectedProto: "HTTP/2.0",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			client := &http.Client{Transport: &http.Transport{Protocols: tt.protocols}}
+
+			resp, err := client.Get(server.URL)
+			require.NoError(t, err)
+			defer resp.Body.Close()
+
+			assert.Equal(t, tt.expectedProto, resp.Header.Get("X-Proto"))
+		})
+	}
+}
@@ -0,0 +1,46 @@
+package networking
+
+import (
+	"net"
+	"net/http"
+	"strings"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+// NamedPipePrefix is the prefix of the paths of Windows named pipes
+const NamedPipePrefix = `\\.\pipe\`
+
+// NamedPipePath returns the path of the named pipe on which the proxy of a server is served
+func NamedPipePath(serverName string) string {
+	return NamedPipePrefix + "toolhive-" + serverName
+}
+
+// IsNamedPipePath returns true if the path is the path of a Windows named pipe
+func IsNamedPipePath(path string) bool {
+	return strings.HasPrefix(path, NamedPipePrefix) && len(path) > len(NamedPipePrefix)
+}
+
+// ServeNamedPipe serves HTTP requests of the server on a Windows named pipe, in addition to its
+// other listeners. Local clients can connect to it without the firewall prompts of TCP ports.
+// The pipe is closed when the server is shut down.
+func ServeNamedPipe(server *http.Server, path string) error {
+	listener, err := ListenNamedPipe(path)
+	if err != nil {
+		return err
+	}
+
+	go func() {
+		logger.Infof("Serving on named pipe %s", path)
+		if err := server.Serve(listener); err != nil && err != http.ErrServerClosed {
+			logger.Errorf("Named pipe listener error: %v", err)
+		}
+	}()
+	return nil
+}
+
+// ListenNamedPipe listens on a Windows named pipe. Only the user running the proxy, administrators
+// and the system can connect to it.
+func ListenNamedPipe(path string) (net.Listener, error) {
+	return listenNamedPipe(path)
+}
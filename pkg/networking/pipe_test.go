@@ -0,0 +1,36 @@
+package networking
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestNamedPipePath(t *testing.T) {
+	t.Parallel()
+
+	path := NamedPipePath("github")
+	assert.Equal(t, `\\.\pipe\toolhive-github`, path)
+	assert.True(t, IsNamedPipePath(path))
+}
+
+func TestIsNamedPipePath(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		path     string
+		expected bool
+	}{
+		{path: `\\.\pipe\toolhive-github`, expected: true},
+		{path: `\\.\pipe\`, expected: false},
+		{path: `toolhive-github`, expected: false},
+		{path: `/tmp/toolhive.sock`, expected: false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.path, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, IsNamedPipePath(tt.path))
+		})
+	}
+}
@@ -0,0 +1,13 @@
+//go:build !windows
+// +build !windows
+
+package networking
+
+import (
+	"fmt"
+	"net"
+)
+
+func listenNamedPipe(path string) (net.Listener, error) {
+	return nil, fmt.Errorf("cannot listen on named pipe %s: named pipes are only supported on Windows", path)
+}
@@ -0,0 +1,25 @@
+//go:build windows
+// +build windows
+
+package networking
+
+import (
+	"fmt"
+	"net"
+
+	"github.com/Microsoft/go-winio"
+)
+
+// namedPipeSecurityDescriptor grants access to the pipe to the owner, administrators and the system
+const namedPipeSecurityDescriptor = "D:P(A;;GA;;;OW)(A;;GA;;;BA)(A;;GA;;;SY)"
+
+func listenNamedPipe(path string) (net.Listener, error) {
+	if !IsNamedPipePath(path) {
+		return nil, fmt.Errorf("invalid named pipe path %s (expected %sNAME)", path, NamedPipePrefix)
+	}
+	listener, err := winio.ListenPipe(path, &winio.PipeConfig{SecurityDescriptor: namedPipeSecurityDescriptor})
+	if err != nil {
+		return nil, fmt.Errorf("failed to listen on named pipe %s: %w", path, err)
+	}
+	return listener, nil
+}
@@ -7,6 +7,7 @@ import (
 	"fmt"
 	"math/big"
 	"net"
+	"strconv"
 
 	"github.com/stacklok/toolhive/pkg/logger"
 )
@@ -55,6 +56,20 @@ func IsAvailable(port int) bool {
 	return true
 }
 
+// CheckPortAvailable returns an error if a TCP listener cannot be bound to the port on the host,
+// e.g. because the port is already in use
+func CheckPortAvailable(host string, port int) error {
+	listener, err := net.Listen("tcp", net.JoinHostPort(host, strconv.Itoa(port)))
+	if err != nil {
+		return fmt.Errorf("port %d is already in use or cannot be bound on %s: %w", port, host, err)
+	}
+	if err := listener.Close(); err != nil {
+		// Log the error but continue, as we're just checking if the port is available
+		logger.Warnf("Warning: Failed to close TCP listener: %v", err)
+	}
+	return nil
+}
+
 // IsIPv6Available checks if IPv6 is available on the system
 // by looking for IPv6 addresses on network interfaces
 func IsIPv6Available() bool {
@@ -0,0 +1,25 @@
+package networking
+
+import (
+	"net"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+func TestCheckPortAvailable(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	require.NoError(t, err)
+	port := listener.Addr().(*net.TCPAddr).Port
+
+	assert.ErrorContains(t, CheckPortAvailable("127.0.0.1", port), "already in use")
+
+	require.NoError(t, listener.Close())
+	assert.NoError(t, CheckPortAvailable("127.0.0.1", port))
+}
@@ -0,0 +1,55 @@
+package networking
+
+import (
+	"fmt"
+	"net"
+	"net/http"
+	"os"
+	"time"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+// socketDialTimeout is how long ListenUnixSocket waits to find out whether a socket is still in use
+const socketDialTimeout = time.Second
+
+// ListenUnixSocket listens on a Unix domain socket, replacing the socket a previous proxy left at the path
+// if nothing listens on it anymore. Only the user running the proxy can connect to it. The socket file is
+// removed when the listener is closed.
+func ListenUnixSocket(path string) (net.Listener, error) {
+	if info, err := os.Lstat(path); err == nil {
+		if info.Mode()&os.ModeSocket == 0 {
+			return nil, fmt.Errorf("cannot listen on %s: the file exists and is not a socket", path)
+		}
+		if conn, err := net.DialTimeout("unix", path, socketDialTimeout); err == nil {
+			_ = conn.Close()
+			return nil, fmt.Errorf("cannot listen on %s: the socket is already in use", path)
+		}
+		if err := os.Remove(path); err != nil {
+			return nil, fmt.Errorf("failed to remove stale socket %s: %w", path, err)
+		}
+	}
+
+	listener, err := net.Listen("unix", path)
+	if err != nil {
+		return nil, fmt.Errorf("failed to listen on socket %s: %w", path, err)
+	}
+	if err := os.Chmod(path, 0600); err != nil {
+		_ = listener.Close()
+		return nil, fmt.Errorf("failed to set the permissions of socket %s: %w", path, err)
+	}
+	return listener, nil
+}
+
+// Serve serves HTTP requests of the server on the listener, or as ListenAndServe does if the listener is nil.
+// HTTPS is served if the server has a TLS configuration.
+func Serve(server *http.Server, listener net.Listener) error {
+	if listener == nil {
+		return ListenAndServe(server)
+	}
+	logger.Infof("Serving on socket %s", listener.Addr())
+	if server.TLSConfig != nil {
+		return server.ServeTLS(listener, "", "")
+	}
+	return server.Serve(listener)
+}
@@ -57,3 +57,19 @@ func AddressReferencesPrivateIp(address string) error {
 
 	return nil
 }
+
+// IsLoopbackHost returns true if the host is localhost or a loopback IP address
+func IsLoopbackHost(host string) bool {
+	if host == "localhost" {
+		return true
+	}
+	ip := net.ParseIP(host)
+	return ip != nil && ip.IsLoopback()
+}
+
+// IsUnspecifiedHost returns true if the host is an unspecified IP address like 0.0.0.0,
+// with which a listener accepts connections on all interfaces
+func IsUnspecifiedHost(host string) bool {
+	ip := net.ParseIP(host)
+	return ip != nil && ip.IsUnspecified()
+}
@@ -0,0 +1,34 @@
+package networking
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestHostKinds(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		host        string
+		loopback    bool
+		unspecified bool
+	}{
+		{host: "localhost", loopback: true},
+		{host: "127.0.0.1", loopback: true},
+		{host: "127.0.1.1", loopback: true},
+		{host: "::1", loopback: true},
+		{host: "0.0.0.0", unspecified: true},
+		{host: "::", unspecified: true},
+		{host: "192.168.1.10"},
+		{host: "example.com"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.host, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.loopback, IsLoopbackHost(tt.host))
+			assert.Equal(t, tt.unspecified, IsUnspecifiedHost(tt.host))
+		})
+	}
+}
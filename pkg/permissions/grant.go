@@ -0,0 +1,100 @@
+package permissions
+
+import (
+	"fmt"
+	"net"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// Grant is a temporary extension of a permission profile, e.g. an extra egress host allowed while
+// debugging. Once it expires, the workload reverts to the permissions of the profile without it.
+type Grant struct {
+	// AllowHost is a list of additional allowed hosts
+	AllowHost []string `json:"allow_host,omitempty"`
+
+	// AllowPort is a list of additional allowed ports
+	AllowPort []int `json:"allow_port,omitempty"`
+
+	// ExpiresAt is the time at which the grant expires
+	ExpiresAt time.Time `json:"expires_at"`
+}
+
+// ParseGrant parses a temporary egress grant in the format HOST[:PORT], which is valid for the given duration
+func ParseGrant(spec string, duration time.Duration, now time.Time) (Grant, error) {
+	if duration <= 0 {
+		return Grant{}, fmt.Errorf("invalid grant duration %s: must be positive", duration)
+	}
+
+	host := spec
+	grant := Grant{ExpiresAt: now.Add(duration)}
+	if h, p, err := net.SplitHostPort(spec); err == nil {
+		port, err := strconv.Atoi(p)
+		if err != nil || port < 1 || port > 65535 {
+			return Grant{}, fmt.Errorf("invalid port in grant %s", spec)
+		}
+		host = h
+		grant.AllowPort = []int{port}
+	}
+	if host == "" || strings.ContainsAny(host, " /:") {
+		return Grant{}, fmt.Errorf("invalid host in grant %s (expected HOST[:PORT])", spec)
+	}
+	grant.AllowHost = []string{host}
+	return grant, nil
+}
+
+// Active returns true if the grant has not expired at the given time
+func (g *Grant) Active(now time.Time) bool {
+	return now.Before(g.ExpiresAt)
+}
+
+// String returns a description of the grant for logging
+func (g *Grant) String() string {
+	var parts []string
+	if len(g.AllowHost) > 0 {
+		parts = append(parts, "hosts "+strings.Join(g.AllowHost, ", "))
+	}
+	if len(g.AllowPort) > 0 {
+		ports := make([]string, 0, len(g.AllowPort))
+		for _, port := range g.AllowPort {
+			ports = append(ports, strconv.Itoa(port))
+		}
+		parts = append(parts, "ports "+strings.Join(ports, ", "))
+	}
+	return fmt.Sprintf("egress to %s until %s", strings.Join(parts, " and "), g.ExpiresAt.Format(time.RFC3339))
+}
+
+// ActiveGrants returns the grants of the profile which have not expired at the given time
+func (p *Profile) ActiveGrants(now time.Time) []Grant {
+	var active []Grant
+	for _, grant := range p.Grants {
+		if grant.Active(now) {
+			active = append(active, grant)
+		}
+	}
+	return active
+}
+
+// NextGrantExpiry returns the earliest expiry of the grants of the profile which are active at the given
+// time, and false if there are none
+func (p *Profile) NextGrantExpiry(now time.Time) (time.Time, bool) {
+	var next time.Time
+	for _, grant := range p.Grants {
+		if grant.Active(now) && (next.IsZero() || grant.ExpiresAt.Before(next)) {
+			next = grant.ExpiresAt
+		}
+	}
+	return next, !next.IsZero()
+}
+
+// ExpiredGrants returns the grants of the profile which expired in the interval (since, now]
+func (p *Profile) ExpiredGrants(since, now time.Time) []Grant {
+	var expired []Grant
+	for _, grant := range p.Grants {
+		if grant.ExpiresAt.After(since) && !grant.Active(now) {
+			expired = append(expired, grant)
+		}
+	}
+	return expired
+}
@@ -0,0 +1,112 @@
+package permissions
+
+import (
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestParseGrant(t *testing.T) {
+	t.Parallel()
+
+	now := time.Now()
+	tests := []struct {
+		name          string
+		spec          string
+		duration      time.Duration
+		expectedHosts []string
+		expectedPorts []int
+		expectError   bool
+	}{
+		{
+			name:          "Host",
+			spec:          "api.example.com",
+			duration:      time.Hour,
+			expectedHosts: []string{"api.example.com"},
+		},
+		{
+			name:          "Host and port",
+			spec:          "api.example.com:443",
+			duration:      2 * time.Hour,
+			expectedHosts: []string{"api.example.com"},
+			expectedPorts: []int{443},
+		},
+		{
+			name:          "Domain wildcard",
+			spec:          ".example.com",
+			duration:      time.Hour,
+			expectedHosts: []string{".example.com"},
+		},
+		{
+			name:        "Invalid port",
+			spec:        "api.example.com:https",
+			duration:    time.Hour,
+			expectError: true,
+		},
+		{
+			name:        "Port out of range",
+			spec:        "api.example.com:70000",
+			duration:    time.Hour,
+			expectError: true,
+		},
+		{
+			name:        "Missing host",
+			spec:        ":443",
+			duration:    time.Hour,
+			expectError: true,
+		},
+		{
+			name:        "URL",
+			spec:        "https://api.example.com",
+			duration:    time.Hour,
+			expectError: true,
+		},
+		{
+			name:        "Zero duration",
+			spec:        "api.example.com",
+			expectError: true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			grant, err := ParseGrant(tt.spec, tt.duration, now)
+			if tt.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.expectedHosts, grant.AllowHost)
+			assert.Equal(t, tt.expectedPorts, grant.AllowPort)
+			assert.Equal(t, now.Add(tt.duration), grant.ExpiresAt)
+		})
+	}
+}
+
+func TestProfile_Grants(t *testing.T) {
+	t.Parallel()
+
+	now := time.Now()
+	expired := Grant{AllowHost: []string{"expired.example.com"}, ExpiresAt: now.Add(-time.Minute)}
+	soon := Grant{AllowHost: []string{"soon.example.com"}, ExpiresAt: now.Add(time.Minute)}
+	later := Grant{AllowHost: []string{"later.example.com"}, ExpiresAt: now.Add(time.Hour)}
+	profile := BuiltinNoneProfile()
+	profile.Grants = []Grant{later, expired, soon}
+
+	assert.Equal(t, []Grant{later, soon}, profile.ActiveGrants(now))
+
+	next, ok := profile.NextGrantExpiry(now)
+	assert.True(t, ok)
+	assert.Equal(t, soon.ExpiresAt, next)
+
+	_, ok = profile.NextGrantExpiry(now.Add(2 * time.Hour))
+	assert.False(t, ok, "no grant should be active once all expired")
+
+	assert.Equal(t, []Grant{soon}, profile.ExpiredGrants(now, now.Add(30*time.Minute)))
+	assert.Equal(t, []Grant{later, soon}, profile.ExpiredGrants(now, now.Add(time.Hour)))
+	assert.Empty(t, profile.ExpiredGrants(now, now))
+}
@@ -37,6 +37,14 @@ type Profile struct {
 
 	// Network defines network permissions
 	Network *NetworkPermissions `json:"network,omitempty"`
+
+	// Grants is a list of temporary network permissions in addition to the profile,
+	// which are revoked automatically once they expire
+	Grants []Grant `json:"grants,omitempty"`
+
+	// NoNewPrivileges prevents the processes of the container, including commands run with thv exec,
+	// from gaining privileges, e.g. through setuid binaries
+	NoNewPrivileges bool `json:"no_new_privileges,omitempty"`
 }
 
 // NetworkPermissions defines network permissions for a container
@@ -149,6 +157,9 @@ var (
 	// Both host path and container path can contain any characters except colon
 	hostPathRegex = regexp.MustCompile(`^([^:]+):([^:]+)$`)
 
+	// windowsDriveRegex matches a path starting with a Windows drive letter, e.g. C:\data or C:/data
+	windowsDriveRegex = regexp.MustCompile(`^[a-zA-Z]:[\\/]`)
+
 	// commandInjectionPattern matches common command injection patterns
 	commandInjectionPattern = regexp.MustCompile(`[$&;|]|\$\(|\` + "`")
 )
@@ -172,6 +183,50 @@ func cleanPath(path string) string {
 	return filepath.Clean(path)
 }
 
+// validateAndCleanPaths validates and cleans the host and container paths of a mount declaration
+func validateAndCleanPaths(hostPath, containerPath string) (string, string, error) {
+	if err := validatePath(hostPath); err != nil {
+		return "", "", err
+	}
+	if err := validatePath(containerPath); err != nil {
+		return "", "", err
+	}
+	return cleanPath(hostPath), cleanPath(containerPath), nil
+}
+
+// hasWindowsDrive returns true if the path starts with a Windows drive letter, e.g. C:\
+func hasWindowsDrive(path string) bool {
+	return windowsDriveRegex.MatchString(path)
+}
+
+// splitWindowsMountDeclaration splits a mount declaration in which the host path, the container path or both
+// start with a Windows drive letter, whose colon is not a separator. It returns false if neither does.
+func splitWindowsMountDeclaration(declaration string) (string, string, bool) {
+	if hasWindowsDrive(declaration) {
+		separator := strings.Index(declaration[2:], ":")
+		if separator < 0 {
+			return declaration, declaration, true
+		}
+		hostPath, containerPath := declaration[:separator+2], declaration[separator+3:]
+		return hostPath, containerPath, validWindowsContainerPath(containerPath)
+	}
+
+	separator := strings.Index(declaration, ":")
+	if separator < 0 || !hasWindowsDrive(declaration[separator+1:]) {
+		return "", "", false
+	}
+	hostPath, containerPath := declaration[:separator], declaration[separator+1:]
+	return hostPath, containerPath, hostPath != "" && validWindowsContainerPath(containerPath)
+}
+
+// validWindowsContainerPath returns true if the container path has no colon other than that of its drive letter
+func validWindowsContainerPath(path string) bool {
+	if hasWindowsDrive(path) {
+		path = path[2:]
+	}
+	return path != "" && !strings.Contains(path, ":")
+}
+
 // Parse parses a mount declaration and returns the source and target paths
 // It also cleans and validates the paths
 func (m MountDeclaration) Parse() (source, target string, err error) {
@@ -198,24 +253,14 @@ func (m MountDeclaration) Parse() (source, target string, err error) {
 		return scheme + "://" + cleanedResource, cleanedTarget, nil
 	}
 
+	// Check if it contains paths with Windows drive letters, e.g. C:\data or C:\host\path:C:\data
+	if hostPath, containerPath, ok := splitWindowsMountDeclaration(declaration); ok {
+		return validateAndCleanPaths(hostPath, containerPath)
+	}
+
 	// Check if it's a host-path:container-path format
 	if matches := hostPathRegex.FindStringSubmatch(declaration); matches != nil {
-		hostPath := matches[1]
-		containerPath := matches[2]
-
-		// Validate paths
-		if err := validatePath(hostPath); err != nil {
-			return "", "", err
-		}
-		if err := validatePath(containerPath); err != nil {
-			return "", "", err
-		}
-
-		// Clean paths
-		cleanedSource := cleanPath(hostPath)
-		cleanedTarget := cleanPath(containerPath)
-
-		return cleanedSource, cleanedTarget, nil
+		return validateAndCleanPaths(matches[1], matches[2])
 	}
 
 	// If it doesn't contain a colon, it's a single path
@@ -276,3 +321,34 @@ func ParseMountDeclarations(declarations []string) ([]MountDeclaration, error) {
 
 	return result, nil
 }
+
+// AllowsMount returns true if the host path of the mount is one of the paths of the profile, or is under one
+// of them, and the profile gives the access the mount requires: read access is given by the read and write
+// paths, and write access by the write paths. Resource URIs are only allowed if the profile declares them.
+func (p *Profile) AllowsMount(mount MountDeclaration, readOnly bool) bool {
+	source, _, err := mount.Parse()
+	if err != nil {
+		return false
+	}
+
+	allowed := p.Write
+	if readOnly {
+		allowed = append(append([]MountDeclaration{}, p.Read...), p.Write...)
+	}
+	for _, declaration := range allowed {
+		allowedSource, _, err := declaration.Parse()
+		if err != nil {
+			continue
+		}
+		if mount.IsResourceURI() || declaration.IsResourceURI() {
+			if source == allowedSource {
+				return true
+			}
+			continue
+		}
+		if IsHostPathUnder(ResolveHostPath(source), ResolveHostPath(allowedSource)) {
+			return true
+		}
+	}
+	return false
+}
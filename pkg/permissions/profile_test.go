@@ -45,6 +45,39 @@ func TestMountDeclaration_Parse(t *testing.T) {
 			expectedTarget: "/container/path",
 			expectError:    false,
 		},
+		{
+			name:           "Windows path",
+			declaration:    `C:\data`,
+			expectedSource: `C:\data`,
+			expectedTarget: `C:\data`,
+			expectError:    false,
+		},
+		{
+			name:           "Windows host path to Windows container path",
+			declaration:    `C:\Users\me\data:C:\data`,
+			expectedSource: `C:\Users\me\data`,
+			expectedTarget: `C:\data`,
+			expectError:    false,
+		},
+		{
+			name:           "Windows host path to container path",
+			declaration:    `D:\data:/data`,
+			expectedSource: `D:\data`,
+			expectedTarget: "/data",
+			expectError:    false,
+		},
+		{
+			name:           "Host path to Windows container path",
+			declaration:    `/host/path:C:\data`,
+			expectedSource: "/host/path",
+			expectedTarget: `C:\data`,
+			expectError:    false,
+		},
+		{
+			name:        "Windows path with too many colons",
+			declaration: `C:\data:C:\data:C:\other`,
+			expectError: true,
+		},
 		// Security-focused tests
 		{
 			name:           "Path with spaces",
@@ -0,0 +1,391 @@
+package permissions
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// Severity is the severity of a finding of the validation of a permission profile
+type Severity string
+
+const (
+	// SeverityError marks a finding which makes the profile unusable or not behave as written
+	SeverityError Severity = "error"
+	// SeverityWarning marks a finding which should be reviewed before the profile is rolled out
+	SeverityWarning Severity = "warning"
+)
+
+// Codes of the findings of the validation of a permission profile
+const (
+	// FindingSchema is reported when the profile is not valid JSON or has unknown fields
+	FindingSchema = "schema"
+	// FindingInvalidMount is reported when a mount declaration cannot be parsed
+	FindingInvalidMount = "invalid-mount"
+	// FindingUnsupportedMount is reported for resource URI mounts, which are not mounted yet
+	FindingUnsupportedMount = "unsupported-mount"
+	// FindingMissingHostPath is reported when the host path of a mount does not exist
+	FindingMissingHostPath = "missing-host-path"
+	// FindingConflictingMount is reported when different host paths are mounted on the same container path
+	FindingConflictingMount = "conflicting-mount"
+	// FindingRedundantMount is reported when a mount is declared more than once
+	FindingRedundantMount = "redundant-mount"
+	// FindingBroadMount is reported when a mount exposes a sensitive or very large part of the host
+	FindingBroadMount = "broad-mount"
+	// FindingInvalidNetwork is reported when a network rule has an invalid value
+	FindingInvalidNetwork = "invalid-network"
+	// FindingContradictoryNetwork is reported when network rules have no effect because all traffic is allowed
+	FindingContradictoryNetwork = "contradictory-network"
+	// FindingBroadNetwork is reported when all outbound network traffic is allowed
+	FindingBroadNetwork = "broad-network"
+)
+
+// Finding is a problem found by the validation of a permission profile
+type Finding struct {
+	// Severity is the severity of the finding
+	Severity Severity `json:"severity"`
+	// Code identifies the kind of finding
+	Code string `json:"code"`
+	// Field is the field of the profile the finding refers to, e.g. read[0]
+	Field string `json:"field,omitempty"`
+	// Message describes the finding
+	Message string `json:"message"`
+}
+
+// maxSymlinks is the maximum number of dangling symbolic links followed when resolving a host path
+const maxSymlinks = 40
+
+// sensitiveHostPaths are host paths which expose the files of the whole host or of all its users when mounted,
+// although the paths under them are not sensitive in general
+var sensitiveHostPaths = []string{
+	"/",
+	"/home",
+	"/Users",
+}
+
+// sensitiveHostRoots are host paths which, like any path under them, give a container control over the host
+// or access to its secrets
+var sensitiveHostRoots = []string{
+	"/etc",
+	"/root",
+	"/var/run/docker.sock",
+	"/run/docker.sock",
+	"/run/podman/podman.sock",
+	"/proc",
+	"/sys",
+	"/dev",
+}
+
+// sensitiveHomePaths are the paths in the home directory of the user which hold credentials
+var sensitiveHomePaths = []string{
+	".ssh",
+	".gnupg",
+	".aws",
+	".kube",
+	".docker",
+}
+
+// validTransports are the transport protocols accepted in allow_transport
+var validTransports = map[string]struct{}{
+	"tcp": {},
+	"udp": {},
+}
+
+// ValidateFile validates the permission profile in the given file
+func ValidateFile(path string) ([]Finding, error) {
+	// #nosec G304 - This is intentional as we're reading a user-specified permission profile
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read permission profile: %w", err)
+	}
+	return Validate(data), nil
+}
+
+// Validate checks a permission profile for schema errors, invalid and contradictory rules,
+// host paths which do not exist and overly broad grants.
+// Relative host paths are resolved against the current directory, as they are when the profile is used.
+func Validate(data []byte) []Finding {
+	var profile Profile
+	decoder := json.NewDecoder(bytes.NewReader(data))
+	decoder.DisallowUnknownFields()
+	if err := decoder.Decode(&profile); err != nil {
+		return []Finding{{
+			Severity: SeverityError,
+			Code:     FindingSchema,
+			Message:  fmt.Sprintf("failed to parse permission profile: %v", err),
+		}}
+	}
+	if _, err := decoder.Token(); !errors.Is(err, io.EOF) {
+		return []Finding{{
+			Severity: SeverityError,
+			Code:     FindingSchema,
+			Message:  "unexpected data after the permission profile",
+		}}
+	}
+
+	findings := validateMounts(&profile)
+	return append(findings, validateNetwork(profile.Network)...)
+}
+
+// HasErrors returns true if any of the findings is an error
+func HasErrors(findings []Finding) bool {
+	for _, finding := range findings {
+		if finding.Severity == SeverityError {
+			return true
+		}
+	}
+	return false
+}
+
+// parsedMount is a mount declaration of a profile which was parsed successfully
+type parsedMount struct {
+	field  string
+	source string
+	target string
+	write  bool
+}
+
+// validateMounts checks the read and write mounts of the profile
+func validateMounts(profile *Profile) []Finding {
+	var findings []Finding
+	var mounts []parsedMount
+	for _, group := range []struct {
+		name   string
+		mounts []MountDeclaration
+	}{
+		{name: "read", mounts: profile.Read},
+		{name: "write", mounts: profile.Write},
+	} {
+		for i, declaration := range group.mounts {
+			field := fmt.Sprintf("%s[%d]", group.name, i)
+			source, target, err := declaration.Parse()
+			if err != nil {
+				findings = append(findings, Finding{
+					Severity: SeverityError,
+					Code:     FindingInvalidMount,
+					Field:    field,
+					Message:  err.Error(),
+				})
+				continue
+			}
+			mount := parsedMount{field: field, source: source, target: target, write: group.name == "write"}
+			findings = append(findings, validateMount(mount)...)
+			findings = append(findings, validateMountAgainst(mount, mounts)...)
+			mounts = append(mounts, mount)
+		}
+	}
+	return findings
+}
+
+// validateMount checks the host path of a mount
+func validateMount(mount parsedMount) []Finding {
+	if strings.Contains(mount.source, "://") {
+		return []Finding{{
+			Severity: SeverityWarning,
+			Code:     FindingUnsupportedMount,
+			Field:    mount.field,
+			Message:  fmt.Sprintf("resource URI mounts are not supported yet and %s is ignored", mount.source),
+		}}
+	}
+
+	var findings []Finding
+	hostPath, err := filepath.Abs(mount.source)
+	if err != nil {
+		hostPath = mount.source
+	}
+	if _, err := os.Stat(hostPath); errors.Is(err, os.ErrNotExist) {
+		findings = append(findings, Finding{
+			Severity: SeverityError,
+			Code:     FindingMissingHostPath,
+			Field:    mount.field,
+			Message:  fmt.Sprintf("host path %s does not exist", hostPath),
+		})
+	}
+	if reason := broadMountReason(hostPath); reason != "" {
+		access := "read"
+		if mount.write {
+			access = "write"
+		}
+		findings = append(findings, Finding{
+			Severity: SeverityWarning,
+			Code:     FindingBroadMount,
+			Field:    mount.field,
+			Message:  fmt.Sprintf("%s access to %s %s", access, hostPath, reason),
+		})
+	}
+	return findings
+}
+
+// IsSensitiveHostPath returns true if mounting the host path gives a container control over the host
+// or access to its secrets. Relative paths are resolved against the current directory, and symbolic
+// links are resolved, so that a link can't be used to mount a sensitive path.
+func IsSensitiveHostPath(hostPath string) bool {
+	hostPath = ResolveHostPath(hostPath)
+	for _, sensitive := range sensitiveHostPaths {
+		if hostPath == sensitive || hostPath == ResolveHostPath(sensitive) {
+			return true
+		}
+	}
+	roots := sensitiveHostRoots
+	if home, err := os.UserHomeDir(); err == nil {
+		for _, path := range sensitiveHomePaths {
+			roots = append(roots, filepath.Join(home, path))
+		}
+	}
+	for _, root := range roots {
+		if IsHostPathUnder(hostPath, root) || IsHostPathUnder(hostPath, ResolveHostPath(root)) {
+			return true
+		}
+	}
+	return false
+}
+
+// ResolveHostPath returns the absolute path of a host path with its symbolic links resolved. The links are
+// resolved in the longest part of the path which exists, since the rest of it may be created later, and links
+// whose target does not exist are followed too.
+func ResolveHostPath(hostPath string) string {
+	abs, err := filepath.Abs(hostPath)
+	if err != nil {
+		abs = filepath.Clean(hostPath)
+	}
+	for links := 0; links < maxSymlinks; links++ {
+		current, rest := abs, ""
+		for {
+			if resolved, err := filepath.EvalSymlinks(current); err == nil {
+				return filepath.Join(resolved, rest)
+			}
+			if target, err := os.Readlink(current); err == nil {
+				if !filepath.IsAbs(target) {
+					target = filepath.Join(filepath.Dir(current), target)
+				}
+				abs = filepath.Join(target, rest)
+				break
+			}
+			parent := filepath.Dir(current)
+			if parent == current {
+				return abs
+			}
+			rest = filepath.Join(filepath.Base(current), rest)
+			current = parent
+		}
+	}
+	return abs
+}
+
+// IsHostPathUnder returns true if the resolved host path is the root or a path under it
+func IsHostPathUnder(hostPath, root string) bool {
+	if hostPath == root {
+		return true
+	}
+	if !strings.HasSuffix(root, string(filepath.Separator)) {
+		root += string(filepath.Separator)
+	}
+	return strings.HasPrefix(hostPath, root)
+}
+
+// broadMountReason returns why mounting the host path is overly broad, or an empty string if it is not
+func broadMountReason(hostPath string) string {
+	if IsSensitiveHostPath(hostPath) {
+		return "exposes a sensitive part of the host"
+	}
+	if home, err := os.UserHomeDir(); err == nil && hostPath == filepath.Clean(home) {
+		return "exposes the whole home directory"
+	}
+	return ""
+}
+
+// validateMountAgainst checks a mount against the mounts declared before it
+func validateMountAgainst(mount parsedMount, previous []parsedMount) []Finding {
+	var findings []Finding
+	for _, other := range previous {
+		if other.target != mount.target || strings.Contains(mount.source, "://") {
+			continue
+		}
+		if other.source != mount.source {
+			findings = append(findings, Finding{
+				Severity: SeverityError,
+				Code:     FindingConflictingMount,
+				Field:    mount.field,
+				Message: fmt.Sprintf("container path %s is already mounted from %s by %s",
+					mount.target, other.source, other.field),
+			})
+			continue
+		}
+		message := fmt.Sprintf("%s is already mounted by %s", mount.source, other.field)
+		if mount.write && !other.write {
+			message = fmt.Sprintf("%s is also mounted read-only by %s, the write mount takes precedence",
+				mount.source, other.field)
+		}
+		findings = append(findings, Finding{
+			Severity: SeverityWarning,
+			Code:     FindingRedundantMount,
+			Field:    mount.field,
+			Message:  message,
+		})
+	}
+	return findings
+}
+
+// validateNetwork checks the network permissions of the profile
+func validateNetwork(network *NetworkPermissions) []Finding {
+	if network == nil || network.Outbound == nil {
+		return nil
+	}
+	outbound := network.Outbound
+
+	var findings []Finding
+	for i, transport := range outbound.AllowTransport {
+		if _, ok := validTransports[strings.ToLower(transport)]; !ok {
+			findings = append(findings, Finding{
+				Severity: SeverityError,
+				Code:     FindingInvalidNetwork,
+				Field:    fmt.Sprintf("network.outbound.allow_transport[%d]", i),
+				Message:  fmt.Sprintf("unknown transport %q (expected tcp or udp)", transport),
+			})
+		}
+	}
+	for i, host := range outbound.AllowHost {
+		if strings.TrimSpace(host) == "" {
+			findings = append(findings, Finding{
+				Severity: SeverityError,
+				Code:     FindingInvalidNetwork,
+				Field:    fmt.Sprintf("network.outbound.allow_host[%d]", i),
+				Message:  "empty host",
+			})
+		}
+	}
+	for i, port := range outbound.AllowPort {
+		if port < 1 || port > 65535 {
+			findings = append(findings, Finding{
+				Severity: SeverityError,
+				Code:     FindingInvalidNetwork,
+				Field:    fmt.Sprintf("network.outbound.allow_port[%d]", i),
+				Message:  fmt.Sprintf("port %d is out of range (1-65535)", port),
+			})
+		}
+	}
+
+	if !outbound.InsecureAllowAll {
+		return findings
+	}
+	findings = append(findings, Finding{
+		Severity: SeverityWarning,
+		Code:     FindingBroadNetwork,
+		Field:    "network.outbound.insecure_allow_all",
+		Message:  "all outbound network traffic is allowed",
+	})
+	if len(outbound.AllowTransport) > 0 || len(outbound.AllowHost) > 0 || len(outbound.AllowPort) > 0 {
+		findings = append(findings, Finding{
+			Severity: SeverityWarning,
+			Code:     FindingContradictoryNetwork,
+			Field:    "network.outbound",
+			Message:  "allow_transport, allow_host and allow_port have no effect when insecure_allow_all is set",
+		})
+	}
+	return findings
+}
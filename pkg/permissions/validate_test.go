@@ -0,0 +1,188 @@
+package permissions
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestValidate(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	other := t.TempDir()
+	missing := filepath.Join(dir, "missing")
+
+	tests := []struct {
+		name          string
+		profile       string
+		expectedCodes []string
+		expectErrors  bool
+	}{
+		{
+			name:    "Valid profile",
+			profile: fmt.Sprintf(`{"read": ["%s"], "network": {"outbound": {"allow_host": ["example.com"], "allow_port": [443]}}}`, dir),
+		},
+		{
+			name:          "Invalid JSON",
+			profile:       `{"read": [`,
+			expectedCodes: []string{FindingSchema},
+			expectErrors:  true,
+		},
+		{
+			name:          "Unknown field",
+			profile:       `{"reads": ["/tmp"]}`,
+			expectedCodes: []string{FindingSchema},
+			expectErrors:  true,
+		},
+		{
+			name:          "Trailing data",
+			profile:       `{} {}`,
+			expectedCodes: []string{FindingSchema},
+			expectErrors:  true,
+		},
+		{
+			name:          "Invalid mount",
+			profile:       `{"read": ["/a:/b:/c"]}`,
+			expectedCodes: []string{FindingInvalidMount},
+			expectErrors:  true,
+		},
+		{
+			name:          "Missing host path",
+			profile:       fmt.Sprintf(`{"write": ["%s:/data"]}`, missing),
+			expectedCodes: []string{FindingMissingHostPath},
+			expectErrors:  true,
+		},
+		{
+			name:          "Resource URI mount",
+			profile:       `{"read": ["volume://data:/data"]}`,
+			expectedCodes: []string{FindingUnsupportedMount},
+		},
+		{
+			name:          "Conflicting mounts",
+			profile:       fmt.Sprintf(`{"read": ["%s:/data"], "write": ["%s:/data"]}`, dir, other),
+			expectedCodes: []string{FindingConflictingMount},
+			expectErrors:  true,
+		},
+		{
+			name:          "Same path read and write",
+			profile:       fmt.Sprintf(`{"read": ["%s"], "write": ["%s"]}`, dir, dir),
+			expectedCodes: []string{FindingRedundantMount},
+		},
+		{
+			name:          "Root mount",
+			profile:       `{"read": ["/:/host"]}`,
+			expectedCodes: []string{FindingBroadMount},
+		},
+		{
+			name:          "Invalid network rules",
+			profile:       `{"network": {"outbound": {"allow_transport": ["icmp"], "allow_host": [""], "allow_port": [0, 70000]}}}`,
+			expectedCodes: []string{FindingInvalidNetwork, FindingInvalidNetwork, FindingInvalidNetwork, FindingInvalidNetwork},
+			expectErrors:  true,
+		},
+		{
+			name:          "Allow all network",
+			profile:       `{"network": {"outbound": {"insecure_allow_all": true}}}`,
+			expectedCodes: []string{FindingBroadNetwork},
+		},
+		{
+			name:          "Allow all network with rules",
+			profile:       `{"network": {"outbound": {"insecure_allow_all": true, "allow_host": ["example.com"]}}}`,
+			expectedCodes: []string{FindingBroadNetwork, FindingContradictoryNetwork},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			findings := Validate([]byte(tt.profile))
+
+			codes := make([]string, 0, len(findings))
+			for _, finding := range findings {
+				codes = append(codes, finding.Code)
+			}
+			if tt.expectedCodes == nil {
+				assert.Empty(t, codes)
+			} else {
+				assert.Equal(t, tt.expectedCodes, codes)
+			}
+			assert.Equal(t, tt.expectErrors, HasErrors(findings))
+		})
+	}
+}
+
+func TestValidateFile(t *testing.T) {
+	t.Parallel()
+
+	path := filepath.Join(t.TempDir(), "profile.json")
+	require.NoError(t, os.WriteFile(path, []byte(`{"network": {"outbound": {"insecure_allow_all": true}}}`), 0600))
+
+	findings, err := ValidateFile(path)
+	require.NoError(t, err)
+	require.Len(t, findings, 1)
+	assert.Equal(t, SeverityWarning, findings[0].Severity)
+	assert.Equal(t, "network.outbound.insecure_allow_all", findings[0].Field)
+
+	_, err = ValidateFile(filepath.Join(t.TempDir(), "missing.json"))
+	assert.Error(t, err)
+}
+
+func TestIsSensitiveHostPath(t *testing.T) {
+	t.Parallel()
+
+	assert.True(t, IsSensitiveHostPath("/"))
+	assert.True(t, IsSensitiveHostPath("/etc"))
+	assert.True(t, IsSensitiveHostPath("/var/run/docker.sock"))
+	assert.True(t, IsSensitiveHostPath("/etc/../proc"))
+	assert.False(t, IsSensitiveHostPath("/opt/myapp"))
+	assert.False(t, IsSensitiveHostPath("/etcetera"))
+
+	dir := t.TempDir()
+	assert.False(t, IsSensitiveHostPath(dir))
+
+	// Paths under sensitive paths
+	assert.True(t, IsSensitiveHostPath("/etc/shadow"))
+	assert.True(t, IsSensitiveHostPath("/etc/myapp"))
+	assert.True(t, IsSensitiveHostPath("/root/.ssh"))
+	assert.True(t, IsSensitiveHostPath("/proc/1/root"))
+	if home, err := os.UserHomeDir(); err == nil {
+		assert.True(t, IsSensitiveHostPath(filepath.Join(home, ".ssh", "id_ed25519")))
+	}
+
+	// Links to sensitive paths, and paths under them
+	link := filepath.Join(dir, "host-config")
+	require.NoError(t, os.Symlink("/etc", link))
+	assert.True(t, IsSensitiveHostPath(link))
+	assert.True(t, IsSensitiveHostPath(filepath.Join(link, "shadow")))
+	assert.True(t, IsSensitiveHostPath(filepath.Join(link, "missing", "file")))
+}
+
+func TestProfile_AllowsMount(t *testing.T) {
+	t.Parallel()
+
+	profile := &Profile{
+		Read:  []MountDeclaration{"/data", "volume://cache:/cache"},
+		Write: []MountDeclaration{"/work:/workspace"},
+	}
+
+	assert.True(t, profile.AllowsMount("/data:/data", true))
+	assert.True(t, profile.AllowsMount("/data/sub:/sub", true))
+	assert.False(t, profile.AllowsMount("/data:/data", false))
+	assert.False(t, profile.AllowsMount("/database:/database", true))
+	assert.True(t, profile.AllowsMount("/work/project:/project", false))
+	assert.True(t, profile.AllowsMount("/work:/elsewhere", true))
+	assert.True(t, profile.AllowsMount("volume://cache:/cache", true))
+	assert.False(t, profile.AllowsMount("volume://other:/cache", true))
+
+	// A link in an allowed directory is checked against the path it points to
+	dir := t.TempDir()
+	link := filepath.Join(dir, "link")
+	require.NoError(t, os.Symlink("/work", link))
+	withLink := &Profile{Read: []MountDeclaration{MountDeclaration(dir)}}
+	assert.False(t, withLink.AllowsMount(MountDeclaration(link+":/work"), true))
+}
@@ -0,0 +1,74 @@
+package ratelimit
+
+import (
+	"math"
+	"net"
+	"net/http"
+	"strconv"
+	"strings"
+
+	"github.com/stacklok/toolhive/pkg/auth"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/networking"
+	"github.com/stacklok/toolhive/pkg/transport/proxy/router"
+)
+
+// Middleware creates an HTTP middleware limiting the requests of each client to the limit, and rejecting the
+// requests over it with 429 Too Many Requests. Clients are identified by the subject of their token when
+// bySubject is true, which requires the middleware to follow the authentication middleware, and by their IP
+// address otherwise. The clients of requests forwarded by thv gateway, which router.TokenMiddleware found the
+// gateway token in, are identified by the address the gateway forwards.
+func Middleware(limit Limit, bySubject bool) func(http.Handler) http.Handler {
+	limiter := NewLimiter(limit)
+	return func(next http.Handler) http.Handler {
+		return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			client := clientKey(r, bySubject)
+			if ok, wait := limiter.Allow(client); !ok {
+				logger.Debugf("Rate limit exceeded for client %s", client)
+				w.Header().Set("Retry-After", strconv.Itoa(int(math.Ceil(wait.Seconds()))))
+				http.Error(w, "Rate limit exceeded", http.StatusTooManyRequests)
+				return
+			}
+			next.ServeHTTP(w, r)
+		})
+	}
+}
+
+// clientKey returns the key of the bucket of the client making a request
+func clientKey(r *http.Request, bySubject bool) string {
+	if bySubject {
+		if claims, ok := auth.GetClaimsFromContext(r.Context()); ok {
+			if sub, ok := claims["sub"].(string); ok && sub != "" {
+				return "sub:" + sub
+			}
+		}
+	}
+	host, _, err := net.SplitHostPort(r.RemoteAddr)
+	if err != nil {
+		host = r.RemoteAddr
+	}
+	// The gateway connects from the loopback interface, like any local client, so only its token proves
+	// that the forwarded address was set by the gateway rather than by the client
+	if networking.IsLoopbackHost(host) && router.FromGateway(r) {
+		if forwarded := forwardedClient(r); forwarded != "" {
+			return "ip:" + forwarded
+		}
+	}
+	return "ip:" + host
+}
+
+// forwardedClient returns the address of the client in the X-Forwarded-For header of a request, or an empty
+// string if there is none. The last address is used, since it is the one added by the reverse proxy the
+// request came from, such as thv gateway, while the others can be set by the client.
+func forwardedClient(r *http.Request) string {
+	values := r.Header.Values("X-Forwarded-For")
+	if len(values) == 0 {
+		return ""
+	}
+	addresses := strings.Split(values[len(values)-1], ",")
+	address := strings.TrimSpace(addresses[len(addresses)-1])
+	if net.ParseIP(address) == nil {
+		return ""
+	}
+	return address
+}
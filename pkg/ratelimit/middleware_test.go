@@ -0,0 +1,236 @@
+package ratelimit
+
+import (
+	"context"
+	"net/http"
+	"net/http/httptest"
+	"net/url"
+	"testing"
+	"time"
+
+	"github.com/golang-jwt/jwt/v5"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/auth"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/transport/proxy/router"
+)
+
+func TestMiddleware(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	tests := []struct {
+		name      string
+		bySubject bool
+		requests  []struct{ addr, sub string }
+		expected  []int
+	}{
+		{
+			name: "By client IP",
+			requests: []struct{ addr, sub string }{
+				{addr: "10.0.0.1:1234", sub: "alice"},
+				{addr: "10.0.0.1:5678", sub: "bob"},
+				{addr: "10.0.0.2:1234", sub: "alice"},
+			},
+			expected: []int{http.StatusOK, http.StatusTooManyRequests, http.StatusOK},
+		},
+		{
+			name:      "By subject",
+			bySubject: true,
+			requests: []struct{ addr, sub string }{
+				{addr: "10.0.0.1:1234", sub: "alice"},
+				{addr: "10.0.0.1:5678", sub: "bob"},
+				{addr: "10.0.0.2:1234", sub: "alice"},
+			},
+			expected: []int{http.StatusOK, http.StatusOK, http.StatusTooManyRequests},
+		},
+		{
+			name:      "By subject without claims",
+			bySubject: true,
+			requests: []struct{ addr, sub string }{
+				{addr: "10.0.0.1:1234"},
+				{addr: "10.0.0.1:5678"},
+			},
+			expected: []int{http.StatusOK, http.StatusTooManyRequests},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			handler := Middleware(Limit{Requests: 1, Period: time.Minute}, tt.bySubject)(
+				http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+					w.WriteHeader(http.StatusOK)
+				}))
+
+			for i, request := range tt.requests {
+				req := httptest.NewRequest(http.MethodPost, "/messages", nil)
+				req.RemoteAddr = request.addr
+				if request.sub != "" {
+					claims := jwt.MapClaims{"sub": request.sub}
+					req = req.WithContext(context.WithValue(req.Context(), auth.ClaimsContextKey{}, claims))
+				}
+				rec := httptest.NewRecorder()
+				handler.ServeHTTP(rec, req)
+
+				assert.Equal(t, tt.expected[i], rec.Code, "request %d", i)
+				if rec.Code == http.StatusTooManyRequests {
+					assert.Equal(t, "60", rec.Header().Get("Retry-After"))
+				}
+			}
+		})
+	}
+}
+
+func TestClientKey_Forwarded(t *testing.T) {
+	t.Parallel()
+
+	const token = "gateway-token"
+	tests := []struct {
+		name      string
+		addr      string
+		forwarded []string
+		token     string
+		expected  string
+	}{
+		{
+			name:     "Loopback without forwarded address",
+			addr:     "127.0.0.1:1234",
+			expected: "ip:127.0.0.1",
+		},
+		{
+			name:      "Loopback with forwarded address",
+			addr:      "127.0.0.1:1234",
+			forwarded: []string{"10.0.0.1"},
+			token:     token,
+			expected:  "ip:10.0.0.1",
+		},
+		{
+			name:      "Loopback with forwarded address without the gateway token",
+			addr:      "127.0.0.1:1234",
+			forwarded: []string{"10.0.0.1"},
+			expected:  "ip:127.0.0.1",
+		},
+		{
+			name:      "Loopback with forwarded address and another token",
+			addr:      "127.0.0.1:1234",
+			forwarded: []string{"10.0.0.1"},
+			token:     "guessed-token",
+			expected:  "ip:127.0.0.1",
+		},
+		{
+			name:      "IPv6 loopback with forwarded address",
+			addr:      "[::1]:1234",
+			forwarded: []string{"10.0.0.1"},
+			token:     token,
+			expected:  "ip:10.0.0.1",
+		},
+		{
+			name:      "Addresses set by the client before the gateway",
+			addr:      "127.0.0.1:1234",
+			forwarded: []string{"192.168.0.1, 10.0.0.2", "10.0.0.1"},
+			token:     token,
+			expected:  "ip:10.0.0.1",
+		},
+		{
+			name:      "Invalid forwarded address",
+			addr:      "127.0.0.1:1234",
+			forwarded: []string{"unknown"},
+			token:     token,
+			expected:  "ip:127.0.0.1",
+		},
+		{
+			name:      "Forwarded address of a remote client",
+			addr:      "10.0.0.1:1234",
+			forwarded: []string{"10.0.0.2"},
+			token:     token,
+			expected:  "ip:10.0.0.1",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			req := httptest.NewRequest(http.MethodPost, "/messages", nil)
+			req.RemoteAddr = tt.addr
+			for _, forwarded := range tt.forwarded {
+				req.Header.Add("X-Forwarded-For", forwarded)
+			}
+			if tt.token != "" {
+				req.Header.Set(router.TokenHeader, tt.token)
+			}
+			var key string
+			router.TokenMiddleware(token)(http.HandlerFunc(func(_ http.ResponseWriter, r *http.Request) {
+				key = clientKey(r, false)
+			})).ServeHTTP(httptest.NewRecorder(), req)
+			assert.Equal(t, tt.expected, key)
+		})
+	}
+}
+
+func TestMiddleware_Gateway(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	const token = "gateway-token"
+	handler := router.TokenMiddleware(token)(Middleware(Limit{Requests: 1, Period: time.Minute}, false)(
+		http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+			w.WriteHeader(http.StatusOK)
+		})))
+	server := httptest.NewServer(handler)
+	t.Cleanup(server.Close)
+	serverURL, err := url.Parse(server.URL)
+	require.NoError(t, err)
+
+	gateway := router.NewRouter("127.0.0.1", 0, func(_ context.Context, _ string) (*url.URL, error) {
+		return serverURL, nil
+	})
+	gateway.SetToken(token)
+
+	// The requests of all the clients reach the proxy from the loopback interface of the gateway,
+	// but each client has its own limit
+	for i, request := range []struct {
+		addr     string
+		expected int
+	}{
+		{addr: "10.0.0.1:1234", expected: http.StatusOK},
+		{addr: "10.0.0.2:1234", expected: http.StatusOK},
+		{addr: "10.0.0.1:5678", expected: http.StatusTooManyRequests},
+	} {
+		req := httptest.NewRequest(http.MethodPost, "/fetch/messages", nil)
+		req.RemoteAddr = request.addr
+		rec := httptest.NewRecorder()
+		gateway.ServeHTTP(rec, req)
+		assert.Equal(t, request.expected, rec.Code, "request %d", i)
+	}
+}
+
+func TestMiddleware_LocalClientForwarding(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	handler := router.TokenMiddleware("gateway-token")(Middleware(Limit{Requests: 1, Period: time.Minute}, false)(
+		http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+			w.WriteHeader(http.StatusOK)
+		})))
+
+	// A local client cannot get past its limit by changing the address it forwards
+	for i, forwarded := range []string{"10.0.0.1", "10.0.0.2", "10.0.0.3"} {
+		req := httptest.NewRequest(http.MethodPost, "/messages", nil)
+		req.RemoteAddr = "127.0.0.1:1234"
+		req.Header.Set("X-Forwarded-For", forwarded)
+		req.Header.Set(router.TokenHeader, "guessed-token")
+		rec := httptest.NewRecorder()
+		handler.ServeHTTP(rec, req)
+
+		expected := http.StatusTooManyRequests
+		if i == 0 {
+			expected = http.StatusOK
+		}
+		assert.Equal(t, expected, rec.Code, "request %d", i)
+	}
+}
@@ -0,0 +1,115 @@
+// Package ratelimit provides per-client rate limiting of the requests to the proxy of an MCP server.
+package ratelimit
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+)
+
+// Limit is a number of requests a client can make in a period
+type Limit struct {
+	Requests int
+	Period   time.Duration
+}
+
+// periodUnits are the units of the period of a limit
+var periodUnits = map[string]time.Duration{
+	"s":      time.Second,
+	"sec":    time.Second,
+	"second": time.Second,
+	"m":      time.Minute,
+	"min":    time.Minute,
+	"minute": time.Minute,
+	"h":      time.Hour,
+	"hour":   time.Hour,
+}
+
+// ParseLimit parses a limit of the form REQUESTS/UNIT, such as 100/min, where UNIT is s, min or h
+func ParseLimit(value string) (Limit, error) {
+	count, unit, ok := strings.Cut(value, "/")
+	if !ok {
+		return Limit{}, fmt.Errorf("invalid rate limit %q, expected REQUESTS/UNIT such as 100/min", value)
+	}
+	requests, err := strconv.Atoi(strings.TrimSpace(count))
+	if err != nil || requests <= 0 {
+		return Limit{}, fmt.Errorf("invalid number of requests in rate limit %q", value)
+	}
+	period, ok := periodUnits[strings.ToLower(strings.TrimSpace(unit))]
+	if !ok {
+		return Limit{}, fmt.Errorf("invalid unit in rate limit %q, expected s, min or h", value)
+	}
+	return Limit{Requests: requests, Period: period}, nil
+}
+
+// sweepInterval is the longest time between two sweeps of the buckets of idle clients
+const sweepInterval = time.Minute
+
+// bucket is the token bucket of a client
+type bucket struct {
+	tokens  float64
+	updated time.Time
+}
+
+// Limiter limits the requests of each client with a token bucket, which holds up to the number of
+// requests of the limit and is refilled at the rate of the limit, so clients can make short bursts.
+type Limiter struct {
+	limit   Limit
+	rate    float64 // tokens per second
+	now     func() time.Time
+	mutex   sync.Mutex
+	buckets map[string]*bucket
+	swept   time.Time
+}
+
+// NewLimiter creates a limiter applying the limit to each client
+func NewLimiter(limit Limit) *Limiter {
+	return &Limiter{
+		limit:   limit,
+		rate:    float64(limit.Requests) / limit.Period.Seconds(),
+		now:     time.Now,
+		buckets: make(map[string]*bucket),
+	}
+}
+
+// Allow takes a token from the bucket of the client if there is one. Otherwise, it returns false
+// and how long the client has to wait for the next token.
+func (l *Limiter) Allow(client string) (bool, time.Duration) {
+	l.mutex.Lock()
+	defer l.mutex.Unlock()
+
+	now := l.now()
+	l.sweep(now)
+
+	b, ok := l.buckets[client]
+	if !ok {
+		b = &bucket{tokens: float64(l.limit.Requests), updated: now}
+		l.buckets[client] = b
+	}
+	b.tokens = min(float64(l.limit.Requests), b.tokens+now.Sub(b.updated).Seconds()*l.rate)
+	b.updated = now
+
+	if b.tokens < 1 {
+		return false, time.Duration((1 - b.tokens) * float64(l.limit.Period) / float64(l.limit.Requests))
+	}
+	b.tokens--
+	return true, 0
+}
+
+// sweep removes the buckets which have been refilled completely, so clients which stopped
+// making requests do not use memory; they get a full bucket again on their next request.
+// It runs at least every sweepInterval, so the buckets of clients making a single request
+// do not pile up during long periods.
+func (l *Limiter) sweep(now time.Time) {
+	if now.Sub(l.swept) < min(l.limit.Period, sweepInterval) {
+		return
+	}
+	l.swept = now
+	for client, b := range l.buckets {
+		if b.tokens+now.Sub(b.updated).Seconds()*l.rate >= float64(l.limit.Requests) {
+			delete(l.buckets, client)
+		}
+	}
+}
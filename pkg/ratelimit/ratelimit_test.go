@@ -0,0 +1,99 @@
+package ratelimit
+
+import (
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestParseLimit(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name        string
+		value       string
+		expected    Limit
+		expectError bool
+	}{
+		{name: "Per minute", value: "100/min", expected: Limit{Requests: 100, Period: time.Minute}},
+		{name: "Per second", value: "5/s", expected: Limit{Requests: 5, Period: time.Second}},
+		{name: "Per hour", value: "1000/hour", expected: Limit{Requests: 1000, Period: time.Hour}},
+		{name: "Spaces and case", value: " 10 / Minute ", expected: Limit{Requests: 10, Period: time.Minute}},
+		{name: "Missing unit", value: "100", expectError: true},
+		{name: "Unknown unit", value: "100/day", expectError: true},
+		{name: "Zero requests", value: "0/min", expectError: true},
+		{name: "Invalid number", value: "many/min", expectError: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			limit, err := ParseLimit(tt.value)
+			if tt.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.expected, limit)
+		})
+	}
+}
+
+func TestLimiter_Allow(t *testing.T) {
+	t.Parallel()
+
+	now := time.Date(2025, 1, 1, 0, 0, 0, 0, time.UTC)
+	limiter := NewLimiter(Limit{Requests: 2, Period: time.Minute})
+	limiter.now = func() time.Time { return now }
+
+	// A client can make a burst of requests up to the limit
+	for i := 0; i < 2; i++ {
+		ok, _ := limiter.Allow("a")
+		assert.True(t, ok)
+	}
+	ok, wait := limiter.Allow("a")
+	assert.False(t, ok)
+	assert.Equal(t, 30*time.Second, wait)
+
+	// Other clients have their own bucket
+	ok, _ = limiter.Allow("b")
+	assert.True(t, ok)
+
+	// The bucket is refilled at the rate of the limit
+	now = now.Add(30 * time.Second)
+	ok, _ = limiter.Allow("a")
+	assert.True(t, ok)
+	ok, _ = limiter.Allow("a")
+	assert.False(t, ok)
+
+	// The buckets of idle clients are removed, and they start again with a full bucket
+	now = now.Add(2 * time.Minute)
+	ok, _ = limiter.Allow("a")
+	assert.True(t, ok)
+	assert.NotContains(t, limiter.buckets, "b")
+}
+
+func TestLimiter_SweepsRefilledBuckets(t *testing.T) {
+	t.Parallel()
+
+	now := time.Date(2025, 1, 1, 0, 0, 0, 0, time.UTC)
+	limiter := NewLimiter(Limit{Requests: 60, Period: time.Hour})
+	limiter.now = func() time.Time { return now }
+
+	ok, _ := limiter.Allow("a")
+	require.True(t, ok)
+	for i := 0; i < 10; i++ {
+		ok, _ = limiter.Allow("b")
+		require.True(t, ok)
+	}
+
+	// The bucket of a is full again after a minute, long before the end of the period, while b still
+	// has to wait for its tokens
+	now = now.Add(2 * time.Minute)
+	ok, _ = limiter.Allow("c")
+	require.True(t, ok)
+	assert.NotContains(t, limiter.buckets, "a")
+	assert.Contains(t, limiter.buckets, "b")
+}
@@ -0,0 +1,171 @@
+package registry
+
+import (
+	"context"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"time"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/state"
+)
+
+// CacheTTL is how long the cached data of a remote registry is used before it is fetched again.
+// Once it expired, the cached data is still used if the registry can't be fetched, e.g. when offline.
+const CacheTTL = time.Hour
+
+// signedRegistry is the data of a remote registry with its detached signature, if any
+type signedRegistry struct {
+	// Data is the registry data, kept byte for byte so its signature can be verified again
+	Data []byte `json:"data"`
+	// Signature is the detached signature of the registry data
+	Signature []byte `json:"signature,omitempty"`
+}
+
+// cacheEntry is the cached data of a remote registry
+type cacheEntry struct {
+	signedRegistry
+	// URL is the URL of the registry
+	URL string `json:"url"`
+	// FetchedAt is the time the data was fetched
+	FetchedAt time.Time `json:"fetched_at"`
+}
+
+// validate checks that the registry data can be parsed and that its signature is valid, if the
+// verifier requires one
+func (r *signedRegistry) validate(verifier *Verifier) error {
+	if _, err := parseRegistryData(r.Data); err != nil {
+		return err
+	}
+	return verifier.Verify(r.Data, r.Signature)
+}
+
+// fresh returns true if the cached data can be used without fetching the registry again
+func (e *cacheEntry) fresh(now time.Time) bool {
+	return now.Sub(e.FetchedAt) < CacheTTL
+}
+
+// fetchCached returns the data of a remote registry from the cache while it is fresh, and fetches it
+// otherwise. The fetched data is cached, and the cached data is used if the registry can't be fetched.
+// Both the fetched and the cached data are only used once they are validated with the verifier.
+func fetchCached(
+	ctx context.Context,
+	registryURL string,
+	verifier *Verifier,
+	fetch func() (*signedRegistry, error),
+) ([]byte, error) {
+	cached, err := loadCacheEntry(ctx, registryURL)
+	if err != nil {
+		logger.Debugf("Failed to load cached registry data of %s: %v", registryURL, err)
+	}
+
+	validate := func(r *signedRegistry) error { return r.validate(verifier) }
+	registry, fetched, err := resolveRegistryData(cached, time.Now(), fetch, validate)
+	if err != nil {
+		return nil, err
+	}
+	if !fetched {
+		return registry.Data, nil
+	}
+
+	entry := &cacheEntry{signedRegistry: *registry, URL: registryURL, FetchedAt: time.Now()}
+	if err := saveCacheEntry(ctx, entry); err != nil {
+		logger.Debugf("Failed to cache registry data of %s: %v", registryURL, err)
+	}
+	return registry.Data, nil
+}
+
+// resolveRegistryData returns the cached data if it is fresh, and otherwise fetches the data, falling back
+// to the cached data if fetching it fails. Data which is not valid is never used. It also returns whether
+// the data was fetched.
+func resolveRegistryData(
+	cached *cacheEntry,
+	now time.Time,
+	fetch func() (*signedRegistry, error),
+	validate func(*signedRegistry) error,
+) (*signedRegistry, bool, error) {
+	if cached != nil {
+		if err := validate(&cached.signedRegistry); err != nil {
+			logger.Warnf("Warning: Ignoring cached registry data: %v", err)
+			cached = nil
+		}
+	}
+	if cached != nil && cached.fresh(now) {
+		return &cached.signedRegistry, false, nil
+	}
+
+	registry, err := fetch()
+	if err == nil {
+		err = validate(registry)
+	}
+	if err == nil {
+		return registry, true, nil
+	}
+
+	if cached == nil || errors.Is(err, ErrInvalidSignature) {
+		return nil, false, err
+	}
+	logger.Warnf("Warning: Using registry data cached at %s, since the registry can't be fetched: %v",
+		cached.FetchedAt.Format(time.RFC3339), err)
+	return &cached.signedRegistry, false, nil
+}
+
+// cacheKey returns the name of the cache entry of a registry URL
+func cacheKey(registryURL string) string {
+	sum := sha256.Sum256([]byte(registryURL))
+	return hex.EncodeToString(sum[:16])
+}
+
+// loadCacheEntry loads the cached data of a registry, or returns nil if there is none
+func loadCacheEntry(ctx context.Context, registryURL string) (*cacheEntry, error) {
+	store, err := state.NewRegistryCacheStore(state.DefaultAppName)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create state store: %w", err)
+	}
+
+	key := cacheKey(registryURL)
+	exists, err := store.Exists(ctx, key)
+	if err != nil {
+		return nil, fmt.Errorf("failed to check if cached registry data exists: %w", err)
+	}
+	if !exists {
+		return nil, nil
+	}
+
+	reader, err := store.GetReader(ctx, key)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get reader for cached registry data: %w", err)
+	}
+	defer reader.Close()
+
+	entry := &cacheEntry{}
+	if err := json.NewDecoder(reader).Decode(entry); err != nil {
+		return nil, fmt.Errorf("failed to read cached registry data: %w", err)
+	}
+	if entry.URL != registryURL {
+		return nil, nil
+	}
+	return entry, nil
+}
+
+// saveCacheEntry caches the data of a registry
+func saveCacheEntry(ctx context.Context, entry *cacheEntry) error {
+	store, err := state.NewRegistryCacheStore(state.DefaultAppName)
+	if err != nil {
+		return fmt.Errorf("failed to create state store: %w", err)
+	}
+
+	writer, err := store.GetWriter(ctx, cacheKey(entry.URL))
+	if err != nil {
+		return fmt.Errorf("failed to get writer for cached registry data: %w", err)
+	}
+	defer writer.Close()
+
+	if err := json.NewEncoder(writer).Encode(entry); err != nil {
+		return fmt.Errorf("failed to write cached registry data: %w", err)
+	}
+	return nil
+}
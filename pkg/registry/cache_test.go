@@ -0,0 +1,150 @@
+package registry
+
+import (
+	"errors"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+func TestResolveRegistryData(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	now := time.Now()
+	cachedData := []byte(`{"version":"1.0.0","servers":{"cached":{}}}`)
+	fetchedData := []byte(`{"version":"1.0.0","servers":{"fetched":{}}}`)
+	errOffline := errors.New("network is unreachable")
+	badSignature := []byte("bad")
+
+	// validate accepts any parseable data, unless it has a bad signature
+	validate := func(r *signedRegistry) error {
+		if string(r.Signature) == string(badSignature) {
+			return ErrInvalidSignature
+		}
+		_, err := parseRegistryData(r.Data)
+		return err
+	}
+
+	tests := []struct {
+		name             string
+		cachedAt         *time.Time
+		cachedSignature  []byte
+		fetchedData      []byte
+		fetchedSignature []byte
+		fetchErr         error
+		expectedData     []byte
+		expectedFetched  bool
+		expectFetch      bool
+		expectedErr      error
+	}{
+		{
+			name:            "Nothing cached",
+			fetchedData:     fetchedData,
+			expectedData:    fetchedData,
+			expectedFetched: true,
+			expectFetch:     true,
+		},
+		{
+			name:         "Fresh cache",
+			cachedAt:     timePtr(now.Add(-CacheTTL + time.Minute)),
+			fetchedData:  fetchedData,
+			expectedData: cachedData,
+		},
+		{
+			name:            "Expired cache",
+			cachedAt:        timePtr(now.Add(-CacheTTL - time.Minute)),
+			fetchedData:     fetchedData,
+			expectedData:    fetchedData,
+			expectedFetched: true,
+			expectFetch:     true,
+		},
+		{
+			name:         "Expired cache while offline",
+			cachedAt:     timePtr(now.Add(-CacheTTL - time.Minute)),
+			fetchErr:     errOffline,
+			expectedData: cachedData,
+			expectFetch:  true,
+		},
+		{
+			name:         "Expired cache and invalid fetched data",
+			cachedAt:     timePtr(now.Add(-CacheTTL - time.Minute)),
+			fetchedData:  []byte(`<html>captive portal</html>`),
+			expectedData: cachedData,
+			expectFetch:  true,
+		},
+		{
+			name:        "Nothing cached while offline",
+			fetchErr:    errOffline,
+			expectFetch: true,
+			expectedErr: errOffline,
+		},
+		{
+			name:            "Fresh cache with invalid signature",
+			cachedAt:        timePtr(now.Add(-CacheTTL + time.Minute)),
+			cachedSignature: badSignature,
+			fetchedData:     fetchedData,
+			expectedData:    fetchedData,
+			expectedFetched: true,
+			expectFetch:     true,
+		},
+		{
+			name:             "Fetched data with invalid signature",
+			cachedAt:         timePtr(now.Add(-CacheTTL - time.Minute)),
+			fetchedData:      fetchedData,
+			fetchedSignature: badSignature,
+			expectFetch:      true,
+			expectedErr:      ErrInvalidSignature,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			var cached *cacheEntry
+			if tt.cachedAt != nil {
+				cached = &cacheEntry{
+					signedRegistry: signedRegistry{Data: cachedData, Signature: tt.cachedSignature},
+					URL:            "https://example.com/registry.json",
+					FetchedAt:      *tt.cachedAt,
+				}
+			}
+			fetched := false
+			fetch := func() (*signedRegistry, error) {
+				fetched = true
+				if tt.fetchErr != nil {
+					return nil, tt.fetchErr
+				}
+				return &signedRegistry{Data: tt.fetchedData, Signature: tt.fetchedSignature}, nil
+			}
+
+			registry, refreshed, err := resolveRegistryData(cached, now, fetch, validate)
+			assert.Equal(t, tt.expectFetch, fetched)
+			if tt.expectedErr != nil {
+				assert.ErrorIs(t, err, tt.expectedErr)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, string(tt.expectedData), string(registry.Data))
+			assert.Equal(t, tt.expectedFetched, refreshed)
+		})
+	}
+}
+
+func TestCacheKey(t *testing.T) {
+	t.Parallel()
+
+	key := cacheKey("https://example.com/registry.json")
+	assert.Len(t, key, 32)
+	assert.Equal(t, key, cacheKey("https://example.com/registry.json"))
+	assert.NotEqual(t, key, cacheKey("oci://ghcr.io/example/registry:latest"))
+}
+
+func timePtr(t time.Time) *time.Time {
+	return &t
+}
@@ -14,8 +14,11 @@ var (
 
 // NewRegistryProvider creates a new registry provider based on the configuration
 func NewRegistryProvider(cfg *config.Config) Provider {
+	if cfg != nil && IsOCIReference(cfg.RegistryUrl) {
+		return NewOCIRegistryProvider(cfg.RegistryUrl, cfg.AllowPrivateRegistryIp).WithTrustedKeys(cfg.RegistryTrustedKeys)
+	}
 	if cfg != nil && len(cfg.RegistryUrl) > 0 {
-		return NewRemoteRegistryProvider(cfg.RegistryUrl, cfg.AllowPrivateRegistryIp)
+		return NewRemoteRegistryProvider(cfg.RegistryUrl, cfg.AllowPrivateRegistryIp).WithTrustedKeys(cfg.RegistryTrustedKeys)
 	}
 	return NewEmbeddedRegistryProvider()
 }
@@ -0,0 +1,163 @@
+package registry
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"strings"
+
+	"github.com/google/go-containerregistry/pkg/authn"
+	"github.com/google/go-containerregistry/pkg/name"
+	v1 "github.com/google/go-containerregistry/pkg/v1"
+	"github.com/google/go-containerregistry/pkg/v1/empty"
+	"github.com/google/go-containerregistry/pkg/v1/mutate"
+	"github.com/google/go-containerregistry/pkg/v1/remote"
+	"github.com/google/go-containerregistry/pkg/v1/static"
+	"github.com/google/go-containerregistry/pkg/v1/types"
+
+	"github.com/stacklok/toolhive/pkg/networking"
+)
+
+const (
+	// OCIScheme is the prefix of registry URLs which reference an OCI artifact, e.g. oci://ghcr.io/org/registry:latest
+	OCIScheme = "oci://"
+	// OCIMediaType is the media type of the layer of an OCI artifact which holds the registry data
+	OCIMediaType types.MediaType = "application/vnd.stacklok.toolhive.registry.v1+json"
+	// OCIConfigMediaType is the media type of the config of an OCI artifact which holds the registry data
+	OCIConfigMediaType types.MediaType = "application/vnd.stacklok.toolhive.registry.config.v1+json"
+	// OCISignatureMediaType is the media type of the layer of an OCI artifact which holds the detached
+	// signature of the registry data
+	OCISignatureMediaType types.MediaType = "application/vnd.stacklok.toolhive.registry.signature.v1+base64"
+
+	// ociTitleAnnotation is the annotation of the file name of the registry data layer
+	ociTitleAnnotation = "org.opencontainers.image.title"
+)
+
+// IsOCIReference returns true if the registry URL references an OCI artifact
+func IsOCIReference(registryURL string) bool {
+	return strings.HasPrefix(registryURL, OCIScheme)
+}
+
+// ParseOCIReference parses the image reference of a registry URL referencing an OCI artifact
+func ParseOCIReference(registryURL string) (name.Reference, error) {
+	ref, err := name.ParseReference(strings.TrimPrefix(registryURL, OCIScheme))
+	if err != nil {
+		return nil, fmt.Errorf("invalid OCI reference %s: %w", registryURL, err)
+	}
+	return ref, nil
+}
+
+// PushOCIArtifact publishes registry data as an OCI artifact, along with its detached signature if any,
+// authenticating with the same credentials as for images (e.g. the credentials stored by docker login).
+// It returns the digest of the artifact.
+func PushOCIArtifact(ctx context.Context, registryURL string, data, signature []byte) (string, error) {
+	ref, err := ParseOCIReference(registryURL)
+	if err != nil {
+		return "", err
+	}
+	if _, err := parseRegistryData(data); err != nil {
+		return "", err
+	}
+
+	artifact, err := newOCIArtifact(data, signature)
+	if err != nil {
+		return "", err
+	}
+	err = remote.Write(ref, artifact, remote.WithContext(ctx), remote.WithAuthFromKeychain(authn.DefaultKeychain))
+	if err != nil {
+		return "", fmt.Errorf("failed to push registry to %s: %w", ref, err)
+	}
+
+	digest, err := artifact.Digest()
+	if err != nil {
+		return "", fmt.Errorf("failed to get digest of registry artifact: %w", err)
+	}
+	return digest.String(), nil
+}
+
+// PullOCIArtifact pulls the registry data of an OCI artifact and its detached signature, if any,
+// authenticating with the same credentials as for images (e.g. the credentials stored by docker login)
+func PullOCIArtifact(ctx context.Context, registryURL string, allowPrivateIp bool) ([]byte, []byte, error) {
+	ref, err := ParseOCIReference(registryURL)
+	if err != nil {
+		return nil, nil, err
+	}
+
+	artifact, err := remote.Image(ref,
+		remote.WithContext(ctx),
+		remote.WithAuthFromKeychain(authn.DefaultKeychain),
+		remote.WithTransport(networking.GetHttpClient(allowPrivateIp).Transport),
+	)
+	if err != nil {
+		return nil, nil, fmt.Errorf("failed to pull registry from %s: %w", ref, err)
+	}
+	return artifactRegistryData(artifact)
+}
+
+// newOCIArtifact creates an OCI artifact with a layer holding the registry data,
+// and a layer holding its detached signature if any
+func newOCIArtifact(data, signature []byte) (v1.Image, error) {
+	addenda := []mutate.Addendum{{
+		Layer:       static.NewLayer(data, OCIMediaType),
+		Annotations: map[string]string{ociTitleAnnotation: "registry.json"},
+	}}
+	if len(signature) > 0 {
+		addenda = append(addenda, mutate.Addendum{
+			Layer:       static.NewLayer(signature, OCISignatureMediaType),
+			Annotations: map[string]string{ociTitleAnnotation: "registry.json" + SignatureSuffix},
+		})
+	}
+
+	artifact, err := mutate.Append(empty.Image, addenda...)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create registry artifact: %w", err)
+	}
+	artifact = mutate.MediaType(artifact, types.OCIManifestSchema1)
+	return mutate.ConfigMediaType(artifact, OCIConfigMediaType), nil
+}
+
+// artifactRegistryData returns the registry data held by an OCI artifact, and its detached signature if any
+func artifactRegistryData(artifact v1.Image) ([]byte, []byte, error) {
+	data, found, err := artifactLayer(artifact, OCIMediaType)
+	if err != nil {
+		return nil, nil, err
+	}
+	if !found {
+		return nil, nil, fmt.Errorf("artifact has no layer of media type %s, it is not a ToolHive registry", OCIMediaType)
+	}
+
+	signature, _, err := artifactLayer(artifact, OCISignatureMediaType)
+	if err != nil {
+		return nil, nil, err
+	}
+	return data, signature, nil
+}
+
+// artifactLayer returns the content of the first layer of an OCI artifact with the given media type
+func artifactLayer(artifact v1.Image, mediaType types.MediaType) ([]byte, bool, error) {
+	manifest, err := artifact.Manifest()
+	if err != nil {
+		return nil, false, fmt.Errorf("failed to get manifest of registry artifact: %w", err)
+	}
+
+	for _, desc := range manifest.Layers {
+		if desc.MediaType != mediaType {
+			continue
+		}
+		layer, err := artifact.LayerByDigest(desc.Digest)
+		if err != nil {
+			return nil, false, fmt.Errorf("failed to get %s layer of artifact: %w", mediaType, err)
+		}
+		reader, err := layer.Compressed()
+		if err != nil {
+			return nil, false, fmt.Errorf("failed to read %s layer of artifact: %w", mediaType, err)
+		}
+		defer reader.Close()
+		content, err := io.ReadAll(reader)
+		if err != nil {
+			return nil, false, fmt.Errorf("failed to read %s layer of artifact: %w", mediaType, err)
+		}
+		return content, true, nil
+	}
+	return nil, false, nil
+}
@@ -0,0 +1,86 @@
+package registry
+
+import (
+	"testing"
+
+	"github.com/google/go-containerregistry/pkg/v1/empty"
+	"github.com/google/go-containerregistry/pkg/v1/types"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestOCIArtifact(t *testing.T) {
+	t.Parallel()
+
+	data := []byte(`{"version":"1.0.0","last_updated":"2025-01-01T00:00:00Z","servers":{}}`)
+	signature := []byte("c2lnbmF0dXJl")
+
+	tests := []struct {
+		name           string
+		signature      []byte
+		expectedLayers int
+	}{
+		{
+			name:           "Unsigned registry",
+			expectedLayers: 1,
+		},
+		{
+			name:           "Signed registry",
+			signature:      signature,
+			expectedLayers: 2,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			artifact, err := newOCIArtifact(data, tt.signature)
+			require.NoError(t, err)
+
+			manifest, err := artifact.Manifest()
+			require.NoError(t, err)
+			assert.Equal(t, types.OCIManifestSchema1, manifest.MediaType)
+			assert.Equal(t, OCIConfigMediaType, manifest.Config.MediaType)
+			require.Len(t, manifest.Layers, tt.expectedLayers)
+			assert.Equal(t, OCIMediaType, manifest.Layers[0].MediaType)
+
+			pulledData, pulledSignature, err := artifactRegistryData(artifact)
+			require.NoError(t, err)
+			assert.Equal(t, data, pulledData)
+			assert.Equal(t, tt.signature, pulledSignature)
+		})
+	}
+
+	_, _, err := artifactRegistryData(empty.Image)
+	assert.Error(t, err, "an image without a registry layer is not a registry artifact")
+}
+
+func TestParseOCIReference(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		registryURL string
+		expected    string
+		wantErr     bool
+	}{
+		{registryURL: "oci://ghcr.io/example/registry:latest", expected: "ghcr.io/example/registry:latest"},
+		{registryURL: "oci://registry.example.com:5000/catalog:v1", expected: "registry.example.com:5000/catalog:v1"},
+		{registryURL: "oci://Invalid Reference", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.registryURL, func(t *testing.T) {
+			t.Parallel()
+			assert.True(t, IsOCIReference(tt.registryURL))
+
+			ref, err := ParseOCIReference(tt.registryURL)
+			if tt.wantErr {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.expected, ref.String())
+		})
+	}
+}
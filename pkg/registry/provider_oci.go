@@ -0,0 +1,134 @@
+package registry
+
+import (
+	"context"
+	"fmt"
+	"strings"
+	"sync"
+)
+
+// OCIRegistryProvider provides registry data from an OCI artifact, pulled like an image
+type OCIRegistryProvider struct {
+	registryURL    string
+	allowPrivateIp bool
+	trustedKeys    []string
+	registry       *Registry
+	registryOnce   sync.Once
+	registryErr    error
+}
+
+// NewOCIRegistryProvider creates a new OCI registry provider for a registry URL starting with oci://
+func NewOCIRegistryProvider(registryURL string, allowPrivateIp bool) *OCIRegistryProvider {
+	return &OCIRegistryProvider{
+		registryURL:    registryURL,
+		allowPrivateIp: allowPrivateIp,
+	}
+}
+
+// WithTrustedKeys sets the public keys the signature of the registry must be valid for.
+// The registry is signed with a detached signature pushed in the same artifact.
+func (p *OCIRegistryProvider) WithTrustedKeys(keyPaths []string) *OCIRegistryProvider {
+	p.trustedKeys = keyPaths
+	return p
+}
+
+// GetRegistry returns the registry data of the OCI artifact, cached for CacheTTL.
+// The cached data is used if the artifact can't be pulled.
+func (p *OCIRegistryProvider) GetRegistry() (*Registry, error) {
+	p.registryOnce.Do(func() {
+		verifier, err := NewVerifier(p.trustedKeys)
+		if err != nil {
+			p.registryErr = err
+			return
+		}
+		ctx := context.Background()
+		data, err := fetchCached(ctx, p.registryURL, verifier, func() (*signedRegistry, error) {
+			data, signature, err := PullOCIArtifact(ctx, p.registryURL, p.allowPrivateIp)
+			if err != nil {
+				return nil, err
+			}
+			return &signedRegistry{Data: data, Signature: signature}, nil
+		})
+		if err != nil {
+			p.registryErr = err
+			return
+		}
+
+		p.registry, p.registryErr = parseRegistryData(data)
+		if p.registryErr != nil {
+			return
+		}
+
+		// Set name field on each server based on map key
+		for name, server := range p.registry.Servers {
+			server.Name = name
+		}
+	})
+
+	return p.registry, p.registryErr
+}
+
+// GetServer returns a specific server by name
+func (p *OCIRegistryProvider) GetServer(name string) (*ImageMetadata, error) {
+	reg, err := p.GetRegistry()
+	if err != nil {
+		return nil, err
+	}
+
+	server, ok := reg.Servers[name]
+	if !ok {
+		return nil, fmt.Errorf("server not found: %s", name)
+	}
+
+	return server, nil
+}
+
+// SearchServers searches for servers matching the query
+func (p *OCIRegistryProvider) SearchServers(query string) ([]*ImageMetadata, error) {
+	reg, err := p.GetRegistry()
+	if err != nil {
+		return nil, err
+	}
+
+	query = strings.ToLower(query)
+	var results []*ImageMetadata
+
+	for name, server := range reg.Servers {
+		// Search in name
+		if strings.Contains(strings.ToLower(name), query) {
+			results = append(results, server)
+			continue
+		}
+
+		// Search in description
+		if strings.Contains(strings.ToLower(server.Description), query) {
+			results = append(results, server)
+			continue
+		}
+
+		// Search in tags
+		for _, tag := range server.Tags {
+			if strings.Contains(strings.ToLower(tag), query) {
+				results = append(results, server)
+				break
+			}
+		}
+	}
+
+	return results, nil
+}
+
+// ListServers returns all available servers
+func (p *OCIRegistryProvider) ListServers() ([]*ImageMetadata, error) {
+	reg, err := p.GetRegistry()
+	if err != nil {
+		return nil, err
+	}
+
+	servers := make([]*ImageMetadata, 0, len(reg.Servers))
+	for _, server := range reg.Servers {
+		servers = append(servers, server)
+	}
+
+	return servers, nil
+}
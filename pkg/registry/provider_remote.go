@@ -1,9 +1,11 @@
 package registry
 
 import (
+	"context"
 	"fmt"
 	"io"
 	"net/http"
+	"net/url"
 	"strings"
 	"sync"
 
@@ -14,6 +16,7 @@ import (
 type RemoteRegistryProvider struct {
 	registryURL    string
 	allowPrivateIp bool
+	trustedKeys    []string
 	registry       *Registry
 	registryOnce   sync.Once
 	registryErr    error
@@ -27,27 +30,25 @@ func NewRemoteRegistryProvider(registryURL string, allowPrivateIp bool) *RemoteR
 	}
 }
 
-// GetRegistry returns the remote registry data
+// WithTrustedKeys sets the public keys the signature of the registry must be valid for.
+// The registry is signed with a detached signature served at its URL with the .sig suffix.
+func (p *RemoteRegistryProvider) WithTrustedKeys(keyPaths []string) *RemoteRegistryProvider {
+	p.trustedKeys = keyPaths
+	return p
+}
+
+// GetRegistry returns the remote registry data, cached for CacheTTL.
+// The cached data is used if the remote endpoint can't be reached.
 func (p *RemoteRegistryProvider) GetRegistry() (*Registry, error) {
 	p.registryOnce.Do(func() {
-		client := networking.GetHttpClient(p.allowPrivateIp)
-		resp, err := client.Get(p.registryURL)
+		verifier, err := NewVerifier(p.trustedKeys)
 		if err != nil {
-			p.registryErr = fmt.Errorf("failed to fetch registry data from URL %s: %w", p.registryURL, err)
+			p.registryErr = err
 			return
 		}
-		defer resp.Body.Close()
-
-		// Check if the response status code is OK
-		if resp.StatusCode != http.StatusOK {
-			p.registryErr = fmt.Errorf("response status code from URL %s not OK: status code %d", p.registryURL, resp.StatusCode)
-			return
-		}
-
-		// Read the response body
-		data, err := io.ReadAll(resp.Body)
+		data, err := fetchCached(context.Background(), p.registryURL, verifier, p.fetch)
 		if err != nil {
-			p.registryErr = fmt.Errorf("failed to read registry data from response body: %w", err)
+			p.registryErr = err
 			return
 		}
 
@@ -65,6 +66,54 @@ func (p *RemoteRegistryProvider) GetRegistry() (*Registry, error) {
 	return p.registry, p.registryErr
 }
 
+// fetch fetches the registry data from the remote HTTP endpoint,
+// along with its detached signature if trusted keys are set
+func (p *RemoteRegistryProvider) fetch() (*signedRegistry, error) {
+	client := networking.GetHttpClient(p.allowPrivateIp)
+	data, err := fetchURL(client, p.registryURL, false)
+	if err != nil {
+		return nil, err
+	}
+	if len(p.trustedKeys) == 0 {
+		return &signedRegistry{Data: data}, nil
+	}
+
+	signatureURL, err := url.Parse(p.registryURL)
+	if err != nil {
+		return nil, fmt.Errorf("invalid registry URL %s: %w", p.registryURL, err)
+	}
+	signatureURL.Path += SignatureSuffix
+	signature, err := fetchURL(client, signatureURL.String(), true)
+	if err != nil {
+		return nil, err
+	}
+	return &signedRegistry{Data: data, Signature: signature}, nil
+}
+
+// fetchURL fetches the content of a URL. If optional is set, nil is returned if the URL is not found.
+func fetchURL(client *http.Client, contentURL string, optional bool) ([]byte, error) {
+	resp, err := client.Get(contentURL)
+	if err != nil {
+		return nil, fmt.Errorf("failed to fetch registry data from URL %s: %w", contentURL, err)
+	}
+	defer resp.Body.Close()
+
+	if optional && resp.StatusCode == http.StatusNotFound {
+		return nil, nil
+	}
+	// Check if the response status code is OK
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("response status code from URL %s not OK: status code %d", contentURL, resp.StatusCode)
+	}
+
+	// Read the response body
+	data, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read registry data from response body: %w", err)
+	}
+	return data, nil
+}
+
 // GetServer returns a specific server by name
 func (p *RemoteRegistryProvider) GetServer(name string) (*ImageMetadata, error) {
 	reg, err := p.GetRegistry()
@@ -32,6 +32,13 @@ func TestNewRegistryProvider(t *testing.T) {
 			},
 			expectedType: "*registry.RemoteRegistryProvider",
 		},
+		{
+			name: "OCI registry URL returns OCI provider",
+			config: &config.Config{
+				RegistryUrl: "oci://ghcr.io/example/registry:latest",
+			},
+			expectedType: "*registry.OCIRegistryProvider",
+		},
 	}
 
 	for _, tt := range tests {
@@ -125,6 +132,8 @@ func getTypeName(v interface{}) string {
 		return "*registry.EmbeddedRegistryProvider"
 	case *RemoteRegistryProvider:
 		return "*registry.RemoteRegistryProvider"
+	case *OCIRegistryProvider:
+		return "*registry.OCIRegistryProvider"
 	default:
 		return "unknown"
 	}
@@ -0,0 +1,161 @@
+package registry
+
+import (
+	"bytes"
+	"crypto"
+	"crypto/ecdsa"
+	"crypto/ed25519"
+	"crypto/rand"
+	"crypto/rsa"
+	"crypto/sha256"
+	"crypto/x509"
+	"encoding/base64"
+	"encoding/pem"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+)
+
+// SignatureSuffix is appended to the path of a registry file or URL to get the path of its detached signature
+const SignatureSuffix = ".sig"
+
+var (
+	// ErrRegistryNotSigned is returned when trusted keys are configured but the registry has no signature
+	ErrRegistryNotSigned = errors.New("registry is not signed")
+	// ErrInvalidSignature is returned when the signature of the registry is not valid for any trusted key
+	ErrInvalidSignature = errors.New("registry signature is not valid for any trusted key")
+)
+
+// Verifier verifies the detached signatures of registry data against a set of trusted public keys.
+// Signatures are base64 encoded, as produced by thv registry sign or cosign sign-blob, and made with
+// ECDSA or RSA over the SHA-256 digest of the data, or with Ed25519 over the data itself.
+type Verifier struct {
+	keys []crypto.PublicKey
+}
+
+// NewVerifier creates a verifier which trusts the PEM encoded public keys in the given files.
+// It returns nil if no files are given, in which case registry data is not verified.
+func NewVerifier(keyPaths []string) (*Verifier, error) {
+	if len(keyPaths) == 0 {
+		return nil, nil
+	}
+
+	verifier := &Verifier{}
+	for _, path := range keyPaths {
+		data, err := os.ReadFile(filepath.Clean(path))
+		if err != nil {
+			return nil, fmt.Errorf("failed to read trusted registry key: %w", err)
+		}
+		key, err := ParsePublicKey(data)
+		if err != nil {
+			return nil, fmt.Errorf("invalid trusted registry key %s: %w", path, err)
+		}
+		verifier.keys = append(verifier.keys, key)
+	}
+	return verifier, nil
+}
+
+// Verify verifies the detached signature of registry data. A nil verifier accepts any data.
+func (v *Verifier) Verify(data, signature []byte) error {
+	if v == nil {
+		return nil
+	}
+	if len(bytes.TrimSpace(signature)) == 0 {
+		return ErrRegistryNotSigned
+	}
+
+	sig, err := decodeSignature(signature)
+	if err != nil {
+		return err
+	}
+	digest := sha256.Sum256(data)
+	for _, key := range v.keys {
+		if verifySignature(key, data, digest[:], sig) {
+			return nil
+		}
+	}
+	return ErrInvalidSignature
+}
+
+// ParsePublicKey parses a PEM encoded PKIX public key
+func ParsePublicKey(data []byte) (crypto.PublicKey, error) {
+	block, _ := pem.Decode(data)
+	if block == nil {
+		return nil, fmt.Errorf("no PEM data found")
+	}
+	key, err := x509.ParsePKIXPublicKey(block.Bytes)
+	if err != nil {
+		return nil, fmt.Errorf("failed to parse public key: %w", err)
+	}
+	switch key.(type) {
+	case *ecdsa.PublicKey, ed25519.PublicKey, *rsa.PublicKey:
+		return key, nil
+	default:
+		return nil, fmt.Errorf("unsupported public key type %T", key)
+	}
+}
+
+// Sign signs registry data with a PEM encoded private key (PKCS #8, SEC 1 or PKCS #1),
+// and returns the base64 encoded detached signature
+func Sign(data []byte, privateKeyPEM []byte) ([]byte, error) {
+	block, _ := pem.Decode(privateKeyPEM)
+	if block == nil {
+		return nil, fmt.Errorf("no PEM data found in private key")
+	}
+	key, err := parsePrivateKey(block)
+	if err != nil {
+		return nil, err
+	}
+
+	digest := sha256.Sum256(data)
+	var sig []byte
+	switch k := key.(type) {
+	case *ecdsa.PrivateKey:
+		sig, err = ecdsa.SignASN1(rand.Reader, k, digest[:])
+	case ed25519.PrivateKey:
+		sig = ed25519.Sign(k, data)
+	case *rsa.PrivateKey:
+		sig, err = rsa.SignPKCS1v15(rand.Reader, k, crypto.SHA256, digest[:])
+	default:
+		return nil, fmt.Errorf("unsupported private key type %T", key)
+	}
+	if err != nil {
+		return nil, fmt.Errorf("failed to sign registry: %w", err)
+	}
+	return []byte(base64.StdEncoding.EncodeToString(sig)), nil
+}
+
+func parsePrivateKey(block *pem.Block) (crypto.PrivateKey, error) {
+	switch block.Type {
+	case "EC PRIVATE KEY":
+		return x509.ParseECPrivateKey(block.Bytes)
+	case "RSA PRIVATE KEY":
+		return x509.ParsePKCS1PrivateKey(block.Bytes)
+	case "PRIVATE KEY":
+		return x509.ParsePKCS8PrivateKey(block.Bytes)
+	default:
+		return nil, fmt.Errorf("unsupported private key type %s, encrypted keys are not supported", block.Type)
+	}
+}
+
+func decodeSignature(signature []byte) ([]byte, error) {
+	sig, err := base64.StdEncoding.DecodeString(string(bytes.TrimSpace(signature)))
+	if err != nil {
+		return nil, fmt.Errorf("registry signature is not base64 encoded: %w", err)
+	}
+	return sig, nil
+}
+
+func verifySignature(key crypto.PublicKey, data, digest, sig []byte) bool {
+	switch k := key.(type) {
+	case *ecdsa.PublicKey:
+		return ecdsa.VerifyASN1(k, digest, sig)
+	case ed25519.PublicKey:
+		return ed25519.Verify(k, data, sig)
+	case *rsa.PublicKey:
+		return rsa.VerifyPKCS1v15(k, crypto.SHA256, digest, sig) == nil
+	default:
+		return false
+	}
+}
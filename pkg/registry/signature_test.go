@@ -0,0 +1,109 @@
+package registry
+
+import (
+	"crypto"
+	"crypto/ecdsa"
+	"crypto/ed25519"
+	"crypto/elliptic"
+	"crypto/rand"
+	"crypto/rsa"
+	"crypto/x509"
+	"encoding/pem"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestSignAndVerify(t *testing.T) {
+	t.Parallel()
+
+	data := []byte(`{"version":"1.0.0","servers":{"github":{"image":"ghcr.io/github/github-mcp-server:latest"}}}`)
+	tampered := []byte(`{"version":"1.0.0","servers":{"github":{"image":"ghcr.io/attacker/github-mcp-server:latest"}}}`)
+
+	tests := []struct {
+		name        string
+		generateKey func() (crypto.Signer, error)
+	}{
+		{
+			name: "ECDSA",
+			generateKey: func() (crypto.Signer, error) {
+				return ecdsa.GenerateKey(elliptic.P256(), rand.Reader)
+			},
+		},
+		{
+			name: "Ed25519",
+			generateKey: func() (crypto.Signer, error) {
+				_, key, err := ed25519.GenerateKey(rand.Reader)
+				return key, err
+			},
+		},
+		{
+			name: "RSA",
+			generateKey: func() (crypto.Signer, error) {
+				return rsa.GenerateKey(rand.Reader, 2048)
+			},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			privateKeyPEM, publicKeyPath := writeTestKey(t, tt.generateKey)
+			_, otherKeyPath := writeTestKey(t, tt.generateKey)
+
+			signature, err := Sign(data, privateKeyPEM)
+			require.NoError(t, err)
+
+			verifier, err := NewVerifier([]string{otherKeyPath, publicKeyPath})
+			require.NoError(t, err)
+			assert.NoError(t, verifier.Verify(data, signature))
+			assert.ErrorIs(t, verifier.Verify(tampered, signature), ErrInvalidSignature)
+			assert.ErrorIs(t, verifier.Verify(data, nil), ErrRegistryNotSigned)
+
+			otherVerifier, err := NewVerifier([]string{otherKeyPath})
+			require.NoError(t, err)
+			assert.ErrorIs(t, otherVerifier.Verify(data, signature), ErrInvalidSignature)
+		})
+	}
+}
+
+func TestNewVerifier(t *testing.T) {
+	t.Parallel()
+
+	verifier, err := NewVerifier(nil)
+	require.NoError(t, err)
+	assert.Nil(t, verifier)
+	assert.NoError(t, verifier.Verify([]byte("{}"), nil), "without trusted keys any registry is accepted")
+
+	invalidKeyPath := filepath.Join(t.TempDir(), "invalid.pem")
+	require.NoError(t, os.WriteFile(invalidKeyPath, []byte("not a key"), 0600))
+	_, err = NewVerifier([]string{invalidKeyPath})
+	assert.Error(t, err)
+
+	_, err = NewVerifier([]string{filepath.Join(t.TempDir(), "missing.pem")})
+	assert.Error(t, err)
+}
+
+// writeTestKey generates a key pair, and returns the PEM encoded private key
+// and the path of a file holding the PEM encoded public key
+func writeTestKey(t *testing.T, generateKey func() (crypto.Signer, error)) ([]byte, string) {
+	t.Helper()
+
+	key, err := generateKey()
+	require.NoError(t, err)
+
+	privateDER, err := x509.MarshalPKCS8PrivateKey(key)
+	require.NoError(t, err)
+	publicDER, err := x509.MarshalPKIXPublicKey(key.Public())
+	require.NoError(t, err)
+
+	publicKeyPath := filepath.Join(t.TempDir(), "key.pub")
+	publicKeyPEM := pem.EncodeToMemory(&pem.Block{Type: "PUBLIC KEY", Bytes: publicDER})
+	require.NoError(t, os.WriteFile(publicKeyPath, publicKeyPEM, 0600))
+
+	return pem.EncodeToMemory(&pem.Block{Type: "PRIVATE KEY", Bytes: privateDER}), publicKeyPath
+}
@@ -33,7 +33,7 @@ type ImageMetadata struct {
 	Tier string `json:"tier"`
 	// The Status indicates whether the server is currently active or deprecated
 	Status string `json:"status"`
-	// Transport defines the communication protocol for the server (stdio, sse, or streamable-http)
+	// Transport defines the communication protocol for the server (stdio, sse, streamable-http or websocket)
 	Transport string `json:"transport"`
 	// TargetPort is the port for the container to expose (only applicable to SSE and Streamable HTTP transports)
 	TargetPort int `json:"target_port,omitempty"`
@@ -54,6 +54,8 @@ type ImageMetadata struct {
 	Tags []string `json:"tags,omitempty"`
 	// DockerTags lists the available Docker tags for this server image
 	DockerTags []string `json:"docker_tags,omitempty"`
+	// Channels maps release channels (e.g. stable, latest) to the Docker tag each channel currently points to
+	Channels map[string]string `json:"channels,omitempty"`
 	// Provenance contains verification and signing metadata
 	Provenance *Provenance `json:"provenance,omitempty"`
 }
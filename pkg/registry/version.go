@@ -0,0 +1,20 @@
+package registry
+
+import "strings"
+
+// SplitServerVersion splits a server reference with a version selector, such as github@^1.2 or
+// github@stable, into the server name and the selector, which is a channel or a version constraint.
+// References without a selector, and image references pinned by digest, are returned unchanged.
+func SplitServerVersion(serverRef string) (string, string) {
+	i := strings.LastIndex(serverRef, "@")
+	if i <= 0 || strings.Contains(serverRef[i+1:], ":") {
+		return serverRef, ""
+	}
+	return serverRef[:i], serverRef[i+1:]
+}
+
+// ChannelTag returns the Docker tag the given release channel of the server currently points to
+func (m *ImageMetadata) ChannelTag(channel string) (string, bool) {
+	tag, ok := m.Channels[channel]
+	return tag, ok && tag != ""
+}
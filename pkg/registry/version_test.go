@@ -0,0 +1,55 @@
+package registry
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestSplitServerVersion(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		serverRef        string
+		expectedName     string
+		expectedSelector string
+	}{
+		{serverRef: "github", expectedName: "github"},
+		{serverRef: "github@^1.2", expectedName: "github", expectedSelector: "^1.2"},
+		{serverRef: "github@stable", expectedName: "github", expectedSelector: "stable"},
+		{serverRef: "ghcr.io/github/github-mcp-server:1.2.0", expectedName: "ghcr.io/github/github-mcp-server:1.2.0"},
+		{
+			serverRef:    "ghcr.io/github/github-mcp-server@sha256:1111111111111111111111111111111111111111111111111111111111111111",
+			expectedName: "ghcr.io/github/github-mcp-server@sha256:1111111111111111111111111111111111111111111111111111111111111111",
+		},
+		{serverRef: "@stable", expectedName: "@stable"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.serverRef, func(t *testing.T) {
+			t.Parallel()
+			name, selector := SplitServerVersion(tt.serverRef)
+			assert.Equal(t, tt.expectedName, name)
+			assert.Equal(t, tt.expectedSelector, selector)
+		})
+	}
+}
+
+func TestImageMetadata_ChannelTag(t *testing.T) {
+	t.Parallel()
+
+	server := &ImageMetadata{Channels: map[string]string{"stable": "1.2.3", "latest": "latest", "beta": ""}}
+
+	tag, ok := server.ChannelTag("stable")
+	assert.True(t, ok)
+	assert.Equal(t, "1.2.3", tag)
+
+	_, ok = server.ChannelTag("beta")
+	assert.False(t, ok, "a channel without a tag should not resolve")
+
+	_, ok = server.ChannelTag("nightly")
+	assert.False(t, ok)
+
+	_, ok = (&ImageMetadata{}).ChannelTag("stable")
+	assert.False(t, ok)
+}
@@ -0,0 +1,69 @@
+package runner
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/stacklok/toolhive/pkg/container/images"
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+// LocalBuild is the build of an MCP server image from a local build context with a Dockerfile
+type LocalBuild struct {
+	// ContextDir is the absolute path of the build context
+	ContextDir string
+	// Dockerfile is the path of the Dockerfile, relative to the build context
+	Dockerfile string
+	// Image is the name the built image is tagged with
+	Image string
+}
+
+// NewLocalBuild creates the build of the given context directory. The Dockerfile is the Dockerfile
+// in the context directory if dockerfile is empty, and must be inside the context directory otherwise.
+// The image is tagged as toolhivelocal/NAME:latest, after the context directory, if tag is empty.
+func NewLocalBuild(contextDir, dockerfile, tag string) (*LocalBuild, error) {
+	absContextDir, err := filepath.Abs(contextDir)
+	if err != nil {
+		return nil, fmt.Errorf("invalid build context %s: %w", contextDir, err)
+	}
+	info, err := os.Stat(absContextDir)
+	if err != nil {
+		return nil, fmt.Errorf("invalid build context %s: %w", contextDir, err)
+	}
+	if !info.IsDir() {
+		return nil, fmt.Errorf("invalid build context %s: not a directory", contextDir)
+	}
+
+	dockerfilePath := filepath.Join(absContextDir, "Dockerfile")
+	if dockerfile != "" {
+		if dockerfilePath, err = filepath.Abs(dockerfile); err != nil {
+			return nil, fmt.Errorf("invalid Dockerfile %s: %w", dockerfile, err)
+		}
+	}
+	relDockerfile, err := filepath.Rel(absContextDir, dockerfilePath)
+	if err != nil || relDockerfile == ".." || strings.HasPrefix(relDockerfile, ".."+string(filepath.Separator)) {
+		return nil, fmt.Errorf("the Dockerfile %s must be inside the build context %s", dockerfilePath, contextDir)
+	}
+	if _, err := os.Stat(dockerfilePath); err != nil {
+		return nil, fmt.Errorf("no Dockerfile found: %w", err)
+	}
+
+	if tag == "" {
+		tag = strings.ToLower(fmt.Sprintf("toolhivelocal/%s:latest", packageNameToImageName(filepath.Base(absContextDir))))
+	}
+
+	return &LocalBuild{ContextDir: absContextDir, Dockerfile: relDockerfile, Image: tag}, nil
+}
+
+// Build builds and tags the image with the container runtime, and returns the name of the image
+func (b *LocalBuild) Build(ctx context.Context, imageManager images.ImageManager) (string, error) {
+	logger.Infof("Building image %s from %s", b.Image, filepath.Join(b.ContextDir, b.Dockerfile))
+	if err := imageManager.BuildImage(ctx, b.ContextDir, b.Dockerfile, b.Image); err != nil {
+		return "", fmt.Errorf("failed to build image %s: %w", b.Image, err)
+	}
+	logger.Infof("Successfully built image: %s", b.Image)
+	return b.Image, nil
+}
@@ -0,0 +1,82 @@
+package runner
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestNewLocalBuild(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	contextDir := filepath.Join(dir, "My.Server")
+	require.NoError(t, os.MkdirAll(filepath.Join(contextDir, "build"), 0750))
+	require.NoError(t, os.WriteFile(filepath.Join(contextDir, "Dockerfile"), []byte("FROM scratch\n"), 0600))
+	require.NoError(t, os.WriteFile(filepath.Join(contextDir, "build", "Dockerfile.mcp"), []byte("FROM scratch\n"), 0600))
+	require.NoError(t, os.WriteFile(filepath.Join(dir, "Dockerfile"), []byte("FROM scratch\n"), 0600))
+	emptyDir := t.TempDir()
+
+	tests := []struct {
+		name       string
+		contextDir string
+		dockerfile string
+		tag        string
+		expected   *LocalBuild
+		wantErr    bool
+	}{
+		{
+			name:       "Dockerfile of the context directory",
+			contextDir: contextDir,
+			expected:   &LocalBuild{ContextDir: contextDir, Dockerfile: "Dockerfile", Image: "toolhivelocal/my-server:latest"},
+		},
+		{
+			name:       "Dockerfile in the context directory",
+			contextDir: contextDir,
+			dockerfile: filepath.Join(contextDir, "build", "Dockerfile.mcp"),
+			tag:        "example/server:dev",
+			expected: &LocalBuild{
+				ContextDir: contextDir,
+				Dockerfile: filepath.Join("build", "Dockerfile.mcp"),
+				Image:      "example/server:dev",
+			},
+		},
+		{
+			name:       "Dockerfile outside the context directory",
+			contextDir: contextDir,
+			dockerfile: filepath.Join(dir, "Dockerfile"),
+			wantErr:    true,
+		},
+		{
+			name:       "No Dockerfile",
+			contextDir: emptyDir,
+			wantErr:    true,
+		},
+		{
+			name:       "Context directory which does not exist",
+			contextDir: filepath.Join(dir, "missing"),
+			wantErr:    true,
+		},
+		{
+			name:       "Context which is not a directory",
+			contextDir: filepath.Join(dir, "Dockerfile"),
+			wantErr:    true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			build, err := NewLocalBuild(tt.contextDir, tt.dockerfile, tt.tag)
+			if tt.wantErr {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.expected, build)
+		})
+	}
+}
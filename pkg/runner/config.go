@@ -6,7 +6,11 @@ import (
 	"encoding/json"
 	"fmt"
 	"io"
+	"path/filepath"
 	"strings"
+	"time"
+
+	"github.com/docker/go-units"
 
 	"github.com/stacklok/toolhive/pkg/audit"
 	"github.com/stacklok/toolhive/pkg/auth"
@@ -16,12 +20,15 @@ import (
 	"github.com/stacklok/toolhive/pkg/environment"
 	"github.com/stacklok/toolhive/pkg/labels"
 	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/mcp"
 	"github.com/stacklok/toolhive/pkg/networking"
 	"github.com/stacklok/toolhive/pkg/permissions"
+	"github.com/stacklok/toolhive/pkg/ratelimit"
 	"github.com/stacklok/toolhive/pkg/registry"
 	"github.com/stacklok/toolhive/pkg/secrets"
 	"github.com/stacklok/toolhive/pkg/telemetry"
 	"github.com/stacklok/toolhive/pkg/transport"
+	"github.com/stacklok/toolhive/pkg/transport/proxy"
 	"github.com/stacklok/toolhive/pkg/transport/types"
 )
 
@@ -80,6 +87,30 @@ type RunConfig struct {
 	// OIDCConfig contains OIDC configuration
 	OIDCConfig *auth.TokenValidatorConfig `json:"oidc_config,omitempty" yaml:"oidc_config,omitempty"`
 
+	// AuthToken refers to the static token clients must send as Bearer token: file:PATH for a token read
+	// from a file each time the server starts, or env:NAME for a token which must be set in the environment
+	// when the server is restarted. The token itself is never saved.
+	AuthToken string `json:"auth_token,omitempty" yaml:"auth_token,omitempty"`
+
+	// StaticToken is the static token given on the command line, which is only kept in memory
+	StaticToken string `json:"-" yaml:"-"`
+
+	// RateLimit is the number of requests each client can make, such as 100/min
+	RateLimit string `json:"rate_limit,omitempty" yaml:"rate_limit,omitempty"`
+
+	// Tools are the only tools of the MCP server exposed by the proxy, or all its tools if empty
+	Tools []string `json:"tools,omitempty" yaml:"tools,omitempty"`
+
+	// ToolOverrideFile is the path of the YAML or JSON file renaming the tools of the MCP server
+	// and rewriting their descriptions
+	ToolOverrideFile string `json:"tool_override_file,omitempty" yaml:"tool_override_file,omitempty"`
+
+	// LogRequests indicates whether the MCP requests going through the proxy are logged
+	LogRequests bool `json:"log_requests,omitempty" yaml:"log_requests,omitempty"`
+
+	// LogPayloads indicates whether the parameters and responses of the logged MCP requests are logged too
+	LogPayloads bool `json:"log_payloads,omitempty" yaml:"log_payloads,omitempty"`
+
 	// AuthzConfig contains the authorization configuration
 	AuthzConfig *authz.Config `json:"authz_config,omitempty" yaml:"authz_config,omitempty"`
 
@@ -108,6 +139,90 @@ type RunConfig struct {
 
 	// IsolateNetwork indicates whether to isolate the network for the container
 	IsolateNetwork bool `json:"isolate_network,omitempty" yaml:"isolate_network,omitempty"`
+
+	// LogSink is an additional destination for the server's logs (file, syslog or journald)
+	LogSink string `json:"log_sink,omitempty" yaml:"log_sink,omitempty"`
+
+	// LogDriver is the container runtime log driver to use for the MCP server container
+	LogDriver string `json:"log_driver,omitempty" yaml:"log_driver,omitempty"`
+
+	// LogOptions are the log driver options (e.g., max-size, max-file)
+	LogOptions map[string]string `json:"log_options,omitempty" yaml:"log_options,omitempty"`
+
+	// AutoUpgrade indicates whether the image of the server is upgraded automatically
+	AutoUpgrade bool `json:"auto_upgrade,omitempty" yaml:"auto_upgrade,omitempty"`
+
+	// UpgradeWindow is the maintenance window during which the server may be upgraded automatically
+	UpgradeWindow string `json:"upgrade_window,omitempty" yaml:"upgrade_window,omitempty"`
+
+	// VersionConstraint is the release channel or semantic version constraint the server was run with
+	// (e.g. stable or ^1.2), which its upgrades respect
+	VersionConstraint string `json:"version_constraint,omitempty" yaml:"version_constraint,omitempty"`
+
+	// StopTimeout is the time in seconds to wait for the server to exit after the stop signal before it is killed
+	StopTimeout int `json:"stop_timeout,omitempty" yaml:"stop_timeout,omitempty"`
+
+	// StopSignal is the signal used to stop the server (e.g., SIGTERM, SIGINT)
+	StopSignal string `json:"stop_signal,omitempty" yaml:"stop_signal,omitempty"`
+
+	// CPUs is the number of CPUs the server may use (e.g., 0.5)
+	CPUs float64 `json:"cpus,omitempty" yaml:"cpus,omitempty"`
+
+	// Memory is the memory limit of the server in bytes
+	Memory int64 `json:"memory,omitempty" yaml:"memory,omitempty"`
+
+	// MemorySwap is the limit of memory and swap of the server in bytes, or -1 for unlimited swap
+	MemorySwap int64 `json:"memory_swap,omitempty" yaml:"memory_swap,omitempty"`
+
+	// PidsLimit is the maximum number of processes of the server
+	PidsLimit int64 `json:"pids_limit,omitempty" yaml:"pids_limit,omitempty"`
+
+	// GPUs are the GPUs of the host made available to the server (all, a number of GPUs, or device=ID[,ID...])
+	GPUs string `json:"gpus,omitempty" yaml:"gpus,omitempty"`
+
+	// Devices are the devices of the host made available to the server (host paths or CDI device names)
+	Devices []string `json:"devices,omitempty" yaml:"devices,omitempty"`
+
+	// RestartPolicy is the policy for restarting the server after it exited (no, always, unless-stopped
+	// or on-failure[:max-retries]); the server is restarted unless it is stopped by default
+	RestartPolicy string `json:"restart_policy,omitempty" yaml:"restart_policy,omitempty"`
+
+	// RestartUnhealthy indicates whether the server is restarted when its health checks fail
+	RestartUnhealthy bool `json:"restart_unhealthy,omitempty" yaml:"restart_unhealthy,omitempty"`
+
+	// Networks are existing networks of the container runtime the server joins, e.g. of a compose stack
+	Networks []string `json:"networks,omitempty" yaml:"networks,omitempty"`
+
+	// SSEPath is the path of the SSE endpoint served by the proxy (stdio transport only)
+	SSEPath string `json:"sse_path,omitempty" yaml:"sse_path,omitempty"`
+
+	// MessagesPath is the path of the message endpoint served by the proxy (stdio transport only)
+	MessagesPath string `json:"messages_path,omitempty" yaml:"messages_path,omitempty"`
+
+	// NamedPipe is the path of the Windows named pipe on which the proxy is served in addition to the port
+	NamedPipe string `json:"named_pipe,omitempty" yaml:"named_pipe,omitempty"`
+
+	// ListenSocket is the path of the Unix domain socket on which the proxy is served instead of the port
+	ListenSocket string `json:"listen_socket,omitempty" yaml:"listen_socket,omitempty"`
+
+	// UpstreamHTTP2 indicates whether the proxy forwards the requests of HTTP/2 clients to the server over HTTP/2
+	UpstreamHTTP2 bool `json:"upstream_http2,omitempty" yaml:"upstream_http2,omitempty"`
+
+	// TLSCertFile is the path of the PEM-encoded certificate with which the proxy serves HTTPS
+	TLSCertFile string `json:"tls_cert_file,omitempty" yaml:"tls_cert_file,omitempty"`
+
+	// TLSKeyFile is the path of the PEM-encoded key of the TLS certificate
+	TLSKeyFile string `json:"tls_key_file,omitempty" yaml:"tls_key_file,omitempty"`
+
+	// TLSSelfSigned indicates whether the proxy serves HTTPS with a generated self-signed certificate
+	TLSSelfSigned bool `json:"tls_self_signed,omitempty" yaml:"tls_self_signed,omitempty"`
+
+	// CORSOrigins are the origins of the web clients allowed to connect to the proxy from a browser,
+	// or "*" for any origin
+	CORSOrigins []string `json:"cors_origins,omitempty" yaml:"cors_origins,omitempty"`
+
+	// ProxyLimits are the timeouts of the proxy and the maximum size of the messages of the clients
+	ProxyLimits types.ProxyLimits `json:"proxy_limits,omitempty" yaml:"proxy_limits,omitempty"`
 }
 
 // WriteJSON serializes the RunConfig to JSON and writes it to the provided writer
@@ -173,6 +288,10 @@ func NewRunConfigFromFlags(
 	k8sPodPatch string,
 	envVarValidator EnvVarValidator,
 ) (*RunConfig, error) {
+	if err := validateK8sPodPatch(k8sPodPatch); err != nil {
+		return nil, err
+	}
+
 	// Ensure default values for host and targetHost
 	if host == "" {
 		host = transport.LocalhostIPv4
@@ -322,18 +441,26 @@ func (c *RunConfig) WithTransport(t string) (*RunConfig, error) {
 	return c, nil
 }
 
-// WithPorts configures the host and target ports
+// WithPorts configures the host and target ports. Free ports are selected for ports which are zero,
+// while ports which are specified must be available.
 func (c *RunConfig) WithPorts(port, targetPort int) (*RunConfig, error) {
+	host := c.Host
+	if host == "" {
+		host = transport.LocalhostIPv4
+	}
+
 	// Select a port for the HTTP proxy (host port)
-	selectedPort, err := networking.FindOrUsePort(port)
+	selectedPort, err := selectPort(host, port)
 	if err != nil {
 		return c, err
 	}
+	logger.Infof("Using port: %d", selectedPort)
 	c.Port = selectedPort
 
-	// Select a target port for the container if using SSE or Streamable HTTP transport
-	if c.Transport == types.TransportTypeSSE || c.Transport == types.TransportTypeStreamableHTTP {
-		selectedTargetPort, err := networking.FindOrUsePort(targetPort)
+	// Select a target port for the container if using SSE, Streamable HTTP or WebSocket transport
+	if c.Transport == types.TransportTypeSSE || c.Transport == types.TransportTypeStreamableHTTP ||
+		c.Transport == types.TransportTypeWebSocket {
+		selectedTargetPort, err := selectPort(transport.LocalhostIPv4, targetPort)
 		if err != nil {
 			return c, fmt.Errorf("target port error: %w", err)
 		}
@@ -344,6 +471,19 @@ func (c *RunConfig) WithPorts(port, targetPort int) (*RunConfig, error) {
 	return c, nil
 }
 
+// selectPort returns the port if it is available on the host, or a free port if it is zero. Unlike
+// networking.FindOrUsePort, a port which is in use is an error rather than being replaced by a free one,
+// since clients are configured with the port which was asked for.
+func selectPort(host string, port int) (int, error) {
+	if port == 0 {
+		return networking.FindOrUsePort(0)
+	}
+	if err := networking.CheckPortAvailable(host, port); err != nil {
+		return 0, err
+	}
+	return port, nil
+}
+
 // ParsePermissionProfile loads and sets the permission profile
 func (c *RunConfig) ParsePermissionProfile() (*RunConfig, error) {
 	if c.PermissionProfileNameOrPath == "" {
@@ -370,6 +510,69 @@ func (c *RunConfig) ParsePermissionProfile() (*RunConfig, error) {
 	return c, nil
 }
 
+// WithNamedPipe serves the proxy on a Windows named pipe named after the server, in addition to its port
+func (c *RunConfig) WithNamedPipe(enabled bool) *RunConfig {
+	if enabled {
+		c.NamedPipe = networking.NamedPipePath(c.BaseName)
+	} else {
+		c.NamedPipe = ""
+	}
+	return c
+}
+
+// maxSocketPathLength is the maximum length of the path of a Unix domain socket
+const maxSocketPathLength = 103
+
+// WithListenSocket serves the proxy on the Unix domain socket at the given path instead of its port.
+// The path is made absolute, since the proxy may run in another directory when detached.
+func (c *RunConfig) WithListenSocket(path string) (*RunConfig, error) {
+	if path == "" {
+		c.ListenSocket = ""
+		return c, nil
+	}
+	absPath, err := filepath.Abs(path)
+	if err != nil {
+		return c, fmt.Errorf("invalid socket path %s: %w", path, err)
+	}
+	if len(absPath) > maxSocketPathLength {
+		return c, fmt.Errorf("socket path %s is longer than %d bytes", absPath, maxSocketPathLength)
+	}
+	c.ListenSocket = absPath
+
+	// Record the socket so the workload can be listed and health checked without its saved state
+	if c.ContainerLabels == nil {
+		c.ContainerLabels = make(map[string]string)
+	}
+	labels.SetListenSocket(c.ContainerLabels, absPath)
+	return c, nil
+}
+
+// WithPermissionGrants adds temporary egress grants in the format HOST[:PORT] to the permission profile,
+// which are revoked once the duration has passed
+func (c *RunConfig) WithPermissionGrants(grants []string, duration time.Duration) (*RunConfig, error) {
+	if len(grants) == 0 {
+		return c, nil
+	}
+	if c.PermissionProfile == nil {
+		return c, fmt.Errorf("a permission profile is required for permission grants")
+	}
+
+	now := time.Now()
+	for _, spec := range grants {
+		grant, err := permissions.ParseGrant(spec, duration, now)
+		if err != nil {
+			return c, err
+		}
+		c.PermissionProfile.Grants = append(c.PermissionProfile.Grants, grant)
+	}
+
+	netPerms := c.PermissionProfile.Network
+	if !c.IsolateNetwork || netPerms == nil || netPerms.Outbound == nil || netPerms.Outbound.InsecureAllowAll {
+		logger.Warnf("Warning: The network access of the server is not restricted, permission grants have no effect")
+	}
+	return c, nil
+}
+
 // WithEnvironmentVariables parses and sets environment variables
 func (c *RunConfig) WithEnvironmentVariables(envVarStrings []string) (*RunConfig, error) {
 	envVars, err := environment.ParseEnvironmentVariables(envVarStrings)
@@ -392,6 +595,386 @@ func (c *RunConfig) WithEnvironmentVariables(envVarStrings []string) (*RunConfig
 	return c, nil
 }
 
+// WithLogConfig sets the container log driver and parses its options
+// Options are in the format "key=value" (e.g., "max-size=10m")
+func (c *RunConfig) WithLogConfig(driver string, options []string) (*RunConfig, error) {
+	c.LogDriver = driver
+
+	for _, option := range options {
+		key, value, ok := strings.Cut(option, "=")
+		if !ok || key == "" {
+			return c, fmt.Errorf("invalid log option format: %s (expected key=value)", option)
+		}
+		if c.LogOptions == nil {
+			c.LogOptions = make(map[string]string)
+		}
+		c.LogOptions[key] = value
+	}
+
+	return c, nil
+}
+
+// WithStopConfig sets the default timeout in seconds and signal used to stop the server
+func (c *RunConfig) WithStopConfig(timeout int, signal string) (*RunConfig, error) {
+	if timeout < 0 {
+		return c, fmt.Errorf("invalid stop timeout: %d (must not be negative)", timeout)
+	}
+	c.StopTimeout = timeout
+	c.StopSignal = signal
+	return c, nil
+}
+
+// WithResourceLimits sets the resource limits of the server. Memory sizes are in bytes, or have a unit
+// suffix like the Docker CLI (e.g., 512m or 2g), and a memory swap of -1 allows unlimited swap.
+func (c *RunConfig) WithResourceLimits(cpus float64, memory, memorySwap string, pidsLimit int64) (*RunConfig, error) {
+	if cpus < 0 {
+		return c, fmt.Errorf("invalid CPUs: %g (must not be negative)", cpus)
+	}
+	if pidsLimit < 0 {
+		return c, fmt.Errorf("invalid PIDs limit: %d (must not be negative)", pidsLimit)
+	}
+	c.CPUs = cpus
+	c.PidsLimit = pidsLimit
+
+	var err error
+	if c.Memory, err = parseMemorySize(memory); err != nil {
+		return c, fmt.Errorf("invalid memory limit: %w", err)
+	}
+	if memorySwap == "-1" {
+		c.MemorySwap = -1
+	} else if c.MemorySwap, err = parseMemorySize(memorySwap); err != nil {
+		return c, fmt.Errorf("invalid memory swap limit: %w", err)
+	}
+	if c.MemorySwap != 0 && c.Memory == 0 {
+		return c, fmt.Errorf("a memory swap limit requires a memory limit")
+	}
+	if c.MemorySwap > 0 && c.MemorySwap < c.Memory {
+		return c, fmt.Errorf("the memory swap limit must not be lower than the memory limit")
+	}
+	return c, nil
+}
+
+// WithDevices sets the GPUs and devices of the host made available to the server. GPUs are requested
+// like with the --gpus flag of the Docker CLI, and devices are host paths or CDI device names.
+func (c *RunConfig) WithDevices(gpus string, devices []string) (*RunConfig, error) {
+	if _, err := rt.ParseGPURequest(gpus); err != nil {
+		return c, err
+	}
+	for _, device := range devices {
+		if _, err := rt.ParseDevice(device); err != nil {
+			return c, err
+		}
+	}
+	c.GPUs = gpus
+	c.Devices = devices
+	return c, nil
+}
+
+// WithRestartPolicy sets the policy for restarting the server after it exited
+func (c *RunConfig) WithRestartPolicy(policy string) (*RunConfig, error) {
+	if _, err := rt.ParseRestartPolicy(policy); err != nil {
+		return c, err
+	}
+	c.RestartPolicy = policy
+	return c, nil
+}
+
+// WithNetworks sets the existing networks of the container runtime the server joins. Joining a network
+// gives the server access to the services on it, so the permission profile must allow outbound network
+// access, and with network isolation all outbound traffic, since traffic on the networks does not go
+// through the egress proxy.
+func (c *RunConfig) WithNetworks(networks []string) (*RunConfig, error) {
+	if len(networks) == 0 {
+		return c, nil
+	}
+	for _, name := range networks {
+		switch {
+		case name == "":
+			return c, fmt.Errorf("the network name must not be empty")
+		case name == "host" || name == "none":
+			return c, fmt.Errorf("the %s network cannot be joined", name)
+		case strings.HasPrefix(name, "toolhive-"):
+			return c, fmt.Errorf("the network %s is managed by ToolHive and cannot be joined", name)
+		}
+	}
+
+	var outbound *permissions.OutboundNetworkPermissions
+	if c.PermissionProfile != nil && c.PermissionProfile.Network != nil {
+		outbound = c.PermissionProfile.Network.Outbound
+	}
+	if outbound == nil || (!outbound.InsecureAllowAll && len(outbound.AllowHost) == 0) {
+		return c, fmt.Errorf("joining networks requires a permission profile which allows outbound network access")
+	}
+	if c.IsolateNetwork && !outbound.InsecureAllowAll {
+		return c, fmt.Errorf("joining networks with --isolate-network requires a permission profile which allows " +
+			"all outbound network access, since traffic on the networks bypasses the egress proxy")
+	}
+
+	c.Networks = networks
+	return c, nil
+}
+
+// parseMemorySize parses a memory size in bytes or with a unit suffix, where an empty size is zero
+func parseMemorySize(value string) (int64, error) {
+	if value == "" {
+		return 0, nil
+	}
+	size, err := units.RAMInBytes(value)
+	if err != nil || size <= 0 {
+		return 0, fmt.Errorf("%s (expected a size like 512m or 2g)", value)
+	}
+	return size, nil
+}
+
+// WithAuthToken requires clients to send a static token as Bearer token, given either as the
+// token itself, as file:PATH or as env:NAME. Static tokens cannot be combined with OIDC validation.
+// A token given as itself is not saved with the state of the server, which refers to the environment
+// variable it must be set with to restart the server instead.
+func (c *RunConfig) WithAuthToken(value string) (*RunConfig, error) {
+	if value == "" {
+		return c, nil
+	}
+	if c.OIDCConfig != nil {
+		return c, fmt.Errorf("a static auth token cannot be used with OIDC validation")
+	}
+	if _, err := auth.ResolveStaticToken(value); err != nil {
+		return c, err
+	}
+	if auth.IsStaticTokenReference(value) {
+		c.AuthToken = value
+		return c, nil
+	}
+	c.StaticToken = value
+	c.AuthToken = auth.StaticTokenEnvReference
+	return c, nil
+}
+
+// staticTokenValue returns the static token given on the command line, or else the reference to it
+func (c *RunConfig) staticTokenValue() string {
+	if c.StaticToken != "" {
+		return c.StaticToken
+	}
+	return c.AuthToken
+}
+
+// WithTLS serves the proxy over HTTPS, with the certificate and key in the given files, or with a
+// self-signed certificate generated for the server. The proxy serves plain HTTP if neither is given.
+func (c *RunConfig) WithTLS(certFile, keyFile string, selfSigned bool) (*RunConfig, error) {
+	if (certFile == "") != (keyFile == "") {
+		return c, fmt.Errorf("a TLS certificate and its key must be specified together")
+	}
+	if selfSigned && certFile != "" {
+		return c, fmt.Errorf("a self-signed certificate cannot be used with a TLS certificate file")
+	}
+
+	c.TLSCertFile = certFile
+	c.TLSKeyFile = keyFile
+	c.TLSSelfSigned = selfSigned
+
+	// Record that the proxy serves HTTPS so the URL of the server can be generated from the container labels
+	if c.IsTLS() {
+		if c.ContainerLabels == nil {
+			c.ContainerLabels = make(map[string]string)
+		}
+		labels.SetTLS(c.ContainerLabels)
+	}
+	return c, nil
+}
+
+// WithRateLimit limits the requests of each client to the proxy to a number per period, such as 100/min
+func (c *RunConfig) WithRateLimit(value string) (*RunConfig, error) {
+	if value == "" {
+		return c, nil
+	}
+	if _, err := ratelimit.ParseLimit(value); err != nil {
+		return c, err
+	}
+	c.RateLimit = value
+	return c, nil
+}
+
+// WithProxyLimits sets the timeouts of the proxy and the maximum size of the messages of the clients,
+// given in bytes or with a unit suffix like 10m. Zero values keep the defaults.
+func (c *RunConfig) WithProxyLimits(
+	connectTimeout, idleTimeout, sseReadTimeout time.Duration, maxMessageSize string,
+) (*RunConfig, error) {
+	if connectTimeout < 0 || idleTimeout < 0 || sseReadTimeout < 0 {
+		return c, fmt.Errorf("the timeouts of the proxy cannot be negative")
+	}
+	size, err := parseMemorySize(maxMessageSize)
+	if err != nil {
+		return c, fmt.Errorf("invalid maximum message size: %w", err)
+	}
+	c.ProxyLimits = types.ProxyLimits{
+		ConnectTimeout: connectTimeout,
+		IdleTimeout:    idleTimeout,
+		SSEReadTimeout: sseReadTimeout,
+		MaxMessageSize: size,
+	}
+	return c, nil
+}
+
+// WithToolOverrideFile renames the tools of the MCP server and rewrites their descriptions with the overrides
+// in the given file
+func (c *RunConfig) WithToolOverrideFile(path string) (*RunConfig, error) {
+	if path == "" {
+		return c, nil
+	}
+	if _, err := mcp.LoadToolOverrides(path); err != nil {
+		return c, err
+	}
+	c.ToolOverrideFile = path
+	return c, nil
+}
+
+// WithCORSOrigins lets web clients of the given origins connect to the proxy from a browser
+func (c *RunConfig) WithCORSOrigins(origins []string) (*RunConfig, error) {
+	for _, origin := range origins {
+		if err := proxy.ValidateCORSOrigin(origin); err != nil {
+			return c, err
+		}
+	}
+	c.CORSOrigins = origins
+	return c, nil
+}
+
+// IsTLS returns true if the proxy serves HTTPS
+func (c *RunConfig) IsTLS() bool {
+	return c.TLSCertFile != "" || c.TLSSelfSigned
+}
+
+// WithEndpointPaths sets the paths at which the proxy serves the SSE and message endpoints.
+// Empty paths leave the defaults in place. Custom paths are only supported for the stdio transport,
+// since for the SSE transport the paths are determined by the server in the container.
+func (c *RunConfig) WithEndpointPaths(ssePath, messagesPath string) (*RunConfig, error) {
+	if ssePath == "" && messagesPath == "" {
+		return c, nil
+	}
+	if c.Transport != types.TransportTypeStdio {
+		return c, fmt.Errorf("custom endpoint paths are only supported for the stdio transport")
+	}
+
+	for _, endpointPath := range []string{ssePath, messagesPath} {
+		if err := validateEndpointPath(endpointPath); err != nil {
+			return c, err
+		}
+	}
+	if ssePath != "" && ssePath == messagesPath {
+		return c, fmt.Errorf("the SSE path and the messages path must be different: %s", ssePath)
+	}
+
+	c.SSEPath = ssePath
+	c.MessagesPath = messagesPath
+
+	// Record the SSE path so the URL of the server can be generated from the container labels
+	if ssePath != "" {
+		if c.ContainerLabels == nil {
+			c.ContainerLabels = make(map[string]string)
+		}
+		c.ContainerLabels[labels.LabelSSEPath] = ssePath
+	}
+	return c, nil
+}
+
+// validateEndpointPath checks that a custom endpoint path is an absolute URL path
+// which does not collide with the other endpoints of the proxy
+func validateEndpointPath(endpointPath string) error {
+	if endpointPath == "" {
+		return nil
+	}
+	if !strings.HasPrefix(endpointPath, "/") {
+		return fmt.Errorf("invalid endpoint path: %s (must start with /)", endpointPath)
+	}
+	if strings.ContainsAny(endpointPath, "?# ") {
+		return fmt.Errorf("invalid endpoint path: %s (must not contain a query, fragment or spaces)", endpointPath)
+	}
+	if endpointPath == "/" || endpointPath == "/health" || endpointPath == "/metrics" {
+		return fmt.Errorf("invalid endpoint path: %s (reserved by the proxy)", endpointPath)
+	}
+	return nil
+}
+
+// WithLabels parses and adds user-defined labels to the container labels
+// Labels are in the format "key=value". Standard ToolHive labels cannot be overridden.
+func (c *RunConfig) WithLabels(labelStrings []string) (*RunConfig, error) {
+	if c.ContainerLabels == nil {
+		c.ContainerLabels = make(map[string]string)
+	}
+
+	for _, label := range labelStrings {
+		key, value, ok := strings.Cut(label, "=")
+		if !ok || key == "" {
+			return c, fmt.Errorf("invalid label format: %s (expected key=value)", label)
+		}
+		if labels.IsStandardLabel(key) {
+			return c, fmt.Errorf("label %s is reserved for ToolHive", key)
+		}
+		c.ContainerLabels[key] = value
+	}
+
+	return c, nil
+}
+
+// WithOverrides applies overrides in the format "key=value" to the configuration, where key is one of
+// env.<NAME> (environment variable), label.<KEY> (user-defined label), image or host
+func (c *RunConfig) WithOverrides(overrides []string) (*RunConfig, error) {
+	for _, override := range overrides {
+		key, value, ok := strings.Cut(override, "=")
+		if !ok || key == "" {
+			return c, fmt.Errorf("invalid override format: %s (expected key=value)", override)
+		}
+
+		var err error
+		switch {
+		case strings.HasPrefix(key, "env."):
+			_, err = c.WithEnvironmentVariables([]string{strings.TrimPrefix(key, "env.") + "=" + value})
+		case strings.HasPrefix(key, "label."):
+			_, err = c.WithLabels([]string{strings.TrimPrefix(key, "label.") + "=" + value})
+		case key == "image" && value != "":
+			c.Image = value
+		case key == "host" && value != "":
+			c.Host = value
+		default:
+			err = fmt.Errorf("unsupported override: %s (expected env.<NAME>, label.<KEY>, image or host)", key)
+		}
+		if err != nil {
+			return c, err
+		}
+	}
+	return c, nil
+}
+
+// DeployWorkloadOptions builds the base workload options for the MCP server container
+func (c *RunConfig) DeployWorkloadOptions() *rt.DeployWorkloadOptions {
+	options := rt.NewDeployWorkloadOptions()
+	options.K8sPodTemplatePatch = c.K8sPodTemplatePatch
+
+	if c.LogDriver != "" || len(c.LogOptions) > 0 {
+		options.LogConfig = &rt.LogConfig{
+			Driver:  c.LogDriver,
+			Options: c.LogOptions,
+		}
+	}
+
+	options.StopTimeout = time.Duration(c.StopTimeout) * time.Second
+	options.StopSignal = c.StopSignal
+
+	if c.CPUs > 0 || c.Memory > 0 || c.PidsLimit > 0 {
+		options.Resources = &rt.ResourceLimits{
+			CPUs:       c.CPUs,
+			Memory:     c.Memory,
+			MemorySwap: c.MemorySwap,
+			PidsLimit:  c.PidsLimit,
+		}
+	}
+	options.GPUs = c.GPUs
+	options.Devices = c.Devices
+	options.RestartPolicy = c.RestartPolicy
+	options.Networks = c.Networks
+
+	return options
+}
+
 // WithSecrets processes secrets and adds them to environment variables
 func (c *RunConfig) WithSecrets(ctx context.Context, secretManager secrets.Provider) (*RunConfig, error) {
 	if len(c.Secrets) == 0 {
@@ -416,6 +999,45 @@ func (c *RunConfig) WithSecrets(ctx context.Context, secretManager secrets.Provi
 	return c, nil
 }
 
+// WithExpandedReferences resolves ${VAR} and ${secret:name} references in the environment variables
+// and arguments of the server. The secret manager is only used if secrets are referenced and may be nil otherwise.
+// NOTE: The references must be resolved after the run config is saved, so the state store keeps the references
+// rather than the resolved values.
+func (c *RunConfig) WithExpandedReferences(ctx context.Context, secretManager secrets.Provider) (*RunConfig, error) {
+	for key, value := range c.EnvVars {
+		expanded, err := environment.ExpandReferences(ctx, value, secretManager)
+		if err != nil {
+			return c, fmt.Errorf("failed to expand environment variable %s: %w", key, err)
+		}
+		c.EnvVars[key] = expanded
+	}
+
+	for i, arg := range c.CmdArgs {
+		expanded, err := environment.ExpandReferences(ctx, arg, secretManager)
+		if err != nil {
+			return c, fmt.Errorf("failed to expand argument %d: %w", i+1, err)
+		}
+		c.CmdArgs[i] = expanded
+	}
+
+	return c, nil
+}
+
+// ReferencesSecrets returns true if the environment variables or arguments of the server reference secrets
+func (c *RunConfig) ReferencesSecrets() bool {
+	for _, value := range c.EnvVars {
+		if environment.HasSecretReferences(value) {
+			return true
+		}
+	}
+	for _, arg := range c.CmdArgs {
+		if environment.HasSecretReferences(arg) {
+			return true
+		}
+	}
+	return false
+}
+
 // WithContainerName generates container name if not already set
 func (c *RunConfig) WithContainerName() *RunConfig {
 	if c.ContainerName == "" && c.Image != "" {
@@ -437,6 +1059,9 @@ func (c *RunConfig) WithStandardLabels() *RunConfig {
 		containerName = c.Name
 	}
 	labels.AddStandardLabels(c.ContainerLabels, containerName, c.BaseName, string(c.Transport), c.Port)
+	if c.PermissionProfileNameOrPath != "" {
+		labels.SetPermissionProfile(c.ContainerLabels, c.PermissionProfileNameOrPath)
+	}
 	return c
 }
 
@@ -491,8 +1116,15 @@ func (c *RunConfig) ProcessVolumeMounts() error {
 			return fmt.Errorf("invalid volume format: %s (%v)", volume, err)
 		}
 
+		if err := c.validateVolumeMount(mount, source, readOnly); err != nil {
+			return fmt.Errorf("invalid volume: %s (%v)", volume, err)
+		}
+
 		// Check for duplicate mount target
 		if existingSource, isDuplicate := existingMounts[target]; isDuplicate {
+			if existingSource != source {
+				return fmt.Errorf("invalid volume: %s (%s is already mounted from %s)", volume, target, existingSource)
+			}
 			logger.Warnf("Skipping duplicate mount target: %s (already mounted from %s)",
 				target, existingSource)
 			continue
@@ -516,6 +1148,40 @@ func (c *RunConfig) ProcessVolumeMounts() error {
 	return nil
 }
 
+// validateVolumeMount checks that a volume mount neither exposes a sensitive host path nor needs more access
+// than the permission profile allows
+func (c *RunConfig) validateVolumeMount(mount permissions.MountDeclaration, source string, readOnly bool) error {
+	// Sensitive host paths are rejected, unlike in a permission profile, where they are reviewed up front
+	if !mount.IsResourceURI() && permissions.IsSensitiveHostPath(source) {
+		return fmt.Errorf("mounting %s exposes a sensitive part of the host", source)
+	}
+
+	if !c.PermissionProfile.AllowsMount(mount, readOnly) {
+		access := "write"
+		if readOnly {
+			access = "read"
+		}
+		return fmt.Errorf("the permission profile does not allow %s access to %s", access, source)
+	}
+	return nil
+}
+
+// validateK8sPodPatch checks that a Kubernetes pod template patch is a JSON object, so an invalid patch
+// is reported before anything is deployed. The patch only applies to the Kubernetes runtime.
+func validateK8sPodPatch(patch string) error {
+	if patch == "" {
+		return nil
+	}
+	var object map[string]any
+	if err := json.Unmarshal([]byte(patch), &object); err != nil {
+		return fmt.Errorf("invalid Kubernetes pod template patch (expected a JSON object): %v", err)
+	}
+	if !container.IsKubernetesRuntime() {
+		logger.Warnf("Ignoring the Kubernetes pod template patch, since the runtime is not Kubernetes")
+	}
+	return nil
+}
+
 // validateConfig ensures the RunConfig is valid and sets up some of the final
 // configuration details which can only be applied after all other flags are added.
 // This function also handles setting missing values based on the image metadata (if present).
@@ -557,7 +1223,8 @@ func (c *RunConfig) validateConfig(
 	// Use registry target port if not overridden and if the mcpTransport is HTTP-based.
 	if imageMetadata != nil {
 		isHTTPServer := mcpTransport == types.TransportTypeSSE.String() ||
-			mcpTransport == types.TransportTypeStreamableHTTP.String()
+			mcpTransport == types.TransportTypeStreamableHTTP.String() ||
+			mcpTransport == types.TransportTypeWebSocket.String()
 		if targetPort == 0 && isHTTPServer && imageMetadata.TargetPort > 0 {
 			logger.Debugf("Using registry target port: %d", imageMetadata.TargetPort)
 			targetPort = imageMetadata.TargetPort
@@ -616,7 +1283,7 @@ func (c *RunConfig) validateConfig(
 
 	// Prepend registry args to command-line args if available
 	if imageMetadata != nil && len(imageMetadata.Args) > 0 {
-		logger.Debugf("Prepending registry args: %v", imageMetadata.Args)
+		logger.Debugf("Prepending registry args: %v", environment.MaskArgs(imageMetadata.Args))
 		c.CmdArgs = append(c.CmdArgs, imageMetadata.Args...)
 	}
 
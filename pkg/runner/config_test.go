@@ -3,16 +3,23 @@ package runner
 import (
 	"bytes"
 	"context"
+	"encoding/json"
 	"fmt"
+	"net"
 	"os"
+	"path/filepath"
 	"strings"
 	"testing"
+	"time"
 
 	"github.com/stretchr/testify/assert"
 	"github.com/stretchr/testify/require"
 
+	"github.com/stacklok/toolhive/pkg/auth"
 	"github.com/stacklok/toolhive/pkg/authz"
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
 	"github.com/stacklok/toolhive/pkg/container/runtime/mocks"
+	"github.com/stacklok/toolhive/pkg/labels"
 	"github.com/stacklok/toolhive/pkg/logger"
 	"github.com/stacklok/toolhive/pkg/permissions"
 	"github.com/stacklok/toolhive/pkg/registry"
@@ -302,35 +309,59 @@ func TestRunConfig_ProcessVolumeMounts(t *testing.T) {
 			expectError: true,
 		},
 		{
-			name: "Volumes without permission profile but with profile name",
+			name: "Volumes with a profile name whose profile allows no paths",
 			config: &RunConfig{
 				Volumes:                     []string{"/host:/container"},
 				PermissionProfileNameOrPath: permissions.ProfileNone,
 			},
-			expectError:         false,
-			expectedReadMounts:  0,
-			expectedWriteMounts: 1,
+			expectError: true,
 		},
 		{
-			name: "Read-only volume with existing profile",
+			name: "Read-only volume of a read path of the profile",
 			config: &RunConfig{
 				Volumes:           []string{"/host:/container:ro"},
-				PermissionProfile: permissions.BuiltinNoneProfile(),
+				PermissionProfile: &permissions.Profile{Read: []permissions.MountDeclaration{"/host"}},
 			},
 			expectError:         false,
 			expectedReadMounts:  1,
 			expectedWriteMounts: 0,
 		},
 		{
-			name: "Read-write volume with existing profile",
+			name: "Read-only volume under a write path of the profile",
 			config: &RunConfig{
-				Volumes:           []string{"/host:/container"},
-				PermissionProfile: permissions.BuiltinNoneProfile(),
+				Volumes:           []string{"/host/project:/project:ro"},
+				PermissionProfile: &permissions.Profile{Write: []permissions.MountDeclaration{"/host"}},
+			},
+			expectError:         false,
+			expectedReadMounts:  1,
+			expectedWriteMounts: 0,
+		},
+		{
+			name: "Read-write volume under a write path of the profile",
+			config: &RunConfig{
+				Volumes:           []string{"/host/project:/project"},
+				PermissionProfile: &permissions.Profile{Write: []permissions.MountDeclaration{"/host"}},
 			},
 			expectError:         false,
 			expectedReadMounts:  0,
 			expectedWriteMounts: 1,
 		},
+		{
+			name: "Read-write volume of a read path of the profile",
+			config: &RunConfig{
+				Volumes:           []string{"/host:/container"},
+				PermissionProfile: &permissions.Profile{Read: []permissions.MountDeclaration{"/host"}},
+			},
+			expectError: true,
+		},
+		{
+			name: "Volume outside the paths of the profile",
+			config: &RunConfig{
+				Volumes:           []string{"/hostile:/container:ro"},
+				PermissionProfile: &permissions.Profile{Write: []permissions.MountDeclaration{"/host"}},
+			},
+			expectError: true,
+		},
 		{
 			name: "Multiple volumes with existing profile",
 			config: &RunConfig{
@@ -339,7 +370,10 @@ func TestRunConfig_ProcessVolumeMounts(t *testing.T) {
 					"/host2:/container2",
 					"/host3:/container3:ro",
 				},
-				PermissionProfile: permissions.BuiltinNoneProfile(),
+				PermissionProfile: &permissions.Profile{
+					Read:  []permissions.MountDeclaration{"/host1", "/host3"},
+					Write: []permissions.MountDeclaration{"/host2"},
+				},
 			},
 			expectError:         false,
 			expectedReadMounts:  2,
@@ -353,6 +387,45 @@ func TestRunConfig_ProcessVolumeMounts(t *testing.T) {
 			},
 			expectError: true,
 		},
+		{
+			name: "Sensitive host path",
+			config: &RunConfig{
+				Volumes:           []string{"/var/run/docker.sock:/var/run/docker.sock"},
+				PermissionProfile: &permissions.Profile{Write: []permissions.MountDeclaration{"/var/run/docker.sock"}},
+			},
+			expectError: true,
+		},
+		{
+			name: "Path under a sensitive host path",
+			config: &RunConfig{
+				Volumes:           []string{"/etc/shadow:/shadow:ro"},
+				PermissionProfile: &permissions.Profile{Read: []permissions.MountDeclaration{"/etc"}},
+			},
+			expectError: true,
+		},
+		{
+			name: "Volume conflicting with a profile mount",
+			config: &RunConfig{
+				Volumes: []string{"/other:/data"},
+				PermissionProfile: &permissions.Profile{
+					Read:  []permissions.MountDeclaration{"/host:/data"},
+					Write: []permissions.MountDeclaration{"/other"},
+				},
+			},
+			expectError: true,
+		},
+		{
+			name: "Volume duplicating a profile mount",
+			config: &RunConfig{
+				Volumes: []string{"/host:/data:ro"},
+				PermissionProfile: &permissions.Profile{
+					Read: []permissions.MountDeclaration{"/host:/data"},
+				},
+			},
+			expectError:         false,
+			expectedReadMounts:  0,
+			expectedWriteMounts: 0,
+		},
 	}
 
 	for _, tc := range testCases {
@@ -388,6 +461,40 @@ func TestRunConfig_ProcessVolumeMounts(t *testing.T) {
 	}
 }
 
+func TestRunConfig_ProcessVolumeMounts_SymlinkToSensitivePath(t *testing.T) {
+	t.Parallel()
+
+	// A link in an allowed directory must not give access to the sensitive path it points to
+	dir := t.TempDir()
+	link := filepath.Join(dir, "config")
+	require.NoError(t, os.Symlink("/etc", link))
+
+	config := &RunConfig{
+		Volumes:           []string{link + ":/config:ro"},
+		PermissionProfile: &permissions.Profile{Read: []permissions.MountDeclaration{permissions.MountDeclaration(dir)}},
+	}
+	err := config.ProcessVolumeMounts()
+	require.Error(t, err)
+	assert.Contains(t, err.Error(), "sensitive")
+}
+
+func TestRunConfig_WithPorts_InUse(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	require.NoError(t, err)
+	defer listener.Close()
+	port := listener.Addr().(*net.TCPAddr).Port
+
+	config := &RunConfig{Transport: types.TransportTypeSSE, Host: "127.0.0.1"}
+	_, err = config.WithPorts(port, 0)
+	assert.ErrorContains(t, err, fmt.Sprintf("port %d is already in use", port))
+
+	_, err = config.WithPorts(0, port)
+	assert.ErrorContains(t, err, "target port error")
+}
+
 func TestRunConfig_WithEnvironmentVariables(t *testing.T) {
 	t.Parallel()
 	testCases := []struct {
@@ -1007,3 +1114,805 @@ func TestCommaSeparatedEnvVars(t *testing.T) {
 		})
 	}
 }
+
+func TestRunConfig_WithLogConfig(t *testing.T) {
+	t.Parallel()
+	testCases := []struct {
+		name        string
+		driver      string
+		options     []string
+		expectError bool
+		expected    map[string]string
+	}{
+		{
+			name:     "No log configuration",
+			driver:   "",
+			options:  nil,
+			expected: nil,
+		},
+		{
+			name:     "Driver with options",
+			driver:   "json-file",
+			options:  []string{"max-size=10m", "max-file=3"},
+			expected: map[string]string{"max-size": "10m", "max-file": "3"},
+		},
+		{
+			name:     "Option value containing equals sign",
+			driver:   "syslog",
+			options:  []string{"tag=a=b"},
+			expected: map[string]string{"tag": "a=b"},
+		},
+		{
+			name:        "Invalid option format",
+			driver:      "json-file",
+			options:     []string{"max-size"},
+			expectError: true,
+		},
+		{
+			name:        "Missing option key",
+			driver:      "json-file",
+			options:     []string{"=10m"},
+			expectError: true,
+		},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			config := &RunConfig{}
+			result, err := config.WithLogConfig(tc.driver, tc.options)
+
+			if tc.expectError {
+				assert.Error(t, err)
+				return
+			}
+
+			require.NoError(t, err)
+			assert.Equal(t, config, result)
+			assert.Equal(t, tc.driver, config.LogDriver)
+			assert.Equal(t, tc.expected, config.LogOptions)
+		})
+	}
+}
+
+func TestRunConfig_DeployWorkloadOptions(t *testing.T) {
+	t.Parallel()
+
+	t.Run("Defaults", func(t *testing.T) {
+		t.Parallel()
+		config := &RunConfig{K8sPodTemplatePatch: `{"spec":{}}`}
+		options := config.DeployWorkloadOptions()
+
+		assert.Equal(t, `{"spec":{}}`, options.K8sPodTemplatePatch)
+		assert.NotNil(t, options.ExposedPorts)
+		assert.NotNil(t, options.PortBindings)
+		assert.Nil(t, options.LogConfig)
+	})
+
+	t.Run("Log configuration", func(t *testing.T) {
+		t.Parallel()
+		config := &RunConfig{
+			LogDriver:  "local",
+			LogOptions: map[string]string{"max-size": "20m"},
+		}
+		options := config.DeployWorkloadOptions()
+
+		require.NotNil(t, options.LogConfig)
+		assert.Equal(t, "local", options.LogConfig.Driver)
+		assert.Equal(t, map[string]string{"max-size": "20m"}, options.LogConfig.Options)
+	})
+
+	t.Run("Stop configuration", func(t *testing.T) {
+		t.Parallel()
+		config := &RunConfig{StopTimeout: 120, StopSignal: "SIGINT"}
+		options := config.DeployWorkloadOptions()
+
+		assert.Equal(t, 2*time.Minute, options.StopTimeout)
+		assert.Equal(t, "SIGINT", options.StopSignal)
+		assert.Nil(t, options.Resources)
+	})
+
+	t.Run("Resource limits", func(t *testing.T) {
+		t.Parallel()
+		config := &RunConfig{CPUs: 0.5, Memory: 512 * 1024 * 1024, PidsLimit: 100}
+		options := config.DeployWorkloadOptions()
+
+		require.NotNil(t, options.Resources)
+		assert.Equal(t, rt.ResourceLimits{CPUs: 0.5, Memory: 512 * 1024 * 1024, PidsLimit: 100}, *options.Resources)
+	})
+}
+
+func TestRunConfig_WithExpandedReferences(t *testing.T) {
+	t.Setenv("THV_TEST_WORKSPACE", "/home/user/project")
+
+	config := &RunConfig{
+		EnvVars: map[string]string{
+			"WORKSPACE": "${THV_TEST_WORKSPACE}",
+			"TOKEN":     "${secret:github}",
+			"LITERAL":   "$${THV_TEST_WORKSPACE}",
+		},
+		CmdArgs: []string{"--root", "${THV_TEST_WORKSPACE}/src"},
+	}
+	assert.True(t, config.ReferencesSecrets())
+
+	secretManager := &mockSecretManager{secrets: map[string]string{"github": "ghp_secret"}}
+	_, err := config.WithExpandedReferences(t.Context(), secretManager)
+	require.NoError(t, err)
+	assert.Equal(t, map[string]string{
+		"WORKSPACE": "/home/user/project",
+		"TOKEN":     "ghp_secret",
+		"LITERAL":   "${THV_TEST_WORKSPACE}",
+	}, config.EnvVars)
+	assert.Equal(t, []string{"--root", "/home/user/project/src"}, config.CmdArgs)
+
+	unset := &RunConfig{EnvVars: map[string]string{"VALUE": "${THV_TEST_UNSET_VARIABLE}"}}
+	assert.False(t, unset.ReferencesSecrets())
+	_, err = unset.WithExpandedReferences(t.Context(), nil)
+	assert.Error(t, err)
+}
+
+func TestRunConfig_WithStopConfig(t *testing.T) {
+	t.Parallel()
+
+	config := &RunConfig{}
+	result, err := config.WithStopConfig(60, "SIGINT")
+	require.NoError(t, err)
+	assert.Equal(t, 60, result.StopTimeout)
+	assert.Equal(t, "SIGINT", result.StopSignal)
+
+	_, err = (&RunConfig{}).WithStopConfig(-1, "")
+	assert.Error(t, err)
+}
+
+func TestRunConfig_WithResourceLimits(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name               string
+		cpus               float64
+		memory             string
+		memorySwap         string
+		pidsLimit          int64
+		expectedMemory     int64
+		expectedMemorySwap int64
+		expectError        bool
+	}{
+		{name: "No limits"},
+		{name: "CPUs and processes", cpus: 1.5, pidsLimit: 256},
+		{name: "Memory", memory: "512m", expectedMemory: 512 * 1024 * 1024},
+		{
+			name:               "Memory and swap",
+			memory:             "1g",
+			memorySwap:         "2g",
+			expectedMemory:     1024 * 1024 * 1024,
+			expectedMemorySwap: 2 * 1024 * 1024 * 1024,
+		},
+		{name: "Unlimited swap", memory: "1g", memorySwap: "-1", expectedMemory: 1024 * 1024 * 1024, expectedMemorySwap: -1},
+		{name: "Negative CPUs", cpus: -1, expectError: true},
+		{name: "Negative processes", pidsLimit: -1, expectError: true},
+		{name: "Invalid memory", memory: "lots", expectError: true},
+		{name: "Swap without memory", memorySwap: "1g", expectError: true},
+		{name: "Swap lower than memory", memory: "2g", memorySwap: "1g", expectError: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			config, err := (&RunConfig{}).WithResourceLimits(tt.cpus, tt.memory, tt.memorySwap, tt.pidsLimit)
+			if tt.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.cpus, config.CPUs)
+			assert.Equal(t, tt.expectedMemory, config.Memory)
+			assert.Equal(t, tt.expectedMemorySwap, config.MemorySwap)
+			assert.Equal(t, tt.pidsLimit, config.PidsLimit)
+		})
+	}
+}
+
+func TestRunConfig_WithProxyLimits(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name           string
+		connectTimeout time.Duration
+		idleTimeout    time.Duration
+		sseReadTimeout time.Duration
+		maxMessageSize string
+		expected       types.ProxyLimits
+		expectError    bool
+	}{
+		{name: "No limits"},
+		{
+			name:           "All limits",
+			connectTimeout: 5 * time.Second,
+			idleTimeout:    time.Minute,
+			sseReadTimeout: 10 * time.Minute,
+			maxMessageSize: "4m",
+			expected: types.ProxyLimits{
+				ConnectTimeout: 5 * time.Second,
+				IdleTimeout:    time.Minute,
+				SSEReadTimeout: 10 * time.Minute,
+				MaxMessageSize: 4 * 1024 * 1024,
+			},
+		},
+		{name: "Size in bytes", maxMessageSize: "1024", expected: types.ProxyLimits{MaxMessageSize: 1024}},
+		{name: "Negative timeout", idleTimeout: -time.Second, expectError: true},
+		{name: "Invalid size", maxMessageSize: "big", expectError: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			config, err := (&RunConfig{}).WithProxyLimits(tt.connectTimeout, tt.idleTimeout, tt.sseReadTimeout, tt.maxMessageSize)
+			if tt.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.expected, config.ProxyLimits)
+		})
+	}
+}
+
+func TestRunConfig_WithDevices(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name        string
+		gpus        string
+		devices     []string
+		expectError bool
+	}{
+		{name: "No GPUs or devices"},
+		{name: "All GPUs", gpus: "all"},
+		{name: "GPUs by ID and devices", gpus: "device=0,1", devices: []string{"/dev/dri", "nvidia.com/gpu=0"}},
+		{name: "Invalid GPUs", gpus: "many", expectError: true},
+		{name: "Invalid device", devices: []string{"dri"}, expectError: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			config, err := (&RunConfig{}).WithDevices(tt.gpus, tt.devices)
+			if tt.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.gpus, config.GPUs)
+			assert.Equal(t, tt.devices, config.Devices)
+
+			options := config.DeployWorkloadOptions()
+			assert.Equal(t, tt.gpus, options.GPUs)
+			assert.Equal(t, tt.devices, options.Devices)
+		})
+	}
+}
+
+func TestRunConfig_WithRestartPolicy(t *testing.T) {
+	t.Parallel()
+
+	config, err := (&RunConfig{}).WithRestartPolicy("on-failure:3")
+	require.NoError(t, err)
+	assert.Equal(t, "on-failure:3", config.RestartPolicy)
+	assert.Equal(t, "on-failure:3", config.DeployWorkloadOptions().RestartPolicy)
+
+	config, err = (&RunConfig{}).WithRestartPolicy("")
+	require.NoError(t, err)
+	assert.Empty(t, config.DeployWorkloadOptions().RestartPolicy)
+
+	_, err = (&RunConfig{}).WithRestartPolicy("sometimes")
+	assert.Error(t, err)
+}
+
+func TestRunConfig_WithNetworks(t *testing.T) {
+	t.Parallel()
+
+	restrictedProfile := &permissions.Profile{
+		Network: &permissions.NetworkPermissions{
+			Outbound: &permissions.OutboundNetworkPermissions{AllowHost: []string{"postgres"}, AllowPort: []int{5432}},
+		},
+	}
+
+	tests := []struct {
+		name           string
+		networks       []string
+		profile        *permissions.Profile
+		isolateNetwork bool
+		expectError    bool
+	}{
+		{name: "No networks", profile: permissions.BuiltinNoneProfile()},
+		{name: "Network profile", networks: []string{"compose_default", "databases"}, profile: permissions.BuiltinNetworkProfile()},
+		{name: "Restricted profile", networks: []string{"compose_default"}, profile: restrictedProfile},
+		{
+			name:           "Network profile with network isolation",
+			networks:       []string{"compose_default"},
+			profile:        permissions.BuiltinNetworkProfile(),
+			isolateNetwork: true,
+		},
+		{
+			name:           "Restricted profile with network isolation",
+			networks:       []string{"compose_default"},
+			profile:        restrictedProfile,
+			isolateNetwork: true,
+			expectError:    true,
+		},
+		{name: "None profile", networks: []string{"compose_default"}, profile: permissions.BuiltinNoneProfile(), expectError: true},
+		{name: "No profile", networks: []string{"compose_default"}, expectError: true},
+		{name: "Host network", networks: []string{"host"}, profile: permissions.BuiltinNetworkProfile(), expectError: true},
+		{name: "Empty name", networks: []string{""}, profile: permissions.BuiltinNetworkProfile(), expectError: true},
+		{
+			name:        "ToolHive network",
+			networks:    []string{"toolhive-external"},
+			profile:     permissions.BuiltinNetworkProfile(),
+			expectError: true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			config := &RunConfig{PermissionProfile: tt.profile, IsolateNetwork: tt.isolateNetwork}
+			_, err := config.WithNetworks(tt.networks)
+			if tt.expectError {
+				assert.Error(t, err)
+				assert.Empty(t, config.Networks)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.networks, config.Networks)
+			assert.Equal(t, tt.networks, config.DeployWorkloadOptions().Networks)
+		})
+	}
+}
+
+func TestRunConfig_WithEndpointPaths(t *testing.T) {
+	t.Parallel()
+	testCases := []struct {
+		name         string
+		transport    types.TransportType
+		ssePath      string
+		messagesPath string
+		expectError  bool
+	}{
+		{
+			name:      "No custom paths",
+			transport: types.TransportTypeSSE,
+		},
+		{
+			name:         "Custom paths for stdio",
+			transport:    types.TransportTypeStdio,
+			ssePath:      "/events",
+			messagesPath: "/message",
+		},
+		{
+			name:      "Only SSE path",
+			transport: types.TransportTypeStdio,
+			ssePath:   "/mcp/sse",
+		},
+		{
+			name:        "Custom paths for SSE transport",
+			transport:   types.TransportTypeSSE,
+			ssePath:     "/events",
+			expectError: true,
+		},
+		{
+			name:        "Relative path",
+			transport:   types.TransportTypeStdio,
+			ssePath:     "events",
+			expectError: true,
+		},
+		{
+			name:         "Path with query",
+			transport:    types.TransportTypeStdio,
+			messagesPath: "/message?x=1",
+			expectError:  true,
+		},
+		{
+			name:         "Reserved path",
+			transport:    types.TransportTypeStdio,
+			messagesPath: "/health",
+			expectError:  true,
+		},
+		{
+			name:         "Same path for both endpoints",
+			transport:    types.TransportTypeStdio,
+			ssePath:      "/mcp",
+			messagesPath: "/mcp",
+			expectError:  true,
+		},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			config := &RunConfig{Transport: tc.transport}
+			result, err := config.WithEndpointPaths(tc.ssePath, tc.messagesPath)
+			if tc.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tc.ssePath, result.SSEPath)
+			assert.Equal(t, tc.messagesPath, result.MessagesPath)
+			if tc.ssePath != "" {
+				assert.Equal(t, tc.ssePath, result.ContainerLabels["toolhive-sse-path"])
+			}
+		})
+	}
+}
+
+func TestRunConfig_WithTLS(t *testing.T) {
+	t.Parallel()
+	testCases := []struct {
+		name        string
+		certFile    string
+		keyFile     string
+		selfSigned  bool
+		expectTLS   bool
+		expectError bool
+	}{
+		{
+			name: "No TLS",
+		},
+		{
+			name:      "Certificate and key",
+			certFile:  "/certs/server.crt",
+			keyFile:   "/certs/server.key",
+			expectTLS: true,
+		},
+		{
+			name:       "Self-signed certificate",
+			selfSigned: true,
+			expectTLS:  true,
+		},
+		{
+			name:        "Certificate without key",
+			certFile:    "/certs/server.crt",
+			expectError: true,
+		},
+		{
+			name:        "Key without certificate",
+			keyFile:     "/certs/server.key",
+			expectError: true,
+		},
+		{
+			name:        "Certificate and self-signed certificate",
+			certFile:    "/certs/server.crt",
+			keyFile:     "/certs/server.key",
+			selfSigned:  true,
+			expectError: true,
+		},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			config := &RunConfig{}
+			result, err := config.WithTLS(tc.certFile, tc.keyFile, tc.selfSigned)
+			if tc.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tc.expectTLS, result.IsTLS())
+			assert.Equal(t, tc.expectTLS, labels.IsTLS(result.ContainerLabels))
+		})
+	}
+}
+
+func TestRunConfig_WithAuthToken(t *testing.T) {
+	t.Parallel()
+	tokenFile := filepath.Join(t.TempDir(), "token")
+	require.NoError(t, os.WriteFile(tokenFile, []byte("file-secret"), 0600))
+
+	testCases := []struct {
+		name           string
+		token          string
+		oidc           bool
+		expected       string
+		expectedStatic string
+		expectError    bool
+	}{
+		{
+			name: "No token",
+		},
+		{
+			name:           "Literal token",
+			token:          "secret",
+			expected:       auth.StaticTokenEnvReference,
+			expectedStatic: "secret",
+		},
+		{
+			name:     "Token file",
+			token:    "file:" + tokenFile,
+			expected: "file:" + tokenFile,
+		},
+		{
+			name:        "Missing token file",
+			token:       "file:/nonexistent/token",
+			expectError: true,
+		},
+		{
+			name:        "Token with OIDC validation",
+			token:       "secret",
+			oidc:        true,
+			expectError: true,
+		},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			config := &RunConfig{}
+			if tc.oidc {
+				config.OIDCConfig = &auth.TokenValidatorConfig{Issuer: "https://issuer.example.com"}
+			}
+			result, err := config.WithAuthToken(tc.token)
+			if tc.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tc.expected, result.AuthToken)
+			assert.Equal(t, tc.expectedStatic, result.StaticToken)
+
+			// The token itself is never saved with the state of the server
+			data, err := json.Marshal(result)
+			require.NoError(t, err)
+			assert.NotContains(t, string(data), "secret")
+		})
+	}
+}
+
+func TestRunConfig_WithRateLimit(t *testing.T) {
+	t.Parallel()
+	testCases := []struct {
+		name        string
+		value       string
+		expectError bool
+	}{
+		{
+			name: "No rate limit",
+		},
+		{
+			name:  "Rate limit",
+			value: "100/min",
+		},
+		{
+			name:        "Invalid rate limit",
+			value:       "100",
+			expectError: true,
+		},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			config := &RunConfig{}
+			result, err := config.WithRateLimit(tc.value)
+			if tc.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tc.value, result.RateLimit)
+		})
+	}
+}
+
+func TestRunConfig_WithCORSOrigins(t *testing.T) {
+	t.Parallel()
+	testCases := []struct {
+		name        string
+		origins     []string
+		expectError bool
+	}{
+		{
+			name: "No origins",
+		},
+		{
+			name:    "Origins",
+			origins: []string{"https://app.example.com", "http://localhost:3000"},
+		},
+		{
+			name:    "Any origin",
+			origins: []string{"*"},
+		},
+		{
+			name:        "Origin with a path",
+			origins:     []string{"https://app.example.com/mcp"},
+			expectError: true,
+		},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			config := &RunConfig{}
+			result, err := config.WithCORSOrigins(tc.origins)
+			if tc.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tc.origins, result.CORSOrigins)
+		})
+	}
+}
+
+func TestRunConfig_WithLabels(t *testing.T) {
+	t.Parallel()
+	testCases := []struct {
+		name        string
+		labels      []string
+		expectError bool
+		expected    map[string]string
+	}{
+		{
+			name:     "No labels",
+			labels:   nil,
+			expected: map[string]string{},
+		},
+		{
+			name:     "Valid labels",
+			labels:   []string{"team=platform", "toolhive-spec-hash=abc"},
+			expected: map[string]string{"team": "platform", "toolhive-spec-hash": "abc"},
+		},
+		{
+			name:        "Invalid label format",
+			labels:      []string{"team"},
+			expectError: true,
+		},
+		{
+			name:        "Standard label cannot be overridden",
+			labels:      []string{"toolhive-name=other"},
+			expectError: true,
+		},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			config := &RunConfig{}
+			_, err := config.WithLabels(tc.labels)
+
+			if tc.expectError {
+				assert.Error(t, err)
+				return
+			}
+
+			require.NoError(t, err)
+			assert.Equal(t, tc.expected, config.ContainerLabels)
+		})
+	}
+}
+
+func TestRunConfig_WithOverrides(t *testing.T) {
+	t.Parallel()
+	testCases := []struct {
+		name        string
+		overrides   []string
+		expectError bool
+		check       func(t *testing.T, config *RunConfig)
+	}{
+		{
+			name:      "Environment variable",
+			overrides: []string{"env.LOG_LEVEL=debug", "env.API_URL=https://staging.example.com"},
+			check: func(t *testing.T, config *RunConfig) {
+				t.Helper()
+				assert.Equal(t, "debug", config.EnvVars["LOG_LEVEL"])
+				assert.Equal(t, "https://staging.example.com", config.EnvVars["API_URL"])
+				assert.Equal(t, "production", config.EnvVars["STAGE"])
+			},
+		},
+		{
+			name:      "Label, image and host",
+			overrides: []string{"label.stage=staging", "image=example/fetch:v2", "host=0.0.0.0"},
+			check: func(t *testing.T, config *RunConfig) {
+				t.Helper()
+				assert.Equal(t, "staging", config.ContainerLabels["stage"])
+				assert.Equal(t, "example/fetch:v2", config.Image)
+				assert.Equal(t, "0.0.0.0", config.Host)
+			},
+		},
+		{
+			name:        "Invalid format",
+			overrides:   []string{"env.LOG_LEVEL"},
+			expectError: true,
+		},
+		{
+			name:        "Unsupported key",
+			overrides:   []string{"port=9001"},
+			expectError: true,
+		},
+		{
+			name:        "Empty image",
+			overrides:   []string{"image="},
+			expectError: true,
+		},
+		{
+			name:        "Standard label",
+			overrides:   []string{"label.toolhive-name=other"},
+			expectError: true,
+		},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			config := &RunConfig{
+				Image:     "example/fetch:v1",
+				Host:      "127.0.0.1",
+				Transport: types.TransportTypeSSE,
+				EnvVars:   map[string]string{"STAGE": "production"},
+			}
+			_, err := config.WithOverrides(tc.overrides)
+
+			if tc.expectError {
+				assert.Error(t, err)
+				return
+			}
+
+			require.NoError(t, err)
+			tc.check(t, config)
+		})
+	}
+}
+
+func TestValidateK8sPodPatch(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	tests := []struct {
+		name        string
+		patch       string
+		expectError bool
+	}{
+		{name: "No patch", patch: ""},
+		{name: "Pod template", patch: `{"spec":{"nodeSelector":{"disktype":"ssd"}}}`},
+		{name: "Invalid JSON", patch: `{"spec":`, expectError: true},
+		{name: "Not an object", patch: `["spec"]`, expectError: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			err := validateK8sPodPatch(tt.patch)
+			if tt.expectError {
+				assert.Error(t, err)
+			} else {
+				assert.NoError(t, err)
+			}
+		})
+	}
+}
+
+func TestRunConfig_WithListenSocket(t *testing.T) {
+	t.Parallel()
+
+	config, err := (&RunConfig{}).WithListenSocket("")
+	require.NoError(t, err)
+	assert.Empty(t, config.ListenSocket)
+
+	config, err = (&RunConfig{}).WithListenSocket("/tmp/toolhive/fetch.sock")
+	require.NoError(t, err)
+	assert.Equal(t, "/tmp/toolhive/fetch.sock", config.ListenSocket)
+	assert.Equal(t, "/tmp/toolhive/fetch.sock", labels.GetListenSocket(config.ContainerLabels))
+
+	// Relative paths are resolved against the working directory
+	config, err = (&RunConfig{}).WithListenSocket("fetch.sock")
+	require.NoError(t, err)
+	assert.True(t, filepath.IsAbs(config.ListenSocket))
+
+	_, err = (&RunConfig{}).WithListenSocket("/tmp/" + strings.Repeat("a", 200) + ".sock")
+	assert.Error(t, err)
+}
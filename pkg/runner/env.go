@@ -9,6 +9,7 @@ import (
 	"golang.org/x/term"
 
 	"github.com/stacklok/toolhive/pkg/config"
+	"github.com/stacklok/toolhive/pkg/environment"
 	"github.com/stacklok/toolhive/pkg/logger"
 	"github.com/stacklok/toolhive/pkg/registry"
 	"github.com/stacklok/toolhive/pkg/secrets"
@@ -305,6 +306,8 @@ func addAsEnvironmentVariable(
 	} else {
 		if envVar.Required {
 			logger.Debugf("Added environment variable: %s", envVar.Name)
+		} else if environment.IsSensitiveName(envVar.Name) {
+			logger.Debugf("Using default value for %s: %s", envVar.Name, environment.MaskedValue)
 		} else {
 			logger.Debugf("Using default value for %s: %s", envVar.Name, value)
 		}
@@ -0,0 +1,81 @@
+package runner
+
+import (
+	"strconv"
+	"strings"
+
+	"github.com/stacklok/toolhive/pkg/container/images"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+const (
+	// TransportLabel is the image label declaring the transport of the MCP server in the image
+	TransportLabel = "io.modelcontextprotocol.transport"
+	// PortLabel is the image label declaring the port on which the MCP server in the image listens
+	PortLabel = "io.modelcontextprotocol.port"
+)
+
+// ImageDefaults are the transport and target port declared by an image, used when they are not set explicitly
+type ImageDefaults struct {
+	// Transport is the transport of the MCP server, or empty if the image doesn't declare one
+	Transport string
+	// TargetPort is the port on which the MCP server listens, or zero if the image doesn't declare one
+	TargetPort int
+}
+
+// ImageDefaultsFromConfig reads the defaults declared by an image. The labels of the image take
+// precedence over its MCP_TRANSPORT and MCP_PORT environment variables. Invalid values are ignored.
+func ImageDefaultsFromConfig(config *images.ImageConfig) ImageDefaults {
+	var defaults ImageDefaults
+	if config == nil {
+		return defaults
+	}
+
+	env := make(map[string]string, len(config.Env))
+	for _, entry := range config.Env {
+		if key, value, found := strings.Cut(entry, "="); found {
+			env[key] = value
+		}
+	}
+
+	for _, transport := range []string{config.Labels[TransportLabel], env["MCP_TRANSPORT"]} {
+		if transport == "" {
+			continue
+		}
+		if parsed, err := types.ParseTransportType(transport); err != nil || parsed == types.TransportTypeInspector {
+			logger.Warnf("Ignoring unsupported transport %s declared by the image", transport)
+			continue
+		}
+		defaults.Transport = strings.ToLower(transport)
+		break
+	}
+
+	for _, port := range []string{config.Labels[PortLabel], env["MCP_PORT"]} {
+		if port == "" {
+			continue
+		}
+		parsed, err := strconv.Atoi(port)
+		if err != nil || parsed <= 0 || parsed > 65535 {
+			logger.Warnf("Ignoring invalid port %s declared by the image", port)
+			continue
+		}
+		defaults.TargetPort = parsed
+		break
+	}
+
+	return defaults
+}
+
+// Apply returns the transport and target port to use, where values which are not set are taken from the image
+func (d ImageDefaults) Apply(transport string, targetPort int) (string, int) {
+	if transport == "" && d.Transport != "" {
+		logger.Debugf("Using transport %s declared by the image", d.Transport)
+		transport = d.Transport
+	}
+	if targetPort == 0 && d.TargetPort > 0 {
+		logger.Debugf("Using target port %d declared by the image", d.TargetPort)
+		targetPort = d.TargetPort
+	}
+	return transport, targetPort
+}
@@ -0,0 +1,83 @@
+package runner
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/stacklok/toolhive/pkg/container/images"
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+func TestImageDefaultsFromConfig(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	tests := []struct {
+		name     string
+		config   *images.ImageConfig
+		expected ImageDefaults
+	}{
+		{
+			name:     "No image config",
+			expected: ImageDefaults{},
+		},
+		{
+			name:     "Labels",
+			config:   &images.ImageConfig{Labels: map[string]string{TransportLabel: "sse", PortLabel: "8080"}},
+			expected: ImageDefaults{Transport: "sse", TargetPort: 8080},
+		},
+		{
+			name:     "Environment variables",
+			config:   &images.ImageConfig{Env: []string{"PATH=/usr/bin", "MCP_TRANSPORT=streamable-http", "MCP_PORT=3000"}},
+			expected: ImageDefaults{Transport: "streamable-http", TargetPort: 3000},
+		},
+		{
+			name: "Labels take precedence over environment variables",
+			config: &images.ImageConfig{
+				Labels: map[string]string{TransportLabel: "SSE", PortLabel: "8080"},
+				Env:    []string{"MCP_TRANSPORT=stdio", "MCP_PORT=3000"},
+			},
+			expected: ImageDefaults{Transport: "sse", TargetPort: 8080},
+		},
+		{
+			name: "Invalid labels fall back to environment variables",
+			config: &images.ImageConfig{
+				Labels: map[string]string{TransportLabel: "websocket", PortLabel: "http"},
+				Env:    []string{"MCP_TRANSPORT=sse", "MCP_PORT=3000"},
+			},
+			expected: ImageDefaults{Transport: "sse", TargetPort: 3000},
+		},
+		{
+			name:     "Invalid values are ignored",
+			config:   &images.ImageConfig{Env: []string{"MCP_TRANSPORT=inspector", "MCP_PORT=70000"}},
+			expected: ImageDefaults{},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, ImageDefaultsFromConfig(tt.config))
+		})
+	}
+}
+
+func TestImageDefaults_Apply(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	defaults := ImageDefaults{Transport: "sse", TargetPort: 8080}
+
+	transport, targetPort := defaults.Apply("", 0)
+	assert.Equal(t, "sse", transport)
+	assert.Equal(t, 8080, targetPort)
+
+	transport, targetPort = defaults.Apply("stdio", 9090)
+	assert.Equal(t, "stdio", transport)
+	assert.Equal(t, 9090, targetPort)
+
+	transport, targetPort = ImageDefaults{}.Apply("", 0)
+	assert.Empty(t, transport)
+	assert.Zero(t, targetPort)
+}
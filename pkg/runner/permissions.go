@@ -1,12 +1,15 @@
 package runner
 
 import (
+	"context"
 	"encoding/json"
 	"fmt"
 	"os"
 	"path/filepath"
 	"strings"
+	"time"
 
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
 	"github.com/stacklok/toolhive/pkg/logger"
 	"github.com/stacklok/toolhive/pkg/permissions"
 )
@@ -97,3 +100,48 @@ func isTempPermissionProfile(filePath string) bool {
 	// If the relative path doesn't start with "..", then it's within the temp directory
 	return !strings.HasPrefix(relPath, "..")
 }
+
+// grantRetryInterval is how long to wait before trying again to revoke expired grants
+const grantRetryInterval = 10 * time.Second
+
+// watchGrants revokes the temporary grants of the permission profile of a running workload once they
+// expire, reverting the workload to the permissions of the profile. It returns once no grant is active
+// any longer, or when the context is cancelled.
+func watchGrants(ctx context.Context, runtime rt.Runtime, workloadName string, profile *permissions.Profile) {
+	since := time.Now()
+	for {
+		next, ok := profile.NextGrantExpiry(since)
+		if !ok {
+			return
+		}
+		if !sleepUntil(ctx, next) {
+			return
+		}
+
+		now := time.Now()
+		if err := runtime.UpdateNetworkPermissions(ctx, workloadName, profile); err != nil {
+			// The grants stay in place until they are revoked, so keep trying rather than giving up
+			logger.Errorf("Failed to revoke expired permission grants of %s: %v", workloadName, err)
+			if !sleepUntil(ctx, now.Add(grantRetryInterval)) {
+				return
+			}
+			continue
+		}
+		for _, grant := range profile.ExpiredGrants(since, now) {
+			logger.Infof("Permission grant of %s expired, reverted to its permission profile: %s", workloadName, grant.String())
+		}
+		since = now
+	}
+}
+
+// sleepUntil waits until the given time, and returns false if the context is cancelled before
+func sleepUntil(ctx context.Context, t time.Time) bool {
+	timer := time.NewTimer(time.Until(t))
+	defer timer.Stop()
+	select {
+	case <-ctx.Done():
+		return false
+	case <-timer.C:
+		return true
+	}
+}
@@ -1,11 +1,17 @@
 package runner
 
 import (
+	"context"
 	"os"
 	"path/filepath"
 	"testing"
+	"time"
 
+	"go.uber.org/mock/gomock"
+
+	"github.com/stacklok/toolhive/pkg/container/runtime/mocks"
 	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/permissions"
 )
 
 func TestIsTempPermissionProfile(t *testing.T) {
@@ -117,3 +123,47 @@ func TestCleanupTempPermissionProfile_NonExistentFile(t *testing.T) {
 		t.Errorf("CleanupTempPermissionProfile should not fail for non-existent files: %v", err)
 	}
 }
+
+func TestWatchGrants(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	profile := permissions.BuiltinNoneProfile()
+	profile.Grants = []permissions.Grant{
+		{AllowHost: []string{"api.example.com"}, ExpiresAt: time.Now().Add(20 * time.Millisecond)},
+		{AllowHost: []string{"expired.example.com"}, ExpiresAt: time.Now().Add(-time.Minute)},
+	}
+
+	ctrl := gomock.NewController(t)
+	mockRuntime := mocks.NewMockRuntime(ctrl)
+	// The workload reverts to its profile once, when the only active grant expires
+	mockRuntime.EXPECT().UpdateNetworkPermissions(gomock.Any(), "test-server", profile).Return(nil).Times(1)
+
+	done := make(chan struct{})
+	go func() {
+		watchGrants(context.Background(), mockRuntime, "test-server", profile)
+		close(done)
+	}()
+
+	select {
+	case <-done:
+	case <-time.After(5 * time.Second):
+		t.Fatal("watchGrants should return once no grant is active")
+	}
+}
+
+func TestWatchGrants_Cancelled(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	profile := permissions.BuiltinNoneProfile()
+	profile.Grants = []permissions.Grant{{AllowHost: []string{"api.example.com"}, ExpiresAt: time.Now().Add(time.Hour)}}
+
+	ctrl := gomock.NewController(t)
+	mockRuntime := mocks.NewMockRuntime(ctrl)
+
+	ctx, cancel := context.WithCancel(context.Background())
+	cancel()
+	// No permissions are updated when the workload stops before the grant expires
+	watchGrants(ctx, mockRuntime, "test-server", profile)
+}
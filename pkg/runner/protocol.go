@@ -62,8 +62,13 @@ func createTemplateData(transportType templates.TransportType, packageName, caCe
 	// Check if this is a local path (for Go packages only)
 	isLocalPath := transportType == templates.TransportTypeGO && isLocalGoPath(packageName)
 
+	mcpPackage := packageName
+	if transportType == templates.TransportTypeGO && !isLocalPath {
+		mcpPackage = goInstallPackage(packageName)
+	}
+
 	templateData := templates.TemplateData{
-		MCPPackage:  packageName,
+		MCPPackage:  mcpPackage,
 		MCPArgs:     []string{}, // No additional arguments for now
 		IsLocalPath: isLocalPath,
 	}
@@ -286,7 +291,7 @@ func buildImageFromTemplate(
 
 	// Build the Docker image
 	logger.Infof("Building Docker image for %s package: %s", transportType, packageName)
-	if err := imageManager.BuildImage(ctx, buildCtx.Dir, imageName); err != nil {
+	if err := imageManager.BuildImage(ctx, buildCtx.Dir, "", imageName); err != nil {
 		return "", fmt.Errorf("failed to build Docker image: %w", err)
 	}
 	logger.Infof("Successfully built Docker image: %s", imageName)
@@ -326,6 +331,15 @@ func isLocalGoPath(path string) bool {
 	return strings.HasPrefix(path, "./") || strings.HasPrefix(path, "../") || strings.HasPrefix(path, "/") || path == "."
 }
 
+// goInstallPackage returns the package to go install for a remote Go package.
+// go install requires a version outside of a module, so the latest version is used if none is given.
+func goInstallPackage(packageName string) string {
+	if strings.Contains(packageName, "@") {
+		return packageName
+	}
+	return packageName + "@latest"
+}
+
 // IsImageProtocolScheme checks if the serverOrImage string contains a protocol scheme (uvx://, npx://, or go://)
 func IsImageProtocolScheme(serverOrImage string) bool {
 	return strings.HasPrefix(serverOrImage, UVXScheme) ||
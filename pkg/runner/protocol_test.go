@@ -176,6 +176,35 @@ func TestIsImageProtocolScheme(t *testing.T) {
 	}
 }
 
+func TestGoInstallPackage(t *testing.T) {
+	t.Parallel()
+	tests := []struct {
+		name        string
+		packageName string
+		expected    string
+	}{
+		{
+			name:        "package with version",
+			packageName: "github.com/example/mcp-server@v1.2.3",
+			expected:    "github.com/example/mcp-server@v1.2.3",
+		},
+		{
+			name:        "package without version",
+			packageName: "github.com/example/mcp-server/cmd/server",
+			expected:    "github.com/example/mcp-server/cmd/server@latest",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			if got := goInstallPackage(tt.packageName); got != tt.expected {
+				t.Errorf("goInstallPackage(%q) = %q, want %q", tt.packageName, got, tt.expected)
+			}
+		})
+	}
+}
+
 func TestTemplateDataWithLocalPath(t *testing.T) {
 	t.Parallel()
 	tests := []struct {
@@ -25,6 +25,31 @@ const (
 	VerifyImageDisabled = "disabled"
 )
 
+// PullPolicy determines when the image of an MCP server is pulled
+type PullPolicy string
+
+const (
+	// PullDefault pulls images with the latest tag, falling back to a local image if the pull fails,
+	// and other images only if they don't exist locally.
+	PullDefault PullPolicy = ""
+	// PullAlways always pulls the image.
+	PullAlways PullPolicy = "always"
+	// PullMissing pulls the image only if it doesn't exist locally.
+	PullMissing PullPolicy = "missing"
+	// PullNever never pulls the image, so it must exist locally.
+	PullNever PullPolicy = "never"
+)
+
+// ParsePullPolicy parses a pull policy, where an empty string is the default policy
+func ParsePullPolicy(policy string) (PullPolicy, error) {
+	switch p := PullPolicy(policy); p {
+	case PullDefault, PullAlways, PullMissing, PullNever:
+		return p, nil
+	default:
+		return "", fmt.Errorf("invalid pull policy %q (valid values: %s, %s, %s)", policy, PullAlways, PullMissing, PullNever)
+	}
+}
+
 var (
 	// ErrBadProtocolScheme is returned when the provided serverOrImage is not a valid protocol scheme.
 	ErrBadProtocolScheme = errors.New("invalid protocol scheme provided for MCP server")
@@ -38,6 +63,7 @@ func GetMCPServer(
 	serverOrImage string,
 	rawCACertPath string,
 	verificationType string,
+	pullPolicy PullPolicy,
 ) (string, *registry.ImageMetadata, error) {
 	var imageMetadata *registry.ImageMetadata
 	var imageToUse string
@@ -57,18 +83,10 @@ func GetMCPServer(
 		imageToUse = generatedImage
 	} else {
 		logger.Debugf("No protocol scheme detected, using image: %s", serverOrImage)
-		// Try to find the imageMetadata in the registry
-		provider, err := registry.GetDefaultProvider()
-		if err != nil {
-			return "", nil, fmt.Errorf("failed to get registry provider: %v", err)
-		}
-		imageMetadata, err = provider.GetServer(serverOrImage)
+		var err error
+		imageToUse, imageMetadata, err = ResolveImage(ctx, serverOrImage)
 		if err != nil {
-			logger.Debugf("ImageMetadata '%s' not found in registry: %v", serverOrImage, err)
-			imageToUse = serverOrImage
-		} else {
-			logger.Debugf("Found imageMetadata '%s' in registry: %v", serverOrImage, imageMetadata)
-			imageToUse = imageMetadata.Image
+			return "", nil, err
 		}
 	}
 
@@ -78,64 +96,215 @@ func GetMCPServer(
 	}
 
 	// Pull the image if necessary
-	if err := pullImage(ctx, imageToUse, imageManager); err != nil {
-		return "", nil, fmt.Errorf("failed to retrieve or pull image: %v", err)
+	if err := pullImage(ctx, imageToUse, imageManager, pullPolicy); err != nil {
+		return "", nil, fmt.Errorf("failed to retrieve or pull image: %w", err)
 	}
 
 	return imageToUse, imageMetadata, nil
 }
 
-// pullImage pulls an image from a remote registry if it has the "latest" tag
-// or if it doesn't exist locally. If the image is a local image, it will not be pulled.
-// If the image has the latest tag, it will be pulled to ensure we have the most recent version.
-// however, if there is a failure in pulling the "latest" tag, it will check if the image exists locally
-// as it is possible that the image was locally built.
-func pullImage(ctx context.Context, image string, imageManager images.ImageManager) error {
-	// Check if the image has the "latest" tag
-	isLatestTag := hasLatestTag(image)
-
-	if isLatestTag {
-		// For "latest" tag, try to pull first
-		logger.Infof("Image %s has 'latest' tag, pulling to ensure we have the most recent version...", image)
-		err := imageManager.PullImage(ctx, image)
+// ResolveImage returns the image of a registry server, with its version selector resolved, and the registry
+// entry of the server. Names which are not found in the registry are returned as image references.
+func ResolveImage(ctx context.Context, serverOrImage string) (string, *registry.ImageMetadata, error) {
+	// Try to find the imageMetadata in the registry
+	provider, err := registry.GetDefaultProvider()
+	if err != nil {
+		return "", nil, fmt.Errorf("failed to get registry provider: %v", err)
+	}
+	serverName, selector := registry.SplitServerVersion(serverOrImage)
+	imageMetadata, err := provider.GetServer(serverName)
+	switch {
+	case err != nil && selector != "":
+		return "", nil, fmt.Errorf("server %s not found in registry, versions can only be selected for registry servers: %v",
+			serverName, err)
+	case err != nil:
+		logger.Debugf("ImageMetadata '%s' not found in registry: %v", serverOrImage, err)
+		return serverOrImage, nil, nil
+	case selector != "":
+		var image string
+		image, err = resolveServerVersion(ctx, imageMetadata, imageMetadata.Image, selector)
 		if err != nil {
-			// Pull failed, check if it exists locally
-			logger.Infof("Pull failed, checking if image exists locally: %s", image)
-			imageExists, checkErr := imageManager.ImageExists(ctx, image)
-			if checkErr != nil {
-				return fmt.Errorf("failed to check if image exists: %v", checkErr)
-			}
+			return "", nil, err
+		}
+		logger.Infof("Resolved %s to %s", serverOrImage, image)
+		return image, imageMetadata, nil
+	default:
+		logger.Debugf("Found imageMetadata '%s' in registry: %v", serverOrImage, imageMetadata)
+		return imageMetadata.Image, imageMetadata, nil
+	}
+}
 
-			if imageExists {
-				logger.Debugf("Using existing local image: %s", image)
-			} else {
-				return fmt.Errorf("%w: %s", ErrImageNotFound, image)
-			}
-		} else {
-			logger.Infof("Successfully pulled image: %s", image)
+// RefreshImage pulls the most recent version of an image which is already in use and verifies it against
+// the provenance information of the registry server which uses the same image repository, if any.
+func RefreshImage(ctx context.Context, image string, verificationType string) error {
+	imageMetadata, err := findImageMetadata(image)
+	if err != nil {
+		return err
+	}
+
+	if err := verifyImage(image, imageMetadata, verificationType); err != nil {
+		return err
+	}
+
+	imageManager := images.NewImageManager(ctx)
+	if err := imageManager.PullImage(ctx, image); err != nil {
+		return fmt.Errorf("failed to pull image %s: %v", image, err)
+	}
+	return nil
+}
+
+// ResolveServerVersion returns the image reference the version selector of a server currently resolves to,
+// in the repository of the given image. The selector is a release channel of the registry server which uses
+// the same image repository, or a semantic version constraint on the tags of the repository.
+func ResolveServerVersion(ctx context.Context, image string, selector string) (string, error) {
+	imageMetadata, err := findImageMetadata(image)
+	if err != nil {
+		return "", err
+	}
+	return resolveServerVersion(ctx, imageMetadata, image, selector)
+}
+
+// resolveServerVersion resolves a version selector in the repository of the given image
+func resolveServerVersion(
+	ctx context.Context,
+	imageMetadata *registry.ImageMetadata,
+	image string,
+	selector string,
+) (string, error) {
+	ref, err := nameref.ParseReference(image)
+	if err != nil {
+		return "", fmt.Errorf("invalid image reference %s: %v", image, err)
+	}
+
+	if imageMetadata != nil {
+		if tag, ok := imageMetadata.ChannelTag(selector); ok {
+			return ref.Context().Tag(tag).String(), nil
 		}
-	} else {
-		// For non-latest tags, check locally first
-		logger.Debugf("Checking if image exists locally: %s", image)
+	}
+
+	constraint, err := images.ParseConstraint(selector)
+	if err != nil {
+		return "", fmt.Errorf("%s is neither a release channel nor a version constraint: %v", selector, err)
+	}
+
+	tags, err := images.NewRemoteRegistry().Tags(ctx, ref.Context())
+	if err != nil {
+		if imageMetadata == nil || len(imageMetadata.DockerTags) == 0 {
+			return "", fmt.Errorf("failed to list the tags of %s: %v", ref.Context(), err)
+		}
+		logger.Warnf("Warning: Failed to list the tags of %s, using the tags of the registry: %v", ref.Context(), err)
+		tags = imageMetadata.DockerTags
+	}
+
+	tag := constraint.Latest(tags)
+	if tag == "" {
+		return "", fmt.Errorf("no version of %s matches %s", ref.Context(), constraint)
+	}
+	return ref.Context().Tag(tag).String(), nil
+}
+
+// findImageMetadata returns the registry entry whose image belongs to the same repository as the given image,
+// or nil if there is none
+func findImageMetadata(image string) (*registry.ImageMetadata, error) {
+	ref, err := nameref.ParseReference(image)
+	if err != nil {
+		return nil, fmt.Errorf("invalid image reference %s: %v", image, err)
+	}
+
+	provider, err := registry.GetDefaultProvider()
+	if err != nil {
+		return nil, fmt.Errorf("failed to get registry provider: %v", err)
+	}
+	servers, err := provider.ListServers()
+	if err != nil {
+		return nil, fmt.Errorf("failed to list registry servers: %v", err)
+	}
+
+	for _, server := range servers {
+		serverRef, err := nameref.ParseReference(server.Image)
+		if err != nil {
+			continue
+		}
+		if serverRef.Context().Name() == ref.Context().Name() {
+			return server, nil
+		}
+	}
+	return nil, nil
+}
+
+// pullImage pulls an image according to the pull policy. With the default policy, images are pulled from
+// a remote registry if they have the "latest" tag or if they don't exist locally. If the image has the latest
+// tag, it will be pulled to ensure we have the most recent version. however, if there is a failure in pulling
+// the "latest" tag, it will check if the image exists locally as it is possible that the image was locally built.
+func pullImage(ctx context.Context, image string, imageManager images.ImageManager, policy PullPolicy) error {
+	switch policy {
+	case PullAlways:
+		if err := imageManager.PullImage(ctx, image); err != nil {
+			return fmt.Errorf("failed to pull image %s: %w", image, err)
+		}
+		logger.Infof("Successfully pulled image: %s", image)
+		return nil
+	case PullNever:
 		imageExists, err := imageManager.ImageExists(ctx, image)
-		logger.Debugf("ImageExists locally: %t", imageExists)
 		if err != nil {
 			return fmt.Errorf("failed to check if image exists locally: %v", err)
 		}
-
-		if imageExists {
-			logger.Debugf("Using existing local image: %s", image)
-		} else {
-			// Image doesn't exist locally, try to pull
-			logger.Infof("Image %s not found locally, pulling...", image)
-			if err := imageManager.PullImage(ctx, image); err != nil {
-				// TODO: need more fine grained error handling here.
-				return fmt.Errorf("%w: %s", ErrImageNotFound, image)
-			}
-			logger.Infof("Successfully pulled image: %s", image)
+		if !imageExists {
+			return fmt.Errorf("%w: %s doesn't exist locally and the pull policy is %s", ErrImageNotFound, image, policy)
 		}
+		logger.Debugf("Using existing local image: %s", image)
+		return nil
+	case PullMissing:
+		return pullMissingImage(ctx, image, imageManager)
+	case PullDefault:
+		// pull images with the latest tag, and other images if they are missing
+	}
+
+	if !hasLatestTag(image) {
+		return pullMissingImage(ctx, image, imageManager)
 	}
 
+	// For "latest" tag, try to pull first
+	logger.Infof("Image %s has 'latest' tag, pulling to ensure we have the most recent version...", image)
+	pullErr := imageManager.PullImage(ctx, image)
+	if pullErr == nil {
+		logger.Infof("Successfully pulled image: %s", image)
+		return nil
+	}
+
+	// Pull failed, check if it exists locally
+	logger.Infof("Pull failed, checking if image exists locally: %s", image)
+	imageExists, err := imageManager.ImageExists(ctx, image)
+	if err != nil {
+		return fmt.Errorf("failed to check if image exists: %v", err)
+	}
+	if !imageExists {
+		return fmt.Errorf("%w: %s: %v", ErrImageNotFound, image, pullErr)
+	}
+	logger.Debugf("Using existing local image: %s", image)
+	return nil
+}
+
+// pullMissingImage pulls an image if it doesn't exist locally
+func pullMissingImage(ctx context.Context, image string, imageManager images.ImageManager) error {
+	logger.Debugf("Checking if image exists locally: %s", image)
+	imageExists, err := imageManager.ImageExists(ctx, image)
+	logger.Debugf("ImageExists locally: %t", imageExists)
+	if err != nil {
+		return fmt.Errorf("failed to check if image exists locally: %v", err)
+	}
+
+	if imageExists {
+		logger.Debugf("Using existing local image: %s", image)
+		return nil
+	}
+
+	// Image doesn't exist locally, try to pull
+	logger.Infof("Image %s not found locally, pulling...", image)
+	if err := imageManager.PullImage(ctx, image); err != nil {
+		return fmt.Errorf("%w: %s: %v", ErrImageNotFound, image, err)
+	}
+	logger.Infof("Successfully pulled image: %s", image)
 	return nil
 }
 
@@ -157,26 +326,31 @@ func resolveCACertPath(flagValue string) string {
 	return ""
 }
 
-// verifyImage verifies the image using the specified verification setting (warn, enabled, or disabled)
+// verifyImage verifies the image using the specified verification setting (warn, enabled, or disabled).
+// Images without provenance information in the registry are verified against the trusted signers of the config.
 func verifyImage(image string, server *registry.ImageMetadata, verifySetting string) error {
 	switch verifySetting {
 	case VerifyImageDisabled:
 		logger.Warn("Image verification is disabled")
 	case VerifyImageWarn, VerifyImageEnabled:
-		// Create a new verifier
-		v, err := verifier.New(server)
-		if err != nil {
-			// This happens if we have no provenance entry in the registry for this server.
+		provenances, sigstoreURL := expectedProvenances(server)
+		if len(provenances) == 0 {
 			// Not finding provenance info in the registry is not a fatal error if the setting is "warn".
-			if errors.Is(err, verifier.ErrProvenanceServerInformationNotSet) && verifySetting == VerifyImageWarn {
+			if verifySetting == VerifyImageWarn {
 				logger.Warnf("MCP server %s has no provenance information set, skipping image verification", image)
 				return nil
 			}
+			return verifier.ErrProvenanceServerInformationNotSet
+		}
+
+		// Create a new verifier
+		v, err := verifier.NewForSigstoreURL(sigstoreURL)
+		if err != nil {
 			return err
 		}
 
-		// Verify the image passing the server info
-		isSafe, err := v.VerifyServer(image, server)
+		// Verify the image against the expected provenance
+		isSafe, err := v.VerifyProvenances(image, provenances)
 		if err != nil {
 			return fmt.Errorf("image verification failed: %v", err)
 		}
@@ -195,6 +369,30 @@ func verifyImage(image string, server *registry.ImageMetadata, verifySetting str
 	return nil
 }
 
+// expectedProvenances returns the provenances an image is expected to match, and the TUF repository of the
+// sigstore instance it is verified with. These are the provenance of the registry server, if any, or else
+// the trusted signers of the config, verified with the sigstore public good instance.
+func expectedProvenances(server *registry.ImageMetadata) ([]*registry.Provenance, string) {
+	if server != nil && server.Provenance != nil {
+		return []*registry.Provenance{server.Provenance}, server.Provenance.SigstoreURL
+	}
+
+	var provenances []*registry.Provenance
+	for _, signer := range config.GetConfig().TrustedSigners {
+		// Signers added before they were validated could match the signatures of other signers
+		if err := signer.Validate(); err != nil {
+			logger.Warnf("Warning: Ignoring trusted signer: %v", err)
+			continue
+		}
+		provenances = append(provenances, &registry.Provenance{
+			SignerIdentity: signer.Identity,
+			CertIssuer:     signer.Issuer,
+			RepositoryURI:  signer.Repository,
+		})
+	}
+	return provenances, ""
+}
+
 // hasLatestTag checks if the given image reference has the "latest" tag or no tag (which defaults to "latest")
 func hasLatestTag(imageRef string) bool {
 	ref, err := nameref.ParseReference(imageRef)
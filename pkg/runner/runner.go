@@ -6,6 +6,7 @@ import (
 	"fmt"
 	"os"
 	"os/signal"
+	"strings"
 	"syscall"
 	"time"
 
@@ -16,9 +17,12 @@ import (
 	"github.com/stacklok/toolhive/pkg/logger"
 	"github.com/stacklok/toolhive/pkg/mcp"
 	"github.com/stacklok/toolhive/pkg/process"
+	"github.com/stacklok/toolhive/pkg/ratelimit"
 	"github.com/stacklok/toolhive/pkg/secrets"
 	"github.com/stacklok/toolhive/pkg/telemetry"
 	"github.com/stacklok/toolhive/pkg/transport"
+	"github.com/stacklok/toolhive/pkg/transport/proxy"
+	"github.com/stacklok/toolhive/pkg/transport/proxy/router"
 	"github.com/stacklok/toolhive/pkg/transport/types"
 )
 
@@ -44,30 +48,100 @@ func NewRunner(runConfig *RunConfig) *Runner {
 func (r *Runner) Run(ctx context.Context) error {
 	// Create transport with runtime
 	transportConfig := types.Config{
-		Type:       r.Config.Transport,
-		Port:       r.Config.Port,
-		TargetPort: r.Config.TargetPort,
-		Host:       r.Config.Host,
-		TargetHost: r.Config.TargetHost,
-		Runtime:    r.Config.Runtime,
-		Debug:      r.Config.Debug,
+		Type:             r.Config.Transport,
+		Port:             r.Config.Port,
+		TargetPort:       r.Config.TargetPort,
+		Host:             r.Config.Host,
+		TargetHost:       r.Config.TargetHost,
+		SSEPath:          r.Config.SSEPath,
+		MessagesPath:     r.Config.MessagesPath,
+		NamedPipe:        r.Config.NamedPipe,
+		ListenSocket:     r.Config.ListenSocket,
+		UpstreamHTTP2:    r.Config.UpstreamHTTP2,
+		RestartUnhealthy: r.Config.RestartUnhealthy,
+		Limits:           r.Config.ProxyLimits,
+		Runtime:          r.Config.Runtime,
+		Debug:            r.Config.Debug,
 	}
 
+	// Serve HTTPS if a TLS certificate is configured or a self-signed one is requested
+	tlsConfig, err := r.Config.serverTLSConfig()
+	if err != nil {
+		return fmt.Errorf("failed to set up TLS: %w", err)
+	}
+	transportConfig.TLSConfig = tlsConfig
+
+	// Never pass the gateway token on to the server. Without the token, the requests forwarded by thv gateway
+	// are not told apart from the requests of local clients.
+	gatewayToken, err := router.LoadToken()
+	if err != nil {
+		logger.Warnf("Warning: Failed to load the gateway token: %v", err)
+	}
+	transportConfig.Middlewares = append(transportConfig.Middlewares, router.TokenMiddleware(gatewayToken))
+
+	// Let the web clients of the allowed origins connect, first so preflight requests are answered directly
+	if len(r.Config.CORSOrigins) > 0 {
+		transportConfig.Middlewares = append(transportConfig.Middlewares, proxy.CORSMiddleware(r.Config.CORSOrigins))
+	}
+
+	// Compress responses for clients which accept it, before the other middlewares so they see plain responses
+	transportConfig.Middlewares = append(transportConfig.Middlewares, proxy.CompressionMiddleware)
+
 	// Get authentication middleware
 	allowOpaqueTokens := false
 	if r.Config.OIDCConfig != nil && r.Config.OIDCConfig.AllowOpaqueTokens {
 		allowOpaqueTokens = r.Config.OIDCConfig.AllowOpaqueTokens
 	}
-	authMiddleware, err := auth.GetAuthenticationMiddleware(ctx, r.Config.OIDCConfig, allowOpaqueTokens)
+	var authMiddleware types.Middleware
+	if value := r.Config.staticTokenValue(); value != "" {
+		logger.Info("Static token authentication enabled")
+		authMiddleware, err = auth.NewStaticTokenMiddleware(value)
+	} else {
+		authMiddleware, err = auth.GetAuthenticationMiddleware(ctx, r.Config.OIDCConfig, allowOpaqueTokens)
+	}
 	if err != nil {
 		return fmt.Errorf("failed to create authentication middleware: %v", err)
 	}
 	transportConfig.Middlewares = append(transportConfig.Middlewares, authMiddleware)
 
+	// Limit the requests of each client, identified by the subject of its token when tokens are validated
+	if r.Config.RateLimit != "" {
+		limit, err := ratelimit.ParseLimit(r.Config.RateLimit)
+		if err != nil {
+			return err
+		}
+		logger.Infof("Rate limiting requests to %s per client", r.Config.RateLimit)
+		transportConfig.Middlewares = append(transportConfig.Middlewares,
+			ratelimit.Middleware(limit, r.Config.OIDCConfig != nil))
+	}
+
+	// Reject requests while the server is paused with thv pause, rather than letting them hang
+	transportConfig.Middlewares = append(transportConfig.Middlewares, proxy.PausedMiddleware(r.Config.ContainerName))
+
 	// Add MCP parsing middleware after authentication
 	logger.Info("MCP parsing middleware enabled for transport")
 	transportConfig.Middlewares = append(transportConfig.Middlewares, mcp.ParsingMiddleware)
 
+	// Rename the tools and rewrite their descriptions, before the middlewares using the names of the tools
+	if r.Config.ToolOverrideFile != "" {
+		overrides, err := mcp.LoadToolOverrides(r.Config.ToolOverrideFile)
+		if err != nil {
+			return err
+		}
+		transportConfig.Middlewares = append(transportConfig.Middlewares, mcp.ToolOverrideMiddleware(overrides))
+	}
+
+	// Only expose the allowed tools, which needs the parsed requests
+	if len(r.Config.Tools) > 0 {
+		logger.Infof("Exposing only the tools %s", strings.Join(r.Config.Tools, ", "))
+		transportConfig.Middlewares = append(transportConfig.Middlewares, mcp.ToolFilterMiddleware(r.Config.Tools))
+	}
+
+	// Log the MCP requests, which needs the parsed requests
+	if r.Config.LogRequests {
+		transportConfig.Middlewares = append(transportConfig.Middlewares, mcp.LoggingMiddleware(r.Config.LogPayloads))
+	}
+
 	// Add telemetry middleware if telemetry configuration is provided
 	if r.Config.TelemetryConfig != nil {
 		logger.Info("OpenTelemetry instrumentation enabled for transport")
@@ -135,10 +209,16 @@ func (r *Runner) Run(ctx context.Context) error {
 		logger.Warnf("Warning: Failed to save run configuration: %v", err)
 	}
 
-	// Process secrets if provided
+	// A server which was paused before it was stopped starts unpaused
+	if err := proxy.UnmarkPaused(ctx, r.Config.ContainerName); err != nil {
+		logger.Warnf("Warning: Failed to clear paused state: %v", err)
+	}
+
+	// Process secrets and resolve ${VAR} and ${secret:name} references if provided
 	// NOTE: This MUST happen after we save the run config to avoid storing
 	// the secrets in the state store.
-	if len(r.Config.Secrets) > 0 {
+	var secretManager secrets.Provider
+	if len(r.Config.Secrets) > 0 || r.Config.ReferencesSecrets() {
 		cfg := config.GetConfig()
 
 		providerType, err := cfg.Secrets.GetProviderType()
@@ -146,22 +226,32 @@ func (r *Runner) Run(ctx context.Context) error {
 			return fmt.Errorf("error determining secrets provider type: %w", err)
 		}
 
-		secretManager, err := secrets.CreateSecretProvider(providerType)
+		secretManager, err = secrets.CreateSecretProvider(providerType)
 		if err != nil {
 			return fmt.Errorf("error instantiating secret manager %v", err)
 		}
+	}
+
+	// References are resolved before the secrets are added, so secret values are never expanded
+	if _, err = r.Config.WithExpandedReferences(ctx, secretManager); err != nil {
+		return err
+	}
 
-		// Process secrets
+	// Process secrets
+	if len(r.Config.Secrets) > 0 {
 		if _, err = r.Config.WithSecrets(ctx, secretManager); err != nil {
 			return err
 		}
 	}
 
+	// The logs of the container are persisted from the time it is deployed
+	logsSince := time.Now()
+
 	// Set up the transport
 	logger.Infof("Setting up %s transport...", r.Config.Transport)
 	if err := transportHandler.Setup(
 		ctx, r.Config.Runtime, r.Config.ContainerName, r.Config.Image, r.Config.CmdArgs,
-		r.Config.EnvVars, r.Config.ContainerLabels, r.Config.PermissionProfile, r.Config.K8sPodTemplatePatch,
+		r.Config.EnvVars, r.Config.ContainerLabels, r.Config.PermissionProfile, r.Config.DeployWorkloadOptions(),
 		r.Config.IsolateNetwork,
 	); err != nil {
 		return fmt.Errorf("failed to set up transport: %v", err)
@@ -175,10 +265,23 @@ func (r *Runner) Run(ctx context.Context) error {
 
 	logger.Infof("MCP server %s started successfully", r.Config.ContainerName)
 
+	// The server is switched to a new container when it is upgraded, without stopping the proxy
+	containerCtx, cancelContainer := context.WithCancel(ctx)
+	defer cancelContainer()
+	switched := make(chan string)
+	go r.followContainer(containerCtx, switched, logsSince)
+	go r.watchSwitchRequests(containerCtx, transportHandler, switched)
+
 	// Update client configurations with the MCP server URL.
 	// Note that this function checks the configuration to determine which
-	// clients should be updated, if any.
-	if err := updateClientConfigurations(r.Config.ContainerName, r.Config.ContainerLabels, "localhost", r.Config.Port); err != nil {
+	// clients should be updated, if any. The clients cannot connect to a
+	// proxy served on a socket with a URL, so they are not configured.
+	if r.Config.ListenSocket != "" {
+		logger.Warnf("The MCP server %s is served on %s, so it is not added to the configuration of the MCP clients",
+			r.Config.ContainerName, r.Config.ListenSocket)
+	} else if err := updateClientConfigurations(
+		r.Config.ContainerName, r.Config.ContainerLabels, "localhost", r.Config.Port,
+	); err != nil {
 		logger.Warnf("Warning: Failed to update client configurations: %v", err)
 	}
 
@@ -271,6 +374,29 @@ func (r *Runner) Run(ctx context.Context) error {
 	return nil
 }
 
+// followContainer persists the logs of the container of the server and revokes its temporary permission
+// grants once they expire, starting again with the new container whenever the server is switched to one,
+// until the context is cancelled
+func (r *Runner) followContainer(ctx context.Context, switched <-chan string, logsSince time.Time) {
+	containerName := r.Config.ContainerName
+	for {
+		containerCtx, cancel := context.WithCancel(ctx)
+		go r.persistServerLogs(containerCtx, containerName, logsSince)
+		if r.Config.PermissionProfile != nil && len(r.Config.PermissionProfile.Grants) > 0 {
+			go watchGrants(containerCtx, r.Config.Runtime, containerName, r.Config.PermissionProfile)
+		}
+
+		select {
+		case <-ctx.Done():
+			cancel()
+			return
+		case containerName = <-switched:
+			cancel()
+			logsSince = time.Now()
+		}
+	}
+}
+
 // Cleanup performs cleanup operations for the runner, including shutting down telemetry.
 func (r *Runner) Cleanup(ctx context.Context) error {
 	if r.telemetryProvider != nil {
@@ -298,7 +424,7 @@ func updateClientConfigurations(containerName string, containerLabels map[string
 
 	// Generate the URL for the MCP server
 	transportType := labels.GetTransportType(containerLabels)
-	url := client.GenerateMCPServerURL(transportType, host, port, containerName)
+	url := client.GenerateMCPServerURLForLabels(transportType, host, port, containerName, containerLabels)
 
 	// Update each configuration file
 	for _, clientConfig := range clientConfigs {
@@ -0,0 +1,70 @@
+package runner
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"time"
+
+	"github.com/adrg/xdg"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+const (
+	// serverLogMaxSize is the size at which the log file of a server is rotated
+	serverLogMaxSize = 10 * 1024 * 1024
+	// serverLogMaxBackups is the number of rotated log files kept for each server
+	serverLogMaxBackups = 3
+	// serverLogRetryInterval is how long to wait before following the logs of a container again, e.g.
+	// after it was restarted
+	serverLogRetryInterval = 2 * time.Second
+)
+
+// ServerLogFilePath returns the path of the file the logs of the container of a server are persisted to
+func ServerLogFilePath(baseName string) (string, error) {
+	return xdg.DataFile(fmt.Sprintf("toolhive/logs/servers/%s.log", baseName))
+}
+
+// persistServerLogs copies the logs of the container of the server, written since the given time, to the
+// log file of the server until the context is cancelled, so they can be read after the container is gone.
+// The output of stdio servers is their MCP traffic, so only their error stream is persisted.
+func (r *Runner) persistServerLogs(ctx context.Context, containerName string, since time.Time) {
+	path, err := ServerLogFilePath(r.Config.BaseName)
+	if err != nil {
+		logger.Warnf("Warning: Failed to get server log file path: %v", err)
+		return
+	}
+	file, err := logger.NewRotatingFile(path, serverLogMaxSize, serverLogMaxBackups)
+	if err != nil {
+		logger.Warnf("Warning: Failed to create server log file: %v", err)
+		return
+	}
+	defer file.Close()
+
+	var stdout io.Writer = file
+	if r.Config.Transport == types.TransportTypeStdio {
+		stdout = nil
+	}
+
+	// The logs are followed again whenever the stream ends, since the container may be restarted
+	for {
+		err := r.Config.Runtime.StreamWorkloadLogs(ctx, containerName, since, stdout, file)
+		if errors.Is(err, errors.ErrUnsupported) {
+			logger.Debugf("Not persisting the logs of %s: %v", containerName, err)
+			return
+		}
+		if err != nil {
+			logger.Debugf("Failed to follow the logs of %s: %v", containerName, err)
+		}
+		since = time.Now()
+
+		select {
+		case <-ctx.Done():
+			return
+		case <-time.After(serverLogRetryInterval):
+		}
+	}
+}
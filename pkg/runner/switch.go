@@ -0,0 +1,208 @@
+package runner
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"time"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/state"
+	transporterrors "github.com/stacklok/toolhive/pkg/transport/errors"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+const (
+	// switchPollInterval is how often the requests to switch a server to a new container and their
+	// status are checked
+	switchPollInterval = time.Second
+	// switchPickupTimeout is how long the proxy of a server has to pick up a request to switch it
+	switchPickupTimeout = 10 * time.Second
+	// switchCompletionMargin is how much longer than the health timeout a switch may take, to deploy the
+	// new container and drain the previous one
+	switchCompletionMargin = time.Minute
+)
+
+// ErrSwitchNotHandled is returned by SwitchContainer when the proxy of a server cannot switch it to a new
+// container, e.g. because of its transport, in which case the server has to be restarted instead
+var ErrSwitchNotHandled = errors.New("the proxy of the server cannot switch it to a new container")
+
+// switchStatus is the status of a request to switch a server to a new container
+type switchStatus string
+
+const (
+	switchRequested   switchStatus = "requested"
+	switchInProgress  switchStatus = "switching"
+	switchDone        switchStatus = "switched"
+	switchFailed      switchStatus = "failed"
+	switchUnsupported switchStatus = "unsupported"
+)
+
+// switchRequest is a request to the proxy of a running server to switch it to a new container,
+// whose status is updated by the proxy
+type switchRequest struct {
+	Image         string        `json:"image"`
+	HealthTimeout time.Duration `json:"health_timeout"`
+	Status        switchStatus  `json:"status"`
+	Error         string        `json:"error,omitempty"`
+}
+
+// SwitchContainer asks the proxy of a running server to switch it to a new container of the image, without
+// interrupting its clients, and waits until it is switched. The proxy keeps the previous container if the
+// new one does not respond within healthTimeout. ErrSwitchNotHandled is returned if the proxy cannot switch
+// the server.
+func SwitchContainer(ctx context.Context, baseName, image string, healthTimeout time.Duration) error {
+	store, err := state.NewSwitchRequestStore(DefaultAppName)
+	if err != nil {
+		return fmt.Errorf("failed to create state store: %w", err)
+	}
+	request := &switchRequest{Image: image, HealthTimeout: healthTimeout, Status: switchRequested}
+	if err := saveSwitchRequest(ctx, store, baseName, request); err != nil {
+		return err
+	}
+	defer func() {
+		if err := store.Delete(ctx, baseName); err != nil {
+			logger.Debugf("Failed to delete the switch request of %s: %v", baseName, err)
+		}
+	}()
+
+	ticker := time.NewTicker(switchPollInterval)
+	defer ticker.Stop()
+
+	requested := time.Now()
+	deadline := requested.Add(healthTimeout + switchCompletionMargin)
+	for {
+		select {
+		case <-ctx.Done():
+			return ctx.Err()
+		case <-ticker.C:
+		}
+
+		// The request may be read while the proxy writes it, in which case it is read again
+		request, err := loadSwitchRequest(ctx, store, baseName)
+		if err != nil {
+			logger.Debugf("Failed to read the switch request of %s: %v", baseName, err)
+			continue
+		}
+		switch request.Status {
+		case switchDone:
+			return nil
+		case switchFailed:
+			return errors.New(request.Error)
+		case switchUnsupported:
+			return ErrSwitchNotHandled
+		case switchRequested:
+			// Proxies started before switching was supported never pick up the request
+			if time.Since(requested) > switchPickupTimeout {
+				return ErrSwitchNotHandled
+			}
+		case switchInProgress:
+			// The proxy is deploying the new container or draining the previous one
+		}
+		if time.Now().After(deadline) {
+			return fmt.Errorf("the proxy of %s did not switch it to a new container in time", baseName)
+		}
+	}
+}
+
+// watchSwitchRequests switches the server to a new container whenever SwitchContainer requests it, and sends
+// the name of the new container to switched, until the context is cancelled
+func (r *Runner) watchSwitchRequests(ctx context.Context, transportHandler types.Transport, switched chan<- string) {
+	store, err := state.NewSwitchRequestStore(DefaultAppName)
+	if err != nil {
+		logger.Warnf("Warning: Failed to create switch request store: %v", err)
+		return
+	}
+
+	ticker := time.NewTicker(switchPollInterval)
+	defer ticker.Stop()
+
+	for {
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+
+		exists, err := store.Exists(ctx, r.Config.BaseName)
+		if err != nil || !exists {
+			continue
+		}
+		request, err := loadSwitchRequest(ctx, store, r.Config.BaseName)
+		if err != nil || request.Status != switchRequested {
+			continue
+		}
+
+		containerName, err := r.switchContainer(ctx, store, transportHandler, request)
+		if err != nil {
+			logger.Errorf("Failed to switch %s to a new container: %v", r.Config.ContainerName, err)
+			continue
+		}
+		select {
+		case switched <- containerName:
+		case <-ctx.Done():
+			return
+		}
+	}
+}
+
+// switchContainer handles a request to switch the server to a new container, and records its outcome
+func (r *Runner) switchContainer(
+	ctx context.Context, store state.Store, transportHandler types.Transport, request *switchRequest,
+) (string, error) {
+	request.Status = switchInProgress
+	if err := saveSwitchRequest(ctx, store, r.Config.BaseName, request); err != nil {
+		return "", err
+	}
+
+	var containerName string
+	err := transporterrors.ErrSwitchUnsupported
+	if switcher, ok := transportHandler.(types.ContainerSwitcher); ok {
+		logger.Infof("Switching %s to a new container of %s...", r.Config.ContainerName, request.Image)
+		containerName, err = switcher.SwitchContainer(ctx, request.Image, request.HealthTimeout)
+	}
+	switch {
+	case errors.Is(err, transporterrors.ErrSwitchUnsupported):
+		request.Status = switchUnsupported
+	case err != nil:
+		request.Status, request.Error = switchFailed, err.Error()
+	default:
+		request.Status = switchDone
+		r.Config.Image = request.Image
+		logger.Infof("Switched %s to container %s", r.Config.ContainerName, containerName)
+	}
+	if saveErr := saveSwitchRequest(ctx, store, r.Config.BaseName, request); saveErr != nil {
+		logger.Warnf("Warning: Failed to record the outcome of the switch of %s: %v", r.Config.ContainerName, saveErr)
+	}
+	return containerName, err
+}
+
+// saveSwitchRequest saves a request to switch the server to a new container
+func saveSwitchRequest(ctx context.Context, store state.Store, baseName string, request *switchRequest) error {
+	writer, err := store.GetWriter(ctx, baseName)
+	if err != nil {
+		return fmt.Errorf("failed to get writer for switch request: %w", err)
+	}
+	defer writer.Close()
+
+	if err := json.NewEncoder(writer).Encode(request); err != nil {
+		return fmt.Errorf("failed to write switch request: %w", err)
+	}
+	return nil
+}
+
+// loadSwitchRequest loads the request to switch the server to a new container
+func loadSwitchRequest(ctx context.Context, store state.Store, baseName string) (*switchRequest, error) {
+	reader, err := store.GetReader(ctx, baseName)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get reader for switch request: %w", err)
+	}
+	defer reader.Close()
+
+	request := &switchRequest{}
+	if err := json.NewDecoder(reader).Decode(request); err != nil {
+		return nil, fmt.Errorf("failed to read switch request: %w", err)
+	}
+	return request, nil
+}
@@ -0,0 +1,48 @@
+package runner
+
+import (
+	"crypto/tls"
+	"fmt"
+	"slices"
+
+	"github.com/adrg/xdg"
+
+	"github.com/stacklok/toolhive/pkg/certs"
+	"github.com/stacklok/toolhive/pkg/networking"
+)
+
+// SelfSignedCertFiles returns the paths of the self-signed certificate generated for a server and of its key
+func SelfSignedCertFiles(baseName string) (string, string, error) {
+	certFile, err := xdg.DataFile(fmt.Sprintf("toolhive/certs/%s.crt", baseName))
+	if err != nil {
+		return "", "", err
+	}
+	keyFile, err := xdg.DataFile(fmt.Sprintf("toolhive/certs/%s.key", baseName))
+	if err != nil {
+		return "", "", err
+	}
+	return certFile, keyFile, nil
+}
+
+// serverTLSConfig returns the TLS configuration with which the proxy serves HTTPS,
+// or nil if the proxy serves plain HTTP
+func (c *RunConfig) serverTLSConfig() (*tls.Config, error) {
+	if c.TLSCertFile != "" {
+		return certs.LoadServingConfig(c.TLSCertFile, c.TLSKeyFile)
+	}
+	if !c.TLSSelfSigned {
+		return nil, nil
+	}
+
+	certFile, keyFile, err := SelfSignedCertFiles(c.BaseName)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get self-signed certificate path: %w", err)
+	}
+	// The certificate covers the loopback addresses, and the host the proxy listens on unless it
+	// listens on all interfaces
+	hosts := []string{"localhost", "127.0.0.1", "::1"}
+	if c.Host != "" && !networking.IsUnspecifiedHost(c.Host) && !slices.Contains(hosts, c.Host) {
+		hosts = append(hosts, c.Host)
+	}
+	return certs.SelfSignedServingConfig(certFile, keyFile, hosts)
+}
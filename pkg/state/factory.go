@@ -6,6 +6,19 @@ const (
 
 	// GroupConfigsDir is the directory name for storing group configurations
 	GroupConfigsDir = "groups"
+
+	// UpgradeHistoryDir is the directory name for storing the upgrade history of servers
+	UpgradeHistoryDir = "upgrades"
+
+	// SessionsDir is the directory name for storing the client sessions of proxies
+	SessionsDir = "sessions"
+
+	// RegistryCacheDir is the directory name for caching the data of remote registries
+	RegistryCacheDir = "registry-cache"
+	// SwitchRequestsDir is the directory name for storing the requests to switch servers to new containers
+	SwitchRequestsDir = "switches"
+	// PausedDir is the directory name for storing the markers of paused servers
+	PausedDir = "paused"
 )
 
 // NewRunConfigStore creates a store for run configuration state
@@ -17,3 +30,28 @@ func NewRunConfigStore(appName string) (Store, error) {
 func NewGroupConfigStore(appName string) (Store, error) {
 	return NewLocalStore(appName, GroupConfigsDir)
 }
+
+// NewUpgradeHistoryStore creates a store for the upgrade history of servers
+func NewUpgradeHistoryStore(appName string) (Store, error) {
+	return NewLocalStore(appName, UpgradeHistoryDir)
+}
+
+// NewSessionStore creates a store for the client sessions of proxies
+func NewSessionStore(appName string) (Store, error) {
+	return NewLocalStore(appName, SessionsDir)
+}
+
+// NewRegistryCacheStore creates a store for caching the data of remote registries
+func NewRegistryCacheStore(appName string) (Store, error) {
+	return NewLocalStore(appName, RegistryCacheDir)
+}
+
+// NewSwitchRequestStore creates a store for the requests to the proxies of servers to switch them to new containers
+func NewSwitchRequestStore(appName string) (Store, error) {
+	return NewLocalStore(appName, SwitchRequestsDir)
+}
+
+// NewPausedStore creates a store for the markers of paused servers, which their proxies check
+func NewPausedStore(appName string) (Store, error) {
+	return NewLocalStore(appName, PausedDir)
+}
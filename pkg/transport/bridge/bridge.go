@@ -0,0 +1,372 @@
+// Package bridge provides a bridge serving an MCP server reached over SSE or streamable HTTP
+// as a stdio MCP server, for clients which can only launch stdio servers.
+package bridge
+
+import (
+	"bufio"
+	"bytes"
+	"context"
+	"crypto/tls"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"strings"
+	"sync"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/mcp"
+	"github.com/stacklok/toolhive/pkg/networking"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+const (
+	// sessionIDHeader is the header carrying the session of the streamable HTTP transport
+	sessionIDHeader = "Mcp-Session-Id"
+	// maxEventSize is the maximum size of an event of an SSE stream
+	maxEventSize = 10 * 1024 * 1024
+)
+
+// ErrConnectionClosed is returned when the MCP server closes the SSE stream of the bridge
+var ErrConnectionClosed = errors.New("the connection to the MCP server was closed")
+
+// Bridge forwards the JSON-RPC messages read from its input, one per line, to an MCP server, and writes
+// the messages of the server to its output, one per line, as the stdio transport of MCP does
+type Bridge struct {
+	serverURL     *url.URL
+	transportType types.TransportType
+	headers       http.Header
+	client        *http.Client
+
+	mutex     sync.Mutex
+	out       io.Writer
+	sessionID string
+	// protocolVersion is the protocol version negotiated by the initialize request of the client
+	protocolVersion string
+}
+
+// New creates a bridge to the MCP server at the URL of its SSE endpoint, or of its streamable HTTP
+// endpoint for the streamable-http transport. The headers are added to all the requests to the server.
+func New(serverURL string, transportType types.TransportType, headers http.Header) (*Bridge, error) {
+	u, err := url.Parse(serverURL)
+	if err != nil {
+		return nil, fmt.Errorf("invalid URL of MCP server: %w", err)
+	}
+	if u.Scheme != "http" && u.Scheme != "https" {
+		return nil, fmt.Errorf("invalid URL of MCP server: %s", serverURL)
+	}
+	u.Fragment = ""
+
+	httpClient := &http.Client{}
+	// The proxies of ToolHive use self-signed certificates when they serve HTTPS on the loopback
+	// interface, so only their address is checked
+	if u.Scheme == "https" && networking.IsLoopbackHost(u.Hostname()) {
+		transport := http.DefaultTransport.(*http.Transport).Clone()
+		transport.TLSClientConfig = &tls.Config{InsecureSkipVerify: true} // #nosec G402 - loopback only
+		httpClient.Transport = transport
+	}
+
+	return &Bridge{serverURL: u, transportType: transportType, headers: headers, client: httpClient}, nil
+}
+
+// Run forwards the messages of in to the server and the messages of the server to out, until in is
+// closed or the server closes the connection
+func (b *Bridge) Run(ctx context.Context, in io.Reader, out io.Writer) error {
+	b.out = out
+	if b.transportType == types.TransportTypeStreamableHTTP {
+		return b.runStreamable(ctx, in)
+	}
+	return b.runSSE(ctx, in)
+}
+
+// runSSE opens the SSE stream of the server, and posts the messages of in to the messages endpoint
+// the server sends in the endpoint event of the stream
+func (b *Bridge) runSSE(ctx context.Context, in io.Reader) error {
+	ctx, cancel := context.WithCancel(ctx)
+	defer cancel()
+
+	req, err := b.newRequest(ctx, http.MethodGet, b.serverURL.String(), nil)
+	if err != nil {
+		return err
+	}
+	req.Header.Set("Accept", "text/event-stream")
+	resp, err := b.client.Do(req)
+	if err != nil {
+		return fmt.Errorf("failed to connect to the MCP server: %w", err)
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return fmt.Errorf("failed to connect to the MCP server: %s", resp.Status)
+	}
+
+	endpointCh := make(chan string, 1)
+	streamErrCh := make(chan error, 1)
+	go func() {
+		streamErrCh <- readEvents(resp.Body, func(event, data string) {
+			switch event {
+			case "endpoint":
+				select {
+				case endpointCh <- data:
+				default:
+				}
+			case "", "message":
+				b.write([]byte(data))
+			}
+		})
+	}()
+
+	var endpoint string
+	select {
+	case data := <-endpointCh:
+		ref, err := url.Parse(data)
+		if err != nil {
+			return fmt.Errorf("invalid messages endpoint %q: %w", data, err)
+		}
+		endpoint = b.serverURL.ResolveReference(ref).String()
+		logger.Debugf("Sending messages to %s", endpoint)
+	case err := <-streamErrCh:
+		return streamError(err)
+	case <-ctx.Done():
+		return ctx.Err()
+	}
+
+	inputErrCh := make(chan error, 1)
+	go func() {
+		inputErrCh <- readMessages(in, func(message []byte) {
+			b.post(ctx, endpoint, message)
+		})
+	}()
+
+	select {
+	case err := <-inputErrCh:
+		return err
+	case err := <-streamErrCh:
+		return streamError(err)
+	case <-ctx.Done():
+		return ctx.Err()
+	}
+}
+
+// streamError returns the error with which the SSE stream of the server ended
+func streamError(err error) error {
+	if err != nil {
+		return fmt.Errorf("%w: %v", ErrConnectionClosed, err)
+	}
+	return ErrConnectionClosed
+}
+
+// runStreamable posts the messages of in to the streamable HTTP endpoint of the server, and writes
+// the messages of its responses, until in is closed
+func (b *Bridge) runStreamable(ctx context.Context, in io.Reader) error {
+	var wg sync.WaitGroup
+	err := readMessages(in, func(message []byte) {
+		// The session is created by the response to the initialize request, so the messages are
+		// only posted concurrently once it is known
+		if b.getSessionID() == "" {
+			b.post(ctx, b.serverURL.String(), message)
+			return
+		}
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			b.post(ctx, b.serverURL.String(), message)
+		}()
+	})
+	wg.Wait()
+	b.closeSession()
+	return err
+}
+
+// post posts a message to the server, writing the messages of the response, or an error response
+// if the message is a request which the server failed to answer
+func (b *Bridge) post(ctx context.Context, endpoint string, message []byte) {
+	req, err := b.newRequest(ctx, http.MethodPost, endpoint, bytes.NewReader(message))
+	if err != nil {
+		b.writeError(message, err)
+		return
+	}
+	req.Header.Set("Content-Type", "application/json")
+	req.Header.Set("Accept", "application/json, text/event-stream")
+
+	resp, err := b.client.Do(req)
+	if err != nil {
+		b.writeError(message, err)
+		return
+	}
+	defer resp.Body.Close()
+
+	if sessionID := resp.Header.Get(sessionIDHeader); sessionID != "" {
+		b.setSessionID(sessionID)
+	}
+	if resp.StatusCode >= http.StatusBadRequest {
+		body, _ := io.ReadAll(io.LimitReader(resp.Body, 1024))
+		b.writeError(message, fmt.Errorf("%s: %s", resp.Status, strings.TrimSpace(string(body))))
+		return
+	}
+
+	// The SSE transport sends the responses on the SSE stream, and servers answer notifications with 202
+	if b.transportType != types.TransportTypeStreamableHTTP || resp.StatusCode == http.StatusAccepted {
+		return
+	}
+	if strings.HasPrefix(resp.Header.Get("Content-Type"), "text/event-stream") {
+		if err := readEvents(resp.Body, func(event, data string) {
+			if event == "" || event == "message" {
+				b.write([]byte(data))
+			}
+		}); err != nil {
+			logger.Warnf("Failed to read the response of the MCP server: %v", err)
+		}
+		return
+	}
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		b.writeError(message, err)
+		return
+	}
+	if body = bytes.TrimSpace(body); len(body) > 0 {
+		b.write(body)
+	}
+}
+
+// closeSession ends the streamable HTTP session of the bridge, if the server created one
+func (b *Bridge) closeSession() {
+	sessionID := b.getSessionID()
+	if sessionID == "" {
+		return
+	}
+	req, err := b.newRequest(context.Background(), http.MethodDelete, b.serverURL.String(), nil)
+	if err != nil {
+		return
+	}
+	resp, err := b.client.Do(req)
+	if err != nil {
+		logger.Debugf("Failed to close session %s: %v", sessionID, err)
+		return
+	}
+	_ = resp.Body.Close()
+}
+
+// newRequest creates a request to the server with the headers of the bridge and its session
+func (b *Bridge) newRequest(ctx context.Context, method, endpoint string, body io.Reader) (*http.Request, error) {
+	req, err := http.NewRequestWithContext(ctx, method, endpoint, body)
+	if err != nil {
+		return nil, err
+	}
+	for name, values := range b.headers {
+		for _, value := range values {
+			req.Header.Add(name, value)
+		}
+	}
+	if sessionID := b.getSessionID(); sessionID != "" {
+		req.Header.Set(sessionIDHeader, sessionID)
+	}
+	if version := b.ProtocolVersion(); version != "" && b.transportType == types.TransportTypeStreamableHTTP {
+		req.Header.Set(mcp.ProtocolVersionHeader, version)
+	}
+	return req, nil
+}
+
+func (b *Bridge) getSessionID() string {
+	b.mutex.Lock()
+	defer b.mutex.Unlock()
+	return b.sessionID
+}
+
+func (b *Bridge) setSessionID(sessionID string) {
+	b.mutex.Lock()
+	defer b.mutex.Unlock()
+	b.sessionID = sessionID
+}
+
+// ProtocolVersion returns the protocol version negotiated by the client and the server,
+// or an empty string until the server answered the initialize request
+func (b *Bridge) ProtocolVersion() string {
+	b.mutex.Lock()
+	defer b.mutex.Unlock()
+	return b.protocolVersion
+}
+
+// write writes a message of the server to the output, on its own line, and records the protocol version
+// of the response to the initialize request
+func (b *Bridge) write(message []byte) {
+	b.mutex.Lock()
+	defer b.mutex.Unlock()
+	if b.protocolVersion == "" {
+		if version := mcp.NegotiatedProtocolVersion(message); version != "" {
+			logger.Debugf("Negotiated protocol version %s with the MCP server", version)
+			b.protocolVersion = version
+		}
+	}
+	if _, err := b.out.Write(append(message, '\n')); err != nil {
+		logger.Warnf("Failed to write message: %v", err)
+	}
+}
+
+// writeError writes the error response to a request which could not be forwarded to the server.
+// Notifications and responses have no response, so the error is only logged for them.
+func (b *Bridge) writeError(message []byte, err error) {
+	logger.Warnf("Failed to forward message to the MCP server: %v", err)
+
+	var request struct {
+		ID     json.RawMessage `json:"id"`
+		Method string          `json:"method"`
+	}
+	if json.Unmarshal(message, &request) != nil || len(request.ID) == 0 || request.Method == "" {
+		return
+	}
+	response, marshalErr := json.Marshal(map[string]any{
+		"jsonrpc": "2.0",
+		"id":      request.ID,
+		"error": map[string]any{
+			"code":    -32603,
+			"message": fmt.Sprintf("Failed to forward the request to the MCP server: %v", err),
+		},
+	})
+	if marshalErr != nil {
+		return
+	}
+	b.write(response)
+}
+
+// readMessages calls handle with each non-empty line of r, until r is closed
+func readMessages(r io.Reader, handle func(message []byte)) error {
+	reader := bufio.NewReader(r)
+	for {
+		line, err := reader.ReadBytes('\n')
+		if message := bytes.TrimSpace(line); len(message) > 0 {
+			handle(message)
+		}
+		if errors.Is(err, io.EOF) {
+			return nil
+		}
+		if err != nil {
+			return fmt.Errorf("failed to read message: %w", err)
+		}
+	}
+}
+
+// readEvents calls handle with the type and the data of each event of an SSE stream, until it ends
+func readEvents(r io.Reader, handle func(event, data string)) error {
+	scanner := bufio.NewScanner(r)
+	scanner.Buffer(make([]byte, 0, 64*1024), maxEventSize)
+
+	var event string
+	var data []string
+	for scanner.Scan() {
+		line := scanner.Text()
+		switch {
+		case line == "":
+			if len(data) > 0 {
+				handle(event, strings.Join(data, "\n"))
+			}
+			event, data = "", nil
+		case strings.HasPrefix(line, "event:"):
+			event = strings.TrimSpace(strings.TrimPrefix(line, "event:"))
+		case strings.HasPrefix(line, "data:"):
+			data = append(data, strings.TrimPrefix(strings.TrimPrefix(line, "data:"), " "))
+		}
+	}
+	return scanner.Err()
+}
@@ -0,0 +1,156 @@
+package bridge
+
+import (
+	"bufio"
+	"bytes"
+	"context"
+	"fmt"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"sync"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+const pingRequest = `{"jsonrpc":"2.0","id":1,"method":"ping"}`
+
+func TestBridge_SSE(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	messages := make(chan string, 1)
+	mux := http.NewServeMux()
+	mux.HandleFunc("/sse", func(w http.ResponseWriter, r *http.Request) {
+		assert.Equal(t, "Bearer token", r.Header.Get("Authorization"))
+		w.Header().Set("Content-Type", "text/event-stream")
+		fmt.Fprint(w, "event: endpoint\ndata: /messages?session_id=abc\n\n")
+		w.(http.Flusher).Flush()
+		for {
+			select {
+			case message := <-messages:
+				fmt.Fprintf(w, "event: message\ndata: %s\n\n", message)
+				w.(http.Flusher).Flush()
+			case <-r.Context().Done():
+				return
+			}
+		}
+	})
+	mux.HandleFunc("/messages", func(w http.ResponseWriter, r *http.Request) {
+		assert.Equal(t, "abc", r.URL.Query().Get("session_id"))
+		body, _ := io.ReadAll(r.Body)
+		messages <- strings.Replace(string(body), `"method":"ping"`, `"result":{}`, 1)
+		w.WriteHeader(http.StatusAccepted)
+	})
+	server := httptest.NewServer(mux)
+	t.Cleanup(server.Close)
+
+	b, err := New(server.URL+"/sse#fetch", types.TransportTypeSSE, http.Header{"Authorization": {"Bearer token"}})
+	require.NoError(t, err)
+
+	inReader, inWriter := io.Pipe()
+	outReader, outWriter := io.Pipe()
+	done := make(chan error, 1)
+	go func() {
+		done <- b.Run(context.Background(), inReader, outWriter)
+	}()
+
+	_, err = inWriter.Write([]byte(pingRequest + "\n"))
+	require.NoError(t, err)
+	line, err := bufio.NewReader(outReader).ReadString('\n')
+	require.NoError(t, err)
+	assert.Equal(t, `{"jsonrpc":"2.0","id":1,"result":{}}`+"\n", line)
+
+	require.NoError(t, inWriter.Close())
+	assert.NoError(t, <-done)
+}
+
+func TestBridge_Streamable(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	tests := []struct {
+		name         string
+		input        string
+		contentType  string
+		status       int
+		response     string
+		expectOutput string
+	}{
+		{
+			name:         "JSON response",
+			input:        pingRequest + "\n",
+			contentType:  "application/json",
+			status:       http.StatusOK,
+			response:     `{"jsonrpc":"2.0","id":1,"result":{}}` + "\n",
+			expectOutput: `{"jsonrpc":"2.0","id":1,"result":{}}` + "\n",
+		},
+		{
+			name:         "Event stream response",
+			input:        pingRequest + "\n",
+			contentType:  "text/event-stream",
+			status:       http.StatusOK,
+			response:     "event: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{}}\n\n",
+			expectOutput: `{"jsonrpc":"2.0","id":1,"result":{}}` + "\n",
+		},
+		{
+			name:   "Notification",
+			input:  `{"jsonrpc":"2.0","method":"notifications/initialized"}`,
+			status: http.StatusAccepted,
+		},
+		{
+			name:         "Server error",
+			input:        pingRequest + "\n",
+			contentType:  "text/plain",
+			status:       http.StatusInternalServerError,
+			response:     "boom",
+			expectOutput: `{"error":{"code":-32603,"message":"Failed to forward the request to the MCP server: ` +
+				`500 Internal Server Error: boom"},"id":1,"jsonrpc":"2.0"}` + "\n",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			var mutex sync.Mutex
+			var deleted bool
+			server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				if r.Method == http.MethodDelete {
+					mutex.Lock()
+					deleted = r.Header.Get(sessionIDHeader) == "session"
+					mutex.Unlock()
+					return
+				}
+				w.Header().Set(sessionIDHeader, "session")
+				w.Header().Set("Content-Type", tt.contentType)
+				w.WriteHeader(tt.status)
+				fmt.Fprint(w, tt.response)
+			}))
+			t.Cleanup(server.Close)
+
+			b, err := New(server.URL+"/mcp", types.TransportTypeStreamableHTTP, nil)
+			require.NoError(t, err)
+			var out bytes.Buffer
+			require.NoError(t, b.Run(context.Background(), strings.NewReader(tt.input), &out))
+
+			assert.Equal(t, tt.expectOutput, out.String())
+			mutex.Lock()
+			defer mutex.Unlock()
+			assert.True(t, deleted)
+		})
+	}
+}
+
+func TestNew_InvalidURL(t *testing.T) {
+	t.Parallel()
+
+	_, err := New("localhost:8080/sse", types.TransportTypeSSE, nil)
+	assert.Error(t, err)
+}
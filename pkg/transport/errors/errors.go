@@ -15,6 +15,7 @@ var (
 	ErrRuntimeNotSet        = errors.New("container runtime not set")
 	ErrContainerIDNotSet    = errors.New("container ID not set")
 	ErrContainerNameNotSet  = errors.New("container name not set")
+	ErrSwitchUnsupported    = errors.New("the container of the MCP server cannot be switched")
 )
 
 // TransportError represents an error related to transport operations
@@ -20,7 +20,20 @@ func (*Factory) Create(config types.Config) (types.Transport, error) {
 	switch config.Type {
 	case types.TransportTypeStdio:
 		return NewStdioTransport(
-			config.Host, config.Port, config.Runtime, config.Debug, config.PrometheusHandler, config.Middlewares...), nil
+			config.Host,
+			config.Port,
+			config.SSEPath,
+			config.MessagesPath,
+			config.Runtime,
+			config.Debug,
+			config.PrometheusHandler,
+			config.NamedPipe,
+			config.ListenSocket,
+			config.TLSConfig,
+			config.RestartUnhealthy,
+			config.Limits,
+			config.Middlewares...,
+		), nil
 	case types.TransportTypeSSE:
 		return NewHTTPTransport(
 			types.TransportTypeSSE,
@@ -31,6 +44,12 @@ func (*Factory) Create(config types.Config) (types.Transport, error) {
 			config.Debug,
 			config.TargetHost,
 			config.PrometheusHandler,
+			config.NamedPipe,
+			config.ListenSocket,
+			config.UpstreamHTTP2,
+			config.TLSConfig,
+			config.RestartUnhealthy,
+			config.Limits,
 			config.Middlewares...,
 		), nil
 	case types.TransportTypeStreamableHTTP:
@@ -43,6 +62,30 @@ func (*Factory) Create(config types.Config) (types.Transport, error) {
 			config.Debug,
 			config.TargetHost,
 			config.PrometheusHandler,
+			config.NamedPipe,
+			config.ListenSocket,
+			config.UpstreamHTTP2,
+			config.TLSConfig,
+			config.RestartUnhealthy,
+			config.Limits,
+			config.Middlewares...,
+		), nil
+	case types.TransportTypeWebSocket:
+		return NewWebSocketTransport(
+			config.Host,
+			config.Port,
+			config.TargetPort,
+			config.Runtime,
+			config.Debug,
+			config.TargetHost,
+			config.SSEPath,
+			config.MessagesPath,
+			config.PrometheusHandler,
+			config.NamedPipe,
+			config.ListenSocket,
+			config.TLSConfig,
+			config.RestartUnhealthy,
+			config.Limits,
 			config.Middlewares...,
 		), nil
 	case types.TransportTypeInspector:
@@ -2,13 +2,16 @@ package transport
 
 import (
 	"context"
+	"crypto/tls"
 	"fmt"
 	"net/http"
 	"sync"
+	"time"
 
 	"github.com/stacklok/toolhive/pkg/container"
 	rt "github.com/stacklok/toolhive/pkg/container/runtime"
 	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/networking"
 	"github.com/stacklok/toolhive/pkg/permissions"
 	"github.com/stacklok/toolhive/pkg/transport/errors"
 	"github.com/stacklok/toolhive/pkg/transport/proxy/transparent"
@@ -20,6 +23,16 @@ const (
 	LocalhostName = "localhost"
 	// LocalhostIPv4 is the standard IPv4 address for localhost
 	LocalhostIPv4 = "127.0.0.1"
+
+	// switchedContainerSuffix is appended to the name of the container of an MCP server for the container
+	// the proxy is switched to, so both containers exist while the proxy is switched, and is dropped again
+	// at the next switch
+	switchedContainerSuffix = "-next"
+	// switchPollInterval is how often a new container is checked while waiting for it to respond
+	switchPollInterval = time.Second
+	// switchDrainTimeout is how long the requests in flight to the previous container may take to complete
+	// before it is stopped
+	switchDrainTimeout = 30 * time.Second
 )
 
 // HTTPTransport implements the Transport interface using Server-Sent/Streamable Events.
@@ -35,12 +48,23 @@ type HTTPTransport struct {
 	debug             bool
 	middlewares       []types.Middleware
 	prometheusHandler http.Handler
+	namedPipe         string
+	listenSocket      string
+	upstreamHTTP2     bool
+	tlsConfig         *tls.Config
+	restartUnhealthy  bool
+	limits            types.ProxyLimits
 
 	// Mutex for protecting shared state
 	mutex sync.Mutex
 
 	// Transparent proxy
-	proxy types.Proxy
+	proxy *transparent.TransparentProxy
+
+	// deployment is how the container of the MCP server was deployed, to deploy the container the proxy
+	// is switched to the same way, and deployedName is the name of the current container
+	deployment   *deployment
+	deployedName string
 
 	// Shutdown channel
 	shutdownCh chan struct{}
@@ -50,6 +74,19 @@ type HTTPTransport struct {
 	errorCh <-chan error
 }
 
+// deployment holds the arguments with which the container of an MCP server is deployed
+type deployment struct {
+	image             string
+	cmdArgs           []string
+	envVars           map[string]string
+	labels            map[string]string
+	permissionProfile *permissions.Profile
+	options           *rt.DeployWorkloadOptions
+	isolateNetwork    bool
+	// containerPort is the port the MCP server listens on in the container
+	containerPort int
+}
+
 // NewHTTPTransport creates a new HTTP transport.
 func NewHTTPTransport(
 	transportType types.TransportType,
@@ -60,6 +97,12 @@ func NewHTTPTransport(
 	debug bool,
 	targetHost string,
 	prometheusHandler http.Handler,
+	namedPipe string,
+	listenSocket string,
+	upstreamHTTP2 bool,
+	tlsConfig *tls.Config,
+	restartUnhealthy bool,
+	limits types.ProxyLimits,
 	middlewares ...types.Middleware,
 ) *HTTPTransport {
 	if host == "" {
@@ -81,6 +124,12 @@ func NewHTTPTransport(
 		runtime:           runtime,
 		debug:             debug,
 		prometheusHandler: prometheusHandler,
+		namedPipe:         namedPipe,
+		listenSocket:      listenSocket,
+		upstreamHTTP2:     upstreamHTTP2,
+		tlsConfig:         tlsConfig,
+		restartUnhealthy:  restartUnhealthy,
+		limits:            limits,
 		shutdownCh:        make(chan struct{}),
 	}
 }
@@ -98,17 +147,19 @@ func (t *HTTPTransport) Port() int {
 var transportEnvMap = map[types.TransportType]string{
 	types.TransportTypeSSE:            "sse",
 	types.TransportTypeStreamableHTTP: "streamable-http",
+	types.TransportTypeWebSocket:      "websocket",
 }
 
 // Setup prepares the transport for use.
 func (t *HTTPTransport) Setup(ctx context.Context, runtime rt.Runtime, containerName string, image string, cmdArgs []string,
-	envVars, labels map[string]string, permissionProfile *permissions.Profile, k8sPodTemplatePatch string,
+	envVars, labels map[string]string, permissionProfile *permissions.Profile, containerOptions *rt.DeployWorkloadOptions,
 	isolateNetwork bool) error {
 	t.mutex.Lock()
 	defer t.mutex.Unlock()
 
 	t.runtime = runtime
 	t.containerName = containerName
+	t.removeSwitchedContainer(ctx)
 
 	env, ok := transportEnvMap[t.transportType]
 	if !ok {
@@ -121,18 +172,25 @@ func (t *HTTPTransport) Setup(ctx context.Context, runtime rt.Runtime, container
 	envVars["FASTMCP_PORT"] = fmt.Sprintf("%d", t.targetPort)
 	envVars["MCP_HOST"] = t.targetHost
 
-	// Create workload options
-	containerOptions := rt.NewDeployWorkloadOptions()
-	containerOptions.K8sPodTemplatePatch = k8sPodTemplatePatch
+	// Create workload options if none were provided
+	if containerOptions == nil {
+		containerOptions = rt.NewDeployWorkloadOptions()
+	}
 
 	// Expose the target port in the container
 	containerPortStr := fmt.Sprintf("%d/tcp", t.targetPort)
 	containerOptions.ExposedPorts[containerPortStr] = struct{}{}
 
-	// Create host port bindings (configurable through the --host flag)
+	// Create host port bindings. The proxy forwards to the published port, so the port is only published
+	// on the loopback interface when the proxy reaches the container there, rather than on the address
+	// the proxy listens on, which would expose the server without the middlewares of the proxy.
+	hostIP := t.host
+	if networking.IsLoopbackHost(t.targetHost) {
+		hostIP = LocalhostIPv4
+	}
 	portBindings := []rt.PortBinding{
 		{
-			HostIP:   t.host,
+			HostIP:   hostIP,
 			HostPort: fmt.Sprintf("%d", t.targetPort),
 		},
 	}
@@ -169,6 +227,17 @@ func (t *HTTPTransport) Setup(ctx context.Context, runtime rt.Runtime, container
 		return fmt.Errorf("failed to create container: %v", err)
 	}
 	t.containerID = containerID
+	t.deployedName = containerName
+	t.deployment = &deployment{
+		image:             image,
+		cmdArgs:           cmdArgs,
+		envVars:           envVars,
+		labels:            labels,
+		permissionProfile: permissionProfile,
+		options:           containerOptions,
+		isolateNetwork:    isolateNetwork,
+		containerPort:     t.targetPort,
+	}
 	logger.Infof("Container created with ID: %s", containerID)
 
 	if t.Mode() == types.TransportTypeSSE && container.IsKubernetesRuntime() {
@@ -230,13 +299,27 @@ func (t *HTTPTransport) Start(ctx context.Context) error {
 		t.port, targetURI)
 
 	// Create the transparent proxy with middlewares
-	t.proxy = transparent.NewTransparentProxy(t.host, t.port, t.containerName, targetURI, t.prometheusHandler, t.middlewares...)
+	transparentProxy := transparent.NewTransparentProxy(
+		t.host, t.port, t.containerName, targetURI, t.prometheusHandler, t.namedPipe, t.tlsConfig, t.middlewares...)
+	if t.restartUnhealthy {
+		transparentProxy.SetUnhealthyHandler(t.restartUnhealthyContainer)
+	}
+	transparentProxy.SetLimits(t.limits)
+	transparentProxy.SetListenSocket(t.listenSocket)
+	transparentProxy.SetUpstreamHTTP2(t.upstreamHTTP2)
+	transparentProxy.SetServerTransport(t.transportType)
+	t.proxy = transparentProxy
 	if err := t.proxy.Start(ctx); err != nil {
 		return err
 	}
 
 	logger.Infof("HTTP transport started for container %s on port %d", t.containerName, t.port)
 
+	return t.startMonitoring(ctx)
+}
+
+// startMonitoring creates a container monitor and stops the transport when the container exits
+func (t *HTTPTransport) startMonitoring(ctx context.Context) error {
 	// Create a container monitor
 	monitorRuntime, err := container.NewFactory().Create(ctx)
 	if err != nil {
@@ -251,11 +334,177 @@ func (t *HTTPTransport) Start(ctx context.Context) error {
 	}
 
 	// Start a goroutine to handle container exit
-	go t.handleContainerExit(ctx)
+	go t.handleContainerExit(ctx, t.errorCh)
 
 	return nil
 }
 
+// restartUnhealthyContainer restarts the container of an unhealthy MCP server.
+// The container is not monitored while it is restarted, so that the transport keeps running.
+func (t *HTTPTransport) restartUnhealthyContainer(ctx context.Context) {
+	t.mutex.Lock()
+	defer t.mutex.Unlock()
+
+	// The transport may have been stopped in the meantime
+	select {
+	case <-t.shutdownCh:
+		return
+	default:
+	}
+
+	if t.monitor != nil {
+		t.monitor.StopMonitoring()
+		t.monitor = nil
+	}
+	if err := restartWorkload(ctx, t.runtime, t.containerID, t.containerName); err != nil {
+		logger.Errorf("Failed to restart unhealthy container %s: %v", t.containerName, err)
+	}
+	if err := t.startMonitoring(ctx); err != nil {
+		logger.Errorf("Failed to monitor restarted container %s: %v", t.containerName, err)
+	}
+}
+
+// removeSwitchedContainer removes the container the proxy of a previous run was switched to, which is
+// replaced by the container deployed under the name of the workload
+func (t *HTTPTransport) removeSwitchedContainer(ctx context.Context) {
+	if container.IsKubernetesRuntime() {
+		return
+	}
+	switched, err := t.runtime.GetWorkloadInfo(ctx, t.containerName+switchedContainerSuffix)
+	if err != nil {
+		return
+	}
+	logger.Infof("Removing container %s, to which %s was switched before", switched.Name, t.containerName)
+	if err := t.runtime.RemoveWorkload(ctx, switched.ID); err != nil {
+		logger.Warnf("Warning: Failed to remove container %s: %v", switched.Name, err)
+	}
+}
+
+// SwitchContainer implements types.ContainerSwitcher.
+func (t *HTTPTransport) SwitchContainer(ctx context.Context, image string, healthTimeout time.Duration) (string, error) {
+	name, previousID, drain, err := t.switchContainer(ctx, image, healthTimeout)
+	if err != nil {
+		return "", err
+	}
+
+	drainCtx, cancel := context.WithTimeout(ctx, switchDrainTimeout)
+	defer cancel()
+	if err := drain(drainCtx); err != nil {
+		logger.Warnf("Stopping the previous container of %s with requests in flight: %v", t.containerName, err)
+	}
+	if err := t.runtime.StopWorkload(ctx, previousID, nil); err != nil {
+		logger.Warnf("Warning: Failed to stop the previous container of %s: %v", t.containerName, err)
+	}
+	if err := t.runtime.RemoveWorkload(ctx, previousID); err != nil {
+		logger.Warnf("Warning: Failed to remove the previous container of %s: %v", t.containerName, err)
+	}
+	return name, nil
+}
+
+// switchContainer deploys the new container, and switches the proxy and the monitoring to it once it responds.
+// It returns the name of the new container, the ID of the previous one and the function draining it.
+func (t *HTTPTransport) switchContainer(
+	ctx context.Context, image string, healthTimeout time.Duration,
+) (string, string, func(context.Context) error, error) {
+	t.mutex.Lock()
+	defer t.mutex.Unlock()
+
+	// The Kubernetes runtime updates the pods of the statefulset of the server itself
+	if container.IsKubernetesRuntime() {
+		return "", "", nil, errors.ErrSwitchUnsupported
+	}
+	if t.proxy == nil || t.deployment == nil {
+		return "", "", nil, errors.ErrTransportNotStarted
+	}
+
+	name := t.containerName
+	if t.deployedName == name {
+		name += switchedContainerSuffix
+	}
+	d := t.deployment
+	options := *d.options
+	containerPortStr := fmt.Sprintf("%d/tcp", d.containerPort)
+	options.PortBindings = make(map[string][]rt.PortBinding, len(d.options.PortBindings))
+	for port, bindings := range d.options.PortBindings {
+		options.PortBindings[port] = bindings
+	}
+	hostIP := t.host
+	if networking.IsLoopbackHost(t.targetHost) {
+		hostIP = LocalhostIPv4
+	}
+	options.PortBindings[containerPortStr] = []rt.PortBinding{
+		{HostIP: hostIP, HostPort: fmt.Sprintf("%d", networking.FindAvailable())},
+	}
+
+	logger.Infof("Deploying container %s of %s from image %s...", name, t.containerName, image)
+	containerID, hostPort, err := t.runtime.DeployWorkload(
+		ctx, image, name, d.cmdArgs, d.envVars, d.labels, d.permissionProfile, t.Mode().String(), &options,
+		d.isolateNetwork,
+	)
+	if err != nil {
+		return "", "", nil, fmt.Errorf("failed to create container: %v", err)
+	}
+
+	targetURI := fmt.Sprintf("http://%s:%d", t.targetHost, hostPort)
+	if err := waitForServer(ctx, t.runtime, containerID, targetURI, healthTimeout); err != nil {
+		if removeErr := t.runtime.RemoveWorkload(ctx, containerID); removeErr != nil {
+			logger.Warnf("Warning: Failed to remove container %s: %v", name, removeErr)
+		}
+		return "", "", nil, fmt.Errorf("container %s did not become healthy: %w", name, err)
+	}
+
+	drain, err := t.proxy.SwitchTarget(targetURI)
+	if err != nil {
+		if removeErr := t.runtime.RemoveWorkload(ctx, containerID); removeErr != nil {
+			logger.Warnf("Warning: Failed to remove container %s: %v", name, removeErr)
+		}
+		return "", "", nil, err
+	}
+
+	// The previous container is stopped once drained, which must not stop the transport
+	if t.monitor != nil {
+		t.monitor.StopMonitoring()
+		t.monitor = nil
+	}
+	previousID := t.containerID
+	t.containerID, t.deployedName, t.targetPort = containerID, name, hostPort
+	d.image = image
+	if err := t.startMonitoring(ctx); err != nil {
+		logger.Errorf("Failed to monitor container %s: %v", name, err)
+	}
+	return name, previousID, drain, nil
+}
+
+// waitForServer waits until the MCP server in a new container responds at targetURI, and returns the last
+// error if it does not before the timeout expires or the container exits
+func waitForServer(ctx context.Context, runtime rt.Runtime, containerID, targetURI string, timeout time.Duration) error {
+	ctx, cancel := context.WithTimeout(ctx, timeout)
+	defer cancel()
+
+	ticker := time.NewTicker(switchPollInterval)
+	defer ticker.Stop()
+
+	pinger := transparent.NewMCPPinger(targetURI)
+	for {
+		running, err := runtime.IsWorkloadRunning(ctx, containerID)
+		if err == nil && !running {
+			return fmt.Errorf("the container exited")
+		}
+		if err == nil {
+			_, err = pinger.Ping(ctx)
+			if err == nil {
+				return nil
+			}
+		}
+
+		select {
+		case <-ctx.Done():
+			return err
+		case <-ticker.C:
+		}
+	}
+}
+
 // Stop gracefully shuts down the transport and the container.
 func (t *HTTPTransport) Stop(ctx context.Context) error {
 	t.mutex.Lock()
@@ -279,7 +528,7 @@ func (t *HTTPTransport) Stop(ctx context.Context) error {
 
 	// Stop the container if runtime is available
 	if t.runtime != nil && t.containerID != "" {
-		if err := t.runtime.StopWorkload(ctx, t.containerID); err != nil {
+		if err := t.runtime.StopWorkload(ctx, t.containerID, nil); err != nil {
 			return fmt.Errorf("failed to stop workload: %w", err)
 		}
 	}
@@ -288,11 +537,13 @@ func (t *HTTPTransport) Stop(ctx context.Context) error {
 }
 
 // handleContainerExit handles container exit events.
-func (t *HTTPTransport) handleContainerExit(ctx context.Context) {
+func (t *HTTPTransport) handleContainerExit(ctx context.Context, errorCh <-chan error) {
 	select {
 	case <-ctx.Done():
 		return
-	case err := <-t.errorCh:
+	case <-t.shutdownCh:
+		return
+	case err := <-errorCh:
 		logger.Infof("Container %s exited: %v", t.containerName, err)
 		// Stop the transport when the container exits
 		if stopErr := t.Stop(ctx); stopErr != nil {
@@ -314,3 +565,15 @@ func (t *HTTPTransport) IsRunning(_ context.Context) (bool, error) {
 		return true, nil
 	}
 }
+
+// restartWorkload stops a workload and starts it again
+func restartWorkload(ctx context.Context, runtime rt.Runtime, containerID, containerName string) error {
+	logger.Infof("Restarting container %s", containerName)
+	if err := runtime.StopWorkload(ctx, containerID, nil); err != nil {
+		return fmt.Errorf("failed to stop workload: %w", err)
+	}
+	if err := runtime.StartWorkload(ctx, containerID); err != nil {
+		return fmt.Errorf("failed to start workload: %w", err)
+	}
+	return nil
+}
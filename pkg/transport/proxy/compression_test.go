@@ -0,0 +1,156 @@
+package proxy
+
+import (
+	"compress/gzip"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestCompressionMiddleware(t *testing.T) {
+	t.Parallel()
+
+	large := `{"jsonrpc":"2.0","id":1,"result":{"contents":"` + strings.Repeat("resource ", 500) + `"}}`
+	small := `{"jsonrpc":"2.0","id":1,"result":{}}`
+
+	tests := []struct {
+		name             string
+		method           string
+		acceptEncoding   string
+		contentType      string
+		contentEncoding  string
+		body             string
+		flush            bool
+		expectCompressed bool
+	}{
+		{
+			name:             "Large JSON response",
+			method:           http.MethodPost,
+			acceptEncoding:   "gzip, deflate, br",
+			contentType:      "application/json",
+			body:             large,
+			expectCompressed: true,
+		},
+		{
+			name:             "Client does not accept gzip",
+			method:           http.MethodPost,
+			acceptEncoding:   "br",
+			contentType:      "application/json",
+			body:             large,
+			expectCompressed: false,
+		},
+		{
+			name:             "Client refuses gzip",
+			method:           http.MethodPost,
+			acceptEncoding:   "gzip;q=0",
+			contentType:      "application/json",
+			body:             large,
+			expectCompressed: false,
+		},
+		{
+			name:             "Small response",
+			method:           http.MethodPost,
+			acceptEncoding:   "gzip",
+			contentType:      "application/json",
+			body:             small,
+			expectCompressed: false,
+		},
+		{
+			name:             "SSE event stream",
+			method:           http.MethodGet,
+			acceptEncoding:   "gzip",
+			contentType:      "text/event-stream",
+			body:             large,
+			expectCompressed: false,
+		},
+		{
+			name:             "Response already encoded",
+			method:           http.MethodPost,
+			acceptEncoding:   "gzip",
+			contentType:      "application/json",
+			contentEncoding:  "identity-test",
+			body:             large,
+			expectCompressed: false,
+		},
+		{
+			name:             "Response flushed before it is large enough",
+			method:           http.MethodPost,
+			acceptEncoding:   "gzip",
+			contentType:      "application/json",
+			body:             small,
+			flush:            true,
+			expectCompressed: false,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			handler := CompressionMiddleware(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				if acceptsGzip(tt.acceptEncoding) {
+					assert.Empty(t, r.Header.Get("Accept-Encoding"), "the server should not compress the response itself")
+				}
+				w.Header().Set("Content-Type", tt.contentType)
+				if tt.contentEncoding != "" {
+					w.Header().Set("Content-Encoding", tt.contentEncoding)
+				}
+				w.WriteHeader(http.StatusOK)
+				_, _ = io.WriteString(w, tt.body)
+				if tt.flush {
+					w.(http.Flusher).Flush()
+				}
+			}))
+
+			req := httptest.NewRequest(tt.method, "/mcp", nil)
+			req.Header.Set("Accept-Encoding", tt.acceptEncoding)
+			rec := httptest.NewRecorder()
+			handler.ServeHTTP(rec, req)
+
+			assert.Equal(t, http.StatusOK, rec.Code)
+			assert.Equal(t, "Accept-Encoding", rec.Header().Get("Vary"))
+			if !tt.expectCompressed {
+				assert.NotEqual(t, "gzip", rec.Header().Get("Content-Encoding"))
+				assert.Equal(t, tt.body, rec.Body.String())
+				return
+			}
+
+			assert.Equal(t, "gzip", rec.Header().Get("Content-Encoding"))
+			assert.Less(t, rec.Body.Len(), len(tt.body))
+			reader, err := gzip.NewReader(rec.Body)
+			require.NoError(t, err)
+			body, err := io.ReadAll(reader)
+			require.NoError(t, err)
+			assert.Equal(t, tt.body, string(body))
+		})
+	}
+}
+
+func TestAcceptsGzip(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		acceptEncoding string
+		expected       bool
+	}{
+		{acceptEncoding: "", expected: false},
+		{acceptEncoding: "gzip", expected: true},
+		{acceptEncoding: "deflate, GZIP", expected: true},
+		{acceptEncoding: "gzip;q=0.5", expected: true},
+		{acceptEncoding: "gzip; q=0", expected: false},
+		{acceptEncoding: "*", expected: true},
+		{acceptEncoding: "br, deflate", expected: false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.acceptEncoding, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, acceptsGzip(tt.acceptEncoding))
+		})
+	}
+}
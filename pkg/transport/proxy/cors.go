@@ -0,0 +1,79 @@
+package proxy
+
+import (
+	"fmt"
+	"net/http"
+	"net/url"
+	"slices"
+	"strings"
+)
+
+const (
+	// corsAllowedMethods are the methods of the MCP transports browsers may use in cross-origin requests
+	corsAllowedMethods = "GET, POST, DELETE, OPTIONS"
+	// corsExposedHeaders are the response headers of the MCP transports browsers let web clients read
+	corsExposedHeaders = "Mcp-Session-Id"
+	// corsMaxAge is how long, in seconds, browsers may cache the response to a preflight request
+	corsMaxAge = "600"
+)
+
+// ValidateCORSOrigin returns an error if an origin allowed to make cross-origin requests is not "*"
+// or a scheme and host, with an optional port, such as https://app.example.com
+func ValidateCORSOrigin(origin string) error {
+	if origin == "*" {
+		return nil
+	}
+	u, err := url.Parse(origin)
+	if err != nil || u.Scheme == "" || u.Host == "" || (u.Path != "" && u.Path != "/") || u.RawQuery != "" {
+		return fmt.Errorf("invalid CORS origin %q, expected SCHEME://HOST[:PORT] or *", origin)
+	}
+	return nil
+}
+
+// CORSMiddleware lets web clients of the given origins, or of any origin with "*", connect to the proxy
+// from a browser. It answers the preflight requests of these origins itself, without passing them to the
+// other middlewares, since browsers send them without the credentials of the client, and rejects the
+// preflight requests of other origins with 403 Forbidden.
+//
+// It must be the first middleware, so its headers are set on the responses of the other middlewares too.
+func CORSMiddleware(origins []string) func(http.Handler) http.Handler {
+	allowed := make([]string, 0, len(origins))
+	for _, origin := range origins {
+		allowed = append(allowed, strings.TrimSuffix(origin, "/"))
+	}
+	allowAny := slices.Contains(allowed, "*")
+
+	return func(next http.Handler) http.Handler {
+		return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			origin := r.Header.Get("Origin")
+			if origin == "" {
+				next.ServeHTTP(w, r)
+				return
+			}
+			w.Header().Add("Vary", "Origin")
+
+			preflight := r.Method == http.MethodOptions && r.Header.Get("Access-Control-Request-Method") != ""
+			if !allowAny && !slices.Contains(allowed, origin) {
+				if preflight {
+					http.Error(w, "Origin not allowed", http.StatusForbidden)
+					return
+				}
+				next.ServeHTTP(w, r)
+				return
+			}
+
+			w.Header().Set("Access-Control-Allow-Origin", origin)
+			if !preflight {
+				w.Header().Set("Access-Control-Expose-Headers", corsExposedHeaders)
+				next.ServeHTTP(w, r)
+				return
+			}
+			w.Header().Set("Access-Control-Allow-Methods", corsAllowedMethods)
+			if headers := r.Header.Get("Access-Control-Request-Headers"); headers != "" {
+				w.Header().Set("Access-Control-Allow-Headers", headers)
+			}
+			w.Header().Set("Access-Control-Max-Age", corsMaxAge)
+			w.WriteHeader(http.StatusNoContent)
+		})
+	}
+}
@@ -0,0 +1,136 @@
+package proxy
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestValidateCORSOrigin(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name        string
+		origin      string
+		expectError bool
+	}{
+		{name: "Any origin", origin: "*"},
+		{name: "Scheme and host", origin: "https://app.example.com"},
+		{name: "Scheme, host and port", origin: "http://localhost:3000"},
+		{name: "Trailing slash", origin: "https://app.example.com/"},
+		{name: "Host without scheme", origin: "app.example.com", expectError: true},
+		{name: "Path", origin: "https://app.example.com/mcp", expectError: true},
+		{name: "Empty", origin: "", expectError: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			err := ValidateCORSOrigin(tt.origin)
+			if tt.expectError {
+				assert.Error(t, err)
+			} else {
+				assert.NoError(t, err)
+			}
+		})
+	}
+}
+
+func TestCORSMiddleware(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name           string
+		origins        []string
+		method         string
+		origin         string
+		requestMethod  string
+		expectStatus   int
+		expectOrigin   string
+		expectNextCall bool
+	}{
+		{
+			name:           "Same-origin request",
+			origins:        []string{"https://app.example.com"},
+			method:         http.MethodPost,
+			expectStatus:   http.StatusOK,
+			expectNextCall: true,
+		},
+		{
+			name:           "Allowed origin",
+			origins:        []string{"https://app.example.com"},
+			method:         http.MethodPost,
+			origin:         "https://app.example.com",
+			expectStatus:   http.StatusOK,
+			expectOrigin:   "https://app.example.com",
+			expectNextCall: true,
+		},
+		{
+			name:           "Other origin",
+			origins:        []string{"https://app.example.com"},
+			method:         http.MethodGet,
+			origin:         "https://evil.example.com",
+			expectStatus:   http.StatusOK,
+			expectNextCall: true,
+		},
+		{
+			name:          "Preflight of allowed origin",
+			origins:       []string{"https://app.example.com/"},
+			method:        http.MethodOptions,
+			origin:        "https://app.example.com",
+			requestMethod: http.MethodPost,
+			expectStatus:  http.StatusNoContent,
+			expectOrigin:  "https://app.example.com",
+		},
+		{
+			name:          "Preflight of any origin",
+			origins:       []string{"*"},
+			method:        http.MethodOptions,
+			origin:        "http://localhost:3000",
+			requestMethod: http.MethodGet,
+			expectStatus:  http.StatusNoContent,
+			expectOrigin:  "http://localhost:3000",
+		},
+		{
+			name:          "Preflight of other origin",
+			origins:       []string{"https://app.example.com"},
+			method:        http.MethodOptions,
+			origin:        "https://evil.example.com",
+			requestMethod: http.MethodPost,
+			expectStatus:  http.StatusForbidden,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			nextCalled := false
+			handler := CORSMiddleware(tt.origins)(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+				nextCalled = true
+				w.WriteHeader(http.StatusOK)
+			}))
+
+			req := httptest.NewRequest(tt.method, "/sse", nil)
+			if tt.origin != "" {
+				req.Header.Set("Origin", tt.origin)
+			}
+			if tt.requestMethod != "" {
+				req.Header.Set("Access-Control-Request-Method", tt.requestMethod)
+				req.Header.Set("Access-Control-Request-Headers", "authorization, content-type")
+			}
+			rec := httptest.NewRecorder()
+			handler.ServeHTTP(rec, req)
+
+			assert.Equal(t, tt.expectStatus, rec.Code)
+			assert.Equal(t, tt.expectOrigin, rec.Header().Get("Access-Control-Allow-Origin"))
+			assert.Equal(t, tt.expectNextCall, nextCalled)
+			if tt.expectStatus == http.StatusNoContent {
+				assert.Equal(t, "authorization, content-type", rec.Header().Get("Access-Control-Allow-Headers"))
+				assert.Contains(t, rec.Header().Get("Access-Control-Allow-Methods"), tt.requestMethod)
+			}
+		})
+	}
+}
@@ -0,0 +1,49 @@
+package proxy
+
+import (
+	"context"
+	"fmt"
+	"net/http"
+	"strings"
+
+	"github.com/stacklok/toolhive/pkg/environment"
+	"github.com/stacklok/toolhive/pkg/secrets"
+)
+
+// ParseUpstreamHeaders parses headers given as "Name: value" into the headers to send to the upstream server.
+// References in the values are resolved like the environment variables of servers: ${VAR} with the
+// environment variable VAR of the host and ${secret:name} with the secret name of secretsManager, which
+// lets the values contain secrets without writing them on the command line; $${ stands for a literal ${.
+func ParseUpstreamHeaders(ctx context.Context, values []string, secretsManager secrets.Provider) (http.Header, error) {
+	headers := make(http.Header, len(values))
+	for _, value := range values {
+		name, raw, ok := strings.Cut(value, ":")
+		name = strings.TrimSpace(name)
+		if !ok || name == "" || strings.ContainsAny(name, " \t\r\n") {
+			return nil, fmt.Errorf("invalid upstream header %q, expected NAME: VALUE", value)
+		}
+
+		expanded, err := environment.ExpandReferences(ctx, strings.TrimSpace(raw), secretsManager)
+		if err != nil {
+			return nil, fmt.Errorf("failed to resolve upstream header %s: %w", name, err)
+		}
+		if strings.ContainsAny(expanded, "\r\n") {
+			return nil, fmt.Errorf("invalid value for upstream header %s", name)
+		}
+		headers.Add(name, expanded)
+	}
+	return headers, nil
+}
+
+// UpstreamHeaderMiddleware sets the headers on the requests forwarded to the upstream server,
+// replacing the values of the same headers sent by clients.
+func UpstreamHeaderMiddleware(headers http.Header) func(http.Handler) http.Handler {
+	return func(next http.Handler) http.Handler {
+		return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			for name, values := range headers {
+				r.Header[name] = values
+			}
+			next.ServeHTTP(w, r)
+		})
+	}
+}
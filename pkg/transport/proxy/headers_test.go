@@ -0,0 +1,129 @@
+package proxy
+
+import (
+	"context"
+	"errors"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/secrets"
+)
+
+// fakeSecretsProvider is a secrets provider holding secrets in memory
+type fakeSecretsProvider struct {
+	secrets.Provider
+	secrets map[string]string
+}
+
+func (f *fakeSecretsProvider) GetSecret(_ context.Context, name string) (string, error) {
+	if value, ok := f.secrets[name]; ok {
+		return value, nil
+	}
+	return "", errors.New("secret not found")
+}
+
+func TestParseUpstreamHeaders(t *testing.T) {
+	t.Parallel()
+
+	provider := &fakeSecretsProvider{secrets: map[string]string{"api-key": "s3cret", "multiline": "a\nb"}}
+
+	tests := []struct {
+		name        string
+		values      []string
+		expected    http.Header
+		expectError bool
+	}{
+		{
+			name:     "No headers",
+			expected: http.Header{},
+		},
+		{
+			name:     "Literal value",
+			values:   []string{"X-Api-Version: 2"},
+			expected: http.Header{"X-Api-Version": {"2"}},
+		},
+		{
+			name:     "Secret reference",
+			values:   []string{"Authorization: Bearer ${secret:api-key}"},
+			expected: http.Header{"Authorization": {"Bearer s3cret"}},
+		},
+		{
+			name:     "Dollars and escaped reference",
+			values:   []string{"x-token: ${secret:api-key}-$1-$${literal}"},
+			expected: http.Header{"X-Token": {"s3cret-$1-${literal}"}},
+		},
+		{
+			name:     "Repeated header",
+			values:   []string{"X-Scope: read", "X-Scope: write"},
+			expected: http.Header{"X-Scope": {"read", "write"}},
+		},
+		{
+			name:        "Missing colon",
+			values:      []string{"Authorization Bearer token"},
+			expectError: true,
+		},
+		{
+			name:        "Empty name",
+			values:      []string{": value"},
+			expectError: true,
+		},
+		{
+			name:        "Unknown secret",
+			values:      []string{"Authorization: Bearer ${secret:missing}"},
+			expectError: true,
+		},
+		{
+			name:        "Value with a line break",
+			values:      []string{"X-Token: ${secret:multiline}"},
+			expectError: true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			headers, err := ParseUpstreamHeaders(context.Background(), tt.values, provider)
+			if tt.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			assert.Equal(t, tt.expected, headers)
+		})
+	}
+}
+
+func TestParseUpstreamHeaders_EnvironmentVariable(t *testing.T) {
+	t.Setenv("THV_TEST_UPSTREAM_TOKEN", "env-token")
+
+	headers, err := ParseUpstreamHeaders(context.Background(),
+		[]string{"Authorization: Bearer ${THV_TEST_UPSTREAM_TOKEN}"}, nil)
+	require.NoError(t, err)
+	assert.Equal(t, "Bearer env-token", headers.Get("Authorization"))
+
+	// Secrets cannot be resolved without a secrets provider
+	_, err = ParseUpstreamHeaders(context.Background(), []string{"Authorization: Bearer ${secret:api-key}"}, nil)
+	assert.Error(t, err)
+}
+
+func TestUpstreamHeaderMiddleware(t *testing.T) {
+	t.Parallel()
+
+	var received http.Header
+	handler := UpstreamHeaderMiddleware(http.Header{"Authorization": {"Bearer upstream"}})(
+		http.HandlerFunc(func(_ http.ResponseWriter, r *http.Request) {
+			received = r.Header.Clone()
+		}))
+
+	req := httptest.NewRequest(http.MethodPost, "/mcp", nil)
+	req.Header.Set("Authorization", "Bearer client")
+	req.Header.Set("Content-Type", "application/json")
+	handler.ServeHTTP(httptest.NewRecorder(), req)
+
+	assert.Equal(t, "Bearer upstream", received.Get("Authorization"))
+	assert.Equal(t, "application/json", received.Get("Content-Type"))
+}
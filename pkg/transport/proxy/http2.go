@@ -0,0 +1,46 @@
+package proxy
+
+import (
+	"net/http"
+	"strings"
+)
+
+// IsGRPCRequest returns true if a request is a gRPC request, which must be forwarded over HTTP/2
+// for its trailers and its streams
+func IsGRPCRequest(r *http.Request) bool {
+	return strings.HasPrefix(r.Header.Get("Content-Type"), "application/grpc")
+}
+
+// protocolTransport forwards the requests which need HTTP/2 to the MCP server over HTTP/2, with prior knowledge
+// (h2c) for cleartext servers, and the other requests over HTTP/1.1. Each stream of an HTTP/2 connection has its
+// own flow control, so a slow stream doesn't hold back the others.
+type protocolTransport struct {
+	http1 *http.Transport
+	http2 *http.Transport
+	// http2Clients is set to forward the requests of HTTP/2 clients over HTTP/2 too
+	http2Clients bool
+}
+
+// newProtocolTransport returns a transport forwarding the requests over HTTP/1.1 with the given transport,
+// or over HTTP/2 with a copy of it
+func newProtocolTransport(transport *http.Transport, http2Clients bool) *protocolTransport {
+	http2 := transport.Clone()
+	http2.Protocols = &http.Protocols{}
+	http2.Protocols.SetHTTP2(true)
+	http2.Protocols.SetUnencryptedHTTP2(true)
+	return &protocolTransport{http1: transport, http2: http2, http2Clients: http2Clients}
+}
+
+// RoundTrip forwards a request over HTTP/2 if it is a gRPC request or, if enabled, a request of an HTTP/2 client
+func (t *protocolTransport) RoundTrip(r *http.Request) (*http.Response, error) {
+	if IsGRPCRequest(r) || (t.http2Clients && r.ProtoMajor == 2) {
+		return t.http2.RoundTrip(r)
+	}
+	return t.http1.RoundTrip(r)
+}
+
+// CloseIdleConnections closes the idle connections of both protocols
+func (t *protocolTransport) CloseIdleConnections() {
+	t.http1.CloseIdleConnections()
+	t.http2.CloseIdleConnections()
+}
@@ -0,0 +1,64 @@
+package proxy
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/networking"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+func TestUpstreamTransport_Protocols(t *testing.T) {
+	t.Parallel()
+
+	server := httptest.NewUnstartedServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		w.Header().Set("X-Proto", r.Proto)
+		w.WriteHeader(http.StatusNoContent)
+	}))
+	server.Config.Protocols = networking.ProxyProtocols()
+	server.Start()
+	t.Cleanup(server.Close)
+
+	tests := []struct {
+		name          string
+		contentType   string
+		clientHTTP2   bool
+		upstreamHTTP2 bool
+		expectedProto string
+	}{
+		{name: "JSON request", contentType: "application/json", expectedProto: "HTTP/1.1"},
+		{name: "gRPC request", contentType: "application/grpc+proto", expectedProto: "HTTP/2.0"},
+		{name: "HTTP/2 client", contentType: "application/json", clientHTTP2: true, expectedProto: "HTTP/1.1"},
+		{
+			name:          "HTTP/2 client with upstream HTTP/2",
+			contentType:   "application/json",
+			clientHTTP2:   true,
+			upstreamHTTP2: true,
+			expectedProto: "HTTP/2.0",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			req, err := http.NewRequest(http.MethodPost, server.URL, strings.NewReader("{}"))
+			require.NoError(t, err)
+			req.Header.Set("Content-Type", tt.contentType)
+			if tt.clientHTTP2 {
+				req.Proto, req.ProtoMajor, req.ProtoMinor = "HTTP/2.0", 2, 0
+			}
+
+			resp, err := UpstreamTransport(types.ProxyLimits{}, tt.upstreamHTTP2).RoundTrip(req)
+			require.NoError(t, err)
+			defer resp.Body.Close()
+
+			assert.Equal(t, tt.expectedProto, resp.Header.Get("X-Proto"))
+		})
+	}
+}
@@ -4,9 +4,13 @@ package httpsse
 
 import (
 	"context"
+	"crypto/tls"
 	"fmt"
 	"io"
+	"net"
 	"net/http"
+	"slices"
+	"sort"
 	"sync"
 	"time"
 
@@ -15,6 +19,8 @@ import (
 
 	"github.com/stacklok/toolhive/pkg/healthcheck"
 	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/networking"
+	"github.com/stacklok/toolhive/pkg/transport/proxy"
 	"github.com/stacklok/toolhive/pkg/transport/ssecommon"
 	"github.com/stacklok/toolhive/pkg/transport/types"
 )
@@ -54,6 +60,10 @@ type HTTPSSEProxy struct {
 	containerName string
 	middlewares   []types.Middleware
 
+	// Paths of the SSE and JSON-RPC message endpoints
+	ssePath      string
+	messagesPath string
+
 	// HTTP server
 	server     *http.Server
 	shutdownCh chan struct{}
@@ -61,10 +71,28 @@ type HTTPSSEProxy struct {
 	// Optional Prometheus metrics handler
 	prometheusHandler http.Handler
 
+	// Optional Windows named pipe on which the proxy is served in addition to the port
+	namedPipe string
+
+	// Optional Unix domain socket on which the proxy is served instead of the port
+	listenSocket string
+
+	// Optional TLS configuration, with which the proxy serves HTTPS on the port
+	tlsConfig *tls.Config
+
 	// SSE clients
 	sseClients      map[string]*ssecommon.SSEClient
 	sseClientsMutex sync.Mutex
 
+	// Sessions of clients of a previous proxy process, which are accepted until the clients reconnect
+	restoredSessions map[string]struct{}
+
+	// Sessions of Streamable HTTP clients, and the requests of these clients waiting for their response
+	// by the ID they were forwarded with
+	streamableSessions map[string]struct{}
+	streamableRequests map[string]chan *jsonrpc2.Response
+	streamableMutex    sync.Mutex
+
 	// Pending messages for SSE clients
 	pendingMessages []*ssecommon.PendingSSEMessage
 	pendingMutex    sync.Mutex
@@ -74,22 +102,56 @@ type HTTPSSEProxy struct {
 
 	// Health checker
 	healthChecker *healthcheck.HealthChecker
+
+	// Pings of the health checker waiting for their response, by request ID
+	pings      map[string]chan error
+	pingsMutex sync.Mutex
+
+	// unhealthyHandler is called when the MCP server becomes unhealthy, if set
+	unhealthyHandler func(ctx context.Context)
+
+	// Timeouts of the proxy and maximum size of the messages of the clients
+	limits types.ProxyLimits
 }
 
 // NewHTTPSSEProxy creates a new HTTP SSE proxy for transports.
+// If ssePath or messagesPath are empty, the default SSE and message endpoints are used.
 func NewHTTPSSEProxy(
-	host string, port int, containerName string, prometheusHandler http.Handler, middlewares ...types.Middleware,
+	host string,
+	port int,
+	containerName string,
+	ssePath string,
+	messagesPath string,
+	prometheusHandler http.Handler,
+	namedPipe string,
+	tlsConfig *tls.Config,
+	middlewares ...types.Middleware,
 ) *HTTPSSEProxy {
+	if ssePath == "" {
+		ssePath = ssecommon.HTTPSSEEndpoint
+	}
+	if messagesPath == "" {
+		messagesPath = ssecommon.HTTPMessagesEndpoint
+	}
+
 	proxy := &HTTPSSEProxy{
-		middlewares:       middlewares,
-		host:              host,
-		port:              port,
-		containerName:     containerName,
-		shutdownCh:        make(chan struct{}),
-		messageCh:         make(chan jsonrpc2.Message, 100),
-		sseClients:        make(map[string]*ssecommon.SSEClient),
-		pendingMessages:   []*ssecommon.PendingSSEMessage{},
-		prometheusHandler: prometheusHandler,
+		middlewares:        middlewares,
+		host:               host,
+		port:               port,
+		containerName:      containerName,
+		ssePath:            ssePath,
+		messagesPath:       messagesPath,
+		shutdownCh:         make(chan struct{}),
+		messageCh:          make(chan jsonrpc2.Message, 100),
+		sseClients:         make(map[string]*ssecommon.SSEClient),
+		restoredSessions:   make(map[string]struct{}),
+		streamableSessions: make(map[string]struct{}),
+		streamableRequests: make(map[string]chan *jsonrpc2.Response),
+		pings:              make(map[string]chan error),
+		pendingMessages:    []*ssecommon.PendingSSEMessage{},
+		prometheusHandler:  prometheusHandler,
+		namedPipe:          namedPipe,
+		tlsConfig:          tlsConfig,
 	}
 
 	// Create MCP pinger and health checker
@@ -108,15 +170,23 @@ func applyMiddlewares(handler http.Handler, middlewares ...types.Middleware) htt
 	return handler
 }
 
+// withDefaultPath serves requests to a custom endpoint path as if they were made to the default path,
+// since middlewares recognize the SSE and message endpoints by their default paths
+func withDefaultPath(defaultPath string, handler http.Handler) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		r.URL.Path = defaultPath
+		r.URL.RawPath = ""
+		handler.ServeHTTP(w, r)
+	})
+}
+
 // Start starts the HTTP SSE proxy.
-func (p *HTTPSSEProxy) Start(_ context.Context) error {
+func (p *HTTPSSEProxy) Start(ctx context.Context) error {
 	// Create a new HTTP server
 	mux := http.NewServeMux()
 
-	// Add handlers for SSE and JSON-RPC with middlewares
-	// At some point we should add support for Streamable HTTP transport here
-	// https://modelcontextprotocol.io/specification/2025-03-26/basic/transports#streamable-http
-	mux.Handle(ssecommon.HTTPSSEEndpoint, applyMiddlewares(
+	// Add handlers for SSE, JSON-RPC and Streamable HTTP with middlewares
+	mux.Handle(p.ssePath, withDefaultPath(ssecommon.HTTPSSEEndpoint, applyMiddlewares(
 		http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
 			if r.Method != http.MethodGet {
 				http.Error(w, "Method not allowed", http.StatusMethodNotAllowed)
@@ -125,9 +195,12 @@ func (p *HTTPSSEProxy) Start(_ context.Context) error {
 			p.handleSSEConnection(w, r)
 		}),
 		p.middlewares...,
-	))
+	)))
 
-	mux.Handle(ssecommon.HTTPMessagesEndpoint, applyMiddlewares(http.HandlerFunc(p.handlePostRequest), p.middlewares...))
+	mux.Handle(p.messagesPath, withDefaultPath(ssecommon.HTTPMessagesEndpoint,
+		applyMiddlewares(http.HandlerFunc(p.handlePostRequest), p.middlewares...)))
+
+	mux.Handle(streamablePath, applyMiddlewares(http.HandlerFunc(p.handleStreamableRequest), p.middlewares...))
 
 	// Add health check endpoint with MCP status (no middlewares)
 	mux.Handle("/health", p.healthChecker)
@@ -143,19 +216,38 @@ func (p *HTTPSSEProxy) Start(_ context.Context) error {
 		Addr:              fmt.Sprintf("%s:%d", p.host, p.port),
 		Handler:           mux,
 		ReadHeaderTimeout: 10 * time.Second, // Prevent Slowloris attacks
+		IdleTimeout:       p.limits.IdleTimeout,
+		Protocols:         networking.ProxyProtocols(),
+		TLSConfig:         p.tlsConfig,
+	}
+	if p.namedPipe != "" {
+		if err := networking.ServeNamedPipe(p.server, p.namedPipe); err != nil {
+			return err
+		}
+	}
+	var listener net.Listener
+	if p.listenSocket != "" {
+		var err error
+		if listener, err = networking.ListenUnixSocket(p.listenSocket); err != nil {
+			return err
+		}
 	}
 
 	// Start the server in a goroutine
 	go func() {
 		logger.Infof("HTTP proxy started for container %s on port %d", p.containerName, p.port)
-		logger.Infof("SSE endpoint: http://%s:%d%s", p.host, p.port, ssecommon.HTTPSSEEndpoint)
-		logger.Infof("JSON-RPC endpoint: http://%s:%d%s", p.host, p.port, ssecommon.HTTPMessagesEndpoint)
+		scheme := networking.ServerScheme(p.server)
+		logger.Infof("SSE endpoint: %s://%s:%d%s", scheme, p.host, p.port, p.ssePath)
+		logger.Infof("JSON-RPC endpoint: %s://%s:%d%s", scheme, p.host, p.port, p.messagesPath)
+		logger.Infof("Streamable HTTP endpoint: %s://%s:%d%s", scheme, p.host, p.port, streamablePath)
 
-		if err := p.server.ListenAndServe(); err != nil && err != http.ErrServerClosed {
+		if err := networking.Serve(p.server, listener); err != nil && err != http.ErrServerClosed {
 			logger.Errorf("HTTP server error: %v", err)
 		}
 	}()
 
+	go p.monitorHealth(ctx)
+
 	return nil
 }
 
@@ -190,7 +282,12 @@ func (p *HTTPSSEProxy) SendMessageToDestination(msg jsonrpc2.Message) error {
 }
 
 // ForwardResponseToClients forwards a response from the destination to all connected SSE clients.
+// Responses to pings of the health checker and to Streamable HTTP requests are delivered to them instead.
 func (p *HTTPSSEProxy) ForwardResponseToClients(_ context.Context, msg jsonrpc2.Message) error {
+	if p.deliverPingResponse(msg) || p.deliverStreamableResponse(msg) {
+		return nil
+	}
+
 	// Serialize the message to JSON
 	data, err := jsonrpc2.EncodeMessage(msg)
 	if err != nil {
@@ -218,15 +315,103 @@ func (p *HTTPSSEProxy) ForwardResponseToClients(_ context.Context, msg jsonrpc2.
 	return nil
 }
 
+// registerPing registers a ping of the health checker, and returns the channel on which its result is delivered
+func (p *HTTPSSEProxy) registerPing(id string) <-chan error {
+	responseCh := make(chan error, 1)
+	p.pingsMutex.Lock()
+	p.pings[id] = responseCh
+	p.pingsMutex.Unlock()
+	return responseCh
+}
+
+// unregisterPing removes a ping of the health checker, once it was answered or timed out
+func (p *HTTPSSEProxy) unregisterPing(id string) {
+	p.pingsMutex.Lock()
+	delete(p.pings, id)
+	p.pingsMutex.Unlock()
+}
+
+// deliverPingResponse delivers a response to a ping of the health checker, and returns false if the
+// message is not such a response
+func (p *HTTPSSEProxy) deliverPingResponse(msg jsonrpc2.Message) bool {
+	resp, ok := msg.(*jsonrpc2.Response)
+	if !ok {
+		return false
+	}
+	id, ok := resp.ID.Raw().(string)
+	if !ok {
+		return false
+	}
+
+	p.pingsMutex.Lock()
+	responseCh, ok := p.pings[id]
+	delete(p.pings, id)
+	p.pingsMutex.Unlock()
+	if !ok {
+		return false
+	}
+	responseCh <- resp.Error
+	return true
+}
+
+// SetUnhealthyHandler sets the function called when the MCP server becomes unhealthy, e.g. to restart it
+func (p *HTTPSSEProxy) SetUnhealthyHandler(handler func(ctx context.Context)) {
+	p.unhealthyHandler = handler
+}
+
+// SetListenSocket sets the path of a Unix domain socket on which the proxy is served instead of its port.
+// It must be called before the proxy is started.
+func (p *HTTPSSEProxy) SetListenSocket(path string) {
+	p.listenSocket = path
+}
+
+// SetLimits sets the timeouts of the proxy and the maximum size of the messages of the clients.
+// It must be called before the proxy is started.
+func (p *HTTPSSEProxy) SetLimits(limits types.ProxyLimits) {
+	p.limits = limits
+}
+
+// monitorHealth probes the health of the MCP server periodically, so its health is reported without delay,
+// and calls the unhealthy handler whenever the MCP server becomes unhealthy.
+func (p *HTTPSSEProxy) monitorHealth(ctx context.Context) {
+	ticker := time.NewTicker(healthcheck.ProbeInterval)
+	defer ticker.Stop()
+
+	wasUnhealthy := false
+	for {
+		select {
+		case <-ctx.Done():
+			return
+		case <-p.shutdownCh:
+			return
+		case <-ticker.C:
+			// A paused server cannot respond, but it is not failing either
+			if proxy.IsPaused(ctx, p.containerName) {
+				continue
+			}
+			unhealthy := p.healthChecker.Probe(ctx).Status == healthcheck.StatusUnhealthy
+			if unhealthy && !wasUnhealthy {
+				logger.Warnf("MCP server %s is unhealthy", p.containerName)
+				if p.unhealthyHandler != nil {
+					p.unhealthyHandler(ctx)
+				}
+			}
+			wasUnhealthy = unhealthy
+		}
+	}
+}
+
 // handleSSEConnection handles an SSE connection.
 func (p *HTTPSSEProxy) handleSSEConnection(w http.ResponseWriter, r *http.Request) {
 	// Set headers for SSE
 	w.Header().Set("Content-Type", "text/event-stream")
 	w.Header().Set("Cache-Control", "no-cache")
-	w.Header().Set("Connection", "keep-alive")
-	w.Header().Set("Access-Control-Allow-Origin", "*")
+	// Connection-specific headers are not allowed in HTTP/2
+	if r.ProtoMajor == 1 {
+		w.Header().Set("Connection", "keep-alive")
+	}
 
-	// Create a unique client ID
+	// Create a unique client ID, unless the client resumes a session of a previous proxy process
 	clientID := uuid.New().String()
 
 	// Create a channel for sending messages to this client
@@ -234,6 +419,12 @@ func (p *HTTPSSEProxy) handleSSEConnection(w http.ResponseWriter, r *http.Reques
 
 	// Register the client
 	p.sseClientsMutex.Lock()
+	if sessionID := r.URL.Query().Get("session_id"); sessionID != "" {
+		if _, restored := p.restoredSessions[sessionID]; restored {
+			clientID = sessionID
+			delete(p.restoredSessions, sessionID)
+		}
+	}
 	p.sseClients[clientID] = &ssecommon.SSEClient{
 		MessageCh: messageCh,
 		CreatedAt: time.Now(),
@@ -263,7 +454,7 @@ func (p *HTTPSSEProxy) handleSSEConnection(w http.ResponseWriter, r *http.Reques
 	baseURL := fmt.Sprintf("%s://%s", scheme, host)
 
 	// Create and send the endpoint event
-	endpointURL := fmt.Sprintf("%s%s?session_id=%s", baseURL, ssecommon.HTTPMessagesEndpoint, clientID)
+	endpointURL := fmt.Sprintf("%s%s?session_id=%s", baseURL, p.messagesPath, clientID)
 	endpointMsg := ssecommon.NewSSEMessage("endpoint", endpointURL)
 
 	// Send the initial event
@@ -326,6 +517,9 @@ func (p *HTTPSSEProxy) handlePostRequest(w http.ResponseWriter, r *http.Request)
 	// Check if the session exists
 	p.sseClientsMutex.Lock()
 	_, exists := p.sseClients[sessionID]
+	if !exists {
+		_, exists = p.restoredSessions[sessionID]
+	}
 	p.sseClientsMutex.Unlock()
 
 	if !exists {
@@ -334,9 +528,8 @@ func (p *HTTPSSEProxy) handlePostRequest(w http.ResponseWriter, r *http.Request)
 	}
 
 	// Read the request body
-	body, err := io.ReadAll(r.Body)
-	if err != nil {
-		http.Error(w, fmt.Sprintf("Error reading request body: %v", err), http.StatusInternalServerError)
+	body, ok := p.readMessage(w, r)
+	if !ok {
 		return
 	}
 
@@ -363,6 +556,58 @@ func (p *HTTPSSEProxy) handlePostRequest(w http.ResponseWriter, r *http.Request)
 	}
 }
 
+// readMessage reads the body of a request carrying a JSON-RPC message, answering the request with an
+// error and returning false if it cannot be read or exceeds the maximum message size
+func (p *HTTPSSEProxy) readMessage(w http.ResponseWriter, r *http.Request) ([]byte, bool) {
+	if !proxy.LimitMessageSize(w, r, p.limits) {
+		return nil, false
+	}
+	body, err := io.ReadAll(r.Body)
+	if proxy.IsMessageTooLarge(err) {
+		proxy.WriteMessageTooLarge(w, p.limits)
+		return nil, false
+	}
+	if err != nil {
+		http.Error(w, fmt.Sprintf("Error reading request body: %v", err), http.StatusInternalServerError)
+		return nil, false
+	}
+	return body, true
+}
+
+// SessionIDs returns the IDs of the sessions of the clients of the proxy, including the Streamable HTTP
+// sessions and the restored sessions of clients which did not reconnect yet.
+func (p *HTTPSSEProxy) SessionIDs() []string {
+	ids := p.streamableSessionIDs()
+
+	p.sseClientsMutex.Lock()
+	defer p.sseClientsMutex.Unlock()
+
+	for id := range p.sseClients {
+		ids = append(ids, id)
+	}
+	for id := range p.restoredSessions {
+		ids = append(ids, id)
+	}
+	sort.Strings(ids)
+	return ids
+}
+
+// RestoreSessions accepts the sessions of clients of a previous proxy process,
+// so the clients can keep sending messages with their session IDs after the proxy is restarted
+// and resume their sessions when they reconnect to the SSE endpoint.
+func (p *HTTPSSEProxy) RestoreSessions(ids []string) {
+	streamableIDs := p.streamableSessionIDs()
+
+	p.sseClientsMutex.Lock()
+	defer p.sseClientsMutex.Unlock()
+
+	for _, id := range ids {
+		if _, connected := p.sseClients[id]; !connected && !slices.Contains(streamableIDs, id) {
+			p.restoredSessions[id] = struct{}{}
+		}
+	}
+}
+
 // sendSSEEvent sends an SSE event to all connected clients.
 func (p *HTTPSSEProxy) sendSSEEvent(msg *ssecommon.SSEMessage) error {
 	// Convert the message to an SSE-formatted string
@@ -0,0 +1,119 @@
+package httpsse
+
+import (
+	"context"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"golang.org/x/exp/jsonrpc2"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/transport/ssecommon"
+)
+
+func TestHTTPSSEProxy_RestoredSessions(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	proxy := NewHTTPSSEProxy("127.0.0.1", 0, "server", "", "", nil, "", nil)
+	proxy.sseClients["connected"] = &ssecommon.SSEClient{MessageCh: make(chan string, 1)}
+	proxy.RestoreSessions([]string{"restored", "connected"})
+
+	assert.Equal(t, []string{"connected", "restored"}, proxy.SessionIDs())
+	assert.NotContains(t, proxy.restoredSessions, "connected")
+
+	tests := []struct {
+		name           string
+		sessionID      string
+		expectedStatus int
+	}{
+		{
+			name:           "Connected session",
+			sessionID:      "connected",
+			expectedStatus: http.StatusAccepted,
+		},
+		{
+			name:           "Restored session",
+			sessionID:      "restored",
+			expectedStatus: http.StatusAccepted,
+		},
+		{
+			name:           "Unknown session",
+			sessionID:      "unknown",
+			expectedStatus: http.StatusNotFound,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			body := strings.NewReader(`{"jsonrpc":"2.0","id":1,"method":"tools/list"}`)
+			req := httptest.NewRequest(http.MethodPost, "/messages?session_id="+tt.sessionID, body)
+			rec := httptest.NewRecorder()
+
+			proxy.handlePostRequest(rec, req)
+
+			assert.Equal(t, tt.expectedStatus, rec.Code)
+		})
+	}
+}
+
+func TestMCPPinger_Ping(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	tests := []struct {
+		name      string
+		respError error
+		wantErr   bool
+	}{
+		{name: "Answered ping"},
+		{name: "Failed ping", respError: jsonrpc2.ErrInternal, wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			proxy := NewHTTPSSEProxy("127.0.0.1", 0, "server", "", "", nil, "", nil)
+			// Answer the ping like the MCP server
+			go func() {
+				msg := <-proxy.GetMessageChannel()
+				req, ok := msg.(*jsonrpc2.Request)
+				if !ok {
+					return
+				}
+				resp, _ := jsonrpc2.NewResponse(req.ID, map[string]any{}, tt.respError)
+				_ = proxy.ForwardResponseToClients(context.Background(), resp)
+			}()
+
+			_, err := NewMCPPinger(proxy).Ping(context.Background())
+			if tt.wantErr {
+				assert.Error(t, err)
+			} else {
+				require.NoError(t, err)
+			}
+			// The response is not forwarded to the clients
+			assert.Empty(t, proxy.pendingMessages)
+			assert.Empty(t, proxy.pings)
+		})
+	}
+}
+
+func TestMCPPinger_PingTimeout(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	proxy := NewHTTPSSEProxy("127.0.0.1", 0, "server", "", "", nil, "", nil)
+	ctx, cancel := context.WithTimeout(context.Background(), 50*time.Millisecond)
+	defer cancel()
+
+	_, err := NewMCPPinger(proxy).Ping(ctx)
+	assert.Error(t, err)
+	assert.Empty(t, proxy.pings)
+}
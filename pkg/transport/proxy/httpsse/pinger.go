@@ -46,6 +46,10 @@ func (p *MCPPinger) Ping(ctx context.Context) (time.Duration, error) {
 		return 0, fmt.Errorf("failed to create ping request: %w", err)
 	}
 
+	// The response is not forwarded to the clients, but delivered to the pinger
+	responseCh := p.proxy.registerPing(pingID)
+	defer p.proxy.unregisterPing(pingID)
+
 	start := time.Now()
 
 	// Send the ping request
@@ -58,12 +62,16 @@ func (p *MCPPinger) Ping(ctx context.Context) (time.Duration, error) {
 		return 0, fmt.Errorf("message channel is full or closed")
 	}
 
-	// For HTTP SSE proxy, we don't have a direct response channel
-	// The response will be forwarded to SSE clients
-	// We'll measure the time it took to send the request
-	// In a real implementation, you might want to set up a response listener
-	duration := time.Since(start)
-
-	logger.Debugf("MCP ping request sent in %v", duration)
-	return duration, nil
+	// Wait for the response of the MCP server
+	select {
+	case err := <-responseCh:
+		duration := time.Since(start)
+		if err != nil {
+			return duration, fmt.Errorf("MCP ping failed: %w", err)
+		}
+		logger.Debugf("MCP ping response received in %v", duration)
+		return duration, nil
+	case <-ctx.Done():
+		return time.Since(start), fmt.Errorf("MCP ping timed out: %w", ctx.Err())
+	}
 }
@@ -0,0 +1,222 @@
+package httpsse
+
+import (
+	"fmt"
+	"net/http"
+
+	"github.com/google/uuid"
+	"golang.org/x/exp/jsonrpc2"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/transport/streamable"
+)
+
+const (
+	// StreamableSessionHeader is the header in which the session ID of Streamable HTTP clients is sent
+	StreamableSessionHeader = "Mcp-Session-Id"
+	// streamableIDPrefix is the prefix of the IDs Streamable HTTP requests are forwarded with, so their
+	// responses are told apart from the responses to the other clients
+	streamableIDPrefix = "streamable_"
+)
+
+// streamablePath is the path of the Streamable HTTP endpoint
+const streamablePath = "/" + streamable.HTTPStreamableHTTPEndpoint
+
+// handleStreamableRequest handles a request to the Streamable HTTP endpoint, following
+// https://modelcontextprotocol.io/specification/2025-03-26/basic/transports#streamable-http
+//
+// Each request of a client is answered with the response of the MCP server as a JSON body. The endpoint
+// doesn't open streams, so the requests and notifications of the MCP server are only sent to SSE clients.
+func (p *HTTPSSEProxy) handleStreamableRequest(w http.ResponseWriter, r *http.Request) {
+	switch r.Method {
+	case http.MethodPost:
+		p.handleStreamablePost(w, r)
+	case http.MethodDelete:
+		sessionID := r.Header.Get(StreamableSessionHeader)
+		if sessionID == "" {
+			http.Error(w, StreamableSessionHeader+" header is required", http.StatusBadRequest)
+			return
+		}
+		p.streamableMutex.Lock()
+		_, exists := p.streamableSessions[sessionID]
+		delete(p.streamableSessions, sessionID)
+		p.streamableMutex.Unlock()
+		if !exists {
+			http.Error(w, "Could not find session", http.StatusNotFound)
+			return
+		}
+		logger.Infof("Streamable HTTP session %s terminated", sessionID)
+		w.WriteHeader(http.StatusNoContent)
+	default:
+		// The MCP server can't send messages to Streamable HTTP clients outside of their requests
+		w.Header().Set("Allow", "POST, DELETE")
+		http.Error(w, "Method not allowed", http.StatusMethodNotAllowed)
+	}
+}
+
+// handleStreamablePost forwards a JSON-RPC message posted to the Streamable HTTP endpoint to the MCP server
+// and, if it is a request, answers it with the response of the MCP server
+func (p *HTTPSSEProxy) handleStreamablePost(w http.ResponseWriter, r *http.Request) {
+	body, ok := p.readMessage(w, r)
+	if !ok {
+		return
+	}
+	msg, err := jsonrpc2.DecodeMessage(body)
+	if err != nil {
+		http.Error(w, fmt.Sprintf("Error parsing JSON-RPC message: %v", err), http.StatusBadRequest)
+		return
+	}
+	req, isRequest := msg.(*jsonrpc2.Request)
+
+	sessionID, ok := p.streamableSession(w, r, isRequest && req.Method == "initialize")
+	if !ok {
+		return
+	}
+	w.Header().Set(StreamableSessionHeader, sessionID)
+
+	// Notifications and responses of the client are not answered
+	if !isRequest || !req.IsCall() {
+		if err := p.SendMessageToDestination(msg); err != nil {
+			http.Error(w, "Failed to send message to destination", http.StatusInternalServerError)
+			return
+		}
+		w.WriteHeader(http.StatusAccepted)
+		return
+	}
+
+	// The request is forwarded with a unique ID, since the IDs of different clients may collide
+	forwardID := streamableIDPrefix + uuid.New().String()
+	forwarded, err := jsonrpc2.NewCall(jsonrpc2.StringID(forwardID), req.Method, req.Params)
+	if err != nil {
+		http.Error(w, fmt.Sprintf("Error forwarding JSON-RPC request: %v", err), http.StatusInternalServerError)
+		return
+	}
+	responseCh := p.registerStreamableRequest(forwardID)
+	defer p.unregisterStreamableRequest(forwardID)
+
+	if err := p.SendMessageToDestination(forwarded); err != nil {
+		http.Error(w, "Failed to send message to destination", http.StatusInternalServerError)
+		return
+	}
+
+	p.writeStreamableResponse(w, r, req.ID, responseCh)
+}
+
+// streamableSession returns the ID of the Streamable HTTP session of a message, starting a new session for
+// the initialize request. Every other message must belong to a session, otherwise the request is answered
+// with an error and false is returned.
+func (p *HTTPSSEProxy) streamableSession(w http.ResponseWriter, r *http.Request, initialize bool) (string, bool) {
+	sessionID := r.Header.Get(StreamableSessionHeader)
+	switch {
+	case initialize:
+		sessionID = uuid.New().String()
+		p.streamableMutex.Lock()
+		p.streamableSessions[sessionID] = struct{}{}
+		p.streamableMutex.Unlock()
+		logger.Infof("Streamable HTTP session %s started", sessionID)
+	case sessionID == "":
+		http.Error(w, StreamableSessionHeader+" header is required", http.StatusBadRequest)
+		return "", false
+	case !p.hasStreamableSession(sessionID):
+		http.Error(w, "Could not find session", http.StatusNotFound)
+		return "", false
+	}
+	return sessionID, true
+}
+
+// writeStreamableResponse waits for the response of the MCP server to a forwarded request, and answers the
+// request of the client with it under the ID of the client
+func (p *HTTPSSEProxy) writeStreamableResponse(
+	w http.ResponseWriter,
+	r *http.Request,
+	id jsonrpc2.ID,
+	responseCh <-chan *jsonrpc2.Response,
+) {
+	select {
+	case resp := <-responseCh:
+		resp.ID = id
+		data, err := jsonrpc2.EncodeMessage(resp)
+		if err != nil {
+			http.Error(w, fmt.Sprintf("Error encoding JSON-RPC response: %v", err), http.StatusInternalServerError)
+			return
+		}
+		w.Header().Set("Content-Type", "application/json")
+		if _, err := w.Write(data); err != nil {
+			logger.Warnf("Warning: Failed to write response: %v", err)
+		}
+	case <-r.Context().Done():
+	case <-p.shutdownCh:
+		http.Error(w, "Proxy is shutting down", http.StatusServiceUnavailable)
+	}
+}
+
+// hasStreamableSession returns true if a Streamable HTTP session exists. Restored sessions of a previous
+// proxy process become Streamable HTTP sessions when they are used on the Streamable HTTP endpoint.
+func (p *HTTPSSEProxy) hasStreamableSession(sessionID string) bool {
+	p.streamableMutex.Lock()
+	defer p.streamableMutex.Unlock()
+	if _, exists := p.streamableSessions[sessionID]; exists {
+		return true
+	}
+
+	p.sseClientsMutex.Lock()
+	_, restored := p.restoredSessions[sessionID]
+	delete(p.restoredSessions, sessionID)
+	p.sseClientsMutex.Unlock()
+	if restored {
+		p.streamableSessions[sessionID] = struct{}{}
+	}
+	return restored
+}
+
+// registerStreamableRequest registers a forwarded Streamable HTTP request, and returns the channel on which
+// its response is delivered
+func (p *HTTPSSEProxy) registerStreamableRequest(id string) <-chan *jsonrpc2.Response {
+	responseCh := make(chan *jsonrpc2.Response, 1)
+	p.streamableMutex.Lock()
+	p.streamableRequests[id] = responseCh
+	p.streamableMutex.Unlock()
+	return responseCh
+}
+
+// unregisterStreamableRequest removes a forwarded Streamable HTTP request, once it was answered or abandoned
+func (p *HTTPSSEProxy) unregisterStreamableRequest(id string) {
+	p.streamableMutex.Lock()
+	delete(p.streamableRequests, id)
+	p.streamableMutex.Unlock()
+}
+
+// deliverStreamableResponse delivers a response to a Streamable HTTP request, and returns false if the
+// message is not such a response
+func (p *HTTPSSEProxy) deliverStreamableResponse(msg jsonrpc2.Message) bool {
+	resp, ok := msg.(*jsonrpc2.Response)
+	if !ok {
+		return false
+	}
+	id, ok := resp.ID.Raw().(string)
+	if !ok {
+		return false
+	}
+
+	p.streamableMutex.Lock()
+	responseCh, ok := p.streamableRequests[id]
+	delete(p.streamableRequests, id)
+	p.streamableMutex.Unlock()
+	if !ok {
+		return false
+	}
+	responseCh <- resp
+	return true
+}
+
+// streamableSessionIDs returns the IDs of the Streamable HTTP sessions
+func (p *HTTPSSEProxy) streamableSessionIDs() []string {
+	p.streamableMutex.Lock()
+	defer p.streamableMutex.Unlock()
+
+	ids := make([]string, 0, len(p.streamableSessions))
+	for id := range p.streamableSessions {
+		ids = append(ids, id)
+	}
+	return ids
+}
@@ -0,0 +1,142 @@
+package httpsse
+
+import (
+	"context"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"golang.org/x/exp/jsonrpc2"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+// answerRequests answers the requests sent to the MCP server with their method as result
+func answerRequests(proxy *HTTPSSEProxy) {
+	for msg := range proxy.GetMessageChannel() {
+		req, ok := msg.(*jsonrpc2.Request)
+		if !ok || !req.IsCall() {
+			continue
+		}
+		resp, _ := jsonrpc2.NewResponse(req.ID, map[string]string{"method": req.Method}, nil)
+		_ = proxy.ForwardResponseToClients(context.Background(), resp)
+	}
+}
+
+func TestHTTPSSEProxy_StreamableSession(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	proxy := NewHTTPSSEProxy("127.0.0.1", 0, "server", "", "", nil, "", nil)
+	go answerRequests(proxy)
+	t.Cleanup(func() { close(proxy.messageCh) })
+
+	// The initialize request starts a session, and is answered with the ID of the client
+	req := httptest.NewRequest(http.MethodPost, "/mcp",
+		strings.NewReader(`{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}`))
+	rec := httptest.NewRecorder()
+	proxy.handleStreamableRequest(rec, req)
+
+	require.Equal(t, http.StatusOK, rec.Code)
+	sessionID := rec.Header().Get(StreamableSessionHeader)
+	require.NotEmpty(t, sessionID)
+	assert.Equal(t, "application/json", rec.Header().Get("Content-Type"))
+	assert.JSONEq(t, `{"jsonrpc":"2.0","id":1,"result":{"method":"initialize"}}`, rec.Body.String())
+	assert.Contains(t, proxy.SessionIDs(), sessionID)
+	assert.Empty(t, proxy.pendingMessages)
+	assert.Empty(t, proxy.streamableRequests)
+
+	tests := []struct {
+		name           string
+		method         string
+		sessionID      string
+		body           string
+		expectedStatus int
+		expectedBody   string
+	}{
+		{
+			name:           "Request",
+			method:         http.MethodPost,
+			sessionID:      sessionID,
+			body:           `{"jsonrpc":"2.0","id":"a","method":"tools/list"}`,
+			expectedStatus: http.StatusOK,
+			expectedBody:   `{"jsonrpc":"2.0","id":"a","result":{"method":"tools/list"}}`,
+		},
+		{
+			name:           "Notification",
+			method:         http.MethodPost,
+			sessionID:      sessionID,
+			body:           `{"jsonrpc":"2.0","method":"notifications/initialized"}`,
+			expectedStatus: http.StatusAccepted,
+		},
+		{
+			name:           "Missing session",
+			method:         http.MethodPost,
+			body:           `{"jsonrpc":"2.0","id":2,"method":"tools/list"}`,
+			expectedStatus: http.StatusBadRequest,
+		},
+		{
+			name:           "Unknown session",
+			method:         http.MethodPost,
+			sessionID:      "unknown",
+			body:           `{"jsonrpc":"2.0","id":2,"method":"tools/list"}`,
+			expectedStatus: http.StatusNotFound,
+		},
+		{
+			name:           "Invalid message",
+			method:         http.MethodPost,
+			sessionID:      sessionID,
+			body:           `not json`,
+			expectedStatus: http.StatusBadRequest,
+		},
+		{
+			name:           "Stream",
+			method:         http.MethodGet,
+			sessionID:      sessionID,
+			expectedStatus: http.StatusMethodNotAllowed,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			req := httptest.NewRequest(tt.method, "/mcp", strings.NewReader(tt.body))
+			if tt.sessionID != "" {
+				req.Header.Set(StreamableSessionHeader, tt.sessionID)
+			}
+			rec := httptest.NewRecorder()
+			proxy.handleStreamableRequest(rec, req)
+
+			assert.Equal(t, tt.expectedStatus, rec.Code)
+			if tt.expectedBody != "" {
+				assert.JSONEq(t, tt.expectedBody, rec.Body.String())
+			}
+		})
+	}
+}
+
+func TestHTTPSSEProxy_StreamableDelete(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	proxy := NewHTTPSSEProxy("127.0.0.1", 0, "server", "", "", nil, "", nil)
+	proxy.streamableSessions["session"] = struct{}{}
+	proxy.RestoreSessions([]string{"session", "restored"})
+
+	// A restored session becomes a Streamable HTTP session when it is used on the Streamable HTTP endpoint
+	assert.True(t, proxy.hasStreamableSession("restored"))
+	assert.NotContains(t, proxy.restoredSessions, "restored")
+
+	for _, expectedStatus := range []int{http.StatusNoContent, http.StatusNotFound} {
+		req := httptest.NewRequest(http.MethodDelete, "/mcp", nil)
+		req.Header.Set(StreamableSessionHeader, "session")
+		rec := httptest.NewRecorder()
+		proxy.handleStreamableRequest(rec, req)
+		assert.Equal(t, expectedStatus, rec.Code)
+	}
+	assert.Equal(t, []string{"restored"}, proxy.SessionIDs())
+}
@@ -0,0 +1,54 @@
+package proxy
+
+import (
+	"errors"
+	"fmt"
+	"net"
+	"net/http"
+	"time"
+
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+// LimitMessageSize limits the body of a request to the maximum size of the messages of the limits. Requests
+// whose length is known to exceed it are answered with 413 Request Entity Too Large, and false is returned.
+// Reading more than the maximum size from the body of other requests fails with an error for which
+// IsMessageTooLarge returns true.
+func LimitMessageSize(w http.ResponseWriter, r *http.Request, limits types.ProxyLimits) bool {
+	if limits.MaxMessageSize <= 0 || r.Body == nil {
+		return true
+	}
+	if r.ContentLength > limits.MaxMessageSize {
+		WriteMessageTooLarge(w, limits)
+		return false
+	}
+	r.Body = http.MaxBytesReader(w, r.Body, limits.MaxMessageSize)
+	return true
+}
+
+// IsMessageTooLarge returns true if an error is the error of reading a body exceeding the maximum message size
+func IsMessageTooLarge(err error) bool {
+	var maxBytesErr *http.MaxBytesError
+	return errors.As(err, &maxBytesErr)
+}
+
+// WriteMessageTooLarge answers a request whose body exceeds the maximum message size
+func WriteMessageTooLarge(w http.ResponseWriter, limits types.ProxyLimits) {
+	http.Error(w, fmt.Sprintf("The message exceeds the maximum size of %d bytes", limits.MaxMessageSize),
+		http.StatusRequestEntityTooLarge)
+}
+
+// UpstreamTransport returns the transport with which the proxy connects to the MCP server, with the connect
+// and idle timeouts of the limits. gRPC requests are forwarded over HTTP/2, and so are the requests of HTTP/2
+// clients if http2 is set; other requests are forwarded over HTTP/1.1.
+func UpstreamTransport(limits types.ProxyLimits, http2 bool) http.RoundTripper {
+	transport := http.DefaultTransport.(*http.Transport).Clone()
+	if limits.ConnectTimeout > 0 {
+		dialer := &net.Dialer{Timeout: limits.ConnectTimeout, KeepAlive: 30 * time.Second}
+		transport.DialContext = dialer.DialContext
+	}
+	if limits.IdleTimeout > 0 {
+		transport.IdleConnTimeout = limits.IdleTimeout
+	}
+	return newProtocolTransport(transport, http2)
+}
@@ -0,0 +1,65 @@
+package proxy
+
+import (
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+func TestLimitMessageSize(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name           string
+		maxMessageSize int64
+		body           string
+		unknownLength  bool
+		expectAllowed  bool
+		expectTooLarge bool
+	}{
+		{name: "No limit", body: strings.Repeat("a", 100), expectAllowed: true},
+		{name: "Within limit", maxMessageSize: 100, body: strings.Repeat("a", 100), expectAllowed: true},
+		{name: "Declared length too large", maxMessageSize: 10, body: strings.Repeat("a", 100)},
+		{
+			name:           "Unknown length too large",
+			maxMessageSize: 10,
+			body:           strings.Repeat("a", 100),
+			unknownLength:  true,
+			expectAllowed:  true,
+			expectTooLarge: true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			req := httptest.NewRequest(http.MethodPost, "/messages", strings.NewReader(tt.body))
+			if tt.unknownLength {
+				req.ContentLength = -1
+			}
+			rec := httptest.NewRecorder()
+
+			allowed := LimitMessageSize(rec, req, types.ProxyLimits{MaxMessageSize: tt.maxMessageSize})
+			assert.Equal(t, tt.expectAllowed, allowed)
+			if !allowed {
+				assert.Equal(t, http.StatusRequestEntityTooLarge, rec.Code)
+				return
+			}
+
+			body, err := io.ReadAll(req.Body)
+			if tt.expectTooLarge {
+				assert.True(t, IsMessageTooLarge(err))
+				return
+			}
+			assert.NoError(t, err)
+			assert.Equal(t, tt.body, string(body))
+		})
+	}
+}
@@ -0,0 +1,99 @@
+package proxy
+
+import (
+	"context"
+	"fmt"
+	"net/http"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/state"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+// pausedCheckInterval is how long a proxy relies on the paused state it last read, so the marker is not
+// checked on every request
+const pausedCheckInterval = time.Second
+
+// MarkPaused marks the workload of a container as paused, so its proxy rejects requests. The marker is kept in
+// the state directory of ToolHive, which only the user can write to.
+func MarkPaused(ctx context.Context, containerName string) error {
+	store, err := state.NewPausedStore(state.DefaultAppName)
+	if err != nil {
+		return fmt.Errorf("failed to create state store: %w", err)
+	}
+	return store.Save(ctx, containerName, strings.NewReader("{}"))
+}
+
+// UnmarkPaused marks the workload of a container as no longer paused
+func UnmarkPaused(ctx context.Context, containerName string) error {
+	store, err := state.NewPausedStore(state.DefaultAppName)
+	if err != nil {
+		return fmt.Errorf("failed to create state store: %w", err)
+	}
+	exists, err := store.Exists(ctx, containerName)
+	if err != nil || !exists {
+		return err
+	}
+	return store.Delete(ctx, containerName)
+}
+
+// IsPaused returns true if the workload of a container is marked as paused
+func IsPaused(ctx context.Context, containerName string) bool {
+	store, err := state.NewPausedStore(state.DefaultAppName)
+	if err != nil {
+		logger.Debugf("Failed to create paused state store: %v", err)
+		return false
+	}
+	exists, err := store.Exists(ctx, containerName)
+	if err != nil {
+		logger.Debugf("Failed to check the paused state of %s: %v", containerName, err)
+	}
+	return exists
+}
+
+// pausedState caches whether the workload of a container is paused, reading the marker again at most once
+// per pausedCheckInterval
+type pausedState struct {
+	containerName string
+	now           func() time.Time
+
+	mutex   sync.Mutex
+	paused  bool
+	checked time.Time
+}
+
+// isPaused returns the cached paused state, reading it again if it is older than pausedCheckInterval
+func (s *pausedState) isPaused(ctx context.Context) bool {
+	s.mutex.Lock()
+	defer s.mutex.Unlock()
+
+	now := s.now()
+	if s.checked.IsZero() || now.Sub(s.checked) >= pausedCheckInterval {
+		s.paused = IsPaused(ctx, s.containerName)
+		s.checked = now
+	}
+	return s.paused
+}
+
+// PausedMiddleware rejects requests with 503 Service Unavailable while the workload of the container is paused,
+// instead of letting them hang until the frozen MCP server is resumed
+func PausedMiddleware(containerName string) types.Middleware {
+	return pausedMiddleware(&pausedState{containerName: containerName, now: time.Now})
+}
+
+func pausedMiddleware(paused *pausedState) types.Middleware {
+	return func(next http.Handler) http.Handler {
+		return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			if paused.isPaused(r.Context()) {
+				w.Header().Set("Retry-After", "60")
+				http.Error(w, fmt.Sprintf("MCP server %s is paused, run thv resume %s to resume it",
+					paused.containerName, paused.containerName), http.StatusServiceUnavailable)
+				return
+			}
+			next.ServeHTTP(w, r)
+		})
+	}
+}
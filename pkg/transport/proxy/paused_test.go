@@ -0,0 +1,55 @@
+package proxy
+
+import (
+	"context"
+	"net/http"
+	"net/http/httptest"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/adrg/xdg"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+func TestPausedMiddleware(t *testing.T) {
+	logger.Initialize()
+	// The paths are resolved again once the environment is restored
+	t.Cleanup(xdg.Reload)
+	t.Setenv("XDG_STATE_HOME", filepath.Join(t.TempDir(), "state"))
+	xdg.Reload()
+	ctx := context.Background()
+
+	containerName := "paused-middleware-test"
+	now := time.Now()
+	paused := &pausedState{containerName: containerName, now: func() time.Time { return now }}
+	handler := pausedMiddleware(paused)(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		w.WriteHeader(http.StatusOK)
+	}))
+	serve := func() *httptest.ResponseRecorder {
+		rec := httptest.NewRecorder()
+		handler.ServeHTTP(rec, httptest.NewRequest(http.MethodPost, "/messages", nil))
+		return rec
+	}
+
+	assert.False(t, IsPaused(ctx, containerName))
+	assert.Equal(t, http.StatusOK, serve().Code)
+
+	require.NoError(t, MarkPaused(ctx, containerName))
+	assert.True(t, IsPaused(ctx, containerName))
+	assert.Equal(t, http.StatusOK, serve().Code, "the paused state should be cached until the next check")
+
+	now = now.Add(pausedCheckInterval)
+	rec := serve()
+	assert.Equal(t, http.StatusServiceUnavailable, rec.Code)
+	assert.Contains(t, rec.Body.String(), "thv resume "+containerName)
+
+	require.NoError(t, UnmarkPaused(ctx, containerName))
+	require.NoError(t, UnmarkPaused(ctx, containerName), "unmarking twice should not fail")
+	assert.False(t, IsPaused(ctx, containerName))
+	now = now.Add(pausedCheckInterval)
+	assert.Equal(t, http.StatusOK, serve().Code)
+}
@@ -0,0 +1,247 @@
+// Package router provides a proxy routing the requests to several MCP servers from a single port,
+// with a path prefix for each server.
+package router
+
+import (
+	"bufio"
+	"context"
+	"crypto/tls"
+	"errors"
+	"fmt"
+	"io"
+	"net"
+	"net/http"
+	"net/http/httputil"
+	"net/url"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/networking"
+)
+
+// backendCacheTTL is how long the URL of the proxy of a server is used before it is resolved again,
+// so servers which are restarted on another port are found again
+const backendCacheTTL = 10 * time.Second
+
+// ErrUnknownServer is returned by resolvers for names which are not the names of MCP servers
+var ErrUnknownServer = errors.New("unknown MCP server")
+
+// Resolver returns the base URL of the proxy of the named MCP server, such as http://127.0.0.1:8080
+type Resolver func(ctx context.Context, name string) (*url.URL, error)
+
+// backend is the proxy of an MCP server requests are routed to
+type backend struct {
+	target   *url.URL
+	proxy    *httputil.ReverseProxy
+	resolved time.Time
+}
+
+// Router routes the requests to /NAME/PATH to PATH on the proxy of the MCP server NAME. Each session stays
+// on the server it was opened with, since the clients send all its requests under the prefix of the server:
+// the URL of the messages endpoint sent in the endpoint event of SSE streams is rewritten with the prefix.
+type Router struct {
+	host    string
+	port    int
+	resolve Resolver
+	token   string
+
+	mutex    sync.Mutex
+	backends map[string]*backend
+	server   *http.Server
+	now      func() time.Time
+}
+
+// NewRouter creates a router listening on the host and port, which finds the proxies of the servers with resolve
+func NewRouter(host string, port int, resolve Resolver) *Router {
+	return &Router{
+		host:     host,
+		port:     port,
+		resolve:  resolve,
+		backends: make(map[string]*backend),
+		now:      time.Now,
+	}
+}
+
+// SetToken sets the token sent to the proxies of the servers with each request, which lets them trust
+// the client address the router forwards
+func (rt *Router) SetToken(token string) {
+	rt.token = token
+}
+
+// Start starts the router, returning an error if it cannot listen on its address
+func (rt *Router) Start() error {
+	rt.server = &http.Server{
+		Addr:              net.JoinHostPort(rt.host, strconv.Itoa(rt.port)),
+		Handler:           rt,
+		ReadHeaderTimeout: 10 * time.Second, // Prevent Slowloris attacks
+	}
+	listener, err := net.Listen("tcp", rt.server.Addr)
+	if err != nil {
+		return fmt.Errorf("failed to listen on %s: %w", rt.server.Addr, err)
+	}
+
+	go func() {
+		logger.Infof("Router listening on http://%s", rt.server.Addr)
+		if err := rt.server.Serve(listener); err != nil && err != http.ErrServerClosed {
+			logger.Errorf("Router error: %v", err)
+		}
+	}()
+	return nil
+}
+
+// Stop stops the router
+func (rt *Router) Stop(ctx context.Context) error {
+	if rt.server == nil {
+		return nil
+	}
+	return rt.server.Shutdown(ctx)
+}
+
+// ServeHTTP routes a request to the proxy of the server named by the first segment of its path
+func (rt *Router) ServeHTTP(w http.ResponseWriter, r *http.Request) {
+	name, rest, _ := strings.Cut(strings.TrimPrefix(r.URL.Path, "/"), "/")
+	if name == "" {
+		http.NotFound(w, r)
+		return
+	}
+
+	b, err := rt.backend(r.Context(), name)
+	if errors.Is(err, ErrUnknownServer) {
+		http.Error(w, fmt.Sprintf("Unknown MCP server: %s", name), http.StatusNotFound)
+		return
+	}
+	if err != nil {
+		logger.Warnf("Failed to find the proxy of %s: %v", name, err)
+		http.Error(w, fmt.Sprintf("MCP server %s is not available", name), http.StatusBadGateway)
+		return
+	}
+
+	logger.Debugf("Router: %s %s -> %s", r.Method, r.URL.Path, b.target)
+	r = r.Clone(r.Context())
+	r.URL.Path = "/" + rest
+	r.URL.RawPath = ""
+	b.proxy.ServeHTTP(w, r)
+}
+
+// backend returns the backend of the named server, resolving its URL again once the cached one is too old
+func (rt *Router) backend(ctx context.Context, name string) (*backend, error) {
+	rt.mutex.Lock()
+	defer rt.mutex.Unlock()
+
+	if b, ok := rt.backends[name]; ok && rt.now().Sub(b.resolved) < backendCacheTTL {
+		return b, nil
+	}
+	target, err := rt.resolve(ctx, name)
+	if err != nil {
+		delete(rt.backends, name)
+		return nil, err
+	}
+	if b, ok := rt.backends[name]; ok && b.target.String() == target.String() {
+		b.resolved = rt.now()
+		return b, nil
+	}
+
+	b := &backend{target: target, proxy: newReverseProxy(name, target, rt.token), resolved: rt.now()}
+	rt.backends[name] = b
+	return b, nil
+}
+
+// newReverseProxy creates the reverse proxy forwarding the requests of a server to its proxy
+func newReverseProxy(name string, target *url.URL, token string) *httputil.ReverseProxy {
+	prefix := "/" + name
+	proxy := &httputil.ReverseProxy{
+		Rewrite: func(pr *httputil.ProxyRequest) {
+			pr.SetURL(target)
+			// The proxy of the server builds the URL of its messages endpoint with the host of the request
+			pr.Out.Host = pr.In.Host
+			pr.SetXForwarded()
+			// The token set by clients is replaced, so only the router can prove where a request comes from
+			pr.Out.Header.Del(TokenHeader)
+			if token != "" {
+				pr.Out.Header.Set(TokenHeader, token)
+			}
+		},
+		ModifyResponse: func(resp *http.Response) error {
+			if strings.HasPrefix(resp.Header.Get("Content-Type"), "text/event-stream") {
+				resp.Body = newEndpointRewriter(resp.Body, prefix)
+			}
+			return nil
+		},
+	}
+	// The proxies of the servers listen on the loopback interface, and use self-signed certificates
+	// when they serve HTTPS, so only their address is checked
+	if target.Scheme == "https" && networking.IsLoopbackHost(target.Hostname()) {
+		transport := http.DefaultTransport.(*http.Transport).Clone()
+		transport.TLSClientConfig = &tls.Config{InsecureSkipVerify: true} // #nosec G402 - loopback only
+		proxy.Transport = transport
+	}
+	return proxy
+}
+
+// endpointRewriter rewrites the URL of the endpoint events of an SSE stream, which clients send their
+// messages to, so it is under the prefix of the server in the router
+type endpointRewriter struct {
+	body     io.ReadCloser
+	reader   *bufio.Reader
+	prefix   string
+	endpoint bool
+	pending  []byte
+}
+
+func newEndpointRewriter(body io.ReadCloser, prefix string) *endpointRewriter {
+	return &endpointRewriter{body: body, reader: bufio.NewReader(body), prefix: prefix}
+}
+
+// Read reads the stream line by line, rewriting the data of endpoint events
+func (e *endpointRewriter) Read(p []byte) (int, error) {
+	for len(e.pending) == 0 {
+		line, err := e.reader.ReadString('\n')
+		if line != "" {
+			e.pending = []byte(e.rewriteLine(line))
+		}
+		if err != nil {
+			if len(e.pending) > 0 {
+				break
+			}
+			return 0, err
+		}
+	}
+	n := copy(p, e.pending)
+	e.pending = e.pending[n:]
+	return n, nil
+}
+
+// Close closes the stream
+func (e *endpointRewriter) Close() error {
+	return e.body.Close()
+}
+
+// rewriteLine rewrites a line of the stream if it is the data of an endpoint event
+func (e *endpointRewriter) rewriteLine(line string) string {
+	trimmed := strings.TrimRight(line, "\r\n")
+	switch {
+	case strings.HasPrefix(trimmed, "event:"):
+		e.endpoint = strings.TrimSpace(strings.TrimPrefix(trimmed, "event:")) == "endpoint"
+	case e.endpoint && strings.HasPrefix(trimmed, "data:"):
+		data := strings.TrimSpace(strings.TrimPrefix(trimmed, "data:"))
+		return "data: " + prefixEndpoint(data, e.prefix) + "\n"
+	case trimmed == "":
+		e.endpoint = false
+	}
+	return line
+}
+
+// prefixEndpoint adds the prefix to the path of the URL of a messages endpoint
+func prefixEndpoint(endpoint, prefix string) string {
+	u, err := url.Parse(endpoint)
+	if err != nil || (u.Host == "" && !strings.HasPrefix(u.Path, "/")) {
+		// Paths relative to the SSE endpoint are already under the prefix
+		return endpoint
+	}
+	u.Path = prefix + u.Path
+	u.RawPath = ""
+	return u.String()
+}
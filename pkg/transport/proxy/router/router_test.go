@@ -0,0 +1,155 @@
+package router
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"net/url"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+// newBackend starts a server answering with its name and the path of the requests, and sending an
+// endpoint event with the URL of its messages endpoint on /sse like the proxy of a stdio server
+func newBackend(t *testing.T, name string) *httptest.Server {
+	t.Helper()
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.URL.Path == "/sse" {
+			w.Header().Set("Content-Type", "text/event-stream")
+			fmt.Fprintf(w, "event: endpoint\ndata: http://%s/messages?session_id=abc\n\n", r.Host)
+			fmt.Fprint(w, "event: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{}}\n\n")
+			return
+		}
+		fmt.Fprintf(w, "%s %s?%s", name, r.URL.Path, r.URL.RawQuery)
+	}))
+	t.Cleanup(server.Close)
+	return server
+}
+
+func TestRouter(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	github := newBackend(t, "github")
+	fetch := newBackend(t, "fetch")
+	backends := map[string]string{"github": github.URL, "fetch": fetch.URL}
+	router := NewRouter("127.0.0.1", 0, func(_ context.Context, name string) (*url.URL, error) {
+		target, ok := backends[name]
+		if !ok {
+			return nil, ErrUnknownServer
+		}
+		return url.Parse(target)
+	})
+	front := httptest.NewServer(router)
+	t.Cleanup(front.Close)
+
+	tests := []struct {
+		name         string
+		path         string
+		expectStatus int
+		expectBody   string
+	}{
+		{
+			name:         "Request to a server",
+			path:         "/github/messages?session_id=abc",
+			expectStatus: http.StatusOK,
+			expectBody:   "github /messages?session_id=abc",
+		},
+		{
+			name:         "Request to another server",
+			path:         "/fetch/mcp",
+			expectStatus: http.StatusOK,
+			expectBody:   "fetch /mcp?",
+		},
+		{
+			name:         "SSE stream",
+			path:         "/fetch/sse",
+			expectStatus: http.StatusOK,
+			expectBody: "event: endpoint\ndata: http://" + strings.TrimPrefix(front.URL, "http://") +
+				"/fetch/messages?session_id=abc\n\nevent: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{}}\n\n",
+		},
+		{
+			name:         "Unknown server",
+			path:         "/unknown/sse",
+			expectStatus: http.StatusNotFound,
+		},
+		{
+			name:         "No server",
+			path:         "/",
+			expectStatus: http.StatusNotFound,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			resp, err := http.Get(front.URL + tt.path)
+			require.NoError(t, err)
+			defer resp.Body.Close()
+			body, err := io.ReadAll(resp.Body)
+			require.NoError(t, err)
+
+			assert.Equal(t, tt.expectStatus, resp.StatusCode)
+			if tt.expectBody != "" {
+				assert.Equal(t, tt.expectBody, string(body))
+			}
+		})
+	}
+}
+
+func TestRouter_ResolvesAgain(t *testing.T) {
+	t.Parallel()
+
+	now := time.Date(2025, 1, 1, 0, 0, 0, 0, time.UTC)
+	resolutions := 0
+	router := NewRouter("127.0.0.1", 0, func(_ context.Context, _ string) (*url.URL, error) {
+		resolutions++
+		return url.Parse(fmt.Sprintf("http://127.0.0.1:%d", 8000+resolutions))
+	})
+	router.now = func() time.Time { return now }
+
+	first, err := router.backend(context.Background(), "github")
+	require.NoError(t, err)
+	cached, err := router.backend(context.Background(), "github")
+	require.NoError(t, err)
+	assert.Same(t, first, cached)
+
+	now = now.Add(backendCacheTTL)
+	moved, err := router.backend(context.Background(), "github")
+	require.NoError(t, err)
+	assert.Equal(t, "http://127.0.0.1:8002", moved.target.String())
+	assert.Equal(t, 2, resolutions)
+}
+
+func TestPrefixEndpoint(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name     string
+		endpoint string
+		expected string
+	}{
+		{
+			name:     "Absolute URL",
+			endpoint: "http://localhost:8080/messages?session_id=1",
+			expected: "http://localhost:8080/github/messages?session_id=1",
+		},
+		{name: "Absolute path", endpoint: "/messages/?session_id=1", expected: "/github/messages/?session_id=1"},
+		{name: "Relative path", endpoint: "messages?session_id=1", expected: "messages?session_id=1"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, prefixEndpoint(tt.endpoint, "/github"))
+		})
+	}
+}
@@ -0,0 +1,121 @@
+package router
+
+import (
+	"context"
+	"crypto/rand"
+	"crypto/subtle"
+	"encoding/hex"
+	"errors"
+	"fmt"
+	"net/http"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/adrg/xdg"
+)
+
+// TokenHeader is the header in which the router sends its token to the proxies of the servers, which
+// proves that a request comes from the router and so that its X-Forwarded-For header can be trusted
+const TokenHeader = "X-ToolHive-Gateway-Token"
+
+// tokenFile is the file holding the token shared by the router and the proxies of the servers
+const tokenFile = "toolhive/gateway.token"
+
+// tokenSize is the number of random bytes of the token
+const tokenSize = 32
+
+// LoadToken returns the token shared by the router and the proxies of the servers, creating it if it
+// does not exist yet. The file holding it is only readable by the user, like the servers and the router.
+func LoadToken() (string, error) {
+	path, err := xdg.DataFile(tokenFile)
+	if err != nil {
+		return "", fmt.Errorf("failed to get gateway token path: %w", err)
+	}
+	return loadToken(path)
+}
+
+func loadToken(path string) (string, error) {
+	token, err := readToken(path)
+	if err != nil || token != "" {
+		return token, err
+	}
+
+	random := make([]byte, tokenSize)
+	if _, err := rand.Read(random); err != nil {
+		return "", fmt.Errorf("failed to generate gateway token: %w", err)
+	}
+	token = hex.EncodeToString(random)
+
+	// The token is written to a temporary file which is then linked into place, so the file is never seen
+	// before the token is written, and the router and a proxy creating it at the same time agree on the token
+	temp, err := os.CreateTemp(filepath.Dir(path), ".gateway.token-*")
+	if err != nil {
+		return "", fmt.Errorf("failed to create gateway token: %w", err)
+	}
+	defer os.Remove(temp.Name())
+	if _, err := temp.WriteString(token); err != nil {
+		_ = temp.Close()
+		return "", fmt.Errorf("failed to write gateway token: %w", err)
+	}
+	if err := temp.Close(); err != nil {
+		return "", fmt.Errorf("failed to write gateway token: %w", err)
+	}
+	if err := os.Link(temp.Name(), path); err != nil {
+		if !errors.Is(err, os.ErrExist) {
+			return "", fmt.Errorf("failed to create gateway token: %w", err)
+		}
+		// Another process created the token first
+		token, err = readToken(path)
+		if err == nil && token == "" {
+			err = fmt.Errorf("gateway token file %s is empty", path)
+		}
+		return token, err
+	}
+	return token, nil
+}
+
+// readToken returns the token in the file, or an empty string if there is no token yet
+func readToken(path string) (string, error) {
+	data, err := os.ReadFile(filepath.Clean(path))
+	if errors.Is(err, os.ErrNotExist) {
+		return "", nil
+	}
+	if err != nil {
+		return "", fmt.Errorf("failed to read gateway token: %w", err)
+	}
+	return strings.TrimSpace(string(data)), nil
+}
+
+// HasToken returns true if the request carries the token of the router
+func HasToken(r *http.Request, token string) bool {
+	if token == "" {
+		return false
+	}
+	return subtle.ConstantTimeCompare([]byte(r.Header.Get(TokenHeader)), []byte(token)) == 1
+}
+
+// gatewayContextKey is the key of the context value recording that a request carried the token of the router
+type gatewayContextKey struct{}
+
+// TokenMiddleware removes the token of the router from every request, so it is never passed on to the server,
+// and records in the context of the request whether it carried the token. It is the outermost middleware of
+// the proxies of the servers, since the server must not see the token whatever else the proxy does.
+func TokenMiddleware(token string) func(http.Handler) http.Handler {
+	return func(next http.Handler) http.Handler {
+		return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			fromGateway := HasToken(r, token)
+			r.Header.Del(TokenHeader)
+			if fromGateway {
+				r = r.WithContext(context.WithValue(r.Context(), gatewayContextKey{}, true))
+			}
+			next.ServeHTTP(w, r)
+		})
+	}
+}
+
+// FromGateway returns true if TokenMiddleware found the token of the router in a request
+func FromGateway(r *http.Request) bool {
+	fromGateway, _ := r.Context().Value(gatewayContextKey{}).(bool)
+	return fromGateway
+}
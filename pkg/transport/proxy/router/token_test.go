@@ -0,0 +1,126 @@
+package router
+
+import (
+	"context"
+	"net/http"
+	"net/http/httptest"
+	"net/http/httputil"
+	"net/url"
+	"os"
+	"path/filepath"
+	"sync"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestLoadToken(t *testing.T) {
+	t.Parallel()
+
+	path := filepath.Join(t.TempDir(), "gateway.token")
+	token, err := loadToken(path)
+	require.NoError(t, err)
+	assert.Len(t, token, 2*tokenSize)
+
+	info, err := os.Stat(path)
+	require.NoError(t, err)
+	assert.Equal(t, os.FileMode(0600), info.Mode().Perm())
+
+	// The router and the proxies of the servers share the same token
+	again, err := loadToken(path)
+	require.NoError(t, err)
+	assert.Equal(t, token, again)
+}
+
+func TestLoadToken_Concurrent(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	path := filepath.Join(dir, "gateway.token")
+	tokens := make([]string, 10)
+	errs := make([]error, len(tokens))
+	var wg sync.WaitGroup
+	for i := range tokens {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			tokens[i], errs[i] = loadToken(path)
+		}()
+	}
+	wg.Wait()
+
+	// The router and the proxies starting at the same time never see an empty token, and agree on it
+	for i := range tokens {
+		require.NoError(t, errs[i])
+		assert.Len(t, tokens[i], 2*tokenSize)
+		assert.Equal(t, tokens[0], tokens[i])
+	}
+	entries, err := os.ReadDir(dir)
+	require.NoError(t, err)
+	assert.Len(t, entries, 1, "the temporary files should be removed")
+}
+
+func TestHasToken(t *testing.T) {
+	t.Parallel()
+
+	req := httptest.NewRequest(http.MethodPost, "/messages", nil)
+	assert.False(t, HasToken(req, "token"))
+
+	req.Header.Set(TokenHeader, "other")
+	assert.False(t, HasToken(req, "token"))
+
+	req.Header.Set(TokenHeader, "token")
+	assert.True(t, HasToken(req, "token"))
+	assert.False(t, HasToken(req, ""))
+}
+
+func TestTokenMiddleware(t *testing.T) {
+	t.Parallel()
+
+	const token = "gateway-token"
+	received := make(chan http.Header, 1)
+	upstream := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		received <- r.Header.Clone()
+		w.WriteHeader(http.StatusOK)
+	}))
+	t.Cleanup(upstream.Close)
+	upstreamURL, err := url.Parse(upstream.URL)
+	require.NoError(t, err)
+
+	// The proxy of the server forwards the requests to the server after its middlewares
+	fromGateway := make(chan bool, 1)
+	forward := httputil.NewSingleHostReverseProxy(upstreamURL)
+	proxy := httptest.NewServer(TokenMiddleware(token)(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		fromGateway <- FromGateway(r)
+		forward.ServeHTTP(w, r)
+	})))
+	t.Cleanup(proxy.Close)
+	proxyURL, err := url.Parse(proxy.URL)
+	require.NoError(t, err)
+
+	gateway := NewRouter("127.0.0.1", 0, func(_ context.Context, _ string) (*url.URL, error) {
+		return proxyURL, nil
+	})
+	gateway.SetToken(token)
+
+	// A request forwarded by the gateway
+	rec := httptest.NewRecorder()
+	gateway.ServeHTTP(rec, httptest.NewRequest(http.MethodPost, "/fetch/messages", nil))
+	require.Equal(t, http.StatusOK, rec.Code)
+	assert.True(t, <-fromGateway)
+	assert.Empty(t, (<-received).Values(TokenHeader), "the token should not be passed on to the server")
+
+	// A request of a client sending a token itself
+	for _, value := range []string{"guessed-token", token} {
+		req, err := http.NewRequest(http.MethodPost, proxy.URL+"/messages", nil)
+		require.NoError(t, err)
+		req.Header.Set(TokenHeader, value)
+		resp, err := http.DefaultClient.Do(req)
+		require.NoError(t, err)
+		resp.Body.Close()
+		require.Equal(t, http.StatusOK, resp.StatusCode)
+		assert.Equal(t, value == token, <-fromGateway)
+		assert.Empty(t, (<-received).Values(TokenHeader), "the token should not be passed on to the server")
+	}
+}
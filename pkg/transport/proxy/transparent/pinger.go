@@ -13,12 +13,18 @@ import (
 
 // MCPPinger implements healthcheck.MCPPinger for transparent proxies
 type MCPPinger struct {
-	targetURL string
+	targetURL func() string
 	client    *http.Client
 }
 
 // NewMCPPinger creates a new MCP pinger for transparent proxies
 func NewMCPPinger(targetURL string) healthcheck.MCPPinger {
+	return newMCPPinger(func() string { return targetURL })
+}
+
+// newMCPPinger creates a pinger checking the server at the URL returned by targetURL,
+// for proxies whose target changes
+func newMCPPinger(targetURL func() string) *MCPPinger {
 	return &MCPPinger{
 		targetURL: targetURL,
 		client: &http.Client{
@@ -33,14 +39,15 @@ func NewMCPPinger(targetURL string) healthcheck.MCPPinger {
 // if the server is responding.
 func (p *MCPPinger) Ping(ctx context.Context) (time.Duration, error) {
 	start := time.Now()
+	targetURL := p.targetURL()
 
 	// Create a simple GET request to check if the server is responding
-	req, err := http.NewRequestWithContext(ctx, http.MethodGet, p.targetURL, nil)
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, targetURL, nil)
 	if err != nil {
 		return 0, fmt.Errorf("failed to create HTTP request: %w", err)
 	}
 
-	logger.Debugf("Checking SSE server health at %s", p.targetURL)
+	logger.Debugf("Checking SSE server health at %s", targetURL)
 
 	// Send the request
 	resp, err := p.client.Do(req)
@@ -0,0 +1,448 @@
+package transparent
+
+import (
+	"bytes"
+	"context"
+	"fmt"
+	"io"
+	"net/http"
+	"sync"
+	"time"
+
+	"github.com/google/uuid"
+	"golang.org/x/exp/jsonrpc2"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/mcp"
+	"github.com/stacklok/toolhive/pkg/transport/bridge"
+	"github.com/stacklok/toolhive/pkg/transport/proxy"
+	"github.com/stacklok/toolhive/pkg/transport/ssecommon"
+	"github.com/stacklok/toolhive/pkg/transport/streamable"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+const (
+	// sessionIDHeader is the header carrying the session of the streamable HTTP transport
+	sessionIDHeader = "Mcp-Session-Id"
+	// shimMessageBuffer is how many messages of the server a shim session buffers for the stream of its client
+	shimMessageBuffer = 100
+	// maxScannedSize is how much of the response to an initialize request is scanned for its protocol version
+	maxScannedSize = 1024 * 1024
+)
+
+// streamablePath is the path of the streamable HTTP endpoint
+const streamablePath = "/" + streamable.HTTPStreamableHTTPEndpoint
+
+// shimSession is a session of a client speaking the other transport of MCP than the server, which the
+// proxy of an SSE server serves on its streamable HTTP endpoint, and the proxy of a streamable HTTP server
+// on its SSE endpoints. The messages of the client are forwarded to the server by a bridge speaking the
+// transport of the server, so old clients keep working with new servers and the other way around.
+type shimSession struct {
+	id       string
+	upstream *bridge.Bridge
+	input    *io.PipeWriter
+	// done is closed when the bridge stops
+	done chan struct{}
+
+	mutex   sync.Mutex
+	pending map[jsonrpc2.ID]chan []byte
+	// messages are the messages of the server which don't answer a pending request of the client
+	messages chan []byte
+}
+
+// SetServerTransport sets the transport the MCP server speaks, so that the proxy serves the other transport
+// of MCP through shim sessions. It must be called before the proxy is started.
+func (p *TransparentProxy) SetServerTransport(transportType types.TransportType) {
+	p.serverTransport = transportType
+}
+
+// serveShim serves the endpoints of the transport of MCP which the server doesn't speak, and returns false
+// for the requests which are forwarded to the server
+func (p *TransparentProxy) serveShim(w http.ResponseWriter, r *http.Request) bool {
+	switch p.serverTransport {
+	case types.TransportTypeSSE:
+		if r.URL.Path != streamablePath {
+			return false
+		}
+		p.handleShimStreamable(w, r)
+	case types.TransportTypeStreamableHTTP:
+		switch {
+		case r.URL.Path == ssecommon.HTTPSSEEndpoint && r.Method == http.MethodGet:
+			p.handleShimSSE(w, r)
+		case r.URL.Path == ssecommon.HTTPMessagesEndpoint && r.Method == http.MethodPost:
+			p.handleShimMessage(w, r)
+		default:
+			return false
+		}
+	default:
+		return false
+	}
+	return true
+}
+
+// handleShimStreamable serves the streamable HTTP endpoint of the proxy of an SSE server
+func (p *TransparentProxy) handleShimStreamable(w http.ResponseWriter, r *http.Request) {
+	switch r.Method {
+	case http.MethodPost:
+		p.handleShimStreamablePost(w, r)
+	case http.MethodGet:
+		session, ok := p.requireShimSession(w, r.Header.Get(sessionIDHeader))
+		if !ok {
+			return
+		}
+		p.streamShimMessages(w, r, session, "")
+	case http.MethodDelete:
+		session, ok := p.requireShimSession(w, r.Header.Get(sessionIDHeader))
+		if !ok {
+			return
+		}
+		session.close()
+		w.WriteHeader(http.StatusNoContent)
+	default:
+		w.Header().Set("Allow", "GET, POST, DELETE")
+		http.Error(w, "Method not allowed", http.StatusMethodNotAllowed)
+	}
+}
+
+// handleShimStreamablePost forwards a message posted to the streamable HTTP endpoint of the proxy of
+// an SSE server and, if it is a request, answers it with the response of the server
+func (p *TransparentProxy) handleShimStreamablePost(w http.ResponseWriter, r *http.Request) {
+	body, ok := p.readShimMessage(w, r)
+	if !ok {
+		return
+	}
+	msg, err := jsonrpc2.DecodeMessage(body)
+	if err != nil {
+		http.Error(w, fmt.Sprintf("Error parsing JSON-RPC message: %v", err), http.StatusBadRequest)
+		return
+	}
+	req, isRequest := msg.(*jsonrpc2.Request)
+
+	// A session is started by the initialize request, and every other message must belong to a session
+	var session *shimSession
+	sessionID := r.Header.Get(sessionIDHeader)
+	if isRequest && req.Method == "initialize" && sessionID == "" {
+		if session, err = p.newShimSession(); err != nil {
+			http.Error(w, fmt.Sprintf("Failed to connect to the MCP server: %v", err), http.StatusBadGateway)
+			return
+		}
+	} else if session, ok = p.requireShimSession(w, sessionID); !ok {
+		return
+	}
+	w.Header().Set(sessionIDHeader, session.id)
+
+	// Notifications and responses of the client are not answered
+	if !isRequest || !req.IsCall() {
+		if err := session.send(body); err != nil {
+			http.Error(w, fmt.Sprintf("Failed to forward the message: %v", err), http.StatusBadGateway)
+			return
+		}
+		w.WriteHeader(http.StatusAccepted)
+		return
+	}
+
+	response, err := session.request(r.Context(), req.ID, body)
+	if err != nil {
+		if r.Context().Err() == nil {
+			http.Error(w, fmt.Sprintf("Failed to forward the request: %v", err), http.StatusBadGateway)
+		}
+		return
+	}
+	w.Header().Set("Content-Type", "application/json")
+	if _, err := w.Write(response); err != nil {
+		logger.Warnf("Failed to write response: %v", err)
+	}
+}
+
+// handleShimSSE serves the SSE endpoint of the proxy of a streamable HTTP server: each stream is a session,
+// which ends when the client closes the stream
+func (p *TransparentProxy) handleShimSSE(w http.ResponseWriter, r *http.Request) {
+	session, err := p.newShimSession()
+	if err != nil {
+		http.Error(w, fmt.Sprintf("Failed to connect to the MCP server: %v", err), http.StatusBadGateway)
+		return
+	}
+	defer session.close()
+
+	endpoint := fmt.Sprintf("%s?session_id=%s", ssecommon.HTTPMessagesEndpoint, session.id)
+	p.streamShimMessages(w, r, session, ssecommon.NewSSEMessage("endpoint", endpoint).ToSSEString())
+}
+
+// handleShimMessage forwards a message posted to the messages endpoint of the proxy of a streamable HTTP
+// server. The response of the server is sent on the SSE stream of the session.
+func (p *TransparentProxy) handleShimMessage(w http.ResponseWriter, r *http.Request) {
+	session, ok := p.requireShimSession(w, r.URL.Query().Get("session_id"))
+	if !ok {
+		return
+	}
+	body, ok := p.readShimMessage(w, r)
+	if !ok {
+		return
+	}
+	if err := session.send(body); err != nil {
+		http.Error(w, fmt.Sprintf("Failed to forward the message: %v", err), http.StatusBadGateway)
+		return
+	}
+	w.WriteHeader(http.StatusAccepted)
+}
+
+// streamShimMessages sends the first event and then the messages of the server to the SSE stream
+// of a client, with keep-alive comments, until the client or the bridge of the session stops
+func (*TransparentProxy) streamShimMessages(w http.ResponseWriter, r *http.Request, session *shimSession, first string) {
+	flusher, ok := w.(http.Flusher)
+	if !ok {
+		http.Error(w, "Streaming not supported", http.StatusInternalServerError)
+		return
+	}
+	w.Header().Set("Content-Type", "text/event-stream")
+	w.Header().Set("Cache-Control", "no-cache")
+	w.Header().Set("Connection", "keep-alive")
+	w.WriteHeader(http.StatusOK)
+	if first != "" {
+		fmt.Fprint(w, first)
+	}
+	flusher.Flush()
+
+	ticker := time.NewTicker(keepAliveInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case message := <-session.messages:
+			fmt.Fprint(w, ssecommon.NewSSEMessage("message", string(message)).ToSSEString())
+			ticker.Reset(keepAliveInterval)
+		case <-ticker.C:
+			fmt.Fprint(w, ": keep-alive\n\n")
+		case <-session.done:
+			return
+		case <-r.Context().Done():
+			return
+		}
+		flusher.Flush()
+	}
+}
+
+// readShimMessage reads the message a client posts, answering the request if it can't be read
+func (p *TransparentProxy) readShimMessage(w http.ResponseWriter, r *http.Request) ([]byte, bool) {
+	body, err := io.ReadAll(r.Body)
+	if err != nil {
+		if proxy.IsMessageTooLarge(err) {
+			proxy.WriteMessageTooLarge(w, p.limits)
+		} else {
+			http.Error(w, fmt.Sprintf("Error reading request body: %v", err), http.StatusBadRequest)
+		}
+		return nil, false
+	}
+	return bytes.TrimSpace(body), true
+}
+
+// newShimSession starts a shim session, whose bridge connects to the endpoint of the transport of the server
+func (p *TransparentProxy) newShimSession() (*shimSession, error) {
+	targetURI := p.currentTargetURI()
+	serverURL := targetURI + ssecommon.HTTPSSEEndpoint
+	if p.serverTransport == types.TransportTypeStreamableHTTP {
+		serverURL = targetURI + streamablePath
+	}
+	b, err := bridge.New(serverURL, p.serverTransport, nil)
+	if err != nil {
+		return nil, err
+	}
+
+	reader, writer := io.Pipe()
+	session := &shimSession{
+		id:       uuid.New().String(),
+		upstream: b,
+		input:    writer,
+		done:     make(chan struct{}),
+		pending:  make(map[jsonrpc2.ID]chan []byte),
+		messages: make(chan []byte, shimMessageBuffer),
+	}
+	p.shimMutex.Lock()
+	p.shimSessions[session.id] = session
+	p.shimMutex.Unlock()
+
+	go func() {
+		defer close(session.done)
+		err := b.Run(context.Background(), reader, session)
+		if err != nil {
+			logger.Warnf("Session %s of %s ended: %v", session.id, p.containerName, err)
+		}
+		// The messages the client sends afterwards fail instead of waiting for the bridge
+		_ = reader.CloseWithError(bridge.ErrConnectionClosed)
+
+		p.shimMutex.Lock()
+		delete(p.shimSessions, session.id)
+		p.shimMutex.Unlock()
+	}()
+	return session, nil
+}
+
+// requireShimSession returns the shim session of the given ID, answering the request if there is none
+func (p *TransparentProxy) requireShimSession(w http.ResponseWriter, sessionID string) (*shimSession, bool) {
+	if sessionID == "" {
+		http.Error(w, "A session ID is required", http.StatusBadRequest)
+		return nil, false
+	}
+	p.shimMutex.Lock()
+	session, ok := p.shimSessions[sessionID]
+	p.shimMutex.Unlock()
+	if !ok {
+		http.Error(w, "Could not find session", http.StatusNotFound)
+		return nil, false
+	}
+	return session, true
+}
+
+// closeShimSessions ends all the shim sessions, when the proxy is stopped
+func (p *TransparentProxy) closeShimSessions() {
+	p.shimMutex.Lock()
+	defer p.shimMutex.Unlock()
+	for _, session := range p.shimSessions {
+		session.close()
+	}
+}
+
+// send forwards a message of the client to the server
+func (s *shimSession) send(message []byte) error {
+	_, err := s.input.Write(append(message, '\n'))
+	return err
+}
+
+// request forwards a request of the client to the server, and returns the response of the server
+func (s *shimSession) request(ctx context.Context, id jsonrpc2.ID, message []byte) ([]byte, error) {
+	responseCh := make(chan []byte, 1)
+	s.mutex.Lock()
+	s.pending[id] = responseCh
+	s.mutex.Unlock()
+	defer func() {
+		s.mutex.Lock()
+		delete(s.pending, id)
+		s.mutex.Unlock()
+	}()
+
+	if err := s.send(message); err != nil {
+		return nil, err
+	}
+	select {
+	case response := <-responseCh:
+		return response, nil
+	case <-s.done:
+		return nil, bridge.ErrConnectionClosed
+	case <-ctx.Done():
+		return nil, ctx.Err()
+	}
+}
+
+// Write receives the messages of the server from the bridge, which writes one message per call. The
+// responses to the pending requests are returned to them, and the other messages are sent to the stream
+// of the client.
+func (s *shimSession) Write(p []byte) (int, error) {
+	message := bytes.TrimSpace(p)
+	if msg, err := jsonrpc2.DecodeMessage(message); err == nil {
+		if resp, ok := msg.(*jsonrpc2.Response); ok {
+			s.mutex.Lock()
+			responseCh, pending := s.pending[resp.ID]
+			delete(s.pending, resp.ID)
+			s.mutex.Unlock()
+			if pending {
+				responseCh <- message
+				return len(p), nil
+			}
+		}
+	}
+
+	select {
+	case s.messages <- message:
+	default:
+		logger.Warnf("Dropping message of the MCP server for session %s, whose client is not reading its stream", s.id)
+	}
+	return len(p), nil
+}
+
+// close ends the session, which closes the session of the bridge with the server
+func (s *shimSession) close() {
+	_ = s.input.Close()
+}
+
+// protocolVersionReader records the protocol version of the response to an initialize request of the
+// streamable HTTP transport while the client reads it, whether it is sent as JSON or as an SSE stream
+type protocolVersionReader struct {
+	io.ReadCloser
+	record  func(version string)
+	data    []byte
+	scanned int
+	done    bool
+}
+
+// Read reads the response, looking for its protocol version in the data read so far
+func (r *protocolVersionReader) Read(p []byte) (int, error) {
+	n, err := r.ReadCloser.Read(p)
+	if r.done {
+		return n, err
+	}
+	r.data = append(r.data, p[:n]...)
+
+	var version string
+	for version == "" {
+		end := bytes.IndexByte(r.data[r.scanned:], '\n')
+		if end < 0 {
+			break
+		}
+		line := bytes.TrimSpace(r.data[r.scanned : r.scanned+end])
+		r.scanned += end + 1
+		version = mcp.NegotiatedProtocolVersion(bytes.TrimSpace(bytes.TrimPrefix(line, []byte("data:"))))
+	}
+	// JSON responses may be spread on several lines, and may not end with a newline. They are scanned as soon
+	// as they are complete, since the client may send its next request before the end of the body is read.
+	if version == "" {
+		version = mcp.NegotiatedProtocolVersion(bytes.TrimSpace(r.data))
+	}
+
+	if version != "" || err != nil || len(r.data) > maxScannedSize {
+		r.done = true
+		r.data = nil
+		if version != "" {
+			r.record(version)
+		}
+	}
+	return n, err
+}
+
+// recordProtocolVersion records the protocol version negotiated by a streamable HTTP session
+func (p *TransparentProxy) recordProtocolVersion(sessionID, version string) {
+	logger.Infof("Streamable HTTP session %s of %s started with protocol version %s", sessionID, p.containerName, version)
+	p.shimMutex.Lock()
+	defer p.shimMutex.Unlock()
+	p.protocolVersions[sessionID] = version
+}
+
+// ProtocolVersion returns the protocol version negotiated by a streamable HTTP session,
+// or an empty string if the session is unknown
+func (p *TransparentProxy) ProtocolVersion(sessionID string) string {
+	p.shimMutex.Lock()
+	defer p.shimMutex.Unlock()
+	if session, ok := p.shimSessions[sessionID]; ok {
+		return session.upstream.ProtocolVersion()
+	}
+	return p.protocolVersions[sessionID]
+}
+
+// trackProtocolVersion sends the protocol version negotiated by the streamable HTTP session of a request to
+// the server when the client doesn't, since clients of older protocol versions don't send it, and forgets the
+// version of the sessions the clients end
+func (p *TransparentProxy) trackProtocolVersion(r *http.Request) {
+	sessionID := r.Header.Get(sessionIDHeader)
+	if sessionID == "" {
+		return
+	}
+	if r.Method == http.MethodDelete {
+		p.shimMutex.Lock()
+		delete(p.protocolVersions, sessionID)
+		p.shimMutex.Unlock()
+		return
+	}
+	if r.Header.Get(mcp.ProtocolVersionHeader) == "" {
+		if version := p.ProtocolVersion(sessionID); version != "" {
+			r.Header.Set(mcp.ProtocolVersionHeader, version)
+		}
+	}
+}
@@ -0,0 +1,202 @@
+package transparent
+
+import (
+	"bufio"
+	"fmt"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"net/url"
+	"strings"
+	"sync"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/mcp"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+const initializeRequest = `{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-06-18"}}`
+
+// streamableServer is an MCP server of the streamable HTTP transport, which records the protocol version
+// header of the requests following the initialize request
+type streamableServer struct {
+	mutex         sync.Mutex
+	eventStream   bool
+	versionHeader string
+}
+
+func newStreamableServer(t *testing.T, eventStream bool) (*httptest.Server, *streamableServer) {
+	t.Helper()
+	s := &streamableServer{eventStream: eventStream}
+	server := httptest.NewServer(http.HandlerFunc(s.handle))
+	t.Cleanup(server.Close)
+	return server, s
+}
+
+func (s *streamableServer) handle(w http.ResponseWriter, r *http.Request) {
+	if r.URL.Path != streamablePath {
+		http.NotFound(w, r)
+		return
+	}
+	if r.Method == http.MethodDelete {
+		return
+	}
+	body, _ := io.ReadAll(r.Body)
+	w.Header().Set(sessionIDHeader, "session")
+
+	response := `{"jsonrpc":"2.0","id":2,"result":{}}`
+	if strings.Contains(string(body), `"method":"initialize"`) {
+		response = `{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-06-18"}}`
+	} else {
+		s.mutex.Lock()
+		s.versionHeader = r.Header.Get(mcp.ProtocolVersionHeader)
+		s.mutex.Unlock()
+	}
+	if !strings.Contains(string(body), `"id"`) {
+		w.WriteHeader(http.StatusAccepted)
+		return
+	}
+	if s.eventStream {
+		w.Header().Set("Content-Type", "text/event-stream")
+		fmt.Fprintf(w, "event: message\ndata: %s\n\n", response)
+		return
+	}
+	w.Header().Set("Content-Type", "application/json")
+	fmt.Fprint(w, response)
+}
+
+func (s *streamableServer) getVersionHeader() string {
+	s.mutex.Lock()
+	defer s.mutex.Unlock()
+	return s.versionHeader
+}
+
+func newShimProxy(t *testing.T, serverURL string, serverTransport types.TransportType) *httptest.Server {
+	t.Helper()
+	targetURL, err := url.Parse(serverURL)
+	require.NoError(t, err)
+	p := NewTransparentProxy("127.0.0.1", 0, "test", serverURL, nil, "", nil)
+	p.SetServerTransport(serverTransport)
+	front := httptest.NewServer(p.proxyHandler(targetURL))
+	t.Cleanup(front.Close)
+	return front
+}
+
+// postStreamable posts a message to a streamable HTTP endpoint, and returns the response
+func postStreamable(t *testing.T, endpoint, sessionID, body string) (*http.Response, string) {
+	t.Helper()
+	req, err := http.NewRequest(http.MethodPost, endpoint, strings.NewReader(body))
+	require.NoError(t, err)
+	req.Header.Set("Content-Type", "application/json")
+	req.Header.Set("Accept", "application/json, text/event-stream")
+	if sessionID != "" {
+		req.Header.Set(sessionIDHeader, sessionID)
+	}
+	resp, err := http.DefaultClient.Do(req)
+	require.NoError(t, err)
+	defer resp.Body.Close()
+	data, err := io.ReadAll(resp.Body)
+	require.NoError(t, err)
+	return resp, string(data)
+}
+
+func TestTransparentProxy_ShimStreamableClient(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	server, _ := newSSEServer(t)
+	front := newShimProxy(t, server.URL, types.TransportTypeSSE)
+
+	resp, body := postStreamable(t, front.URL+streamablePath, "", initializeRequest)
+	require.Equal(t, http.StatusOK, resp.StatusCode)
+	assert.JSONEq(t, `{"jsonrpc":"2.0","id":1,"result":{"session":"1"}}`, body)
+	sessionID := resp.Header.Get(sessionIDHeader)
+	require.NotEmpty(t, sessionID)
+
+	resp, _ = postStreamable(t, front.URL+streamablePath, sessionID, `{"jsonrpc":"2.0","method":"notifications/initialized"}`)
+	assert.Equal(t, http.StatusAccepted, resp.StatusCode)
+
+	resp, body = postStreamable(t, front.URL+streamablePath, sessionID, `{"jsonrpc":"2.0","id":2,"method":"ping"}`)
+	require.Equal(t, http.StatusOK, resp.StatusCode)
+	assert.JSONEq(t, `{"jsonrpc":"2.0","id":2,"result":{"session":"1"}}`, body)
+
+	resp, _ = postStreamable(t, front.URL+streamablePath, "unknown", `{"jsonrpc":"2.0","id":3,"method":"ping"}`)
+	assert.Equal(t, http.StatusNotFound, resp.StatusCode)
+
+	req, err := http.NewRequest(http.MethodDelete, front.URL+streamablePath, nil)
+	require.NoError(t, err)
+	req.Header.Set(sessionIDHeader, sessionID)
+	deleteResp, err := http.DefaultClient.Do(req)
+	require.NoError(t, err)
+	require.NoError(t, deleteResp.Body.Close())
+	assert.Equal(t, http.StatusNoContent, deleteResp.StatusCode)
+}
+
+func TestTransparentProxy_ShimSSEClient(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	server, state := newStreamableServer(t, false)
+	front := newShimProxy(t, server.URL, types.TransportTypeStreamableHTTP)
+
+	resp, err := http.Get(front.URL + "/sse")
+	require.NoError(t, err)
+	defer resp.Body.Close()
+	reader := bufio.NewReader(resp.Body)
+
+	eventType, endpoint := readEvent(t, reader)
+	require.Equal(t, "endpoint", eventType)
+	require.True(t, strings.HasPrefix(endpoint, "/messages?session_id="))
+
+	post := func(body string) {
+		resp, err := http.Post(front.URL+endpoint, "application/json", strings.NewReader(body))
+		require.NoError(t, err)
+		require.NoError(t, resp.Body.Close())
+		require.Equal(t, http.StatusAccepted, resp.StatusCode)
+	}
+
+	post(initializeRequest)
+	_, data := readEvent(t, reader)
+	assert.JSONEq(t, `{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-06-18"}}`, data)
+
+	// The bridge sends the negotiated protocol version, which SSE clients don't know about
+	post(`{"jsonrpc":"2.0","id":2,"method":"ping"}`)
+	_, data = readEvent(t, reader)
+	assert.JSONEq(t, `{"jsonrpc":"2.0","id":2,"result":{}}`, data)
+	assert.Equal(t, "2025-06-18", state.getVersionHeader())
+}
+
+func TestTransparentProxy_ProtocolVersionHeader(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	tests := []struct {
+		name        string
+		eventStream bool
+	}{
+		{name: "JSON response"},
+		{name: "Event stream response", eventStream: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			server, state := newStreamableServer(t, tt.eventStream)
+			front := newShimProxy(t, server.URL, types.TransportTypeStreamableHTTP)
+
+			resp, _ := postStreamable(t, front.URL+streamablePath, "", initializeRequest)
+			require.Equal(t, http.StatusOK, resp.StatusCode)
+			require.Equal(t, "session", resp.Header.Get(sessionIDHeader))
+
+			// Clients of older protocol versions don't send the header, so the proxy sends the negotiated version
+			resp, _ = postStreamable(t, front.URL+streamablePath, "session", `{"jsonrpc":"2.0","id":2,"method":"ping"}`)
+			require.Equal(t, http.StatusOK, resp.StatusCode)
+			assert.Equal(t, "2025-06-18", state.getVersionHeader())
+		})
+	}
+}
@@ -0,0 +1,420 @@
+package transparent
+
+import (
+	"bufio"
+	"bytes"
+	"context"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"strings"
+	"sync"
+	"time"
+
+	"golang.org/x/exp/jsonrpc2"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/mcp"
+	transportsession "github.com/stacklok/toolhive/pkg/transport/session"
+)
+
+const (
+	// keepAliveInterval is how often a comment is sent on the SSE streams of the clients when the server
+	// sends no events, so that idle connections are not closed by clients and intermediaries
+	keepAliveInterval = 30 * time.Second
+	// reconnectTimeout is how long the proxy tries to reconnect to the SSE endpoint of the server
+	// after its stream was closed, e.g. while the container of the server is restarted
+	reconnectTimeout = time.Minute
+	// reconnectInterval is how often the proxy tries to reconnect to the SSE endpoint of the server
+	reconnectInterval = time.Second
+)
+
+// sseSession is a session of the SSE transport, which the proxy keeps when it reconnects to the server
+// after the server closed its stream: the clients keep sending their messages to the endpoint of the
+// first stream, and the proxy forwards them to the endpoint of the current stream.
+type sseSession struct {
+	mutex sync.Mutex
+	// endpoint is the path and query of the messages endpoint of the current stream of the server
+	endpoint string
+	// target is the server of the current stream, to which the messages of the client are posted
+	target *upstreamTarget
+	// tracker tracks the requests of the client, whose handshake is replayed on the new streams
+	tracker transportsession.Tracker
+	// protocolVersion is the protocol version negotiated by the initialize request of the client
+	protocolVersion string
+}
+
+// currentEndpoint returns the path and query of the messages endpoint of the current stream of the server
+func (s *sseSession) currentEndpoint() string {
+	s.mutex.Lock()
+	defer s.mutex.Unlock()
+	return s.endpoint
+}
+
+// currentTarget returns the server of the current stream of the session
+func (s *sseSession) currentTarget() *upstreamTarget {
+	s.mutex.Lock()
+	defer s.mutex.Unlock()
+	return s.target
+}
+
+// recordRequest records a message the client posts, to replay its initialize handshake on the new streams
+func (s *sseSession) recordRequest(body []byte) {
+	msg, err := jsonrpc2.DecodeMessage(body)
+	if err != nil {
+		return
+	}
+	s.tracker.RecordRequest(msg)
+}
+
+// recordResponse records the data of an event of the server, and returns false if it is the response to
+// the replayed initialize request, which is not forwarded to the client
+func (s *sseSession) recordResponse(data string) bool {
+	msg, err := jsonrpc2.DecodeMessage([]byte(data))
+	if err != nil {
+		return true
+	}
+	resp, ok := msg.(*jsonrpc2.Response)
+	if !ok {
+		return true
+	}
+
+	if !s.tracker.RecordResponse(resp) {
+		// The client keeps using the version it negotiated, which the restarted server may no longer support
+		version := mcp.NegotiatedProtocolVersion([]byte(data))
+		if negotiated := s.negotiatedProtocolVersion(); negotiated != "" && version != negotiated {
+			logger.Warnf("The MCP server negotiated protocol version %s after reconnecting, but the client uses %s",
+				version, negotiated)
+		}
+		return false
+	}
+
+	initialize, _ := s.tracker.Handshake()
+	if initialize == nil || resp.ID != initialize.ID {
+		return true
+	}
+	s.mutex.Lock()
+	defer s.mutex.Unlock()
+	if s.protocolVersion == "" {
+		s.protocolVersion = mcp.NegotiatedProtocolVersion([]byte(data))
+		logger.Infof("SSE session started with protocol version %s", s.protocolVersion)
+	}
+	return true
+}
+
+// negotiatedProtocolVersion returns the protocol version negotiated by the initialize request of the client
+func (s *sseSession) negotiatedProtocolVersion() string {
+	s.mutex.Lock()
+	defer s.mutex.Unlock()
+	return s.protocolVersion
+}
+
+// routeSSEMessage forwards a message the client posts to the endpoint of the first stream of its
+// session to the endpoint of the current stream, records the message, and returns the server of the
+// current stream, or nil if the message is not posted to a session of the SSE transport
+func (p *TransparentProxy) routeSSEMessage(r *http.Request) (*upstreamTarget, error) {
+	if r.Method != http.MethodPost {
+		return nil, nil
+	}
+	p.sseSessionsMutex.Lock()
+	clientSession, ok := p.sseSessions[r.URL.RequestURI()]
+	p.sseSessionsMutex.Unlock()
+	if !ok {
+		return nil, nil
+	}
+
+	body, err := io.ReadAll(r.Body)
+	if err != nil {
+		return nil, err
+	}
+	r.Body = io.NopCloser(bytes.NewReader(body))
+	clientSession.recordRequest(body)
+
+	if endpoint := clientSession.currentEndpoint(); endpoint != r.URL.RequestURI() {
+		if u, err := url.Parse(endpoint); err == nil {
+			r.URL.Path, r.URL.RawPath, r.URL.RawQuery = u.Path, u.RawPath, u.RawQuery
+		}
+	}
+	return clientSession.currentTarget(), nil
+}
+
+// modifyResponse wraps the SSE streams the clients open with GET requests, so they get keep-alive
+// comments and survive the restarts of the server, and records the protocol versions of the streamable
+// HTTP sessions the server starts
+func (p *TransparentProxy) modifyResponse(resp *http.Response) error {
+	sessionID := resp.Header.Get(sessionIDHeader)
+	if sessionID != "" && resp.Request.Method == http.MethodPost && resp.Request.Header.Get(sessionIDHeader) == "" {
+		resp.Body = &protocolVersionReader{
+			ReadCloser: resp.Body,
+			record:     func(version string) { p.recordProtocolVersion(sessionID, version) },
+		}
+		return nil
+	}
+	if resp.Request.Method != http.MethodGet || resp.StatusCode != http.StatusOK ||
+		!strings.HasPrefix(resp.Header.Get("Content-Type"), "text/event-stream") {
+		return nil
+	}
+	resp.Body = p.newSSEStream(resp.Request, resp.Body)
+	return nil
+}
+
+// sseStream is the SSE stream of a client, which forwards the events of the stream of the server with
+// keep-alive comments, and reconnects to the server when its stream is closed
+type sseStream struct {
+	proxy   *TransparentProxy
+	ctx     context.Context
+	cancel  context.CancelFunc
+	request *http.Request
+	// target is the server of the current stream, which is replaced when the stream is reconnected
+	target *upstreamTarget
+
+	events  chan string
+	pending []byte
+	ticker  *time.Ticker
+
+	// session is the SSE transport session of the stream, which is nil until the endpoint event is received
+	// and for the streams of the streamable HTTP transport
+	session *sseSession
+	// key is the path and query of the messages endpoint of the first stream, which the client posts to,
+	// guarded by the mutex of the sessions of the proxy
+	key string
+}
+
+// newSSEStream creates the stream of the client of a request, reading the events of body
+func (p *TransparentProxy) newSSEStream(req *http.Request, body io.ReadCloser) *sseStream {
+	ctx, cancel := context.WithCancel(req.Context())
+	s := &sseStream{
+		proxy:   p,
+		ctx:     ctx,
+		cancel:  cancel,
+		request: req,
+		target:  requestTarget(req),
+		events:  make(chan string),
+		ticker:  time.NewTicker(keepAliveInterval),
+	}
+	go s.pump(body)
+	return s
+}
+
+// Read returns the events of the server, or a keep-alive comment if the server sent no event for a while
+func (s *sseStream) Read(p []byte) (int, error) {
+	if len(s.pending) == 0 {
+		select {
+		case event, ok := <-s.events:
+			if !ok {
+				return 0, io.EOF
+			}
+			s.pending = []byte(event)
+			s.ticker.Reset(keepAliveInterval)
+		case <-s.ticker.C:
+			s.pending = []byte(": keep-alive\n\n")
+		case <-s.ctx.Done():
+			return 0, io.EOF
+		}
+	}
+	n := copy(p, s.pending)
+	s.pending = s.pending[n:]
+	return n, nil
+}
+
+// Close closes the stream of the server, and forgets the session of the stream
+func (s *sseStream) Close() error {
+	s.cancel()
+	s.ticker.Stop()
+	s.proxy.sseSessionsMutex.Lock()
+	defer s.proxy.sseSessionsMutex.Unlock()
+	if s.key != "" {
+		delete(s.proxy.sseSessions, s.key)
+	}
+	return nil
+}
+
+// pump sends the events of the stream of the server to the client, reconnecting to the server
+// whenever it closes the stream of a session of the SSE transport
+func (s *sseStream) pump(body io.ReadCloser) {
+	defer close(s.events)
+	for {
+		err := s.forwardEvents(body)
+		_ = body.Close()
+		if s.ctx.Err() != nil || s.session == nil {
+			return
+		}
+
+		logger.Infof("SSE stream of %s closed (%v), reconnecting...", s.proxy.containerName, err)
+		body, err = s.reconnect()
+		if err != nil {
+			if s.ctx.Err() == nil {
+				logger.Warnf("Failed to reconnect to the SSE endpoint of %s: %v", s.proxy.containerName, err)
+			}
+			return
+		}
+		logger.Infof("Reconnected to the SSE endpoint of %s", s.proxy.containerName)
+	}
+}
+
+// forwardEvents sends the events of a stream of the server to the client, until the stream ends
+func (s *sseStream) forwardEvents(body io.ReadCloser) error {
+	// A stream on which the server sends nothing for the SSE read timeout is considered dropped
+	timeout := s.proxy.limits.SSEReadTimeout
+	var readTimer *time.Timer
+	if timeout > 0 {
+		readTimer = time.AfterFunc(timeout, func() {
+			logger.Warnf("SSE stream of %s sent nothing for %s, closing it", s.proxy.containerName, timeout)
+			_ = body.Close()
+		})
+		defer readTimer.Stop()
+	}
+
+	reader := bufio.NewReader(body)
+	var event strings.Builder
+	var eventType, data string
+	for {
+		line, err := reader.ReadString('\n')
+		if readTimer != nil {
+			readTimer.Reset(timeout)
+		}
+		if err != nil {
+			if err == io.EOF {
+				return nil
+			}
+			return err
+		}
+		event.WriteString(line)
+
+		trimmed := strings.TrimRight(line, "\r\n")
+		switch {
+		case strings.HasPrefix(trimmed, "event:"):
+			eventType = strings.TrimSpace(strings.TrimPrefix(trimmed, "event:"))
+		case strings.HasPrefix(trimmed, "data:"):
+			data = strings.TrimSpace(strings.TrimPrefix(trimmed, "data:"))
+		case trimmed == "":
+			if s.handleEvent(eventType, data) {
+				select {
+				case s.events <- event.String():
+				case <-s.ctx.Done():
+					return s.ctx.Err()
+				}
+			}
+			event.Reset()
+			eventType, data = "", ""
+		}
+	}
+}
+
+// handleEvent tracks the session of the stream with its endpoint events, and returns false
+// for the events which are not forwarded to the client
+func (s *sseStream) handleEvent(eventType, data string) bool {
+	if eventType == "endpoint" {
+		u, err := url.Parse(data)
+		if err != nil {
+			return true
+		}
+		endpoint := u.RequestURI()
+		if s.session == nil {
+			s.session = &sseSession{endpoint: endpoint, target: s.target}
+			s.proxy.sseSessionsMutex.Lock()
+			s.key = endpoint
+			s.proxy.sseSessions[endpoint] = s.session
+			s.proxy.sseSessionsMutex.Unlock()
+			return true
+		}
+		// The client keeps posting to the endpoint of the first stream
+		s.session.mutex.Lock()
+		s.session.endpoint, s.session.target = endpoint, s.target
+		s.session.mutex.Unlock()
+		go s.replayHandshake()
+		return false
+	}
+	if s.session == nil {
+		return true
+	}
+	return s.session.recordResponse(data)
+}
+
+// reconnect opens a new stream to the SSE endpoint of the current target of the proxy, which is a new
+// server if the proxy was switched to one, until it succeeds or the reconnect timeout expires
+func (s *sseStream) reconnect() (io.ReadCloser, error) {
+	ctx, cancel := context.WithTimeout(s.ctx, reconnectTimeout)
+	defer cancel()
+
+	ticker := time.NewTicker(reconnectInterval)
+	defer ticker.Stop()
+
+	for {
+		target := s.proxy.target.Load()
+		req, err := http.NewRequestWithContext(s.ctx, http.MethodGet, targetURL(s.request.URL, target).String(), nil)
+		if err != nil {
+			return nil, err
+		}
+		req.Header = s.request.Header.Clone()
+		resp, err := s.proxy.upstreamClient.Do(req)
+		if err == nil && resp.StatusCode == http.StatusOK {
+			s.target = target
+			return resp.Body, nil
+		}
+		if err == nil {
+			_ = resp.Body.Close()
+		}
+
+		select {
+		case <-ctx.Done():
+			return nil, fmt.Errorf("the server did not accept connections within %s", reconnectTimeout)
+		case <-ticker.C:
+		}
+	}
+}
+
+// replayHandshake replays the initialize handshake of the client on the new session of the server
+func (s *sseStream) replayHandshake() {
+	// The requests the previous session of the server did not answer will not be answered by the new one
+	if interrupted := s.session.tracker.TakePending(); len(interrupted) > 0 {
+		logger.Warnf("%d requests to %s were interrupted by the reconnection", len(interrupted), s.proxy.containerName)
+	}
+
+	endpoint := s.session.currentEndpoint()
+	for _, msg := range s.session.tracker.Replay() {
+		if err := s.post(endpoint, msg); err != nil {
+			logger.Warnf("Failed to replay the initialize handshake to %s: %v", s.proxy.containerName, err)
+			return
+		}
+	}
+}
+
+// post posts a message to the messages endpoint of the server
+func (s *sseStream) post(endpoint string, msg jsonrpc2.Message) error {
+	data, err := jsonrpc2.EncodeMessage(msg)
+	if err != nil {
+		return err
+	}
+	target, err := targetURL(s.request.URL, s.session.currentTarget()).Parse(endpoint)
+	if err != nil {
+		return err
+	}
+	req, err := http.NewRequestWithContext(s.ctx, http.MethodPost, target.String(), bytes.NewReader(data))
+	if err != nil {
+		return err
+	}
+	req.Header = s.request.Header.Clone()
+	req.Header.Set("Content-Type", "application/json")
+	req.Header.Del("Accept")
+
+	resp, err := s.proxy.upstreamClient.Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode >= http.StatusBadRequest {
+		return fmt.Errorf("unexpected status %s", resp.Status)
+	}
+	return nil
+}
+
+// targetURL returns the URL of a request of the proxy to the server, with the server replaced by target
+func targetURL(u *url.URL, target *upstreamTarget) *url.URL {
+	rewritten := *u
+	if target != nil {
+		rewritten.Scheme, rewritten.Host = target.url.Scheme, target.url.Host
+	}
+	return &rewritten
+}
@@ -0,0 +1,213 @@
+package transparent
+
+import (
+	"bufio"
+	"context"
+	"fmt"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"net/url"
+	"strings"
+	"sync"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+)
+
+// sseServer is an MCP server of the SSE transport, whose first stream can be closed as if it was restarted
+type sseServer struct {
+	mutex      sync.Mutex
+	sessions   int
+	streams    map[string]chan string
+	closeFirst chan struct{}
+	postedTo   chan string
+	postedBody chan string
+}
+
+func newSSEServer(t *testing.T) (*httptest.Server, *sseServer) {
+	t.Helper()
+	s := &sseServer{
+		streams:    make(map[string]chan string),
+		closeFirst: make(chan struct{}),
+		postedTo:   make(chan string, 10),
+		postedBody: make(chan string, 10),
+	}
+	mux := http.NewServeMux()
+	mux.HandleFunc("/sse", s.handleSSE)
+	mux.HandleFunc("/messages", s.handleMessage)
+	mux.HandleFunc("/close", func(_ http.ResponseWriter, _ *http.Request) { close(s.closeFirst) })
+	server := httptest.NewServer(mux)
+	t.Cleanup(server.Close)
+	return server, s
+}
+
+func (s *sseServer) handleSSE(w http.ResponseWriter, r *http.Request) {
+	s.mutex.Lock()
+	s.sessions++
+	session := fmt.Sprint(s.sessions)
+	events := make(chan string, 10)
+	s.streams[session] = events
+	s.mutex.Unlock()
+
+	w.Header().Set("Content-Type", "text/event-stream")
+	fmt.Fprintf(w, "event: endpoint\ndata: /messages?session_id=%s\n\n", session)
+	w.(http.Flusher).Flush()
+
+	var closed <-chan struct{}
+	if session == "1" {
+		closed = s.closeFirst
+	}
+	for {
+		select {
+		case event := <-events:
+			fmt.Fprintf(w, "event: message\ndata: %s\n\n", event)
+			w.(http.Flusher).Flush()
+		case <-closed:
+			return
+		case <-r.Context().Done():
+			return
+		}
+	}
+}
+
+// handleMessage answers the requests on the stream of their session
+func (s *sseServer) handleMessage(w http.ResponseWriter, r *http.Request) {
+	session := r.URL.Query().Get("session_id")
+	body, _ := io.ReadAll(r.Body)
+	s.postedTo <- session
+	s.postedBody <- string(body)
+
+	s.mutex.Lock()
+	events := s.streams[session]
+	s.mutex.Unlock()
+	if id, ok := strings.CutPrefix(string(body), `{"jsonrpc":"2.0","id":`); ok {
+		id, _, _ = strings.Cut(id, ",")
+		events <- fmt.Sprintf(`{"jsonrpc":"2.0","id":%s,"result":{"session":"%s"}}`, id, session)
+	}
+	w.WriteHeader(http.StatusAccepted)
+}
+
+// readEvent reads an event of an SSE stream, skipping the keep-alive comments
+func readEvent(t *testing.T, reader *bufio.Reader) (string, string) {
+	t.Helper()
+	var eventType, data string
+	for {
+		line, err := reader.ReadString('\n')
+		require.NoError(t, err)
+		line = strings.TrimRight(line, "\n")
+		switch {
+		case strings.HasPrefix(line, "event: "):
+			eventType = strings.TrimPrefix(line, "event: ")
+		case strings.HasPrefix(line, "data: "):
+			data = strings.TrimPrefix(line, "data: ")
+		case line == "" && data != "":
+			return eventType, data
+		}
+	}
+}
+
+func TestTransparentProxy_SSEReconnect(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	server, state := newSSEServer(t)
+	targetURL, err := url.Parse(server.URL)
+	require.NoError(t, err)
+	p := NewTransparentProxy("127.0.0.1", 0, "test", server.URL, nil, "", nil)
+	front := httptest.NewServer(p.proxyHandler(targetURL))
+	t.Cleanup(front.Close)
+
+	resp, err := http.Get(front.URL + "/sse")
+	require.NoError(t, err)
+	defer resp.Body.Close()
+	reader := bufio.NewReader(resp.Body)
+
+	eventType, endpoint := readEvent(t, reader)
+	require.Equal(t, "endpoint", eventType)
+	require.Equal(t, "/messages?session_id=1", endpoint)
+
+	post := func(body string) {
+		resp, err := http.Post(front.URL+endpoint, "application/json", strings.NewReader(body))
+		require.NoError(t, err)
+		require.NoError(t, resp.Body.Close())
+	}
+
+	post(`{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26"}}`)
+	assert.Equal(t, "1", <-state.postedTo)
+	<-state.postedBody
+	_, data := readEvent(t, reader)
+	assert.Equal(t, `{"jsonrpc":"2.0","id":1,"result":{"session":"1"}}`, data)
+
+	// Restart the server: the proxy reconnects and replays the initialize request on the new session
+	closeResp, err := http.Get(server.URL + "/close")
+	require.NoError(t, err)
+	require.NoError(t, closeResp.Body.Close())
+	assert.Equal(t, "2", <-state.postedTo)
+	assert.Contains(t, <-state.postedBody, `"id":"toolhive-reinitialize"`)
+
+	// The client keeps posting to its endpoint, and gets the responses of the new session
+	post(`{"jsonrpc":"2.0","id":2,"method":"ping"}`)
+	assert.Equal(t, "2", <-state.postedTo)
+	<-state.postedBody
+	eventType, data = readEvent(t, reader)
+	assert.Equal(t, "message", eventType)
+	assert.Equal(t, `{"jsonrpc":"2.0","id":2,"result":{"session":"2"}}`, data)
+}
+
+func TestTransparentProxy_SwitchTarget(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	previous, previousState := newSSEServer(t)
+	next, nextState := newSSEServer(t)
+	targetURL, err := url.Parse(previous.URL)
+	require.NoError(t, err)
+	p := NewTransparentProxy("127.0.0.1", 0, "test", previous.URL, nil, "", nil)
+	front := httptest.NewServer(p.proxyHandler(targetURL))
+	t.Cleanup(front.Close)
+
+	resp, err := http.Get(front.URL + "/sse")
+	require.NoError(t, err)
+	defer resp.Body.Close()
+	reader := bufio.NewReader(resp.Body)
+	_, endpoint := readEvent(t, reader)
+
+	post := func(body string) {
+		resp, err := http.Post(front.URL+endpoint, "application/json", strings.NewReader(body))
+		require.NoError(t, err)
+		require.NoError(t, resp.Body.Close())
+	}
+	post(`{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26"}}`)
+	assert.Equal(t, "1", <-previousState.postedTo)
+	<-previousState.postedBody
+	readEvent(t, reader)
+
+	drain, err := p.SwitchTarget(next.URL)
+	require.NoError(t, err)
+	assert.Equal(t, next.URL, p.currentTargetURI())
+
+	// The session keeps posting to the previous server while its stream is open
+	post(`{"jsonrpc":"2.0","id":2,"method":"ping"}`)
+	assert.Equal(t, "1", <-previousState.postedTo)
+	<-previousState.postedBody
+	_, data := readEvent(t, reader)
+	assert.Equal(t, `{"jsonrpc":"2.0","id":2,"result":{"session":"1"}}`, data)
+	require.NoError(t, drain(context.Background()))
+
+	// Once the previous server is stopped, the session is reconnected to the new server
+	closeResp, err := http.Get(previous.URL + "/close")
+	require.NoError(t, err)
+	require.NoError(t, closeResp.Body.Close())
+	assert.Equal(t, "1", <-nextState.postedTo)
+	assert.Contains(t, <-nextState.postedBody, `"id":"toolhive-reinitialize"`)
+
+	post(`{"jsonrpc":"2.0","id":3,"method":"ping"}`)
+	assert.Equal(t, "1", <-nextState.postedTo)
+	<-nextState.postedBody
+	_, data = readEvent(t, reader)
+	assert.Equal(t, `{"jsonrpc":"2.0","id":3,"result":{"session":"1"}}`, data)
+}
@@ -4,17 +4,22 @@ package transparent
 
 import (
 	"context"
+	"crypto/tls"
 	"fmt"
+	"net"
 	"net/http"
 	"net/http/httputil"
 	"net/url"
 	"sync"
+	"sync/atomic"
 	"time"
 
 	"golang.org/x/exp/jsonrpc2"
 
 	"github.com/stacklok/toolhive/pkg/healthcheck"
 	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/networking"
+	"github.com/stacklok/toolhive/pkg/transport/proxy"
 	"github.com/stacklok/toolhive/pkg/transport/types"
 )
 
@@ -45,8 +50,48 @@ type TransparentProxy struct {
 	// Health checker
 	healthChecker *healthcheck.HealthChecker
 
+	// unhealthyHandler is called when the MCP server becomes unhealthy, if set
+	unhealthyHandler func(ctx context.Context)
+
 	// Optional Prometheus metrics handler
 	prometheusHandler http.Handler
+
+	// Optional Windows named pipe on which the proxy is served in addition to the port
+	namedPipe string
+
+	// Optional Unix domain socket on which the proxy is served instead of the port
+	listenSocket string
+
+	// upstreamHTTP2 is set to forward the requests of HTTP/2 clients to the MCP server over HTTP/2
+	upstreamHTTP2 bool
+
+	// Optional TLS configuration, with which the proxy serves HTTPS on the port
+	tlsConfig *tls.Config
+
+	// Timeouts of the proxy and maximum size of the messages of the clients
+	limits types.ProxyLimits
+
+	// Transport with which the requests are forwarded to the MCP server, and client with which the proxy
+	// connects to the MCP server outside of the requests it forwards
+	upstreamTransport http.RoundTripper
+	upstreamClient    *http.Client
+
+	// target is the MCP server the requests are forwarded to, which is replaced when the proxy is switched
+	// to a new container of the server
+	target atomic.Pointer[upstreamTarget]
+
+	// Sessions of the SSE transport, by the path and query of the messages endpoint the clients post to
+	sseSessions      map[string]*sseSession
+	sseSessionsMutex sync.Mutex
+
+	// Transport of the MCP server, whose proxy also serves the other transport of MCP through shim sessions
+	serverTransport types.TransportType
+
+	// Shim sessions by ID, and protocol versions negotiated by the streamable HTTP sessions of the server
+	// by session ID, guarded by shimMutex
+	shimSessions     map[string]*shimSession
+	protocolVersions map[string]string
+	shimMutex        sync.Mutex
 }
 
 // NewTransparentProxy creates a new transparent proxy with optional middlewares.
@@ -56,6 +101,8 @@ func NewTransparentProxy(
 	containerName string,
 	targetURI string,
 	prometheusHandler http.Handler,
+	namedPipe string,
+	tlsConfig *tls.Config,
 	middlewares ...types.Middleware,
 ) *TransparentProxy {
 	proxy := &TransparentProxy{
@@ -66,10 +113,15 @@ func NewTransparentProxy(
 		middlewares:       middlewares,
 		shutdownCh:        make(chan struct{}),
 		prometheusHandler: prometheusHandler,
+		namedPipe:         namedPipe,
+		tlsConfig:         tlsConfig,
+		sseSessions:       make(map[string]*sseSession),
+		shimSessions:      make(map[string]*shimSession),
+		protocolVersions:  make(map[string]string),
 	}
 
-	// Create MCP pinger and health checker
-	mcpPinger := NewMCPPinger(targetURI)
+	// Create MCP pinger and health checker, which check the current target
+	mcpPinger := newMCPPinger(proxy.currentTargetURI)
 	proxy.healthChecker = healthcheck.NewHealthChecker("sse", mcpPinger)
 
 	return proxy
@@ -86,14 +138,7 @@ func (p *TransparentProxy) Start(ctx context.Context) error {
 		return fmt.Errorf("failed to parse target URI: %w", err)
 	}
 
-	// Create a reverse proxy
-	proxy := httputil.NewSingleHostReverseProxy(targetURL)
-
-	// Create a handler that logs requests
-	handler := http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
-		logger.Infof("Transparent proxy: %s %s -> %s", r.Method, r.URL.Path, targetURL)
-		proxy.ServeHTTP(w, r)
-	})
+	handler := p.proxyHandler(targetURL)
 
 	// Create a mux to handle both proxy and health endpoints
 	mux := http.NewServeMux()
@@ -129,14 +174,28 @@ func (p *TransparentProxy) Start(ctx context.Context) error {
 		Addr:              fmt.Sprintf("%s:%d", p.host, p.port),
 		Handler:           mux,
 		ReadHeaderTimeout: 10 * time.Second, // Prevent Slowloris attacks
+		IdleTimeout:       p.limits.IdleTimeout,
+		Protocols:         networking.ProxyProtocols(),
+		TLSConfig:         p.tlsConfig,
+	}
+	if p.namedPipe != "" {
+		if err := networking.ServeNamedPipe(p.server, p.namedPipe); err != nil {
+			return err
+		}
+	}
+	var listener net.Listener
+	if p.listenSocket != "" {
+		if listener, err = networking.ListenUnixSocket(p.listenSocket); err != nil {
+			return err
+		}
 	}
 
 	// Start the server in a goroutine
 	go func() {
-		logger.Infof("Transparent proxy started for container %s on %s:%d -> %s",
-			p.containerName, p.host, p.port, p.targetURI)
+		logger.Infof("Transparent proxy started for container %s on %s://%s:%d -> %s",
+			p.containerName, networking.ServerScheme(p.server), p.host, p.port, p.targetURI)
 
-		if err := p.server.ListenAndServe(); err != nil && err != http.ErrServerClosed {
+		if err := networking.Serve(p.server, listener); err != nil && err != http.ErrServerClosed {
 			logger.Errorf("Transparent proxy error: %v", err)
 		}
 	}()
@@ -147,10 +206,160 @@ func (p *TransparentProxy) Start(ctx context.Context) error {
 	return nil
 }
 
+// proxyHandler returns the handler forwarding the requests to the target, whose SSE streams
+// get keep-alive comments and are reconnected when the server closes them
+func (p *TransparentProxy) proxyHandler(targetURL *url.URL) http.Handler {
+	p.upstreamTransport = proxy.UpstreamTransport(p.limits, p.upstreamHTTP2)
+	p.upstreamClient = &http.Client{Transport: p.upstreamTransport}
+	p.target.Store(p.newUpstreamTarget(targetURL))
+
+	// Create a handler that logs requests
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if !proxy.LimitMessageSize(w, r, p.limits) {
+			return
+		}
+		if p.serveShim(w, r) {
+			return
+		}
+		p.trackProtocolVersion(r)
+		// The messages of SSE sessions are posted to the server holding their stream
+		target, err := p.routeSSEMessage(r)
+		if err != nil {
+			if proxy.IsMessageTooLarge(err) {
+				proxy.WriteMessageTooLarge(w, p.limits)
+				return
+			}
+			http.Error(w, fmt.Sprintf("Error reading request body: %v", err), http.StatusBadRequest)
+			return
+		}
+		if target == nil {
+			target = p.target.Load()
+		}
+		logger.Infof("Transparent proxy: %s %s -> %s", r.Method, r.URL.Path, target.url)
+		target.serve(w, r)
+	})
+}
+
+// drainPollInterval is how often the requests in flight to a previous target are checked while draining it
+const drainPollInterval = 100 * time.Millisecond
+
+// upstreamTarget is an MCP server the proxy forwards requests to
+type upstreamTarget struct {
+	url          *url.URL
+	reverseProxy *httputil.ReverseProxy
+	// inFlight is the number of requests being forwarded to the server, other than the GET requests of
+	// event streams, which are reconnected to the new target when the server closes them
+	inFlight atomic.Int64
+}
+
+// upstreamTargetKey is the context key of the target a request is forwarded to
+type upstreamTargetKey struct{}
+
+// newUpstreamTarget creates the target forwarding the requests to the server at targetURL
+func (p *TransparentProxy) newUpstreamTarget(targetURL *url.URL) *upstreamTarget {
+	reverseProxy := httputil.NewSingleHostReverseProxy(targetURL)
+	reverseProxy.Transport = p.upstreamTransport
+	reverseProxy.ModifyResponse = p.modifyResponse
+	reverseProxy.ErrorHandler = func(w http.ResponseWriter, r *http.Request, err error) {
+		if proxy.IsMessageTooLarge(err) {
+			proxy.WriteMessageTooLarge(w, p.limits)
+			return
+		}
+		logger.Warnf("Transparent proxy: %s %s failed: %v", r.Method, r.URL.Path, err)
+		w.WriteHeader(http.StatusBadGateway)
+	}
+	return &upstreamTarget{url: targetURL, reverseProxy: reverseProxy}
+}
+
+// serve forwards a request to the server, recording the target in the context of the request
+func (t *upstreamTarget) serve(w http.ResponseWriter, r *http.Request) {
+	if r.Method != http.MethodGet {
+		t.inFlight.Add(1)
+		defer t.inFlight.Add(-1)
+	}
+	t.reverseProxy.ServeHTTP(w, r.WithContext(context.WithValue(r.Context(), upstreamTargetKey{}, t)))
+}
+
+// drain waits until the requests in flight to the server completed, or the context is done
+func (t *upstreamTarget) drain(ctx context.Context) error {
+	ticker := time.NewTicker(drainPollInterval)
+	defer ticker.Stop()
+
+	for t.inFlight.Load() > 0 {
+		select {
+		case <-ctx.Done():
+			return fmt.Errorf("%d requests to %s still in flight: %w", t.inFlight.Load(), t.url, ctx.Err())
+		case <-ticker.C:
+		}
+	}
+	return nil
+}
+
+// requestTarget returns the target a request of the proxy to the server was forwarded to, if known
+func requestTarget(r *http.Request) *upstreamTarget {
+	target, _ := r.Context().Value(upstreamTargetKey{}).(*upstreamTarget)
+	return target
+}
+
+// currentTargetURI returns the URI of the MCP server the requests are currently forwarded to
+func (p *TransparentProxy) currentTargetURI() string {
+	if target := p.target.Load(); target != nil {
+		return target.url.String()
+	}
+	return p.targetURI
+}
+
+// SwitchTarget makes the proxy forward the new requests to another MCP server, such as a new container
+// of the server, and returns a function waiting until the requests in flight to the previous server
+// completed. The SSE sessions stay on the previous server until their stream is closed, e.g. when the
+// previous server is stopped, and are then reconnected to the new server with their handshake replayed.
+func (p *TransparentProxy) SwitchTarget(targetURI string) (func(ctx context.Context) error, error) {
+	targetURL, err := url.Parse(targetURI)
+	if err != nil {
+		return nil, fmt.Errorf("failed to parse target URI: %w", err)
+	}
+
+	p.mutex.Lock()
+	defer p.mutex.Unlock()
+
+	if p.upstreamTransport == nil {
+		return nil, fmt.Errorf("transparent proxy for %s is not started", p.containerName)
+	}
+	previous := p.target.Swap(p.newUpstreamTarget(targetURL))
+	logger.Infof("Transparent proxy for %s switched from %s to %s", p.containerName, previous.url, targetURL)
+	return previous.drain, nil
+}
+
+// SetListenSocket sets the path of a Unix domain socket on which the proxy is served instead of its port.
+// It must be called before the proxy is started.
+func (p *TransparentProxy) SetListenSocket(path string) {
+	p.listenSocket = path
+}
+
+// SetUpstreamHTTP2 sets whether the requests of HTTP/2 clients are forwarded to the MCP server over HTTP/2,
+// for servers relying on HTTP/2 streams. gRPC requests are always forwarded over HTTP/2.
+// It must be called before the proxy is started.
+func (p *TransparentProxy) SetUpstreamHTTP2(enabled bool) {
+	p.upstreamHTTP2 = enabled
+}
+
+// SetLimits sets the timeouts of the proxy and the maximum size of the messages of the clients.
+// It must be called before the proxy is started.
+func (p *TransparentProxy) SetLimits(limits types.ProxyLimits) {
+	p.limits = limits
+}
+
+// SetUnhealthyHandler sets the function called when the MCP server becomes unhealthy, e.g. to restart it.
+// Without a handler the proxy is stopped when the MCP server becomes unhealthy.
+func (p *TransparentProxy) SetUnhealthyHandler(handler func(ctx context.Context)) {
+	p.unhealthyHandler = handler
+}
+
 func (p *TransparentProxy) monitorHealth(parentCtx context.Context) {
-	ticker := time.NewTicker(10 * time.Second)
+	ticker := time.NewTicker(healthcheck.ProbeInterval)
 	defer ticker.Stop()
 
+	wasUnhealthy := false
 	for {
 		select {
 		case <-parentCtx.Done():
@@ -160,14 +369,23 @@ func (p *TransparentProxy) monitorHealth(parentCtx context.Context) {
 			logger.Infof("Shutdown initiated, stopping health monitor for %s", p.containerName)
 			return
 		case <-ticker.C:
-			alive := p.healthChecker.CheckHealth(parentCtx)
-			if alive.Status != healthcheck.StatusHealthy {
+			// A paused server cannot respond, but it is not failing either
+			if proxy.IsPaused(parentCtx, p.containerName) {
+				continue
+			}
+			// Servers which are starting or only failed a few probes are given time to recover
+			unhealthy := p.healthChecker.Probe(parentCtx).Status == healthcheck.StatusUnhealthy
+			if unhealthy && !wasUnhealthy && p.unhealthyHandler != nil {
+				logger.Warnf("MCP server %s is unhealthy", p.containerName)
+				p.unhealthyHandler(parentCtx)
+			} else if unhealthy && p.unhealthyHandler == nil {
 				logger.Infof("Health check failed for %s; initiating proxy shutdown", p.containerName)
 				if err := p.Stop(parentCtx); err != nil {
 					logger.Errorf("Failed to stop proxy for %s: %v", p.containerName, err)
 				}
 				return
 			}
+			wasUnhealthy = unhealthy
 		}
 	}
 }
@@ -179,6 +397,7 @@ func (p *TransparentProxy) Stop(ctx context.Context) error {
 
 	// Signal shutdown
 	close(p.shutdownCh)
+	p.closeShimSessions()
 
 	// Stop the HTTP server
 	if p.server != nil {
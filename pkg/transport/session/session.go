@@ -0,0 +1,131 @@
+// Package session persists the state of the client sessions of a proxy, so that clients
+// keep their sessions when the proxy process is restarted or upgraded.
+package session
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"time"
+
+	"github.com/stacklok/toolhive/pkg/state"
+)
+
+// MaxAge is how long the session state of a proxy is kept after it was last saved.
+// Clients are not expected to resume older sessions, so older state is discarded.
+const MaxAge = 24 * time.Hour
+
+// State is the state of the client sessions of the proxy of a workload
+type State struct {
+	// SessionIDs are the IDs of the sessions of the clients of the proxy
+	SessionIDs []string `json:"session_ids,omitempty"`
+	// Initialize is the initialize request of the client, replayed to a new MCP server process
+	Initialize json.RawMessage `json:"initialize,omitempty"`
+	// Initialized is the initialized notification of the client, replayed after the initialize request
+	Initialized json.RawMessage `json:"initialized,omitempty"`
+	// Pending are the requests sent to the MCP server which were not answered yet
+	Pending []json.RawMessage `json:"pending,omitempty"`
+	// SavedAt is the time the state was saved
+	SavedAt time.Time `json:"saved_at"`
+}
+
+// Expired returns true if the state is too old to be restored
+func (s *State) Expired(now time.Time) bool {
+	return now.Sub(s.SavedAt) > MaxAge
+}
+
+// Load loads the session state of the proxy of the given container.
+// It returns nil if there is no state, or if it expired.
+func Load(ctx context.Context, containerName string) (*State, error) {
+	store, err := state.NewSessionStore(state.DefaultAppName)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create state store: %w", err)
+	}
+
+	exists, err := store.Exists(ctx, containerName)
+	if err != nil {
+		return nil, fmt.Errorf("failed to check if session state exists: %w", err)
+	}
+	if !exists {
+		return nil, nil
+	}
+
+	reader, err := store.GetReader(ctx, containerName)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get reader for session state: %w", err)
+	}
+	defer reader.Close()
+
+	sessionState := &State{}
+	if err := json.NewDecoder(reader).Decode(sessionState); err != nil {
+		return nil, fmt.Errorf("failed to read session state: %w", err)
+	}
+	if sessionState.Expired(time.Now()) {
+		return nil, nil
+	}
+	return sessionState, nil
+}
+
+// Save saves the session state of the proxy of the given container
+func Save(ctx context.Context, containerName string, sessionState *State) error {
+	store, err := state.NewSessionStore(state.DefaultAppName)
+	if err != nil {
+		return fmt.Errorf("failed to create state store: %w", err)
+	}
+
+	writer, err := store.GetWriter(ctx, containerName)
+	if err != nil {
+		return fmt.Errorf("failed to get writer for session state: %w", err)
+	}
+	defer writer.Close()
+
+	saved := *sessionState
+	saved.SavedAt = time.Now()
+	if err := json.NewEncoder(writer).Encode(&saved); err != nil {
+		return fmt.Errorf("failed to write session state: %w", err)
+	}
+	return nil
+}
+
+// Delete deletes the session state of the proxy of the given container, if any
+func Delete(ctx context.Context, containerName string) error {
+	store, err := state.NewSessionStore(state.DefaultAppName)
+	if err != nil {
+		return fmt.Errorf("failed to create state store: %w", err)
+	}
+
+	exists, err := store.Exists(ctx, containerName)
+	if err != nil {
+		return fmt.Errorf("failed to check if session state exists: %w", err)
+	}
+	if !exists {
+		return nil
+	}
+	return store.Delete(ctx, containerName)
+}
+
+// Rename moves the session state of the proxy of a container, if any, to the new name of the container
+func Rename(ctx context.Context, oldName, newName string) error {
+	store, err := state.NewSessionStore(state.DefaultAppName)
+	if err != nil {
+		return fmt.Errorf("failed to create state store: %w", err)
+	}
+
+	exists, err := store.Exists(ctx, oldName)
+	if err != nil {
+		return fmt.Errorf("failed to check if session state exists: %w", err)
+	}
+	if !exists {
+		return nil
+	}
+
+	reader, err := store.GetReader(ctx, oldName)
+	if err != nil {
+		return fmt.Errorf("failed to get reader for session state: %w", err)
+	}
+	defer reader.Close()
+	if err := store.Save(ctx, newName, reader); err != nil {
+		return fmt.Errorf("failed to write session state: %w", err)
+	}
+	return store.Delete(ctx, oldName)
+}
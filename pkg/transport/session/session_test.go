@@ -0,0 +1,48 @@
+package session
+
+import (
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestState_Expired(t *testing.T) {
+	t.Parallel()
+
+	now := time.Now()
+	tests := []struct {
+		name     string
+		savedAt  time.Time
+		expected bool
+	}{
+		{
+			name:     "Just saved",
+			savedAt:  now,
+			expected: false,
+		},
+		{
+			name:     "Saved within the maximum age",
+			savedAt:  now.Add(-MaxAge + time.Minute),
+			expected: false,
+		},
+		{
+			name:     "Saved before the maximum age",
+			savedAt:  now.Add(-MaxAge - time.Minute),
+			expected: true,
+		},
+		{
+			name:     "Never saved",
+			savedAt:  time.Time{},
+			expected: true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			state := &State{SavedAt: tt.savedAt}
+			assert.Equal(t, tt.expected, state.Expired(now))
+		})
+	}
+}
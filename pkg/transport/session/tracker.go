@@ -0,0 +1,134 @@
+package session
+
+import (
+	"sync"
+
+	"golang.org/x/exp/jsonrpc2"
+)
+
+// ReplayedInitializeID is the ID of the initialize request replayed to the MCP server once the proxy is
+// connected to it again, which tells the response to the replayed request apart from the other responses
+var ReplayedInitializeID = jsonrpc2.StringID("toolhive-reinitialize")
+
+// Tracker tracks the session of a client through a proxy whose connection to the MCP server can be
+// re-established, e.g. after the container of the server was restarted: it keeps the initialize handshake
+// of the client, which is replayed on the new connection so the client does not have to re-initialize,
+// and the requests the server has not answered yet. The response to the replayed initialize request is
+// dropped, since the client already received one. It is safe for concurrent use.
+type Tracker struct {
+	mutex sync.Mutex
+	// initialize and initialized are the initialize handshake of the client
+	initialize  *jsonrpc2.Request
+	initialized *jsonrpc2.Request
+	// awaitingReplayResponse is true until the response to the replayed initialize request is dropped
+	awaitingReplayResponse bool
+	// pending are the requests sent to the server which were not answered yet
+	pending map[jsonrpc2.ID]*jsonrpc2.Request
+}
+
+// RecordRequest records a message a client sends to the MCP server. The messages of the initialize
+// handshake are kept to be replayed, and the other requests are pending until the server answers them.
+// The initialize request is not pending, since it is replayed with the handshake.
+func (t *Tracker) RecordRequest(msg jsonrpc2.Message) {
+	req, ok := msg.(*jsonrpc2.Request)
+	if !ok {
+		return
+	}
+
+	t.mutex.Lock()
+	defer t.mutex.Unlock()
+	switch req.Method {
+	case "initialize":
+		t.initialize = req
+		return
+	case "notifications/initialized":
+		t.initialized = req
+	}
+	if !req.IsCall() {
+		return
+	}
+	if t.pending == nil {
+		t.pending = make(map[jsonrpc2.ID]*jsonrpc2.Request)
+	}
+	t.pending[req.ID] = req
+}
+
+// RecordResponse records a message the MCP server sends to the client, and returns false if it must be
+// dropped since it is the response to the replayed initialize request. The request answered by any other
+// response is no longer pending.
+func (t *Tracker) RecordResponse(msg jsonrpc2.Message) bool {
+	resp, ok := msg.(*jsonrpc2.Response)
+	if !ok {
+		return true
+	}
+
+	t.mutex.Lock()
+	defer t.mutex.Unlock()
+	if resp.ID == ReplayedInitializeID && t.awaitingReplayResponse {
+		t.awaitingReplayResponse = false
+		return false
+	}
+	delete(t.pending, resp.ID)
+	return true
+}
+
+// Replay returns the messages replaying the initialize handshake of the client on a new connection to the
+// MCP server, in order, and expects the response to the replayed initialize request from then on
+func (t *Tracker) Replay() []jsonrpc2.Message {
+	t.mutex.Lock()
+	defer t.mutex.Unlock()
+
+	var msgs []jsonrpc2.Message
+	if t.initialize != nil {
+		msgs = append(msgs, &jsonrpc2.Request{
+			ID:     ReplayedInitializeID,
+			Method: t.initialize.Method,
+			Params: t.initialize.Params,
+		})
+		t.awaitingReplayResponse = true
+	}
+	if t.initialized != nil {
+		msgs = append(msgs, t.initialized)
+	}
+	return msgs
+}
+
+// TakePending returns the requests which were not answered and forgets them, since the server will not
+// answer them on a new connection
+func (t *Tracker) TakePending() []*jsonrpc2.Request {
+	t.mutex.Lock()
+	defer t.mutex.Unlock()
+
+	requests := make([]*jsonrpc2.Request, 0, len(t.pending))
+	for _, req := range t.pending {
+		requests = append(requests, req)
+	}
+	t.pending = nil
+	return requests
+}
+
+// Handshake returns the initialize request and the initialized notification of the client, if recorded
+func (t *Tracker) Handshake() (initialize, initialized *jsonrpc2.Request) {
+	t.mutex.Lock()
+	defer t.mutex.Unlock()
+	return t.initialize, t.initialized
+}
+
+// Pending returns the requests which were not answered yet
+func (t *Tracker) Pending() []*jsonrpc2.Request {
+	t.mutex.Lock()
+	defer t.mutex.Unlock()
+
+	requests := make([]*jsonrpc2.Request, 0, len(t.pending))
+	for _, req := range t.pending {
+		requests = append(requests, req)
+	}
+	return requests
+}
+
+// RestoreHandshake restores the initialize handshake of a client recorded by a previous proxy process
+func (t *Tracker) RestoreHandshake(initialize, initialized *jsonrpc2.Request) {
+	t.mutex.Lock()
+	defer t.mutex.Unlock()
+	t.initialize, t.initialized = initialize, initialized
+}
@@ -0,0 +1,76 @@
+package session
+
+import (
+	"encoding/json"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"golang.org/x/exp/jsonrpc2"
+)
+
+func TestTracker(t *testing.T) {
+	t.Parallel()
+
+	var tracker Tracker
+	assert.Empty(t, tracker.Replay())
+
+	initialize, err := jsonrpc2.NewCall(jsonrpc2.Int64ID(1), "initialize", json.RawMessage(`{"protocolVersion":"2025-06-18"}`))
+	require.NoError(t, err)
+	initialized, err := jsonrpc2.NewNotification("notifications/initialized", json.RawMessage(`{}`))
+	require.NoError(t, err)
+	answered, err := jsonrpc2.NewCall(jsonrpc2.Int64ID(2), "tools/list", nil)
+	require.NoError(t, err)
+	interrupted, err := jsonrpc2.NewCall(jsonrpc2.Int64ID(3), "tools/call", json.RawMessage(`{"name":"fetch"}`))
+	require.NoError(t, err)
+	for _, msg := range []jsonrpc2.Message{initialize, initialized, answered, interrupted} {
+		tracker.RecordRequest(msg)
+	}
+
+	// The handshake is recorded, and only the calls other than initialize are pending
+	recordedInitialize, recordedInitialized := tracker.Handshake()
+	assert.Equal(t, initialize, recordedInitialize)
+	assert.Equal(t, initialized, recordedInitialized)
+	assert.Len(t, tracker.Pending(), 2)
+
+	response, err := jsonrpc2.NewResponse(jsonrpc2.Int64ID(2), json.RawMessage(`{}`), nil)
+	require.NoError(t, err)
+	assert.True(t, tracker.RecordResponse(response))
+	pending := tracker.TakePending()
+	require.Len(t, pending, 1)
+	assert.Equal(t, jsonrpc2.Int64ID(3), pending[0].ID)
+	assert.Empty(t, tracker.Pending())
+
+	// The initialize request is replayed with its own ID, followed by the initialized notification
+	replayed := tracker.Replay()
+	require.Len(t, replayed, 2)
+	replayedInitialize, ok := replayed[0].(*jsonrpc2.Request)
+	require.True(t, ok)
+	assert.Equal(t, ReplayedInitializeID, replayedInitialize.ID)
+	assert.Equal(t, "initialize", replayedInitialize.Method)
+	assert.JSONEq(t, `{"protocolVersion":"2025-06-18"}`, string(replayedInitialize.Params))
+	assert.Equal(t, initialized, replayed[1])
+
+	// Only the first response to the replayed request is dropped
+	replayResponse, err := jsonrpc2.NewResponse(ReplayedInitializeID, json.RawMessage(`{}`), nil)
+	require.NoError(t, err)
+	assert.False(t, tracker.RecordResponse(replayResponse))
+	assert.True(t, tracker.RecordResponse(replayResponse))
+}
+
+func TestTracker_RestoreHandshake(t *testing.T) {
+	t.Parallel()
+
+	initialize, err := jsonrpc2.NewCall(jsonrpc2.Int64ID(1), "initialize", json.RawMessage(`{}`))
+	require.NoError(t, err)
+
+	var tracker Tracker
+	tracker.RestoreHandshake(initialize, nil)
+
+	// A response with the ID of the replayed request is only dropped once the handshake is replayed
+	replayResponse, err := jsonrpc2.NewResponse(ReplayedInitializeID, json.RawMessage(`{}`), nil)
+	require.NoError(t, err)
+	assert.True(t, tracker.RecordResponse(replayResponse))
+	assert.Len(t, tracker.Replay(), 1)
+	assert.False(t, tracker.RecordResponse(replayResponse))
+}
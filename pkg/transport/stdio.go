@@ -3,6 +3,7 @@ package transport
 import (
 	"bytes"
 	"context"
+	"crypto/tls"
 	"fmt"
 	"io"
 	"net/http"
@@ -19,20 +20,33 @@ import (
 	"github.com/stacklok/toolhive/pkg/permissions"
 	"github.com/stacklok/toolhive/pkg/transport/errors"
 	"github.com/stacklok/toolhive/pkg/transport/proxy/httpsse"
+	"github.com/stacklok/toolhive/pkg/transport/session"
 	"github.com/stacklok/toolhive/pkg/transport/types"
 )
 
+const (
+	// reattachTimeout is how long to wait for a container to be restarted after its attach stream was closed
+	reattachTimeout = time.Minute
+	// reattachPollInterval is how often the container is checked while waiting for it to be restarted
+	reattachPollInterval = time.Second
+)
+
 // StdioTransport implements the Transport interface using standard input/output.
 // It acts as a proxy between the MCP client and the container's stdin/stdout.
 type StdioTransport struct {
 	host              string
 	port              int
+	ssePath           string
+	messagesPath      string
 	containerID       string
 	containerName     string
 	runtime           rt.Runtime
 	debug             bool
 	middlewares       []types.Middleware
 	prometheusHandler http.Handler
+	namedPipe         string
+	listenSocket      string
+	tlsConfig         *tls.Config
 
 	// Mutex for protecting shared state
 	mutex sync.Mutex
@@ -48,26 +62,58 @@ type StdioTransport struct {
 	stdin  io.WriteCloser
 	stdout io.ReadCloser
 
-	// Container monitor
-	monitor rt.Monitor
+	// attachMutex is held for writing while re-attaching to a restarted container,
+	// so that messages from clients are only sent to the container once it is attached again
+	attachMutex sync.RWMutex
+
+	// Session of the client, whose handshake is replayed when re-attaching to a restarted container
+	tracker session.Tracker
+
+	// persistSessions is set once the session state is restored, to save it while the transport runs
+	persistSessions bool
+
+	// Container monitor, which restarts the container according to its restart policy
+	monitor       rt.Monitor
+	restartPolicy rt.RestartPolicy
+
+	// restartUnhealthy is set to restart the container when the MCP server becomes unhealthy
+	restartUnhealthy bool
+
+	// limits are the timeouts of the proxy and the maximum size of the messages of the clients
+	limits types.ProxyLimits
 }
 
 // NewStdioTransport creates a new stdio transport.
+// The SSE and message endpoints of the proxy are served at ssePath and messagesPath, or at the defaults if empty.
 func NewStdioTransport(
 	host string,
 	port int,
+	ssePath string,
+	messagesPath string,
 	runtime rt.Runtime,
 	debug bool,
 	prometheusHandler http.Handler,
+	namedPipe string,
+	listenSocket string,
+	tlsConfig *tls.Config,
+	restartUnhealthy bool,
+	limits types.ProxyLimits,
 	middlewares ...types.Middleware,
 ) *StdioTransport {
 	return &StdioTransport{
 		host:              host,
 		port:              port,
+		ssePath:           ssePath,
+		messagesPath:      messagesPath,
 		runtime:           runtime,
 		debug:             debug,
 		middlewares:       middlewares,
 		prometheusHandler: prometheusHandler,
+		namedPipe:         namedPipe,
+		listenSocket:      listenSocket,
+		tlsConfig:         tlsConfig,
+		restartUnhealthy:  restartUnhealthy,
+		limits:            limits,
 		shutdownCh:        make(chan struct{}),
 	}
 }
@@ -91,7 +137,7 @@ func (t *StdioTransport) Setup(
 	cmdArgs []string,
 	envVars, labels map[string]string,
 	permissionProfile *permissions.Profile,
-	k8sPodTemplatePatch string,
+	containerOptions *rt.DeployWorkloadOptions,
 	isolateNetwork bool,
 ) error {
 	t.mutex.Lock()
@@ -103,10 +149,16 @@ func (t *StdioTransport) Setup(
 	// Add transport-specific environment variables
 	envVars["MCP_TRANSPORT"] = "stdio"
 
-	// Create workload options
-	containerOptions := rt.NewDeployWorkloadOptions()
+	// Create workload options if none were provided
+	if containerOptions == nil {
+		containerOptions = rt.NewDeployWorkloadOptions()
+	}
 	containerOptions.AttachStdio = true
-	containerOptions.K8sPodTemplatePatch = k8sPodTemplatePatch
+	restartPolicy, err := rt.ParseRestartPolicy(containerOptions.RestartPolicy)
+	if err != nil {
+		return err
+	}
+	t.restartPolicy = restartPolicy
 
 	// Create the container
 	logger.Infof("Deploying workload %s from image %s...", containerName, image)
@@ -157,31 +209,57 @@ func (t *StdioTransport) Start(ctx context.Context) error {
 	}
 
 	// Create and start the HTTP SSE proxy with middlewares
-	t.httpProxy = httpsse.NewHTTPSSEProxy(t.host, t.port, t.containerName, t.prometheusHandler, t.middlewares...)
+	httpProxy := httpsse.NewHTTPSSEProxy(
+		t.host, t.port, t.containerName, t.ssePath, t.messagesPath, t.prometheusHandler, t.namedPipe, t.tlsConfig, t.middlewares...)
+	if t.restartUnhealthy {
+		// The container is re-attached to once it is running again, like after any other restart
+		httpProxy.SetUnhealthyHandler(func(ctx context.Context) {
+			if err := restartWorkload(ctx, t.runtime, t.containerID, t.containerName); err != nil {
+				logger.Errorf("Failed to restart unhealthy container %s: %v", t.containerName, err)
+			}
+		})
+	}
+	httpProxy.SetLimits(t.limits)
+	httpProxy.SetListenSocket(t.listenSocket)
+	t.httpProxy = httpProxy
+	// Clients of a previous proxy process keep their sessions
+	if err := t.restoreSessionState(ctx); err != nil {
+		logger.Warnf("Warning: Failed to restore client sessions of %s: %v", t.containerName, err)
+	}
 	if err := t.httpProxy.Start(ctx); err != nil {
 		return err
 	}
 	logger.Info("HTTP SSE proxy started, processing messages...")
+	go t.saveSessionStates(ctx)
 
 	// Start processing messages in a goroutine
-	go t.processMessages(ctx, t.stdin, t.stdout)
+	go t.processMessages(ctx, t.stdout)
+
+	// Start monitoring the container
+	if err := t.startMonitoring(ctx); err != nil {
+		return err
+	}
+
+	// Start a goroutine to handle container exit
+	go t.handleContainerExit(ctx)
 
-	// Create a container monitor
+	return nil
+}
+
+// startMonitoring creates a container monitor and starts monitoring the container
+func (t *StdioTransport) startMonitoring(ctx context.Context) error {
 	monitorRuntime, err := container.NewFactory().Create(ctx)
 	if err != nil {
 		return fmt.Errorf("failed to create container monitor: %v", err)
 	}
-	t.monitor = container.NewMonitor(monitorRuntime, t.containerID, t.containerName)
+	// The proxy owns the lifecycle of the container, so it restarts the container when it exits,
+	// and processContainerOutput re-attaches to it
+	t.monitor = container.NewSupervisor(monitorRuntime, t.containerID, t.containerName, t.restartPolicy)
 
-	// Start monitoring the container
 	t.errorCh, err = t.monitor.StartMonitoring(ctx)
 	if err != nil {
 		return fmt.Errorf("failed to start container monitoring: %v", err)
 	}
-
-	// Start a goroutine to handle container exit
-	go t.handleContainerExit(ctx)
-
 	return nil
 }
 
@@ -198,6 +276,9 @@ func (t *StdioTransport) Stop(ctx context.Context) error {
 		// Channel is still open, proceed with stopping
 	}
 
+	// Save the client sessions before the proxy is stopped, so they are kept when it is restarted
+	t.saveSessionState(ctx)
+
 	// Now lock the mutex for the actual stopping process
 	t.mutex.Lock()
 	defer t.mutex.Unlock()
@@ -242,7 +323,7 @@ func (t *StdioTransport) Stop(ctx context.Context) error {
 			logger.Warnf("Warning: Failed to check workload status: %v", err)
 		} else if running {
 			// Only try to stop the workload if it's still running
-			if err := t.runtime.StopWorkload(ctx, t.containerID); err != nil {
+			if err := t.runtime.StopWorkload(ctx, t.containerID, nil); err != nil {
 				logger.Warnf("Warning: Failed to stop workload: %v", err)
 			}
 		}
@@ -266,7 +347,7 @@ func (t *StdioTransport) IsRunning(_ context.Context) (bool, error) {
 }
 
 // processMessages handles the message exchange between the client and container.
-func (t *StdioTransport) processMessages(ctx context.Context, stdin io.WriteCloser, stdout io.ReadCloser) {
+func (t *StdioTransport) processMessages(ctx context.Context, stdout io.ReadCloser) {
 	// Create a context that will be canceled when shutdown is signaled
 	ctx, cancel := context.WithCancel(ctx)
 	defer cancel()
@@ -282,7 +363,7 @@ func (t *StdioTransport) processMessages(ctx context.Context, stdin io.WriteClos
 	}()
 
 	// Start a goroutine to read from stdout
-	go t.processStdout(ctx, stdout)
+	go t.processContainerOutput(ctx, stdout)
 	// Process incoming messages and send them to the container
 	messageCh := t.httpProxy.GetMessageChannel()
 
@@ -292,7 +373,7 @@ func (t *StdioTransport) processMessages(ctx context.Context, stdin io.WriteClos
 			return
 		case msg := <-messageCh:
 			logger.Info("Process incoming messages and sending message to container")
-			if err := t.sendMessageToContainer(ctx, stdin, msg); err != nil {
+			if err := t.sendToAttachedContainer(ctx, msg); err != nil {
 				logger.Errorf("Error sending message to container: %v", err)
 			}
 			logger.Info("Messages processed")
@@ -300,6 +381,112 @@ func (t *StdioTransport) processMessages(ctx context.Context, stdin io.WriteClos
 	}
 }
 
+// sendToAttachedContainer sends a message to the container, waiting for a pending re-attachment to complete.
+func (t *StdioTransport) sendToAttachedContainer(ctx context.Context, msg jsonrpc2.Message) error {
+	t.attachMutex.RLock()
+	defer t.attachMutex.RUnlock()
+
+	t.tracker.RecordRequest(msg)
+	t.mutex.Lock()
+	stdin := t.stdin
+	t.mutex.Unlock()
+	if stdin == nil {
+		return fmt.Errorf("container stdin is closed")
+	}
+	return t.sendMessageToContainer(ctx, stdin, msg)
+}
+
+// processContainerOutput processes the container's stdout, re-attaching to the container
+// whenever it is restarted, until the transport is stopped.
+func (t *StdioTransport) processContainerOutput(ctx context.Context, stdout io.ReadCloser) {
+	for {
+		t.processStdout(ctx, stdout)
+		if ctx.Err() != nil {
+			return
+		}
+
+		logger.Infof("Attach stream of container %s closed, waiting for it to be restarted...", t.containerName)
+		var err error
+		stdout, err = t.reattach(ctx)
+		if err != nil {
+			if ctx.Err() == nil {
+				logger.Errorf("Failed to re-attach to container %s: %v", t.containerName, err)
+			}
+			return
+		}
+		logger.Infof("Re-attached to container %s", t.containerName)
+	}
+}
+
+// reattach waits for the container to be running again, attaches to the new container process,
+// and replays the initialize handshake of the client, so the restart is transparent to the client.
+// Requests which were not answered by the previous container process are answered with an error.
+func (t *StdioTransport) reattach(ctx context.Context) (io.ReadCloser, error) {
+	t.attachMutex.Lock()
+	defer t.attachMutex.Unlock()
+
+	if err := t.waitForRestart(ctx); err != nil {
+		return nil, err
+	}
+
+	stdin, stdout, err := t.runtime.AttachToWorkload(ctx, t.containerID)
+	if err != nil {
+		return nil, fmt.Errorf("failed to attach to container: %w", err)
+	}
+
+	t.mutex.Lock()
+	previous := t.stdin
+	t.stdin, t.stdout = stdin, stdout
+	t.mutex.Unlock()
+	handshake := t.tracker.Replay()
+	interrupted := t.tracker.TakePending()
+
+	if previous != nil {
+		if err := previous.Close(); err != nil {
+			logger.Debugf("Failed to close previous container stdin: %v", err)
+		}
+	}
+
+	if err := t.replayHandshake(ctx, stdin, handshake); err != nil {
+		return nil, err
+	}
+	t.failRequests(ctx, interrupted, "the MCP server was restarted")
+
+	return stdout, nil
+}
+
+// replayHandshake sends the messages replaying the initialize handshake of the client to a new container process.
+func (t *StdioTransport) replayHandshake(ctx context.Context, stdin io.Writer, handshake []jsonrpc2.Message) error {
+	for _, msg := range handshake {
+		if err := t.sendMessageToContainer(ctx, stdin, msg); err != nil {
+			return fmt.Errorf("failed to replay the initialize handshake: %w", err)
+		}
+	}
+	return nil
+}
+
+// waitForRestart waits until the container is running again, or the reattach timeout expires.
+func (t *StdioTransport) waitForRestart(ctx context.Context) error {
+	ctx, cancel := context.WithTimeout(ctx, reattachTimeout)
+	defer cancel()
+
+	ticker := time.NewTicker(reattachPollInterval)
+	defer ticker.Stop()
+
+	for {
+		running, err := t.runtime.IsWorkloadRunning(ctx, t.containerID)
+		if err == nil && running {
+			return nil
+		}
+
+		select {
+		case <-ctx.Done():
+			return fmt.Errorf("container %s was not restarted within %s", t.containerName, reattachTimeout)
+		case <-ticker.C:
+		}
+	}
+}
+
 // processStdout reads from the container's stdout and processes JSON-RPC messages.
 func (t *StdioTransport) processStdout(ctx context.Context, stdout io.ReadCloser) {
 	// Create a buffer for accumulating data
@@ -420,6 +607,11 @@ func (t *StdioTransport) parseAndForwardJSONRPC(ctx context.Context, line string
 	// Log the message
 	logger.Infof("Received JSON-RPC message: %T", msg)
 
+	if !t.tracker.RecordResponse(msg) {
+		logger.Infof("Dropping the response to the replayed initialize request of %s", t.containerName)
+		return
+	}
+
 	// Forward to SSE clients via the HTTP proxy
 	if err := t.httpProxy.ForwardResponseToClients(ctx, msg); err != nil {
 		logger.Errorf("Error forwarding to SSE clients: %v", err)
@@ -448,34 +640,78 @@ func (*StdioTransport) sendMessageToContainer(_ context.Context, stdin io.Writer
 }
 
 // handleContainerExit handles container exit events.
+// If the container is restarted, for example by its restart policy, monitoring continues
+// and the transport re-attaches to it; otherwise, the transport is stopped.
 func (t *StdioTransport) handleContainerExit(ctx context.Context) {
-	select {
-	case <-ctx.Done():
-		return
-	case err, ok := <-t.errorCh:
-		// Check if the channel is closed
-		if !ok {
-			logger.Infof("Container monitor channel closed for %s", t.containerName)
+	for {
+		select {
+		case <-ctx.Done():
 			return
-		}
+		case err, ok := <-t.errorCh:
+			// Check if the channel is closed
+			if !ok {
+				logger.Infof("Container monitor channel closed for %s", t.containerName)
+				return
+			}
 
-		logger.Infof("Container %s exited: %v", t.containerName, err)
+			logger.Infof("Container %s exited: %v", t.containerName, err)
 
-		// Check if the transport is already stopped before trying to stop it
-		select {
-		case <-t.shutdownCh:
-			// Transport is already stopping or stopped
-			logger.Infof("Transport for %s is already stopping or stopped", t.containerName)
-			return
-		default:
-			// Transport is still running, stop it
-			// Create a context with timeout for stopping the transport
-			stopCtx, cancel := context.WithTimeout(context.Background(), 30*time.Second)
-			defer cancel()
+			// Check if the transport is already stopped before trying to stop it
+			select {
+			case <-t.shutdownCh:
+				// Transport is already stopping or stopped
+				logger.Infof("Transport for %s is already stopping or stopped", t.containerName)
+				return
+			default:
+			}
 
-			if stopErr := t.Stop(stopCtx); stopErr != nil {
-				logger.Errorf("Error stopping transport after container exit: %v", stopErr)
+			if t.restartMonitoring(ctx) {
+				logger.Infof("Container %s was restarted", t.containerName)
+				continue
 			}
+
+			// Transport is still running, stop it
+			t.stopAfterExit()
+			return
 		}
 	}
 }
+
+// stopAfterExit stops the transport after its container exited.
+func (t *StdioTransport) stopAfterExit() {
+	// Create a context with timeout for stopping the transport
+	stopCtx, cancel := context.WithTimeout(context.Background(), 30*time.Second)
+	defer cancel()
+
+	if stopErr := t.Stop(stopCtx); stopErr != nil {
+		logger.Errorf("Error stopping transport after container exit: %v", stopErr)
+	}
+}
+
+// restartMonitoring waits for an exited container to be restarted and monitors it again.
+// It returns false if the container was not restarted.
+func (t *StdioTransport) restartMonitoring(ctx context.Context) bool {
+	if err := t.waitForRestart(ctx); err != nil {
+		logger.Info(err.Error())
+		return false
+	}
+
+	t.mutex.Lock()
+	defer t.mutex.Unlock()
+
+	// The transport may have been stopped while waiting
+	select {
+	case <-t.shutdownCh:
+		return false
+	default:
+	}
+
+	if t.monitor != nil {
+		t.monitor.StopMonitoring()
+	}
+	if err := t.startMonitoring(ctx); err != nil {
+		logger.Errorf("Failed to monitor restarted container %s: %v", t.containerName, err)
+		return false
+	}
+	return true
+}
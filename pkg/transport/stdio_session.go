@@ -0,0 +1,178 @@
+package transport
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"reflect"
+	"sort"
+	"time"
+
+	"golang.org/x/exp/jsonrpc2"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/transport/session"
+)
+
+const (
+	// sessionSaveInterval is how often the session state is checked for changes and saved
+	sessionSaveInterval = time.Second
+	// interruptedRequestCode is the JSON-RPC error code of the responses to interrupted requests
+	interruptedRequestCode = -32603
+)
+
+// sessionProxy is implemented by proxies which can report and restore the sessions of their clients
+type sessionProxy interface {
+	// SessionIDs returns the IDs of the sessions of the clients of the proxy
+	SessionIDs() []string
+	// RestoreSessions accepts the sessions of clients of a previous proxy process
+	RestoreSessions(ids []string)
+}
+
+// failRequests answers requests which will not be answered by the container with an error,
+// so clients do not wait for them forever and can retry them
+func (t *StdioTransport) failRequests(ctx context.Context, requests []*jsonrpc2.Request, reason string) {
+	for _, req := range requests {
+		resp, err := jsonrpc2.NewResponse(req.ID, nil,
+			jsonrpc2.NewError(interruptedRequestCode, fmt.Sprintf("request %s was interrupted: %s", req.Method, reason)))
+		if err != nil {
+			logger.Warnf("Warning: Failed to create response to interrupted request %s: %v", req.Method, err)
+			continue
+		}
+		if err := t.httpProxy.ForwardResponseToClients(ctx, resp); err != nil {
+			logger.Warnf("Warning: Failed to answer interrupted request %s: %v", req.Method, err)
+		}
+	}
+}
+
+// restoreSessionState restores the client sessions saved by a previous proxy process of the container,
+// so that restarting or upgrading the proxy does not force clients to re-initialize: their session IDs
+// are accepted again, their initialize handshake is replayed to the new container process, and their
+// requests which were not answered are answered with an error. The mutex must be held.
+func (t *StdioTransport) restoreSessionState(ctx context.Context) error {
+	t.persistSessions = true
+
+	state, err := session.Load(ctx, t.containerName)
+	if err != nil || state == nil {
+		return err
+	}
+
+	if proxy, ok := t.httpProxy.(sessionProxy); ok {
+		proxy.RestoreSessions(state.SessionIDs)
+	}
+	interrupted := t.applySessionState(state)
+	if err := t.replayHandshake(ctx, t.stdin, t.tracker.Replay()); err != nil {
+		return err
+	}
+	t.failRequests(ctx, interrupted, "the proxy was restarted")
+
+	logger.Infof("Restored %d client sessions of %s", len(state.SessionIDs), t.containerName)
+	return nil
+}
+
+// applySessionState restores the initialize handshake of a saved session state,
+// and returns the requests of the state which were not answered
+func (t *StdioTransport) applySessionState(state *session.State) []*jsonrpc2.Request {
+	t.tracker.RestoreHandshake(decodeRequest(state.Initialize), decodeRequest(state.Initialized))
+
+	var pending []*jsonrpc2.Request
+	for _, data := range state.Pending {
+		if req := decodeRequest(data); req != nil {
+			pending = append(pending, req)
+		}
+	}
+	return pending
+}
+
+// sessionState returns the current state of the client sessions
+func (t *StdioTransport) sessionState() *session.State {
+	state := &session.State{}
+	if proxy, ok := t.httpProxy.(sessionProxy); ok {
+		state.SessionIDs = proxy.SessionIDs()
+	}
+
+	initialize, initialized := t.tracker.Handshake()
+	state.Initialize = encodeRequest(initialize)
+	state.Initialized = encodeRequest(initialized)
+	for _, req := range t.tracker.Pending() {
+		if data := encodeRequest(req); data != nil {
+			state.Pending = append(state.Pending, data)
+		}
+	}
+	// Keep the order stable, so unchanged state is not saved again
+	sort.Slice(state.Pending, func(i, j int) bool {
+		return string(state.Pending[i]) < string(state.Pending[j])
+	})
+	return state
+}
+
+// saveSessionStates saves the state of the client sessions whenever it changes, until the transport is stopped
+func (t *StdioTransport) saveSessionStates(ctx context.Context) {
+	ticker := time.NewTicker(sessionSaveInterval)
+	defer ticker.Stop()
+
+	var saved *session.State
+	for {
+		select {
+		case <-ctx.Done():
+			return
+		case <-t.shutdownCh:
+			return
+		case <-ticker.C:
+		}
+
+		state := t.sessionState()
+		if reflect.DeepEqual(state, saved) {
+			continue
+		}
+		if err := session.Save(ctx, t.containerName, state); err != nil {
+			logger.Warnf("Warning: Failed to save client sessions of %s: %v", t.containerName, err)
+			continue
+		}
+		saved = state
+	}
+}
+
+// saveSessionState saves the state of the client sessions, if the transport persists them
+func (t *StdioTransport) saveSessionState(ctx context.Context) {
+	t.mutex.Lock()
+	persist := t.persistSessions
+	t.mutex.Unlock()
+	if !persist {
+		return
+	}
+
+	if err := session.Save(ctx, t.containerName, t.sessionState()); err != nil {
+		logger.Warnf("Warning: Failed to save client sessions of %s: %v", t.containerName, err)
+	}
+}
+
+// encodeRequest encodes a request of a client for the session state, or returns nil if there is none
+func encodeRequest(req *jsonrpc2.Request) json.RawMessage {
+	if req == nil {
+		return nil
+	}
+	data, err := jsonrpc2.EncodeMessage(req)
+	if err != nil {
+		logger.Debugf("Failed to encode request %s: %v", req.Method, err)
+		return nil
+	}
+	return data
+}
+
+// decodeRequest decodes a request of a client from the session state, or returns nil if it is not a request
+func decodeRequest(data json.RawMessage) *jsonrpc2.Request {
+	if len(data) == 0 {
+		return nil
+	}
+	msg, err := jsonrpc2.DecodeMessage(data)
+	if err != nil {
+		logger.Debugf("Failed to decode saved request: %v", err)
+		return nil
+	}
+	req, ok := msg.(*jsonrpc2.Request)
+	if !ok {
+		return nil
+	}
+	return req
+}
@@ -1,15 +1,23 @@
 package transport
 
 import (
+	"bytes"
 	"context"
+	"encoding/json"
 	"fmt"
+	"io"
+	"strings"
 	"testing"
 
 	"github.com/stretchr/testify/assert"
 	"github.com/stretchr/testify/mock"
+	"github.com/stretchr/testify/require"
+	"go.uber.org/mock/gomock"
 	"golang.org/x/exp/jsonrpc2"
 
+	"github.com/stacklok/toolhive/pkg/container/runtime/mocks"
 	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/transport/session"
 )
 
 // MockHTTPProxy is a mock implementation of types.Proxy
@@ -216,3 +224,138 @@ func TestIsSpace(t *testing.T) {
 		})
 	}
 }
+
+// bufferWriteCloser records the messages written to the container stdin
+type bufferWriteCloser struct {
+	bytes.Buffer
+}
+
+func (*bufferWriteCloser) Close() error {
+	return nil
+}
+
+func TestStdioTransport_ReattachReplaysHandshake(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	ctrl := gomock.NewController(t)
+	mockRuntime := mocks.NewMockRuntime(ctrl)
+
+	stdin := &bufferWriteCloser{}
+	stdout := io.NopCloser(strings.NewReader(""))
+	mockRuntime.EXPECT().IsWorkloadRunning(gomock.Any(), "container-id").Return(true, nil)
+	mockRuntime.EXPECT().AttachToWorkload(gomock.Any(), "container-id").Return(stdin, stdout, nil)
+
+	transport := &StdioTransport{containerID: "container-id", containerName: "server", runtime: mockRuntime}
+
+	initialize, err := jsonrpc2.NewCall(jsonrpc2.Int64ID(1), "initialize", json.RawMessage(`{"protocolVersion":"2025-03-26"}`))
+	require.NoError(t, err)
+	initialized, err := jsonrpc2.NewNotification("notifications/initialized", json.RawMessage(`{}`))
+	require.NoError(t, err)
+	transport.tracker.RecordRequest(initialize)
+	transport.tracker.RecordRequest(initialized)
+
+	reattached, err := transport.reattach(context.Background())
+	require.NoError(t, err)
+	assert.Equal(t, stdout, reattached)
+
+	// The initialize request is replayed with its own ID, followed by the initialized notification
+	lines := strings.Split(strings.TrimSpace(stdin.String()), "\n")
+	require.Len(t, lines, 2)
+	replayed, err := jsonrpc2.DecodeMessage([]byte(lines[0]))
+	require.NoError(t, err)
+	replayedRequest, ok := replayed.(*jsonrpc2.Request)
+	require.True(t, ok)
+	assert.Equal(t, "initialize", replayedRequest.Method)
+	assert.Equal(t, session.ReplayedInitializeID, replayedRequest.ID)
+	assert.JSONEq(t, `{"protocolVersion":"2025-03-26"}`, string(replayedRequest.Params))
+	assert.Contains(t, lines[1], "notifications/initialized")
+
+	// Only the response to the replayed request is dropped instead of being forwarded to clients
+	mockProxy := new(MockHTTPProxy)
+	mockProxy.On("ForwardResponseToClients", mock.Anything, mock.Anything).Return(nil)
+	transport.httpProxy = mockProxy
+	response := `{"jsonrpc":"2.0","id":"toolhive-reinitialize","result":{}}`
+	transport.parseAndForwardJSONRPC(context.Background(), response)
+	mockProxy.AssertNotCalled(t, "ForwardResponseToClients", mock.Anything, mock.Anything)
+	transport.parseAndForwardJSONRPC(context.Background(), response)
+	mockProxy.AssertNumberOfCalls(t, "ForwardResponseToClients", 1)
+}
+
+func TestStdioTransport_ReattachFailsPendingRequests(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	ctrl := gomock.NewController(t)
+	mockRuntime := mocks.NewMockRuntime(ctrl)
+
+	stdin := &bufferWriteCloser{}
+	stdout := io.NopCloser(strings.NewReader(""))
+	mockRuntime.EXPECT().IsWorkloadRunning(gomock.Any(), "container-id").Return(true, nil)
+	mockRuntime.EXPECT().AttachToWorkload(gomock.Any(), "container-id").Return(stdin, stdout, nil)
+
+	mockProxy := new(MockHTTPProxy)
+	mockProxy.On("ForwardResponseToClients", mock.Anything, mock.Anything).Return(nil)
+	transport := &StdioTransport{
+		containerID:   "container-id",
+		containerName: "server",
+		runtime:       mockRuntime,
+		httpProxy:     mockProxy,
+	}
+
+	answered, err := jsonrpc2.NewCall(jsonrpc2.Int64ID(1), "tools/list", nil)
+	require.NoError(t, err)
+	interrupted, err := jsonrpc2.NewCall(jsonrpc2.Int64ID(2), "tools/call", json.RawMessage(`{"name":"fetch"}`))
+	require.NoError(t, err)
+	transport.tracker.RecordRequest(answered)
+	transport.tracker.RecordRequest(interrupted)
+	transport.parseAndForwardJSONRPC(context.Background(), `{"jsonrpc":"2.0","id":1,"result":{}}`)
+
+	_, err = transport.reattach(context.Background())
+	require.NoError(t, err)
+
+	// Only the request which was not answered is answered with an error
+	mockProxy.AssertNumberOfCalls(t, "ForwardResponseToClients", 2)
+	last := mockProxy.Calls[1].Arguments.Get(1)
+	resp, ok := last.(*jsonrpc2.Response)
+	require.True(t, ok)
+	assert.Equal(t, jsonrpc2.Int64ID(2), resp.ID)
+	assert.Error(t, resp.Error)
+	assert.Empty(t, transport.tracker.Pending())
+}
+
+func TestStdioTransport_SessionState(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	transport := &StdioTransport{containerName: "server"}
+
+	initialize, err := jsonrpc2.NewCall(jsonrpc2.Int64ID(1), "initialize", json.RawMessage(`{"protocolVersion":"2025-03-26"}`))
+	require.NoError(t, err)
+	initialized, err := jsonrpc2.NewNotification("notifications/initialized", json.RawMessage(`{}`))
+	require.NoError(t, err)
+	call, err := jsonrpc2.NewCall(jsonrpc2.StringID("call"), "tools/call", json.RawMessage(`{"name":"fetch"}`))
+	require.NoError(t, err)
+	transport.tracker.RecordRequest(initialize)
+	transport.tracker.RecordRequest(initialized)
+	transport.tracker.RecordRequest(call)
+
+	// The initialize request and notifications are not pending requests
+	state := transport.sessionState()
+	assert.Empty(t, state.SessionIDs)
+	assert.Contains(t, string(state.Initialize), `"initialize"`)
+	assert.Contains(t, string(state.Initialized), `"notifications/initialized"`)
+	require.Len(t, state.Pending, 1)
+	assert.Contains(t, string(state.Pending[0]), `"tools/call"`)
+
+	// A new transport restores the handshake and returns the requests to answer
+	restored := &StdioTransport{containerName: "server"}
+	pending := restored.applySessionState(state)
+	restoredInitialize, restoredInitialized := restored.tracker.Handshake()
+	require.NotNil(t, restoredInitialize)
+	assert.Equal(t, "initialize", restoredInitialize.Method)
+	require.NotNil(t, restoredInitialized)
+	assert.Len(t, restored.tracker.Replay(), 2)
+	require.Len(t, pending, 1)
+	assert.Equal(t, jsonrpc2.StringID("call"), pending[0].ID)
+}
@@ -4,7 +4,9 @@ package types
 
 import (
 	"context"
+	"crypto/tls"
 	"net/http"
+	"time"
 
 	"golang.org/x/exp/jsonrpc2"
 
@@ -28,9 +30,10 @@ type Transport interface {
 	// Setup prepares the transport for use.
 	// The runtime parameter provides access to container operations.
 	// The permissionProfile is used to configure container permissions.
-	// The k8sPodTemplatePatch is a JSON string to patch the Kubernetes pod template.
+	// The containerOptions are the base workload options, which the transport extends with its own
+	// settings (e.g. exposed ports or stdio attachment). It may be nil.
 	Setup(ctx context.Context, runtime rt.Runtime, containerName string, image string, cmdArgs []string,
-		envVars, labels map[string]string, permissionProfile *permissions.Profile, k8sPodTemplatePatch string,
+		envVars, labels map[string]string, permissionProfile *permissions.Profile, containerOptions *rt.DeployWorkloadOptions,
 		isolateNetwork bool) error
 
 	// Start initializes the transport and begins processing messages.
@@ -44,6 +47,16 @@ type Transport interface {
 	IsRunning(ctx context.Context) (bool, error)
 }
 
+// ContainerSwitcher is implemented by the transports which can replace the container of the MCP server
+// while the proxy keeps serving the clients, so the server can be upgraded without downtime.
+type ContainerSwitcher interface {
+	// SwitchContainer deploys a new container of the MCP server from the image, switches the proxy to it
+	// once it responds within healthTimeout, and removes the previous container once the requests in flight
+	// to it completed. It returns the name of the new container. The previous container keeps serving the
+	// clients if the new container does not respond.
+	SwitchContainer(ctx context.Context, image string, healthTimeout time.Duration) (string, error)
+}
+
 // TransportType represents the type of transport to use.
 //
 //nolint:revive // Intentionally named TransportType despite package name
@@ -59,6 +72,9 @@ const (
 	// TransportTypeStreamableHTTP represents the streamable HTTP transport.
 	TransportTypeStreamableHTTP TransportType = "streamable-http"
 
+	// TransportTypeWebSocket represents the WebSocket transport.
+	TransportTypeWebSocket TransportType = "websocket"
+
 	// TransportTypeInspector represents the transport mode for MCP Inspector.
 	TransportTypeInspector TransportType = "inspector"
 )
@@ -77,6 +93,8 @@ func ParseTransportType(s string) (TransportType, error) {
 		return TransportTypeSSE, nil
 	case "streamable-http", "STREAMABLE-HTTP":
 		return TransportTypeStreamableHTTP, nil
+	case "websocket", "WEBSOCKET":
+		return TransportTypeWebSocket, nil
 	case "inspector", "INSPECTOR":
 		return TransportTypeInspector, nil
 	default:
@@ -121,6 +139,14 @@ type Config struct {
 	// Host is the host to use for network transports.
 	Host string
 
+	// SSEPath is the path of the SSE endpoint served by the proxy.
+	// This is only applicable to stdio transport. If empty, the default path is used.
+	SSEPath string
+
+	// MessagesPath is the path of the JSON-RPC message endpoint served by the proxy.
+	// This is only applicable to stdio transport. If empty, the default path is used.
+	MessagesPath string
+
 	// Runtime is the container runtime to use.
 	// This is used for container operations like creating, starting, and attaching.
 	Runtime rt.Runtime
@@ -136,4 +162,46 @@ type Config struct {
 	// PrometheusHandler is an optional HTTP handler for Prometheus metrics endpoint.
 	// If provided, it will be exposed at /metrics on the transport's HTTP server.
 	PrometheusHandler http.Handler
+
+	// NamedPipe is the path of a Windows named pipe on which the proxy is served in addition to the port.
+	// If empty, the proxy is only served on the port.
+	NamedPipe string
+
+	// ListenSocket is the path of a Unix domain socket on which the proxy is served instead of the port.
+	// If empty, the proxy is served on the port.
+	ListenSocket string
+
+	// UpstreamHTTP2 indicates whether the proxy forwards the requests of HTTP/2 clients to the MCP server
+	// over HTTP/2. gRPC requests are always forwarded over HTTP/2.
+	UpstreamHTTP2 bool
+
+	// TLSConfig is the TLS configuration with which the proxy serves HTTPS.
+	// If nil, the proxy serves plain HTTP.
+	TLSConfig *tls.Config
+
+	// RestartUnhealthy indicates whether the container is restarted when the MCP server becomes unhealthy.
+	// If false, an unhealthy MCP server is only reported as such.
+	RestartUnhealthy bool
+
+	// Limits are the timeouts of the proxy and the maximum size of the messages of the clients.
+	Limits ProxyLimits
+}
+
+// ProxyLimits are the timeouts of the proxy of a transport and the maximum size of the messages it accepts.
+// Zero values keep the defaults: the proxy waits 30 seconds to connect to the MCP server, keeps idle
+// connections open for 90 seconds, never considers SSE streams of the MCP server dropped while they are
+// open, and accepts messages of any size.
+type ProxyLimits struct {
+	// ConnectTimeout is how long the proxy waits to connect to the MCP server.
+	ConnectTimeout time.Duration `json:"connect_timeout,omitempty" yaml:"connect_timeout,omitempty"`
+
+	// IdleTimeout is how long idle connections of the clients and to the MCP server are kept open.
+	IdleTimeout time.Duration `json:"idle_timeout,omitempty" yaml:"idle_timeout,omitempty"`
+
+	// SSEReadTimeout is how long an SSE stream of the MCP server may send nothing before it is considered
+	// dropped and reconnected.
+	SSEReadTimeout time.Duration `json:"sse_read_timeout,omitempty" yaml:"sse_read_timeout,omitempty"`
+
+	// MaxMessageSize is the maximum size in bytes of the JSON-RPC messages the clients send.
+	MaxMessageSize int64 `json:"max_message_size,omitempty" yaml:"max_message_size,omitempty"`
 }
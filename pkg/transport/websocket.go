@@ -0,0 +1,312 @@
+package transport
+
+import (
+	"context"
+	"crypto/tls"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"time"
+
+	"github.com/cenkalti/backoff/v5"
+	"github.com/gorilla/websocket"
+	"golang.org/x/exp/jsonrpc2"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/transport/errors"
+	"github.com/stacklok/toolhive/pkg/transport/proxy/httpsse"
+	"github.com/stacklok/toolhive/pkg/transport/session"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+const (
+	// WebSocketEndpoint is the path of the WebSocket endpoint of MCP servers using the WebSocket transport
+	WebSocketEndpoint = "/ws"
+	// webSocketSubprotocol is the subprotocol of the MCP WebSocket transport
+	webSocketSubprotocol = "mcp"
+	// webSocketHandshakeTimeout is how long to wait for the WebSocket handshake with the MCP server
+	webSocketHandshakeTimeout = 10 * time.Second
+	// webSocketWriteTimeout is how long to wait for a message to be written to the MCP server
+	webSocketWriteTimeout = 10 * time.Second
+	// webSocketInitialBackoff and webSocketMaxBackoff bound the delay before connecting to the MCP server again
+	webSocketInitialBackoff = 500 * time.Millisecond
+	webSocketMaxBackoff     = 30 * time.Second
+)
+
+// WebSocketTransport implements the Transport interface for MCP servers exposing a WebSocket endpoint.
+// The container is deployed like for the HTTP transports, and the proxy serves the same endpoints as for
+// the stdio transport, exchanging the messages of the clients with the MCP server over a WebSocket
+// connection. The connection is re-established with an exponential backoff whenever it is lost.
+type WebSocketTransport struct {
+	*HTTPTransport
+
+	ssePath      string
+	messagesPath string
+
+	// Session of the client, whose handshake is replayed when connecting to the MCP server again
+	tracker session.Tracker
+}
+
+// NewWebSocketTransport creates a new WebSocket transport.
+// The SSE and message endpoints of the proxy are served at ssePath and messagesPath, or at the defaults if empty.
+func NewWebSocketTransport(
+	host string,
+	port int,
+	targetPort int,
+	runtime rt.Runtime,
+	debug bool,
+	targetHost string,
+	ssePath string,
+	messagesPath string,
+	prometheusHandler http.Handler,
+	namedPipe string,
+	listenSocket string,
+	tlsConfig *tls.Config,
+	restartUnhealthy bool,
+	limits types.ProxyLimits,
+	middlewares ...types.Middleware,
+) *WebSocketTransport {
+	return &WebSocketTransport{
+		HTTPTransport: NewHTTPTransport(
+			types.TransportTypeWebSocket,
+			host,
+			port,
+			targetPort,
+			runtime,
+			debug,
+			targetHost,
+			prometheusHandler,
+			namedPipe,
+			listenSocket,
+			false,
+			tlsConfig,
+			restartUnhealthy,
+			limits,
+			middlewares...,
+		),
+		ssePath:      ssePath,
+		messagesPath: messagesPath,
+	}
+}
+
+// Start initializes the transport and begins processing messages.
+// The transport is responsible for connecting to the WebSocket endpoint of the container.
+func (t *WebSocketTransport) Start(ctx context.Context) error {
+	t.mutex.Lock()
+	defer t.mutex.Unlock()
+
+	if t.containerID == "" {
+		return errors.ErrContainerIDNotSet
+	}
+
+	if t.containerName == "" {
+		return errors.ErrContainerNameNotSet
+	}
+
+	if t.runtime == nil {
+		return fmt.Errorf("container runtime not set")
+	}
+
+	if t.targetPort <= 0 {
+		return fmt.Errorf("target port not set for WebSocket transport")
+	}
+
+	targetURL := fmt.Sprintf("ws://%s:%d%s", t.targetHost, t.targetPort, WebSocketEndpoint)
+	logger.Infof("Setting up WebSocket proxy to forward from host port %d to %s", t.port, targetURL)
+
+	// Create and start the HTTP SSE proxy with middlewares
+	httpProxy := httpsse.NewHTTPSSEProxy(
+		t.host, t.port, t.containerName, t.ssePath, t.messagesPath, t.prometheusHandler, t.namedPipe, t.tlsConfig, t.middlewares...)
+	if t.restartUnhealthy {
+		httpProxy.SetUnhealthyHandler(t.restartUnhealthyContainer)
+	}
+	httpProxy.SetLimits(t.limits)
+	httpProxy.SetListenSocket(t.listenSocket)
+	t.proxy = httpProxy
+	if err := t.proxy.Start(ctx); err != nil {
+		return err
+	}
+
+	go t.bridge(ctx, t.proxy, targetURL)
+
+	logger.Infof("WebSocket transport started for container %s on port %d", t.containerName, t.port)
+
+	return t.startMonitoring(ctx)
+}
+
+// bridge exchanges the messages of the clients with the MCP server until the transport is stopped,
+// connecting to the MCP server again with an exponential backoff whenever the connection is lost.
+func (t *WebSocketTransport) bridge(ctx context.Context, proxy types.Proxy, targetURL string) {
+	ctx, cancel := context.WithCancel(ctx)
+	defer cancel()
+
+	// Monitor for shutdown signal
+	go func() {
+		select {
+		case <-t.shutdownCh:
+			cancel()
+		case <-ctx.Done():
+		}
+	}()
+
+	expBackoff := backoff.NewExponentialBackOff()
+	expBackoff.InitialInterval = webSocketInitialBackoff
+	expBackoff.MaxInterval = webSocketMaxBackoff
+
+	for {
+		conn, err := dialWebSocket(ctx, targetURL, t.limits.ConnectTimeout)
+		if err == nil {
+			logger.Infof("Connected to the WebSocket endpoint of %s", t.containerName)
+			expBackoff.Reset()
+			t.exchangeMessages(ctx, conn, proxy)
+			t.failPendingRequests(ctx, proxy)
+		}
+		if ctx.Err() != nil {
+			return
+		}
+
+		delay := expBackoff.NextBackOff()
+		if err != nil {
+			logger.Debugf("Failed to connect to %s, retrying in %s: %v", targetURL, delay, err)
+		} else {
+			logger.Infof("Connection to %s lost, reconnecting in %s", t.containerName, delay)
+		}
+		select {
+		case <-ctx.Done():
+			return
+		case <-time.After(delay):
+		}
+	}
+}
+
+// dialWebSocket opens a WebSocket connection to the MCP server, waiting for the handshake for the
+// connect timeout, or for the default handshake timeout if it is zero
+func dialWebSocket(ctx context.Context, targetURL string, connectTimeout time.Duration) (*websocket.Conn, error) {
+	if connectTimeout <= 0 {
+		connectTimeout = webSocketHandshakeTimeout
+	}
+	dialer := websocket.Dialer{
+		HandshakeTimeout: connectTimeout,
+		Subprotocols:     []string{webSocketSubprotocol},
+	}
+	conn, resp, err := dialer.DialContext(ctx, targetURL, nil)
+	if resp != nil && resp.Body != nil {
+		_ = resp.Body.Close()
+	}
+	return conn, err
+}
+
+// exchangeMessages exchanges messages with the MCP server over a connection until it is lost or the
+// context is cancelled. The initialize handshake of the client is replayed first if the connection was
+// re-established. Requests which could not be sent are answered with an error once the connection is lost.
+func (t *WebSocketTransport) exchangeMessages(ctx context.Context, conn *websocket.Conn, proxy types.Proxy) {
+	defer conn.Close()
+
+	readErrCh := make(chan error, 1)
+	go func() {
+		readErrCh <- t.readMessages(ctx, conn, proxy)
+	}()
+
+	for _, msg := range t.tracker.Replay() {
+		if err := writeWebSocketMessage(conn, msg); err != nil {
+			logger.Warnf("Warning: Failed to replay the initialize handshake to %s: %v", t.containerName, err)
+			return
+		}
+	}
+
+	messageCh := proxy.GetMessageChannel()
+	for {
+		select {
+		case <-ctx.Done():
+			closeMessage := websocket.FormatCloseMessage(websocket.CloseNormalClosure, "")
+			_ = conn.WriteControl(websocket.CloseMessage, closeMessage, time.Now().Add(time.Second))
+			return
+		case err := <-readErrCh:
+			logger.Debugf("Failed to read from the WebSocket endpoint of %s: %v", t.containerName, err)
+			return
+		case msg := <-messageCh:
+			t.tracker.RecordRequest(msg)
+			if err := writeWebSocketMessage(conn, msg); err != nil {
+				logger.Debugf("Failed to write to the WebSocket endpoint of %s: %v", t.containerName, err)
+				return
+			}
+		}
+	}
+}
+
+// readMessages forwards the messages of the MCP server to the clients until the connection fails
+func (t *WebSocketTransport) readMessages(ctx context.Context, conn *websocket.Conn, proxy types.Proxy) error {
+	for {
+		_, data, err := conn.ReadMessage()
+		if err != nil {
+			return err
+		}
+
+		msgs, err := decodeWebSocketFrame(data)
+		if err != nil {
+			logger.Errorf("Error parsing JSON-RPC message: %v", err)
+			continue
+		}
+		for _, msg := range msgs {
+			if !t.tracker.RecordResponse(msg) {
+				logger.Infof("Dropping the response to the replayed initialize request of %s", t.containerName)
+				continue
+			}
+			if err := proxy.ForwardResponseToClients(ctx, msg); err != nil {
+				logger.Errorf("Error forwarding to SSE clients: %v", err)
+			}
+		}
+	}
+}
+
+// writeWebSocketMessage writes a JSON-RPC message to the MCP server as a text frame
+func writeWebSocketMessage(conn *websocket.Conn, msg jsonrpc2.Message) error {
+	data, err := jsonrpc2.EncodeMessage(msg)
+	if err != nil {
+		return fmt.Errorf("failed to encode JSON-RPC message: %w", err)
+	}
+	if err := conn.SetWriteDeadline(time.Now().Add(webSocketWriteTimeout)); err != nil {
+		return err
+	}
+	return conn.WriteMessage(websocket.TextMessage, data)
+}
+
+// decodeWebSocketFrame decodes the JSON-RPC messages of a frame, which holds either a single message
+// or a batch of messages
+func decodeWebSocketFrame(data []byte) ([]jsonrpc2.Message, error) {
+	var batch []json.RawMessage
+	if err := json.Unmarshal(data, &batch); err != nil {
+		msg, err := jsonrpc2.DecodeMessage(data)
+		if err != nil {
+			return nil, err
+		}
+		return []jsonrpc2.Message{msg}, nil
+	}
+
+	msgs := make([]jsonrpc2.Message, 0, len(batch))
+	for _, raw := range batch {
+		msg, err := jsonrpc2.DecodeMessage(raw)
+		if err != nil {
+			return nil, err
+		}
+		msgs = append(msgs, msg)
+	}
+	return msgs, nil
+}
+
+// failPendingRequests answers the requests which will not be answered by the MCP server, since the
+// connection to it was lost, with an error, so clients do not wait for them forever and can retry them
+func (t *WebSocketTransport) failPendingRequests(ctx context.Context, proxy types.Proxy) {
+	for _, req := range t.tracker.TakePending() {
+		resp, err := jsonrpc2.NewResponse(req.ID, nil, jsonrpc2.NewError(interruptedRequestCode,
+			fmt.Sprintf("request %s was interrupted: the connection to the MCP server was lost", req.Method)))
+		if err != nil {
+			logger.Warnf("Warning: Failed to create response to interrupted request %s: %v", req.Method, err)
+			continue
+		}
+		if err := proxy.ForwardResponseToClients(ctx, resp); err != nil {
+			logger.Warnf("Warning: Failed to answer interrupted request %s: %v", req.Method, err)
+		}
+	}
+}
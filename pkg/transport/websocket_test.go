@@ -0,0 +1,230 @@
+package transport
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"sync/atomic"
+	"testing"
+	"time"
+
+	"github.com/gorilla/websocket"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/mock"
+	"github.com/stretchr/testify/require"
+	"golang.org/x/exp/jsonrpc2"
+
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/transport/session"
+)
+
+func TestDecodeWebSocketFrame(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name          string
+		frame         string
+		expectedTypes []string
+		expectError   bool
+	}{
+		{
+			name:          "Request",
+			frame:         `{"jsonrpc":"2.0","id":1,"method":"tools/list"}`,
+			expectedTypes: []string{"*jsonrpc2.Request"},
+		},
+		{
+			name:          "Response",
+			frame:         `{"jsonrpc":"2.0","id":1,"result":{}}`,
+			expectedTypes: []string{"*jsonrpc2.Response"},
+		},
+		{
+			name: "Batch",
+			frame: `[{"jsonrpc":"2.0","id":1,"result":{}},` +
+				`{"jsonrpc":"2.0","method":"notifications/tools/list_changed"}]`,
+			expectedTypes: []string{"*jsonrpc2.Response", "*jsonrpc2.Request"},
+		},
+		{
+			name:        "Invalid message in batch",
+			frame:       `[{"jsonrpc":"2.0","id":1,"result":{}},{"id":2}]`,
+			expectError: true,
+		},
+		{
+			name:        "Not JSON",
+			frame:       `hello`,
+			expectError: true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			msgs, err := decodeWebSocketFrame([]byte(tt.frame))
+			if tt.expectError {
+				assert.Error(t, err)
+				return
+			}
+			require.NoError(t, err)
+			msgTypes := make([]string, 0, len(msgs))
+			for _, msg := range msgs {
+				msgTypes = append(msgTypes, fmt.Sprintf("%T", msg))
+			}
+			assert.Equal(t, tt.expectedTypes, msgTypes)
+		})
+	}
+}
+
+func TestWebSocketTransport_ReconnectReplaysHandshake(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	// The first connection is lost after its first response
+	var connections atomic.Int32
+	received := make(chan jsonrpc2.Message, 10)
+	server := newWebSocketServer(t, func(conn *websocket.Conn) {
+		first := connections.Add(1) == 1
+		for {
+			msg, ok := readTestMessage(conn)
+			if !ok {
+				return
+			}
+			received <- msg
+			if req, isRequest := msg.(*jsonrpc2.Request); isRequest && req.IsCall() {
+				writeTestResponse(t, conn, req)
+				if first {
+					return
+				}
+			}
+		}
+	})
+
+	messageCh, forwarded, mockProxy := newChannelProxy()
+	transport := newTestWebSocketTransport()
+	go transport.bridge(context.Background(), mockProxy, webSocketURL(server))
+	defer close(transport.shutdownCh)
+
+	initialize, err := jsonrpc2.NewCall(jsonrpc2.Int64ID(1), "initialize", json.RawMessage(`{"protocolVersion":"2025-03-26"}`))
+	require.NoError(t, err)
+	messageCh <- initialize
+	assert.Equal(t, "initialize", receiveTest(t, received).(*jsonrpc2.Request).Method)
+	assert.Equal(t, jsonrpc2.Int64ID(1), receiveTest(t, forwarded).(*jsonrpc2.Response).ID)
+
+	// The initialize request is replayed with its own ID once connected again
+	replayed := receiveTest(t, received).(*jsonrpc2.Request)
+	assert.Equal(t, "initialize", replayed.Method)
+	assert.Equal(t, session.ReplayedInitializeID, replayed.ID)
+	assert.JSONEq(t, `{"protocolVersion":"2025-03-26"}`, string(replayed.Params))
+
+	// The response to the replayed request is dropped instead of being forwarded to clients
+	listTools, err := jsonrpc2.NewCall(jsonrpc2.Int64ID(2), "tools/list", nil)
+	require.NoError(t, err)
+	messageCh <- listTools
+	assert.Equal(t, "tools/list", receiveTest(t, received).(*jsonrpc2.Request).Method)
+	assert.Equal(t, jsonrpc2.Int64ID(2), receiveTest(t, forwarded).(*jsonrpc2.Response).ID)
+}
+
+func TestWebSocketTransport_ConnectionLossFailsPendingRequests(t *testing.T) {
+	t.Parallel()
+	logger.Initialize()
+
+	// The connection is lost without answering the request
+	server := newWebSocketServer(t, func(conn *websocket.Conn) {
+		readTestMessage(conn)
+	})
+
+	messageCh, forwarded, mockProxy := newChannelProxy()
+	transport := newTestWebSocketTransport()
+	go transport.bridge(context.Background(), mockProxy, webSocketURL(server))
+	defer close(transport.shutdownCh)
+
+	call, err := jsonrpc2.NewCall(jsonrpc2.Int64ID(3), "tools/call", json.RawMessage(`{"name":"fetch"}`))
+	require.NoError(t, err)
+	messageCh <- call
+
+	resp := receiveTest(t, forwarded).(*jsonrpc2.Response)
+	assert.Equal(t, jsonrpc2.Int64ID(3), resp.ID)
+	assert.ErrorContains(t, resp.Error, "the connection to the MCP server was lost")
+}
+
+// newWebSocketServer starts a WebSocket server handling connections to the WebSocket endpoint
+func newWebSocketServer(t *testing.T, handle func(conn *websocket.Conn)) *httptest.Server {
+	t.Helper()
+
+	upgrader := websocket.Upgrader{Subprotocols: []string{webSocketSubprotocol}}
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.URL.Path != WebSocketEndpoint {
+			http.NotFound(w, r)
+			return
+		}
+		conn, err := upgrader.Upgrade(w, r, nil)
+		if err != nil {
+			return
+		}
+		defer conn.Close()
+		handle(conn)
+	}))
+	t.Cleanup(server.Close)
+	return server
+}
+
+// webSocketURL returns the URL of the WebSocket endpoint of a test server
+func webSocketURL(server *httptest.Server) string {
+	return "ws" + strings.TrimPrefix(server.URL, "http") + WebSocketEndpoint
+}
+
+// newTestWebSocketTransport creates a WebSocket transport without a container
+func newTestWebSocketTransport() *WebSocketTransport {
+	return &WebSocketTransport{
+		HTTPTransport: &HTTPTransport{containerName: "server", shutdownCh: make(chan struct{})},
+	}
+}
+
+// newChannelProxy returns a mock proxy whose clients send the messages of the message channel,
+// and receive the messages on the forwarded channel
+func newChannelProxy() (chan jsonrpc2.Message, chan jsonrpc2.Message, *MockHTTPProxy) {
+	messageCh := make(chan jsonrpc2.Message, 10)
+	forwarded := make(chan jsonrpc2.Message, 10)
+	mockProxy := new(MockHTTPProxy)
+	mockProxy.On("GetMessageChannel").Return(messageCh)
+	mockProxy.On("ForwardResponseToClients", mock.Anything, mock.Anything).Run(func(args mock.Arguments) {
+		forwarded <- args.Get(1).(jsonrpc2.Message)
+	}).Return(nil)
+	return messageCh, forwarded, mockProxy
+}
+
+// readTestMessage reads a JSON-RPC message from a WebSocket connection
+func readTestMessage(conn *websocket.Conn) (jsonrpc2.Message, bool) {
+	_, data, err := conn.ReadMessage()
+	if err != nil {
+		return nil, false
+	}
+	msg, err := jsonrpc2.DecodeMessage(data)
+	return msg, err == nil
+}
+
+// writeTestResponse answers a request with its method as result
+func writeTestResponse(t *testing.T, conn *websocket.Conn, req *jsonrpc2.Request) {
+	t.Helper()
+
+	resp, err := jsonrpc2.NewResponse(req.ID, map[string]string{"method": req.Method}, nil)
+	assert.NoError(t, err)
+	data, err := jsonrpc2.EncodeMessage(resp)
+	assert.NoError(t, err)
+	assert.NoError(t, conn.WriteMessage(websocket.TextMessage, data))
+}
+
+// receiveTest receives a message from a channel, failing the test if none arrives in time
+func receiveTest(t *testing.T, ch <-chan jsonrpc2.Message) jsonrpc2.Message {
+	t.Helper()
+
+	select {
+	case msg := <-ch:
+		return msg
+	case <-time.After(10 * time.Second):
+		require.FailNow(t, "timed out waiting for a message")
+		return nil
+	}
+}
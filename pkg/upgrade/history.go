@@ -0,0 +1,150 @@
+package upgrade
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"time"
+
+	"github.com/stacklok/toolhive/pkg/runner"
+	"github.com/stacklok/toolhive/pkg/state"
+)
+
+// MaxHistoryEntries is the maximum number of transitions kept in the history of a server
+const MaxHistoryEntries = 20
+
+// TransitionKind is the kind of change that moved a server from one image to another
+type TransitionKind string
+
+const (
+	// TransitionUpgrade is an upgrade to a newer image
+	TransitionUpgrade TransitionKind = "upgrade"
+	// TransitionRollback is a rollback to the image the server ran before an upgrade
+	TransitionRollback TransitionKind = "rollback"
+)
+
+// Transition records the move of a server from one image to another
+type Transition struct {
+	// Kind is the kind of transition
+	Kind TransitionKind `json:"kind"`
+	// Time is when the transition happened
+	Time time.Time `json:"time"`
+	// Image is the image reference of the server
+	Image string `json:"image"`
+	// FromImageID is the ID of the image the server was running before the transition
+	FromImageID string `json:"from_image_id"`
+	// ToImageID is the ID of the image the server is running after the transition
+	ToImageID string `json:"to_image_id"`
+	// FromDigest is the registry digest of the image before the transition, if known
+	FromDigest string `json:"from_digest,omitempty"`
+	// ToDigest is the registry digest of the image after the transition, if known
+	ToDigest string `json:"to_digest,omitempty"`
+	// Config is the run configuration of the server before the transition
+	Config *runner.RunConfig `json:"config,omitempty"`
+	// RolledBack indicates that an upgrade was reverted by a rollback
+	RolledBack bool `json:"rolled_back,omitempty"`
+}
+
+// History is the changelog of the image transitions of a server, oldest first
+type History struct {
+	Transitions []Transition `json:"transitions"`
+}
+
+// Record appends a transition to the history, discarding the oldest transitions
+// once the history holds more than MaxHistoryEntries
+func (h *History) Record(transition Transition) {
+	h.Transitions = append(h.Transitions, transition)
+	if len(h.Transitions) > MaxHistoryEntries {
+		h.Transitions = h.Transitions[len(h.Transitions)-MaxHistoryEntries:]
+	}
+}
+
+// LastUpgrade returns the most recent upgrade which has not been rolled back yet, or nil if there is none.
+// Rolling back upgrades one at a time walks back through the history.
+func (h *History) LastUpgrade() *Transition {
+	for i := len(h.Transitions) - 1; i >= 0; i-- {
+		transition := &h.Transitions[i]
+		if transition.Kind == TransitionUpgrade && !transition.RolledBack {
+			return transition
+		}
+	}
+	return nil
+}
+
+// IsRolledBack returns true if an upgrade to the given image was rolled back,
+// in which case the server should not be upgraded to that image again
+func (h *History) IsRolledBack(imageID string) bool {
+	for _, transition := range h.Transitions {
+		if transition.Kind == TransitionUpgrade && transition.RolledBack && transition.ToImageID == imageID {
+			return true
+		}
+	}
+	return false
+}
+
+// LoadHistory loads the upgrade history of the given server.
+// An empty history is returned if the server was never upgraded.
+func LoadHistory(ctx context.Context, baseName string) (*History, error) {
+	store, err := state.NewUpgradeHistoryStore(state.DefaultAppName)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create state store: %w", err)
+	}
+
+	exists, err := store.Exists(ctx, baseName)
+	if err != nil {
+		return nil, fmt.Errorf("failed to check if upgrade history exists: %w", err)
+	}
+	if !exists {
+		return &History{}, nil
+	}
+
+	reader, err := store.GetReader(ctx, baseName)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get reader for upgrade history: %w", err)
+	}
+	defer reader.Close()
+
+	history := &History{}
+	if err := json.NewDecoder(reader).Decode(history); err != nil {
+		return nil, fmt.Errorf("failed to read upgrade history: %w", err)
+	}
+	return history, nil
+}
+
+// SaveHistory saves the upgrade history of the given server
+func SaveHistory(ctx context.Context, baseName string, history *History) error {
+	store, err := state.NewUpgradeHistoryStore(state.DefaultAppName)
+	if err != nil {
+		return fmt.Errorf("failed to create state store: %w", err)
+	}
+
+	writer, err := store.GetWriter(ctx, baseName)
+	if err != nil {
+		return fmt.Errorf("failed to get writer for upgrade history: %w", err)
+	}
+	defer writer.Close()
+
+	encoder := json.NewEncoder(writer)
+	encoder.SetIndent("", "  ")
+	if err := encoder.Encode(history); err != nil {
+		return fmt.Errorf("failed to write upgrade history: %w", err)
+	}
+	return nil
+}
+
+// DeleteHistory deletes the upgrade history of the given server, if any
+func DeleteHistory(ctx context.Context, baseName string) error {
+	store, err := state.NewUpgradeHistoryStore(state.DefaultAppName)
+	if err != nil {
+		return fmt.Errorf("failed to create state store: %w", err)
+	}
+
+	exists, err := store.Exists(ctx, baseName)
+	if err != nil {
+		return fmt.Errorf("failed to check if upgrade history exists: %w", err)
+	}
+	if !exists {
+		return nil
+	}
+	return store.Delete(ctx, baseName)
+}
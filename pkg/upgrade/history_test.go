@@ -0,0 +1,46 @@
+package upgrade
+
+import (
+	"fmt"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestHistory_Record(t *testing.T) {
+	t.Parallel()
+
+	history := &History{}
+	for i := 0; i < MaxHistoryEntries+5; i++ {
+		history.Record(Transition{ToImageID: fmt.Sprintf("sha256:%d", i)})
+	}
+
+	assert.Len(t, history.Transitions, MaxHistoryEntries)
+	assert.Equal(t, "sha256:5", history.Transitions[0].ToImageID)
+	assert.Equal(t, fmt.Sprintf("sha256:%d", MaxHistoryEntries+4), history.Transitions[MaxHistoryEntries-1].ToImageID)
+}
+
+func TestHistory_LastUpgrade(t *testing.T) {
+	t.Parallel()
+
+	history := &History{}
+	assert.Nil(t, history.LastUpgrade())
+
+	history.Record(Transition{Kind: TransitionUpgrade, FromImageID: "sha256:a", ToImageID: "sha256:b"})
+	history.Record(Transition{Kind: TransitionUpgrade, FromImageID: "sha256:b", ToImageID: "sha256:c"})
+
+	last := history.LastUpgrade()
+	assert.Equal(t, "sha256:c", last.ToImageID)
+	assert.False(t, history.IsRolledBack("sha256:c"))
+
+	// Rolling back walks back through the upgrades
+	last.RolledBack = true
+	history.Record(Transition{Kind: TransitionRollback, FromImageID: "sha256:c", ToImageID: "sha256:b"})
+	assert.True(t, history.IsRolledBack("sha256:c"))
+	assert.Equal(t, "sha256:b", history.LastUpgrade().ToImageID)
+
+	history.LastUpgrade().RolledBack = true
+	assert.Nil(t, history.LastUpgrade())
+	assert.True(t, history.IsRolledBack("sha256:b"))
+	assert.False(t, history.IsRolledBack("sha256:a"))
+}
@@ -0,0 +1,40 @@
+package upgrade
+
+// DefaultKeepImages is the default number of superseded images of a server kept for rollbacks
+const DefaultKeepImages = 1
+
+// RollbackImages returns the IDs of the images the given number of rollbacks would restore, most recent first
+func (h *History) RollbackImages(keep int) []string {
+	var kept []string
+	for i := len(h.Transitions) - 1; i >= 0 && len(kept) < keep; i-- {
+		transition := h.Transitions[i]
+		if transition.Kind == TransitionUpgrade && !transition.RolledBack && transition.FromImageID != "" {
+			kept = append(kept, transition.FromImageID)
+		}
+	}
+	return kept
+}
+
+// SupersededImages returns the IDs of the images recorded in the history which are neither in use
+// nor kept for the given number of rollbacks, most recent first. The caller should still check that
+// no other server references them before removing them.
+func (h *History) SupersededImages(keep int, inUse map[string]bool) []string {
+	retained := make(map[string]bool)
+	for _, imageID := range h.RollbackImages(keep) {
+		retained[imageID] = true
+	}
+
+	var superseded []string
+	seen := make(map[string]bool)
+	for i := len(h.Transitions) - 1; i >= 0; i-- {
+		transition := h.Transitions[i]
+		for _, imageID := range []string{transition.ToImageID, transition.FromImageID} {
+			if imageID == "" || seen[imageID] || retained[imageID] || inUse[imageID] {
+				continue
+			}
+			seen[imageID] = true
+			superseded = append(superseded, imageID)
+		}
+	}
+	return superseded
+}
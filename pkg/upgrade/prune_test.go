@@ -0,0 +1,65 @@
+package upgrade
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestHistory_SupersededImages(t *testing.T) {
+	t.Parallel()
+
+	// a -> b -> c -> d, then d was rolled back to c
+	history := &History{}
+	history.Record(Transition{Kind: TransitionUpgrade, FromImageID: "sha256:a", ToImageID: "sha256:b"})
+	history.Record(Transition{Kind: TransitionUpgrade, FromImageID: "sha256:b", ToImageID: "sha256:c"})
+	history.Record(Transition{Kind: TransitionUpgrade, FromImageID: "sha256:c", ToImageID: "sha256:d", RolledBack: true})
+	history.Record(Transition{Kind: TransitionRollback, FromImageID: "sha256:d", ToImageID: "sha256:c"})
+
+	tests := []struct {
+		name     string
+		keep     int
+		inUse    map[string]bool
+		expected []string
+	}{
+		{
+			name:     "Keep one image for rollback",
+			keep:     1,
+			inUse:    map[string]bool{"sha256:c": true},
+			expected: []string{"sha256:d", "sha256:a"},
+		},
+		{
+			name:     "Keep all images for rollback",
+			keep:     2,
+			inUse:    map[string]bool{"sha256:c": true},
+			expected: []string{"sha256:d"},
+		},
+		{
+			name:     "Keep no images",
+			keep:     0,
+			inUse:    map[string]bool{"sha256:c": true},
+			expected: []string{"sha256:d", "sha256:b", "sha256:a"},
+		},
+		{
+			name:     "Image used by another server",
+			keep:     0,
+			inUse:    map[string]bool{"sha256:c": true, "sha256:a": true},
+			expected: []string{"sha256:d", "sha256:b"},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, history.SupersededImages(tt.keep, tt.inUse))
+		})
+	}
+}
+
+func TestHistory_SupersededImages_Empty(t *testing.T) {
+	t.Parallel()
+
+	history := &History{}
+	assert.Empty(t, history.RollbackImages(DefaultKeepImages))
+	assert.Empty(t, history.SupersededImages(DefaultKeepImages, nil))
+}
@@ -0,0 +1,124 @@
+// Package upgrade contains logic for upgrading the images of MCP servers
+// and keeping track of the image transitions of each server, so upgrades can be rolled back.
+package upgrade
+
+import (
+	"fmt"
+	"strings"
+	"time"
+)
+
+// Window is a recurring maintenance window during which servers may be upgraded automatically,
+// e.g. "Sat,Sun 02:00-04:00", "Mon-Fri 22:00-01:00" or "03:00-04:00" for every day.
+// Windows are evaluated in local time. A window whose end is before its start wraps past midnight.
+type Window struct {
+	days  [7]bool
+	start int // minutes since midnight
+	end   int // minutes since midnight
+}
+
+var weekdays = map[string]time.Weekday{
+	"sun": time.Sunday,
+	"mon": time.Monday,
+	"tue": time.Tuesday,
+	"wed": time.Wednesday,
+	"thu": time.Thursday,
+	"fri": time.Friday,
+	"sat": time.Saturday,
+}
+
+// ParseWindow parses a maintenance window in the format "[DAYS ]HH:MM-HH:MM", where DAYS is a
+// comma-separated list of weekdays or weekday ranges (e.g. "Mon-Fri,Sun"). If DAYS is omitted
+// or is "daily", the window applies to every day.
+func ParseWindow(value string) (*Window, error) {
+	fields := strings.Fields(value)
+	var daysSpec, timeSpec string
+	switch len(fields) {
+	case 1:
+		timeSpec = fields[0]
+	case 2:
+		daysSpec, timeSpec = fields[0], fields[1]
+	default:
+		return nil, fmt.Errorf("invalid maintenance window %q: expected [DAYS ]HH:MM-HH:MM", value)
+	}
+
+	window := &Window{}
+	if err := window.parseDays(daysSpec); err != nil {
+		return nil, fmt.Errorf("invalid maintenance window %q: %w", value, err)
+	}
+
+	startSpec, endSpec, ok := strings.Cut(timeSpec, "-")
+	if !ok {
+		return nil, fmt.Errorf("invalid maintenance window %q: expected a time range HH:MM-HH:MM", value)
+	}
+	var err error
+	if window.start, err = parseTimeOfDay(startSpec); err != nil {
+		return nil, fmt.Errorf("invalid maintenance window %q: %w", value, err)
+	}
+	if window.end, err = parseTimeOfDay(endSpec); err != nil {
+		return nil, fmt.Errorf("invalid maintenance window %q: %w", value, err)
+	}
+	if window.start == window.end {
+		return nil, fmt.Errorf("invalid maintenance window %q: start and end must differ", value)
+	}
+
+	return window, nil
+}
+
+func (w *Window) parseDays(spec string) error {
+	if spec == "" || strings.EqualFold(spec, "daily") {
+		for i := range w.days {
+			w.days[i] = true
+		}
+		return nil
+	}
+
+	for _, part := range strings.Split(spec, ",") {
+		from, to, isRange := strings.Cut(part, "-")
+		first, err := parseWeekday(from)
+		if err != nil {
+			return err
+		}
+		last := first
+		if isRange {
+			if last, err = parseWeekday(to); err != nil {
+				return err
+			}
+		}
+		for day := first; ; day = (day + 1) % 7 {
+			w.days[day] = true
+			if day == last {
+				break
+			}
+		}
+	}
+	return nil
+}
+
+func parseWeekday(value string) (time.Weekday, error) {
+	day, ok := weekdays[strings.ToLower(value)]
+	if !ok {
+		return 0, fmt.Errorf("unknown weekday %q", value)
+	}
+	return day, nil
+}
+
+func parseTimeOfDay(value string) (int, error) {
+	t, err := time.Parse("15:04", value)
+	if err != nil {
+		return 0, fmt.Errorf("invalid time of day %q: expected HH:MM", value)
+	}
+	return t.Hour()*60 + t.Minute(), nil
+}
+
+// Contains returns true if the given time falls inside the window
+func (w *Window) Contains(t time.Time) bool {
+	minute := t.Hour()*60 + t.Minute()
+	if w.start < w.end {
+		return w.days[t.Weekday()] && minute >= w.start && minute < w.end
+	}
+
+	// The window wraps past midnight, so it belongs to the day it started on
+	yesterday := (t.Weekday() + 6) % 7
+	return (w.days[t.Weekday()] && minute >= w.start) || (w.days[yesterday] && minute < w.end)
+}
@@ -0,0 +1,71 @@
+package upgrade
+
+import (
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+// 2025-07-05 is a Saturday
+func at(day, hour, minute int) time.Time {
+	return time.Date(2025, time.July, day, hour, minute, 0, 0, time.Local)
+}
+
+func TestParseWindow_Invalid(t *testing.T) {
+	t.Parallel()
+
+	tests := []string{
+		"",
+		"02:00",
+		"25:00-26:00",
+		"Someday 02:00-04:00",
+		"Mon-Fri 02:00-02:00",
+		"Mon 02:00-04:00 extra",
+	}
+
+	for _, value := range tests {
+		t.Run(value, func(t *testing.T) {
+			t.Parallel()
+			_, err := ParseWindow(value)
+			assert.Error(t, err)
+		})
+	}
+}
+
+func TestWindow_Contains(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name     string
+		window   string
+		time     time.Time
+		expected bool
+	}{
+		{name: "daily inside", window: "03:00-04:00", time: at(5, 3, 30), expected: true},
+		{name: "daily at start", window: "03:00-04:00", time: at(5, 3, 0), expected: true},
+		{name: "daily at end", window: "03:00-04:00", time: at(5, 4, 0), expected: false},
+		{name: "daily keyword", window: "daily 03:00-04:00", time: at(7, 3, 15), expected: true},
+		{name: "weekend on saturday", window: "Sat,Sun 02:00-04:00", time: at(5, 2, 30), expected: true},
+		{name: "weekend on monday", window: "Sat,Sun 02:00-04:00", time: at(7, 2, 30), expected: false},
+		{name: "weekday range", window: "Mon-Fri 12:00-13:00", time: at(9, 12, 30), expected: true},
+		{name: "weekday range on saturday", window: "Mon-Fri 12:00-13:00", time: at(5, 12, 30), expected: false},
+		{name: "range wrapping the week", window: "Fri-Mon 12:00-13:00", time: at(6, 12, 30), expected: true},
+		{name: "case insensitive days", window: "sat 02:00-04:00", time: at(5, 3, 0), expected: true},
+		{name: "past midnight before", window: "Fri 22:00-01:00", time: at(4, 23, 0), expected: true},
+		{name: "past midnight after", window: "Fri 22:00-01:00", time: at(5, 0, 30), expected: true},
+		{name: "past midnight wrong day", window: "Fri 22:00-01:00", time: at(5, 23, 0), expected: false},
+		{name: "past midnight outside", window: "Fri 22:00-01:00", time: at(5, 1, 30), expected: false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			window, err := ParseWindow(tt.window)
+			require.NoError(t, err)
+			assert.Equal(t, tt.expected, window.Contains(tt.time))
+		})
+	}
+}
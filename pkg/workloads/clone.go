@@ -0,0 +1,64 @@
+package workloads
+
+import (
+	"context"
+	"errors"
+	"fmt"
+
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/networking"
+	"github.com/stacklok/toolhive/pkg/runner"
+)
+
+// CloneWorkload runs a copy of the specified workload under a new name in the background.
+// The copy has the saved configuration of the workload with the given overrides applied
+// (see runner.RunConfig.WithOverrides). It listens on the given port, or on an available port
+// if port is 0, since the port of the workload is usually in use.
+func (d *defaultManager) CloneWorkload(
+	ctx context.Context,
+	name, newName string,
+	port int,
+	overrides []string,
+) (*runner.RunConfig, error) {
+	for _, n := range []string{name, newName} {
+		if err := validateWorkloadName(n); err != nil {
+			return nil, fmt.Errorf("invalid workload name '%s': %w", n, err)
+		}
+	}
+	if err := d.checkNameAvailable(ctx, newName); err != nil {
+		return nil, err
+	}
+
+	// The saved state of a stopped workload can be cloned even if its container was removed
+	baseName := name
+	container, err := d.findContainerByName(ctx, name)
+	if err != nil && !errors.Is(err, ErrContainerNotFound) {
+		return nil, fmt.Errorf("failed to find workload %s: %v", name, err)
+	}
+	if container != nil {
+		baseName = labels.GetContainerBaseName(container.Labels)
+	}
+	mcpRunner, err := d.loadRunnerFromState(ctx, baseName)
+	if err != nil {
+		return nil, fmt.Errorf("failed to load state for %s: %v", baseName, err)
+	}
+	config := mcpRunner.Config
+
+	if config.Port, err = networking.FindOrUsePort(port); err != nil {
+		return nil, err
+	}
+	if port != 0 && config.Port != port {
+		return nil, fmt.Errorf("port %d is not available", port)
+	}
+	renameRunConfig(config, newName)
+	if _, err := config.WithOverrides(overrides); err != nil {
+		return nil, err
+	}
+
+	logger.Infof("Starting tooling server %s as a copy of %s...", newName, name)
+	if err := d.RunWorkloadDetached(config); err != nil {
+		return nil, err
+	}
+	return config, nil
+}
@@ -0,0 +1,143 @@
+package workloads
+
+import (
+	"context"
+	"time"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/environment"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/permissions"
+	"github.com/stacklok/toolhive/pkg/process"
+	"github.com/stacklok/toolhive/pkg/runner"
+	"github.com/stacklok/toolhive/pkg/transport/proxy"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+// Details describes the configuration and the state of a workload.
+type Details struct {
+	// Name is the name of the workload.
+	Name string `json:"name"`
+	// Image is the image reference the workload was run from.
+	Image string `json:"image"`
+	// ImageID is the digest of the image of the container of the workload.
+	ImageID string `json:"image_id,omitempty"`
+	// Args are the arguments passed to the MCP server, whose values holding secrets are masked.
+	Args []string `json:"args,omitempty"`
+	// Env are the environment variables of the MCP server, whose values holding secrets are masked.
+	Env map[string]string `json:"env,omitempty"`
+	// Secrets are the secrets passed to the MCP server, in the format NAME,target=TARGET.
+	Secrets []string `json:"secrets,omitempty"`
+	// TransportType is the type of transport used for this workload.
+	TransportType types.TransportType `json:"transport_type"`
+	// Host is the address the proxy of the workload listens on.
+	Host string `json:"host,omitempty"`
+	// Port is the port the proxy of the workload listens on.
+	Port int `json:"port"`
+	// ListenSocket is the path of the Unix domain socket the proxy is served on instead of its port.
+	ListenSocket string `json:"listen_socket,omitempty"`
+	// TargetPort is the port of the MCP server in the container the proxy forwards requests to.
+	TargetPort int `json:"target_port,omitempty"`
+	// PermissionProfileName is the name or the path of the permission profile of the workload.
+	PermissionProfileName string `json:"permission_profile_name,omitempty"`
+	// PermissionProfile is the permission profile of the workload.
+	PermissionProfile *permissions.Profile `json:"permission_profile,omitempty"`
+	// ContainerID is the ID of the container of the workload, if it exists.
+	ContainerID string `json:"container_id,omitempty"`
+	// State is the state of the container of the workload, e.g. running or exited.
+	State string `json:"state"`
+	// Status is the status of the container reported by the runtime, e.g. Up 2 hours.
+	Status string `json:"status,omitempty"`
+	// CreatedAt is the time the container of the workload was created.
+	CreatedAt time.Time `json:"created_at"`
+	// ProxyPID is the PID of the proxy process of the workload, or zero if it is not running in the background.
+	ProxyPID int `json:"proxy_pid,omitempty"`
+	// Labels are the user-defined labels of the workload.
+	Labels map[string]string `json:"labels,omitempty"`
+}
+
+// GetWorkloadDetails returns the configuration and the state of the named workload.
+func (d *defaultManager) GetWorkloadDetails(ctx context.Context, name string) (Details, error) {
+	if err := validateWorkloadName(name); err != nil {
+		return Details{}, err
+	}
+
+	container, err := d.findContainerByName(ctx, name)
+	if err != nil {
+		return Details{}, err
+	}
+	baseName := labels.GetContainerBaseName(container.Labels)
+
+	// The saved state holds the configuration which is not recorded in the labels
+	var config *runner.RunConfig
+	if mcpRunner, err := runner.LoadState(ctx, baseName); err != nil {
+		logger.Debugf("Could not load state for %s, showing the details of its container only: %v", name, err)
+	} else {
+		config = mcpRunner.Config
+	}
+
+	var proxyPID int
+	if proxy.IsRunning(baseName) {
+		proxyPID, _ = process.ReadPIDFile(baseName)
+	}
+	return detailsFromContainer(container, config, proxyPID), nil
+}
+
+// detailsFromContainer builds the details of a workload from its container, its run configuration,
+// which may be nil if it was not saved, and the PID of its proxy process
+func detailsFromContainer(container *rt.ContainerInfo, config *runner.RunConfig, proxyPID int) Details {
+	name := labels.GetContainerName(container.Labels)
+	if name == "" {
+		name = container.Name
+	}
+	port, err := labels.GetPort(container.Labels)
+	if err != nil {
+		port = 0
+	}
+
+	details := Details{
+		Name:                  name,
+		Image:                 container.Image,
+		ImageID:               container.ImageID,
+		TransportType:         types.TransportType(labels.GetTransportType(container.Labels)),
+		Port:                  port,
+		ListenSocket:          labels.GetListenSocket(container.Labels),
+		PermissionProfileName: labels.GetPermissionProfile(container.Labels),
+		ContainerID:           container.ID,
+		State:                 container.State,
+		Status:                container.Status,
+		CreatedAt:             container.Created,
+		ProxyPID:              proxyPID,
+		Labels:                labels.GetUserLabels(container.Labels),
+	}
+	if config == nil {
+		return details
+	}
+
+	// The image of the configuration is the reference the workload was run from, rather than the resolved image
+	if config.Image != "" {
+		details.Image = config.Image
+	}
+	details.Args = environment.MaskArgs(config.CmdArgs)
+	details.Secrets = config.Secrets
+	details.Host = config.Host
+	if config.ListenSocket != "" {
+		details.ListenSocket = config.ListenSocket
+	}
+	if config.Transport != types.TransportTypeStdio {
+		details.TargetPort = config.TargetPort
+	}
+	if config.PermissionProfileNameOrPath != "" {
+		details.PermissionProfileName = config.PermissionProfileNameOrPath
+	}
+	details.PermissionProfile = config.PermissionProfile
+	if len(config.EnvVars) > 0 {
+		masker := environment.NewMasker(config.Secrets)
+		details.Env = make(map[string]string, len(config.EnvVars))
+		for key, value := range config.EnvVars {
+			details.Env[key] = masker.Mask(key, value)
+		}
+	}
+	return details
+}
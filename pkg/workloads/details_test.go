@@ -0,0 +1,123 @@
+package workloads
+
+import (
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/environment"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/permissions"
+	"github.com/stacklok/toolhive/pkg/runner"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+func TestDetailsFromContainer(t *testing.T) {
+	t.Parallel()
+
+	created := time.Date(2025, 7, 1, 8, 0, 0, 0, time.UTC)
+	container := &rt.ContainerInfo{
+		ID:      "abc123",
+		Name:    "github",
+		Image:   "ghcr.io/github/github-mcp-server:latest",
+		ImageID: "sha256:0123456789abcdef",
+		State:   "running",
+		Status:  "Up 2 hours",
+		Created: created,
+		Labels: map[string]string{
+			labels.LabelName:      "github",
+			labels.LabelTransport: "sse",
+			labels.LabelPort:      "9090",
+			"team":                "platform",
+		},
+	}
+	profile := permissions.BuiltinNetworkProfile()
+
+	tests := []struct {
+		name     string
+		config   *runner.RunConfig
+		proxyPID int
+		expected Details
+	}{
+		{
+			name: "saved state",
+			config: &runner.RunConfig{
+				Image:                       "ghcr.io/github/github-mcp-server:v0.5.0",
+				CmdArgs:                     []string{"stdio", "--read-only"},
+				EnvVars:                     map[string]string{"GITHUB_TOKEN": "ghp_secret", "LOG_LEVEL": "debug"},
+				Secrets:                     []string{"github,target=GITHUB_TOKEN"},
+				Transport:                   types.TransportTypeSSE,
+				Host:                        "127.0.0.1",
+				TargetPort:                  8000,
+				PermissionProfileNameOrPath: "network",
+				PermissionProfile:           profile,
+			},
+			proxyPID: 4242,
+			expected: Details{
+				Name:                  "github",
+				Image:                 "ghcr.io/github/github-mcp-server:v0.5.0",
+				ImageID:               "sha256:0123456789abcdef",
+				Args:                  []string{"stdio", "--read-only"},
+				Env:                   map[string]string{"GITHUB_TOKEN": environment.MaskedValue, "LOG_LEVEL": "debug"},
+				Secrets:               []string{"github,target=GITHUB_TOKEN"},
+				TransportType:         types.TransportTypeSSE,
+				Host:                  "127.0.0.1",
+				Port:                  9090,
+				TargetPort:            8000,
+				PermissionProfileName: "network",
+				PermissionProfile:     profile,
+				ContainerID:           "abc123",
+				State:                 "running",
+				Status:                "Up 2 hours",
+				CreatedAt:             created,
+				ProxyPID:              4242,
+				Labels:                map[string]string{"team": "platform"},
+			},
+		},
+		{
+			name: "served on a socket",
+			config: &runner.RunConfig{
+				Image:        "ghcr.io/github/github-mcp-server:latest",
+				Transport:    types.TransportTypeSSE,
+				ListenSocket: "/run/user/1000/github.sock",
+			},
+			expected: Details{
+				Name:          "github",
+				Image:         "ghcr.io/github/github-mcp-server:latest",
+				ImageID:       "sha256:0123456789abcdef",
+				TransportType: types.TransportTypeSSE,
+				Port:          9090,
+				ListenSocket:  "/run/user/1000/github.sock",
+				ContainerID:   "abc123",
+				State:         "running",
+				Status:        "Up 2 hours",
+				CreatedAt:     created,
+				Labels:        map[string]string{"team": "platform"},
+			},
+		},
+		{
+			name: "no saved state",
+			expected: Details{
+				Name:          "github",
+				Image:         "ghcr.io/github/github-mcp-server:latest",
+				ImageID:       "sha256:0123456789abcdef",
+				TransportType: types.TransportTypeSSE,
+				Port:          9090,
+				ContainerID:   "abc123",
+				State:         "running",
+				Status:        "Up 2 hours",
+				CreatedAt:     created,
+				Labels:        map[string]string{"team": "platform"},
+			},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, detailsFromContainer(container, tt.config, tt.proxyPID))
+		})
+	}
+}
@@ -0,0 +1,131 @@
+package workloads
+
+import (
+	"context"
+	"fmt"
+	"net"
+	"strconv"
+
+	"github.com/stacklok/toolhive/pkg/client"
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/runner"
+	"github.com/stacklok/toolhive/pkg/transport"
+	"github.com/stacklok/toolhive/pkg/transport/ssecommon"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+// PortMapping is a port of the container of a workload which is published on the host.
+type PortMapping struct {
+	// ContainerPort is the port inside the container
+	ContainerPort int `json:"container_port"`
+	// HostPort is the port on the host
+	HostPort int `json:"host_port"`
+	// Protocol is the protocol (tcp, udp)
+	Protocol string `json:"protocol"`
+}
+
+// Endpoints describes the addresses a workload listens on and the URLs clients use to reach it.
+type Endpoints struct {
+	// Name is the name of the workload.
+	Name string `json:"name"`
+	// TransportType is the type of transport used for this workload.
+	TransportType types.TransportType `json:"transport_type"`
+	// ProxyAddress is the address the proxy of the workload listens on.
+	ProxyAddress string `json:"proxy_address"`
+	// URL is the URL clients connect to, as written to the client configurations.
+	URL string `json:"url"`
+	// MessagesURL is the URL of the JSON-RPC endpoint of the proxy of a stdio workload.
+	// Clients learn it from the SSE endpoint, so it does not need to be configured.
+	MessagesURL string `json:"messages_url,omitempty"`
+	// HealthURL is the URL of the health check endpoint of the proxy.
+	HealthURL string `json:"health_url"`
+	// MetricsURL is the URL of the Prometheus metrics endpoint of the proxy, if it is enabled.
+	MetricsURL string `json:"metrics_url,omitempty"`
+	// TargetPort is the port of the MCP server in the container the proxy forwards requests to.
+	TargetPort int `json:"target_port,omitempty"`
+	// ContainerPorts are the ports of the container which are published on the host.
+	ContainerPorts []PortMapping `json:"container_ports"`
+}
+
+// GetWorkloadEndpoints returns the addresses and URLs of the named workload.
+func (d *defaultManager) GetWorkloadEndpoints(ctx context.Context, name string) (Endpoints, error) {
+	if err := validateWorkloadName(name); err != nil {
+		return Endpoints{}, err
+	}
+
+	container, err := d.findContainerByName(ctx, name)
+	if err != nil {
+		return Endpoints{}, err
+	}
+
+	// The saved state holds the options of the proxy which are not recorded in the labels
+	var config *runner.RunConfig
+	if mcpRunner, err := runner.LoadState(ctx, labels.GetContainerBaseName(container.Labels)); err != nil {
+		logger.Debugf("Could not load state for %s, showing defaults for the proxy: %v", name, err)
+	} else {
+		config = mcpRunner.Config
+	}
+
+	return endpointsFromContainer(container, config), nil
+}
+
+// endpointsFromContainer builds the endpoints of a workload from its container and its run configuration,
+// which may be nil if it was not saved
+func endpointsFromContainer(container *rt.ContainerInfo, config *runner.RunConfig) Endpoints {
+	name := labels.GetContainerName(container.Labels)
+	if name == "" {
+		name = container.Name
+	}
+	transportType := labels.GetTransportType(container.Labels)
+	port, err := labels.GetPort(container.Labels)
+	if err != nil {
+		port = 0
+	}
+
+	endpoints := Endpoints{
+		Name:           name,
+		TransportType:  types.TransportType(transportType),
+		ContainerPorts: make([]PortMapping, 0, len(container.Ports)),
+	}
+	for _, p := range container.Ports {
+		endpoints.ContainerPorts = append(endpoints.ContainerPorts, PortMapping{
+			ContainerPort: p.ContainerPort,
+			HostPort:      p.HostPort,
+			Protocol:      p.Protocol,
+		})
+	}
+	if port == 0 {
+		return endpoints
+	}
+
+	host := transport.LocalhostIPv4
+	scheme := "http"
+	if labels.IsTLS(container.Labels) {
+		scheme = "https"
+	}
+	messagesPath := ssecommon.HTTPMessagesEndpoint
+	if config != nil {
+		if config.Host != "" {
+			host = config.Host
+		}
+		if config.MessagesPath != "" {
+			messagesPath = config.MessagesPath
+		}
+		if config.Transport != types.TransportTypeStdio {
+			endpoints.TargetPort = config.TargetPort
+		}
+		if config.TelemetryConfig != nil && config.TelemetryConfig.EnablePrometheusMetricsPath {
+			endpoints.MetricsURL = fmt.Sprintf("%s://%s:%d/metrics", scheme, transport.LocalhostIPv4, port)
+		}
+	}
+
+	endpoints.ProxyAddress = net.JoinHostPort(host, strconv.Itoa(port))
+	endpoints.URL = client.GenerateMCPServerURLForLabels(transportType, transport.LocalhostIPv4, port, name, container.Labels)
+	endpoints.HealthURL = fmt.Sprintf("%s://%s:%d/health", scheme, transport.LocalhostIPv4, port)
+	if endpoints.TransportType == types.TransportTypeStdio {
+		endpoints.MessagesURL = fmt.Sprintf("%s://%s:%d%s", scheme, transport.LocalhostIPv4, port, messagesPath)
+	}
+	return endpoints
+}
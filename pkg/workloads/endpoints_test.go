@@ -0,0 +1,106 @@
+package workloads
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/runner"
+	"github.com/stacklok/toolhive/pkg/telemetry"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+func TestEndpointsFromContainer(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name      string
+		container *rt.ContainerInfo
+		config    *runner.RunConfig
+		expected  Endpoints
+	}{
+		{
+			name: "stdio server with custom paths",
+			container: &rt.ContainerInfo{
+				Name: "fetch",
+				Labels: map[string]string{
+					labels.LabelName:      "fetch",
+					labels.LabelTransport: "stdio",
+					labels.LabelPort:      "8080",
+					labels.LabelSSEPath:   "/events",
+				},
+			},
+			config: &runner.RunConfig{
+				Host:         "0.0.0.0",
+				Transport:    types.TransportTypeStdio,
+				MessagesPath: "/rpc",
+			},
+			expected: Endpoints{
+				Name:           "fetch",
+				TransportType:  types.TransportTypeStdio,
+				ProxyAddress:   "0.0.0.0:8080",
+				URL:            "http://127.0.0.1:8080/events#fetch",
+				MessagesURL:    "http://127.0.0.1:8080/rpc",
+				HealthURL:      "http://127.0.0.1:8080/health",
+				ContainerPorts: []PortMapping{},
+			},
+		},
+		{
+			name: "SSE server with published port and metrics",
+			container: &rt.ContainerInfo{
+				Name: "osv",
+				Labels: map[string]string{
+					labels.LabelName:      "osv",
+					labels.LabelTransport: "sse",
+					labels.LabelPort:      "9090",
+				},
+				Ports: []rt.PortMapping{{ContainerPort: 8000, HostPort: 45123, Protocol: "tcp"}},
+			},
+			config: &runner.RunConfig{
+				Host:            "127.0.0.1",
+				Transport:       types.TransportTypeSSE,
+				TargetPort:      8000,
+				TelemetryConfig: &telemetry.Config{EnablePrometheusMetricsPath: true},
+			},
+			expected: Endpoints{
+				Name:           "osv",
+				TransportType:  types.TransportTypeSSE,
+				ProxyAddress:   "127.0.0.1:9090",
+				URL:            "http://127.0.0.1:9090/sse#osv",
+				HealthURL:      "http://127.0.0.1:9090/health",
+				MetricsURL:     "http://127.0.0.1:9090/metrics",
+				TargetPort:     8000,
+				ContainerPorts: []PortMapping{{ContainerPort: 8000, HostPort: 45123, Protocol: "tcp"}},
+			},
+		},
+		{
+			name: "no saved state",
+			container: &rt.ContainerInfo{
+				Name: "notes",
+				Labels: map[string]string{
+					labels.LabelName:      "notes",
+					labels.LabelTransport: "stdio",
+					labels.LabelPort:      "8081",
+				},
+			},
+			expected: Endpoints{
+				Name:           "notes",
+				TransportType:  types.TransportTypeStdio,
+				ProxyAddress:   "127.0.0.1:8081",
+				URL:            "http://127.0.0.1:8081/sse#notes",
+				MessagesURL:    "http://127.0.0.1:8081/messages",
+				HealthURL:      "http://127.0.0.1:8081/health",
+				ContainerPorts: []PortMapping{},
+			},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, endpointsFromContainer(tt.container, tt.config))
+		})
+	}
+}
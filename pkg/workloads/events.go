@@ -0,0 +1,70 @@
+package workloads
+
+import (
+	"context"
+	"strconv"
+	"time"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/labels"
+)
+
+// Event is a lifecycle or health transition of a workload.
+type Event struct {
+	// Time is when the transition happened.
+	Time time.Time `json:"time"`
+	// Name is the name of the workload.
+	Name string `json:"name"`
+	// Action is the transition, e.g. start, die, pause or health_status: unhealthy.
+	Action string `json:"action"`
+	// ExitCode is the exit code of the workload, if it exited.
+	ExitCode *int `json:"exit_code,omitempty"`
+}
+
+// GetEvents returns the events of the named workload, or of all workloads if name is empty.
+// The event channel is closed once all events were returned, unless options.Follow is set.
+func (d *defaultManager) GetEvents(ctx context.Context, name string, options *rt.EventOptions) (<-chan Event, <-chan error) {
+	if name != "" {
+		if err := validateWorkloadName(name); err != nil {
+			workloadEvents := make(chan Event)
+			close(workloadEvents)
+			errs := make(chan error, 1)
+			errs <- err
+			return workloadEvents, errs
+		}
+	}
+
+	runtimeEvents, errs := d.runtime.WorkloadEvents(ctx, options)
+	workloadEvents := make(chan Event)
+	go func() {
+		defer close(workloadEvents)
+		for runtimeEvent := range runtimeEvents {
+			event := eventFromWorkloadEvent(runtimeEvent)
+			if name != "" && event.Name != name {
+				continue
+			}
+			select {
+			case workloadEvents <- event:
+			case <-ctx.Done():
+				return
+			}
+		}
+	}()
+	return workloadEvents, errs
+}
+
+// eventFromWorkloadEvent converts an event reported by the runtime into a workload event
+func eventFromWorkloadEvent(runtimeEvent rt.WorkloadEvent) Event {
+	event := Event{
+		Time:   runtimeEvent.Time,
+		Name:   labels.GetContainerName(runtimeEvent.Attributes),
+		Action: runtimeEvent.Action,
+	}
+	if event.Name == "" {
+		event.Name = runtimeEvent.Attributes["name"]
+	}
+	if exitCode, err := strconv.Atoi(runtimeEvent.Attributes["exitCode"]); err == nil {
+		event.ExitCode = &exitCode
+	}
+	return event
+}
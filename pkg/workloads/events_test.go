@@ -0,0 +1,59 @@
+package workloads
+
+import (
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/labels"
+)
+
+func TestEventFromWorkloadEvent(t *testing.T) {
+	t.Parallel()
+
+	eventTime := time.Date(2025, 7, 1, 12, 0, 0, 0, time.UTC)
+	exitCode := 137
+
+	tests := []struct {
+		name     string
+		event    rt.WorkloadEvent
+		expected Event
+	}{
+		{
+			name: "exit with code",
+			event: rt.WorkloadEvent{
+				Time:       eventTime,
+				Action:     "die",
+				Attributes: map[string]string{labels.LabelName: "fetch", "name": "fetch", "exitCode": "137"},
+			},
+			expected: Event{Time: eventTime, Name: "fetch", Action: "die", ExitCode: &exitCode},
+		},
+		{
+			name: "health transition",
+			event: rt.WorkloadEvent{
+				Time:       eventTime,
+				Action:     "health_status: unhealthy",
+				Attributes: map[string]string{labels.LabelName: "fetch"},
+			},
+			expected: Event{Time: eventTime, Name: "fetch", Action: "health_status: unhealthy"},
+		},
+		{
+			name: "container name fallback",
+			event: rt.WorkloadEvent{
+				Time:       eventTime,
+				Action:     "start",
+				Attributes: map[string]string{"name": "legacy"},
+			},
+			expected: Event{Time: eventTime, Name: "legacy", Action: "start"},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, eventFromWorkloadEvent(tt.event))
+		})
+	}
+}
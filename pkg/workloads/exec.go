@@ -0,0 +1,36 @@
+package workloads
+
+import (
+	"context"
+	"fmt"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+)
+
+// ExecWorkload runs a command in the container of the specified running workload and returns its exit code.
+func (d *defaultManager) ExecWorkload(
+	ctx context.Context,
+	name string,
+	command []string,
+	options *rt.ExecOptions,
+) (int, error) {
+	if err := validateWorkloadName(name); err != nil {
+		return 0, fmt.Errorf("invalid workload name '%s': %w", name, err)
+	}
+	if len(command) == 0 {
+		return 0, fmt.Errorf("no command specified")
+	}
+
+	container, err := d.findContainerByName(ctx, name)
+	if err != nil {
+		return 0, err
+	}
+	if container.State == containerStatePaused {
+		return 0, fmt.Errorf("container %s is paused, resume it with thv resume first", name)
+	}
+	if !isContainerRunning(container) {
+		return 0, fmt.Errorf("%w: %s", ErrContainerNotRunning, name)
+	}
+
+	return d.runtime.ExecInWorkload(ctx, container.ID, command, options)
+}
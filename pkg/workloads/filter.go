@@ -0,0 +1,139 @@
+package workloads
+
+import (
+	"fmt"
+	"path"
+	"strings"
+
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+// Filter selects workloads by their attributes.
+// Values given for the same attribute are combined with OR, different attributes with AND.
+type Filter struct {
+	statuses   []WorkloadStatus
+	transports []types.TransportType
+	names      []string
+	labels     []string
+}
+
+// ParseFilters parses filters in key=value format into a Filter.
+// Supported keys are status, transport, name (a glob pattern) and label (key or key=value).
+func ParseFilters(filters []string) (*Filter, error) {
+	f := &Filter{}
+	for _, filter := range filters {
+		key, value, ok := strings.Cut(filter, "=")
+		if !ok || value == "" {
+			return nil, fmt.Errorf("invalid filter format: %s (expected key=value)", filter)
+		}
+
+		switch strings.ToLower(key) {
+		case "status":
+			status, err := parseWorkloadStatus(value)
+			if err != nil {
+				return nil, err
+			}
+			f.statuses = append(f.statuses, status)
+		case "transport":
+			transportType, err := types.ParseTransportType(value)
+			if err != nil {
+				return nil, fmt.Errorf("invalid transport filter %s: %w", value, err)
+			}
+			f.transports = append(f.transports, transportType)
+		case "name":
+			if _, err := path.Match(value, ""); err != nil {
+				return nil, fmt.Errorf("invalid name pattern %s: %w", value, err)
+			}
+			f.names = append(f.names, value)
+		case "label":
+			f.labels = append(f.labels, value)
+		default:
+			return nil, fmt.Errorf("unsupported filter: %s (supported filters are status, transport, name and label)", key)
+		}
+	}
+	return f, nil
+}
+
+// SelectsStatus returns true if the filter selects workloads by status
+func (f *Filter) SelectsStatus() bool {
+	return len(f.statuses) > 0
+}
+
+// Matches returns true if the workload matches the filter
+func (f *Filter) Matches(workload Workload) bool {
+	return f.matchesStatus(workload) &&
+		f.matchesTransport(workload) &&
+		f.matchesName(workload) &&
+		f.matchesLabels(workload)
+}
+
+// Apply returns the workloads which match the filter
+func (f *Filter) Apply(workloads []Workload) []Workload {
+	var matching []Workload
+	for _, workload := range workloads {
+		if f.Matches(workload) {
+			matching = append(matching, workload)
+		}
+	}
+	return matching
+}
+
+func (f *Filter) matchesStatus(workload Workload) bool {
+	if len(f.statuses) == 0 {
+		return true
+	}
+	for _, status := range f.statuses {
+		if workload.Status == status {
+			return true
+		}
+	}
+	return false
+}
+
+func (f *Filter) matchesTransport(workload Workload) bool {
+	if len(f.transports) == 0 {
+		return true
+	}
+	for _, transportType := range f.transports {
+		if workload.TransportType == transportType {
+			return true
+		}
+	}
+	return false
+}
+
+func (f *Filter) matchesName(workload Workload) bool {
+	if len(f.names) == 0 {
+		return true
+	}
+	for _, pattern := range f.names {
+		if matched, _ := path.Match(pattern, workload.Name); matched {
+			return true
+		}
+	}
+	return false
+}
+
+func (f *Filter) matchesLabels(workload Workload) bool {
+	if len(f.labels) == 0 {
+		return true
+	}
+	for _, label := range f.labels {
+		key, value, hasValue := strings.Cut(label, "=")
+		actual, ok := workload.Labels[key]
+		if ok && (!hasValue || actual == value) {
+			return true
+		}
+	}
+	return false
+}
+
+func parseWorkloadStatus(value string) (WorkloadStatus, error) {
+	status := WorkloadStatus(strings.ToLower(value))
+	switch status {
+	case WorkloadStatusRunning, WorkloadStatusStopped, WorkloadStatusError, WorkloadStatusStarting, WorkloadStatusUnknown:
+		return status, nil
+	default:
+		return "", fmt.Errorf("invalid status filter: %s", value)
+	}
+}
@@ -0,0 +1,97 @@
+package workloads
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/transport/types"
+)
+
+func TestParseFilters_Invalid(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name   string
+		filter string
+	}{
+		{name: "missing value", filter: "status"},
+		{name: "empty value", filter: "name="},
+		{name: "unknown key", filter: "image=fetch"},
+		{name: "unknown status", filter: "status=sleeping"},
+		{name: "unknown transport", filter: "transport=carrier-pigeon"},
+		{name: "invalid pattern", filter: "name=[fetch"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			_, err := ParseFilters([]string{tt.filter})
+			assert.Error(t, err)
+		})
+	}
+}
+
+func TestFilter_Apply(t *testing.T) {
+	t.Parallel()
+
+	workloads := []Workload{
+		{
+			Name:          "github",
+			Status:        WorkloadStatusRunning,
+			TransportType: types.TransportTypeSSE,
+			Labels:        map[string]string{"team": "platform"},
+		},
+		{
+			Name:          "github-enterprise",
+			Status:        WorkloadStatusStopped,
+			TransportType: types.TransportTypeStdio,
+			Labels:        map[string]string{"team": "security"},
+		},
+		{
+			Name:          "fetch",
+			Status:        WorkloadStatusRunning,
+			TransportType: types.TransportTypeStdio,
+		},
+	}
+
+	tests := []struct {
+		name     string
+		filters  []string
+		expected []string
+	}{
+		{name: "no filters", filters: nil, expected: []string{"github", "github-enterprise", "fetch"}},
+		{name: "status", filters: []string{"status=stopped"}, expected: []string{"github-enterprise"}},
+		{name: "transport", filters: []string{"transport=stdio"}, expected: []string{"github-enterprise", "fetch"}},
+		{name: "name glob", filters: []string{"name=github*"}, expected: []string{"github", "github-enterprise"}},
+		{name: "label key", filters: []string{"label=team"}, expected: []string{"github", "github-enterprise"}},
+		{name: "label key and value", filters: []string{"label=team=platform"}, expected: []string{"github"}},
+		{
+			name:     "same key is combined with or",
+			filters:  []string{"name=fetch", "name=github"},
+			expected: []string{"github", "fetch"},
+		},
+		{
+			name:     "different keys are combined with and",
+			filters:  []string{"transport=stdio", "name=github*"},
+			expected: []string{"github-enterprise"},
+		},
+		{name: "no match", filters: []string{"label=team=none"}, expected: nil},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			filter, err := ParseFilters(tt.filters)
+			require.NoError(t, err)
+
+			var names []string
+			for _, workload := range filter.Apply(workloads) {
+				names = append(names, workload.Name)
+			}
+			assert.Equal(t, tt.expected, names)
+		})
+	}
+}
@@ -14,9 +14,9 @@ import (
 	"strings"
 	"time"
 
-	"github.com/adrg/xdg"
 	"golang.org/x/sync/errgroup"
 
+	"github.com/stacklok/toolhive/pkg/auth"
 	"github.com/stacklok/toolhive/pkg/client"
 	"github.com/stacklok/toolhive/pkg/config"
 	ct "github.com/stacklok/toolhive/pkg/container"
@@ -27,20 +27,29 @@ import (
 	"github.com/stacklok/toolhive/pkg/runner"
 	"github.com/stacklok/toolhive/pkg/secrets"
 	"github.com/stacklok/toolhive/pkg/transport/proxy"
+	"github.com/stacklok/toolhive/pkg/transport/session"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+	"github.com/stacklok/toolhive/pkg/upgrade"
 )
 
 // Manager is responsible for managing the state of ToolHive-managed containers.
 type Manager interface {
 	// GetWorkload returns information about the named container.
 	GetWorkload(ctx context.Context, name string) (Workload, error)
+	// GetWorkloadEndpoints returns the addresses and URLs of the named workload.
+	GetWorkloadEndpoints(ctx context.Context, name string) (Endpoints, error)
+	// GetWorkloadDetails returns the configuration and the state of the named workload,
+	// with the values of its environment variables and arguments holding secrets masked.
+	GetWorkloadDetails(ctx context.Context, name string) (Details, error)
 	// ListWorkloads lists all ToolHive-managed containers.
 	ListWorkloads(ctx context.Context, listAll bool) ([]Workload, error)
 	// DeleteWorkloads deletes the specified workloads by name.
 	// It is implemented as an asynchronous operation which returns an errgroup.Group
 	DeleteWorkloads(ctx context.Context, names []string) (*errgroup.Group, error)
 	// StopWorkloads stops the specified workloads by name.
+	// If options is nil, the stop timeout and signal of each workload are used.
 	// It is implemented as an asynchronous operation which returns an errgroup.Group
-	StopWorkloads(ctx context.Context, names []string) (*errgroup.Group, error)
+	StopWorkloads(ctx context.Context, names []string, options *rt.StopOptions) (*errgroup.Group, error)
 	// RunWorkload runs a container in the foreground.
 	RunWorkload(ctx context.Context, runConfig *runner.RunConfig) error
 	// RunWorkloadDetached runs a container in the background.
@@ -48,8 +57,26 @@ type Manager interface {
 	// RestartWorkloads restarts the specified workloads by name.
 	// It is implemented as an asynchronous operation which returns an errgroup.Group
 	RestartWorkloads(ctx context.Context, names []string) (*errgroup.Group, error)
+	// RestartWorkloadForeground restarts the specified workload in the foreground,
+	// blocking until it is stopped. This is intended for processes supervised by a service manager.
+	RestartWorkloadForeground(ctx context.Context, name string) error
+	// RenameWorkload renames the specified workload, restarting it under the new name if it is running.
+	RenameWorkload(ctx context.Context, oldName, newName string) error
+	// CloneWorkload runs a copy of the specified workload under a new name in the background,
+	// with the given overrides applied to its configuration. It returns the configuration of the copy.
+	CloneWorkload(ctx context.Context, name, newName string, port int, overrides []string) (*runner.RunConfig, error)
+	// PauseWorkload freezes the container of the specified workload. Its proxy rejects requests until it is resumed.
+	PauseWorkload(ctx context.Context, name string) error
+	// ResumeWorkload unfreezes the container of the specified paused workload.
+	ResumeWorkload(ctx context.Context, name string) error
+	// ExecWorkload runs a command in the container of the specified running workload and returns its exit code.
+	ExecWorkload(ctx context.Context, name string, command []string, options *rt.ExecOptions) (int, error)
 	// GetLogs retrieves the logs of a container.
-	GetLogs(ctx context.Context, containerName string, follow bool) (string, error)
+	GetLogs(ctx context.Context, containerName string, options rt.LogOptions) (string, error)
+	// GetProxyLogs retrieves the logs of the proxy of a container running in the background.
+	GetProxyLogs(ctx context.Context, containerName string, options rt.LogOptions) (string, error)
+	// GetEvents returns the lifecycle and health events of the named workload, or of all workloads if name is empty.
+	GetEvents(ctx context.Context, name string, options *rt.EventOptions) (<-chan Event, <-chan error)
 }
 
 type defaultManager struct {
@@ -62,6 +89,7 @@ var (
 	ErrContainerNotFound   = fmt.Errorf("container not found")
 	ErrContainerNotRunning = fmt.Errorf("container not running")
 	ErrInvalidWorkloadName = fmt.Errorf("invalid workload name")
+	ErrContainerNotPaused  = fmt.Errorf("container not paused")
 )
 
 const (
@@ -131,7 +159,11 @@ func (d *defaultManager) ListWorkloads(ctx context.Context, listAll bool) ([]Wor
 	return workloads, nil
 }
 
-func (d *defaultManager) StopWorkloads(ctx context.Context, names []string) (*errgroup.Group, error) {
+func (d *defaultManager) StopWorkloads(
+	ctx context.Context,
+	names []string,
+	options *rt.StopOptions,
+) (*errgroup.Group, error) {
 	// Validate all workload names to prevent path traversal attacks
 	for _, name := range names {
 		if err := validateWorkloadName(name); err != nil {
@@ -162,10 +194,836 @@ func (d *defaultManager) StopWorkloads(ctx context.Context, names []string) (*er
 		containers = append(containers, container)
 	}
 
-	return d.stopWorkloads(ctx, containers), nil
+	return d.stopWorkloads(ctx, containers, options), nil
 }
 
 func (*defaultManager) RunWorkload(ctx context.Context, runConfig *runner.RunConfig) error {
+	// Forward the logs of the server to its sink, tagged with its name. This is done here rather than by
+	// the run command, so the sink is also set up when the server is restarted from its saved state.
+	if err := logger.EnableSink(logger.SinkType(runConfig.LogSink), "thv-"+runConfig.BaseName); err != nil {
+		return err
+	}
+	mcpRunner := runner.NewRunner(runConfig)
+	return mcpRunner.Run(ctx)
+}
+
+//nolint:gocyclo // This function is complex but manageable
+func (*defaultManager) RunWorkloadDetached(runConfig *runner.RunConfig) error {
+	// Get the current executable path
+	execPath, err := os.Executable()
+	if err != nil {
+		return fmt.Errorf("failed to get executable path: %v", err)
+	}
+
+	// Create a log file for the detached process
+	logFilePath, err := ProxyLogFilePath(runConfig.BaseName)
+	if err != nil {
+		return fmt.Errorf("failed to create log file path: %v", err)
+	}
+	// #nosec G304 - This is safe as baseName is generated by the application
+	logFile, err := os.OpenFile(logFilePath, os.O_CREATE|os.O_WRONLY|os.O_APPEND, 0600)
+	if err != nil {
+		logger.Warnf("Warning: Failed to create log file: %v", err)
+	} else {
+		defer logFile.Close()
+		logger.Infof("Logging to: %s", logFilePath)
+	}
+
+	// Prepare the command arguments for the detached process
+	// We'll run the same command but with the --foreground flag
+	// The image was already retrieved, so the detached process only pulls it if it was removed since
+	detachedArgs := []string{"run", "--foreground", "--pull", "missing"}
+
+	// Add all the original flags
+	if runConfig.Transport != "stdio" {
+		detachedArgs = append(detachedArgs, "--transport", string(runConfig.Transport))
+	}
+
+	if runConfig.Debug {
+		detachedArgs = append(detachedArgs, "--debug")
+	}
+
+	if runConfig.IsolateNetwork {
+		detachedArgs = append(detachedArgs, "--isolate-network")
+	}
+
+	detachedArgs = append(detachedArgs, logSinkArgs(runConfig)...)
+
+	if runConfig.LogDriver != "" {
+		detachedArgs = append(detachedArgs, "--log-driver", runConfig.LogDriver)
+	}
+
+	for key, value := range runConfig.LogOptions {
+		detachedArgs = append(detachedArgs, "--log-opt", fmt.Sprintf("%s=%s", key, value))
+	}
+
+	detachedArgs = append(detachedArgs, upgradeArgs(runConfig)...)
+
+	if runConfig.StopTimeout > 0 {
+		detachedArgs = append(detachedArgs, "--stop-timeout", strconv.Itoa(runConfig.StopTimeout))
+	}
+
+	if runConfig.StopSignal != "" {
+		detachedArgs = append(detachedArgs, "--stop-signal", runConfig.StopSignal)
+	}
+	detachedArgs = append(detachedArgs, resourceLimitArgs(runConfig)...)
+	if runConfig.RestartPolicy != "" {
+		detachedArgs = append(detachedArgs, "--restart", runConfig.RestartPolicy)
+	}
+	if runConfig.RestartUnhealthy {
+		detachedArgs = append(detachedArgs, "--restart-unhealthy")
+	}
+	for _, name := range runConfig.Networks {
+		detachedArgs = append(detachedArgs, "--network", name)
+	}
+
+	if runConfig.SSEPath != "" {
+		detachedArgs = append(detachedArgs, "--sse-path", runConfig.SSEPath)
+	}
+
+	if runConfig.MessagesPath != "" {
+		detachedArgs = append(detachedArgs, "--messages-path", runConfig.MessagesPath)
+	}
+
+	if runConfig.NamedPipe != "" {
+		detachedArgs = append(detachedArgs, "--named-pipe")
+	}
+
+	if runConfig.ListenSocket != "" {
+		detachedArgs = append(detachedArgs, "--listen-socket", runConfig.ListenSocket)
+	}
+
+	if runConfig.UpstreamHTTP2 {
+		detachedArgs = append(detachedArgs, "--upstream-http2")
+	}
+
+	if runConfig.TLSCertFile != "" {
+		detachedArgs = append(detachedArgs, "--tls-cert", runConfig.TLSCertFile, "--tls-key", runConfig.TLSKeyFile)
+	}
+
+	if runConfig.TLSSelfSigned {
+		detachedArgs = append(detachedArgs, "--tls-self-signed")
+	}
+
+	if runConfig.ToolOverrideFile != "" {
+		detachedArgs = append(detachedArgs, "--tool-override", runConfig.ToolOverrideFile)
+	}
+
+	if len(runConfig.Tools) > 0 {
+		detachedArgs = append(detachedArgs, "--tools", strings.Join(runConfig.Tools, ","))
+	}
+
+	if runConfig.LogPayloads {
+		detachedArgs = append(detachedArgs, "--log-payloads")
+	} else if runConfig.LogRequests {
+		detachedArgs = append(detachedArgs, "--log-requests")
+	}
+
+	if runConfig.RateLimit != "" {
+		detachedArgs = append(detachedArgs, "--rate-limit", runConfig.RateLimit)
+	}
+
+	for _, origin := range runConfig.CORSOrigins {
+		detachedArgs = append(detachedArgs, "--cors-origin", origin)
+	}
+
+	detachedArgs = append(detachedArgs, proxyLimitArgs(runConfig.ProxyLimits)...)
+
+	if runConfig.K8sPodTemplatePatch != "" {
+		detachedArgs = append(detachedArgs, "--k8s-pod-patch", runConfig.K8sPodTemplatePatch)
+	}
+
+	// Pass user-defined labels; the standard labels are recreated by the detached process
+	for key, value := range runConfig.ContainerLabels {
+		if !labels.IsStandardLabel(key) {
+			detachedArgs = append(detachedArgs, "--label", fmt.Sprintf("%s=%s", key, value))
+		}
+	}
+
+	// Use Name if available
+	if runConfig.Name != "" {
+		detachedArgs = append(detachedArgs, "--name", runConfig.Name)
+	}
+
+	// Use ContainerName if available
+	if runConfig.ContainerName != "" {
+		detachedArgs = append(detachedArgs, "--name", runConfig.ContainerName)
+	}
+
+	if runConfig.Host != "" {
+		detachedArgs = append(detachedArgs, "--host", runConfig.Host)
+	}
+
+	if runConfig.Port != 0 {
+		detachedArgs = append(detachedArgs, "--port", strconv.Itoa(runConfig.Port))
+	}
+
+	if runConfig.TargetPort != 0 {
+		detachedArgs = append(detachedArgs, "--target-port", strconv.Itoa(runConfig.TargetPort))
+	}
+
+	// Add target host if it's not the default
+	if runConfig.TargetHost != "localhost" {
+		detachedArgs = append(detachedArgs, "--target-host", runConfig.TargetHost)
+	}
+
+	// Pass the permission profile to the detached process
+	if runConfig.PermissionProfile != nil {
+		// We need to create a temporary file for the permission profile
+		permProfilePath, err := runner.CreatePermissionProfileFile(runConfig.BaseName, runConfig.PermissionProfile)
+		if err != nil {
+			logger.Warnf("Warning: Failed to create permission profile file: %v", err)
+		} else {
+			detachedArgs = append(detachedArgs, "--permission-profile", permProfilePath)
+		}
+	}
+
+	// Add environment variables
+	for key, value := range runConfig.EnvVars {
+		detachedArgs = append(detachedArgs, "--env", fmt.Sprintf("%s=%s", key, value))
+	}
+
+	// Add volume mounts if they were provided
+	for _, volume := range runConfig.Volumes {
+		detachedArgs = append(detachedArgs, "--volume", volume)
+	}
+
+	// Add secrets if they were provided
+	for _, secret := range runConfig.Secrets {
+		detachedArgs = append(detachedArgs, "--secret", secret)
+	}
+
+	// Add OIDC flags if they were provided
+	if runConfig.OIDCConfig != nil {
+		if runConfig.OIDCConfig.Issuer != "" {
+			detachedArgs = append(detachedArgs, "--oidc-issuer", runConfig.OIDCConfig.Issuer)
+		}
+		if runConfig.OIDCConfig.Audience != "" {
+			detachedArgs = append(detachedArgs, "--oidc-audience", runConfig.OIDCConfig.Audience)
+		}
+		if runConfig.OIDCConfig.JWKSURL != "" {
+			detachedArgs = append(detachedArgs, "--oidc-jwks-url", runConfig.OIDCConfig.JWKSURL)
+		}
+		if runConfig.OIDCConfig.ClientID != "" {
+			detachedArgs = append(detachedArgs, "--oidc-client-id", runConfig.OIDCConfig.ClientID)
+		}
+	}
+
+	// Add authz config if it was provided
+	if runConfig.AuthzConfigPath != "" {
+		detachedArgs = append(detachedArgs, "--authz-config", runConfig.AuthzConfigPath)
+	}
+
+	// Add audit config if it was provided
+	if runConfig.AuditConfigPath != "" {
+		detachedArgs = append(detachedArgs, "--audit-config", runConfig.AuditConfigPath)
+	}
+
+	// Add telemetry flags if telemetry config is provided
+	if runConfig.TelemetryConfig != nil {
+		if runConfig.TelemetryConfig.Endpoint != "" {
+			detachedArgs = append(detachedArgs, "--otel-endpoint", runConfig.TelemetryConfig.Endpoint)
+		}
+		if runConfig.TelemetryConfig.ServiceName != "" {
+			detachedArgs = append(detachedArgs, "--otel-service-name", runConfig.TelemetryConfig.ServiceName)
+		}
+		if runConfig.TelemetryConfig.SamplingRate != 0.1 { // Only add if not default
+			detachedArgs = append(detachedArgs, "--otel-sampling-rate", fmt.Sprintf("%f", runConfig.TelemetryConfig.SamplingRate))
+		}
+		for key, value := range runConfig.TelemetryConfig.Headers {
+			detachedArgs = append(detachedArgs, "--otel-headers", fmt.Sprintf("%s=%s", key, value))
+		}
+		if runConfig.TelemetryConfig.Insecure {
+			detachedArgs = append(detachedArgs, "--otel-insecure")
+		}
+		if runConfig.TelemetryConfig.EnablePrometheusMetricsPath {
+			detachedArgs = append(detachedArgs, "--otel-enable-prometheus-metrics-path")
+		}
+		for _, envVar := range runConfig.TelemetryConfig.EnvironmentVariables {
+			detachedArgs = append(detachedArgs, "--otel-env-vars", envVar)
+		}
+	}
+
+	// Add enable audit flag if audit config is set but no config path is provided
+	if runConfig.AuditConfig != nil && runConfig.AuditConfigPath == "" {
+		detachedArgs = append(detachedArgs, "--enable-audit")
+	}
+
+	// Add the image and any arguments
+	detachedArgs = append(detachedArgs, runConfig.Image)
+	if len(runConfig.CmdArgs) > 0 {
+		detachedArgs = append(detachedArgs, "--")
+		detachedArgs = append(detachedArgs, runConfig.CmdArgs...)
+	}
+
+	// Create a new command
+	// #nosec G204 - This is safe as execPath is the path to the current binary
+	detachedCmd := exec.Command(execPath, detachedArgs...)
+
+	// Set environment variables for the detached process
+	detachedCmd.Env = append(os.Environ(), fmt.Sprintf("%s=%s", process.ToolHiveDetachedEnv, process.ToolHiveDetachedValue))
+
+	// If we need the decrypt password, set it as an environment variable in the detached process.
+	// NOTE: This breaks the abstraction slightly since this is only relevant for the CLI, but there
+	// are checks inside `GetSecretsPassword` to ensure this does not get called in a detached process.
+	// This will be addressed in a future re-think of the secrets manager interface.
+	if needSecretsPassword(runConfig.Secrets) {
+		password, err := secrets.GetSecretsPassword("")
+		if err != nil {
+			return fmt.Errorf("failed to get secrets password: %v", err)
+		}
+		detachedCmd.Env = append(detachedCmd.Env, fmt.Sprintf("%s=%s", secrets.PasswordEnvVar, password))
+	}
+
+	// Pass the static auth token in the environment rather than as a flag, which is visible in the process list
+	token, err := detachedAuthToken(runConfig)
+	if err != nil {
+		return err
+	}
+	if token != "" {
+		detachedCmd.Env = append(detachedCmd.Env, fmt.Sprintf("%s=%s", auth.StaticTokenEnvVar, token))
+	}
+
+	// Redirect stdout and stderr to the log file if it was created successfully
+	if logFile != nil {
+		detachedCmd.Stdout = logFile
+		detachedCmd.Stderr = logFile
+	} else {
+		// Otherwise, discard the output
+		detachedCmd.Stdout = nil
+		detachedCmd.Stderr = nil
+	}
+
+	// Detach the process from the terminal
+	detachedCmd.Stdin = nil
+	detachedCmd.SysProcAttr = getSysProcAttr()
+
+	// Start the detached process
+	if err := detachedCmd.Start(); err != nil {
+		return fmt.Errorf("failed to start detached process: %v", err)
+	}
+
+	// Write the PID to a file so the stop command can kill the process
+	if err := process.WritePIDFile(runConfig.BaseName, detachedCmd.Process.Pid); err != nil {
+		logger.Warnf("Warning: Failed to write PID file: %v", err)
+	}
+
+	logger.Infof("MCP server is running in the background (PID: %d)", detachedCmd.Process.Pid)
+	logger.Infof("Use 'thv stop %s' to stop the server", runConfig.ContainerName)
+
+	return nil
+}
+
+// detachedAuthToken returns the static auth token passed to the detached process: the token given on the
+// command line, or the reference to it. When the server is restarted from its saved state, which does not
+// hold the token itself, the token is read from the environment variable the detached process is given it with.
+func detachedAuthToken(runConfig *runner.RunConfig) (string, error) {
+	if runConfig.StaticToken != "" {
+		return runConfig.StaticToken, nil
+	}
+	if runConfig.AuthToken != auth.StaticTokenEnvReference {
+		return runConfig.AuthToken, nil
+	}
+	token, err := auth.ResolveStaticToken(runConfig.AuthToken)
+	if err != nil {
+		return "", fmt.Errorf("failed to start %s: %w", runConfig.ContainerName, err)
+	}
+	return token, nil
+}
+
+// upgradeArgs returns the flags of the upgrade configuration of a workload for the detached process.
+// The detached process is given the resolved image, so the version constraint is passed separately.
+func upgradeArgs(runConfig *runner.RunConfig) []string {
+	var args []string
+	if runConfig.AutoUpgrade {
+		args = append(args, "--auto-upgrade")
+	}
+	if runConfig.UpgradeWindow != "" {
+		args = append(args, "--upgrade-window", runConfig.UpgradeWindow)
+	}
+	if runConfig.VersionConstraint != "" {
+		args = append(args, "--version-constraint", runConfig.VersionConstraint)
+	}
+	return args
+}
+
+// logSinkArgs returns the flag of the log sink of a workload for the detached process, if it is not the default
+func logSinkArgs(runConfig *runner.RunConfig) []string {
+	if runConfig.LogSink == "" || runConfig.LogSink == string(logger.SinkFile) {
+		return nil
+	}
+	return []string{"--log-sink", runConfig.LogSink}
+}
+
+// resourceLimitArgs returns the flags of the resource limits, GPUs and devices of a workload for the
+// detached process. Memory sizes are passed in bytes.
+func resourceLimitArgs(runConfig *runner.RunConfig) []string {
+	var args []string
+	if runConfig.CPUs > 0 {
+		args = append(args, "--cpus", strconv.FormatFloat(runConfig.CPUs, 'f', -1, 64))
+	}
+	if runConfig.Memory > 0 {
+		args = append(args, "--memory", strconv.FormatInt(runConfig.Memory, 10))
+	}
+	if runConfig.MemorySwap != 0 {
+		args = append(args, "--memory-swap", strconv.FormatInt(runConfig.MemorySwap, 10))
+	}
+	if runConfig.PidsLimit > 0 {
+		args = append(args, "--pids-limit", strconv.FormatInt(runConfig.PidsLimit, 10))
+	}
+	if runConfig.GPUs != "" {
+		args = append(args, "--gpu", runConfig.GPUs)
+	}
+	for _, device := range runConfig.Devices {
+		args = append(args, "--device", device)
+	}
+	return args
+}
+
+// proxyLimitArgs returns the flags of the timeouts and the maximum message size of the proxy of a workload
+// for the detached process. The maximum message size is passed in bytes.
+func proxyLimitArgs(limits types.ProxyLimits) []string {
+	var args []string
+	if limits.ConnectTimeout > 0 {
+		args = append(args, "--proxy-connect-timeout", limits.ConnectTimeout.String())
+	}
+	if limits.IdleTimeout > 0 {
+		args = append(args, "--proxy-idle-timeout", limits.IdleTimeout.String())
+	}
+	if limits.SSEReadTimeout > 0 {
+		args = append(args, "--proxy-sse-read-timeout", limits.SSEReadTimeout.String())
+	}
+	if limits.MaxMessageSize > 0 {
+		args = append(args, "--max-message-size", strconv.FormatInt(limits.MaxMessageSize, 10))
+	}
+	return args
+}
+
+func (d *defaultManager) GetLogs(ctx context.Context, containerName string, options rt.LogOptions) (string, error) {
+	container, err := d.findContainerByName(ctx, containerName)
+	if err != nil {
+		// Propagate the error if the container is not found and none of its logs were persisted
+		if errors.Is(err, ErrContainerNotFound) {
+			if logs, ok := persistedServerLogs(containerName, options.Tail); ok {
+				return logs, nil
+			}
+			return "", fmt.Errorf("%w: %s", ErrContainerNotFound, containerName)
+		}
+		return "", fmt.Errorf("failed to find container %s: %v", containerName, err)
+	}
+
+	// Get the logs from the runtime
+	logs, err := d.runtime.GetWorkloadLogs(ctx, container.ID, options)
+	if err != nil {
+		return "", fmt.Errorf("failed to get container logs %s: %v", containerName, err)
+	}
+
+	return logs, nil
+}
+
+func (d *defaultManager) findContainerByName(ctx context.Context, name string) (*rt.ContainerInfo, error) {
+	// List containers to find the one with the given name
+	containers, err := d.runtime.ListWorkloads(ctx)
+	if err != nil {
+		return nil, fmt.Errorf("failed to list containers: %v", err)
+	}
+
+	// Find the container with the given name
+	for _, c := range containers {
+		// Check if the container is managed by ToolHive
+		if !labels.IsToolHiveContainer(c.Labels) {
+			continue
+		}
+
+		// Check if the container name matches
+		containerName := labels.GetContainerName(c.Labels)
+		if containerName == "" {
+			name = c.Name // Fallback to container name
+		}
+
+		// Check if the name matches (exact match or prefix match)
+		if containerName == name || c.ID == name {
+			return &c, nil
+		}
+	}
+
+	return nil, fmt.Errorf("%w: %s", ErrContainerNotFound, name)
+}
+
+func shouldRemoveClientConfig() bool {
+	c := config.GetConfig()
+	return len(c.Clients.RegisteredClients) > 0
+}
+
+// TODO: Move to dedicated config management interface.
+// updateClientConfigurations updates client configuration files with the MCP server URL
+func removeClientConfigurations(containerName string) error {
+	// Find client configuration files
+	configs, err := client.FindClientConfigs()
+	if err != nil {
+		return fmt.Errorf("failed to find client configurations: %w", err)
+	}
+
+	if len(configs) == 0 {
+		logger.Info("No client configuration files found")
+		return nil
+	}
+
+	for _, c := range configs {
+		logger.Infof("Removing MCP server from client configuration: %s", c.Path)
+
+		if err := c.ConfigUpdater.Remove(containerName); err != nil {
+			logger.Warnf("Warning: Failed to remove MCP server from client configuration %s: %v", c.Path, err)
+			continue
+		}
+
+		logger.Infof("Successfully removed MCP server from client configuration: %s", c.Path)
+	}
+
+	return nil
+}
+
+func isContainerRunning(container *rt.ContainerInfo) bool {
+	// A paused container is still running, its processes are only frozen
+	return container.State == "running" || container.State == containerStatePaused
+}
+
+// loadRunnerFromState attempts to load a Runner from the state store
+func (d *defaultManager) loadRunnerFromState(ctx context.Context, baseName string) (*runner.Runner, error) {
+	// Load the runner from the state store
+	r, err := runner.LoadState(ctx, baseName)
+	if err != nil {
+		return nil, err
+	}
+
+	// Update the runtime in the loaded configuration
+	r.Config.Runtime = d.runtime
+
+	return r, nil
+}
+
+func needSecretsPassword(secretOptions []string) bool {
+	// If the user did not ask for any secrets, then don't attempt to instantiate
+	// the secrets manager.
+	if len(secretOptions) == 0 {
+		return false
+	}
+	// Ignore err - if the flag is not set, it's not needed.
+	providerType, _ := config.GetConfig().Secrets.GetProviderType()
+	return providerType == secrets.EncryptedType
+}
+
+// cleanupTempPermissionProfile cleans up temporary permission profile files for a given base name
+func (*defaultManager) cleanupTempPermissionProfile(ctx context.Context, baseName string) error {
+	// Try to load the saved configuration to get the permission profile path
+	r, err := runner.LoadState(ctx, baseName)
+	if err != nil {
+		// If we can't load the state, there's nothing to clean up
+		logger.Debugf("Could not load state for %s, skipping permission profile cleanup: %v", baseName, err)
+		return nil
+	}
+
+	// Clean up the temporary permission profile if it exists
+	if r.Config.PermissionProfileNameOrPath != "" {
+		if err := runner.CleanupTempPermissionProfile(r.Config.PermissionProfileNameOrPath); err != nil {
+			return fmt.Errorf("failed to cleanup temporary permission profile: %v", err)
+		}
+	}
+
+	return nil
+}
+
+// stopWorkloads stops the named workloads concurrently.
+// It assumes that the workloads exist in the running state.
+func (d *defaultManager) stopWorkloads(
+	_ context.Context,
+	workloads []*rt.ContainerInfo,
+	options *rt.StopOptions,
+) *errgroup.Group {
+	group := errgroup.Group{}
+	for _, workload := range workloads {
+		group.Go(func() error {
+			childCtx, cancel := context.WithTimeout(context.Background(), AsyncOperationTimeout)
+			defer cancel()
+
+			name := labels.GetContainerBaseName(workload.Labels)
+			// Stop the proxy process
+			proxy.StopProcess(name)
+
+			// Resume a paused container, so it can handle the stop signal
+			if workload.State == containerStatePaused {
+				if err := d.runtime.UnpauseWorkload(childCtx, workload.ID); err != nil {
+					logger.Warnf("Warning: Failed to resume paused container: %v", err)
+				}
+				if err := proxy.UnmarkPaused(childCtx, labels.GetContainerName(workload.Labels)); err != nil {
+					logger.Warnf("Warning: Failed to clear paused state: %v", err)
+				}
+			}
+
+			logger.Infof("Stopping containers for %s...", name)
+			// Stop the container
+			if err := d.runtime.StopWorkload(childCtx, workload.ID, options); err != nil {
+				return fmt.Errorf("failed to stop container: %w", err)
+			}
+
+			if shouldRemoveClientConfig() {
+				if err := removeClientConfigurations(name); err != nil {
+					logger.Warnf("Warning: Failed to remove client configurations: %v", err)
+				} else {
+					logger.Infof("Client configurations for %s removed", name)
+				}
+			}
+
+			logger.Infof("Successfully stopped %s...", name)
+			return nil
+		})
+	}
+
+	return &group
+}
+
+// DeleteWorkloads deletes the specified workloads by name.
+func (d *defaultManager) DeleteWorkloads(_ context.Context, names []string) (*errgroup.Group, error) {
+	// Validate all workload names to prevent path traversal attacks
+	for _, name := range names {
+		if err := validateWorkloadName(name); err != nil {
+			return nil, fmt.Errorf("invalid workload name '%s': %w", name, err)
+		}
+	}
+
+	group := &errgroup.Group{}
+
+	for _, name := range names {
+		group.Go(func() error {
+			// Create a child context with a longer timeout
+			childCtx, cancel := context.WithTimeout(context.Background(), AsyncOperationTimeout)
+			defer cancel()
+
+			// Find the container
+			container, err := d.findContainerByName(childCtx, name)
+			if err != nil {
+				if errors.Is(err, ErrContainerNotFound) {
+					// Log but don't fail the entire operation for not found containers
+					logger.Warnf("Warning: Failed to delete workload %s: %v", name, err)
+					return nil
+				}
+				return fmt.Errorf("failed to find workload %s: %v", name, err)
+			}
+
+			containerID := container.ID
+			containerLabels := container.Labels
+			baseName := labels.GetContainerBaseName(containerLabels)
+			isRunning := isContainerRunning(container)
+
+			if isRunning {
+				// Stop the proxy process first (like StopWorkload does)
+				logger.Infof("Removing proxy process for %s...", name)
+				if baseName != "" {
+					proxy.StopProcess(baseName)
+				}
+			}
+
+			// Remove the container
+			logger.Infof("Removing container %s...", name)
+			if err := d.runtime.RemoveWorkload(childCtx, containerID); err != nil {
+				return fmt.Errorf("failed to remove container: %v", err)
+			}
+
+			// Get the base name from the container labels
+			if baseName != "" {
+				d.cleanupWorkloadState(childCtx, name, baseName)
+			}
+
+			return nil
+		})
+	}
+
+	return group, nil
+}
+
+// cleanupWorkloadState removes what ToolHive keeps about a deleted workload: its saved state, temporary
+// permission profile, upgrade history, client sessions and client configurations
+func (d *defaultManager) cleanupWorkloadState(ctx context.Context, name, baseName string) {
+	// Clean up temporary permission profile before deleting saved state
+	if err := d.cleanupTempPermissionProfile(ctx, baseName); err != nil {
+		logger.Warnf("Warning: Failed to cleanup temporary permission profile: %v", err)
+	}
+
+	// Delete the saved state if it exists
+	if err := runner.DeleteSavedConfig(ctx, baseName); err != nil {
+		logger.Warnf("Warning: Failed to delete saved state: %v", err)
+	} else {
+		logger.Infof("Saved state for %s removed", baseName)
+	}
+
+	if err := upgrade.DeleteHistory(ctx, baseName); err != nil {
+		logger.Warnf("Warning: Failed to delete upgrade history: %v", err)
+	}
+
+	if err := session.Delete(ctx, name); err != nil {
+		logger.Warnf("Warning: Failed to delete client sessions: %v", err)
+	}
+
+	logger.Infof("Container %s removed", name)
+
+	if shouldRemoveClientConfig() {
+		if err := removeClientConfigurations(name); err != nil {
+			logger.Warnf("Warning: Failed to remove client configurations: %v", err)
+		} else {
+			logger.Infof("Client configurations for %s removed", name)
+		}
+	}
+}
+
+// RestartWorkloads restarts the specified workloads by name.
+	// It is implemented as an asynchronous operation which returns an errgroup.Group
+	RestartWorkloads(ctx context.Context, names []string) (*errgroup.Group, error)
+	// RestartWorkloadForeground restarts the specified workload in the foreground,
+	// blocking until it is stopped. This is intended for processes supervised by a service manager.
+	RestartWorkloadForeground(ctx context.Context, name string) error
+	// RenameWorkload renames the specified workload, restarting it under the new name if it is running.
+	RenameWorkload(ctx context.Context, oldName, newName string) error
+	// CloneWorkload runs a copy of the specified workload under a new name in the background,
+	// with the given overrides applied to its configuration. It returns the configuration of the copy.
+	CloneWorkload(ctx context.Context, name, newName string, port int, overrides []string) (*runner.RunConfig, error)
+	// PauseWorkload freezes the container of the specified workload. Its proxy rejects requests until it is resumed.
+	PauseWorkload(ctx context.Context, name string) error
+	// ResumeWorkload unfreezes the container of the specified paused workload.
+	ResumeWorkload(ctx context.Context, name string) error
+	// ExecWorkload runs a command in the container of the specified running workload and returns its exit code.
+	ExecWorkload(ctx context.Context, name string, command []string, options *rt.ExecOptions) (int, error)
+	// GetLogs retrieves the logs of a container.
+	GetLogs(ctx context.Context, containerName string, options rt.LogOptions) (string, error)
+	// GetProxyLogs retrieves the logs of the proxy of a container running in the background.
+	GetProxyLogs(ctx context.Context, containerName string, options rt.LogOptions) (string, error)
+	// GetEvents returns the lifecycle and health events of the named workload, or of all workloads if name is empty.
+	GetEvents(ctx context.Context, name string, options *rt.EventOptions) (<-chan Event, <-chan error)
+}
+
+type defaultManager struct {
+	runtime rt.Runtime
+}
+
+// ErrContainerNotFound is returned when a container cannot be found by name.
+// ErrInvalidWorkloadName is returned when a workload name fails validation.
+var (
+	ErrContainerNotFound   = fmt.Errorf("container not found")
+	ErrContainerNotRunning = fmt.Errorf("container not running")
+	ErrInvalidWorkloadName = fmt.Errorf("invalid workload name")
+	ErrContainerNotPaused  = fmt.Errorf("container not paused")
+)
+
+const (
+	// AsyncOperationTimeout is the timeout for async workload operations
+	AsyncOperationTimeout = 5 * time.Minute
+)
+
+// validateWorkloadName validates workload names to prevent path traversal attacks
+// and other security issues. Workload names should only contain alphanumeric
+// characters, hyphens, underscores, and dots.
+var workloadNamePattern = regexp.MustCompile(`^[a-zA-Z0-9._-]+$`)
+
+// NewManager creates a new container manager instance.
+func NewManager(ctx context.Context) (Manager, error) {
+	runtime, err := ct.NewFactory().Create(ctx)
+	if err != nil {
+		return nil, err
+	}
+
+	return &defaultManager{
+		runtime: runtime,
+	}, nil
+}
+
+// NewManagerFromRuntime creates a new container manager instance from an existing runtime.
+func NewManagerFromRuntime(runtime rt.Runtime) Manager {
+	return &defaultManager{
+		runtime: runtime,
+	}
+}
+
+func (d *defaultManager) GetWorkload(ctx context.Context, name string) (Workload, error) {
+	// Validate workload name to prevent path traversal attacks
+	if err := validateWorkloadName(name); err != nil {
+		return Workload{}, err
+	}
+
+	container, err := d.findContainerByName(ctx, name)
+	if err != nil {
+		// Note that `findContainerByName` already wraps the error with a more specific message.
+		return Workload{}, err
+	}
+
+	return WorkloadFromContainerInfo(container)
+}
+
+func (d *defaultManager) ListWorkloads(ctx context.Context, listAll bool) ([]Workload, error) {
+	// List containers
+	containers, err := d.runtime.ListWorkloads(ctx)
+	if err != nil {
+		return nil, fmt.Errorf("failed to list containers: %v", err)
+	}
+
+	// Filter containers to only show those managed by ToolHive
+	var workloads []Workload
+	for _, c := range containers {
+		// If the caller did not set `listAll` to true, only include running containers.
+		if labels.IsToolHiveContainer(c.Labels) && (isContainerRunning(&c) || listAll) {
+			workload, err := WorkloadFromContainerInfo(&c)
+			if err != nil {
+				return nil, err
+			}
+			workloads = append(workloads, workload)
+		}
+	}
+
+	return workloads, nil
+}
+
+func (d *defaultManager) StopWorkloads(
+	ctx context.Context,
+	names []string,
+	options *rt.StopOptions,
+) (*errgroup.Group, error) {
+	// Validate all workload names to prevent path traversal attacks
+	for _, name := range names {
+		if err := validateWorkloadName(name); err != nil {
+			return nil, fmt.Errorf("invalid workload name '%s': %w", name, err)
+		}
+	}
+
+	// Find all containers first
+	var containers []*rt.ContainerInfo
+	for _, name := range names {
+		container, err := d.findContainerByName(ctx, name)
+		if err != nil {
+			if errors.Is(err, ErrContainerNotFound) {
+				// Log but don't fail the entire operation for not found containers
+				logger.Warnf("Warning: Failed to stop workload %s: %v", name, err)
+				continue
+			}
+			return nil, fmt.Errorf("failed to find workload %s: %v", name, err)
+		}
+
+		running := isContainerRunning(container)
+		if !running {
+			// Log but don't fail the entire operation for not running containers
+			logger.Warnf("Warning: Failed to stop workload %s: %v", name, ErrContainerNotRunning)
+			continue
+		}
+
+		containers = append(containers, container)
+	}
+
+	return d.stopWorkloads(ctx, containers, options), nil
+}
+
+func (*defaultManager) RunWorkload(ctx context.Context, runConfig *runner.RunConfig) error {
+	// Forward the logs of the server to its sink, tagged with its name. This is done here rather than by
+	// the run command, so the sink is also set up when the server is restarted from its saved state.
+	if err := logger.EnableSink(logger.SinkType(runConfig.LogSink), "thv-"+runConfig.BaseName); err != nil {
+		return err
+	}
 	mcpRunner := runner.NewRunner(runConfig)
 	return mcpRunner.Run(ctx)
 }
@@ -179,7 +1037,7 @@ func (*defaultManager) RunWorkloadDetached(runConfig *runner.RunConfig) error {
 	}
 
 	// Create a log file for the detached process
-	logFilePath, err := xdg.DataFile(fmt.Sprintf("toolhive/logs/%s.log", runConfig.BaseName))
+	logFilePath, err := ProxyLogFilePath(runConfig.BaseName)
 	if err != nil {
 		return fmt.Errorf("failed to create log file path: %v", err)
 	}
@@ -194,7 +1052,8 @@ func (*defaultManager) RunWorkloadDetached(runConfig *runner.RunConfig) error {
 
 	// Prepare the command arguments for the detached process
 	// We'll run the same command but with the --foreground flag
-	detachedArgs := []string{"run", "--foreground"}
+	// The image was already retrieved, so the detached process only pulls it if it was removed since
+	detachedArgs := []string{"run", "--foreground", "--pull", "missing"}
 
 	// Add all the original flags
 	if runConfig.Transport != "stdio" {
@@ -209,6 +1068,99 @@ func (*defaultManager) RunWorkloadDetached(runConfig *runner.RunConfig) error {
 		detachedArgs = append(detachedArgs, "--isolate-network")
 	}
 
+	detachedArgs = append(detachedArgs, logSinkArgs(runConfig)...)
+
+	if runConfig.LogDriver != "" {
+		detachedArgs = append(detachedArgs, "--log-driver", runConfig.LogDriver)
+	}
+
+	for key, value := range runConfig.LogOptions {
+		detachedArgs = append(detachedArgs, "--log-opt", fmt.Sprintf("%s=%s", key, value))
+	}
+
+	detachedArgs = append(detachedArgs, upgradeArgs(runConfig)...)
+
+	if runConfig.StopTimeout > 0 {
+		detachedArgs = append(detachedArgs, "--stop-timeout", strconv.Itoa(runConfig.StopTimeout))
+	}
+
+	if runConfig.StopSignal != "" {
+		detachedArgs = append(detachedArgs, "--stop-signal", runConfig.StopSignal)
+	}
+	detachedArgs = append(detachedArgs, resourceLimitArgs(runConfig)...)
+	if runConfig.RestartPolicy != "" {
+		detachedArgs = append(detachedArgs, "--restart", runConfig.RestartPolicy)
+	}
+	if runConfig.RestartUnhealthy {
+		detachedArgs = append(detachedArgs, "--restart-unhealthy")
+	}
+	for _, name := range runConfig.Networks {
+		detachedArgs = append(detachedArgs, "--network", name)
+	}
+
+	if runConfig.SSEPath != "" {
+		detachedArgs = append(detachedArgs, "--sse-path", runConfig.SSEPath)
+	}
+
+	if runConfig.MessagesPath != "" {
+		detachedArgs = append(detachedArgs, "--messages-path", runConfig.MessagesPath)
+	}
+
+	if runConfig.NamedPipe != "" {
+		detachedArgs = append(detachedArgs, "--named-pipe")
+	}
+
+	if runConfig.ListenSocket != "" {
+		detachedArgs = append(detachedArgs, "--listen-socket", runConfig.ListenSocket)
+	}
+
+	if runConfig.UpstreamHTTP2 {
+		detachedArgs = append(detachedArgs, "--upstream-http2")
+	}
+
+	if runConfig.TLSCertFile != "" {
+		detachedArgs = append(detachedArgs, "--tls-cert", runConfig.TLSCertFile, "--tls-key", runConfig.TLSKeyFile)
+	}
+
+	if runConfig.TLSSelfSigned {
+		detachedArgs = append(detachedArgs, "--tls-self-signed")
+	}
+
+	if runConfig.ToolOverrideFile != "" {
+		detachedArgs = append(detachedArgs, "--tool-override", runConfig.ToolOverrideFile)
+	}
+
+	if len(runConfig.Tools) > 0 {
+		detachedArgs = append(detachedArgs, "--tools", strings.Join(runConfig.Tools, ","))
+	}
+
+	if runConfig.LogPayloads {
+		detachedArgs = append(detachedArgs, "--log-payloads")
+	} else if runConfig.LogRequests {
+		detachedArgs = append(detachedArgs, "--log-requests")
+	}
+
+	if runConfig.RateLimit != "" {
+		detachedArgs = append(detachedArgs, "--rate-limit", runConfig.RateLimit)
+	}
+
+	for _, origin := range runConfig.CORSOrigins {
+		detachedArgs = append(detachedArgs, "--cors-origin", origin)
+	}
+
+	detachedArgs = append(detachedArgs, proxyLimitArgs(runConfig.ProxyLimits)...)
+
+	if runConfig.K8sPodTemplatePatch != "" {
+		detachedArgs = append(detachedArgs, "--k8s-pod-patch", runConfig.K8sPodTemplatePatch)
+	}
+
+	// Pass user-defined labels; the standard labels are recreated by the detached process
+	for key, value := range runConfig.ContainerLabels {
+		if !labels.IsStandardLabel(key) {
+			detachedArgs = append(detachedArgs, "--label", fmt.Sprintf("%s=%s", key, value))
+		}
+	}
+
 	// Use Name if available
 	if runConfig.Name != "" {
 		detachedArgs = append(detachedArgs, "--name", runConfig.Name)
@@ -344,6 +1296,15 @@ func (*defaultManager) RunWorkloadDetached(runConfig *runner.RunConfig) error {
 		detachedCmd.Env = append(detachedCmd.Env, fmt.Sprintf("%s=%s", secrets.PasswordEnvVar, password))
 	}
 
+	// Pass the static auth token in the environment rather than as a flag, which is visible in the process list
+	token, err := detachedAuthToken(runConfig)
+	if err != nil {
+		return err
+	}
+	if token != "" {
+		detachedCmd.Env = append(detachedCmd.Env, fmt.Sprintf("%s=%s", auth.StaticTokenEnvVar, token))
+	}
+
 	// Redirect stdout and stderr to the log file if it was created successfully
 	if logFile != nil {
 		detachedCmd.Stdout = logFile
@@ -374,18 +1335,106 @@ func (*defaultManager) RunWorkloadDetached(runConfig *runner.RunConfig) error {
 	return nil
 }
 
-func (d *defaultManager) GetLogs(ctx context.Context, containerName string, follow bool) (string, error) {
+// detachedAuthToken returns the static auth token passed to the detached process: the token given on the
+// command line, or the reference to it. When the server is restarted from its saved state, which does not
+// hold the token itself, the token is read from the environment variable the detached process is given it with.
+func detachedAuthToken(runConfig *runner.RunConfig) (string, error) {
+	if runConfig.StaticToken != "" {
+		return runConfig.StaticToken, nil
+	}
+	if runConfig.AuthToken != auth.StaticTokenEnvReference {
+		return runConfig.AuthToken, nil
+	}
+	token, err := auth.ResolveStaticToken(runConfig.AuthToken)
+	if err != nil {
+		return "", fmt.Errorf("failed to start %s: %w", runConfig.ContainerName, err)
+	}
+	return token, nil
+}
+
+// upgradeArgs returns the flags of the upgrade configuration of a workload for the detached process.
+// The detached process is given the resolved image, so the version constraint is passed separately.
+func upgradeArgs(runConfig *runner.RunConfig) []string {
+	var args []string
+	if runConfig.AutoUpgrade {
+		args = append(args, "--auto-upgrade")
+	}
+	if runConfig.UpgradeWindow != "" {
+		args = append(args, "--upgrade-window", runConfig.UpgradeWindow)
+	}
+	if runConfig.VersionConstraint != "" {
+		args = append(args, "--version-constraint", runConfig.VersionConstraint)
+	}
+	return args
+}
+
+// logSinkArgs returns the flag of the log sink of a workload for the detached process, if it is not the default
+func logSinkArgs(runConfig *runner.RunConfig) []string {
+	if runConfig.LogSink == "" || runConfig.LogSink == string(logger.SinkFile) {
+		return nil
+	}
+	return []string{"--log-sink", runConfig.LogSink}
+}
+
+// resourceLimitArgs returns the flags of the resource limits, GPUs and devices of a workload for the
+// detached process. Memory sizes are passed in bytes.
+func resourceLimitArgs(runConfig *runner.RunConfig) []string {
+	var args []string
+	if runConfig.CPUs > 0 {
+		args = append(args, "--cpus", strconv.FormatFloat(runConfig.CPUs, 'f', -1, 64))
+	}
+	if runConfig.Memory > 0 {
+		args = append(args, "--memory", strconv.FormatInt(runConfig.Memory, 10))
+	}
+	if runConfig.MemorySwap != 0 {
+		args = append(args, "--memory-swap", strconv.FormatInt(runConfig.MemorySwap, 10))
+	}
+	if runConfig.PidsLimit > 0 {
+		args = append(args, "--pids-limit", strconv.FormatInt(runConfig.PidsLimit, 10))
+	}
+	if runConfig.GPUs != "" {
+		args = append(args, "--gpu", runConfig.GPUs)
+	}
+	for _, device := range runConfig.Devices {
+		args = append(args, "--device", device)
+	}
+	return args
+}
+
+// proxyLimitArgs returns the flags of the timeouts and the maximum message size of the proxy of a workload
+// for the detached process. The maximum message size is passed in bytes.
+func proxyLimitArgs(limits types.ProxyLimits) []string {
+	var args []string
+	if limits.ConnectTimeout > 0 {
+		args = append(args, "--proxy-connect-timeout", limits.ConnectTimeout.String())
+	}
+	if limits.IdleTimeout > 0 {
+		args = append(args, "--proxy-idle-timeout", limits.IdleTimeout.String())
+	}
+	if limits.SSEReadTimeout > 0 {
+		args = append(args, "--proxy-sse-read-timeout", limits.SSEReadTimeout.String())
+	}
+	if limits.MaxMessageSize > 0 {
+		args = append(args, "--max-message-size", strconv.FormatInt(limits.MaxMessageSize, 10))
+	}
+	return args
+}
+
+func (d *defaultManager) GetLogs(ctx context.Context, containerName string, options rt.LogOptions) (string, error) {
 	container, err := d.findContainerByName(ctx, containerName)
 	if err != nil {
-		// Propagate the error if the container is not found
+		// Propagate the error if the container is not found and none of its logs were persisted
 		if errors.Is(err, ErrContainerNotFound) {
+			if logs, ok := persistedServerLogs(containerName, options.Tail); ok {
+				return logs, nil
+			}
 			return "", fmt.Errorf("%w: %s", ErrContainerNotFound, containerName)
 		}
 		return "", fmt.Errorf("failed to find container %s: %v", containerName, err)
 	}
 
 	// Get the logs from the runtime
-	logs, err := d.runtime.GetWorkloadLogs(ctx, container.ID, follow)
+	logs, err := d.runtime.GetWorkloadLogs(ctx, container.ID, options)
 	if err != nil {
 		return "", fmt.Errorf("failed to get container logs %s: %v", containerName, err)
 	}
@@ -456,7 +1505,8 @@ func removeClientConfigurations(containerName string) error {
 }
 
 func isContainerRunning(container *rt.ContainerInfo) bool {
-	return container.State == "running"
+	// A paused container is still running, its processes are only frozen
+	return container.State == "running" || container.State == containerStatePaused
 }
 
 // loadRunnerFromState attempts to load a Runner from the state store
@@ -506,7 +1556,11 @@ func (*defaultManager) cleanupTempPermissionProfile(ctx context.Context, baseNam
 
 // stopWorkloads stops the named workloads concurrently.
 // It assumes that the workloads exist in the running state.
-func (d *defaultManager) stopWorkloads(_ context.Context, workloads []*rt.ContainerInfo) *errgroup.Group {
+func (d *defaultManager) stopWorkloads(
+	_ context.Context,
+	workloads []*rt.ContainerInfo,
+	options *rt.StopOptions,
+) *errgroup.Group {
 	group := errgroup.Group{}
 	for _, workload := range workloads {
 		group.Go(func() error {
@@ -517,9 +1571,19 @@ func (d *defaultManager) stopWorkloads(_ context.Context, workloads []*rt.Contai
 			// Stop the proxy process
 			proxy.StopProcess(name)
 
+			// Resume a paused container, so it can handle the stop signal
+			if workload.State == containerStatePaused {
+				if err := d.runtime.UnpauseWorkload(childCtx, workload.ID); err != nil {
+					logger.Warnf("Warning: Failed to resume paused container: %v", err)
+				}
+				if err := proxy.UnmarkPaused(childCtx, labels.GetContainerName(workload.Labels)); err != nil {
+					logger.Warnf("Warning: Failed to clear paused state: %v", err)
+				}
+			}
+
 			logger.Infof("Stopping containers for %s...", name)
 			// Stop the container
-			if err := d.runtime.StopWorkload(childCtx, workload.ID); err != nil {
+			if err := d.runtime.StopWorkload(childCtx, workload.ID, options); err != nil {
 				return fmt.Errorf("failed to stop container: %w", err)
 			}
 
@@ -600,6 +1664,14 @@ func (d *defaultManager) DeleteWorkloads(_ context.Context, names []string) (*er
 					logger.Infof("Saved state for %s removed", baseName)
 				}
 
+				if err := upgrade.DeleteHistory(childCtx, baseName); err != nil {
+					logger.Warnf("Warning: Failed to delete upgrade history: %v", err)
+				}
+
+				if err := session.Delete(childCtx, name); err != nil {
+					logger.Warnf("Warning: Failed to delete client sessions: %v", err)
+				}
+
 				logger.Infof("Container %s removed", name)
 
 				if shouldRemoveClientConfig() {
@@ -683,7 +1755,7 @@ func (d *defaultManager) RestartWorkloads(_ context.Context, names []string) (*e
 			// If the container is running but the proxy is not, stop the container first
 			if containerID != "" && running { // && !proxyRunning was previously here but is implied by previous if statement.
 				logger.Infof("Container %s is running but proxy is not. Stopping container...", name)
-				if err = d.runtime.StopWorkload(childCtx, containerID); err != nil {
+				if err = d.runtime.StopWorkload(childCtx, containerID, nil); err != nil {
 					return fmt.Errorf("failed to stop container %s: %v", name, err)
 				}
 				logger.Infof("Container %s stopped", name)
@@ -696,6 +1768,42 @@ func (d *defaultManager) RestartWorkloads(_ context.Context, names []string) (*e
 	return group, nil
 }
 
+// RestartWorkloadForeground restarts the specified workload in the foreground.
+// If the workload is already running, its proxy and container are stopped first.
+func (d *defaultManager) RestartWorkloadForeground(ctx context.Context, name string) error {
+	if err := validateWorkloadName(name); err != nil {
+		return fmt.Errorf("invalid workload name '%s': %w", name, err)
+	}
+
+	containerBaseName := name
+	container, err := d.findContainerByName(ctx, name)
+	if err != nil && !errors.Is(err, ErrContainerNotFound) {
+		return fmt.Errorf("failed to find workload %s: %v", name, err)
+	}
+	if container != nil {
+		containerBaseName = labels.GetContainerBaseName(container.Labels)
+	}
+
+	mcpRunner, err := d.loadRunnerFromState(ctx, containerBaseName)
+	if err != nil {
+		return fmt.Errorf("failed to load state for %s: %v", containerBaseName, err)
+	}
+
+	// Take over from a proxy running in the background, if any
+	if proxy.IsRunning(containerBaseName) {
+		proxy.StopProcess(containerBaseName)
+	}
+	if container != nil && isContainerRunning(container) {
+		logger.Infof("Stopping container %s...", name)
+		if err := d.runtime.StopWorkload(ctx, container.ID, nil); err != nil {
+			return fmt.Errorf("failed to stop container %s: %v", name, err)
+		}
+	}
+
+	logger.Infof("Starting tooling server %s in the foreground...", name)
+	return d.RunWorkload(ctx, mcpRunner.Config)
+}
+
 func validateWorkloadName(name string) error {
 	if name == "" {
 		return fmt.Errorf("%w: workload name cannot be empty", ErrInvalidWorkloadName)
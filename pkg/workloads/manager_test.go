@@ -0,0 +1,126 @@
+package workloads
+
+import (
+	"bytes"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/auth"
+	"github.com/stacklok/toolhive/pkg/runner"
+)
+
+func TestUpgradeArgs(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name     string
+		config   *runner.RunConfig
+		expected []string
+	}{
+		{
+			name:   "no upgrade configuration",
+			config: &runner.RunConfig{Image: "ghcr.io/github/github-mcp-server:v1.2.3"},
+		},
+		{
+			name: "version constraint",
+			config: &runner.RunConfig{
+				Image:             "ghcr.io/github/github-mcp-server:v1.2.3",
+				VersionConstraint: "^1.2",
+			},
+			expected: []string{"--version-constraint", "^1.2"},
+		},
+		{
+			name: "automatic upgrades within a channel",
+			config: &runner.RunConfig{
+				Image:             "ghcr.io/github/github-mcp-server:v1.2.3",
+				AutoUpgrade:       true,
+				UpgradeWindow:     "Sat,Sun 02:00-04:00",
+				VersionConstraint: "stable",
+			},
+			expected: []string{
+				"--auto-upgrade", "--upgrade-window", "Sat,Sun 02:00-04:00", "--version-constraint", "stable",
+			},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, upgradeArgs(tt.config))
+		})
+	}
+}
+
+func TestLogSinkArgs(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name     string
+		logSink  string
+		expected []string
+	}{
+		{name: "no log sink"},
+		{name: "default log sink", logSink: "file"},
+		{name: "syslog", logSink: "syslog", expected: []string{"--log-sink", "syslog"}},
+		{name: "journald", logSink: "journald", expected: []string{"--log-sink", "journald"}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			// The log sink is kept in the saved state, so restarted servers send their logs to it again
+			var saved bytes.Buffer
+			require.NoError(t, (&runner.RunConfig{Name: "fetch", LogSink: tt.logSink}).WriteJSON(&saved))
+			restored, err := runner.ReadJSON(&saved)
+			require.NoError(t, err)
+			assert.Equal(t, tt.logSink, restored.LogSink)
+			assert.Equal(t, tt.expected, logSinkArgs(restored))
+		})
+	}
+}
+
+func TestDetachedAuthToken(t *testing.T) {
+	// The token of a server restarted from its saved state is read from the environment
+	t.Setenv(auth.StaticTokenEnvVar, "restart-secret")
+
+	tests := []struct {
+		name     string
+		config   *runner.RunConfig
+		expected string
+	}{
+		{name: "no token", config: &runner.RunConfig{}},
+		{
+			name:     "token given on the command line",
+			config:   &runner.RunConfig{AuthToken: auth.StaticTokenEnvReference, StaticToken: "secret"},
+			expected: "secret",
+		},
+		{
+			name:     "token file",
+			config:   &runner.RunConfig{AuthToken: "file:/run/secrets/token"},
+			expected: "file:/run/secrets/token",
+		},
+		{
+			name:     "saved state",
+			config:   &runner.RunConfig{AuthToken: auth.StaticTokenEnvReference},
+			expected: "restart-secret",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			token, err := detachedAuthToken(tt.config)
+			require.NoError(t, err)
+			assert.Equal(t, tt.expected, token)
+		})
+	}
+}
+
+func TestDetachedAuthToken_NotSet(t *testing.T) {
+	t.Setenv(auth.StaticTokenEnvVar, "")
+
+	_, err := detachedAuthToken(&runner.RunConfig{ContainerName: "fetch", AuthToken: auth.StaticTokenEnvReference})
+	assert.ErrorContains(t, err, auth.StaticTokenEnvVar)
+}
@@ -40,6 +40,9 @@ type Workload struct {
 	// Port is the port on which the workload is exposed.
 	// This is embedded in the URL.
 	Port int `json:"port"`
+	// ListenSocket is the path of the Unix domain socket the proxy is served on instead of its port.
+	// The URL is empty for workloads served on a socket, as clients cannot connect to the port.
+	ListenSocket string `json:"listen_socket,omitempty"`
 	// ToolType is the type of tool this workload represents.
 	// For now, it will always be "mcp" - representing an MCP server.
 	ToolType string `json:"tool_type"`
@@ -52,6 +55,8 @@ type Workload struct {
 	StatusContext string `json:"status_context,omitempty"`
 	// CreatedAt is the timestamp when the workload was created.
 	CreatedAt time.Time `json:"created_at"`
+	// Labels are the user-defined labels of the workload.
+	Labels map[string]string `json:"labels,omitempty"`
 }
 
 // WorkloadFromContainerInfo creates a Workload struct from the runtime container info.
@@ -74,10 +79,11 @@ func WorkloadFromContainerInfo(container *runtime.ContainerInfo) (Workload, erro
 	// check if we have the label for transport type (toolhive-transport)
 	transportType := labels.GetTransportType(container.Labels)
 
-	// Generate URL for the MCP server
+	// Generate URL for the MCP server, unless its proxy is served on a socket
+	listenSocket := labels.GetListenSocket(container.Labels)
 	url := ""
-	if port > 0 {
-		url = client.GenerateMCPServerURL(transportType, transport.LocalhostIPv4, port, name)
+	if port > 0 && listenSocket == "" {
+		url = client.GenerateMCPServerURLForLabels(transportType, transport.LocalhostIPv4, port, name, container.Labels)
 	}
 
 	// https://docs.docker.com/reference/api/engine/version/v1.45/#tag/Container/operation/ContainerList
@@ -111,5 +117,7 @@ func WorkloadFromContainerInfo(container *runtime.ContainerInfo) (Workload, erro
 		StatusContext: container.Status,
 		CreatedAt:     container.Created,
 		Port:          port,
+		ListenSocket:  listenSocket,
+		Labels:        labels.GetUserLabels(container.Labels),
 	}, nil
 }
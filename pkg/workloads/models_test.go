@@ -0,0 +1,30 @@
+package workloads
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/labels"
+)
+
+func TestWorkloadFromContainerInfo_ListenSocket(t *testing.T) {
+	t.Parallel()
+
+	containerLabels := map[string]string{}
+	labels.AddStandardLabels(containerLabels, "fetch", "fetch", "sse", 8080)
+
+	workload, err := WorkloadFromContainerInfo(&rt.ContainerInfo{Name: "fetch", State: "running", Labels: containerLabels})
+	require.NoError(t, err)
+	assert.Equal(t, "http://127.0.0.1:8080/sse#fetch", workload.URL)
+	assert.Empty(t, workload.ListenSocket)
+
+	// Clients cannot connect to the port of a proxy served on a socket, so there is no URL
+	labels.SetListenSocket(containerLabels, "/tmp/fetch.sock")
+	workload, err = WorkloadFromContainerInfo(&rt.ContainerInfo{Name: "fetch", State: "running", Labels: containerLabels})
+	require.NoError(t, err)
+	assert.Empty(t, workload.URL)
+	assert.Equal(t, "/tmp/fetch.sock", workload.ListenSocket)
+}
@@ -0,0 +1,69 @@
+package workloads
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/transport/proxy"
+)
+
+// containerStatePaused is the state of a container whose processes are frozen
+const containerStatePaused = "paused"
+
+// PauseWorkload freezes the container of the specified workload.
+// The proxy of the workload keeps running and rejects requests until the workload is resumed.
+func (d *defaultManager) PauseWorkload(ctx context.Context, name string) error {
+	if err := validateWorkloadName(name); err != nil {
+		return fmt.Errorf("invalid workload name '%s': %w", name, err)
+	}
+
+	container, err := d.findContainerByName(ctx, name)
+	if err != nil {
+		return err
+	}
+	if container.State == containerStatePaused {
+		logger.Infof("Container %s is already paused", name)
+		return nil
+	}
+	if !isContainerRunning(container) {
+		return fmt.Errorf("%w: %s", ErrContainerNotRunning, name)
+	}
+
+	// Mark the workload as paused first, so requests are rejected rather than left hanging on the frozen container
+	containerName := labels.GetContainerName(container.Labels)
+	if err := proxy.MarkPaused(ctx, containerName); err != nil {
+		return fmt.Errorf("failed to mark %s as paused: %v", name, err)
+	}
+	if err := d.runtime.PauseWorkload(ctx, container.ID); err != nil {
+		if err := proxy.UnmarkPaused(ctx, containerName); err != nil {
+			logger.Warnf("Warning: Failed to clear paused state: %v", err)
+		}
+		return fmt.Errorf("failed to pause container %s: %v", name, err)
+	}
+	return nil
+}
+
+// ResumeWorkload unfreezes the container of the specified paused workload.
+func (d *defaultManager) ResumeWorkload(ctx context.Context, name string) error {
+	if err := validateWorkloadName(name); err != nil {
+		return fmt.Errorf("invalid workload name '%s': %w", name, err)
+	}
+
+	container, err := d.findContainerByName(ctx, name)
+	if err != nil {
+		return err
+	}
+	if container.State != containerStatePaused {
+		return fmt.Errorf("%w: %s", ErrContainerNotPaused, name)
+	}
+
+	if err := d.runtime.UnpauseWorkload(ctx, container.ID); err != nil {
+		return fmt.Errorf("failed to resume container %s: %v", name, err)
+	}
+	if err := proxy.UnmarkPaused(ctx, labels.GetContainerName(container.Labels)); err != nil {
+		return fmt.Errorf("failed to clear paused state of %s: %v", name, err)
+	}
+	return nil
+}
@@ -0,0 +1,137 @@
+package workloads
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/adrg/xdg"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/runner"
+)
+
+const (
+	// proxyLogPollInterval is how often the proxy log file is checked for new output when following it
+	proxyLogPollInterval = 500 * time.Millisecond
+)
+
+// ProxyLogFilePath returns the path of the file the proxy of a workload running in the background logs to
+func ProxyLogFilePath(baseName string) (string, error) {
+	return xdg.DataFile(fmt.Sprintf("toolhive/logs/%s.log", baseName))
+}
+
+// GetProxyLogs retrieves the last options.Tail lines of the logs of the proxy of a workload running in the
+// background. If options.Follow is true, new output is written to stdout until the context is cancelled.
+// The logs can't be selected by time.
+func (d *defaultManager) GetProxyLogs(ctx context.Context, containerName string, options rt.LogOptions) (string, error) {
+	if !options.Since.IsZero() {
+		return "", fmt.Errorf("proxy logs can't be selected by time")
+	}
+	container, err := d.findContainerByName(ctx, containerName)
+	if err != nil {
+		if errors.Is(err, ErrContainerNotFound) {
+			return "", fmt.Errorf("%w: %s", ErrContainerNotFound, containerName)
+		}
+		return "", fmt.Errorf("failed to find container %s: %v", containerName, err)
+	}
+
+	baseName := labels.GetContainerBaseName(container.Labels)
+	if baseName == "" {
+		baseName = containerName
+	}
+	logFilePath, err := ProxyLogFilePath(baseName)
+	if err != nil {
+		return "", fmt.Errorf("failed to get proxy log file path: %v", err)
+	}
+
+	// #nosec G304 - the path is derived from the base name of a ToolHive-managed workload
+	file, err := os.Open(logFilePath)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return "", fmt.Errorf("no proxy logs found for %s; only servers running in the background log to a file", containerName)
+		}
+		return "", fmt.Errorf("failed to open proxy log file: %v", err)
+	}
+	defer file.Close()
+
+	data, err := io.ReadAll(file)
+	if err != nil {
+		return "", fmt.Errorf("failed to read proxy log file: %v", err)
+	}
+	logs := tailLines(string(data), options.Tail)
+	if !options.Follow {
+		return logs, nil
+	}
+
+	fmt.Print(logs)
+	return "", followFile(ctx, file, os.Stdout)
+}
+
+// persistedServerLogs returns the last lines of the logs persisted for a server whose container no longer
+// exists, and whether there are any
+func persistedServerLogs(baseName string, tail int) (string, bool) {
+	logFilePath, err := runner.ServerLogFilePath(baseName)
+	if err != nil {
+		return "", false
+	}
+	// #nosec G304 - the path is derived from the base name of a ToolHive-managed workload
+	data, err := os.ReadFile(logFilePath)
+	if err != nil {
+		return "", false
+	}
+	logger.Infof("Container %s not found, showing the logs persisted in %s", baseName, logFilePath)
+	return tailLines(string(data), tail), true
+}
+
+// tailLines returns the last n lines of the given text, or all of them if n is zero
+func tailLines(text string, n int) string {
+	trimmed := strings.TrimSuffix(text, "\n")
+	if trimmed == "" {
+		return ""
+	}
+	lines := strings.Split(trimmed, "\n")
+	if n > 0 && len(lines) > n {
+		lines = lines[len(lines)-n:]
+	}
+	return strings.Join(lines, "\n") + "\n"
+}
+
+// followFile copies the output appended to a file to the writer until the context is cancelled.
+// If the file is truncated, for example because it was rotated, it is read again from the start.
+func followFile(ctx context.Context, file *os.File, w io.Writer) error {
+	ticker := time.NewTicker(proxyLogPollInterval)
+	defer ticker.Stop()
+
+	for {
+		if _, err := io.Copy(w, file); err != nil {
+			return fmt.Errorf("failed to read proxy log file: %v", err)
+		}
+
+		select {
+		case <-ctx.Done():
+			return nil
+		case <-ticker.C:
+		}
+
+		offset, err := file.Seek(0, io.SeekCurrent)
+		if err != nil {
+			return fmt.Errorf("failed to read proxy log file: %v", err)
+		}
+		info, err := file.Stat()
+		if err != nil {
+			return fmt.Errorf("failed to read proxy log file: %v", err)
+		}
+		if info.Size() < offset {
+			if _, err := file.Seek(0, io.SeekStart); err != nil {
+				return fmt.Errorf("failed to read proxy log file: %v", err)
+			}
+		}
+	}
+}
@@ -0,0 +1,33 @@
+package workloads
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestTailLines(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name     string
+		text     string
+		n        int
+		expected string
+	}{
+		{name: "empty", text: "", n: 3, expected: ""},
+		{name: "fewer lines", text: "a\nb\n", n: 3, expected: "a\nb\n"},
+		{name: "more lines", text: "a\nb\nc\nd\n", n: 2, expected: "c\nd\n"},
+		{name: "no trailing newline", text: "a\nb\nc", n: 2, expected: "b\nc\n"},
+		{name: "many lines", text: strings.Repeat("line\n", 500), n: 100, expected: strings.Repeat("line\n", 100)},
+		{name: "all lines", text: "a\nb\nc\n", n: 0, expected: "a\nb\nc\n"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			assert.Equal(t, tt.expected, tailLines(tt.text, tt.n))
+		})
+	}
+}
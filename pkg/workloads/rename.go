@@ -0,0 +1,187 @@
+package workloads
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"os"
+
+	rt "github.com/stacklok/toolhive/pkg/container/runtime"
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/runner"
+	"github.com/stacklok/toolhive/pkg/transport/proxy"
+	"github.com/stacklok/toolhive/pkg/transport/session"
+	"github.com/stacklok/toolhive/pkg/upgrade"
+)
+
+// ErrWorkloadExists is returned when a workload is renamed to the name of an existing workload.
+var ErrWorkloadExists = errors.New("workload already exists")
+
+// RenameWorkload renames the specified workload.
+// The labels of a container cannot be changed, so the container is removed and the workload is
+// recreated under the new name with the same configuration and port, which preserves its endpoint.
+// A running workload is stopped, which removes it from the client configurations, and restarted
+// in the background under the new name. A stopped workload is started again with thv restart.
+func (d *defaultManager) RenameWorkload(ctx context.Context, oldName, newName string) error {
+	for _, name := range []string{oldName, newName} {
+		if err := validateWorkloadName(name); err != nil {
+			return fmt.Errorf("invalid workload name '%s': %w", name, err)
+		}
+	}
+	if oldName == newName {
+		return fmt.Errorf("workload %s already has this name", oldName)
+	}
+	if err := d.checkNameAvailable(ctx, newName); err != nil {
+		return err
+	}
+
+	container, err := d.findContainerByName(ctx, oldName)
+	if err != nil {
+		return err
+	}
+	baseName := labels.GetContainerBaseName(container.Labels)
+	mcpRunner, err := d.loadRunnerFromState(ctx, baseName)
+	if err != nil {
+		return fmt.Errorf("failed to load state for %s: %v", baseName, err)
+	}
+
+	running := isContainerRunning(container)
+	if running {
+		if err := d.stopWorkloads(ctx, []*rt.ContainerInfo{container}, nil).Wait(); err != nil {
+			return fmt.Errorf("failed to stop workload %s: %v", oldName, err)
+		}
+	}
+	logger.Infof("Removing container %s...", oldName)
+	if err := d.runtime.RemoveWorkload(ctx, container.ID); err != nil {
+		return fmt.Errorf("failed to remove container %s: %v", oldName, err)
+	}
+
+	if err := renameState(ctx, mcpRunner, baseName, newName); err != nil {
+		return err
+	}
+	logger.Infof("Workload %s renamed to %s", oldName, newName)
+
+	if !running {
+		return nil
+	}
+	logger.Infof("Starting tooling server %s...", newName)
+	return d.RunWorkloadDetached(mcpRunner.Config)
+}
+
+// checkNameAvailable returns ErrWorkloadExists if a workload or saved state with the given name exists
+func (d *defaultManager) checkNameAvailable(ctx context.Context, name string) error {
+	_, err := d.findContainerByName(ctx, name)
+	if err == nil {
+		return fmt.Errorf("%w: %s", ErrWorkloadExists, name)
+	}
+	if !errors.Is(err, ErrContainerNotFound) {
+		return err
+	}
+
+	savedConfigs, err := runner.ListSavedConfigs(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to list saved configurations: %v", err)
+	}
+	for _, savedConfig := range savedConfigs {
+		if savedConfig == name {
+			return fmt.Errorf("%w: %s", ErrWorkloadExists, name)
+		}
+	}
+	return nil
+}
+
+// renameState saves the run configuration of a workload under its new name, and moves the files
+// kept under its old name
+func renameState(ctx context.Context, mcpRunner *runner.Runner, oldBaseName, newName string) error {
+	oldContainerName := mcpRunner.Config.ContainerName
+	renameRunConfig(mcpRunner.Config, newName)
+	if err := mcpRunner.SaveState(ctx); err != nil {
+		return fmt.Errorf("failed to save state for %s: %v", newName, err)
+	}
+	if err := runner.DeleteSavedConfig(ctx, oldBaseName); err != nil {
+		logger.Warnf("Warning: Failed to delete saved state of %s: %v", oldBaseName, err)
+	}
+	renameFiles(ctx, oldBaseName, oldContainerName, newName)
+	return nil
+}
+
+// renameFiles moves the upgrade history, the log files, the self-signed certificate and the client
+// sessions of a workload to its new name, and clears its paused state, since its container is recreated
+// unpaused. Failures are only logged, since the workload runs without them.
+func renameFiles(ctx context.Context, oldBaseName, oldContainerName, newName string) {
+	if err := renameUpgradeHistory(ctx, oldBaseName, newName); err != nil {
+		logger.Warnf("Warning: Failed to move upgrade history of %s: %v", oldBaseName, err)
+	}
+	if err := renameFile(ProxyLogFilePath, oldBaseName, newName); err != nil {
+		logger.Warnf("Warning: Failed to move proxy log file of %s: %v", oldBaseName, err)
+	}
+	if err := renameFile(runner.ServerLogFilePath, oldBaseName, newName); err != nil {
+		logger.Warnf("Warning: Failed to move server log file of %s: %v", oldBaseName, err)
+	}
+	if err := renameFile(selfSignedCertFile, oldBaseName, newName); err != nil {
+		logger.Warnf("Warning: Failed to move self-signed certificate of %s: %v", oldBaseName, err)
+	}
+	if err := renameFile(selfSignedKeyFile, oldBaseName, newName); err != nil {
+		logger.Warnf("Warning: Failed to move self-signed certificate key of %s: %v", oldBaseName, err)
+	}
+	if err := session.Rename(ctx, oldContainerName, newName); err != nil {
+		logger.Warnf("Warning: Failed to move client sessions of %s: %v", oldContainerName, err)
+	}
+	for _, containerName := range []string{oldContainerName, newName} {
+		if err := proxy.UnmarkPaused(ctx, containerName); err != nil {
+			logger.Warnf("Warning: Failed to clear paused state of %s: %v", containerName, err)
+		}
+	}
+}
+
+// renameRunConfig sets the names and the standard labels of the run configuration to the new name
+func renameRunConfig(config *runner.RunConfig, newName string) {
+	config.Name = newName
+	config.ContainerName = newName
+	config.BaseName = newName
+	config.WithStandardLabels()
+}
+
+// renameUpgradeHistory moves the upgrade history of a workload, if any, to its new name
+func renameUpgradeHistory(ctx context.Context, oldBaseName, newBaseName string) error {
+	history, err := upgrade.LoadHistory(ctx, oldBaseName)
+	if err != nil {
+		return err
+	}
+	if len(history.Transitions) == 0 {
+		return nil
+	}
+	if err := upgrade.SaveHistory(ctx, newBaseName, history); err != nil {
+		return err
+	}
+	return upgrade.DeleteHistory(ctx, oldBaseName)
+}
+
+// renameFile moves a file of a workload, if any, to its new name
+func renameFile(filePath func(string) (string, error), oldBaseName, newBaseName string) error {
+	oldPath, err := filePath(oldBaseName)
+	if err != nil {
+		return err
+	}
+	newPath, err := filePath(newBaseName)
+	if err != nil {
+		return err
+	}
+	if err := os.Rename(oldPath, newPath); err != nil && !errors.Is(err, os.ErrNotExist) {
+		return err
+	}
+	return nil
+}
+
+// selfSignedCertFile returns the path of the self-signed certificate of a workload
+func selfSignedCertFile(baseName string) (string, error) {
+	certFile, _, err := runner.SelfSignedCertFiles(baseName)
+	return certFile, err
+}
+
+// selfSignedKeyFile returns the path of the key of the self-signed certificate of a workload
+func selfSignedKeyFile(baseName string) (string, error) {
+	_, keyFile, err := runner.SelfSignedCertFiles(baseName)
+	return keyFile, err
+}
@@ -0,0 +1,106 @@
+package workloads
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/adrg/xdg"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/stacklok/toolhive/pkg/labels"
+	"github.com/stacklok/toolhive/pkg/logger"
+	"github.com/stacklok/toolhive/pkg/runner"
+	"github.com/stacklok/toolhive/pkg/transport/proxy"
+	"github.com/stacklok/toolhive/pkg/transport/session"
+	"github.com/stacklok/toolhive/pkg/transport/types"
+	"github.com/stacklok/toolhive/pkg/upgrade"
+)
+
+func TestRenameRunConfig(t *testing.T) {
+	t.Parallel()
+
+	config := &runner.RunConfig{
+		Name:          "fetch",
+		ContainerName: "fetch",
+		BaseName:      "fetch",
+		Transport:     types.TransportTypeSSE,
+		Port:          8080,
+		ContainerLabels: map[string]string{
+			labels.LabelName:     "fetch",
+			labels.LabelBaseName: "fetch",
+			labels.LabelPort:     "8080",
+			"team":               "platform",
+		},
+	}
+
+	renameRunConfig(config, "web-fetch")
+
+	assert.Equal(t, "web-fetch", config.Name)
+	assert.Equal(t, "web-fetch", config.ContainerName)
+	assert.Equal(t, "web-fetch", config.BaseName)
+	assert.Equal(t, 8080, config.Port)
+	assert.Equal(t, "web-fetch", labels.GetContainerName(config.ContainerLabels))
+	assert.Equal(t, "web-fetch", labels.GetContainerBaseName(config.ContainerLabels))
+	assert.Equal(t, "8080", config.ContainerLabels[labels.LabelPort])
+	assert.Equal(t, "platform", config.ContainerLabels["team"])
+}
+
+func TestRenameFiles(t *testing.T) {
+	logger.Initialize()
+	// The paths are resolved again once the environment is restored
+	t.Cleanup(xdg.Reload)
+	t.Setenv("XDG_DATA_HOME", filepath.Join(t.TempDir(), "data"))
+	t.Setenv("XDG_STATE_HOME", filepath.Join(t.TempDir(), "state"))
+	xdg.Reload()
+	ctx := context.Background()
+
+	files := map[string]func(string) (string, error){
+		"proxy log":               ProxyLogFilePath,
+		"server log":              runner.ServerLogFilePath,
+		"self-signed certificate": selfSignedCertFile,
+		"self-signed key":         selfSignedKeyFile,
+	}
+	for kind, filePath := range files {
+		path, err := filePath("fetch")
+		require.NoError(t, err)
+		require.NoError(t, os.WriteFile(path, []byte(kind), 0600))
+	}
+	require.NoError(t, session.Save(ctx, "fetch", &session.State{SessionIDs: []string{"session-1"}}))
+	history := &upgrade.History{}
+	history.Record(upgrade.Transition{Kind: upgrade.TransitionUpgrade, Image: "mcp/fetch:v2"})
+	require.NoError(t, upgrade.SaveHistory(ctx, "fetch", history))
+	require.NoError(t, proxy.MarkPaused(ctx, "fetch"))
+
+	renameFiles(ctx, "fetch", "fetch", "web-fetch")
+
+	for kind, filePath := range files {
+		oldPath, err := filePath("fetch")
+		require.NoError(t, err)
+		assert.NoFileExists(t, oldPath, kind)
+		newPath, err := filePath("web-fetch")
+		require.NoError(t, err)
+		data, err := os.ReadFile(newPath)
+		require.NoError(t, err, kind)
+		assert.Equal(t, kind, string(data))
+	}
+
+	sessions, err := session.Load(ctx, "web-fetch")
+	require.NoError(t, err)
+	require.NotNil(t, sessions)
+	assert.Equal(t, []string{"session-1"}, sessions.SessionIDs)
+	sessions, err = session.Load(ctx, "fetch")
+	require.NoError(t, err)
+	assert.Nil(t, sessions)
+
+	renamedHistory, err := upgrade.LoadHistory(ctx, "web-fetch")
+	require.NoError(t, err)
+	require.Len(t, renamedHistory.Transitions, 1)
+	assert.Equal(t, "mcp/fetch:v2", renamedHistory.Transitions[0].Image)
+
+	// The renamed workload runs in a new container, which is not paused
+	assert.False(t, proxy.IsPaused(ctx, "fetch"))
+	assert.False(t, proxy.IsPaused(ctx, "web-fetch"))
+}